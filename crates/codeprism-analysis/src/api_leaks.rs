@@ -0,0 +1,252 @@
+//! Detection of public APIs that expose internal types
+//!
+//! A public function whose parameters or return type name a private struct or
+//! class forces callers to depend on something they cannot name. Types are
+//! classified with the [`ApiSurfaceAnalyzer`] public API patterns, and
+//! signature types come from the same parameter parsing the data clump
+//! analysis uses.
+
+use crate::api_surface::ApiSurfaceAnalyzer;
+use crate::data_clumps::{parameters, split_signature};
+use anyhow::Result;
+use codeprism_core::Language;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Where a leaked type appears in a signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeakPosition {
+    Parameter(String),
+    Return,
+}
+
+/// An internal type referenced from a public signature
+#[derive(Debug, Clone)]
+pub struct LeakedType {
+    pub type_name: String,
+    pub position: LeakPosition,
+}
+
+/// A public function or method whose signature exposes internal types
+#[derive(Debug, Clone)]
+pub struct ApiLeak {
+    pub symbol: String,
+    pub element_type: String,
+    pub file_path: String,
+    pub signature: String,
+    pub leaked_types: Vec<LeakedType>,
+}
+
+/// Result of checking public signatures for internal types
+#[derive(Debug, Clone, Default)]
+pub struct ApiLeakReport {
+    pub leaks: Vec<ApiLeak>,
+    pub public_symbols_checked: usize,
+    pub internal_types: BTreeSet<String>,
+}
+
+/// Analyzer flagging public signatures that reference internal types
+pub struct ApiLeakAnalyzer {
+    api_surface: ApiSurfaceAnalyzer,
+}
+
+impl ApiLeakAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            api_surface: ApiSurfaceAnalyzer::new(),
+        }
+    }
+
+    /// Check every public function and method in a file path -> content map
+    ///
+    /// A type is internal when no file declares it public, or when its name
+    /// marks it as internal (`_Foo`, `InternalFoo`). Internal symbols are not
+    /// checked themselves.
+    pub fn analyze(&self, sources: &BTreeMap<String, String>) -> Result<ApiLeakReport> {
+        let public_api = ["public_api".to_string()];
+
+        let mut public_types = HashSet::new();
+        let mut declared_types = BTreeSet::new();
+        let mut functions = Vec::new();
+        for (path, content) in sources {
+            for element in self
+                .api_surface
+                .analyze_api_surface(content, &public_api, true)?
+            {
+                match element.element_type.as_str() {
+                    "class" => {
+                        if element.visibility == "public"
+                            && self.api_surface.is_public_api_element(&element.name)
+                        {
+                            public_types.insert(element.name.clone());
+                        }
+                        declared_types.insert(element.name);
+                    }
+                    "function" | "method"
+                        if element.visibility == "public"
+                            && self.api_surface.is_public_api_element(&element.name) =>
+                    {
+                        functions.push((path, content, element));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut report = ApiLeakReport {
+            internal_types: declared_types
+                .into_iter()
+                .filter(|name| !public_types.contains(name))
+                .collect(),
+            ..Default::default()
+        };
+
+        let mut seen = HashSet::new();
+        for (path, content, element) in functions {
+            let matched = element.signature.unwrap_or_default();
+            // Top-level functions match both the function and the method pattern
+            if !seen.insert((
+                path.clone(),
+                element.name.clone(),
+                matched.trim().to_string(),
+            )) {
+                continue;
+            }
+            report.public_symbols_checked += 1;
+
+            let header = content
+                .find(&matched)
+                .map(|start| signature_header(&content[start..]))
+                .unwrap_or(&matched);
+            let leaked_types = self.leaked_types(header, &report.internal_types);
+            if !leaked_types.is_empty() {
+                report.leaks.push(ApiLeak {
+                    symbol: element.name,
+                    element_type: element.element_type,
+                    file_path: path.clone(),
+                    signature: header.split_whitespace().collect::<Vec<_>>().join(" "),
+                    leaked_types,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Internal types named by the parameters and return type of a signature
+    fn leaked_types(&self, header: &str, internal_types: &BTreeSet<String>) -> Vec<LeakedType> {
+        let mut leaked = Vec::new();
+        for param in parameters(header, Language::Rust) {
+            if let Some(type_name) = &param.type_name {
+                for name in internal_type_names(type_name, internal_types) {
+                    leaked.push(LeakedType {
+                        type_name: name,
+                        position: LeakPosition::Parameter(param.name.clone()),
+                    });
+                }
+            }
+        }
+        if let Some((_, rest)) = split_signature(header) {
+            if let Some((_, return_type)) = rest.split_once("->") {
+                for name in internal_type_names(return_type, internal_types) {
+                    leaked.push(LeakedType {
+                        type_name: name,
+                        position: LeakPosition::Return,
+                    });
+                }
+            }
+        }
+        leaked
+    }
+}
+
+impl Default for ApiLeakAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Signature text up to the body, a `;` or a `where` clause
+fn signature_header(text: &str) -> &str {
+    let end = [text.find('{'), text.find(';'), text.find(" where ")]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(text.len());
+    text[..end].trim()
+}
+
+/// Identifiers in a type expression such as `Result<Vec<Row>, Error>` that are internal
+fn internal_type_names(type_expr: &str, internal_types: &BTreeSet<String>) -> Vec<String> {
+    let mut names: Vec<String> = type_expr
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| internal_types.contains(*word))
+        .map(str::to_string)
+        .collect();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(files: &[(&str, &str)]) -> BTreeMap<String, String> {
+        files
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_public_function_returning_private_struct_is_flagged() {
+        let report = ApiLeakAnalyzer::new()
+            .analyze(&sources(&[(
+                "lib.rs",
+                "struct Cursor {\n    pos: usize,\n}\n\npub struct Row {\n    id: u64,\n}\n\npub fn open_cursor(rows: &[Row]) -> Cursor {\n    Cursor { pos: rows.len() }\n}\n",
+            )]))
+            .unwrap();
+
+        assert_eq!(report.public_symbols_checked, 1);
+        assert_eq!(report.leaks.len(), 1);
+        let leak = &report.leaks[0];
+        assert_eq!(leak.symbol, "open_cursor");
+        assert_eq!(leak.signature, "pub fn open_cursor(rows: &[Row]) -> Cursor");
+        assert_eq!(leak.leaked_types.len(), 1);
+        assert_eq!(leak.leaked_types[0].type_name, "Cursor");
+        assert_eq!(leak.leaked_types[0].position, LeakPosition::Return);
+    }
+
+    #[test]
+    fn test_public_types_across_files_are_not_flagged() {
+        let report = ApiLeakAnalyzer::new()
+            .analyze(&sources(&[
+                ("model.rs", "pub struct Row {\n    id: u64,\n}\n"),
+                (
+                    "query.rs",
+                    "struct Plan;\n\npub fn fetch(id: u64) -> Option<Row> {\n    None\n}\n\nfn plan(row: Row) -> Plan {\n    Plan\n}\n",
+                ),
+            ]))
+            .unwrap();
+
+        assert_eq!(report.public_symbols_checked, 1);
+        assert!(report.leaks.is_empty(), "{:?}", report.leaks);
+        assert!(report.internal_types.contains("Plan"));
+    }
+
+    #[test]
+    fn test_internal_parameter_type_is_reported_with_parameter_name() {
+        let report = ApiLeakAnalyzer::new()
+            .analyze(&sources(&[(
+                "lib.rs",
+                "struct Settings;\n\npub fn configure(name: &str, settings: Vec<Settings>) -> bool {\n    true\n}\n",
+            )]))
+            .unwrap();
+
+        let leak = &report.leaks[0];
+        assert_eq!(leak.leaked_types[0].type_name, "Settings");
+        assert_eq!(
+            leak.leaked_types[0].position,
+            LeakPosition::Parameter("settings".to_string())
+        );
+    }
+}
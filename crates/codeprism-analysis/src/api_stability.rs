@@ -0,0 +1,372 @@
+//! Public API stability analysis across revisions
+//!
+//! Compares the public symbols of two snapshots of a code base. Symbols that
+//! disappear under one name and reappear under another with the same structure
+//! are reported as renames instead of a removal plus an addition.
+
+use crate::api_surface::ApiSurfaceAnalyzer;
+use crate::duplicates::DuplicateAnalyzer;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+
+/// A public symbol extracted from a source file
+#[derive(Debug, Clone)]
+pub struct PublicSymbol {
+    pub name: String,
+    pub element_type: String,
+    pub file_path: String,
+    pub signature: String,
+    pub structural_hash: u64,
+}
+
+/// A public symbol whose name changed between revisions
+#[derive(Debug, Clone)]
+pub struct SymbolRename {
+    pub old_name: String,
+    pub new_name: String,
+    pub element_type: String,
+    pub old_file: String,
+    pub new_file: String,
+}
+
+/// A public symbol that moved to another file under the same name
+#[derive(Debug, Clone)]
+pub struct SymbolMove {
+    pub name: String,
+    pub element_type: String,
+    pub old_file: String,
+    pub new_file: String,
+}
+
+/// Differences in the public API between two revisions
+#[derive(Debug, Clone, Default)]
+pub struct ApiStabilityReport {
+    pub renamed: Vec<SymbolRename>,
+    pub moved: Vec<SymbolMove>,
+    pub removed: Vec<PublicSymbol>,
+    pub added: Vec<PublicSymbol>,
+    pub unchanged: usize,
+}
+
+impl ApiStabilityReport {
+    /// Whether existing callers keep compiling (no renames or removals)
+    pub fn is_stable(&self) -> bool {
+        self.renamed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Analyzer comparing public symbols between revisions
+pub struct ApiStabilityAnalyzer {
+    api_surface: ApiSurfaceAnalyzer,
+    duplicates: DuplicateAnalyzer,
+}
+
+impl ApiStabilityAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            api_surface: ApiSurfaceAnalyzer::new(),
+            duplicates: DuplicateAnalyzer::new(),
+        }
+    }
+
+    /// Extract public symbols from a source file together with their structural hash
+    pub fn extract_public_symbols(
+        &self,
+        file_path: &str,
+        content: &str,
+    ) -> Result<Vec<PublicSymbol>> {
+        let elements =
+            self.api_surface
+                .analyze_api_surface(content, &["public_api".to_string()], false)?;
+
+        let mut seen = HashSet::new();
+        let mut symbols = Vec::new();
+
+        for element in elements {
+            let signature = element.signature.unwrap_or_default();
+            // Top-level functions match both the function and the method pattern
+            if !seen.insert((element.name.clone(), signature.trim().to_string())) {
+                continue;
+            }
+
+            let definition = content
+                .find(&signature)
+                .map(|start| Self::definition_text(&content[start..]))
+                .unwrap_or(&signature);
+
+            symbols.push(PublicSymbol {
+                structural_hash: self.duplicates.structural_hash(definition),
+                name: element.name,
+                element_type: element.element_type,
+                file_path: file_path.to_string(),
+                signature: signature.trim().to_string(),
+            });
+        }
+
+        Ok(symbols)
+    }
+
+    /// Take the text of a definition up to its terminating `;` or matching `}`
+    fn definition_text(text: &str) -> &str {
+        let mut depth = 0usize;
+        for (index, ch) in text.char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return &text[..=index];
+                    }
+                }
+                ';' if depth == 0 => return &text[..=index],
+                _ => {}
+            }
+        }
+        text
+    }
+
+    /// Compare the public symbols of two snapshots given as file path -> content maps
+    pub fn compare(
+        &self,
+        old_sources: &BTreeMap<String, String>,
+        new_sources: &BTreeMap<String, String>,
+    ) -> Result<ApiStabilityReport> {
+        let mut old_symbols = Vec::new();
+        for (path, content) in old_sources {
+            old_symbols.extend(self.extract_public_symbols(path, content)?);
+        }
+        let mut new_symbols = Vec::new();
+        for (path, content) in new_sources {
+            new_symbols.extend(self.extract_public_symbols(path, content)?);
+        }
+
+        let mut report = ApiStabilityReport::default();
+
+        // Same name in the same file: unchanged (the body may still differ)
+        old_symbols.retain(|old| {
+            match new_symbols.iter().position(|new| {
+                new.name == old.name
+                    && new.element_type == old.element_type
+                    && new.file_path == old.file_path
+            }) {
+                Some(index) => {
+                    new_symbols.remove(index);
+                    report.unchanged += 1;
+                    false
+                }
+                None => true,
+            }
+        });
+
+        // Same name in another file: moved
+        old_symbols.retain(|old| {
+            match new_symbols
+                .iter()
+                .position(|new| new.name == old.name && new.element_type == old.element_type)
+            {
+                Some(index) => {
+                    let new = new_symbols.remove(index);
+                    report.moved.push(SymbolMove {
+                        name: old.name.clone(),
+                        element_type: old.element_type.clone(),
+                        old_file: old.file_path.clone(),
+                        new_file: new.file_path,
+                    });
+                    false
+                }
+                None => true,
+            }
+        });
+
+        // Different name, same structure: renamed (possibly moved as well)
+        old_symbols.retain(|old| {
+            match new_symbols.iter().position(|new| {
+                new.structural_hash == old.structural_hash && new.element_type == old.element_type
+            }) {
+                Some(index) => {
+                    let new = new_symbols.remove(index);
+                    report.renamed.push(SymbolRename {
+                        old_name: old.name.clone(),
+                        new_name: new.name,
+                        element_type: old.element_type.clone(),
+                        old_file: old.file_path.clone(),
+                        new_file: new.file_path,
+                    });
+                    false
+                }
+                None => true,
+            }
+        });
+
+        report.removed = old_symbols;
+        report.added = new_symbols;
+
+        Ok(report)
+    }
+
+    /// Compare the public symbols of two git refs of the repository at `repo_path`
+    #[cfg(feature = "git")]
+    pub fn compare_refs(
+        &self,
+        repo_path: &std::path::Path,
+        old_ref: &str,
+        new_ref: &str,
+        extensions: &[String],
+    ) -> Result<ApiStabilityReport> {
+        let old_sources = load_sources_at_ref(repo_path, old_ref, extensions)?;
+        let new_sources = load_sources_at_ref(repo_path, new_ref, extensions)?;
+        self.compare(&old_sources, &new_sources)
+    }
+}
+
+impl Default for ApiStabilityAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read all files with the given extensions as they exist at a git ref
+#[cfg(feature = "git")]
+pub fn load_sources_at_ref(
+    repo_path: &std::path::Path,
+    git_ref: &str,
+    extensions: &[String],
+) -> Result<BTreeMap<String, String>> {
+    use anyhow::anyhow;
+    use std::process::Command;
+
+    let git = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .args(args)
+            .output()
+            .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    let mut sources = BTreeMap::new();
+    for path in git(&["ls-tree", "-r", "--name-only", git_ref])?.lines() {
+        let matches_extension = std::path::Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.iter().any(|e| e.trim_start_matches('.') == ext));
+        if matches_extension {
+            let content = git(&["show", &format!("{git_ref}:{path}")])?;
+            sources.insert(path.to_string(), content);
+        }
+    }
+
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(files: &[(&str, &str)]) -> BTreeMap<String, String> {
+        files
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_renamed_function_reported_as_rename() {
+        let analyzer = ApiStabilityAnalyzer::new();
+        let old = sources(&[(
+            "lib.rs",
+            "pub fn compute_total(items: &[u32]) -> u32 {\n    items.iter().sum()\n}\n\npub fn keep() {}\n",
+        )]);
+        let new = sources(&[(
+            "lib.rs",
+            "pub fn sum_items(items: &[u32]) -> u32 {\n    items.iter().sum()\n}\n\npub fn keep() {}\n",
+        )]);
+
+        let report = analyzer.compare(&old, &new).unwrap();
+        assert_eq!(report.renamed.len(), 1, "Should report one rename");
+        assert_eq!(report.renamed[0].old_name, "compute_total");
+        assert_eq!(report.renamed[0].new_name, "sum_items");
+        assert!(report.removed.is_empty(), "Rename is not a removal");
+        assert!(report.added.is_empty(), "Rename is not an addition");
+        assert_eq!(report.unchanged, 1);
+        assert!(!report.is_stable());
+    }
+
+    #[test]
+    fn test_moved_function_is_not_a_rename() {
+        let analyzer = ApiStabilityAnalyzer::new();
+        let old = sources(&[("a.rs", "pub fn shared() -> u8 {\n    1\n}\n")]);
+        let new = sources(&[("b.rs", "pub fn shared() -> u8 {\n    1\n}\n")]);
+
+        let report = analyzer.compare(&old, &new).unwrap();
+        assert_eq!(report.moved.len(), 1);
+        assert!(report.renamed.is_empty());
+        assert!(report.is_stable());
+    }
+
+    #[test]
+    fn test_true_removal_and_addition() {
+        let analyzer = ApiStabilityAnalyzer::new();
+        let old = sources(&[("lib.rs", "pub fn old_api(x: u32) -> u32 {\n    x + 1\n}\n")]);
+        let new = sources(&[("lib.rs", "pub struct Config {\n    pub verbose: bool,\n}\n")]);
+
+        let report = analyzer.compare(&old, &new).unwrap();
+        assert!(report.renamed.is_empty());
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].name, "old_api");
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.added[0].name, "Config");
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_compare_refs_detects_rename() {
+        use std::process::Command;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+
+        git(&["init", "-q"]);
+        std::fs::write(
+            dir.join("lib.rs"),
+            "pub fn parse_input(raw: &str) -> usize {\n    raw.trim().len()\n}\n",
+        )
+        .unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "initial"]);
+        git(&["tag", "v1"]);
+
+        std::fs::write(
+            dir.join("lib.rs"),
+            "pub fn input_len(raw: &str) -> usize {\n    raw.trim().len()\n}\n",
+        )
+        .unwrap();
+        git(&["commit", "-q", "-am", "rename"]);
+
+        let report = ApiStabilityAnalyzer::new()
+            .compare_refs(dir, "v1", "HEAD", &["rs".to_string()])
+            .unwrap();
+        assert_eq!(report.renamed.len(), 1);
+        assert_eq!(report.renamed[0].old_name, "parse_input");
+        assert_eq!(report.renamed[0].new_name, "input_len");
+        assert!(report.removed.is_empty() && report.added.is_empty());
+    }
+}
@@ -1,9 +1,11 @@
 //! API surface analysis module
 
 use anyhow::Result;
+use codeprism_core::{Node, NodeKind, Span};
 use regex::Regex;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
 /// API element information
 #[derive(Debug, Clone)]
@@ -17,6 +19,45 @@ pub struct ApiElement {
     pub breaking_change_risk: String,
 }
 
+/// A public symbol of an indexed graph
+#[derive(Debug, Clone)]
+pub struct ApiSymbol {
+    pub name: String,
+    pub kind: NodeKind,
+    pub file: PathBuf,
+    pub signature: Option<String>,
+    pub span: Span,
+}
+
+/// The public symbols of one indexed state of a code base
+#[derive(Debug, Clone, Default)]
+pub struct ApiSurface {
+    pub symbols: Vec<ApiSymbol>,
+}
+
+/// A public symbol whose signature differs between two surfaces
+#[derive(Debug, Clone)]
+pub struct SignatureChange {
+    pub old: ApiSymbol,
+    pub new: ApiSymbol,
+}
+
+/// Changes between two API surfaces
+#[derive(Debug, Clone, Default)]
+pub struct ApiSurfaceDiff {
+    pub added: Vec<ApiSymbol>,
+    pub removed: Vec<ApiSymbol>,
+    pub signature_changed: Vec<SignatureChange>,
+    pub unchanged: usize,
+}
+
+impl ApiSurfaceDiff {
+    /// Whether callers of the old surface may break (removals or signature changes)
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.signature_changed.is_empty()
+    }
+}
+
 /// API surface analyzer
 pub struct ApiSurfaceAnalyzer {
     patterns: HashMap<String, Vec<ApiPattern>>,
@@ -281,6 +322,94 @@ impl ApiSurfaceAnalyzer {
         !name.starts_with('_') && !name.starts_with("internal") && !name.starts_with("private")
     }
 
+    /// Collect the public functions, methods and types among `nodes`
+    ///
+    /// Visibility recorded by the language parser (`visibility` or `exported`
+    /// metadata) decides; without it, [`Self::is_public_api_element`] does.
+    /// Python dunder methods count as public.
+    pub fn surface<'a>(&self, nodes: impl IntoIterator<Item = &'a Node>) -> ApiSurface {
+        let symbols = nodes
+            .into_iter()
+            .filter(|node| {
+                matches!(
+                    node.kind,
+                    NodeKind::Function
+                        | NodeKind::Method
+                        | NodeKind::Class
+                        | NodeKind::Interface
+                        | NodeKind::TypeAlias
+                        | NodeKind::Enum
+                )
+            })
+            .filter(|node| {
+                if let Some(visibility) = node.metadata["visibility"].as_str() {
+                    visibility == "public"
+                } else if let Some(exported) = node.metadata["exported"].as_bool() {
+                    exported
+                } else {
+                    self.is_public_api_element(&node.name)
+                        || (node.name.starts_with("__") && node.name.ends_with("__"))
+                }
+            })
+            .map(|node| ApiSymbol {
+                name: node.name.clone(),
+                kind: node.kind,
+                file: node.file.clone(),
+                signature: node.signature.clone(),
+                span: node.span.clone(),
+            })
+            .collect();
+
+        ApiSurface { symbols }
+    }
+
+    /// Compare two surfaces, matching symbols by file, kind and name
+    ///
+    /// Symbols sharing a file, kind and name (such as same-named methods of
+    /// different classes) are paired in source order.
+    pub fn diff(&self, old: &ApiSurface, new: &ApiSurface) -> ApiSurfaceDiff {
+        type Key = (PathBuf, String, String);
+        let group = |surface: &ApiSurface| {
+            let mut groups: BTreeMap<Key, Vec<ApiSymbol>> = BTreeMap::new();
+            for symbol in &surface.symbols {
+                groups
+                    .entry((
+                        symbol.file.clone(),
+                        format!("{:?}", symbol.kind),
+                        symbol.name.clone(),
+                    ))
+                    .or_default()
+                    .push(symbol.clone());
+            }
+            for symbols in groups.values_mut() {
+                symbols.sort_by_key(|symbol| symbol.span.start_byte);
+            }
+            groups
+        };
+
+        let mut diff = ApiSurfaceDiff::default();
+        let mut new_groups = group(new);
+        for (key, old_symbols) in group(old) {
+            let mut new_symbols = new_groups.remove(&key).unwrap_or_default().into_iter();
+            for old_symbol in old_symbols {
+                match new_symbols.next() {
+                    Some(new_symbol) if new_symbol.signature != old_symbol.signature => {
+                        diff.signature_changed.push(SignatureChange {
+                            old: old_symbol,
+                            new: new_symbol,
+                        });
+                    }
+                    Some(_) => diff.unchanged += 1,
+                    None => diff.removed.push(old_symbol),
+                }
+            }
+            diff.added.extend(new_symbols);
+        }
+        diff.added.extend(new_groups.into_values().flatten());
+
+        diff
+    }
+
     /// Get API recommendations
     pub fn get_api_recommendations(&self, elements: &[ApiElement]) -> Vec<String> {
         let mut recommendations = Vec::new();
@@ -516,6 +645,73 @@ mod tests {
         assert!(!analyzer.is_public_api_element("internal_function"));
     }
 
+    fn node(name: &str, kind: NodeKind, signature: &str, start: usize) -> Node {
+        let mut node = Node::new(
+            "repo",
+            kind,
+            name.to_string(),
+            codeprism_core::Language::Python,
+            PathBuf::from("billing.py"),
+            Span::new(start, start + 10, 1, 2, 1, 1),
+        );
+        node.signature = Some(signature.to_string());
+        node
+    }
+
+    #[test]
+    fn test_surface_keeps_public_symbols() {
+        let analyzer = ApiSurfaceAnalyzer::new();
+        let mut exported = node("render", NodeKind::Function, "render()", 40);
+        exported.metadata = serde_json::json!({ "exported": false });
+        let nodes = vec![
+            node("total", NodeKind::Function, "total(items)", 0),
+            node("_round", NodeKind::Function, "_round(value)", 10),
+            node("__init__", NodeKind::Method, "__init__(self)", 20),
+            node("items", NodeKind::Variable, "items", 30),
+            exported,
+        ];
+
+        let names: Vec<String> = analyzer
+            .surface(&nodes)
+            .symbols
+            .into_iter()
+            .map(|symbol| symbol.name)
+            .collect();
+        assert_eq!(names, vec!["total", "__init__"]);
+    }
+
+    #[test]
+    fn test_diff_classifies_added_removed_and_changed_symbols() {
+        let analyzer = ApiSurfaceAnalyzer::new();
+        let old = analyzer.surface(&[
+            node("total", NodeKind::Function, "total(items)", 0),
+            node("refund", NodeKind::Function, "refund(order)", 10),
+            node("Invoice", NodeKind::Class, "class Invoice", 20),
+        ]);
+        let new = analyzer.surface(&[
+            node("total", NodeKind::Function, "total(items, currency)", 0),
+            node("Invoice", NodeKind::Class, "class Invoice", 30),
+            node("void", NodeKind::Function, "void(order)", 40),
+        ]);
+
+        let diff = analyzer.diff(&old, &new);
+        assert!(diff.is_breaking());
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "refund");
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "void");
+        assert_eq!(diff.signature_changed.len(), 1);
+        let change = &diff.signature_changed[0];
+        assert_eq!(change.old.signature.as_deref(), Some("total(items)"));
+        assert_eq!(
+            change.new.signature.as_deref(),
+            Some("total(items, currency)")
+        );
+
+        assert!(!analyzer.diff(&old, &old).is_breaking());
+    }
+
     #[test]
     fn test_api_recommendations() {
         let analyzer = ApiSurfaceAnalyzer::new();
@@ -0,0 +1,207 @@
+//! Change frequency analysis from git history
+//!
+//! Counts how often each file changed and ranks the files that change often
+//! while being poorly covered by tests. Such files are the likeliest places
+//! for regressions to slip through.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "coverage")]
+use crate::coverage::CoverageOverlay;
+
+/// A file ranked by change frequency weighted by missing coverage
+#[derive(Debug, Clone)]
+pub struct ChurnHotspot {
+    pub path: String,
+    pub changes: usize,
+    /// Line coverage ratio, `None` when the coverage report has no entry
+    pub coverage: Option<f64>,
+    /// `changes * (1 - coverage)`, files missing from the report count as uncovered
+    pub score: f64,
+}
+
+/// Analyzer for file churn in git history
+pub struct ChurnAnalyzer;
+
+impl ChurnAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Count changes per path in `git log --format= --name-only` output
+    pub fn count_changes(&self, log_output: &str) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for path in log_output.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            *counts.entry(path.to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Count commits touching each file of the repository at `repo_path`
+    ///
+    /// `since` is passed to `git log --since` (e.g. "6 months ago").
+    #[cfg(feature = "git")]
+    pub fn change_counts(
+        &self,
+        repo_path: &std::path::Path,
+        since: Option<&str>,
+    ) -> anyhow::Result<BTreeMap<String, usize>> {
+        use anyhow::anyhow;
+        use std::process::Command;
+
+        let mut command = Command::new("git");
+        command
+            .arg("-C")
+            .arg(repo_path)
+            .args(["log", "--format=", "--name-only", "--no-renames"]);
+        if let Some(since) = since {
+            command.arg(format!("--since={since}"));
+        }
+
+        let output = command
+            .output()
+            .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git log failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(self.count_changes(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Rank files by change count weighted by inverse coverage
+    ///
+    /// Files with a zero score are dropped. Ties are broken by change count,
+    /// then by path.
+    #[cfg(feature = "coverage")]
+    pub fn rank(
+        &self,
+        changes: &BTreeMap<String, usize>,
+        coverage: &CoverageOverlay,
+        limit: usize,
+    ) -> Vec<ChurnHotspot> {
+        let mut hotspots: Vec<ChurnHotspot> = changes
+            .iter()
+            .map(|(path, &count)| {
+                let ratio = coverage.coverage_for(path);
+                ChurnHotspot {
+                    path: path.clone(),
+                    changes: count,
+                    coverage: ratio,
+                    score: count as f64 * (1.0 - ratio.unwrap_or(0.0)),
+                }
+            })
+            .filter(|hotspot| hotspot.score > 0.0)
+            .collect();
+
+        hotspots.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(b.changes.cmp(&a.changes))
+                .then(a.path.cmp(&b.path))
+        });
+        hotspots.truncate(limit);
+        hotspots
+    }
+
+    /// Most-churned, least-covered files that still exist in the working tree
+    #[cfg(all(feature = "git", feature = "coverage"))]
+    pub fn churned_untested(
+        &self,
+        repo_path: &std::path::Path,
+        coverage: &CoverageOverlay,
+        since: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<ChurnHotspot>> {
+        let mut changes = self.change_counts(repo_path, since)?;
+        changes.retain(|path, _| repo_path.join(path).is_file());
+        Ok(self.rank(&changes, coverage, limit))
+    }
+}
+
+impl Default for ChurnAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_changes_from_log_output() {
+        let log = "src/a.rs\nsrc/b.rs\n\nsrc/a.rs\n\nsrc/a.rs\nREADME.md\n";
+        let counts = ChurnAnalyzer::new().count_changes(log);
+        assert_eq!(counts["src/a.rs"], 3);
+        assert_eq!(counts["src/b.rs"], 1);
+        assert_eq!(counts["README.md"], 1);
+    }
+
+    #[cfg(all(feature = "git", feature = "coverage"))]
+    #[test]
+    fn test_churned_low_coverage_file_ranks_above_stable_tested_file() {
+        use std::process::Command;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+
+        git(&["init", "-q"]);
+        std::fs::write(dir.join("stable.rs"), "pub fn stable() {}\n").unwrap();
+        std::fs::write(dir.join("hot.rs"), "pub fn hot() -> u32 { 0 }\n").unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "initial"]);
+        for i in 1..=4 {
+            std::fs::write(
+                dir.join("hot.rs"),
+                format!("pub fn hot() -> u32 {{ {i} }}\n"),
+            )
+            .unwrap();
+            git(&["commit", "-q", "-am", &format!("change {i}")]);
+        }
+
+        let lcov = format!(
+            "SF:{root}/stable.rs\nDA:1,5\nend_of_record\nSF:{root}/hot.rs\nDA:1,0\nDA:2,1\nDA:3,0\nDA:4,0\nend_of_record\n",
+            root = dir.display()
+        );
+        let coverage = CoverageOverlay::from_lcov(&lcov, Some(dir));
+
+        let hotspots = ChurnAnalyzer::new()
+            .churned_untested(dir, &coverage, None, 10)
+            .unwrap();
+        assert_eq!(
+            hotspots.len(),
+            1,
+            "Fully covered file has nothing to report"
+        );
+        assert_eq!(hotspots[0].path, "hot.rs");
+        assert_eq!(hotspots[0].changes, 5);
+        assert_eq!(hotspots[0].coverage, Some(0.25));
+        assert!((hotspots[0].score - 3.75).abs() < f64::EPSILON);
+
+        let partial = CoverageOverlay::from_lcov(
+            &format!(
+                "SF:{}/stable.rs\nDA:1,1\nDA:2,0\nend_of_record\n",
+                dir.display()
+            ),
+            Some(dir),
+        );
+        let hotspots = ChurnAnalyzer::new()
+            .churned_untested(dir, &partial, None, 10)
+            .unwrap();
+        let order: Vec<&str> = hotspots.iter().map(|h| h.path.as_str()).collect();
+        assert_eq!(order, vec!["hot.rs", "stable.rs"]);
+    }
+}
@@ -0,0 +1,586 @@
+//! Commented-out code detection module
+
+use anyhow::Result;
+use codeprism_core::{Language, LanguageRegistry, ParseContext};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A comment block that appears to contain disabled source code
+#[derive(Debug, Clone)]
+pub struct CommentedCodeBlock {
+    pub file_path: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub code_lines: usize,
+    pub total_lines: usize,
+    pub confidence: f32,
+    pub preview: String,
+}
+
+/// Comment syntax for a language family
+#[derive(Debug, Clone)]
+struct CommentSyntax {
+    single_line: &'static str,
+    doc_prefixes: Vec<&'static str>,
+    block: Option<(&'static str, &'static str)>,
+}
+
+/// Analyzer that finds comment blocks whose content tokenizes like code
+pub struct CommentedCodeAnalyzer {
+    syntaxes: HashMap<String, CommentSyntax>,
+    code_patterns: HashMap<String, Vec<Regex>>,
+    /// Parsers confirming that a candidate block is code
+    registry: Option<Arc<LanguageRegistry>>,
+}
+
+impl CommentedCodeAnalyzer {
+    pub fn new() -> Self {
+        let mut analyzer = Self {
+            syntaxes: HashMap::new(),
+            code_patterns: HashMap::new(),
+            registry: None,
+        };
+        analyzer.initialize_patterns();
+        analyzer
+    }
+
+    /// Confirm candidate blocks with the parsers registered in `registry`
+    ///
+    /// Without a registry, or for a language it has no parser for, blocks are
+    /// judged by the line heuristic alone.
+    pub fn with_language_registry(mut self, registry: Arc<LanguageRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    fn initialize_patterns(&mut self) {
+        let c_like = CommentSyntax {
+            single_line: "//",
+            doc_prefixes: vec!["///", "//!", "/**", "/*!"],
+            block: Some(("/*", "*/")),
+        };
+        let hash = CommentSyntax {
+            single_line: "#",
+            doc_prefixes: vec!["#!"],
+            block: None,
+        };
+
+        for language in [
+            "rust",
+            "javascript",
+            "typescript",
+            "java",
+            "kotlin",
+            "php",
+            "c",
+            "cpp",
+            "go",
+        ] {
+            self.syntaxes.insert(language.to_string(), c_like.clone());
+        }
+        for language in ["python", "ruby", "shell"] {
+            self.syntaxes.insert(language.to_string(), hash.clone());
+        }
+
+        // Patterns shared by every language: statement terminators, lone braces,
+        // assignments and bare calls
+        let common = vec![
+            Regex::new(r"[;{]\s*$").unwrap(),
+            Regex::new(r"^[}\])]+[;,)]*\s*$").unwrap(),
+            Regex::new(r"^[A-Za-z_][\w.\[\]]*\s*(=|\+=|-=|\*=|/=)\s*[^=\s]").unwrap(),
+            Regex::new(r"^[A-Za-z_][\w.:]*\([^()]*(\([^()]*\))*[^()]*\)\s*;?$").unwrap(),
+            Regex::new(r"^return\b").unwrap(),
+        ];
+
+        let rust = vec![
+            Regex::new(r"^(pub\s+)?(fn|struct|enum|impl|trait|mod|use)\s+\w").unwrap(),
+            Regex::new(r"^let\s+(mut\s+)?\w+").unwrap(),
+            Regex::new(r"^(if|while|for|match|loop)\b.*\{\s*$").unwrap(),
+        ];
+        let javascript = vec![
+            Regex::new(r"^(const|let|var)\s+\w+\s*=").unwrap(),
+            Regex::new(r"^(export\s+)?(async\s+)?function\s*\w*\s*\(").unwrap(),
+            Regex::new(r"^import\s+.+\s+from\s+").unwrap(),
+            Regex::new(r"^(if|while|for|switch)\s*\(.*\)\s*\{?\s*$").unwrap(),
+        ];
+        let java = vec![
+            Regex::new(r"^(public|private|protected|static|final)\s+[\w<>\[\]]+\s+\w+").unwrap(),
+            Regex::new(r"^import\s+[\w.]+(\.\*)?;").unwrap(),
+            Regex::new(r"^(if|while|for|switch)\s*\(.*\)\s*\{?\s*$").unwrap(),
+        ];
+        let python = vec![
+            Regex::new(r"^(async\s+)?def\s+\w+\s*\(.*\)\s*(->\s*[\w\[\], .]+)?:\s*$").unwrap(),
+            Regex::new(r"^class\s+\w+(\(.*\))?:\s*$").unwrap(),
+            Regex::new(r"^(if|elif|while|for|with)\s+.+:\s*$").unwrap(),
+            Regex::new(r"^(else|try|finally):\s*$").unwrap(),
+            Regex::new(r"^except(\s+[\w.]+(\s+as\s+\w+)?)?:\s*$").unwrap(),
+            Regex::new(r"^(from\s+[\w.]+\s+)?import\s+[\w., ]+$").unwrap(),
+            Regex::new(r"^(pass|break|continue)\s*$").unwrap(),
+        ];
+
+        self.code_patterns.insert("common".to_string(), common);
+        self.code_patterns.insert("rust".to_string(), rust);
+        self.code_patterns
+            .insert("javascript".to_string(), javascript.clone());
+        self.code_patterns
+            .insert("typescript".to_string(), javascript);
+        self.code_patterns.insert("java".to_string(), java.clone());
+        self.code_patterns
+            .insert("kotlin".to_string(), java.clone());
+        self.code_patterns.insert("php".to_string(), java.clone());
+        self.code_patterns.insert("c".to_string(), java.clone());
+        self.code_patterns.insert("cpp".to_string(), java);
+        self.code_patterns.insert("python".to_string(), python);
+    }
+
+    /// Map a file extension to the language key used by this analyzer
+    pub fn language_from_path(path: &Path) -> &'static str {
+        match path.extension().and_then(|ext| ext.to_str()).unwrap_or("") {
+            "rs" => "rust",
+            "js" | "jsx" | "mjs" | "cjs" => "javascript",
+            "ts" | "tsx" => "typescript",
+            "java" => "java",
+            "kt" | "kts" => "kotlin",
+            "php" => "php",
+            "c" | "h" => "c",
+            "cpp" | "cc" | "cxx" | "hpp" => "cpp",
+            "go" => "go",
+            "py" | "pyi" => "python",
+            "rb" => "ruby",
+            "sh" | "bash" => "shell",
+            _ => "unknown",
+        }
+    }
+
+    /// Analyze a file on disk for commented-out code
+    pub fn analyze_file(
+        &self,
+        path: &Path,
+        min_code_lines: usize,
+    ) -> Result<Vec<CommentedCodeBlock>> {
+        let content = std::fs::read_to_string(path)?;
+        let language = Self::language_from_path(path);
+        self.analyze_content(
+            &content,
+            language,
+            Some(&path.display().to_string()),
+            min_code_lines,
+        )
+    }
+
+    /// Find comment blocks that contain at least `min_code_lines` code-like lines
+    ///
+    /// Blocks where code-like lines are outnumbered by prose lines are ignored so that
+    /// explanatory comments quoting a single expression are not reported. For
+    /// languages with a registered parser, a block is only reported when it also
+    /// parses without errors.
+    pub fn analyze_content(
+        &self,
+        content: &str,
+        language: &str,
+        file_path: Option<&str>,
+        min_code_lines: usize,
+    ) -> Result<Vec<CommentedCodeBlock>> {
+        let Some(syntax) = self.syntaxes.get(language) else {
+            return Ok(Vec::new());
+        };
+        let min_code_lines = min_code_lines.max(2);
+
+        let mut blocks = Vec::new();
+        for comment in self.extract_comment_blocks(content, syntax) {
+            let non_blank: Vec<&(usize, String)> = comment
+                .iter()
+                .filter(|(_, text)| !text.trim().is_empty())
+                .collect();
+            if non_blank.len() < min_code_lines {
+                continue;
+            }
+
+            let code_lines = non_blank
+                .iter()
+                .filter(|(_, text)| self.is_code_like(text.trim(), language))
+                .count();
+            let ratio = code_lines as f32 / non_blank.len() as f32;
+
+            // Blocks the heuristic accepts must also parse, where a parser exists
+            if code_lines >= min_code_lines
+                && ratio >= 0.5
+                && self.parses_as_code(&comment, language).unwrap_or(true)
+            {
+                let start_line = comment.first().map(|(line, _)| *line).unwrap_or(0);
+                let end_line = comment.last().map(|(line, _)| *line).unwrap_or(start_line);
+                let preview = non_blank
+                    .iter()
+                    .take(3)
+                    .map(|(_, text)| text.trim())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                blocks.push(CommentedCodeBlock {
+                    file_path: file_path.map(|p| p.to_string()),
+                    start_line,
+                    end_line,
+                    code_lines,
+                    total_lines: non_blank.len(),
+                    confidence: ratio,
+                    preview,
+                });
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Group comments into blocks of (1-based line number, comment text)
+    fn extract_comment_blocks(
+        &self,
+        content: &str,
+        syntax: &CommentSyntax,
+    ) -> Vec<Vec<(usize, String)>> {
+        let mut blocks = Vec::new();
+        let mut current: Vec<(usize, String)> = Vec::new();
+        let mut in_block_comment = false;
+
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let trimmed = line.trim();
+
+            if in_block_comment {
+                let (text, closed) = match syntax.block {
+                    Some((_, end)) if trimmed.contains(end) => {
+                        (trimmed.split(end).next().unwrap_or(""), true)
+                    }
+                    _ => (trimmed, false),
+                };
+                current.push((line_number, text.trim_start_matches('*').to_string()));
+                if closed {
+                    in_block_comment = false;
+                    blocks.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+
+            let is_doc = syntax
+                .doc_prefixes
+                .iter()
+                .any(|prefix| trimmed.starts_with(prefix));
+
+            if let Some((start, end)) = syntax.block {
+                if trimmed.starts_with(start) && !is_doc {
+                    if !current.is_empty() {
+                        blocks.push(std::mem::take(&mut current));
+                    }
+                    let body = &trimmed[start.len()..];
+                    if let Some(inner) = body
+                        .strip_suffix(end)
+                        .or_else(|| body.find(end).map(|pos| &body[..pos]))
+                    {
+                        blocks.push(vec![(line_number, inner.to_string())]);
+                    } else {
+                        current.push((line_number, body.to_string()));
+                        in_block_comment = true;
+                    }
+                    continue;
+                }
+            }
+
+            if trimmed.starts_with(syntax.single_line) && !is_doc {
+                let text = &trimmed[syntax.single_line.len()..];
+                current.push((line_number, text.to_string()));
+            } else if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        }
+
+        if !current.is_empty() {
+            blocks.push(current);
+        }
+
+        blocks
+    }
+
+    /// Check whether a single comment line tokenizes like code in the given language
+    fn is_code_like(&self, text: &str, language: &str) -> bool {
+        if text.is_empty() {
+            return false;
+        }
+
+        // Prose sentences end with a period and read as words separated by spaces
+        let word_count = text.split_whitespace().count();
+        if word_count >= 4 && text.ends_with('.') && !text.contains('(') {
+            return false;
+        }
+
+        let matches_any = |key: &str| {
+            self.code_patterns
+                .get(key)
+                .is_some_and(|patterns| patterns.iter().any(|p| p.is_match(text)))
+        };
+
+        matches_any("common") || matches_any(language)
+    }
+
+    /// Registry language and file name used to parse a block of `language`
+    fn parse_target(language: &str) -> Option<(Language, &'static str)> {
+        let target = match language {
+            "rust" => (Language::Rust, "commented.rs"),
+            "javascript" => (Language::JavaScript, "commented.js"),
+            "typescript" => (Language::TypeScript, "commented.ts"),
+            "java" => (Language::Java, "Commented.java"),
+            "kotlin" => (Language::Kotlin, "Commented.kt"),
+            "php" => (Language::Php, "commented.php"),
+            "c" => (Language::C, "commented.c"),
+            "cpp" => (Language::Cpp, "commented.cpp"),
+            "go" => (Language::Go, "commented.go"),
+            "python" => (Language::Python, "commented.py"),
+            "ruby" => (Language::Ruby, "commented.rb"),
+            _ => return None,
+        };
+        Some(target)
+    }
+
+    /// Prefix and suffix placed around a block whose lines are only valid
+    /// inside a class or function body
+    fn wrappers(language: &str) -> &'static [(&'static str, &'static str)] {
+        match language {
+            "rust" => &[("fn __commented() {\n", "\n}")],
+            "java" => &[
+                ("class __Commented {\n", "\n}"),
+                ("class __Commented { void __commented() {\n", "\n} }"),
+            ],
+            "kotlin" => &[
+                ("class __Commented {\n", "\n}"),
+                ("fun __commented() {\n", "\n}"),
+            ],
+            // Outside `<?php` everything is inline HTML and always parses
+            "php" => &[
+                ("<?php\n", ""),
+                ("<?php\nclass __Commented {\n", "\n}"),
+                ("<?php\nfunction __commented() {\n", "\n}"),
+            ],
+            "c" | "cpp" => &[("void __commented() {\n", "\n}")],
+            "go" => &[
+                ("package commented\n", ""),
+                ("package commented\nfunc __commented() {\n", "\n}"),
+            ],
+            _ => &[],
+        }
+    }
+
+    /// Whether a comment block parses without errors in `language`
+    ///
+    /// Returns `None` when no parser is registered for the language, which
+    /// then relies on the line heuristic alone.
+    fn parses_as_code(&self, lines: &[(usize, String)], language: &str) -> Option<bool> {
+        let (registry_language, file_name) = Self::parse_target(language)?;
+        let parser = self.registry.as_ref()?.get(registry_language)?;
+
+        // Strip the indentation every line shares, so indentation-sensitive
+        // grammars see the block at the top level
+        let indent = lines
+            .iter()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .map(|(_, text)| text.len() - text.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let source = lines
+            .iter()
+            .map(|(_, text)| text.get(indent..).unwrap_or("").trim_end())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let parses = |source: String| {
+            let context =
+                ParseContext::new("commented".to_string(), PathBuf::from(file_name), source);
+            parser
+                .parse(&context)
+                .is_ok_and(|result| !result.tree.root_node().has_error())
+        };
+        let bare: &[(&str, &str)] = if language == "php" { &[] } else { &[("", "")] };
+        Some(
+            bare.iter()
+                .chain(Self::wrappers(language))
+                .any(|(prefix, suffix)| parses(format!("{prefix}{source}{suffix}"))),
+        )
+    }
+
+    /// Get recommendations for the detected blocks
+    pub fn get_recommendations(&self, blocks: &[CommentedCodeBlock]) -> Vec<String> {
+        let mut recommendations = Vec::new();
+
+        if blocks.is_empty() {
+            return recommendations;
+        }
+
+        let total_lines: usize = blocks.iter().map(|b| b.total_lines).sum();
+        recommendations.push(format!(
+            "Found {} commented-out code block(s) spanning {} lines; delete them or restore the code",
+            blocks.len(),
+            total_lines
+        ));
+        recommendations.push(
+            "Version control keeps the history of removed code, so disabled code does not need to stay in comments"
+                .to_string(),
+        );
+
+        if blocks.iter().any(|b| b.total_lines >= 10) {
+            recommendations.push(
+                "Large disabled blocks often hide unfinished features; track them in an issue instead"
+                    .to_string(),
+            );
+        }
+
+        recommendations
+    }
+}
+
+impl Default for CommentedCodeAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_commented_out_rust_function() {
+        let analyzer = CommentedCodeAnalyzer::new();
+        let code = r#"
+fn active() {}
+
+// fn old_handler(input: &str) -> usize {
+//     let trimmed = input.trim();
+//     trimmed.len()
+// }
+
+fn other() {}
+"#;
+        let blocks = analyzer.analyze_content(code, "rust", None, 2).unwrap();
+        assert_eq!(blocks.len(), 1, "Should flag exactly one block");
+        assert_eq!(blocks[0].start_line, 4);
+        assert_eq!(blocks[0].end_line, 7);
+        assert!(blocks[0].code_lines >= 3);
+    }
+
+    #[test]
+    fn test_detects_commented_out_python_function() {
+        let analyzer = CommentedCodeAnalyzer::new();
+        let code = r#"
+# def legacy_total(items):
+#     total = 0
+#     for item in items:
+#         total += item.price
+#     return total
+"#;
+        let blocks = analyzer.analyze_content(code, "python", None, 2).unwrap();
+        assert_eq!(blocks.len(), 1, "Should flag the commented-out function");
+        assert_eq!(blocks[0].total_lines, 5);
+    }
+
+    #[test]
+    fn test_detects_block_comment_code() {
+        let analyzer = CommentedCodeAnalyzer::new();
+        let code = r#"
+/*
+function oldRender(props) {
+    const value = props.value;
+    return value;
+}
+*/
+"#;
+        let blocks = analyzer
+            .analyze_content(code, "javascript", None, 2)
+            .unwrap();
+        assert_eq!(blocks.len(), 1, "Should flag the block comment");
+    }
+
+    #[test]
+    fn test_prose_comments_not_flagged() {
+        let analyzer = CommentedCodeAnalyzer::new();
+        let code = r#"
+// This function walks the graph breadth-first and stops at the
+// configured depth. Callers should make sure the start node exists,
+// otherwise the result is empty.
+fn walk() {}
+
+# Compute totals for the report. The values are cached between runs
+# so repeated calls are cheap.
+"#;
+        assert!(analyzer
+            .analyze_content(code, "rust", None, 2)
+            .unwrap()
+            .is_empty());
+        assert!(analyzer
+            .analyze_content(code, "python", None, 2)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_single_code_line_not_flagged() {
+        let analyzer = CommentedCodeAnalyzer::new();
+        let code = r#"
+// Previously we used:
+// let x = compute();
+// but that was too slow for large inputs, so now we stream results
+"#;
+        let blocks = analyzer.analyze_content(code, "rust", None, 2).unwrap();
+        assert!(blocks.is_empty(), "One code-like line should not be enough");
+    }
+
+    #[test]
+    fn test_doc_comments_ignored() {
+        let analyzer = CommentedCodeAnalyzer::new();
+        let code = r#"
+/// let value = parse(input);
+/// assert_eq!(value, 1);
+fn parse() {}
+"#;
+        let blocks = analyzer.analyze_content(code, "rust", None, 2).unwrap();
+        assert!(blocks.is_empty(), "Doc examples are not commented-out code");
+    }
+
+    #[test]
+    fn test_languages_without_parser_use_heuristic() {
+        let analyzer = CommentedCodeAnalyzer::new();
+        let code = r#"
+# load config;
+# validate every input;
+# return result;
+"#;
+        assert!(CommentedCodeAnalyzer::parse_target("shell").is_none());
+        let blocks = analyzer.analyze_content(code, "shell", None, 2).unwrap();
+        assert_eq!(blocks.len(), 1, "Heuristic alone flags the block");
+
+        // Without a registry, no language is checked by parsing
+        let blocks = analyzer
+            .analyze_content(&code.replace('#', "//"), "rust", None, 2)
+            .unwrap();
+        assert_eq!(blocks.len(), 1);
+    }
+
+    #[test]
+    fn test_kotlin_and_php_comments_are_scanned() {
+        let analyzer = CommentedCodeAnalyzer::new();
+        assert_eq!(
+            CommentedCodeAnalyzer::language_from_path(Path::new("App.kt")),
+            "kotlin"
+        );
+        assert_eq!(
+            CommentedCodeAnalyzer::language_from_path(Path::new("index.php")),
+            "php"
+        );
+        let code = r#"
+// $total = count($items);
+// return $total;
+"#;
+        for language in ["kotlin", "php"] {
+            let blocks = analyzer.analyze_content(code, language, None, 2).unwrap();
+            assert_eq!(blocks.len(), 1, "{language}");
+        }
+    }
+}
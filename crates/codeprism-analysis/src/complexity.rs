@@ -1,8 +1,11 @@
 //! Code complexity analysis module
 
+use crate::infinite_loops::mask_strings_and_comments;
 use anyhow::Result;
+use codeprism_core::{Language, Node, NodeKind};
+use serde::Serialize;
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::Path;
 
 /// Complexity metrics for code analysis
@@ -17,6 +20,97 @@ pub struct ComplexityMetrics {
     pub lines_of_code: usize,
 }
 
+/// Complexity of a single function or method in a heatmap
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionHeat {
+    pub name: String,
+    pub kind: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub cyclomatic: usize,
+    pub cognitive: u32,
+}
+
+/// Complexity of one file in a heatmap, summed over its functions
+#[derive(Debug, Clone, Serialize)]
+pub struct FileHeat {
+    /// Path relative to the repository root, `/`-separated
+    pub path: String,
+    /// Directory containing the file, `.` for the repository root
+    pub directory: String,
+    pub lines_of_code: usize,
+    pub cyclomatic: usize,
+    pub cognitive: u32,
+    pub max_cyclomatic: usize,
+    pub functions: Vec<FunctionHeat>,
+}
+
+/// Complexity rolled up over every file at or below a directory
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DirectoryHeat {
+    pub path: String,
+    /// Number of path components, `0` for the repository root
+    pub depth: usize,
+    pub file_count: usize,
+    pub function_count: usize,
+    pub lines_of_code: usize,
+    pub cyclomatic: usize,
+    pub cognitive: u32,
+}
+
+/// Heatmap-ready complexity data for a set of files
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplexityHeatmap {
+    pub files: Vec<FileHeat>,
+    /// Cumulative totals for each directory, sorted by path
+    pub directories: Vec<DirectoryHeat>,
+}
+
+impl ComplexityHeatmap {
+    /// Build a heatmap, rolling each file up into all of its ancestor directories
+    pub fn from_files(mut files: Vec<FileHeat>) -> Self {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut directories: BTreeMap<String, DirectoryHeat> = BTreeMap::new();
+        for file in &files {
+            for (depth, path) in ancestor_directories(&file.directory)
+                .into_iter()
+                .enumerate()
+            {
+                let totals = directories.entry(path.clone()).or_insert(DirectoryHeat {
+                    path,
+                    depth,
+                    ..DirectoryHeat::default()
+                });
+                totals.file_count += 1;
+                totals.function_count += file.functions.len();
+                totals.lines_of_code += file.lines_of_code;
+                totals.cyclomatic += file.cyclomatic;
+                totals.cognitive += file.cognitive;
+            }
+        }
+
+        Self {
+            files,
+            directories: directories.into_values().collect(),
+        }
+    }
+}
+
+/// The root `.` followed by each prefix of a `/`-separated directory path
+fn ancestor_directories(directory: &str) -> Vec<String> {
+    let mut ancestors = vec![".".to_string()];
+    let mut current = String::new();
+    for component in directory.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        if !current.is_empty() {
+            current.push('/');
+        }
+        current.push_str(component);
+        ancestors.push(current.clone());
+    }
+    ancestors
+}
+
 /// Complexity analyzer for code analysis
 pub struct ComplexityAnalyzer;
 
@@ -128,45 +222,95 @@ impl ComplexityAnalyzer {
         complexity
     }
 
-    /// Calculate cognitive complexity (simplified)
+    /// Calculate cognitive complexity of a block of code
+    ///
+    /// Python is recognised by its block headers; everything else is scored as
+    /// a brace-delimited language. See [`Self::cognitive_complexity`].
     pub fn calculate_cognitive_complexity(&self, content: &str) -> usize {
-        let mut complexity = 0;
-        let mut nesting_level: usize = 0;
-
-        let lines = content.lines();
-        for line in lines {
-            let trimmed = line.trim();
-
-            // Increment nesting for certain constructs
-            if trimmed.contains('{')
-                || trimmed.starts_with("if ")
-                || trimmed.starts_with("for ")
-                || trimmed.starts_with("while ")
-                || trimmed.starts_with("try ")
-                || trimmed.starts_with("def ")
-                || trimmed.starts_with("function ")
-            {
-                nesting_level += 1;
-            }
-
-            // Decrement nesting
-            if trimmed.contains('}') {
-                nesting_level = nesting_level.saturating_sub(1usize);
-            }
+        let score = if looks_like_python(content) {
+            python_cognitive_complexity(content)
+        } else {
+            brace_cognitive_complexity(content, false)
+        };
+        score as usize
+    }
 
-            // Add complexity based on constructs
-            if trimmed.contains("if ") || trimmed.contains("elif ") || trimmed.contains("else if") {
-                complexity += 1 + nesting_level;
-            }
-            if trimmed.contains("while ") || trimmed.contains("for ") {
-                complexity += 1 + nesting_level;
-            }
-            if trimmed.contains("catch ") || trimmed.contains("except ") {
-                complexity += 1 + nesting_level;
-            }
+    /// Cognitive complexity of a function or method node
+    ///
+    /// Follows SonarSource's definition: every `if`, loop, `match`/`switch`,
+    /// `catch` and ternary adds one plus the current nesting level, while
+    /// `else if`/`elif`/`else` add a flat one so long chains are not punished
+    /// like deep nesting. Each run of mixed `&&`/`||` operators adds one, and
+    /// nested functions deepen the nesting of their bodies.
+    pub fn cognitive_complexity(&self, node: &Node, source: &str) -> u32 {
+        let code = node_source(node, source);
+        match node.lang {
+            Language::Python => python_cognitive_complexity(code),
+            Language::Rust => brace_cognitive_complexity(code, true),
+            _ => brace_cognitive_complexity(code, false),
         }
+    }
 
-        complexity
+    /// Cyclomatic and cognitive complexity of each function and method in `nodes`
+    pub fn analyze_functions(&self, nodes: &[Node], source: &str) -> Vec<Value> {
+        let mut functions: Vec<&Node> = nodes
+            .iter()
+            .filter(|node| matches!(node.kind, NodeKind::Function | NodeKind::Method))
+            .collect();
+        functions.sort_by_key(|node| node.span.start_byte);
+
+        functions
+            .into_iter()
+            .map(|node| {
+                serde_json::json!({
+                    "name": node.name,
+                    "kind": format!("{:?}", node.kind),
+                    "line": node.span.start_line,
+                    "cyclomatic": self.calculate_cyclomatic_complexity(node_source(node, source)),
+                    "cognitive": self.cognitive_complexity(node, source)
+                })
+            })
+            .collect()
+    }
+
+    /// Heatmap entry for a file from its function and method nodes
+    ///
+    /// `path` is the file's repository-relative path; the file's complexity is
+    /// the sum over its functions.
+    pub fn file_heat(&self, path: &str, nodes: &[Node], source: &str) -> FileHeat {
+        let path = path.replace('\\', "/");
+        let directory = match path.rsplit_once('/') {
+            Some((directory, _)) if !directory.is_empty() => directory.to_string(),
+            _ => ".".to_string(),
+        };
+
+        let mut functions: Vec<&Node> = nodes
+            .iter()
+            .filter(|node| matches!(node.kind, NodeKind::Function | NodeKind::Method))
+            .collect();
+        functions.sort_by_key(|node| node.span.start_byte);
+
+        let functions: Vec<FunctionHeat> = functions
+            .into_iter()
+            .map(|node| FunctionHeat {
+                name: node.name.clone(),
+                kind: format!("{:?}", node.kind),
+                start_line: node.span.start_line,
+                end_line: node.span.end_line,
+                cyclomatic: self.calculate_cyclomatic_complexity(node_source(node, source)),
+                cognitive: self.cognitive_complexity(node, source),
+            })
+            .collect();
+
+        FileHeat {
+            path,
+            directory,
+            lines_of_code: source.lines().count(),
+            cyclomatic: functions.iter().map(|f| f.cyclomatic).sum(),
+            cognitive: functions.iter().map(|f| f.cognitive).sum(),
+            max_cyclomatic: functions.iter().map(|f| f.cyclomatic).max().unwrap_or(0),
+            functions,
+        }
     }
 
     /// Calculate Halstead complexity metrics (simplified)
@@ -248,6 +392,243 @@ impl Default for ComplexityAnalyzer {
     }
 }
 
+/// Source text covered by a node, or the whole source if the span is out of range
+fn node_source<'a>(node: &Node, source: &'a str) -> &'a str {
+    source
+        .get(node.span.start_byte..node.span.end_byte)
+        .unwrap_or(source)
+}
+
+/// Whether code uses Python-style `keyword ...:` block headers
+fn looks_like_python(content: &str) -> bool {
+    content.lines().any(|line| {
+        let trimmed = line.trim();
+        ["def ", "if ", "elif ", "for ", "while ", "class "]
+            .iter()
+            .any(|keyword| trimmed.starts_with(keyword))
+            && trimmed.ends_with(':')
+    })
+}
+
+/// Split masked code into identifier and operator tokens
+fn cognitive_tokens(code: &str) -> Vec<&str> {
+    let bytes = code.as_bytes();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'\'' {
+            let start = index;
+            index += 1;
+            while index < bytes.len()
+                && (bytes[index].is_ascii_alphanumeric() || bytes[index] == b'_')
+            {
+                index += 1;
+            }
+            tokens.push(&code[start..index]);
+        } else if matches!(
+            &bytes[index..],
+            [b'&', b'&', ..] | [b'|', b'|', ..] | [b'?', b'?', ..]
+        ) {
+            tokens.push(&code[index..index + 2]);
+            index += 2;
+        } else if byte == b'?' {
+            // Only a spaced `?` is a ternary, not `?.`, `x?: T` or Rust's `?`
+            if bytes
+                .get(index + 1)
+                .is_some_and(|next| next.is_ascii_whitespace())
+            {
+                tokens.push(&code[index..index + 1]);
+            }
+            index += 1;
+        } else if matches!(byte, b'{' | b'}' | b'(' | b')' | b';' | b',') {
+            tokens.push(&code[index..index + 1]);
+            index += 1;
+        } else {
+            index += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Cognitive complexity of brace-delimited code
+///
+/// A structure keyword marks the next `{` as a nesting block. Statements
+/// without braces (`if (x) return;`) clear that mark at the next `;`.
+fn brace_cognitive_complexity(code: &str, is_rust: bool) -> u32 {
+    let masked = mask_strings_and_comments(code);
+    let tokens = cognitive_tokens(&masked);
+
+    let mut score = 0u32;
+    let mut nesting = 0u32;
+    // Each open brace records whether it increased nesting
+    let mut blocks: Vec<bool> = Vec::new();
+    let mut nest_next_block = false;
+    let mut seen_function = false;
+    let mut paren_depth = 0usize;
+    let mut last_logical: Option<&str> = None;
+    let mut closed_do_block = false;
+    let mut do_blocks: Vec<usize> = Vec::new();
+
+    for (position, &token) in tokens.iter().enumerate() {
+        let previous = position.checked_sub(1).map(|p| tokens[p]);
+        let after_closed_do = std::mem::take(&mut closed_do_block);
+
+        match token {
+            "if" if previous == Some("else") => {
+                nest_next_block = true;
+            }
+            "if" | "for" | "switch" | "match" | "select" | "catch" => {
+                score += 1 + nesting;
+                nest_next_block = true;
+            }
+            "while" if after_closed_do => {}
+            "while" | "loop" => {
+                score += 1 + nesting;
+                nest_next_block = true;
+            }
+            "do" => {
+                score += 1 + nesting;
+                nest_next_block = true;
+                do_blocks.push(blocks.len());
+            }
+            "else" => {
+                score += 1;
+                nest_next_block = true;
+            }
+            "goto" => score += 1,
+            "break" | "continue"
+                if tokens
+                    .get(position + 1)
+                    .is_some_and(|next| next.starts_with('\'')) =>
+            {
+                score += 1
+            }
+            "fn" | "func" | "function" => {
+                // The analysed function's own body is not nested
+                if seen_function {
+                    nest_next_block = true;
+                }
+                seen_function = true;
+            }
+            "?" if !is_rust => score += 1 + nesting,
+            "&&" | "||" => {
+                if last_logical != Some(token) {
+                    score += 1;
+                }
+                last_logical = Some(token);
+            }
+            "(" => paren_depth += 1,
+            ")" => paren_depth = paren_depth.saturating_sub(1),
+            ";" => {
+                last_logical = None;
+                if paren_depth == 0 {
+                    nest_next_block = false;
+                }
+            }
+            "," => last_logical = None,
+            "{" => {
+                last_logical = None;
+                blocks.push(nest_next_block);
+                if nest_next_block {
+                    nesting += 1;
+                }
+                nest_next_block = false;
+            }
+            "}" => {
+                last_logical = None;
+                if blocks.pop() == Some(true) {
+                    nesting = nesting.saturating_sub(1);
+                }
+                if do_blocks.last() == Some(&blocks.len()) {
+                    do_blocks.pop();
+                    closed_do_block = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    score
+}
+
+/// Cognitive complexity of Python code, using indentation for nesting
+fn python_cognitive_complexity(code: &str) -> u32 {
+    let mut score = 0u32;
+    // Open blocks as (indentation, increases nesting)
+    let mut blocks: Vec<(usize, bool)> = Vec::new();
+    let mut seen_function = false;
+
+    for line in code.lines() {
+        let without_comment = line.split('#').next().unwrap_or("");
+        let trimmed = without_comment.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let indent = without_comment.len() - without_comment.trim_start().len();
+        while blocks.last().is_some_and(|&(open, _)| open >= indent) {
+            blocks.pop();
+        }
+        let nesting = blocks.iter().filter(|&&(_, nests)| nests).count() as u32;
+
+        let starts = |keyword: &str| {
+            trimmed.starts_with(keyword)
+                && trimmed[keyword.len()..]
+                    .chars()
+                    .next()
+                    .is_none_or(|c| !c.is_alphanumeric() && c != '_')
+        };
+
+        let is_structure = starts("if")
+            || starts("for")
+            || starts("async for")
+            || starts("while")
+            || starts("except")
+            || (starts("match") && trimmed.ends_with(':'));
+
+        let opens_block = if is_structure {
+            score += 1 + nesting;
+            Some(true)
+        } else if starts("elif") || starts("else") {
+            score += 1;
+            Some(true)
+        } else if starts("def") || starts("async def") {
+            let nested = seen_function;
+            seen_function = true;
+            Some(nested)
+        } else if trimmed.ends_with(':') {
+            // try, finally, with, class and case blocks do not add nesting
+            Some(false)
+        } else {
+            None
+        };
+
+        // Conditional expressions
+        if opens_block.is_none() && trimmed.contains(" if ") && trimmed.contains(" else ") {
+            score += 1 + nesting;
+        }
+
+        let mut last_logical = None;
+        for word in trimmed.split(|c: char| !c.is_alphanumeric() && c != '_') {
+            if word == "and" || word == "or" {
+                if last_logical != Some(word) {
+                    score += 1;
+                }
+                last_logical = Some(word);
+            }
+        }
+
+        if let Some(nests) = opens_block {
+            blocks.push((indent, nests));
+        }
+    }
+
+    score
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,4 +676,188 @@ mod tests {
 
         assert!((0.0..=100.0).contains(&mi));
     }
+
+    fn function_node(lang: Language, source: &str) -> Node {
+        Node::new(
+            "repo",
+            NodeKind::Function,
+            "subject".to_string(),
+            lang,
+            std::path::PathBuf::from("subject"),
+            codeprism_core::Span::new(0, source.len(), 1, source.lines().count(), 1, 1),
+        )
+    }
+
+    #[test]
+    fn test_cognitive_complexity_penalizes_nesting() {
+        let analyzer = ComplexityAnalyzer::new();
+        let nested = r#"function check(a, b, c) {
+    if (a) {                  // +1
+        for (const x of b) {  // +2 (nesting 1)
+            if (x && c) {     // +3 (nesting 2), +1 for &&
+                return x;
+            }
+        }
+    }
+    return null;
+}"#;
+        let node = function_node(Language::JavaScript, nested);
+        assert_eq!(analyzer.cognitive_complexity(&node, nested), 7);
+    }
+
+    #[test]
+    fn test_cognitive_complexity_else_if_chain_is_flat() {
+        let analyzer = ComplexityAnalyzer::new();
+        let chain = r#"fn grade(score: u32) -> char {
+    if score > 90 {
+        'A'
+    } else if score > 80 {
+        'B'
+    } else if score > 70 {
+        'C'
+    } else {
+        'F'
+    }
+}"#;
+        let node = function_node(Language::Rust, chain);
+        // if + two else-ifs + else, with no nesting penalty
+        assert_eq!(analyzer.cognitive_complexity(&node, chain), 4);
+        assert!(analyzer.calculate_cyclomatic_complexity(chain) > 4);
+    }
+
+    #[test]
+    fn test_cognitive_complexity_python_uses_indentation() {
+        let analyzer = ComplexityAnalyzer::new();
+        let code = r#"def process(items):
+    for item in items:          # +1
+        if item.ready:          # +2
+            handle(item)
+        elif item.failed:       # +1
+            retry(item)
+    return True
+"#;
+        let node = function_node(Language::Python, code);
+        assert_eq!(analyzer.cognitive_complexity(&node, code), 4);
+        assert_eq!(analyzer.calculate_cognitive_complexity(code), 4);
+    }
+
+    #[test]
+    fn test_analyze_functions_reports_both_metrics() {
+        let analyzer = ComplexityAnalyzer::new();
+        let source = "function a(x) { if (x) { return 1; } return 0; }";
+        let functions =
+            analyzer.analyze_functions(&[function_node(Language::JavaScript, source)], source);
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0]["cognitive"], 1);
+        assert!(functions[0]["cyclomatic"].as_u64().unwrap() >= 2);
+    }
+
+    fn file_heat(path: &str, complexities: &[usize]) -> FileHeat {
+        FileHeat {
+            path: path.to_string(),
+            directory: path
+                .rsplit_once('/')
+                .map_or(".", |(dir, _)| dir)
+                .to_string(),
+            lines_of_code: 10,
+            cyclomatic: complexities.iter().sum(),
+            cognitive: 0,
+            max_cyclomatic: complexities.iter().copied().max().unwrap_or(0),
+            functions: complexities
+                .iter()
+                .enumerate()
+                .map(|(index, cyclomatic)| FunctionHeat {
+                    name: format!("f{index}"),
+                    kind: "Function".to_string(),
+                    start_line: index * 5 + 1,
+                    end_line: index * 5 + 4,
+                    cyclomatic: *cyclomatic,
+                    cognitive: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_file_heat_sums_function_complexity() {
+        let analyzer = ComplexityAnalyzer::new();
+        let source = "function a(x) { if (x) { return 1; } return 0; }\nfunction b() { return 2; }";
+        let split = source.find('\n').unwrap();
+        let span = |start: usize, end: usize, line: usize| {
+            codeprism_core::Span::new(start, end, line, line, 1, 1)
+        };
+        let nodes = vec![
+            Node::new(
+                "repo",
+                NodeKind::Function,
+                "b".to_string(),
+                Language::JavaScript,
+                std::path::PathBuf::from("src/util/a.js"),
+                span(split + 1, source.len(), 2),
+            ),
+            Node::new(
+                "repo",
+                NodeKind::Function,
+                "a".to_string(),
+                Language::JavaScript,
+                std::path::PathBuf::from("src/util/a.js"),
+                span(0, split, 1),
+            ),
+        ];
+
+        let heat = analyzer.file_heat("src/util/a.js", &nodes, source);
+
+        assert_eq!(heat.directory, "src/util");
+        assert_eq!(heat.lines_of_code, 2);
+        let names: Vec<_> = heat.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(heat.functions[1].start_line, 2);
+        assert_eq!(
+            heat.cyclomatic,
+            heat.functions[0].cyclomatic + heat.functions[1].cyclomatic
+        );
+        assert_eq!(heat.max_cyclomatic, heat.functions[0].cyclomatic);
+        assert_eq!(heat.cognitive, 1);
+    }
+
+    #[test]
+    fn test_heatmap_rolls_files_up_into_directory_totals() {
+        let heatmap = ComplexityHeatmap::from_files(vec![
+            file_heat("src/api/routes.js", &[3, 4]),
+            file_heat("src/api/handlers.js", &[2]),
+            file_heat("src/db/query.js", &[5, 1, 1]),
+            file_heat("main.js", &[1]),
+        ]);
+
+        let files: Vec<_> = heatmap.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            files,
+            vec![
+                "main.js",
+                "src/api/handlers.js",
+                "src/api/routes.js",
+                "src/db/query.js"
+            ]
+        );
+        assert_eq!(heatmap.files[2].cyclomatic, 7);
+
+        let directory = |path: &str| {
+            heatmap
+                .directories
+                .iter()
+                .find(|d| d.path == path)
+                .unwrap_or_else(|| panic!("missing directory {path}"))
+        };
+        assert_eq!(heatmap.directories.len(), 4);
+        assert_eq!(directory("src/api").cyclomatic, 9);
+        assert_eq!(directory("src/api").function_count, 3);
+        assert_eq!(directory("src/db").cyclomatic, 7);
+        assert_eq!(directory("src").cyclomatic, 16);
+        assert_eq!(directory("src").file_count, 3);
+        assert_eq!(directory("src").depth, 1);
+        assert_eq!(directory(".").cyclomatic, 17);
+        assert_eq!(directory(".").lines_of_code, 40);
+        assert_eq!(directory(".").depth, 0);
+    }
 }
@@ -0,0 +1,242 @@
+//! Logical coupling from git history
+//!
+//! Files that keep changing in the same commits are logically coupled, even
+//! when no import or call links them. Pairs are scored by support, the share
+//! of all commits touching both files, and confidence, the share of either
+//! file's commits that also touch the other. Commits touching many files
+//! (mass renames, formatting sweeps) are left out as they couple everything.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Record separator printed by `--format=%x1e` before the files of each commit
+const COMMIT_SEPARATOR: char = '\u{1e}';
+
+/// Two files that change together
+#[derive(Debug, Clone, PartialEq)]
+pub struct CouplingPair {
+    pub first: String,
+    pub second: String,
+    /// Commits touching both files
+    pub co_changes: usize,
+    /// `co_changes` over all commits considered
+    pub support: f64,
+    /// `co_changes` over the commits of the more often changed file
+    pub confidence: f64,
+}
+
+/// Thresholds for reporting a pair
+#[derive(Debug, Clone)]
+pub struct CouplingOptions {
+    /// Fewest commits the two files must share
+    pub min_co_changes: usize,
+    /// Lowest confidence reported
+    pub min_confidence: f64,
+    /// Commits touching more files are ignored
+    pub max_files_per_commit: usize,
+}
+
+impl Default for CouplingOptions {
+    fn default() -> Self {
+        Self {
+            min_co_changes: 3,
+            min_confidence: 0.5,
+            max_files_per_commit: 30,
+        }
+    }
+}
+
+/// Analyzer for files changed together in git history
+pub struct LogicalCouplingAnalyzer;
+
+impl LogicalCouplingAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Split `git log --format=%x1e --name-only` output into the files of each commit
+    pub fn parse_commits(&self, log_output: &str) -> Vec<Vec<String>> {
+        log_output
+            .split(COMMIT_SEPARATOR)
+            .map(|commit| {
+                commit
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>()
+            })
+            .filter(|files| !files.is_empty())
+            .collect()
+    }
+
+    /// Pairs of files changed together often enough to pass `options`
+    ///
+    /// Pairs are sorted by confidence, then co-change count, then path.
+    pub fn coupled_pairs(
+        &self,
+        commits: &[Vec<String>],
+        options: &CouplingOptions,
+    ) -> Vec<CouplingPair> {
+        let commits: Vec<&Vec<String>> = commits
+            .iter()
+            .filter(|files| files.len() <= options.max_files_per_commit)
+            .collect();
+
+        let mut changes: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut co_changes: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+        for files in &commits {
+            for (index, first) in files.iter().enumerate() {
+                *changes.entry(first).or_insert(0) += 1;
+                for second in &files[index + 1..] {
+                    *co_changes.entry((first, second)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut pairs: Vec<CouplingPair> = co_changes
+            .into_iter()
+            .filter(|&(_, count)| count >= options.min_co_changes)
+            .map(|((first, second), count)| {
+                let most_changes = changes[first].max(changes[second]);
+                CouplingPair {
+                    first: first.to_string(),
+                    second: second.to_string(),
+                    co_changes: count,
+                    support: count as f64 / commits.len() as f64,
+                    confidence: count as f64 / most_changes as f64,
+                }
+            })
+            .filter(|pair| pair.confidence >= options.min_confidence)
+            .collect();
+
+        pairs.sort_by(|a, b| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then(b.co_changes.cmp(&a.co_changes))
+                .then(a.first.cmp(&b.first))
+                .then(a.second.cmp(&b.second))
+        });
+        pairs
+    }
+
+    /// Coupled pairs of files in the repository at `repo_path`
+    ///
+    /// `since` is passed to `git log --since`. Pairs with a file no longer in
+    /// the working tree are dropped.
+    #[cfg(feature = "git")]
+    pub fn coupled_files(
+        &self,
+        repo_path: &std::path::Path,
+        since: Option<&str>,
+        options: &CouplingOptions,
+    ) -> anyhow::Result<Vec<CouplingPair>> {
+        use anyhow::anyhow;
+        use std::process::Command;
+
+        let mut command = Command::new("git");
+        command.arg("-C").arg(repo_path).args([
+            "log",
+            "--format=%x1e",
+            "--name-only",
+            "--no-renames",
+        ]);
+        if let Some(since) = since {
+            command.arg(format!("--since={since}"));
+        }
+
+        let output = command
+            .output()
+            .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git log failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let commits = self.parse_commits(&String::from_utf8_lossy(&output.stdout));
+        let mut pairs = self.coupled_pairs(&commits, options);
+        pairs.retain(|pair| {
+            repo_path.join(&pair.first).is_file() && repo_path.join(&pair.second).is_file()
+        });
+        Ok(pairs)
+    }
+}
+
+impl Default for LogicalCouplingAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coupled_pairs_from_log_output() {
+        let log = "\u{1e}\n\na.rs\nb.rs\n\u{1e}\n\na.rs\nb.rs\nc.rs\n\u{1e}\n\na.rs\nb.rs\n\u{1e}\n\nc.rs\n";
+        let analyzer = LogicalCouplingAnalyzer::new();
+        let commits = analyzer.parse_commits(log);
+        assert_eq!(commits.len(), 4);
+
+        let pairs = analyzer.coupled_pairs(&commits, &CouplingOptions::default());
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(
+            (pairs[0].first.as_str(), pairs[0].second.as_str()),
+            ("a.rs", "b.rs")
+        );
+        assert_eq!(pairs[0].co_changes, 3);
+        assert_eq!(pairs[0].support, 0.75);
+        assert_eq!(pairs[0].confidence, 1.0);
+
+        // Bulk commits are ignored
+        let strict = CouplingOptions {
+            max_files_per_commit: 2,
+            min_co_changes: 2,
+            ..CouplingOptions::default()
+        };
+        assert_eq!(analyzer.coupled_pairs(&commits, &strict)[0].co_changes, 2);
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_files_always_changed_together_are_coupled() {
+        use std::process::Command;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+
+        git(&["init", "-q"]);
+        for i in 1..=4 {
+            std::fs::write(dir.join("pricing.py"), format!("RATE = {i}\n")).unwrap();
+            std::fs::write(dir.join("invoice_template.html"), format!("<p>{i}</p>\n")).unwrap();
+            if i % 2 == 0 {
+                std::fs::write(dir.join("readme.md"), format!("v{i}\n")).unwrap();
+            }
+            git(&["add", "-A"]);
+            git(&["commit", "-q", "-m", &format!("change {i}")]);
+        }
+
+        let pairs = LogicalCouplingAnalyzer::new()
+            .coupled_files(dir, None, &CouplingOptions::default())
+            .unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].first, "invoice_template.html");
+        assert_eq!(pairs[0].second, "pricing.py");
+        assert_eq!(pairs[0].co_changes, 4);
+        assert_eq!(pairs[0].confidence, 1.0);
+    }
+}
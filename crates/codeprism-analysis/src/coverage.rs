@@ -0,0 +1,155 @@
+//! Line coverage overlays from lcov reports
+//!
+//! Maps repository-relative file paths to the fraction of instrumented lines
+//! that were executed, so other analyses can weigh findings by how well the
+//! affected code is tested.
+
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Instrumented and executed line counts for one file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileCoverage {
+    pub lines_found: usize,
+    pub lines_hit: usize,
+}
+
+impl FileCoverage {
+    /// Fraction of instrumented lines that were executed, between 0.0 and 1.0
+    pub fn ratio(&self) -> f64 {
+        if self.lines_found == 0 {
+            return 0.0;
+        }
+        (self.lines_hit as f64 / self.lines_found as f64).min(1.0)
+    }
+}
+
+/// Per-file line coverage keyed by repository-relative path
+#[derive(Debug, Clone, Default)]
+pub struct CoverageOverlay {
+    files: BTreeMap<String, FileCoverage>,
+}
+
+impl CoverageOverlay {
+    /// Parse an lcov tracefile
+    ///
+    /// Source paths under `root` are stored relative to it. `DA` records take
+    /// precedence over the `LF`/`LH` summary lines when both are present.
+    pub fn from_lcov(content: &str, root: Option<&Path>) -> Self {
+        let mut files = BTreeMap::new();
+        let mut current: Option<String> = None;
+        let mut from_lines = FileCoverage::default();
+        let mut from_summary = FileCoverage::default();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(source) = line.strip_prefix("SF:") {
+                current = Some(normalize_path(source, root));
+                from_lines = FileCoverage::default();
+                from_summary = FileCoverage::default();
+            } else if let Some(record) = line.strip_prefix("DA:") {
+                let mut fields = record.split(',');
+                let hits = fields.nth(1).and_then(|count| count.parse::<u64>().ok());
+                if let Some(hits) = hits {
+                    from_lines.lines_found += 1;
+                    if hits > 0 {
+                        from_lines.lines_hit += 1;
+                    }
+                }
+            } else if let Some(count) = line.strip_prefix("LF:") {
+                from_summary.lines_found = count.parse().unwrap_or(0);
+            } else if let Some(count) = line.strip_prefix("LH:") {
+                from_summary.lines_hit = count.parse().unwrap_or(0);
+            } else if line == "end_of_record" {
+                if let Some(path) = current.take() {
+                    let coverage = if from_lines.lines_found > 0 {
+                        from_lines
+                    } else {
+                        from_summary
+                    };
+                    let entry: &mut FileCoverage = files.entry(path).or_default();
+                    entry.lines_found += coverage.lines_found;
+                    entry.lines_hit += coverage.lines_hit;
+                }
+            }
+        }
+
+        Self { files }
+    }
+
+    /// Read and parse an lcov tracefile from disk
+    pub fn load(report_path: &Path, root: Option<&Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(report_path).map_err(|e| {
+            anyhow!(
+                "Failed to read coverage report {}: {}",
+                report_path.display(),
+                e
+            )
+        })?;
+        Ok(Self::from_lcov(&content, root))
+    }
+
+    /// Coverage ratio of a repository-relative path, if the report mentions it
+    pub fn coverage_for(&self, path: &str) -> Option<f64> {
+        self.files
+            .get(path.trim_start_matches("./"))
+            .map(FileCoverage::ratio)
+    }
+
+    /// Raw line counts of a repository-relative path
+    pub fn file(&self, path: &str) -> Option<&FileCoverage> {
+        self.files.get(path.trim_start_matches("./"))
+    }
+
+    /// Number of files in the report
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether the report covers no files
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+fn normalize_path(source: &str, root: Option<&Path>) -> String {
+    let path = Path::new(source);
+    let relative = root
+        .and_then(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path);
+    relative
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lcov_line_records_and_summaries() {
+        let report = "\
+TN:
+SF:/repo/src/parser.rs
+DA:1,4
+DA:2,0
+DA:3,1
+DA:4,0
+LF:4
+LH:2
+end_of_record
+SF:/repo/src/lib.rs
+LF:10
+LH:9
+end_of_record
+";
+        let overlay = CoverageOverlay::from_lcov(report, Some(Path::new("/repo")));
+        assert_eq!(overlay.len(), 2);
+        assert_eq!(overlay.coverage_for("src/parser.rs"), Some(0.5));
+        assert_eq!(overlay.coverage_for("./src/lib.rs"), Some(0.9));
+        assert_eq!(overlay.coverage_for("src/missing.rs"), None);
+    }
+}
@@ -0,0 +1,414 @@
+//! Data clump and primitive obsession detection
+//!
+//! A data clump is a group of primitive parameters that travels together
+//! through several function signatures, such as `(lat, lon, alt)` or
+//! `(start_date, end_date, timezone)`. Each recurring group is a candidate for
+//! extraction into a struct or class. Parameters come from the signatures the
+//! language parsers record on function and method nodes.
+
+use codeprism_core::{Language, Node, NodeKind};
+use std::collections::BTreeSet;
+
+/// Type names treated as primitives across the supported languages
+const PRIMITIVE_TYPES: &[&str] = &[
+    "int",
+    "float",
+    "str",
+    "bool",
+    "bytes",
+    "complex",
+    "number",
+    "string",
+    "boolean",
+    "bigint",
+    "char",
+    "byte",
+    "short",
+    "long",
+    "double",
+    "integer",
+    "character",
+    "rune",
+    "uint",
+    "uintptr",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "f32",
+    "f64",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "float32",
+    "float64",
+    "unsigned",
+    "signed",
+    "size_t",
+];
+
+/// Receivers that are never part of a clump
+const RECEIVERS: &[&str] = &["self", "cls", "this", "&self", "&mut self", "mut self"];
+
+/// A parameter parsed from a signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: String,
+    /// Declared type, `None` when the signature carries no annotation
+    pub type_name: Option<String>,
+}
+
+impl Parameter {
+    /// Whether the parameter is a primitive; unannotated parameters count too
+    pub fn is_primitive(&self) -> bool {
+        match &self.type_name {
+            None => true,
+            Some(type_name) => {
+                let base = type_name
+                    .trim_start_matches('&')
+                    .trim_start_matches("mut ")
+                    .to_lowercase();
+                // Multi-word C types like `unsigned int` are primitive word by word
+                base.split_whitespace()
+                    .all(|word| PRIMITIVE_TYPES.contains(&word))
+            }
+        }
+    }
+}
+
+/// A group of primitive parameters shared by several functions
+#[derive(Debug, Clone)]
+pub struct DataClump {
+    /// Parameter names, sorted
+    pub parameters: Vec<String>,
+    /// Functions and methods whose signatures contain the whole group
+    pub sites: Vec<Node>,
+    pub suggestion: String,
+}
+
+/// Finds recurring groups of primitive parameters
+pub struct DataClumpAnalyzer;
+
+impl DataClumpAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find groups of at least `min_params` primitive parameters that appear
+    /// together in at least `min_occurrences` signatures
+    ///
+    /// A group is only reported in its largest form: a subset shared by exactly
+    /// the same functions is dropped in favour of the full group.
+    pub fn find_clumps(
+        &self,
+        functions: &[Node],
+        min_params: usize,
+        min_occurrences: usize,
+    ) -> Vec<DataClump> {
+        let min_params = min_params.max(2);
+        let signatures: Vec<(&Node, BTreeSet<String>)> = functions
+            .iter()
+            .filter(|node| matches!(node.kind, NodeKind::Function | NodeKind::Method))
+            .filter_map(|node| {
+                let names: BTreeSet<String> = parameters(node.signature.as_deref()?, node.lang)
+                    .into_iter()
+                    .filter(Parameter::is_primitive)
+                    .map(|param| param.name)
+                    .collect();
+                (names.len() >= min_params).then_some((node, names))
+            })
+            .collect();
+
+        let mut candidates: BTreeSet<Vec<String>> = BTreeSet::new();
+        for (i, (_, a)) in signatures.iter().enumerate() {
+            for (_, b) in &signatures[i + 1..] {
+                let shared: Vec<String> = a.intersection(b).cloned().collect();
+                if shared.len() >= min_params {
+                    candidates.insert(shared);
+                }
+            }
+        }
+
+        let mut clumps: Vec<(Vec<String>, Vec<usize>)> = candidates
+            .into_iter()
+            .map(|group| {
+                let sites = signatures
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, names))| group.iter().all(|name| names.contains(name)))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+                (group, sites)
+            })
+            .filter(|(_, sites)| sites.len() >= min_occurrences.max(2))
+            .collect();
+
+        let subsumed: Vec<bool> = clumps
+            .iter()
+            .map(|(group, sites)| {
+                clumps.iter().any(|(other, other_sites)| {
+                    other.len() > group.len()
+                        && other_sites == sites
+                        && group.iter().all(|name| other.contains(name))
+                })
+            })
+            .collect();
+        let mut subsumed = subsumed.into_iter();
+        clumps.retain(|_| !subsumed.next().unwrap_or(false));
+
+        clumps.sort_by(|(a, a_sites), (b, b_sites)| {
+            b_sites
+                .len()
+                .cmp(&a_sites.len())
+                .then(b.len().cmp(&a.len()))
+                .then(a.cmp(b))
+        });
+
+        clumps
+            .into_iter()
+            .map(|(parameters, sites)| {
+                let sites: Vec<Node> = sites
+                    .into_iter()
+                    .map(|index| signatures[index].0.clone())
+                    .collect();
+                DataClump {
+                    suggestion: suggestion(&parameters, &sites),
+                    parameters,
+                    sites,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for DataClumpAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse the parameter list of a signature such as `f(a: int, b=2) -> str`
+///
+/// Java and C-family signatures put the type first; Go puts it after the
+/// name without a colon; everything else uses `name: Type`.
+pub fn parameters(signature: &str, language: Language) -> Vec<Parameter> {
+    let Some((list, _)) = split_signature(signature) else {
+        return Vec::new();
+    };
+
+    split_top_level(list)
+        .into_iter()
+        .filter_map(|raw| {
+            let raw = raw.split('=').next().unwrap_or(raw).trim();
+            // Receivers, variadics and Python's `*args`/`**kwargs` never form clumps
+            if raw.is_empty()
+                || RECEIVERS.contains(&raw)
+                || raw.starts_with('*')
+                || raw.contains("...")
+            {
+                return None;
+            }
+            let (name, type_name) = match language {
+                Language::Java | Language::C | Language::Cpp => {
+                    let mut words = raw.rsplitn(2, char::is_whitespace);
+                    let name = words.next()?;
+                    let type_name = words.next().map(|t| {
+                        // Drop modifiers and annotations that precede the type
+                        t.split_whitespace()
+                            .filter(|word| !word.starts_with('@') && *word != "final")
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    });
+                    (name.trim_start_matches(['*', '&']), type_name)
+                }
+                Language::Go => {
+                    let mut words = raw.splitn(2, char::is_whitespace);
+                    let name = words.next()?;
+                    (name, words.next().map(|t| t.trim().to_string()))
+                }
+                _ => match raw.split_once(':') {
+                    Some((name, type_name)) => (name.trim(), Some(type_name.trim().to_string())),
+                    None => (raw, None),
+                },
+            };
+            let name = name.trim_start_matches("mut ").trim().trim_end_matches('?');
+            if name.is_empty()
+                || RECEIVERS.contains(&name)
+                || !name.chars().all(|c| c.is_alphanumeric() || c == '_')
+            {
+                return None;
+            }
+            Some(Parameter {
+                name: name.to_string(),
+                type_name,
+            })
+        })
+        .collect()
+}
+
+/// Text between the first `(` and its matching `)`, and the text after it
+pub(crate) fn split_signature(signature: &str) -> Option<(&str, &str)> {
+    let open = signature.find('(')?;
+    let mut depth = 0;
+    for (offset, c) in signature[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let close = open + offset;
+                    return Some((&signature[open + 1..close], &signature[close + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on commas outside brackets, so `Dict[str, int]` stays one parameter
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&list[start..]);
+    parts
+}
+
+/// Extraction hint naming a type after the grouped parameters
+fn suggestion(parameters: &[String], sites: &[Node]) -> String {
+    let type_name: String = parameters
+        .iter()
+        .map(|name| {
+            name.split('_')
+                .map(|part| {
+                    let mut chars = part.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                        .unwrap_or_default()
+                })
+                .collect::<String>()
+        })
+        .collect();
+    let construct = match sites.first().map(|node| node.lang) {
+        Some(Language::Rust | Language::Go | Language::C | Language::Cpp) => "struct",
+        _ => "class",
+    };
+    format!(
+        "Extract ({}) into a {} such as `{}` and pass it to the {} functions that take these values together",
+        parameters.join(", "),
+        construct,
+        type_name,
+        sites.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::Span;
+    use std::path::PathBuf;
+
+    fn function(name: &str, file: &str, signature: &str, lang: Language) -> Node {
+        let mut node = Node::new(
+            "repo",
+            NodeKind::Function,
+            name.to_string(),
+            lang,
+            PathBuf::from(file),
+            Span::new(0, 10, 1, 1, 1, 11),
+        );
+        node.signature = Some(signature.to_string());
+        node
+    }
+
+    #[test]
+    fn test_shared_coordinate_triple_is_reported_with_all_sites() {
+        let functions = vec![
+            function(
+                "distance_to",
+                "geo.py",
+                "distance_to(lat: float, lon: float, alt: float, other: Point) -> float",
+                Language::Python,
+            ),
+            function(
+                "render_marker",
+                "map.py",
+                "render_marker(self, lat, lon, alt, label: str)",
+                Language::Python,
+            ),
+            function(
+                "store_fix",
+                "store.py",
+                "store_fix(alt: float, lat: float, lon: float, timestamp: int = 0)",
+                Language::Python,
+            ),
+            // Shares only two of the three, so it is not a site
+            function(
+                "geohash",
+                "geo.py",
+                "geohash(lat: float, lon: float, precision: Precision)",
+                Language::Python,
+            ),
+        ];
+
+        let clumps = DataClumpAnalyzer::new().find_clumps(&functions, 3, 2);
+        assert_eq!(clumps.len(), 1);
+        let clump = &clumps[0];
+        assert_eq!(clump.parameters, vec!["alt", "lat", "lon"]);
+        let sites: Vec<&str> = clump.sites.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(sites, vec!["distance_to", "render_marker", "store_fix"]);
+        assert!(clump.suggestion.contains("class such as `AltLatLon`"));
+    }
+
+    #[test]
+    fn test_parameters_follow_language_conventions() {
+        let java = parameters(
+            "double bearing(final double lat, @Positive double lon, Map<String, Integer> cache)",
+            Language::Java,
+        );
+        assert_eq!(java[0].name, "lat");
+        assert!(java[0].is_primitive());
+        assert_eq!(java[2].name, "cache");
+        assert_eq!(java[2].type_name.as_deref(), Some("Map<String, Integer>"));
+        assert!(!java[2].is_primitive());
+        assert!(parameters("int clamp(unsigned int v)", Language::C)[0].is_primitive());
+
+        let rust = parameters(
+            "fn shift(&self, dx: i64, label: &str, grid: Grid)",
+            Language::Rust,
+        );
+        let names: Vec<&str> = rust.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["dx", "label", "grid"]);
+        assert!(rust[1].is_primitive());
+        assert!(!rust[2].is_primitive());
+
+        let go = parameters("func Move(x int, y float64)", Language::Go);
+        assert_eq!(go[1].type_name.as_deref(), Some("float64"));
+    }
+}
@@ -0,0 +1,309 @@
+//! Error-handling style consistency analysis
+//!
+//! Classifies each function by how it signals failure: raising exceptions
+//! (`throw`, `raise`, `panic`), returning error values (`return nil, err`,
+//! `Err(...)`, `return { error }`, error-first callbacks) or both. A function
+//! with a `Raises` edge in the graph counts as raising even when its source
+//! shows no raise. Modules, taken as source files, are flagged when the
+//! minority style exceeds a configurable share of their error-handling
+//! functions.
+
+use crate::infinite_loops::mask_strings_and_comments;
+use codeprism_core::{Language, Node, NodeKind};
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// How a function signals failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorStyle {
+    /// Raises exceptions or panics
+    Raises,
+    /// Returns error values
+    ReturnsErrors,
+    /// Does both
+    Mixed,
+    /// Shows no error handling
+    None,
+}
+
+impl ErrorStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorStyle::Raises => "raises",
+            ErrorStyle::ReturnsErrors => "returns_errors",
+            ErrorStyle::Mixed => "mixed",
+            ErrorStyle::None => "none",
+        }
+    }
+}
+
+/// Error-handling style of one function
+#[derive(Debug, Clone)]
+pub struct FunctionErrorStyle {
+    pub function: Node,
+    pub style: ErrorStyle,
+}
+
+/// Error-handling styles of the functions in one module
+#[derive(Debug, Clone)]
+pub struct ModuleErrorStyle {
+    pub file: PathBuf,
+    pub functions: Vec<FunctionErrorStyle>,
+    /// Functions that raise, including mixed ones
+    pub raising: usize,
+    /// Functions that return errors, including mixed ones
+    pub returning: usize,
+    /// Share of error-handling functions using the less common style
+    pub minority_ratio: f64,
+    /// Whether the minority ratio exceeds the configured maximum
+    pub inconsistent: bool,
+}
+
+impl ModuleErrorStyle {
+    /// The style used by most error-handling functions, if any handle errors
+    pub fn dominant_style(&self) -> Option<ErrorStyle> {
+        match (self.raising, self.returning) {
+            (0, 0) => None,
+            (raising, returning) if raising > returning => Some(ErrorStyle::Raises),
+            (raising, returning) if returning > raising => Some(ErrorStyle::ReturnsErrors),
+            _ => Some(ErrorStyle::Mixed),
+        }
+    }
+}
+
+/// Classifies functions by error-handling style and flags mixed modules
+pub struct ErrorStyleAnalyzer {
+    raise_pattern: Regex,
+    go_panic_pattern: Regex,
+    rust_panic_pattern: Regex,
+    error_return_pattern: Regex,
+    rust_err_pattern: Regex,
+    error_callback_pattern: Regex,
+}
+
+impl ErrorStyleAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            raise_pattern: Regex::new(r"\b(?:throw|raise)\b").unwrap(),
+            go_panic_pattern: Regex::new(r"\bpanic\(").unwrap(),
+            rust_panic_pattern: Regex::new(r"\bpanic!\(").unwrap(),
+            error_return_pattern: Regex::new(
+                r"\breturn\b[^\n;]*\b(?:err|error|errors\.New|fmt\.Errorf|\w*Error)\b",
+            )
+            .unwrap(),
+            rust_err_pattern: Regex::new(r"\bErr\(").unwrap(),
+            error_callback_pattern: Regex::new(
+                r"\b(?:callback|cb|done|next)\(\s*(?:err\b|error\b|new\s+\w*Error\b)",
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Classify a function from its source and whether the graph records it raising
+    pub fn classify_function(
+        &self,
+        node: &Node,
+        source: &str,
+        has_raises_edge: bool,
+    ) -> ErrorStyle {
+        let body = source
+            .get(node.span.start_byte..node.span.end_byte)
+            .unwrap_or_default();
+        let code = mask_strings_and_comments(body);
+
+        let raises = has_raises_edge
+            || match node.lang {
+                Language::Go => self.go_panic_pattern.is_match(&code),
+                Language::Rust => self.rust_panic_pattern.is_match(&code),
+                _ => self.raise_pattern.is_match(&code),
+            };
+        let returns_errors = match node.lang {
+            Language::Rust => self.rust_err_pattern.is_match(&code),
+            _ => {
+                self.error_return_pattern.is_match(&code)
+                    || self.error_callback_pattern.is_match(&code)
+            }
+        };
+
+        match (raises, returns_errors) {
+            (true, true) => ErrorStyle::Mixed,
+            (true, false) => ErrorStyle::Raises,
+            (false, true) => ErrorStyle::ReturnsErrors,
+            (false, false) => ErrorStyle::None,
+        }
+    }
+
+    /// Classify the functions of one module and decide whether it mixes styles
+    ///
+    /// `raising_ids` holds the functions with outgoing `Raises` edges. A module
+    /// is inconsistent when the minority style's share of its error-handling
+    /// functions is greater than `max_minority_ratio`.
+    pub fn analyze_module(
+        &self,
+        file: PathBuf,
+        functions: &[Node],
+        source: &str,
+        raising_ids: &HashSet<codeprism_core::NodeId>,
+        max_minority_ratio: f64,
+    ) -> ModuleErrorStyle {
+        let mut functions: Vec<FunctionErrorStyle> = functions
+            .iter()
+            .filter(|node| matches!(node.kind, NodeKind::Function | NodeKind::Method))
+            .map(|node| FunctionErrorStyle {
+                style: self.classify_function(node, source, raising_ids.contains(&node.id)),
+                function: node.clone(),
+            })
+            .collect();
+        functions.sort_by_key(|f| f.function.span.start_byte);
+
+        let count = |styles: &[ErrorStyle]| {
+            functions
+                .iter()
+                .filter(|f| styles.contains(&f.style))
+                .count()
+        };
+        let raising = count(&[ErrorStyle::Raises, ErrorStyle::Mixed]);
+        let returning = count(&[ErrorStyle::ReturnsErrors, ErrorStyle::Mixed]);
+        let handling = count(&[
+            ErrorStyle::Raises,
+            ErrorStyle::ReturnsErrors,
+            ErrorStyle::Mixed,
+        ]);
+
+        let minority_ratio = if handling == 0 {
+            0.0
+        } else {
+            raising.min(returning) as f64 / handling as f64
+        };
+
+        ModuleErrorStyle {
+            file,
+            functions,
+            raising,
+            returning,
+            minority_ratio,
+            inconsistent: minority_ratio > max_minority_ratio,
+        }
+    }
+}
+
+impl Default for ErrorStyleAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Function nodes for each top-level `function` in a JavaScript source
+    fn js_functions(source: &str) -> Vec<Node> {
+        let starts: Vec<usize> = source.match_indices("function ").map(|(i, _)| i).collect();
+        starts
+            .iter()
+            .enumerate()
+            .map(|(index, &start)| {
+                let end = starts.get(index + 1).copied().unwrap_or(source.len());
+                let name = source[start + 9..].split('(').next().unwrap().to_string();
+                Node::new(
+                    "repo",
+                    NodeKind::Function,
+                    name,
+                    Language::JavaScript,
+                    PathBuf::from("users.js"),
+                    codeprism_core::Span::new(start, end, index + 1, index + 1, 1, 1),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_half_raising_half_returning_module_is_inconsistent() {
+        let analyzer = ErrorStyleAnalyzer::new();
+        let source = r#"
+function load(id) { if (!id) { throw new Error("missing id"); } return db.get(id); }
+function save(user) { if (!user.name) { throw new ValidationError("name"); } db.put(user); }
+function remove(id) { if (!db.has(id)) { return { error: "not found" }; } db.delete(id); }
+function rename(id, name, callback) { if (!name) { return callback(new Error("name")); } callback(null); }
+function count() { return db.size(); }
+"#;
+        let functions = js_functions(source);
+        let report = analyzer.analyze_module(
+            PathBuf::from("users.js"),
+            &functions,
+            source,
+            &HashSet::new(),
+            0.2,
+        );
+
+        let styles: Vec<_> = report
+            .functions
+            .iter()
+            .map(|f| (f.function.name.as_str(), f.style))
+            .collect();
+        assert_eq!(
+            styles,
+            vec![
+                ("load", ErrorStyle::Raises),
+                ("save", ErrorStyle::Raises),
+                ("remove", ErrorStyle::ReturnsErrors),
+                ("rename", ErrorStyle::ReturnsErrors),
+                ("count", ErrorStyle::None),
+            ]
+        );
+        assert_eq!(report.raising, 2);
+        assert_eq!(report.returning, 2);
+        assert_eq!(report.minority_ratio, 0.5);
+        assert!(report.inconsistent);
+        assert_eq!(report.dominant_style(), Some(ErrorStyle::Mixed));
+
+        // A looser threshold accepts the same module
+        let relaxed = analyzer.analyze_module(
+            PathBuf::from("users.js"),
+            &functions,
+            source,
+            &HashSet::new(),
+            0.5,
+        );
+        assert!(!relaxed.inconsistent);
+    }
+
+    #[test]
+    fn test_raises_edge_and_language_specific_markers() {
+        let analyzer = ErrorStyleAnalyzer::new();
+        let source = "function quiet() { return 1; }";
+        let node = &js_functions(source)[0];
+        assert_eq!(
+            analyzer.classify_function(node, source, false),
+            ErrorStyle::None
+        );
+        assert_eq!(
+            analyzer.classify_function(node, source, true),
+            ErrorStyle::Raises
+        );
+
+        // Strings and comments are ignored
+        let source = "function note() { log(\"throw it\"); // raise later\n}";
+        let node = &js_functions(source)[0];
+        assert_eq!(
+            analyzer.classify_function(node, source, false),
+            ErrorStyle::None
+        );
+
+        let go = "func Parse(s string) (int, error) {\n\tif s == \"\" {\n\t\treturn 0, errors.New(\"empty\")\n\t}\n\tif s == \"!\" {\n\t\tpanic(\"bang\")\n\t}\n\treturn len(s), nil\n}";
+        let node = Node::new(
+            "repo",
+            NodeKind::Function,
+            "Parse".to_string(),
+            Language::Go,
+            PathBuf::from("parse.go"),
+            codeprism_core::Span::new(0, go.len(), 1, 9, 1, 1),
+        );
+        assert_eq!(
+            analyzer.classify_function(&node, go, false),
+            ErrorStyle::Mixed
+        );
+    }
+}
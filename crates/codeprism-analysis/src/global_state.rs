@@ -0,0 +1,723 @@
+//! Global mutable state and singleton detection module
+//!
+//! Module-level variables (Python, JavaScript/TypeScript) and `static mut`
+//! items (Rust) are collected first, then each function body is scanned for
+//! writes to them: rebinding (after `global` in Python), item and attribute
+//! assignment, and calls to mutating methods. A local of the same name shadows
+//! the global for the rest of that function. State written from more than one
+//! function, and lazily created singletons, are reported with their write
+//! sites. A write counts as synchronized inside a `with <lock>:` block or
+//! after a lock is acquired in the same function; the check is textual, so
+//! it cannot tell whether the lock taken is the one guarding the variable.
+
+use crate::commented_code::CommentedCodeAnalyzer;
+use crate::infinite_loops::mask_strings_and_comments;
+use crate::null_safety::{indentation, mask_python, mentions};
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Kind of shared state found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalStateKind {
+    /// Module-level variable mutated from functions
+    MutableGlobal,
+    /// Module-level slot that starts empty and is assigned an instance on first use
+    Singleton,
+}
+
+impl GlobalStateKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GlobalStateKind::MutableGlobal => "mutable_global",
+            GlobalStateKind::Singleton => "singleton",
+        }
+    }
+}
+
+/// A function body writing to global state
+#[derive(Debug, Clone)]
+pub struct WriteSite {
+    pub function: String,
+    /// Line of the write (1-based)
+    pub line: usize,
+    /// Whether a lock is held at the write
+    pub synchronized: bool,
+    pub snippet: String,
+}
+
+/// Global state written from function bodies
+#[derive(Debug, Clone)]
+pub struct GlobalState {
+    pub file_path: Option<String>,
+    pub name: String,
+    /// Line of the module-level declaration (1-based)
+    pub line: usize,
+    pub kind: GlobalStateKind,
+    pub write_sites: Vec<WriteSite>,
+    /// Whether every write site holds a lock
+    pub synchronized: bool,
+}
+
+impl GlobalState {
+    /// Distinct functions writing this state
+    pub fn writers(&self) -> Vec<&str> {
+        let mut writers: Vec<&str> = self
+            .write_sites
+            .iter()
+            .map(|site| site.function.as_str())
+            .collect();
+        writers.sort_unstable();
+        writers.dedup();
+        writers
+    }
+}
+
+/// Module-level declaration being tracked while scanning functions
+#[derive(Debug)]
+struct Declaration {
+    name: String,
+    line: usize,
+    /// Whether the name itself may be reassigned (not a `const` binding)
+    rebindable: bool,
+    /// Whether it starts out as `None`/`null`/`undefined`
+    starts_empty: bool,
+    lazily_created: bool,
+    sites: Vec<WriteSite>,
+}
+
+impl Declaration {
+    fn new(name: &str, line: usize, rebindable: bool, starts_empty: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            line,
+            rebindable,
+            starts_empty,
+            lazily_created: false,
+            sites: Vec::new(),
+        }
+    }
+}
+
+/// How a line writes to a tracked name
+#[derive(Debug, PartialEq)]
+enum Write {
+    /// The name is rebound; `instance` when the new value is constructed
+    Assign { instance: bool },
+    /// The value is changed in place
+    Mutate,
+}
+
+/// Function whose body is being scanned
+#[derive(Debug)]
+struct OpenFunction {
+    name: String,
+    /// Indentation (Python) or body brace depth (brace languages)
+    depth: usize,
+    globals: HashSet<String>,
+    locals: HashSet<String>,
+    acquired: bool,
+}
+
+impl OpenFunction {
+    fn new(name: &str, depth: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            depth,
+            globals: HashSet::new(),
+            locals: HashSet::new(),
+            acquired: false,
+        }
+    }
+}
+
+const PYTHON_MUTATORS: &[&str] = &[
+    "append",
+    "extend",
+    "insert",
+    "update",
+    "pop",
+    "popitem",
+    "clear",
+    "setdefault",
+    "add",
+    "remove",
+    "discard",
+    "sort",
+    "reverse",
+];
+const JS_MUTATORS: &[&str] = &[
+    "push", "pop", "shift", "unshift", "splice", "set", "delete", "clear", "add", "sort",
+    "reverse", "fill",
+];
+const RUST_MUTATORS: &[&str] = &[
+    "push", "pop", "insert", "remove", "clear", "extend", "entry", "truncate", "retain", "drain",
+    "get_mut", "iter_mut",
+];
+
+/// Analyzer that finds global mutable state and singletons shared between functions
+pub struct GlobalStateAnalyzer {
+    python_def: Regex,
+    python_module_assignment: Regex,
+    python_lock_constructor: Regex,
+    js_module_declaration: Regex,
+    js_function: Regex,
+    rust_static_mut: Regex,
+    rust_function: Regex,
+    lock_expression: Regex,
+    acquire_call: Regex,
+    instance_value: Regex,
+}
+
+impl GlobalStateAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            python_def: Regex::new(r"^(\s*)(?:async\s+)?def\s+(\w+)\s*\(").unwrap(),
+            python_module_assignment: Regex::new(r"^(\w+)\s*(?::[^=]*)?=\s*([^=].*)$").unwrap(),
+            python_lock_constructor: Regex::new(
+                r"\b(?:R?Lock|Semaphore|BoundedSemaphore|Condition)\s*\(",
+            )
+            .unwrap(),
+            js_module_declaration: Regex::new(
+                r"^(?:export\s+)?(let|var|const)\s+(\w+)\s*(?::[^=]+)?=\s*(.*)$",
+            )
+            .unwrap(),
+            js_function: Regex::new(
+                r"(?:\bfunction\s*\*?\s*(\w+)\s*\(|\b(\w+)\s*=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*(?::[^=]*)?=>|\w+\s*=>)|^\s*(?:(?:async|static|public|private|protected)\s+)*(\w+)\s*\([^)]*\)\s*(?::[^{]*)?\{)",
+            )
+            .unwrap(),
+            rust_static_mut: Regex::new(
+                r"^\s*(?:pub(?:\([^)]*\))?\s+)?static\s+mut\s+(\w+)\s*:[^=]*=\s*(.*)$",
+            )
+            .unwrap(),
+            rust_function: Regex::new(r"\bfn\s+(\w+)").unwrap(),
+            lock_expression: Regex::new(r"(?i)lock|mutex|semaphore").unwrap(),
+            acquire_call: Regex::new(r"\.(?:acquire|lock|write|runExclusive)\s*\(").unwrap(),
+            instance_value: Regex::new(r"^(?:new\s+|await\s+)?[A-Za-z_][\w.:]*\s*[({]").unwrap(),
+        }
+    }
+
+    /// Analyze a file on disk for shared global state
+    pub fn analyze_file(&self, path: &Path) -> Result<Vec<GlobalState>> {
+        let content = std::fs::read_to_string(path)?;
+        let language = CommentedCodeAnalyzer::language_from_path(path);
+        self.analyze_content(&content, language, Some(&path.display().to_string()))
+    }
+
+    /// Find global state written from several functions, and lazily created singletons
+    pub fn analyze_content(
+        &self,
+        content: &str,
+        language: &str,
+        file_path: Option<&str>,
+    ) -> Result<Vec<GlobalState>> {
+        let declarations = match language {
+            "python" => self.python_writes(content),
+            "javascript" | "typescript" | "rust" => self.brace_writes(content, language),
+            _ => Vec::new(),
+        };
+
+        Ok(declarations
+            .into_iter()
+            .filter_map(|declaration| {
+                let state = GlobalState {
+                    file_path: file_path.map(|p| p.to_string()),
+                    kind: if declaration.lazily_created {
+                        GlobalStateKind::Singleton
+                    } else {
+                        GlobalStateKind::MutableGlobal
+                    },
+                    synchronized: declaration.sites.iter().all(|site| site.synchronized),
+                    name: declaration.name,
+                    line: declaration.line,
+                    write_sites: declaration.sites,
+                };
+                let shared = state.writers().len() > 1;
+                let singleton = state.kind == GlobalStateKind::Singleton;
+                (shared || singleton).then_some(state)
+            })
+            .collect())
+    }
+
+    fn python_writes(&self, content: &str) -> Vec<Declaration> {
+        let masked = mask_python(content);
+        let original: Vec<&str> = content.lines().collect();
+
+        let mut declarations: Vec<Declaration> = Vec::new();
+        for (index, line) in masked.lines().enumerate() {
+            let Some(captures) = self.python_module_assignment.captures(line) else {
+                continue;
+            };
+            let value = captures[2].trim();
+            if self.python_lock_constructor.is_match(value)
+                || declarations.iter().any(|d| d.name == captures[1])
+            {
+                continue;
+            }
+            declarations.push(Declaration::new(
+                &captures[1],
+                index + 1,
+                true,
+                value == "None",
+            ));
+        }
+        if declarations.is_empty() {
+            return declarations;
+        }
+
+        let mut functions: Vec<OpenFunction> = Vec::new();
+        let mut lock_blocks: Vec<usize> = Vec::new();
+        for (index, line) in masked.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let indent = indentation(line);
+            while functions.last().is_some_and(|f| indent <= f.depth) {
+                functions.pop();
+            }
+            while lock_blocks.last().is_some_and(|depth| indent <= *depth) {
+                lock_blocks.pop();
+            }
+
+            if let Some(captures) = self.python_def.captures(line) {
+                functions.push(OpenFunction::new(&captures[2], indent));
+                continue;
+            }
+            let Some(function) = functions.last_mut() else {
+                continue;
+            };
+
+            let trimmed = line.trim_start();
+            if let Some(names) = trimmed.strip_prefix("global ") {
+                function
+                    .globals
+                    .extend(names.split(',').map(|name| name.trim().to_string()));
+                continue;
+            }
+            if trimmed.starts_with("with ") && self.lock_expression.is_match(trimmed) {
+                lock_blocks.push(indent);
+                continue;
+            }
+            if self.acquire_call.is_match(trimmed) {
+                function.acquired = true;
+            }
+
+            let synchronized = !lock_blocks.is_empty() || function.acquired;
+            for declaration in &mut declarations {
+                let rebindable = function.globals.contains(&declaration.name);
+                if !rebindable && is_plain_assignment(trimmed, &declaration.name) {
+                    function.locals.insert(declaration.name.clone());
+                }
+                if function.locals.contains(&declaration.name) {
+                    continue;
+                }
+                if let Some(write) = write_kind(
+                    line,
+                    &declaration.name,
+                    rebindable,
+                    PYTHON_MUTATORS,
+                    &self.instance_value,
+                ) {
+                    record(declaration, write, function, index, synchronized, &original);
+                }
+            }
+        }
+
+        declarations
+    }
+
+    /// Writes in languages whose function bodies are delimited by braces
+    fn brace_writes(&self, content: &str, language: &str) -> Vec<Declaration> {
+        let masked = mask_strings_and_comments(content);
+        let original: Vec<&str> = content.lines().collect();
+        let (function_pattern, mutators) = if language == "rust" {
+            (&self.rust_function, RUST_MUTATORS)
+        } else {
+            (&self.js_function, JS_MUTATORS)
+        };
+
+        let mut declarations: Vec<Declaration> = Vec::new();
+        let mut depth = 0usize;
+        for (index, line) in masked.lines().enumerate() {
+            if depth == 0 {
+                if let Some(declaration) = self.brace_declaration(line, language, index) {
+                    declarations.push(declaration);
+                }
+            }
+            depth = (depth + line.matches('{').count()).saturating_sub(line.matches('}').count());
+        }
+        if declarations.is_empty() {
+            return declarations;
+        }
+
+        let mut functions: Vec<OpenFunction> = Vec::new();
+        let mut depth = 0usize;
+        for (index, line) in masked.lines().enumerate() {
+            let depth_before = depth;
+            depth = (depth + line.matches('{').count()).saturating_sub(line.matches('}').count());
+
+            let header = function_pattern.captures(line).and_then(|captures| {
+                let name = captures.iter().skip(1).flatten().next()?.as_str();
+                let keyword = matches!(name, "if" | "for" | "while" | "switch" | "catch");
+                (!keyword && line.contains('{')).then_some(name)
+            });
+            if let Some(name) = header {
+                functions.push(OpenFunction::new(name, depth_before + 1));
+            } else if let Some(function) = functions.last_mut() {
+                let trimmed = line.trim_start();
+                if self.acquire_call.is_match(trimmed) {
+                    function.acquired = true;
+                }
+                for declaration in &mut declarations {
+                    if declares_local(line, &declaration.name) {
+                        function.locals.insert(declaration.name.clone());
+                    }
+                    if function.locals.contains(&declaration.name) {
+                        continue;
+                    }
+                    if let Some(write) = write_kind(
+                        line,
+                        &declaration.name,
+                        declaration.rebindable,
+                        mutators,
+                        &self.instance_value,
+                    ) {
+                        let synchronized = function.acquired;
+                        record(declaration, write, function, index, synchronized, &original);
+                    }
+                }
+            }
+
+            while functions.last().is_some_and(|f| depth < f.depth) {
+                functions.pop();
+            }
+        }
+
+        declarations
+    }
+
+    fn brace_declaration(&self, line: &str, language: &str, index: usize) -> Option<Declaration> {
+        if language == "rust" {
+            let captures = self.rust_static_mut.captures(line)?;
+            let value = captures[2].trim();
+            return Some(Declaration::new(
+                &captures[1],
+                index + 1,
+                true,
+                value.starts_with("None"),
+            ));
+        }
+
+        let captures = self.js_module_declaration.captures(line)?;
+        let value = captures[3].trim();
+        let rebindable = &captures[1] != "const";
+        let container = ["{", "[", "new Map", "new Set", "new WeakMap", "new Array"]
+            .iter()
+            .any(|prefix| value.starts_with(prefix));
+        // Constants holding functions or scalars cannot change
+        if !rebindable && !container {
+            return None;
+        }
+        Some(Declaration::new(
+            &captures[2],
+            index + 1,
+            rebindable,
+            value.starts_with("null") || value.starts_with("undefined"),
+        ))
+    }
+
+    /// Get recommendations for the shared state found
+    pub fn get_recommendations(&self, states: &[GlobalState]) -> Vec<String> {
+        if states.is_empty() {
+            return Vec::new();
+        }
+
+        let unsynchronized: Vec<&str> = states
+            .iter()
+            .filter(|state| !state.synchronized)
+            .map(|state| state.name.as_str())
+            .collect();
+        let singletons = states
+            .iter()
+            .filter(|state| state.kind == GlobalStateKind::Singleton)
+            .count();
+
+        let mut recommendations = vec![format!(
+            "Found {} piece(s) of global state written from function bodies; prefer passing state explicitly",
+            states.len()
+        )];
+        if !unsynchronized.is_empty() {
+            recommendations.push(format!(
+                "Guard writes to {} with a lock, or confine them to a single owner",
+                unsynchronized.join(", ")
+            ));
+        }
+        if singletons > 0 {
+            recommendations.push(
+                "Create singletons eagerly or under a lock; check-then-assign can build two instances"
+                    .to_string(),
+            );
+        }
+        recommendations
+    }
+}
+
+impl Default for GlobalStateAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn record(
+    declaration: &mut Declaration,
+    write: Write,
+    function: &OpenFunction,
+    index: usize,
+    synchronized: bool,
+    original: &[&str],
+) {
+    if write == (Write::Assign { instance: true }) && declaration.starts_empty {
+        declaration.lazily_created = true;
+    }
+    declaration.sites.push(WriteSite {
+        function: function.name.clone(),
+        line: index + 1,
+        synchronized,
+        snippet: original
+            .get(index)
+            .map(|l| l.trim())
+            .unwrap_or("")
+            .to_string(),
+    });
+}
+
+/// How `line` writes to `name`, if it does
+fn write_kind(
+    line: &str,
+    name: &str,
+    rebindable: bool,
+    mutators: &[&str],
+    instance_value: &Regex,
+) -> Option<Write> {
+    mentions(line, name).find_map(|end| {
+        let before = line[..end - name.len()].trim_end();
+        let rest = line[end..].trim_start();
+
+        if before.ends_with("&mut") {
+            return Some(Write::Mutate);
+        }
+        if rebindable && (rest.starts_with("++") || rest.starts_with("--")) {
+            return Some(Write::Assign { instance: false });
+        }
+        if rebindable && !before.ends_with('(') && !before.ends_with(',') {
+            if let Some(value) = assigned_value(rest) {
+                return Some(Write::Assign {
+                    instance: instance_value.is_match(value.trim_start()),
+                });
+            }
+        }
+
+        if let Some(subscript) = rest.strip_prefix('[') {
+            let after = subscript.split_once(']')?.1;
+            let deleted = before.ends_with("del");
+            return (deleted || assigned_value(after.trim_start()).is_some())
+                .then_some(Write::Mutate);
+        }
+        if let Some(member_access) = rest.strip_prefix('.') {
+            let member: String = member_access
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            let after = member_access[member.len()..].trim_start();
+            let sets_attribute = assigned_value(after).is_some();
+            let mutates = after.starts_with('(') && mutators.contains(&member.as_str());
+            return (sets_attribute || mutates).then_some(Write::Mutate);
+        }
+        None
+    })
+}
+
+/// Value of an assignment (`= v` or an augmented `+= v`) at the start of `rest`
+fn assigned_value(rest: &str) -> Option<&str> {
+    const OPERATORS: &[&str] = &[
+        "**", "//", "<<", ">>", "??", "||", "&&", "+", "-", "*", "/", "%", "|", "&", "^",
+    ];
+    let rest = OPERATORS
+        .iter()
+        .find_map(|op| rest.strip_prefix(op))
+        .unwrap_or(rest);
+    let value = rest.strip_prefix('=')?;
+    (!value.starts_with('=')).then_some(value)
+}
+
+/// Whether a Python line binds `name` as a function local (`name = ...`)
+fn is_plain_assignment(trimmed: &str, name: &str) -> bool {
+    let Some(rest) = trimmed.strip_prefix(name) else {
+        return false;
+    };
+    let rest = match rest.chars().next() {
+        Some(c) if c.is_alphanumeric() || c == '_' => return false,
+        _ => rest.trim_start(),
+    };
+    let rest = match rest.strip_prefix(':') {
+        Some(annotated) => match annotated.split_once('=') {
+            Some((_, value)) => return !value.starts_with('='),
+            None => return false,
+        },
+        None => rest,
+    };
+    rest.strip_prefix('=')
+        .is_some_and(|value| !value.starts_with('='))
+}
+
+/// Whether a brace-language line declares a local named `name`
+fn declares_local(line: &str, name: &str) -> bool {
+    mentions(line, name).any(|end| {
+        let before = line[..end - name.len()].trim_end();
+        ["let", "let mut", "const", "var"]
+            .iter()
+            .any(|keyword| before.ends_with(keyword))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python_dict_written_from_two_functions_without_lock() {
+        let code = r#"
+import threading
+
+CACHE = {}
+MAX_ITEMS = 100
+_lock = threading.Lock()
+_client = None
+REGISTRY = {}
+
+def remember(key, value):
+    if len(CACHE) >= MAX_ITEMS:
+        CACHE.clear()
+    CACHE[key] = value
+
+def forget(key):
+    CACHE.pop(key, None)
+
+def limit():
+    return MAX_ITEMS * 2
+
+def local_only():
+    CACHE = {}
+    CACHE["x"] = 1
+    return CACHE
+
+def get_client():
+    global _client
+    if _client is None:
+        _client = Client()
+    return _client
+
+def register(name, handler):
+    with _lock:
+        REGISTRY[name] = handler
+
+def unregister(name):
+    _lock.acquire()
+    del REGISTRY[name]
+    _lock.release()
+"#;
+        let states = GlobalStateAnalyzer::new()
+            .analyze_content(code, "python", Some("state.py"))
+            .unwrap();
+        let names: Vec<&str> = states.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["CACHE", "_client", "REGISTRY"]);
+
+        let cache = &states[0];
+        assert_eq!(cache.kind, GlobalStateKind::MutableGlobal);
+        assert_eq!(cache.line, 4);
+        assert_eq!(cache.writers(), vec!["forget", "remember"]);
+        let lines: Vec<usize> = cache.write_sites.iter().map(|s| s.line).collect();
+        assert_eq!(lines, vec![12, 13, 16], "Local shadow is not a write");
+        assert!(!cache.synchronized);
+
+        assert_eq!(states[1].kind, GlobalStateKind::Singleton);
+        assert_eq!(states[1].write_sites.len(), 1);
+        assert!(states[2].synchronized, "Both writes hold the lock");
+    }
+
+    #[test]
+    fn test_javascript_module_state() {
+        let code = r#"
+const LIMIT = 5;
+const handlers = [];
+let counter = 0;
+let instance = null;
+
+function increment() {
+  if (counter < LIMIT) {
+    counter++;
+  }
+}
+
+const reset = () => {
+  counter = 0;
+};
+
+function register(handler) {
+  handlers.push(handler);
+}
+
+function getInstance() {
+  if (!instance) {
+    instance = new Service();
+  }
+  return instance;
+}
+
+function shadow() {
+  let counter = 10;
+  counter += 1;
+}
+"#;
+        let states = GlobalStateAnalyzer::new()
+            .analyze_content(code, "javascript", None)
+            .unwrap();
+        let names: Vec<&str> = states.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["counter", "instance"], "Got {states:?}");
+        assert_eq!(states[0].writers(), vec!["increment", "reset"]);
+        assert_eq!(states[1].kind, GlobalStateKind::Singleton);
+    }
+
+    #[test]
+    fn test_rust_static_mut_written_from_two_functions() {
+        let code = r#"
+static mut COUNTER: u32 = 0;
+static LIMIT: u32 = 10;
+static mut SEEN: Vec<u32> = Vec::new();
+
+fn bump() {
+    unsafe {
+        COUNTER += 1;
+        SEEN.push(COUNTER);
+    }
+}
+
+fn reset() {
+    let _guard = RESET_LOCK.lock().unwrap();
+    unsafe {
+        COUNTER = 0;
+    }
+}
+"#;
+        let states = GlobalStateAnalyzer::new()
+            .analyze_content(code, "rust", None)
+            .unwrap();
+        assert_eq!(states.len(), 1, "Got {states:?}");
+        assert_eq!(states[0].name, "COUNTER");
+        assert_eq!(states[0].writers(), vec!["bump", "reset"]);
+        assert!(!states[0].write_sites[0].synchronized);
+        assert!(states[0].write_sites[1].synchronized);
+        assert!(!states[0].synchronized);
+    }
+}
@@ -0,0 +1,1027 @@
+//! Import ordering and grouping conventions
+//!
+//! Top-level imports (Python, JavaScript/TypeScript, Rust) are classified as
+//! standard library, third-party or local. Third-party packages are recognized
+//! from the manifests and lockfiles of the nearest project directory, so a
+//! bare name that is not a declared dependency but exists in the project
+//! counts as local. Imports are checked in runs not interrupted by code: each
+//! run must list its groups in the configured order and, optionally, separate
+//! consecutive groups with a blank line.
+
+use crate::commented_code::CommentedCodeAnalyzer;
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Top-level Python standard library modules
+const PYTHON_STDLIB: &[&str] = &[
+    "__future__",
+    "abc",
+    "argparse",
+    "array",
+    "ast",
+    "asyncio",
+    "atexit",
+    "base64",
+    "bdb",
+    "binascii",
+    "bisect",
+    "builtins",
+    "bz2",
+    "calendar",
+    "cmath",
+    "cmd",
+    "code",
+    "codecs",
+    "collections",
+    "colorsys",
+    "concurrent",
+    "configparser",
+    "contextlib",
+    "contextvars",
+    "copy",
+    "copyreg",
+    "cProfile",
+    "csv",
+    "ctypes",
+    "curses",
+    "dataclasses",
+    "datetime",
+    "dbm",
+    "decimal",
+    "difflib",
+    "dis",
+    "doctest",
+    "email",
+    "encodings",
+    "enum",
+    "errno",
+    "faulthandler",
+    "fcntl",
+    "filecmp",
+    "fileinput",
+    "fnmatch",
+    "fractions",
+    "ftplib",
+    "functools",
+    "gc",
+    "getopt",
+    "getpass",
+    "gettext",
+    "glob",
+    "graphlib",
+    "grp",
+    "gzip",
+    "hashlib",
+    "heapq",
+    "hmac",
+    "html",
+    "http",
+    "imaplib",
+    "importlib",
+    "inspect",
+    "io",
+    "ipaddress",
+    "itertools",
+    "json",
+    "keyword",
+    "linecache",
+    "locale",
+    "logging",
+    "lzma",
+    "mailbox",
+    "marshal",
+    "math",
+    "mimetypes",
+    "mmap",
+    "multiprocessing",
+    "netrc",
+    "numbers",
+    "operator",
+    "optparse",
+    "os",
+    "pathlib",
+    "pdb",
+    "pickle",
+    "pkgutil",
+    "platform",
+    "plistlib",
+    "poplib",
+    "posixpath",
+    "pprint",
+    "profile",
+    "pstats",
+    "pty",
+    "pwd",
+    "py_compile",
+    "queue",
+    "quopri",
+    "random",
+    "re",
+    "readline",
+    "reprlib",
+    "resource",
+    "runpy",
+    "sched",
+    "secrets",
+    "select",
+    "selectors",
+    "shelve",
+    "shlex",
+    "shutil",
+    "signal",
+    "site",
+    "smtplib",
+    "socket",
+    "socketserver",
+    "sqlite3",
+    "ssl",
+    "stat",
+    "statistics",
+    "string",
+    "struct",
+    "subprocess",
+    "sys",
+    "sysconfig",
+    "syslog",
+    "tarfile",
+    "tempfile",
+    "termios",
+    "textwrap",
+    "threading",
+    "time",
+    "timeit",
+    "tkinter",
+    "token",
+    "tokenize",
+    "tomllib",
+    "trace",
+    "traceback",
+    "tracemalloc",
+    "tty",
+    "turtle",
+    "types",
+    "typing",
+    "unicodedata",
+    "unittest",
+    "urllib",
+    "uuid",
+    "venv",
+    "warnings",
+    "wave",
+    "weakref",
+    "webbrowser",
+    "winreg",
+    "wsgiref",
+    "xml",
+    "xmlrpc",
+    "zipapp",
+    "zipfile",
+    "zipimport",
+    "zlib",
+    "zoneinfo",
+];
+
+/// Node.js built-in modules, also importable with the `node:` scheme
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "timers",
+    "tls",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "worker_threads",
+    "zlib",
+];
+
+/// Crates shipped with the Rust toolchain
+const RUST_STDLIB: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
+
+/// Import group an import belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportGroup {
+    /// Standard library of the language
+    Stdlib,
+    /// Packages installed from a registry
+    ThirdParty,
+    /// Modules of the project itself
+    Local,
+}
+
+impl ImportGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportGroup::Stdlib => "stdlib",
+            ImportGroup::ThirdParty => "third_party",
+            ImportGroup::Local => "local",
+        }
+    }
+}
+
+impl std::str::FromStr for ImportGroup {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "stdlib" | "std" | "standard_library" => Ok(Self::Stdlib),
+            "third_party" | "third-party" | "external" => Ok(Self::ThirdParty),
+            "local" | "first_party" | "first-party" | "project" => Ok(Self::Local),
+            other => Err(anyhow::anyhow!(
+                "Unknown import group '{other}'. Expected one of: stdlib, third_party, local"
+            )),
+        }
+    }
+}
+
+/// Expected layout of import blocks
+#[derive(Debug, Clone)]
+pub struct ImportOrderConfig {
+    /// Groups in the order they must appear; unlisted groups go last
+    pub groups: Vec<ImportGroup>,
+    /// Whether consecutive groups must be separated by a blank line
+    pub require_separation: bool,
+}
+
+impl Default for ImportOrderConfig {
+    fn default() -> Self {
+        Self {
+            groups: vec![
+                ImportGroup::Stdlib,
+                ImportGroup::ThirdParty,
+                ImportGroup::Local,
+            ],
+            require_separation: true,
+        }
+    }
+}
+
+impl ImportOrderConfig {
+    fn rank(&self, group: ImportGroup) -> usize {
+        self.groups
+            .iter()
+            .position(|g| *g == group)
+            .unwrap_or(self.groups.len())
+    }
+
+    fn describe_order(&self) -> String {
+        self.groups
+            .iter()
+            .map(|group| group.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A classified import statement
+#[derive(Debug, Clone)]
+pub struct ImportStatement {
+    /// Line the statement starts on (1-based)
+    pub line: usize,
+    /// Imported module as written, e.g. `os.path` or `@scope/pkg`
+    pub module: String,
+    pub group: ImportGroup,
+}
+
+/// Kind of import layout violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportOrderIssueKind {
+    /// An import of an earlier group follows one of a later group
+    OutOfOrder,
+    /// Two different groups are not separated by a blank line
+    MissingSeparator,
+}
+
+impl ImportOrderIssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportOrderIssueKind::OutOfOrder => "out_of_order",
+            ImportOrderIssueKind::MissingSeparator => "missing_separator",
+        }
+    }
+}
+
+/// An import layout violation
+#[derive(Debug, Clone)]
+pub struct ImportOrderIssue {
+    pub kind: ImportOrderIssueKind,
+    /// Line of the offending import (1-based)
+    pub line: usize,
+    pub message: String,
+}
+
+/// Imports of one file and the violations found among them
+#[derive(Debug, Clone)]
+pub struct ImportOrderReport {
+    pub file_path: String,
+    pub imports: Vec<ImportStatement>,
+    pub issues: Vec<ImportOrderIssue>,
+}
+
+impl ImportOrderReport {
+    /// Whether the file follows the configured layout
+    pub fn is_ordered(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Packages declared by a project's manifests and lockfiles
+#[derive(Debug, Clone, Default)]
+pub struct ProjectDependencies {
+    /// Directory holding the manifests, if any were found
+    pub root: Option<PathBuf>,
+    /// Declared and locked package names, normalized
+    pub packages: HashSet<String>,
+    /// Names the project itself is published under, normalized
+    pub own_names: HashSet<String>,
+}
+
+impl ProjectDependencies {
+    /// Read the manifests of the nearest directory above `file` that has any
+    ///
+    /// The search stops at the repository root (a directory containing `.git`).
+    pub fn discover(file: &Path) -> Self {
+        for dir in file.ancestors().skip(1) {
+            let dependencies = Self::load(dir);
+            if dependencies.root.is_some() || dir.join(".git").exists() {
+                return dependencies;
+            }
+        }
+        Self::default()
+    }
+
+    /// Read the manifests and lockfiles in `dir`
+    pub fn load(dir: &Path) -> Self {
+        let mut dependencies = Self::default();
+        let mut found = false;
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return dependencies;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let parse: fn(&mut Self, &str) = match name.as_str() {
+                "pyproject.toml" => Self::parse_pyproject,
+                "package.json" => Self::parse_package_json,
+                "package-lock.json" => Self::parse_package_lock,
+                "Cargo.toml" => Self::parse_cargo_toml,
+                "Cargo.lock" | "poetry.lock" => Self::parse_toml_lock,
+                _ if name.starts_with("requirements") && name.ends_with(".txt") => {
+                    Self::parse_requirements
+                }
+                _ => continue,
+            };
+            if let Ok(content) = std::fs::read_to_string(entry.path()) {
+                parse(&mut dependencies, &content);
+                found = true;
+            }
+        }
+        if found {
+            dependencies.root = Some(dir.to_path_buf());
+        }
+        dependencies
+    }
+
+    /// Whether `name` is a declared or locked package
+    pub fn contains(&self, name: &str) -> bool {
+        self.packages.contains(&normalize(name))
+    }
+
+    fn add(&mut self, name: &str) {
+        let name = name.trim();
+        if !name.is_empty() {
+            self.packages.insert(normalize(name));
+        }
+    }
+
+    fn parse_requirements(&mut self, content: &str) {
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+                continue;
+            }
+            self.add(requirement_name(line));
+        }
+    }
+
+    fn parse_pyproject(&mut self, content: &str) {
+        let mut section = "";
+        let mut in_list = false;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('[') && !in_list {
+                section = line;
+                continue;
+            }
+            let quoted = quoted_strings(line);
+            if in_list {
+                for requirement in &quoted {
+                    self.add(requirement_name(requirement));
+                }
+                in_list = !strip_quoted(line).contains(']');
+            } else if section == "[project]" && line.starts_with("name") {
+                if let Some(name) = quoted.first() {
+                    self.own_names.insert(normalize(name));
+                }
+            } else if (section == "[project]" && line.starts_with("dependencies"))
+                || section == "[project.optional-dependencies]"
+            {
+                let Some((_, value)) = line.split_once('=') else {
+                    continue;
+                };
+                for requirement in quoted_strings(value) {
+                    self.add(requirement_name(&requirement));
+                }
+                in_list = value.contains('[') && !strip_quoted(value).contains(']');
+            } else if section.starts_with("[tool.poetry") && section.ends_with("dependencies]") {
+                if let Some((key, _)) = line.split_once('=') {
+                    let key = key.trim().trim_matches('"');
+                    if key != "python" {
+                        self.add(key);
+                    }
+                }
+            } else if section == "[tool.poetry]" && line.starts_with("name") {
+                if let Some(name) = quoted.first() {
+                    self.own_names.insert(normalize(name));
+                }
+            }
+        }
+    }
+
+    fn parse_package_json(&mut self, content: &str) {
+        let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+            return;
+        };
+        if let Some(name) = manifest.get("name").and_then(|name| name.as_str()) {
+            self.own_names.insert(normalize(name));
+        }
+        for field in [
+            "dependencies",
+            "devDependencies",
+            "peerDependencies",
+            "optionalDependencies",
+        ] {
+            if let Some(packages) = manifest.get(field).and_then(|value| value.as_object()) {
+                for name in packages.keys() {
+                    self.add(name);
+                }
+            }
+        }
+    }
+
+    fn parse_package_lock(&mut self, content: &str) {
+        let Ok(lock) = serde_json::from_str::<serde_json::Value>(content) else {
+            return;
+        };
+        for field in ["packages", "dependencies"] {
+            if let Some(packages) = lock.get(field).and_then(|value| value.as_object()) {
+                for key in packages.keys() {
+                    // `packages` keys are install paths like `node_modules/a/node_modules/b`
+                    let name = key.rsplit("node_modules/").next().unwrap_or(key);
+                    self.add(name);
+                }
+            }
+        }
+    }
+
+    fn parse_cargo_toml(&mut self, content: &str) {
+        let mut section = "";
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('[') {
+                section = line;
+                // `[dependencies.serde]` declares a dependency in its header
+                if let Some(name) = section
+                    .trim_matches(|c| c == '[' || c == ']')
+                    .split_once("dependencies.")
+                    .map(|(_, name)| name)
+                {
+                    self.add(name.trim_matches('"'));
+                }
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().trim_matches('"');
+            if section.ends_with("dependencies]") {
+                self.add(key);
+            } else if (section == "[package]" || section == "[lib]") && key == "name" {
+                self.own_names
+                    .insert(normalize(value.trim().trim_matches('"')));
+            }
+        }
+    }
+
+    fn parse_toml_lock(&mut self, content: &str) {
+        for line in content.lines() {
+            if let Some(value) = line.strip_prefix("name = ") {
+                self.add(value.trim().trim_matches('"'));
+            }
+        }
+    }
+}
+
+/// Package names compare case-insensitively with `-` and `.` treated as `_`
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace(['-', '.'], "_")
+}
+
+/// Package name at the start of a requirement specifier like `requests[socks]>=2`
+fn requirement_name(requirement: &str) -> &str {
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    &requirement[..end]
+}
+
+fn quoted_strings(text: &str) -> Vec<String> {
+    text.split('"')
+        .skip(1)
+        .step_by(2)
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn strip_quoted(text: &str) -> String {
+    text.split('"').step_by(2).collect()
+}
+
+/// Analyzer for import ordering and grouping
+pub struct ImportOrderAnalyzer {
+    python_import: Regex,
+    python_from: Regex,
+    js_specifier: Regex,
+    js_require: Regex,
+    rust_use: Regex,
+}
+
+impl ImportOrderAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            python_import: Regex::new(r"^import\s+([\w.]+)").unwrap(),
+            python_from: Regex::new(r"^from\s+(\.*[\w.]*)\s+import\b").unwrap(),
+            js_specifier: Regex::new(r#"(?:\bfrom\s*|^import\s*|\brequire\(\s*)['"]([^'"]+)['"]"#)
+                .unwrap(),
+            js_require: Regex::new(r"^(?:const|let|var)\s+[^=]+=\s*require\(").unwrap(),
+            rust_use: Regex::new(r"^(?:pub(?:\([^)]*\))?\s+)?use\s+(?:::)?(\w+)").unwrap(),
+        }
+    }
+
+    /// Analyze a file on disk, classifying imports with its project's dependencies
+    pub fn analyze_file(
+        &self,
+        path: &Path,
+        config: &ImportOrderConfig,
+    ) -> Result<ImportOrderReport> {
+        let dependencies = ProjectDependencies::discover(path);
+        self.analyze_file_with(path, &dependencies, config)
+    }
+
+    /// Analyze a file on disk against already loaded project dependencies
+    pub fn analyze_file_with(
+        &self,
+        path: &Path,
+        dependencies: &ProjectDependencies,
+        config: &ImportOrderConfig,
+    ) -> Result<ImportOrderReport> {
+        let content = std::fs::read_to_string(path)?;
+        let language = CommentedCodeAnalyzer::language_from_path(path);
+        Ok(self.analyze_content(&content, language, path, dependencies, config))
+    }
+
+    /// Classify the top-level imports of `content` and check their layout
+    pub fn analyze_content(
+        &self,
+        content: &str,
+        language: &str,
+        file_path: &Path,
+        dependencies: &ProjectDependencies,
+        config: &ImportOrderConfig,
+    ) -> ImportOrderReport {
+        let parsed = self.parse_imports(content, language);
+        let imports: Vec<ImportStatement> = parsed
+            .iter()
+            .map(|import| ImportStatement {
+                line: import.line,
+                module: import.module.clone(),
+                group: classify(&import.module, language, file_path, dependencies),
+            })
+            .collect();
+
+        let mut issues = Vec::new();
+        for (pair, statements) in parsed.windows(2).zip(imports.windows(2)) {
+            let (before, after) = (&statements[0], &statements[1]);
+            if pair[0].run != pair[1].run || before.group == after.group {
+                continue;
+            }
+            if config.rank(after.group) < config.rank(before.group) {
+                issues.push(ImportOrderIssue {
+                    kind: ImportOrderIssueKind::OutOfOrder,
+                    line: after.line,
+                    message: format!(
+                        "{} import '{}' follows {} import '{}'; expected order: {}",
+                        after.group.as_str(),
+                        after.module,
+                        before.group.as_str(),
+                        before.module,
+                        config.describe_order()
+                    ),
+                });
+            } else if config.require_separation && !pair[1].after_blank_line {
+                issues.push(ImportOrderIssue {
+                    kind: ImportOrderIssueKind::MissingSeparator,
+                    line: after.line,
+                    message: format!(
+                        "{} imports start without a blank line after {} imports",
+                        after.group.as_str(),
+                        before.group.as_str()
+                    ),
+                });
+            }
+        }
+
+        ImportOrderReport {
+            file_path: file_path.display().to_string(),
+            imports,
+            issues,
+        }
+    }
+
+    /// Get recommendations for the reports of the analyzed files
+    pub fn get_recommendations(
+        &self,
+        reports: &[ImportOrderReport],
+        config: &ImportOrderConfig,
+    ) -> Vec<String> {
+        let issues = || reports.iter().flat_map(|report| &report.issues);
+        let mut recommendations = Vec::new();
+        if issues().any(|issue| issue.kind == ImportOrderIssueKind::OutOfOrder) {
+            recommendations.push(format!(
+                "Order imports by group: {}",
+                config.describe_order()
+            ));
+        }
+        if issues().any(|issue| issue.kind == ImportOrderIssueKind::MissingSeparator) {
+            recommendations.push("Separate import groups with a blank line".to_string());
+        }
+        if !recommendations.is_empty() {
+            recommendations.push(
+                "Enforce the layout with a formatter such as isort, ruff, eslint-plugin-import or rustfmt's group_imports"
+                    .to_string(),
+            );
+        }
+        recommendations
+    }
+
+    /// Top-level import statements, grouped into runs not interrupted by code
+    fn parse_imports(&self, content: &str, language: &str) -> Vec<ParsedImport> {
+        let comment_prefixes: &[&str] = match language {
+            "python" => &["#"],
+            "javascript" | "typescript" => &["//", "/*", "*"],
+            "rust" => &["//", "/*", "*", "#["],
+            _ => return Vec::new(),
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut imports = Vec::new();
+        let mut run = 0;
+        let mut after_blank_line = false;
+        let mut index = 0;
+        while index < lines.len() {
+            let line = lines[index];
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                after_blank_line = true;
+                index += 1;
+                continue;
+            }
+            if comment_prefixes
+                .iter()
+                .any(|prefix| trimmed.starts_with(prefix))
+            {
+                index += 1;
+                continue;
+            }
+
+            match self.parse_statement(&lines, index, language) {
+                Some((module, end)) => {
+                    imports.push(ParsedImport {
+                        line: index + 1,
+                        module,
+                        run,
+                        after_blank_line,
+                    });
+                    index = end + 1;
+                }
+                None => {
+                    run += 1;
+                    index += 1;
+                }
+            }
+            after_blank_line = false;
+        }
+        imports
+    }
+
+    /// Module imported by the statement starting at `start`, and its last line
+    fn parse_statement(
+        &self,
+        lines: &[&str],
+        start: usize,
+        language: &str,
+    ) -> Option<(String, usize)> {
+        let line = lines[start];
+        match language {
+            "python" => {
+                let module = self
+                    .python_from
+                    .captures(line)
+                    .or_else(|| self.python_import.captures(line))?[1]
+                    .to_string();
+                let mut end = start;
+                if line.contains('(') && !line.contains(')') {
+                    while end + 1 < lines.len() && !lines[end].contains(')') {
+                        end += 1;
+                    }
+                } else {
+                    while end + 1 < lines.len() && lines[end].trim_end().ends_with('\\') {
+                        end += 1;
+                    }
+                }
+                Some((module, end))
+            }
+            "javascript" | "typescript" => {
+                let is_import = line.starts_with("import ")
+                    || line.starts_with("import{")
+                    || line.starts_with("import'")
+                    || line.starts_with("import\"");
+                if !is_import && !self.js_require.is_match(line) {
+                    return None;
+                }
+                let mut statement = String::new();
+                for (end, text) in lines.iter().enumerate().skip(start).take(50) {
+                    statement.push_str(text);
+                    statement.push('\n');
+                    if let Some(captures) = self.js_specifier.captures(&statement) {
+                        return Some((captures[1].to_string(), end));
+                    }
+                }
+                None
+            }
+            "rust" => {
+                let module = self.rust_use.captures(line)?[1].to_string();
+                let end = (start..lines.len())
+                    .find(|&end| lines[end].contains(';'))
+                    .unwrap_or(start);
+                Some((module, end))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for ImportOrderAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Import statement before classification
+#[derive(Debug)]
+struct ParsedImport {
+    line: usize,
+    module: String,
+    /// Index of the run of imports this statement belongs to
+    run: usize,
+    /// Whether a blank line separates it from the previous statement
+    after_blank_line: bool,
+}
+
+fn classify(
+    module: &str,
+    language: &str,
+    file_path: &Path,
+    dependencies: &ProjectDependencies,
+) -> ImportGroup {
+    let exists_locally = |name: &str| {
+        let mut dirs: Vec<&Path> = file_path.parent().into_iter().collect();
+        if let Some(root) = &dependencies.root {
+            dirs.push(root);
+        }
+        let candidates: &[&str] = match language {
+            "python" => &["", ".py", "/__init__.py"],
+            _ => &["", ".js", ".ts", ".jsx", ".tsx"],
+        };
+        dirs.iter().any(|dir| {
+            [dir.to_path_buf(), dir.join("src")].iter().any(|base| {
+                candidates
+                    .iter()
+                    .any(|suffix| base.join(format!("{name}{suffix}")).exists())
+            })
+        })
+    };
+
+    match language {
+        "python" => {
+            if module.starts_with('.') {
+                return ImportGroup::Local;
+            }
+            let top = module.split('.').next().unwrap_or(module);
+            if PYTHON_STDLIB.contains(&top) {
+                ImportGroup::Stdlib
+            } else if dependencies.contains(top) {
+                ImportGroup::ThirdParty
+            } else if dependencies.own_names.contains(&normalize(top)) || exists_locally(top) {
+                ImportGroup::Local
+            } else {
+                ImportGroup::ThirdParty
+            }
+        }
+        "javascript" | "typescript" => {
+            if module.starts_with('.')
+                || module.starts_with('/')
+                || module.starts_with("@/")
+                || module.starts_with("~/")
+                || module.starts_with('#')
+            {
+                return ImportGroup::Local;
+            }
+            let mut segments = module.split('/');
+            let first = segments.next().unwrap_or(module);
+            if module.starts_with("node:") || NODE_BUILTINS.contains(&first) {
+                return ImportGroup::Stdlib;
+            }
+            let package = match (first.starts_with('@'), segments.next()) {
+                (true, Some(name)) => format!("{first}/{name}"),
+                _ => first.to_string(),
+            };
+            if dependencies.contains(&package) {
+                ImportGroup::ThirdParty
+            } else if dependencies.own_names.contains(&normalize(&package)) || exists_locally(first)
+            {
+                ImportGroup::Local
+            } else {
+                ImportGroup::ThirdParty
+            }
+        }
+        "rust" => {
+            if RUST_STDLIB.contains(&module) {
+                ImportGroup::Stdlib
+            } else if matches!(module, "crate" | "self" | "super")
+                || dependencies.own_names.contains(&normalize(module))
+            {
+                ImportGroup::Local
+            } else if dependencies.contains(module) || dependencies.root.is_none() {
+                ImportGroup::ThirdParty
+            } else {
+                // Paths not naming a declared crate resolve to items of this crate
+                ImportGroup::Local
+            }
+        }
+        _ => ImportGroup::ThirdParty,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_project(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_local_import_before_stdlib_is_flagged() {
+        let project = write_project(&[
+            ("requirements.txt", "requests>=2.31\n"),
+            ("myapp/__init__.py", ""),
+            ("myapp/models.py", "class User: ...\n"),
+            (
+                "main.py",
+                "\"\"\"Entry point.\"\"\"\nfrom myapp.models import User\nimport os\nimport requests\n\n\ndef main():\n    pass\n",
+            ),
+        ]);
+        let analyzer = ImportOrderAnalyzer::new();
+        let report = analyzer
+            .analyze_file(
+                &project.path().join("main.py"),
+                &ImportOrderConfig::default(),
+            )
+            .unwrap();
+
+        let groups: Vec<(&str, ImportGroup)> = report
+            .imports
+            .iter()
+            .map(|import| (import.module.as_str(), import.group))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("myapp.models", ImportGroup::Local),
+                ("os", ImportGroup::Stdlib),
+                ("requests", ImportGroup::ThirdParty),
+            ]
+        );
+        assert!(!report.is_ordered());
+        assert_eq!(report.issues[0].kind, ImportOrderIssueKind::OutOfOrder);
+        assert_eq!(report.issues[0].line, 3);
+        assert_eq!(
+            report.issues[1].kind,
+            ImportOrderIssueKind::MissingSeparator
+        );
+        assert_eq!(report.issues[1].line, 4);
+    }
+
+    #[test]
+    fn test_correctly_ordered_file_is_not_flagged() {
+        let project = write_project(&[
+            ("pyproject.toml", "[project]\nname = \"myapp\"\ndependencies = [\n    \"requests[socks]>=2\",\n    \"Flask\",\n]\n"),
+            ("myapp/__init__.py", ""),
+            (
+                "myapp/views.py",
+                "import os\nimport sys\n\nimport requests\nfrom flask import Flask\n\nfrom myapp import models\nfrom . import (\n    utils,\n)\n\nos.getcwd()\nimport json\n",
+            ),
+        ]);
+        let analyzer = ImportOrderAnalyzer::new();
+        let config = ImportOrderConfig::default();
+        let report = analyzer
+            .analyze_file(&project.path().join("myapp/views.py"), &config)
+            .unwrap();
+
+        assert_eq!(report.imports.len(), 7);
+        assert!(report.is_ordered(), "{:?}", report.issues);
+        assert!(analyzer.get_recommendations(&[report], &config).is_empty());
+    }
+
+    #[test]
+    fn test_rust_paths_outside_declared_crates_are_local() {
+        let project = write_project(&[(
+            "Cargo.toml",
+            "[package]\nname = \"my-tool\"\n\n[dependencies]\nserde = \"1\"\n\n[dependencies.tokio]\nversion = \"1\"\n",
+        )]);
+        let content = "use std::fs;\nuse serde::Serialize;\nuse tokio::sync::Mutex;\n\nuse my_tool::config;\nuse parser::Token;\n";
+        let dependencies = ProjectDependencies::load(project.path());
+        let report = ImportOrderAnalyzer::new().analyze_content(
+            content,
+            "rust",
+            &project.path().join("src/main.rs"),
+            &dependencies,
+            &ImportOrderConfig::default(),
+        );
+
+        let groups: Vec<ImportGroup> = report.imports.iter().map(|i| i.group).collect();
+        assert_eq!(
+            groups,
+            vec![
+                ImportGroup::Stdlib,
+                ImportGroup::ThirdParty,
+                ImportGroup::ThirdParty,
+                ImportGroup::Local,
+                ImportGroup::Local,
+            ]
+        );
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(
+            report.issues[0].kind,
+            ImportOrderIssueKind::MissingSeparator
+        );
+        assert_eq!(report.issues[0].line, 2);
+    }
+}
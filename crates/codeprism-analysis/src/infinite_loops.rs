@@ -0,0 +1,701 @@
+//! Potential infinite loop detection module
+//!
+//! Flags loops whose condition can never become false on its own (`while True`,
+//! `loop {}`, `for(;;)`, Go's bare `for {}`) and whose body has no exit path:
+//! no `break` targeting the loop, no `return`, no raised exception or process
+//! exit. `while` loops over a plain variable are also flagged when the body
+//! neither exits nor touches the variable.
+
+use crate::commented_code::CommentedCodeAnalyzer;
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A loop that appears to have no way to terminate
+#[derive(Debug, Clone)]
+pub struct InfiniteLoop {
+    pub file_path: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub loop_kind: String,
+    pub reason: String,
+    pub snippet: String,
+}
+
+/// Exit constructs recognised for a language
+#[derive(Debug, Clone)]
+struct ExitRules {
+    /// Statements that leave the enclosing loop (only when not nested in another breakable)
+    breaks: Regex,
+    /// Statements that leave the loop from any depth (return, raise, labelled break, ...)
+    exits: Vec<Regex>,
+    /// Constructs that capture an unlabelled `break`
+    breakables: Regex,
+}
+
+/// Analyzer that flags loops without an exit path
+pub struct InfiniteLoopAnalyzer {
+    loop_patterns: HashMap<String, Vec<(Regex, &'static str)>>,
+    conditional_loops: HashMap<String, Regex>,
+    exit_rules: HashMap<String, ExitRules>,
+}
+
+impl InfiniteLoopAnalyzer {
+    pub fn new() -> Self {
+        let mut analyzer = Self {
+            loop_patterns: HashMap::new(),
+            conditional_loops: HashMap::new(),
+            exit_rules: HashMap::new(),
+        };
+        analyzer.initialize_patterns();
+        analyzer
+    }
+
+    fn initialize_patterns(&mut self) {
+        let c_like_loops = vec![
+            (Regex::new(r"\bfor\s*\(\s*;\s*;\s*\)").unwrap(), "for(;;)"),
+            (
+                Regex::new(r"\bwhile\s*\(\s*(true|1)\s*\)").unwrap(),
+                "while (true)",
+            ),
+        ];
+        let c_like_exits = ExitRules {
+            breaks: Regex::new(r"\bbreak\s*;").unwrap(),
+            exits: vec![
+                Regex::new(r"\breturn\b").unwrap(),
+                Regex::new(r"\bthrow\b").unwrap(),
+                Regex::new(r"\bgoto\b").unwrap(),
+                Regex::new(r"\b(exit|abort|_exit)\s*\(").unwrap(),
+                Regex::new(r"\bprocess\.exit\s*\(").unwrap(),
+                Regex::new(r"\bSystem\.exit\s*\(").unwrap(),
+                Regex::new(r"\bbreak\s+[A-Za-z_]\w*\s*;").unwrap(),
+            ],
+            breakables: Regex::new(r"\b(for|while|do|switch)\b").unwrap(),
+        };
+
+        let c_like_conditional =
+            Regex::new(r"\bwhile\s*\(\s*(!\s*)?([A-Za-z_][\w.]*)\s*\)\s*\{").unwrap();
+
+        for language in ["javascript", "typescript", "java", "c", "cpp"] {
+            self.loop_patterns
+                .insert(language.to_string(), c_like_loops.clone());
+            self.exit_rules
+                .insert(language.to_string(), c_like_exits.clone());
+            self.conditional_loops
+                .insert(language.to_string(), c_like_conditional.clone());
+        }
+
+        self.loop_patterns.insert(
+            "rust".to_string(),
+            vec![
+                (Regex::new(r"\bloop\s*\{").unwrap(), "loop"),
+                (Regex::new(r"\bwhile\s+true\s*\{").unwrap(), "while true"),
+            ],
+        );
+        self.exit_rules.insert(
+            "rust".to_string(),
+            ExitRules {
+                breaks: Regex::new(r"\bbreak\b").unwrap(),
+                exits: vec![
+                    Regex::new(r"\breturn\b").unwrap(),
+                    Regex::new(r"\?\s*;").unwrap(),
+                    Regex::new(r"\b(panic|unreachable|todo|unimplemented)!").unwrap(),
+                    Regex::new(r"\bprocess::exit\s*\(").unwrap(),
+                    Regex::new(r"\bbreak\s+'\w+").unwrap(),
+                ],
+                breakables: Regex::new(r"\b(for|while|loop)\b").unwrap(),
+            },
+        );
+        self.conditional_loops.insert(
+            "rust".to_string(),
+            Regex::new(r"\bwhile\s+(!\s*)?([A-Za-z_][\w.]*)\s*\{").unwrap(),
+        );
+
+        self.loop_patterns.insert(
+            "go".to_string(),
+            vec![
+                (Regex::new(r"\bfor\s*\{").unwrap(), "for {}"),
+                (Regex::new(r"\bfor\s+true\s*\{").unwrap(), "for true"),
+                (Regex::new(r"\bfor\s*;\s*;\s*\{").unwrap(), "for ;;"),
+            ],
+        );
+        self.exit_rules.insert(
+            "go".to_string(),
+            ExitRules {
+                breaks: Regex::new(r"\bbreak\s*($|;|\})").unwrap(),
+                exits: vec![
+                    Regex::new(r"\breturn\b").unwrap(),
+                    Regex::new(r"\bpanic\s*\(").unwrap(),
+                    Regex::new(r"\bgoto\b").unwrap(),
+                    Regex::new(r"\bos\.Exit\s*\(").unwrap(),
+                    Regex::new(r"\blog\.Fatal\w*\s*\(").unwrap(),
+                    Regex::new(r"\bbreak\s+[A-Za-z_]\w*").unwrap(),
+                ],
+                breakables: Regex::new(r"\b(for|switch|select)\b").unwrap(),
+            },
+        );
+        self.conditional_loops.insert(
+            "go".to_string(),
+            Regex::new(r"\bfor\s+(!\s*)?([A-Za-z_][\w.]*)\s*\{").unwrap(),
+        );
+
+        self.loop_patterns.insert(
+            "python".to_string(),
+            vec![(
+                Regex::new(r"^\s*while\s+(True|1)\s*:").unwrap(),
+                "while True",
+            )],
+        );
+        self.exit_rules.insert(
+            "python".to_string(),
+            ExitRules {
+                breaks: Regex::new(r"^\s*break\b").unwrap(),
+                exits: vec![
+                    Regex::new(r"^\s*return\b").unwrap(),
+                    Regex::new(r"^\s*raise\b").unwrap(),
+                    Regex::new(r"\b(sys\.exit|os\._exit|exit|quit)\s*\(").unwrap(),
+                ],
+                breakables: Regex::new(r"^\s*(for|while)\b").unwrap(),
+            },
+        );
+        self.conditional_loops.insert(
+            "python".to_string(),
+            Regex::new(r"^\s*while\s+(not\s+)?([A-Za-z_][\w.]*)\s*:").unwrap(),
+        );
+    }
+
+    /// Analyze a file on disk for potential infinite loops
+    pub fn analyze_file(&self, path: &Path) -> Result<Vec<InfiniteLoop>> {
+        let content = std::fs::read_to_string(path)?;
+        let language = CommentedCodeAnalyzer::language_from_path(path);
+        self.analyze_content(&content, language, Some(&path.display().to_string()))
+    }
+
+    /// Find loops in `content` that have no exit path
+    pub fn analyze_content(
+        &self,
+        content: &str,
+        language: &str,
+        file_path: Option<&str>,
+    ) -> Result<Vec<InfiniteLoop>> {
+        let (Some(patterns), Some(rules)) = (
+            self.loop_patterns.get(language),
+            self.exit_rules.get(language),
+        ) else {
+            return Ok(Vec::new());
+        };
+
+        let loops = if language == "python" {
+            self.python_loops(content, patterns, rules)
+        } else {
+            self.brace_loops(content, language, patterns, rules)
+        };
+
+        Ok(loops
+            .into_iter()
+            .map(|mut found| {
+                found.file_path = file_path.map(|p| p.to_string());
+                found
+            })
+            .collect())
+    }
+
+    /// Detect loops in indentation-delimited Python code
+    fn python_loops(
+        &self,
+        content: &str,
+        patterns: &[(Regex, &'static str)],
+        rules: &ExitRules,
+    ) -> Vec<InfiniteLoop> {
+        let lines: Vec<&str> = content.lines().collect();
+        let conditional = self.conditional_loops.get("python");
+        let mut found = Vec::new();
+
+        for (index, line) in lines.iter().enumerate() {
+            let constant_kind = patterns
+                .iter()
+                .find(|(pattern, _)| pattern.is_match(line))
+                .map(|(_, kind)| *kind);
+            let condition_var = match constant_kind {
+                Some(_) => None,
+                None => match conditional.and_then(|c| c.captures(line)) {
+                    Some(captures) if !matches!(&captures[2], "True" | "False" | "None") => {
+                        Some(captures[2].to_string())
+                    }
+                    _ => continue,
+                },
+            };
+
+            let indent = indentation(line);
+            let mut body: Vec<&str> = Vec::new();
+            // One-line loops such as `while True: pass`
+            let inline = line
+                .split_once(':')
+                .map(|(_, rest)| rest.trim())
+                .unwrap_or("");
+            if !inline.is_empty() && !inline.starts_with('#') {
+                body.push(inline);
+            }
+            let mut end = index;
+            for (offset, body_line) in lines.iter().enumerate().skip(index + 1) {
+                let trimmed = body_line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    continue;
+                }
+                if indentation(body_line) <= indent {
+                    break;
+                }
+                body.push(body_line);
+                end = offset;
+            }
+
+            let mut has_exit = false;
+            let mut nested_loop_indent: Option<usize> = None;
+            for body_line in &body {
+                let code = body_line.split('#').next().unwrap_or("");
+                let line_indent = indentation(code);
+                if nested_loop_indent.is_some_and(|nested| line_indent <= nested) {
+                    nested_loop_indent = None;
+                }
+                if rules.exits.iter().any(|exit| exit.is_match(code))
+                    || (nested_loop_indent.is_none() && rules.breaks.is_match(code))
+                {
+                    has_exit = true;
+                    break;
+                }
+                if nested_loop_indent.is_none() && rules.breakables.is_match(code) {
+                    nested_loop_indent = Some(line_indent);
+                }
+            }
+
+            if has_exit {
+                continue;
+            }
+
+            let kind = constant_kind.unwrap_or("while <variable>");
+            if let Some(var) = &condition_var {
+                if is_mutated(var, &body.join("\n")) {
+                    continue;
+                }
+            }
+
+            found.push(InfiniteLoop {
+                file_path: None,
+                start_line: index + 1,
+                end_line: end + 1,
+                loop_kind: kind.to_string(),
+                reason: exit_reason(condition_var.as_deref()),
+                snippet: line.trim().to_string(),
+            });
+        }
+
+        found
+    }
+
+    /// Detect loops in brace-delimited languages
+    fn brace_loops(
+        &self,
+        content: &str,
+        language: &str,
+        patterns: &[(Regex, &'static str)],
+        rules: &ExitRules,
+    ) -> Vec<InfiniteLoop> {
+        let masked = mask_strings_and_comments(content);
+        let mut candidates: Vec<(usize, &'static str, Option<String>)> = Vec::new();
+
+        for (pattern, kind) in patterns {
+            for m in pattern.find_iter(&masked) {
+                candidates.push((m.start(), kind, None));
+            }
+        }
+        if let Some(conditional) = self.conditional_loops.get(language) {
+            for captures in conditional.captures_iter(&masked) {
+                let whole = captures.get(0).unwrap();
+                let var = captures[2].to_string();
+                if matches!(var.as_str(), "true" | "false" | "1" | "0") {
+                    continue;
+                }
+                candidates.push((whole.start(), "while <variable>", Some(var)));
+            }
+        }
+        candidates.sort_by_key(|(start, _, _)| *start);
+        candidates.dedup_by_key(|(start, _, _)| *start);
+
+        let mut found = Vec::new();
+        for (start, kind, condition_var) in candidates {
+            let Some(open) = masked[start..].find('{').map(|pos| start + pos) else {
+                continue;
+            };
+            let Some(close) = matching_brace(&masked, open) else {
+                continue;
+            };
+            let body = &masked[open + 1..close];
+
+            if has_exit(body, rules) {
+                continue;
+            }
+            if let Some(var) = &condition_var {
+                if is_mutated(var, body) {
+                    continue;
+                }
+            }
+
+            found.push(InfiniteLoop {
+                file_path: None,
+                start_line: line_of(content, start),
+                end_line: line_of(content, close),
+                loop_kind: kind.to_string(),
+                reason: exit_reason(condition_var.as_deref()),
+                snippet: content[start..]
+                    .lines()
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .to_string(),
+            });
+        }
+
+        found
+    }
+
+    /// Get recommendations for the detected loops
+    pub fn get_recommendations(&self, loops: &[InfiniteLoop]) -> Vec<String> {
+        let mut recommendations = Vec::new();
+
+        if loops.is_empty() {
+            return recommendations;
+        }
+
+        recommendations.push(format!(
+            "Found {} loop(s) without an exit path; add a break condition or make the loop bound explicit",
+            loops.len()
+        ));
+
+        if loops.iter().any(|l| l.loop_kind == "while <variable>") {
+            recommendations.push(
+                "Loop conditions that are never updated in the body only change through other threads or aliases; document that or update the condition in the loop"
+                    .to_string(),
+            );
+        }
+
+        recommendations.push(
+            "Intentional event loops and servers should exit on a shutdown signal so they can be stopped cleanly"
+                .to_string(),
+        );
+
+        recommendations
+    }
+}
+
+impl Default for InfiniteLoopAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+fn exit_reason(condition_var: Option<&str>) -> String {
+    match condition_var {
+        Some(var) => format!("loop condition '{var}' is never updated and the body has no exit"),
+        None => "constant loop condition and no break, return or exit in the body".to_string(),
+    }
+}
+
+/// Whether the body assigns to, or calls a method on, the condition variable
+fn is_mutated(var: &str, body: &str) -> bool {
+    let escaped = regex::escape(var);
+    let root = regex::escape(var.split('.').next().unwrap_or(var));
+    let assignment = Regex::new(&format!(
+        r"(^|[^\w.]){escaped}\s*(=[^=]|\+=|-=|\*=|/=|\|=|&=|\+\+|--)|(\+\+|--){escaped}\b"
+    ))
+    .unwrap();
+    let method_call = Regex::new(&format!(r"(^|[^\w.]){root}\s*\.\s*\w+\s*\(")).unwrap();
+    let passed_by_reference = Regex::new(&format!(r"&\s*(mut\s+)?{root}\b")).unwrap();
+
+    assignment.is_match(body) || method_call.is_match(body) || passed_by_reference.is_match(body)
+}
+
+/// Check a brace-delimited loop body for an exit that targets this loop
+fn has_exit(body: &str, rules: &ExitRules) -> bool {
+    if rules.exits.iter().any(|exit| exit.is_match(body)) {
+        return true;
+    }
+
+    // An unlabelled break only leaves this loop when it is not nested inside
+    // another loop or switch
+    let bytes = body.as_bytes();
+    let mut stack: Vec<bool> = Vec::new();
+    let mut segment_start = 0;
+    // Loop headers may contain `;` (`for (i = 0; i < n; i++)`), so they extend back
+    // to the previous brace rather than the previous statement
+    let mut block_start = 0;
+
+    for (index, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'{' => {
+                let header = &body[block_start..index];
+                stack.push(rules.breakables.is_match(header));
+                segment_start = index + 1;
+                block_start = index + 1;
+            }
+            b'}' => {
+                stack.pop();
+                segment_start = index + 1;
+                block_start = index + 1;
+            }
+            b';' => {
+                let statement = &body[segment_start..=index];
+                if !stack.iter().any(|breakable| *breakable) && rules.breaks.is_match(statement) {
+                    return true;
+                }
+                segment_start = index + 1;
+            }
+            b'\n' => {
+                // Newline-terminated statements (Rust, Go)
+                let statement = &body[segment_start..index];
+                if !stack.iter().any(|breakable| *breakable) && rules.breaks.is_match(statement) {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    !stack.iter().any(|breakable| *breakable) && rules.breaks.is_match(&body[segment_start..])
+}
+
+/// Find the index of the brace closing the one at `open`
+pub(crate) fn matching_brace(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, byte) in text.bytes().enumerate().skip(open) {
+        match byte {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Replace string literal contents and comments with spaces, keeping offsets and newlines
+pub(crate) fn mask_strings_and_comments(content: &str) -> String {
+    let bytes = content.as_bytes();
+    let mut masked = bytes.to_vec();
+    let mut index = 0;
+
+    let blank = |masked: &mut Vec<u8>, from: usize, to: usize| {
+        for byte in &mut masked[from..to] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' | b'`' => {
+                let quote = bytes[index];
+                let start = index + 1;
+                index += 1;
+                while index < bytes.len() && bytes[index] != quote {
+                    if bytes[index] == b'\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+                let end = index.min(bytes.len());
+                blank(&mut masked, start, end);
+                index += 1;
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'/') => {
+                let start = index;
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+                blank(&mut masked, start, index);
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                let start = index;
+                index += 2;
+                while index + 1 < bytes.len() && !(bytes[index] == b'*' && bytes[index + 1] == b'/')
+                {
+                    index += 1;
+                }
+                index = (index + 2).min(bytes.len());
+                blank(&mut masked, start, index);
+            }
+            _ => index += 1,
+        }
+    }
+
+    // Blanked ranges start and end at ASCII delimiters, so the result stays valid UTF-8
+    String::from_utf8(masked).unwrap_or_else(|_| content.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_python_while_true_without_break() {
+        let analyzer = InfiniteLoopAnalyzer::new();
+        let code = r#"
+def serve():
+    while True:
+        request = accept()
+        handle(request)
+"#;
+
+        let loops = analyzer.analyze_content(code, "python", None).unwrap();
+        assert_eq!(loops.len(), 1, "Loop without break should be flagged");
+        assert_eq!(loops[0].start_line, 3);
+        assert_eq!(loops[0].loop_kind, "while True");
+    }
+
+    #[test]
+    fn test_python_conditional_break_not_flagged() {
+        let analyzer = InfiniteLoopAnalyzer::new();
+        let code = r#"
+def serve():
+    while True:
+        request = accept()
+        if request is None:
+            break
+        handle(request)
+"#;
+
+        let loops = analyzer.analyze_content(code, "python", None).unwrap();
+        assert!(loops.is_empty(), "Conditional break is an exit path");
+    }
+
+    #[test]
+    fn test_break_in_nested_loop_does_not_count() {
+        let analyzer = InfiniteLoopAnalyzer::new();
+        let python = r#"
+while True:
+    for item in items:
+        if item:
+            break
+"#;
+        assert_eq!(
+            analyzer
+                .analyze_content(python, "python", None)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let rust = r#"
+fn run() {
+    loop {
+        for item in items() {
+            if item { break; }
+        }
+    }
+}
+"#;
+        assert_eq!(
+            analyzer.analyze_content(rust, "rust", None).unwrap().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_rust_and_c_like_exits() {
+        let analyzer = InfiniteLoopAnalyzer::new();
+        let rust = r#"
+fn next_token(lexer: &mut Lexer) -> Token {
+    loop {
+        let c = lexer.bump()?;
+        if c.is_alphanumeric() {
+            return Token::Ident;
+        }
+    }
+}
+"#;
+        assert!(analyzer
+            .analyze_content(rust, "rust", None)
+            .unwrap()
+            .is_empty());
+
+        let js = r#"
+function poll() {
+    for (;;) {
+        // break; would stop polling
+        tick();
+    }
+}
+"#;
+        let loops = analyzer.analyze_content(js, "javascript", None).unwrap();
+        assert_eq!(loops.len(), 1, "Commented-out break is not an exit");
+        assert_eq!(loops[0].loop_kind, "for(;;)");
+
+        let java = r#"
+while (true) {
+    switch (state) {
+        case DONE: break;
+    }
+}
+"#;
+        assert_eq!(
+            analyzer.analyze_content(java, "java", None).unwrap().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_unmodified_condition_variable() {
+        let analyzer = InfiniteLoopAnalyzer::new();
+        let stuck = r#"
+while running:
+    process()
+"#;
+        let loops = analyzer.analyze_content(stuck, "python", None).unwrap();
+        assert_eq!(loops.len(), 1);
+        assert!(loops[0].reason.contains("running"));
+
+        let updated = r#"
+while running:
+    running = process()
+"#;
+        assert!(analyzer
+            .analyze_content(updated, "python", None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_go_bare_for() {
+        let analyzer = InfiniteLoopAnalyzer::new();
+        let code = r#"
+func worker(jobs chan int) {
+	for {
+		select {
+		case j := <-jobs:
+			process(j)
+			break
+		}
+	}
+}
+"#;
+        let loops = analyzer.analyze_content(code, "go", None).unwrap();
+        assert_eq!(
+            loops.len(),
+            1,
+            "break inside select does not leave the loop"
+        );
+    }
+}
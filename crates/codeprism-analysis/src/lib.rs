@@ -1,17 +1,57 @@
 //! Language-agnostic code analysis tools for CodePrism
 
+pub mod api_leaks;
+pub mod api_stability;
 pub mod api_surface;
+pub mod churn;
+pub mod commented_code;
 pub mod complexity;
+pub mod coupling;
+#[cfg(feature = "coverage")]
+pub mod coverage;
+pub mod data_clumps;
 pub mod duplicates;
+pub mod error_style;
+pub mod global_state;
+pub mod import_order;
+pub mod infinite_loops;
+pub mod local_variables;
+pub mod null_safety;
+pub mod orphans;
 pub mod performance;
+pub mod review;
 pub mod security;
 pub mod semantic;
+pub mod similarity;
+pub mod tech_debt;
+pub mod type_coverage;
+pub mod whitespace;
 
+pub use api_leaks::ApiLeakAnalyzer;
+pub use api_stability::ApiStabilityAnalyzer;
 pub use api_surface::ApiSurfaceAnalyzer;
+pub use churn::ChurnAnalyzer;
+pub use commented_code::CommentedCodeAnalyzer;
 pub use complexity::ComplexityAnalyzer;
-pub use duplicates::DuplicateAnalyzer;
+pub use coupling::LogicalCouplingAnalyzer;
+#[cfg(feature = "coverage")]
+pub use coverage::CoverageOverlay;
+pub use data_clumps::DataClumpAnalyzer;
+pub use duplicates::{DuplicateAnalyzer, DuplicateConfig};
+pub use error_style::ErrorStyleAnalyzer;
+pub use global_state::GlobalStateAnalyzer;
+pub use import_order::ImportOrderAnalyzer;
+pub use infinite_loops::InfiniteLoopAnalyzer;
+pub use local_variables::LocalVariableAnalyzer;
+pub use null_safety::NullSafetyAnalyzer;
+pub use orphans::OrphanAnalyzer;
 pub use performance::PerformanceAnalyzer;
-pub use security::SecurityAnalyzer;
+pub use review::DiffMap;
+pub use security::{SecretScanConfig, SecurityAnalyzer};
+pub use similarity::StructuralHasher;
+pub use tech_debt::TechDebtAnalyzer;
+pub use type_coverage::TypeCoverageAnalyzer;
+pub use whitespace::WhitespaceAnalyzer;
 
 // Remove unused imports
 
@@ -22,6 +62,22 @@ pub struct CodeAnalyzer {
     pub security: SecurityAnalyzer,
     pub performance: PerformanceAnalyzer,
     pub api_surface: ApiSurfaceAnalyzer,
+    pub api_stability: ApiStabilityAnalyzer,
+    pub api_leaks: ApiLeakAnalyzer,
+    pub commented_code: CommentedCodeAnalyzer,
+    pub infinite_loops: InfiniteLoopAnalyzer,
+    pub churn: ChurnAnalyzer,
+    pub null_safety: NullSafetyAnalyzer,
+    pub global_state: GlobalStateAnalyzer,
+    pub import_order: ImportOrderAnalyzer,
+    pub orphans: OrphanAnalyzer,
+    pub data_clumps: DataClumpAnalyzer,
+    pub tech_debt: TechDebtAnalyzer,
+    pub error_style: ErrorStyleAnalyzer,
+    pub coupling: LogicalCouplingAnalyzer,
+    pub type_coverage: TypeCoverageAnalyzer,
+    pub local_variables: LocalVariableAnalyzer,
+    pub whitespace: WhitespaceAnalyzer,
 }
 
 impl CodeAnalyzer {
@@ -32,6 +88,22 @@ impl CodeAnalyzer {
             security: SecurityAnalyzer::new(),
             performance: PerformanceAnalyzer::new(),
             api_surface: ApiSurfaceAnalyzer::new(),
+            api_stability: ApiStabilityAnalyzer::new(),
+            api_leaks: ApiLeakAnalyzer::new(),
+            commented_code: CommentedCodeAnalyzer::new(),
+            infinite_loops: InfiniteLoopAnalyzer::new(),
+            churn: ChurnAnalyzer::new(),
+            null_safety: NullSafetyAnalyzer::new(),
+            global_state: GlobalStateAnalyzer::new(),
+            import_order: ImportOrderAnalyzer::new(),
+            orphans: OrphanAnalyzer::new(),
+            data_clumps: DataClumpAnalyzer::new(),
+            tech_debt: TechDebtAnalyzer::new(),
+            error_style: ErrorStyleAnalyzer::new(),
+            coupling: LogicalCouplingAnalyzer::new(),
+            type_coverage: TypeCoverageAnalyzer::new(),
+            local_variables: LocalVariableAnalyzer::new(),
+            whitespace: WhitespaceAnalyzer::new(),
         }
     }
 }
@@ -0,0 +1,154 @@
+//! Local variable count analysis
+//!
+//! A function juggling many locals is hard to follow and usually does more
+//! than one thing. Locals are the `Variable` nodes whose span lies inside a
+//! function's span; a variable inside a nested function counts only towards
+//! the innermost one.
+
+use codeprism_core::{Node, NodeKind};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// A function declaring more distinct locals than the threshold
+#[derive(Debug, Clone)]
+pub struct VariableHeavyFunction {
+    pub function: Node,
+    /// Number of distinct local variable names
+    pub variable_count: usize,
+    /// Local variable names, sorted
+    pub variables: Vec<String>,
+}
+
+/// Counts distinct local variables per function
+pub struct LocalVariableAnalyzer;
+
+impl LocalVariableAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Distinct local variable names of each function, keyed by function index
+    ///
+    /// Repeated assignments to one name produce several variable nodes, so
+    /// locals are counted by name.
+    pub fn count_locals(&self, functions: &[Node], variables: &[Node]) -> Vec<BTreeSet<String>> {
+        let mut by_file: HashMap<&Path, Vec<usize>> = HashMap::new();
+        for (index, function) in functions.iter().enumerate() {
+            if matches!(function.kind, NodeKind::Function | NodeKind::Method) {
+                by_file.entry(&function.file).or_default().push(index);
+            }
+        }
+
+        let mut locals = vec![BTreeSet::new(); functions.len()];
+        for variable in variables
+            .iter()
+            .filter(|node| node.kind == NodeKind::Variable)
+        {
+            let Some(candidates) = by_file.get(variable.file.as_path()) else {
+                continue;
+            };
+            let innermost = candidates
+                .iter()
+                .filter(|&&index| {
+                    let span = &functions[index].span;
+                    span.start_byte <= variable.span.start_byte
+                        && variable.span.end_byte <= span.end_byte
+                })
+                .min_by_key(|&&index| {
+                    let span = &functions[index].span;
+                    span.end_byte - span.start_byte
+                });
+            if let Some(&index) = innermost {
+                locals[index].insert(variable.name.clone());
+            }
+        }
+        locals
+    }
+
+    /// Functions with more than `threshold` distinct locals, most first
+    pub fn find_variable_heavy(
+        &self,
+        functions: &[Node],
+        variables: &[Node],
+        threshold: usize,
+    ) -> Vec<VariableHeavyFunction> {
+        let mut heavy: Vec<VariableHeavyFunction> = self
+            .count_locals(functions, variables)
+            .into_iter()
+            .zip(functions)
+            .filter(|(names, _)| names.len() > threshold)
+            .map(|(names, function)| VariableHeavyFunction {
+                function: function.clone(),
+                variable_count: names.len(),
+                variables: names.into_iter().collect(),
+            })
+            .collect();
+        heavy.sort_by(|a, b| {
+            b.variable_count.cmp(&a.variable_count).then_with(|| {
+                (&a.function.file, a.function.span.start_byte)
+                    .cmp(&(&b.function.file, b.function.span.start_byte))
+            })
+        });
+        heavy
+    }
+}
+
+impl Default for LocalVariableAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::{Language, Span};
+    use std::path::PathBuf;
+
+    fn node(kind: NodeKind, name: &str, start: usize, end: usize) -> Node {
+        Node::new(
+            "repo",
+            kind,
+            name.to_string(),
+            Language::Python,
+            PathBuf::from("app.py"),
+            Span::new(start, end, 1, 1, 1, 1),
+        )
+    }
+
+    #[test]
+    fn test_flags_function_with_many_locals_only() {
+        let functions = vec![
+            node(NodeKind::Function, "heavy", 0, 500),
+            node(NodeKind::Function, "nested", 300, 400),
+            node(NodeKind::Function, "lean", 600, 700),
+        ];
+        let mut variables: Vec<Node> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| node(NodeKind::Variable, name, 10 + i * 10, 11 + i * 10))
+            .collect();
+        // A reassignment does not add a local
+        variables.push(node(NodeKind::Variable, "a", 200, 201));
+        // Locals of the nested function are not counted towards `heavy`
+        variables.push(node(NodeKind::Variable, "inner", 310, 315));
+        variables.push(node(NodeKind::Variable, "total", 610, 615));
+        // Module-level variables belong to no function
+        variables.push(node(NodeKind::Variable, "CONFIG", 550, 556));
+
+        let analyzer = LocalVariableAnalyzer::new();
+        let heavy = analyzer.find_variable_heavy(&functions, &variables, 3);
+
+        assert_eq!(heavy.len(), 1);
+        assert_eq!(heavy[0].function.name, "heavy");
+        assert_eq!(heavy[0].variable_count, 5);
+        assert_eq!(heavy[0].variables, vec!["a", "b", "c", "d", "e"]);
+
+        let counts: Vec<usize> = analyzer
+            .count_locals(&functions, &variables)
+            .iter()
+            .map(BTreeSet::len)
+            .collect();
+        assert_eq!(counts, vec![5, 1, 1]);
+    }
+}
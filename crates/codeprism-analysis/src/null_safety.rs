@@ -0,0 +1,586 @@
+//! Missing null/None check detection module
+//!
+//! First collects the functions in a file that can return `None` (Python) or
+//! `null`/`undefined` (JavaScript/TypeScript): functions annotated as optional
+//! and functions that mix value returns with empty ones. A member access on a
+//! local assigned from such a call is flagged when nothing between the
+//! assignment and the access checks the variable. Any mention of the variable
+//! in a condition, assertion or boolean expression counts as a check, which
+//! keeps the false-positive rate low at the cost of missing some bugs.
+
+use crate::commented_code::CommentedCodeAnalyzer;
+use crate::infinite_loops::mask_strings_and_comments;
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A member access on a value that may be `None`/`null`/`undefined`
+#[derive(Debug, Clone)]
+pub struct NullableAccess {
+    pub file_path: Option<String>,
+    /// Line of the unguarded access (1-based)
+    pub line: usize,
+    /// Line of the assignment from the optional-returning call (1-based)
+    pub assignment_line: usize,
+    pub variable: String,
+    pub member: String,
+    pub source_function: String,
+    /// Why `source_function` is considered to return an optional value
+    pub reason: String,
+    pub snippet: String,
+}
+
+/// Analyzer that flags member access on possibly-missing values
+pub struct NullSafetyAnalyzer {
+    python_def: Regex,
+    python_optional_annotation: Regex,
+    python_return: Regex,
+    python_assignment: Regex,
+    python_or_default: Regex,
+    js_function: Regex,
+    js_optional_annotation: Regex,
+    js_return: Regex,
+    js_assignment: Regex,
+}
+
+/// Return statements seen in a function body
+#[derive(Debug, Default)]
+struct ReturnCounts {
+    empty: usize,
+    value: usize,
+}
+
+impl NullSafetyAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            python_def: Regex::new(r"^(\s*)(?:async\s+)?def\s+(\w+)\s*\(").unwrap(),
+            python_optional_annotation: Regex::new(
+                r"->\s*(?:(?:typing\.)?Optional\[|[^:]*\|\s*None\b|None\s*\|)",
+            )
+            .unwrap(),
+            python_return: Regex::new(r"^\s*return\b\s*(.*?)\s*$").unwrap(),
+            python_assignment: Regex::new(
+                r"^(\s*)(\w+)\s*=\s*(?:await\s+)?(?:(?:self|cls)\.)?(\w+)\s*\(",
+            )
+            .unwrap(),
+            python_or_default: Regex::new(r"\)\s*or\b").unwrap(),
+            js_function: Regex::new(
+                r"(?:\bfunction\s+(\w+)\s*\(|\b(?:const|let|var)\s+(\w+)\s*=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*(?::[^=]*)?=>|\w+\s*=>))",
+            )
+            .unwrap(),
+            js_optional_annotation: Regex::new(r"\)\s*:\s*[^{=]*\|\s*(?:null|undefined)\b")
+                .unwrap(),
+            js_return: Regex::new(r"\breturn\b\s*([^;}]*)").unwrap(),
+            js_assignment: Regex::new(
+                r"\b(?:const|let|var)\s+(\w+)\s*(?::[^=]+)?=\s*(?:await\s+)?(?:this\.)?(\w+)\s*\(",
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Analyze a file on disk for missing null checks
+    pub fn analyze_file(&self, path: &Path) -> Result<Vec<NullableAccess>> {
+        let content = std::fs::read_to_string(path)?;
+        let language = CommentedCodeAnalyzer::language_from_path(path);
+        self.analyze_content(&content, language, Some(&path.display().to_string()))
+    }
+
+    /// Find unguarded member access on values from optional-returning calls
+    pub fn analyze_content(
+        &self,
+        content: &str,
+        language: &str,
+        file_path: Option<&str>,
+    ) -> Result<Vec<NullableAccess>> {
+        let found = match language {
+            "python" => {
+                let masked = mask_python(content);
+                let functions = self.python_optional_functions(&masked);
+                self.python_accesses(content, &masked, &functions)
+            }
+            "javascript" | "typescript" => {
+                let masked = mask_strings_and_comments(content);
+                let functions = self.js_optional_functions(&masked);
+                self.js_accesses(content, &masked, &functions)
+            }
+            _ => Vec::new(),
+        };
+
+        Ok(found
+            .into_iter()
+            .map(|mut access| {
+                access.file_path = file_path.map(|p| p.to_string());
+                access
+            })
+            .collect())
+    }
+
+    /// Names of Python functions that may return `None`, with the reason
+    fn python_optional_functions(&self, masked: &str) -> HashMap<String, String> {
+        let mut optional = HashMap::new();
+        // Open functions as (indent, name, annotated optional, returns)
+        let mut open: Vec<(usize, String, bool, ReturnCounts)> = Vec::new();
+
+        let mut close = |function: (usize, String, bool, ReturnCounts)| {
+            let (_, name, annotated, returns) = function;
+            if annotated {
+                optional.insert(name, "return type is annotated as optional".to_string());
+            } else if returns.empty > 0 && returns.value > 0 {
+                optional.insert(
+                    name,
+                    "returns None on some paths and a value on others".to_string(),
+                );
+            }
+        };
+
+        for line in masked.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let indent = indentation(line);
+            while open.last().is_some_and(|(depth, ..)| indent <= *depth) {
+                close(open.pop().unwrap());
+            }
+
+            if let Some(captures) = self.python_def.captures(line) {
+                open.push((
+                    indent,
+                    captures[2].to_string(),
+                    self.python_optional_annotation.is_match(line),
+                    ReturnCounts::default(),
+                ));
+            } else if let Some(captures) = self.python_return.captures(line) {
+                if let Some((.., returns)) = open.last_mut() {
+                    match &captures[1] {
+                        "" | "None" => returns.empty += 1,
+                        _ => returns.value += 1,
+                    }
+                }
+            }
+        }
+        for function in open.into_iter().rev() {
+            close(function);
+        }
+
+        optional
+    }
+
+    /// Names of JavaScript/TypeScript functions that may return `null`/`undefined`
+    fn js_optional_functions(&self, masked: &str) -> HashMap<String, String> {
+        let mut optional = HashMap::new();
+        // Open functions as (brace depth of the body, name, annotated optional, returns)
+        let mut open: Vec<(usize, String, bool, ReturnCounts)> = Vec::new();
+        let mut pending: Option<(String, bool)> = None;
+        let mut depth = 0usize;
+
+        for line in masked.lines() {
+            if let Some(captures) = self.js_function.captures(line) {
+                let name = captures.get(1).or(captures.get(2)).unwrap().as_str();
+                pending = Some((name.to_string(), self.js_optional_annotation.is_match(line)));
+            }
+
+            if let Some((.., returns)) = open.last_mut() {
+                for captures in self.js_return.captures_iter(line) {
+                    match captures[1].trim() {
+                        "" | "null" | "undefined" => returns.empty += 1,
+                        _ => returns.value += 1,
+                    }
+                }
+            }
+
+            for c in line.chars() {
+                match c {
+                    '{' => {
+                        depth += 1;
+                        if let Some((name, annotated)) = pending.take() {
+                            open.push((depth, name, annotated, ReturnCounts::default()));
+                        }
+                    }
+                    '}' => {
+                        if open.last().is_some_and(|(body, ..)| *body == depth) {
+                            let (_, name, annotated, returns) = open.pop().unwrap();
+                            if annotated {
+                                optional.insert(
+                                    name,
+                                    "return type includes null or undefined".to_string(),
+                                );
+                            } else if returns.empty > 0 && returns.value > 0 {
+                                optional.insert(
+                                    name,
+                                    "returns null or undefined on some paths and a value on others"
+                                        .to_string(),
+                                );
+                            }
+                        }
+                        depth = depth.saturating_sub(1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        optional
+    }
+
+    fn python_accesses(
+        &self,
+        content: &str,
+        masked: &str,
+        functions: &HashMap<String, String>,
+    ) -> Vec<NullableAccess> {
+        let original: Vec<&str> = content.lines().collect();
+        let lines: Vec<&str> = masked.lines().collect();
+        let mut found = Vec::new();
+
+        for (index, line) in lines.iter().enumerate() {
+            let Some(captures) = self.python_assignment.captures(line) else {
+                continue;
+            };
+            let variable = &captures[2];
+            let function = &captures[3];
+            let Some(reason) = functions.get(function) else {
+                continue;
+            };
+            // `x = find() or default` never holds None
+            if self.python_or_default.is_match(line) {
+                continue;
+            }
+
+            let scope_indent = captures[1].len();
+            let reassigned = Regex::new(&format!(r"^\s*{variable}\s*=[^=]")).unwrap();
+            for (offset, next) in lines[index + 1..].iter().enumerate() {
+                if next.trim().is_empty() {
+                    continue;
+                }
+                if indentation(next) < scope_indent || reassigned.is_match(next) {
+                    break;
+                }
+                if is_guard(next, variable, PYTHON_GUARDS) {
+                    break;
+                }
+                if let Some(member) = member_access(next, variable) {
+                    let line_index = index + 1 + offset;
+                    found.push(NullableAccess {
+                        file_path: None,
+                        line: line_index + 1,
+                        assignment_line: index + 1,
+                        variable: variable.to_string(),
+                        member,
+                        source_function: function.to_string(),
+                        reason: reason.clone(),
+                        snippet: original.get(line_index).unwrap_or(next).trim().to_string(),
+                    });
+                    break;
+                }
+            }
+        }
+
+        found
+    }
+
+    fn js_accesses(
+        &self,
+        content: &str,
+        masked: &str,
+        functions: &HashMap<String, String>,
+    ) -> Vec<NullableAccess> {
+        let original: Vec<&str> = content.lines().collect();
+        let lines: Vec<&str> = masked.lines().collect();
+        let mut found = Vec::new();
+
+        for (index, line) in lines.iter().enumerate() {
+            let Some(captures) = self.js_assignment.captures(line) else {
+                continue;
+            };
+            let variable = &captures[1];
+            let function = &captures[2];
+            let Some(reason) = functions.get(function) else {
+                continue;
+            };
+            // `const x = find() ?? fallback` never holds null
+            if line.contains("??") || line.contains("||") {
+                continue;
+            }
+
+            let reassigned = Regex::new(&format!(r"(?:^|[^.\w]){variable}\s*=[^=]")).unwrap();
+            let mut depth: isize = 0;
+            for (offset, next) in lines[index + 1..].iter().enumerate() {
+                if is_guard(next, variable, JS_GUARDS) || reassigned.is_match(next) {
+                    break;
+                }
+                if let Some(member) = member_access(next, variable) {
+                    let line_index = index + 1 + offset;
+                    found.push(NullableAccess {
+                        file_path: None,
+                        line: line_index + 1,
+                        assignment_line: index + 1,
+                        variable: variable.to_string(),
+                        member,
+                        source_function: function.to_string(),
+                        reason: reason.clone(),
+                        snippet: original.get(line_index).unwrap_or(next).trim().to_string(),
+                    });
+                    break;
+                }
+
+                depth += next.matches('{').count() as isize;
+                depth -= next.matches('}').count() as isize;
+                if depth < 0 {
+                    break;
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Get recommendations for the detected accesses
+    pub fn get_recommendations(&self, accesses: &[NullableAccess]) -> Vec<String> {
+        if accesses.is_empty() {
+            return Vec::new();
+        }
+
+        let mut functions: Vec<&str> = accesses
+            .iter()
+            .map(|access| access.source_function.as_str())
+            .collect();
+        functions.sort_unstable();
+        functions.dedup();
+
+        vec![
+            format!(
+                "Found {} member access(es) on values that may be missing; check the value before using it",
+                accesses.len()
+            ),
+            format!(
+                "Callers of {} must handle the empty result, or the function should raise instead of returning nothing",
+                functions.join(", ")
+            ),
+        ]
+    }
+}
+
+impl Default for NullSafetyAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keywords and operators that make a bare mention of a variable a check
+const PYTHON_GUARDS: &[&str] = &[
+    "if ",
+    "elif ",
+    "while ",
+    "assert ",
+    " and ",
+    " or ",
+    "not ",
+    " is ",
+    "==",
+    "!=",
+    "isinstance(",
+];
+const JS_GUARDS: &[&str] = &[
+    "if ", "if(", "while ", "while(", "&&", "||", "??", "?", "==", "!=", "typeof ", "assert",
+];
+
+/// Whether `line` checks `variable` for presence
+///
+/// The variable has to appear on its own (not as `variable.member`) in a
+/// line containing one of the `guards` keywords or operators.
+fn is_guard(line: &str, variable: &str, guards: &[&str]) -> bool {
+    let has_bare_mention = mentions(line, variable).any(|end| {
+        !matches!(
+            line[end..].trim_start().chars().next(),
+            Some('.') | Some('(') | Some('[') | Some('=')
+        ) || line[end..].trim_start().starts_with("==")
+    });
+    has_bare_mention && guards.iter().any(|guard| line.contains(guard))
+}
+
+/// Member name of the first `variable.member` access in `line`
+fn member_access(line: &str, variable: &str) -> Option<String> {
+    mentions(line, variable).find_map(|end| {
+        let rest = line[end..].strip_prefix('.')?;
+        let member: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        (!member.is_empty()).then_some(member)
+    })
+}
+
+/// End offsets of standalone occurrences of `variable` in `line`
+///
+/// Occurrences that are themselves members (`self.variable`) are skipped.
+pub(crate) fn mentions<'a>(line: &'a str, variable: &'a str) -> impl Iterator<Item = usize> + 'a {
+    line.match_indices(variable).filter_map(move |(start, _)| {
+        let end = start + variable.len();
+        let before = line[..start].chars().next_back();
+        let after = line[end..].chars().next();
+        let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        (!is_word(before) && !is_word(after) && before != Some('.')).then_some(end)
+    })
+}
+
+pub(crate) fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Blank out Python string contents and comments, keeping line structure
+pub(crate) fn mask_python(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut masked = String::with_capacity(content.len());
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        if c == '#' {
+            while index < chars.len() && chars[index] != '\n' {
+                masked.push(' ');
+                index += 1;
+            }
+        } else if c == '"' || c == '\'' {
+            let triple = chars.get(index + 1) == Some(&c) && chars.get(index + 2) == Some(&c);
+            let quote_len = if triple { 3 } else { 1 };
+            masked.extend(std::iter::repeat_n(c, quote_len));
+            index += quote_len;
+            while index < chars.len() {
+                if chars[index] == '\\' {
+                    masked.push(' ');
+                    index += 1;
+                } else {
+                    let closes = chars[index] == c
+                        && (!triple
+                            || (chars.get(index + 1) == Some(&c)
+                                && chars.get(index + 2) == Some(&c)));
+                    if closes || (chars[index] == '\n' && !triple) {
+                        break;
+                    }
+                }
+                if index < chars.len() {
+                    masked.push(if chars[index] == '\n' { '\n' } else { ' ' });
+                    index += 1;
+                }
+            }
+            if index < chars.len() && chars[index] == c {
+                masked.extend(std::iter::repeat_n(c, quote_len));
+                index += quote_len;
+            }
+        } else {
+            masked.push(c);
+            index += 1;
+        }
+    }
+
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_unguarded_access_on_optional_python_result() {
+        let code = r#"
+def find_user(users, name):
+    for user in users:
+        if user.name == name:
+            return user
+    return None
+
+def greet(users):
+    user = find_user(users, "bob")
+    print("hello")
+    return user.email
+"#;
+        let accesses = NullSafetyAnalyzer::new()
+            .analyze_content(code, "python", Some("app.py"))
+            .unwrap();
+        assert_eq!(accesses.len(), 1, "Should flag the unguarded access");
+        assert_eq!(accesses[0].variable, "user");
+        assert_eq!(accesses[0].member, "email");
+        assert_eq!(accesses[0].source_function, "find_user");
+        assert_eq!(accesses[0].line, 11);
+        assert_eq!(accesses[0].assignment_line, 9);
+    }
+
+    #[test]
+    fn test_guarded_python_access_not_flagged() {
+        let code = r#"
+from typing import Optional
+
+def load(path) -> Optional[dict]:
+    ...
+
+def first(items):
+    if items:
+        return items[0]
+    return
+
+def use_guard():
+    config = load("a.toml")
+    if config is None:
+        raise ValueError("missing")
+    return config.get("key")
+
+def use_and():
+    item = first([])
+    return item and item.name
+
+def use_default():
+    item = first([]) or Item()
+    return item.name
+
+def always_returns(x):
+    return x
+
+def unrelated():
+    value = always_returns(1)
+    return value.real
+"#;
+        let accesses = NullSafetyAnalyzer::new()
+            .analyze_content(code, "python", None)
+            .unwrap();
+        assert!(accesses.is_empty(), "Guarded access flagged: {accesses:?}");
+    }
+
+    #[test]
+    fn test_javascript_optional_chaining_and_guards() {
+        let code = r#"
+function findOrder(orders, id) {
+  for (const order of orders) {
+    if (order.id === id) {
+      return order;
+    }
+  }
+  return null;
+}
+
+function total(orders) {
+  const order = findOrder(orders, 1);
+  return order.amount;
+}
+
+function safeTotal(orders) {
+  const order = findOrder(orders, 1);
+  if (!order) {
+    return 0;
+  }
+  return order.amount;
+}
+
+function chained(orders) {
+  const order = findOrder(orders, 2);
+  return order?.amount;
+}
+"#;
+        let accesses = NullSafetyAnalyzer::new()
+            .analyze_content(code, "javascript", None)
+            .unwrap();
+        assert_eq!(accesses.len(), 1, "Only the unguarded access: {accesses:?}");
+        assert_eq!(accesses[0].line, 13);
+        assert_eq!(accesses[0].member, "amount");
+    }
+}
@@ -0,0 +1,142 @@
+//! Orphan symbol detection
+//!
+//! An orphan is a function, method or class that is private, unreachable
+//! from any entry point and referenced by nothing. Unused public API is a
+//! separate concern, since callers may live outside the repository; orphans
+//! are safe deletion candidates.
+
+use crate::api_surface::ApiSurfaceAnalyzer;
+use codeprism_core::{EdgeKind, GraphQuery, GraphStore, Language, Node, NodeId, NodeKind};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A private symbol nothing reaches or refers to
+#[derive(Debug, Clone)]
+pub struct Orphan {
+    pub node: Node,
+}
+
+/// Combines export status, reachability and reference counts to find orphans
+pub struct OrphanAnalyzer {
+    api_surface: ApiSurfaceAnalyzer,
+}
+
+impl OrphanAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            api_surface: ApiSurfaceAnalyzer::new(),
+        }
+    }
+
+    /// Find orphans in `graph`, treating `roots` as entry points
+    pub fn find_orphans(&self, graph: &Arc<GraphStore>, roots: &[NodeId]) -> Vec<Orphan> {
+        let query = GraphQuery::new(Arc::clone(graph));
+        let unreachable: HashSet<NodeId> = query.find_unreachable(roots).into_iter().collect();
+
+        let mut orphans: Vec<Orphan> = [NodeKind::Function, NodeKind::Method, NodeKind::Class]
+            .into_iter()
+            .flat_map(|kind| graph.get_nodes_by_kind(kind))
+            .filter(|node| unreachable.contains(&node.id))
+            .filter(|node| !self.is_exported(node))
+            .filter(|node| reference_count(&query, graph, node) == 0)
+            .map(|node| Orphan { node })
+            .collect();
+        orphans.sort_by(|a, b| {
+            (&a.node.file, a.node.span.start_byte).cmp(&(&b.node.file, b.node.span.start_byte))
+        });
+        orphans
+    }
+
+    /// Whether a symbol is visible outside its module
+    ///
+    /// Parser metadata wins when present. JavaScript and TypeScript symbols
+    /// are only exported when marked so; other languages without visibility
+    /// metadata fall back to the API surface naming conventions.
+    pub fn is_exported(&self, node: &Node) -> bool {
+        if node.metadata["exported"] == true {
+            return true;
+        }
+        if let Some(visibility) = node.metadata["visibility"].as_str() {
+            return matches!(visibility, "pub" | "public");
+        }
+        match node.lang {
+            Language::JavaScript | Language::TypeScript => false,
+            _ => self.api_surface.is_public_api_element(&node.name),
+        }
+    }
+}
+
+impl Default for OrphanAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Incoming references from other symbols, including name-matched call sites
+fn reference_count(query: &GraphQuery, graph: &GraphStore, node: &Node) -> usize {
+    let direct = graph
+        .get_incoming_edges(&node.id)
+        .iter()
+        .filter(|edge| edge.source != node.id && edge.kind != EdgeKind::Calls)
+        .count();
+    let callers = query
+        .find_callers(&node.id)
+        .map(|callers| callers.iter().filter(|c| c.node.id != node.id).count())
+        .unwrap_or(0);
+    direct + callers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::{Edge, Span};
+    use std::path::PathBuf;
+
+    fn function(name: &str, start: usize) -> Node {
+        Node::new(
+            "repo",
+            NodeKind::Function,
+            name.to_string(),
+            Language::Python,
+            PathBuf::from("app.py"),
+            Span::new(start, start + 10, 1, 1, 1, 11),
+        )
+    }
+
+    #[test]
+    fn test_uncalled_private_helper_is_an_orphan() {
+        let graph = Arc::new(GraphStore::new());
+        let main = function("main", 0);
+        let called = function("_format", 20);
+        let orphan = function("_legacy_format", 40);
+        let public = function("render", 60);
+        // Unreachable itself, but its one reference keeps `_helper` alive
+        let dead_caller = function("_old_entry", 80);
+        let helper = function("_helper", 100);
+        for node in [&main, &called, &orphan, &public, &dead_caller, &helper] {
+            graph.add_node(node.clone());
+        }
+        graph.add_edge(Edge::new(main.id, called.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(dead_caller.id, helper.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(orphan.id, orphan.id, EdgeKind::Calls));
+
+        let orphans = OrphanAnalyzer::new().find_orphans(&graph, &[main.id]);
+        let names: Vec<&str> = orphans.iter().map(|o| o.node.name.as_str()).collect();
+        assert_eq!(names, vec!["_legacy_format", "_old_entry"]);
+    }
+
+    #[test]
+    fn test_export_status_prefers_parser_metadata() {
+        let analyzer = OrphanAnalyzer::new();
+        let mut node = function("helper", 0);
+        assert!(analyzer.is_exported(&node));
+        node.metadata = serde_json::json!({ "visibility": "private" });
+        assert!(!analyzer.is_exported(&node));
+
+        let mut js = function("helper", 0);
+        js.lang = Language::JavaScript;
+        assert!(!analyzer.is_exported(&js));
+        js.metadata = serde_json::json!({ "exported": true });
+        assert!(analyzer.is_exported(&js));
+    }
+}
@@ -1,6 +1,8 @@
 //! Performance analysis module
 
+use crate::infinite_loops::{mask_strings_and_comments, matching_brace};
 use anyhow::Result;
+use codeprism_core::Span;
 use regex::Regex;
 use serde::Serialize;
 use serde_json::Value;
@@ -17,6 +19,10 @@ pub struct PerformanceIssue {
     pub complexity_estimate: Option<String>,
     pub impact_score: Option<f64>,
     pub optimization_effort: Option<String>,
+    /// Loop the issue occurs in, for issues tied to a loop
+    pub loop_span: Option<Span>,
+    /// Query run on every iteration, for N+1 query issues
+    pub query_span: Option<Span>,
 }
 
 /// Recursive complexity information
@@ -41,6 +47,7 @@ pub struct MemoryPattern {
 pub struct PerformanceAnalyzer {
     patterns: HashMap<String, Vec<PerformancePattern>>,
     language_specific_patterns: HashMap<String, Vec<PerformancePattern>>,
+    query_loops: QueryLoopPatterns,
 }
 
 #[derive(Debug, Clone)]
@@ -55,11 +62,79 @@ struct PerformancePattern {
     optimization_effort: String,
 }
 
+/// Analysis type reporting queries that run once per loop iteration
+pub const N_PLUS_ONE: &str = "n_plus_one";
+
+const N_PLUS_ONE_COMPLEXITY: &str = "O(n) queries";
+
+/// Loops and database calls recognised by the N+1 query detection
+struct QueryLoopPatterns {
+    /// Loops whose body is the following, further indented lines (Python
+    /// `for` statements, Ruby `each` blocks); the first group holds the loop
+    /// variables
+    indented_loops: Vec<Regex>,
+    /// Loops whose body is the block opened by the last `{` of the match
+    brace_loops: Vec<Regex>,
+    /// SQL execution and ORM lookups
+    query_call: Regex,
+    identifier: Regex,
+}
+
+impl QueryLoopPatterns {
+    fn new() -> Self {
+        Self {
+            indented_loops: vec![
+                Regex::new(r"(?m)^[ \t]*(?:async\s+)?for\s+(.+?)\s+in\s+.+:[ \t]*(?:#.*)?$")
+                    .unwrap(),
+                Regex::new(r"(?m)\.each(?:_with_index)?\s+do\s*\|([^|]*)\|[ \t]*$").unwrap(),
+            ],
+            brace_loops: vec![
+                Regex::new(
+                    r"\bfor\s*(?:await\s*)?\(\s*(?:const|let|var)?\s*([^;()]+?)\s+(?:of|in)\s+[^)]*\)\s*\{",
+                )
+                .unwrap(),
+                Regex::new(
+                    r"\.(?:forEach|map)\(\s*(?:async\s*)?(?:function\s*\w*\s*)?\(?([\w\s,{}]*?)\)?\s*(?:=>)?\s*\{",
+                )
+                .unwrap(),
+                Regex::new(r"\.each(?:_with_index)?\s*\{\s*\|([^|]*)\|").unwrap(),
+            ],
+            query_call: Regex::new(
+                r"(?:\.objects\.(?:get|filter|exclude|get_or_create|update_or_create|count)|\.query\.(?:get|filter|filter_by)|\.(?:filter_by|find_by|where|findOne|findById|findByPk|findUnique|findFirst|findMany|findAll|scalars?)|\b(?:query|execute|executemany|raw|fetchrow|fetchval))\s*\(",
+            )
+            .unwrap(),
+            identifier: Regex::new(r"[A-Za-z_]\w*").unwrap(),
+        }
+    }
+
+    /// Variables bound by a loop header, without declaration keywords
+    fn variables(&self, header: &str) -> Vec<String> {
+        const KEYWORDS: &[&str] = &["const", "let", "var", "async", "function", "_"];
+        self.identifier
+            .find_iter(header)
+            .map(|name| name.as_str())
+            .filter(|name| !KEYWORDS.contains(name))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// A loop and the variables bound on each iteration
+struct QueryLoop {
+    /// Byte offsets of the whole loop
+    start: usize,
+    end: usize,
+    /// Byte offset where the body begins
+    body_start: usize,
+    variables: Vec<String>,
+}
+
 impl PerformanceAnalyzer {
     pub fn new() -> Self {
         let mut analyzer = Self {
             patterns: HashMap::new(),
             language_specific_patterns: HashMap::new(),
+            query_loops: QueryLoopPatterns::new(),
         };
         analyzer.initialize_patterns();
         analyzer.initialize_language_specific_patterns();
@@ -445,12 +520,20 @@ impl PerformanceAnalyzer {
         let mut issues = Vec::new();
 
         let target_types = if analysis_types.contains(&"all".to_string()) {
-            self.patterns.keys().cloned().collect::<Vec<_>>()
+            let mut types = self.patterns.keys().cloned().collect::<Vec<_>>();
+            types.push(N_PLUS_ONE.to_string());
+            types
         } else {
             analysis_types.to_vec()
         };
 
         for analysis_type in target_types {
+            if analysis_type == N_PLUS_ONE {
+                if self.meets_complexity_threshold(N_PLUS_ONE_COMPLEXITY, complexity_threshold) {
+                    issues.extend(self.detect_n_plus_one(content));
+                }
+                continue;
+            }
             if let Some(patterns) = self.patterns.get(&analysis_type) {
                 for pattern in patterns {
                     if self.meets_complexity_threshold(&pattern.complexity, complexity_threshold) {
@@ -465,6 +548,8 @@ impl PerformanceAnalyzer {
                                 complexity_estimate: Some(pattern.complexity.clone()),
                                 impact_score: Some(pattern.impact_score),
                                 optimization_effort: Some(pattern.optimization_effort.clone()),
+                                loop_span: None,
+                                query_span: None,
                             });
                         }
                     }
@@ -475,6 +560,130 @@ impl PerformanceAnalyzer {
         Ok(issues)
     }
 
+    /// Find queries run once per loop iteration with a value from the loop
+    ///
+    /// A database call in a loop body is only reported when its statement
+    /// mentions one of the loop's variables, so a constant lookup that merely
+    /// sits in a loop is left to the `hot_spots` patterns. Each query is
+    /// reported against the innermost loop it depends on.
+    pub fn detect_n_plus_one(&self, content: &str) -> Vec<PerformanceIssue> {
+        // Loop headers and calls are matched outside string literals and comments
+        let masked = mask_strings_and_comments(content);
+        let loops = self.find_query_loops(content, &masked);
+        if loops.is_empty() {
+            return Vec::new();
+        }
+
+        let mut issues = Vec::new();
+        let mut reported_until = 0;
+        for call in self.query_loops.query_call.find_iter(&masked) {
+            if call.start() < reported_until {
+                // A chained call of a query already reported
+                continue;
+            }
+            let statement_end = statement_end(&masked, call.end() - 1);
+            let statement = &content[call.start()..statement_end];
+            let Some(query_loop) = loops
+                .iter()
+                .filter(|query_loop| {
+                    query_loop.body_start <= call.start() && call.start() < query_loop.end
+                })
+                .filter(|query_loop| {
+                    query_loop
+                        .variables
+                        .iter()
+                        .any(|variable| mentions(statement, variable))
+                })
+                .min_by_key(|query_loop| query_loop.end - query_loop.start)
+            else {
+                continue;
+            };
+
+            reported_until = statement_end;
+            issues.push(PerformanceIssue {
+                issue_type: "N+1 Query".to_string(),
+                severity: "high".to_string(),
+                description: format!(
+                    "Query depends on loop variable '{}' and runs once per iteration",
+                    query_loop
+                        .variables
+                        .iter()
+                        .find(|variable| mentions(statement, variable))
+                        .map(String::as_str)
+                        .unwrap_or_default()
+                ),
+                location: Some(self.get_line_info(content, call.start())),
+                recommendation: "Load the rows for all iterations with one query before the loop: an IN clause, a join, select_related/prefetch_related or eager loading".to_string(),
+                complexity_estimate: Some(N_PLUS_ONE_COMPLEXITY.to_string()),
+                impact_score: Some(8.5),
+                optimization_effort: Some("Medium".to_string()),
+                loop_span: Some(span_between(content, query_loop.start, query_loop.end)),
+                query_span: Some(span_between(content, call.start(), statement_end)),
+            });
+        }
+        issues
+    }
+
+    fn find_query_loops(&self, content: &str, masked: &str) -> Vec<QueryLoop> {
+        let mut loops = Vec::new();
+        for pattern in &self.query_loops.indented_loops {
+            for captures in pattern.captures_iter(masked) {
+                let header = captures.get(0).unwrap();
+                let line_start = masked[..header.start()].rfind('\n').map_or(0, |i| i + 1);
+                let indent = masked[line_start..].len() - masked[line_start..].trim_start().len();
+                let header_end = masked[header.end()..]
+                    .find('\n')
+                    .map_or(masked.len(), |i| header.end() + i);
+
+                // The body is every following line indented deeper than the header
+                let mut end = header_end;
+                let mut offset = header_end;
+                for line in masked[header_end..].split_inclusive('\n').skip(1) {
+                    let trimmed = line.trim();
+                    if !trimmed.is_empty() && !trimmed.starts_with('#') {
+                        if line.len() - line.trim_start().len() <= indent {
+                            break;
+                        }
+                        end = offset + 1 + line.trim_end().len();
+                    }
+                    offset += line.len();
+                }
+
+                loops.push(QueryLoop {
+                    start: line_start + indent,
+                    end,
+                    body_start: header_end,
+                    variables: self
+                        .query_loops
+                        .variables(&content[captures.get(1).unwrap().range()]),
+                });
+            }
+        }
+        for pattern in &self.query_loops.brace_loops {
+            for captures in pattern.captures_iter(masked) {
+                let header = captures.get(0).unwrap();
+                let Some(open) = masked[header.range()]
+                    .rfind('{')
+                    .map(|i| header.start() + i)
+                else {
+                    continue;
+                };
+                let Some(close) = matching_brace(masked, open) else {
+                    continue;
+                };
+                loops.push(QueryLoop {
+                    start: header.start(),
+                    end: close + 1,
+                    body_start: open + 1,
+                    variables: self
+                        .query_loops
+                        .variables(&content[captures.get(1).unwrap().range()]),
+                });
+            }
+        }
+        loops
+    }
+
     /// Analyze recursive function complexity
     pub fn analyze_recursive_complexity(&self, content: &str) -> Result<Vec<RecursiveComplexity>> {
         let mut recursive_functions = Vec::new();
@@ -689,7 +898,9 @@ impl PerformanceAnalyzer {
         priority_issues.sort_by(|a, b| b.1.cmp(a.1));
 
         // Critical issue recommendations
-        if issue_counts.contains_key("Database Query in Loop") {
+        if issue_counts.contains_key("Database Query in Loop")
+            || issue_counts.contains_key("N+1 Query")
+        {
             recommendations.push("HIGH PRIORITY: Eliminate N+1 query problems with batch operations and proper ORM usage".to_string());
         }
 
@@ -872,6 +1083,8 @@ impl PerformanceAnalyzer {
                             complexity_estimate: Some(pattern.complexity.clone()),
                             impact_score: Some(pattern.impact_score),
                             optimization_effort: Some(pattern.optimization_effort.clone()),
+                            loop_span: None,
+                            query_span: None,
                         });
                     }
                 }
@@ -940,6 +1153,53 @@ impl PerformanceAnalyzer {
     }
 }
 
+/// Whether `text` uses `variable` as a name of its own
+fn mentions(text: &str, variable: &str) -> bool {
+    text.match_indices(variable).any(|(index, _)| {
+        let before = text[..index].chars().next_back();
+        let after = text[index + variable.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// End of the statement whose call opens the parenthesis at `open`: the end
+/// of the line holding the closing parenthesis, so chained calls are included
+fn statement_end(masked: &str, open: usize) -> usize {
+    let mut depth = 0usize;
+    let mut close = masked.len();
+    for (index, byte) in masked.bytes().enumerate().skip(open) {
+        match byte {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = index;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    masked[close..]
+        .find('\n')
+        .map_or(masked.len(), |i| close + i)
+}
+
+/// Span of a byte range, with 1-based lines and columns
+pub(crate) fn span_between(content: &str, start: usize, end: usize) -> Span {
+    let position = |offset: usize| {
+        let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+        (
+            content[..offset].matches('\n').count() + 1,
+            offset - line_start + 1,
+        )
+    };
+    let (start_line, start_column) = position(start);
+    let (end_line, end_column) = position(end);
+    Span::new(start, end, start_line, end_line, start_column, end_column)
+}
+
 impl Default for PerformanceAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -1100,6 +1360,48 @@ mod tests {
             .any(|i| i.issue_type == "Database Query in Loop"));
     }
 
+    #[test]
+    fn test_n_plus_one_query_depends_on_loop_variable() {
+        let analyzer = PerformanceAnalyzer::new();
+        let code = "def totals(users):\n    settings = Settings.objects.get(pk=1)\n    for user in users:\n        # one query per user\n        orders = Order.objects.filter(customer=user.id)\n        rate = Rate.objects.get(pk=1)\n        print(orders, rate)\n    return settings\n";
+
+        let issues = analyzer
+            .analyze_content(code, &[N_PLUS_ONE.to_string()], "medium")
+            .unwrap();
+
+        // The constant lookups in and before the loop are not N+1 queries
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.issue_type, "N+1 Query");
+        let loop_span = issue.loop_span.as_ref().unwrap();
+        assert_eq!((loop_span.start_line, loop_span.end_line), (3, 7));
+        let query_span = issue.query_span.as_ref().unwrap();
+        assert_eq!(query_span.start_line, 5);
+        assert_eq!(
+            &code[query_span.start_byte..query_span.end_byte],
+            ".objects.filter(customer=user.id)"
+        );
+    }
+
+    #[test]
+    fn test_n_plus_one_in_javascript_callbacks_and_sql() {
+        let analyzer = PerformanceAnalyzer::new();
+        let code = "async function load(ids) {\n  const all = await db.query('SELECT * FROM items');\n  ids.forEach(async (id) => {\n    const row = await db.query(`SELECT * FROM items WHERE id = ${id}`);\n    console.log(row);\n  });\n  for (const item of all) {\n    await Item.findByPk(item.id).then(save);\n  }\n}\n";
+
+        let issues = analyzer.detect_n_plus_one(code);
+
+        let lines: Vec<usize> = issues
+            .iter()
+            .map(|issue| issue.query_span.as_ref().unwrap().start_line)
+            .collect();
+        assert_eq!(lines, vec![4, 8]);
+        assert_eq!(issues[0].loop_span.as_ref().unwrap().start_line, 3);
+        assert!(analyzer
+            .get_performance_recommendations(&issues)
+            .iter()
+            .any(|recommendation| recommendation.contains("N+1")));
+    }
+
     #[test]
     fn test_string_concatenation() {
         let analyzer = PerformanceAnalyzer::new();
@@ -1138,6 +1440,8 @@ mod tests {
             complexity_estimate: Some("O(n)".to_string()),
             impact_score: Some(9.0),
             optimization_effort: Some("Medium".to_string()),
+            loop_span: None,
+            query_span: None,
         }];
 
         let recommendations = analyzer.get_performance_recommendations(&issues);
@@ -0,0 +1,265 @@
+//! Anchoring analysis findings to unified diff positions
+//!
+//! Review bots post findings as inline comments, which must reference a line
+//! that is visible in the pull request diff. [`DiffMap`] parses a unified
+//! diff and translates a `(path, line)` in the new revision into the diff
+//! position and side expected by code review APIs.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A finding located in the new revision of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewFinding {
+    pub path: String,
+    /// Line in the new revision (1-based)
+    pub line: usize,
+    pub body: String,
+}
+
+/// A review comment anchored to a diff position
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewComment {
+    pub path: String,
+    /// Offset below the file's first hunk header, counting every diff line
+    pub position: usize,
+    /// Line in the new revision
+    pub line: usize,
+    /// Side of the diff the line belongs to; findings always target `RIGHT`
+    pub side: String,
+    pub body: String,
+}
+
+/// Findings split by whether they could be anchored to the diff
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnchoredFindings {
+    pub comments: Vec<ReviewComment>,
+    pub outside_diff: Vec<ReviewFinding>,
+}
+
+impl AnchoredFindings {
+    /// Count of findings outside the diff, per file
+    pub fn outside_diff_summary(&self) -> BTreeMap<String, usize> {
+        let mut summary = BTreeMap::new();
+        for finding in &self.outside_diff {
+            *summary.entry(finding.path.clone()).or_insert(0) += 1;
+        }
+        summary
+    }
+}
+
+/// Diff position of one line of the new revision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DiffLine {
+    position: usize,
+    added: bool,
+}
+
+/// New-revision lines visible in a unified diff, per file
+#[derive(Debug, Clone, Default)]
+pub struct DiffMap {
+    files: HashMap<String, HashMap<usize, DiffLine>>,
+    /// Files in diff order, so callers can iterate them deterministically
+    order: Vec<String>,
+}
+
+impl DiffMap {
+    /// Parse a unified diff as produced by `git diff`
+    ///
+    /// Deleted files are skipped since nothing in them can be commented on in
+    /// the new revision.
+    pub fn parse(diff: &str) -> Self {
+        let mut map = Self::default();
+        let mut current: Option<String> = None;
+        let mut position = 0;
+        let mut new_line = 0;
+        let mut in_hunk = false;
+
+        for line in diff.lines() {
+            if line.starts_with("diff --git ") {
+                current = None;
+                in_hunk = false;
+            } else if let Some(path) = line.strip_prefix("+++ ").filter(|_| !in_hunk) {
+                let path = path.split('\t').next().unwrap_or(path);
+                current = match path {
+                    "/dev/null" => None,
+                    path => Some(path.strip_prefix("b/").unwrap_or(path).to_string()),
+                };
+                if let Some(path) = &current {
+                    if !map.files.contains_key(path) {
+                        map.order.push(path.clone());
+                    }
+                    map.files.entry(path.clone()).or_default();
+                }
+                position = 0;
+            } else if line.starts_with("--- ") && !in_hunk {
+                continue;
+            } else if let Some(header) = line.strip_prefix("@@ ") {
+                // The first hunk header is position 0; later headers count as lines
+                if in_hunk {
+                    position += 1;
+                }
+                in_hunk = true;
+                new_line = parse_new_start(header).unwrap_or(1);
+            } else if in_hunk {
+                let Some(path) = &current else { continue };
+                if line.starts_with('\\') {
+                    // "\ No newline at end of file" still occupies a position
+                    position += 1;
+                    continue;
+                }
+                position += 1;
+                match line.chars().next() {
+                    Some('+') => {
+                        map.record(path, new_line, position, true);
+                        new_line += 1;
+                    }
+                    Some('-') => {}
+                    _ => {
+                        map.record(path, new_line, position, false);
+                        new_line += 1;
+                    }
+                }
+            }
+        }
+
+        map
+    }
+
+    fn record(&mut self, path: &str, line: usize, position: usize, added: bool) {
+        if let Some(lines) = self.files.get_mut(path) {
+            lines.insert(line, DiffLine { position, added });
+        }
+    }
+
+    /// Files present in the new revision, in diff order
+    pub fn files(&self) -> &[String] {
+        &self.order
+    }
+
+    /// Whether `line` of `path` was added or modified by the diff
+    pub fn is_changed(&self, path: &str, line: usize) -> bool {
+        self.lookup(path, line).is_some_and(|l| l.added)
+    }
+
+    fn lookup(&self, path: &str, line: usize) -> Option<DiffLine> {
+        self.files.get(path)?.get(&line).copied()
+    }
+
+    /// Anchor findings to the diff
+    ///
+    /// With `changed_only`, findings on unchanged context lines are treated as
+    /// outside the diff as well.
+    pub fn anchor(&self, findings: Vec<ReviewFinding>, changed_only: bool) -> AnchoredFindings {
+        let mut anchored = AnchoredFindings::default();
+        for finding in findings {
+            match self
+                .lookup(&finding.path, finding.line)
+                .filter(|l| l.added || !changed_only)
+            {
+                Some(diff_line) => anchored.comments.push(ReviewComment {
+                    path: finding.path,
+                    position: diff_line.position,
+                    line: finding.line,
+                    side: "RIGHT".to_string(),
+                    body: finding.body,
+                }),
+                None => anchored.outside_diff.push(finding),
+            }
+        }
+        anchored
+            .comments
+            .sort_by(|a, b| (&a.path, a.position).cmp(&(&b.path, b.position)));
+        anchored
+    }
+}
+
+/// Start line of the new revision from a hunk header like `-3,7 +3,8 @@ fn main`
+fn parse_new_start(header: &str) -> Option<usize> {
+    let new_range = header
+        .split_whitespace()
+        .find(|part| part.starts_with('+'))?;
+    new_range[1..].split(',').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/app.py b/src/app.py
+index 83db48f..bf269f4 100644
+--- a/src/app.py
++++ b/src/app.py
+@@ -1,4 +1,5 @@
+ import os
++import sys
+
+ def main():
+-    run()
++    run(sys.argv)
+@@ -20,3 +21,4 @@ def helper():
+     a = 1
++    b = a.value
+     return a
+diff --git a/old.py b/old.py
+deleted file mode 100644
+--- a/old.py
++++ /dev/null
+@@ -1 +0,0 @@
+-print('gone')
+";
+
+    fn finding(path: &str, line: usize) -> ReviewFinding {
+        ReviewFinding {
+            path: path.to_string(),
+            line,
+            body: format!("finding at {line}"),
+        }
+    }
+
+    #[test]
+    fn test_finding_on_changed_line_is_anchored() {
+        let map = DiffMap::parse(DIFF);
+        assert_eq!(map.files(), &["src/app.py".to_string()]);
+        assert!(map.is_changed("src/app.py", 2));
+        assert!(!map.is_changed("src/app.py", 1));
+
+        let anchored = map.anchor(
+            vec![
+                finding("src/app.py", 22),
+                finding("src/app.py", 5),
+                finding("src/app.py", 3),
+                finding("src/app.py", 40),
+                finding("lib/other.py", 1),
+            ],
+            true,
+        );
+
+        assert_eq!(
+            anchored.comments,
+            vec![
+                ReviewComment {
+                    path: "src/app.py".to_string(),
+                    position: 6,
+                    line: 5,
+                    side: "RIGHT".to_string(),
+                    body: "finding at 5".to_string(),
+                },
+                ReviewComment {
+                    path: "src/app.py".to_string(),
+                    position: 9,
+                    line: 22,
+                    side: "RIGHT".to_string(),
+                    body: "finding at 22".to_string(),
+                },
+            ]
+        );
+        // Line 3 is unchanged context, line 40 is outside any hunk
+        assert_eq!(anchored.outside_diff.len(), 3);
+        assert_eq!(anchored.outside_diff_summary()["src/app.py"], 2);
+
+        let with_context = map.anchor(vec![finding("src/app.py", 3)], false);
+        assert_eq!(with_context.comments[0].position, 3);
+    }
+}
@@ -0,0 +1,549 @@
+//! Structurally similar function search
+//!
+//! Functions are reduced to a shape tree built from their source: identifiers
+//! and literals collapse to placeholders, statements become nodes and every
+//! bracketed group or indented block nests below the statement that opens it.
+//! Two functions are compared through the bags of hashes of all their
+//! statement and block subtrees, a cheap approximation of tree edit distance
+//! that survives renames and changed constants.
+//!
+//! Shapes only cover a function's own span and never follow calls, so
+//! recursive and mutually recursive functions hash like any other.
+
+use anyhow::{anyhow, Result};
+use codeprism_core::{GraphStore, Language, Node, NodeId, NodeKind};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Keywords kept verbatim in shapes; every other identifier becomes `id`
+const SHAPE_KEYWORDS: &[&str] = &[
+    "and", "async", "await", "break", "case", "catch", "continue", "def", "default", "defer", "do",
+    "elif", "else", "except", "finally", "fn", "for", "func", "function", "go", "if", "in", "is",
+    "lambda", "let", "loop", "match", "new", "not", "or", "raise", "return", "select", "switch",
+    "throw", "try", "while", "with", "yield",
+];
+
+/// Keyword literals that collapse to `lit` like numbers and strings
+const LITERAL_WORDS: &[&str] = &["true", "false", "True", "False", "None", "null", "nil"];
+
+/// Normalized structure of a function body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionShape {
+    /// Hash of the whole shape tree
+    pub hash: u64,
+    /// Number of tokens in the function
+    pub size: usize,
+    /// Sorted hashes of every statement, group and block subtree
+    subtrees: Vec<u64>,
+}
+
+impl FunctionShape {
+    /// Dice coefficient of the two subtree bags, 1.0 for identical shapes
+    pub fn similarity(&self, other: &FunctionShape) -> f32 {
+        if self.hash == other.hash {
+            return 1.0;
+        }
+        let total = self.subtrees.len() + other.subtrees.len();
+        if total == 0 {
+            return 0.0;
+        }
+        let (mut i, mut j, mut common) = (0, 0, 0);
+        while i < self.subtrees.len() && j < other.subtrees.len() {
+            match self.subtrees[i].cmp(&other.subtrees[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    common += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        (2 * common) as f32 / total as f32
+    }
+
+    /// Upper bound on [`FunctionShape::similarity`] from the bag sizes alone
+    fn max_similarity(&self, other: &FunctionShape) -> f32 {
+        let total = self.subtrees.len() + other.subtrees.len();
+        if total == 0 {
+            return 0.0;
+        }
+        (2 * self.subtrees.len().min(other.subtrees.len())) as f32 / total as f32
+    }
+}
+
+/// A function ranked by structural similarity
+#[derive(Debug, Clone)]
+pub struct SimilarFunction {
+    pub node: Node,
+    pub similarity: f32,
+}
+
+/// Hashes function shapes and searches the graph for structural look-alikes
+pub struct StructuralHasher {
+    graph: Arc<GraphStore>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl StructuralHasher {
+    pub fn new(graph: Arc<GraphStore>) -> Self {
+        Self {
+            graph,
+            cancel: None,
+        }
+    }
+
+    /// Stop [`Self::find_similar`] early once `cancel` is set
+    pub fn with_cancel(mut self, cancel: Option<Arc<AtomicBool>>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Compute the shape of a function's source text
+    pub fn shape_of_source(source: &str, language: Language) -> FunctionShape {
+        let indented = matches!(language, Language::Python);
+        let tree = ShapeBuilder::new(indented).build(&tokenize(source, language));
+        let mut subtrees = Vec::new();
+        let hash = hash_subtree(&tree, &mut subtrees);
+        subtrees.sort_unstable();
+        FunctionShape {
+            hash,
+            size: count_tokens(&tree),
+            subtrees,
+        }
+    }
+
+    /// Rank every other function and method in the graph by similarity to `node`
+    ///
+    /// Matches below `threshold` (0.0 to 1.0) are dropped. Candidates come from
+    /// all indexed files; those whose source cannot be read are skipped. When
+    /// cancelled, the matches found so far are returned.
+    pub fn find_similar(&self, node: NodeId, threshold: f32) -> Result<Vec<SimilarFunction>> {
+        let target = self
+            .graph
+            .get_node(&node)
+            .ok_or_else(|| anyhow!("Symbol not found: {}", node.to_hex()))?;
+        let mut sources = SourceCache::default();
+        let target_shape = self
+            .shape_of(&target, &mut sources)
+            .ok_or_else(|| anyhow!("Could not read source of {}", target.name))?;
+
+        let mut matches: Vec<SimilarFunction> = Vec::new();
+        for candidate in [NodeKind::Function, NodeKind::Method]
+            .into_iter()
+            .flat_map(|kind| self.graph.get_nodes_by_kind(kind))
+            .filter(|candidate| candidate.id != target.id)
+        {
+            if self
+                .cancel
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::Relaxed))
+            {
+                break;
+            }
+            let Some(shape) = self.shape_of(&candidate, &mut sources) else {
+                continue;
+            };
+            if target_shape.max_similarity(&shape) < threshold {
+                continue;
+            }
+            let similarity = target_shape.similarity(&shape);
+            if similarity >= threshold {
+                matches.push(SimilarFunction {
+                    node: candidate,
+                    similarity,
+                });
+            }
+        }
+
+        matches.sort_by(|a, b| {
+            b.similarity.total_cmp(&a.similarity).then_with(|| {
+                (&a.node.file, a.node.span.start_byte).cmp(&(&b.node.file, b.node.span.start_byte))
+            })
+        });
+        Ok(matches)
+    }
+
+    fn shape_of(&self, node: &Node, sources: &mut SourceCache) -> Option<FunctionShape> {
+        let source = sources.get(&node.file)?;
+        let text = source.get(node.span.start_byte..node.span.end_byte)?;
+        Some(Self::shape_of_source(text, node.lang))
+    }
+}
+
+/// File contents read once per search
+#[derive(Default)]
+struct SourceCache {
+    files: HashMap<PathBuf, Option<String>>,
+}
+
+impl SourceCache {
+    fn get(&mut self, path: &PathBuf) -> Option<&str> {
+        self.files
+            .entry(path.clone())
+            .or_insert_with(|| std::fs::read_to_string(path).ok())
+            .as_deref()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Open(char),
+    Close(char),
+    Separator,
+    /// Start of a new line with its indentation width
+    Line(usize),
+}
+
+/// Split source into normalized tokens, dropping comments
+fn tokenize(source: &str, language: Language) -> Vec<Token> {
+    let hash_comments = matches!(language, Language::Python);
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                i += 1;
+                let start = i;
+                while i < chars.len() && (chars[i] == ' ' || chars[i] == '\t') {
+                    i += 1;
+                }
+                tokens.push(Token::Line(i - start));
+            }
+            c if c.is_whitespace() => i += 1,
+            '#' if hash_comments => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            '"' | '\'' | '`' => {
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+                tokens.push(Token::Word("lit".to_string()));
+            }
+            '(' | '[' | '{' => {
+                tokens.push(Token::Open(c));
+                i += 1;
+            }
+            ')' | ']' | '}' => {
+                tokens.push(Token::Close(c));
+                i += 1;
+            }
+            ';' => {
+                tokens.push(Token::Separator);
+                i += 1;
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let label = if c.is_ascii_digit() || LITERAL_WORDS.contains(&word.as_str()) {
+                    "lit".to_string()
+                } else if SHAPE_KEYWORDS.contains(&word.as_str()) {
+                    word
+                } else {
+                    "id".to_string()
+                };
+                tokens.push(Token::Word(label));
+            }
+            c => {
+                tokens.push(Token::Word(c.to_string()));
+                i += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+#[derive(Debug)]
+struct ShapeNode {
+    label: String,
+    children: Vec<ShapeNode>,
+}
+
+impl ShapeNode {
+    fn leaf(label: String) -> Self {
+        Self {
+            label,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// An open bracket group or indented block while building the tree
+struct Frame {
+    label: String,
+    /// Indentation width for indented blocks, `None` for bracket groups
+    indent: Option<usize>,
+    statements: Vec<ShapeNode>,
+    current: Vec<ShapeNode>,
+}
+
+impl Frame {
+    fn new(label: impl Into<String>, indent: Option<usize>) -> Self {
+        Self {
+            label: label.into(),
+            indent,
+            statements: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+
+    fn end_statement(&mut self) {
+        if !self.current.is_empty() {
+            self.statements.push(ShapeNode {
+                label: "stmt".to_string(),
+                children: std::mem::take(&mut self.current),
+            });
+        }
+    }
+
+    fn finish(mut self) -> ShapeNode {
+        self.end_statement();
+        ShapeNode {
+            label: self.label,
+            children: self.statements,
+        }
+    }
+}
+
+struct ShapeBuilder {
+    indented: bool,
+    frames: Vec<Frame>,
+    /// Indentation of the latest line break, applied once the line has content
+    pending_line: Option<usize>,
+}
+
+impl ShapeBuilder {
+    fn new(indented: bool) -> Self {
+        Self {
+            indented,
+            frames: vec![Frame::new("fn", Some(0))],
+            pending_line: None,
+        }
+    }
+
+    fn top(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("root frame is never popped")
+    }
+
+    fn build(mut self, tokens: &[Token]) -> ShapeNode {
+        for token in tokens {
+            if let Token::Line(indent) = token {
+                // Blank and comment-only lines must not close indented blocks
+                self.pending_line = Some(*indent);
+                continue;
+            }
+            if let Some(indent) = self.pending_line.take() {
+                self.new_line(indent);
+            }
+            match token {
+                Token::Word(label) => self.top().current.push(ShapeNode::leaf(label.clone())),
+                Token::Separator => self.top().end_statement(),
+                Token::Open(bracket) => self.frames.push(Frame::new(bracket.to_string(), None)),
+                Token::Close(_) => self.close_group(),
+                Token::Line(_) => {}
+            }
+        }
+        while self.frames.len() > 1 {
+            self.pop_frame();
+        }
+        self.frames.pop().expect("root frame").finish()
+    }
+
+    fn new_line(&mut self, indent: usize) {
+        // Lines inside parentheses and brackets continue the same statement
+        if matches!(self.top().label.as_str(), "(" | "[") {
+            return;
+        }
+        self.top().end_statement();
+        if !self.indented {
+            return;
+        }
+        while self.frames.len() > 1 && self.top().indent.is_some_and(|level| indent < level) {
+            self.pop_frame();
+        }
+        if self.top().indent.is_some_and(|level| indent > level) {
+            self.frames.push(Frame::new("block", Some(indent)));
+        }
+    }
+
+    fn close_group(&mut self) {
+        // Blocks opened inside the group end with it; unmatched closers are ignored
+        if !self.frames[1..].iter().any(|frame| frame.indent.is_none()) {
+            return;
+        }
+        while self.top().indent.is_some() {
+            self.pop_frame();
+        }
+        self.pop_frame();
+    }
+
+    fn pop_frame(&mut self) {
+        let frame = self.frames.pop().expect("frame to pop");
+        let is_block = frame.indent.is_some();
+        let node = frame.finish();
+        let parent = self.top();
+        if is_block {
+            // An indented block belongs to the statement that opened it
+            match parent.statements.last_mut() {
+                Some(opener) => opener.children.push(node),
+                None => parent.statements.push(node),
+            }
+        } else {
+            parent.current.push(node);
+        }
+    }
+}
+
+/// Hash a subtree, recording the hashes of all non-leaf subtrees below it
+fn hash_subtree(node: &ShapeNode, subtrees: &mut Vec<u64>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node.label.hash(&mut hasher);
+    for child in &node.children {
+        hash_subtree(child, subtrees).hash(&mut hasher);
+    }
+    let hash = hasher.finish();
+    if !node.children.is_empty() {
+        subtrees.push(hash);
+    }
+    hash
+}
+
+fn count_tokens(node: &ShapeNode) -> usize {
+    if node.children.is_empty() {
+        1
+    } else {
+        node.children.iter().map(count_tokens).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::Span;
+
+    const ORIGINAL: &str = "\
+def total_price(items, tax):
+    total = 0
+    for item in items:
+        if item.price > 100:
+            total += item.price * 0.9
+        else:
+            total += item.price
+    return total * (1 + tax)
+";
+
+    const RENAMED: &str = "\
+def sum_costs(products, rate):
+    # Same logic, different names and constants
+    acc = 0
+
+# Column-zero comments and blank lines leave blocks open
+    for p in products:
+        if p.cost > 250:
+            acc += p.cost * 0.8
+        else:
+            acc += p.cost
+    return acc * (1 + rate)
+";
+
+    const UNRELATED: &str = "\
+def fib(n):
+    if n < 2:
+        return n
+    return fib(n - 1) + fib(n - 2)
+";
+
+    fn function(name: &str, path: PathBuf, source: &str) -> Node {
+        Node::new(
+            "repo",
+            NodeKind::Function,
+            name.to_string(),
+            Language::Python,
+            path,
+            Span::new(0, source.len(), 1, 1, source.lines().count(), 1),
+        )
+    }
+
+    #[test]
+    fn test_shape_ignores_identifiers_literals_and_comments() {
+        let original = StructuralHasher::shape_of_source(ORIGINAL, Language::Python);
+        let renamed = StructuralHasher::shape_of_source(RENAMED, Language::Python);
+        assert_eq!(original.hash, renamed.hash);
+
+        let c_like = "int f(int a) { if (a > 1) { return a; } return 0; }";
+        let c_renamed = "int g(int b) { /* guard */ if (b > 7) { return b; } return 1; }";
+        assert_eq!(
+            StructuralHasher::shape_of_source(c_like, Language::C).hash,
+            StructuralHasher::shape_of_source(c_renamed, Language::C).hash
+        );
+    }
+
+    #[test]
+    fn test_find_similar_ranks_cross_file_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let graph = Arc::new(GraphStore::new());
+        let mut nodes = Vec::new();
+        let edited = RENAMED.replace("    return acc", "    acc = round(acc)\n    return acc");
+        for (name, file, source) in [
+            ("total_price", "pricing.py", ORIGINAL),
+            ("sum_costs", "billing.py", RENAMED),
+            ("rounded_costs", "reports.py", edited.as_str()),
+            ("fib", "math.py", UNRELATED),
+        ] {
+            let path = dir.path().join(file);
+            std::fs::write(&path, source).unwrap();
+            let node = function(name, path, source);
+            graph.add_node(node.clone());
+            nodes.push(node);
+        }
+
+        let hasher = StructuralHasher::new(Arc::clone(&graph));
+        let matches = hasher.find_similar(nodes[0].id, 0.5).unwrap();
+        let ranked: Vec<&str> = matches.iter().map(|m| m.node.name.as_str()).collect();
+        assert_eq!(ranked, vec!["sum_costs", "rounded_costs"]);
+        assert_eq!(matches[0].similarity, 1.0);
+        assert!(matches[1].similarity < 1.0);
+
+        // A recursive function is compared by its own body only
+        let fib_matches = hasher.find_similar(nodes[3].id, 0.0).unwrap();
+        assert_eq!(fib_matches.len(), 3);
+        assert!(fib_matches.iter().all(|m| m.similarity < 0.5));
+
+        let cancelled = StructuralHasher::new(Arc::clone(&graph))
+            .with_cancel(Some(Arc::new(AtomicBool::new(true))))
+            .find_similar(nodes[0].id, 0.0)
+            .unwrap();
+        assert!(cancelled.is_empty());
+    }
+}
@@ -0,0 +1,350 @@
+//! Technical debt markers aged with git blame
+//!
+//! Finds TODO, FIXME, HACK and XXX comments and, with the `git` feature,
+//! attributes each one to the commit that last touched its line. Markers that
+//! have lingered for years surface first when sorted by age.
+
+use anyhow::Result;
+use regex::Regex;
+use serde::Serialize;
+
+/// Comment markers reported as technical debt
+pub const DEBT_MARKERS: &[&str] = &["TODO", "FIXME", "HACK", "XXX"];
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// A debt marker comment and, once blamed, who wrote it and when
+#[derive(Debug, Clone, Serialize)]
+pub struct DebtMarker {
+    pub path: String,
+    /// 1-based line number
+    pub line: usize,
+    /// Marker keyword, e.g. `TODO`
+    pub marker: String,
+    /// Comment text after the marker
+    pub text: String,
+    /// Author of the line, `None` until blamed or when the line is uncommitted
+    pub author: Option<String>,
+    pub commit: Option<String>,
+    /// Author time of the line as a Unix timestamp
+    pub authored_at: Option<i64>,
+    /// Whole days between `authored_at` and the time of the analysis
+    pub age_days: Option<u64>,
+}
+
+/// Order of reported markers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebtOrder {
+    /// Oldest first; markers without blame information come last
+    #[default]
+    Oldest,
+    /// Newest first; markers without blame information come last
+    Newest,
+    /// By path, then line
+    Path,
+}
+
+impl std::str::FromStr for DebtOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "oldest" | "age" => Ok(Self::Oldest),
+            "newest" => Ok(Self::Newest),
+            "path" => Ok(Self::Path),
+            other => Err(anyhow::anyhow!(
+                "Unknown sort order '{other}'. Expected one of: oldest, newest, path"
+            )),
+        }
+    }
+}
+
+/// Analyzer for TODO-style debt markers
+pub struct TechDebtAnalyzer {
+    marker_pattern: Regex,
+}
+
+impl TechDebtAnalyzer {
+    pub fn new() -> Self {
+        let pattern = format!(
+            r"(?://+|#+|/\*+|^\s*\*+|--|<!--)\s*\b({})\b(?:\([^)]*\))?:?\s*(.*?)\s*(?:\*/|-->)?\s*$",
+            DEBT_MARKERS.join("|")
+        );
+        Self {
+            marker_pattern: Regex::new(&pattern).unwrap(),
+        }
+    }
+
+    /// Find debt marker comments in a file's content
+    pub fn find_markers(&self, path: &str, content: &str) -> Vec<DebtMarker> {
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let captures = self.marker_pattern.captures(line)?;
+                Some(DebtMarker {
+                    path: path.to_string(),
+                    line: index + 1,
+                    marker: captures[1].to_string(),
+                    text: captures[2].to_string(),
+                    author: None,
+                    commit: None,
+                    authored_at: None,
+                    age_days: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Sort markers in place
+    pub fn sort(&self, markers: &mut [DebtMarker], order: DebtOrder) {
+        match order {
+            DebtOrder::Oldest => markers.sort_by_key(|m| {
+                (
+                    m.authored_at.is_none(),
+                    m.authored_at,
+                    m.path.clone(),
+                    m.line,
+                )
+            }),
+            DebtOrder::Newest => markers.sort_by_key(|m| {
+                (
+                    m.authored_at.is_none(),
+                    std::cmp::Reverse(m.authored_at),
+                    m.path.clone(),
+                    m.line,
+                )
+            }),
+            DebtOrder::Path => {
+                markers.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)))
+            }
+        }
+    }
+
+    /// List the debt markers of all files tracked in the repository at `repo_path`
+    ///
+    /// Markers younger than `min_age_days` are dropped; uncommitted markers
+    /// have no age and are only kept when no minimum is given.
+    #[cfg(feature = "git")]
+    pub fn list_tech_debt(
+        &self,
+        repo_path: &std::path::Path,
+        min_age_days: Option<u64>,
+        order: DebtOrder,
+    ) -> Result<Vec<DebtMarker>> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default();
+
+        let mut markers = Vec::new();
+        for path in git(repo_path, &["ls-files", "-z"])?
+            .split('\0')
+            .filter(|path| !path.is_empty())
+        {
+            // Binary and non-UTF-8 files have no comments to report
+            let Ok(content) = std::fs::read_to_string(repo_path.join(path)) else {
+                continue;
+            };
+            let mut found = self.find_markers(path, &content);
+            if found.is_empty() {
+                continue;
+            }
+            let blame = git(repo_path, &["blame", "--line-porcelain", "--", path])?;
+            apply_blame(&mut found, &blame, now);
+            markers.extend(found);
+        }
+
+        if let Some(min_age_days) = min_age_days {
+            markers.retain(|marker| marker.age_days.is_some_and(|age| age >= min_age_days));
+        }
+        self.sort(&mut markers, order);
+        Ok(markers)
+    }
+}
+
+impl Default for TechDebtAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fill in author, commit and age from `git blame --line-porcelain` output
+pub fn apply_blame(markers: &mut [DebtMarker], blame_output: &str, now: i64) {
+    let mut line = 0;
+    let mut commit = "";
+    let mut author = "";
+    let mut authored_at = None;
+    for entry in blame_output.lines() {
+        if let Some(field) = entry.strip_prefix("author ") {
+            author = field;
+        } else if let Some(field) = entry.strip_prefix("author-time ") {
+            authored_at = field.trim().parse::<i64>().ok();
+        } else if entry.starts_with('\t') {
+            // The content line closes the record for `line`
+            let committed = !commit.bytes().all(|b| b == b'0');
+            for marker in markers.iter_mut().filter(|marker| marker.line == line) {
+                if committed {
+                    marker.author = Some(author.to_string());
+                    marker.commit = Some(commit.to_string());
+                    marker.authored_at = authored_at;
+                    marker.age_days =
+                        authored_at.map(|time| ((now - time).max(0) / SECONDS_PER_DAY) as u64);
+                }
+            }
+        } else {
+            // Record header: `<sha> <original line> <final line> [<group size>]`
+            let mut fields = entry.split(' ');
+            let (Some(sha), Some(_), Some(final_line)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            if sha.len() >= 40 && sha.bytes().all(|b| b.is_ascii_hexdigit()) {
+                if let Ok(final_line) = final_line.parse() {
+                    commit = sha;
+                    line = final_line;
+                    author = "";
+                    authored_at = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "git")]
+fn git(repo_path: &std::path::Path, args: &[&str]) -> Result<String> {
+    use anyhow::anyhow;
+    use std::process::Command;
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_markers_in_comments_only() {
+        let content = "\
+fn main() {
+    // TODO: handle errors
+    let todo_list = vec![]; // FIXME(sam) leaks on retry
+    /* HACK: work around the parser */
+    println!(\"TODO is not a comment here\");
+}
+# XXX python-style marker
+";
+        let markers = TechDebtAnalyzer::new().find_markers("main.rs", content);
+        let found: Vec<(usize, &str, &str)> = markers
+            .iter()
+            .map(|m| (m.line, m.marker.as_str(), m.text.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, "TODO", "handle errors"),
+                (3, "FIXME", "leaks on retry"),
+                (4, "HACK", "work around the parser"),
+                (7, "XXX", "python-style marker"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_blame_skips_uncommitted_lines() {
+        let analyzer = TechDebtAnalyzer::new();
+        let mut markers = analyzer.find_markers("a.py", "# TODO one\n# TODO two\n");
+        let blame = "\
+1111111111111111111111111111111111111111 1 1 1
+author Ada
+author-time 86400
+summary old
+filename a.py
+\t# TODO one
+0000000000000000000000000000000000000000 2 2 1
+author Not Committed Yet
+author-time 864000
+filename a.py
+\t# TODO two
+";
+        apply_blame(&mut markers, blame, 10 * SECONDS_PER_DAY);
+
+        assert_eq!(markers[0].author.as_deref(), Some("Ada"));
+        assert_eq!(markers[0].age_days, Some(9));
+        assert!(markers[1].author.is_none());
+        assert!(markers[1].age_days.is_none());
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_old_todo_reports_greater_age_than_recent_one() {
+        use std::process::Command;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        // Commits without a date are authored now
+        let git = |args: &[&str], date: Option<&str>| {
+            let mut command = Command::new("git");
+            command
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args);
+            if let Some(date) = date {
+                command
+                    .env("GIT_AUTHOR_DATE", date)
+                    .env("GIT_COMMITTER_DATE", date);
+            }
+            let status = command.status().unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+
+        let old = Some("2016-03-01T12:00:00Z");
+        git(&["init", "-q"], None);
+        std::fs::write(
+            dir.join("legacy.py"),
+            "def run():\n    # TODO: remove shim\n",
+        )
+        .unwrap();
+        git(&["add", "-A"], old);
+        git(&["commit", "-q", "-m", "legacy"], old);
+
+        std::fs::write(dir.join("fresh.rs"), "// FIXME: tune buffer size\n").unwrap();
+        git(&["add", "-A"], None);
+        git(&["commit", "-q", "-m", "fresh"], None);
+
+        let analyzer = TechDebtAnalyzer::new();
+        let markers = analyzer
+            .list_tech_debt(dir, None, DebtOrder::Oldest)
+            .unwrap();
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[0].path, "legacy.py");
+        assert_eq!(markers[0].line, 2);
+        assert_eq!(markers[0].author.as_deref(), Some("test"));
+        assert_eq!(markers[1].path, "fresh.rs");
+        let old_age = markers[0].age_days.unwrap();
+        let new_age = markers[1].age_days.unwrap();
+        assert!(old_age > new_age, "{old_age} should exceed {new_age}");
+        assert!(old_age > 365);
+
+        let stale = analyzer
+            .list_tech_debt(dir, Some(365), DebtOrder::Newest)
+            .unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].marker, "TODO");
+    }
+}
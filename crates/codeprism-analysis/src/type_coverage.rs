@@ -0,0 +1,256 @@
+//! Type annotation coverage for gradually-typed code
+//!
+//! Measures how many of the places that can carry a type annotation actually
+//! do: each parameter of a function or method, plus its return type. Only
+//! Python and TypeScript are measured, since annotations are optional there
+//! and coverage is what a migration to type hints tracks. Annotations are read
+//! from the signatures the language parsers record on function nodes.
+
+use crate::data_clumps::{parameters, split_signature};
+use codeprism_core::{Language, Node, NodeKind};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Annotation counts for one function or method
+#[derive(Debug, Clone)]
+pub struct FunctionTypeCoverage {
+    pub node: Node,
+    pub parameters: usize,
+    pub annotated_parameters: usize,
+    /// Whether the return type is counted; TypeScript constructors have none
+    pub has_return: bool,
+    pub annotated_return: bool,
+}
+
+impl FunctionTypeCoverage {
+    /// Places that can carry an annotation
+    pub fn total(&self) -> usize {
+        self.parameters + usize::from(self.has_return)
+    }
+
+    /// Places that carry one
+    pub fn annotated(&self) -> usize {
+        self.annotated_parameters + usize::from(self.has_return && self.annotated_return)
+    }
+
+    /// Fraction of places annotated, between 0.0 and 1.0
+    pub fn coverage(&self) -> f64 {
+        ratio(self.annotated(), self.total())
+    }
+}
+
+/// Annotation counts summed over one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileTypeCoverage {
+    pub file: PathBuf,
+    pub language: Language,
+    pub functions: usize,
+    pub annotated: usize,
+    pub total: usize,
+}
+
+impl FileTypeCoverage {
+    /// Fraction of places annotated, between 0.0 and 1.0
+    pub fn coverage(&self) -> f64 {
+        ratio(self.annotated, self.total)
+    }
+}
+
+/// Coverage per function, per file and overall
+#[derive(Debug, Clone, Default)]
+pub struct TypeCoverageReport {
+    pub annotated: usize,
+    pub total: usize,
+    /// Files, least covered first
+    pub files: Vec<FileTypeCoverage>,
+    /// Functions, least covered first
+    pub functions: Vec<FunctionTypeCoverage>,
+}
+
+impl TypeCoverageReport {
+    /// Fraction of places annotated, between 0.0 and 1.0
+    pub fn coverage(&self) -> f64 {
+        ratio(self.annotated, self.total)
+    }
+}
+
+/// Measures type annotation coverage of Python and TypeScript functions
+pub struct TypeCoverageAnalyzer;
+
+impl TypeCoverageAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Measure the annotation coverage of `functions`
+    ///
+    /// Receivers (`self`, `cls`) and variadics (`*args`, `...rest`) are not
+    /// counted, nor are anonymous functions, which usually take their types
+    /// from the call they are passed to. Nodes in other languages or without
+    /// a signature are skipped.
+    pub fn analyze(&self, functions: &[Node]) -> TypeCoverageReport {
+        let mut functions: Vec<FunctionTypeCoverage> = functions
+            .iter()
+            .filter(|node| matches!(node.kind, NodeKind::Function | NodeKind::Method))
+            .filter(|node| matches!(node.lang, Language::Python | Language::TypeScript))
+            .filter(|node| !node.name.starts_with('<'))
+            .filter_map(function_coverage)
+            .filter(|function| function.total() > 0)
+            .collect();
+        functions.sort_by(|a, b| {
+            a.coverage()
+                .total_cmp(&b.coverage())
+                .then((b.total() - b.annotated()).cmp(&(a.total() - a.annotated())))
+                .then(a.node.file.cmp(&b.node.file))
+                .then(a.node.span.start_line.cmp(&b.node.span.start_line))
+        });
+
+        let mut files: BTreeMap<&PathBuf, FileTypeCoverage> = BTreeMap::new();
+        for function in &functions {
+            let file = files
+                .entry(&function.node.file)
+                .or_insert_with(|| FileTypeCoverage {
+                    file: function.node.file.clone(),
+                    language: function.node.lang,
+                    functions: 0,
+                    annotated: 0,
+                    total: 0,
+                });
+            file.functions += 1;
+            file.annotated += function.annotated();
+            file.total += function.total();
+        }
+        let mut files: Vec<FileTypeCoverage> = files.into_values().collect();
+        files.sort_by(|a, b| a.coverage().total_cmp(&b.coverage()));
+
+        TypeCoverageReport {
+            annotated: files.iter().map(|file| file.annotated).sum(),
+            total: files.iter().map(|file| file.total).sum(),
+            files,
+            functions,
+        }
+    }
+}
+
+impl Default for TypeCoverageAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn function_coverage(node: &Node) -> Option<FunctionTypeCoverage> {
+    let signature = node.signature.as_deref()?;
+    let (_, after_parameters) = split_signature(signature)?;
+    let params = parameters(signature, node.lang);
+    let return_annotation = match node.lang {
+        Language::Python => "->",
+        _ => ":",
+    };
+    Some(FunctionTypeCoverage {
+        node: node.clone(),
+        parameters: params.len(),
+        annotated_parameters: params
+            .iter()
+            .filter(|param| param.type_name.is_some())
+            .count(),
+        has_return: !(node.lang == Language::TypeScript && node.name == "constructor"),
+        annotated_return: after_parameters.trim_start().starts_with(return_annotation),
+    })
+}
+
+fn ratio(annotated: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        annotated as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::Span;
+
+    fn function(name: &str, file: &str, line: usize, signature: &str, lang: Language) -> Node {
+        let mut node = Node::new(
+            "repo",
+            NodeKind::Function,
+            name.to_string(),
+            lang,
+            PathBuf::from(file),
+            Span::new(line * 10, line * 10 + 5, line, line, 1, 6),
+        );
+        node.signature = Some(signature.to_string());
+        node
+    }
+
+    #[test]
+    fn test_half_typed_file_reports_untyped_function_first() {
+        let functions = vec![
+            function(
+                "typed",
+                "app.py",
+                1,
+                "typed(a: int, b: Dict[str, int] = {}) -> bool",
+                Language::Python,
+            ),
+            function(
+                "untyped",
+                "app.py",
+                4,
+                "untyped(self, a, b=2)",
+                Language::Python,
+            ),
+            function("helper", "util.js", 1, "helper(a)", Language::JavaScript),
+        ];
+
+        let report = TypeCoverageAnalyzer::new().analyze(&functions);
+
+        assert_eq!((report.annotated, report.total), (3, 6));
+        assert!((report.coverage() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(report.files[0].functions, 2);
+        assert_eq!(report.functions[0].node.name, "untyped");
+        assert_eq!(report.functions[0].coverage(), 0.0);
+        assert_eq!(report.functions[1].coverage(), 1.0);
+    }
+
+    #[test]
+    fn test_typescript_returns_and_constructors() {
+        let mut constructor = function(
+            "constructor",
+            "user.ts",
+            2,
+            "constructor(name: string)",
+            Language::TypeScript,
+        );
+        constructor.kind = NodeKind::Method;
+        let functions = vec![
+            constructor,
+            function(
+                "load",
+                "user.ts",
+                5,
+                "load(id: number, cache?): Promise<User>",
+                Language::TypeScript,
+            ),
+            function(
+                "<anonymous>",
+                "user.ts",
+                9,
+                "<anonymous>(x)",
+                Language::TypeScript,
+            ),
+        ];
+
+        let report = TypeCoverageAnalyzer::new().analyze(&functions);
+
+        assert_eq!(report.functions.len(), 2);
+        let load = &report.functions[0];
+        assert_eq!(load.node.name, "load");
+        assert_eq!((load.annotated(), load.total()), (2, 3));
+        assert!(load.annotated_return);
+        assert!(!report.functions[1].has_return);
+        assert_eq!(report.functions[1].coverage(), 1.0);
+    }
+}
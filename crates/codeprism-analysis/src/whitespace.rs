@@ -0,0 +1,354 @@
+//! Whitespace and indentation consistency
+//!
+//! Works on raw file text, so it needs no parser. A file's indentation style
+//! is the one most of its indented lines use; lines indented the other way
+//! are reported, as are indents with a space before a tab, block indents that
+//! differ from the file's usual indentation width and trailing whitespace.
+//! Tabs followed by alignment spaces count as tab indentation.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Kind of whitespace problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WhitespaceIssueKind {
+    /// A space comes before a tab in the line's indentation
+    MixedIndentation,
+    /// The line is indented with tabs in a space-indented file, or the reverse
+    IndentStyle,
+    /// A block is indented by a different width than the rest of the file
+    IndentWidth,
+    /// The line ends in spaces or tabs
+    TrailingWhitespace,
+}
+
+impl WhitespaceIssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WhitespaceIssueKind::MixedIndentation => "mixed_indentation",
+            WhitespaceIssueKind::IndentStyle => "indent_style",
+            WhitespaceIssueKind::IndentWidth => "indent_width",
+            WhitespaceIssueKind::TrailingWhitespace => "trailing_whitespace",
+        }
+    }
+}
+
+/// Dominant indentation of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tabs,
+    Spaces,
+    /// No line is indented
+    None,
+}
+
+impl IndentStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndentStyle::Tabs => "tabs",
+            IndentStyle::Spaces => "spaces",
+            IndentStyle::None => "none",
+        }
+    }
+}
+
+/// A whitespace problem on one line
+#[derive(Debug, Clone)]
+pub struct WhitespaceIssue {
+    pub kind: WhitespaceIssueKind,
+    /// Line number (1-based)
+    pub line: usize,
+    pub message: String,
+}
+
+/// Indentation summary and whitespace problems of one file
+#[derive(Debug, Clone)]
+pub struct WhitespaceReport {
+    pub file_path: String,
+    pub indent_style: IndentStyle,
+    /// Most common block indentation width of a space-indented file
+    pub indent_width: Option<usize>,
+    pub tab_indented_lines: usize,
+    pub space_indented_lines: usize,
+    /// Issues in line order
+    pub issues: Vec<WhitespaceIssue>,
+}
+
+impl WhitespaceReport {
+    /// Whether the file has no whitespace problems
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Number of issues of each kind
+    pub fn issue_counts(&self) -> BTreeMap<WhitespaceIssueKind, usize> {
+        let mut counts = BTreeMap::new();
+        for issue in &self.issues {
+            *counts.entry(issue.kind).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// An indented, non-blank line
+struct IndentedLine {
+    line: usize,
+    indent: String,
+    /// Whether the previous non-blank line opens a block (`:` or `{`)
+    opens_block: bool,
+    /// Indentation of the previous non-blank line
+    previous_indent: String,
+}
+
+/// Detects inconsistent indentation and trailing whitespace
+pub struct WhitespaceAnalyzer;
+
+impl WhitespaceAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Analyze a file on disk
+    pub fn analyze_file(&self, path: &Path) -> Result<WhitespaceReport> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(self.analyze_content(&content, path))
+    }
+
+    /// Analyze the text of a file
+    pub fn analyze_content(&self, content: &str, file_path: &Path) -> WhitespaceReport {
+        let mut issues = Vec::new();
+        let mut indented = Vec::new();
+        let mut previous: Option<&str> = None;
+
+        // `lines` also strips the `\r` of CRLF endings
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            if line.ends_with([' ', '\t']) {
+                issues.push(WhitespaceIssue {
+                    kind: WhitespaceIssueKind::TrailingWhitespace,
+                    line: line_number,
+                    message: if line.trim().is_empty() {
+                        "Line contains only whitespace".to_string()
+                    } else {
+                        "Trailing whitespace".to_string()
+                    },
+                });
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let content_start = line.len() - line.trim_start_matches([' ', '\t']).len();
+            let indent = &line[..content_start];
+            if indent.contains(" \t") {
+                issues.push(WhitespaceIssue {
+                    kind: WhitespaceIssueKind::MixedIndentation,
+                    line: line_number,
+                    message: "Indentation has a space before a tab".to_string(),
+                });
+            }
+            if !indent.is_empty() {
+                let previous_line = previous.unwrap_or("");
+                indented.push(IndentedLine {
+                    line: line_number,
+                    indent: indent.to_string(),
+                    opens_block: previous_line.trim_end().ends_with([':', '{']),
+                    previous_indent: leading_whitespace(previous_line).to_string(),
+                });
+            }
+            previous = Some(line);
+        }
+
+        let tab_indented_lines = indented
+            .iter()
+            .filter(|line| line.indent.starts_with('\t'))
+            .count();
+        let space_indented_lines = indented.len() - tab_indented_lines;
+        let indent_style = if indented.is_empty() {
+            IndentStyle::None
+        } else if tab_indented_lines > space_indented_lines {
+            IndentStyle::Tabs
+        } else {
+            IndentStyle::Spaces
+        };
+
+        for line in &indented {
+            let uses_tabs = line.indent.starts_with('\t');
+            let message = match indent_style {
+                IndentStyle::Spaces if uses_tabs => {
+                    "Indented with tabs; the file mostly uses spaces"
+                }
+                IndentStyle::Tabs if !uses_tabs => {
+                    "Indented with spaces; the file mostly uses tabs"
+                }
+                _ => continue,
+            };
+            issues.push(WhitespaceIssue {
+                kind: WhitespaceIssueKind::IndentStyle,
+                line: line.line,
+                message: message.to_string(),
+            });
+        }
+
+        let indent_width = if indent_style == IndentStyle::Spaces {
+            let steps = block_indent_steps(&indented);
+            let width = most_common(steps.iter().map(|&(_, step)| step));
+            if let Some(width) = width {
+                for (line, step) in steps.into_iter().filter(|&(_, step)| step != width) {
+                    issues.push(WhitespaceIssue {
+                        kind: WhitespaceIssueKind::IndentWidth,
+                        line,
+                        message: format!(
+                            "Block indented by {step} spaces; the file mostly indents by {width}"
+                        ),
+                    });
+                }
+            }
+            width
+        } else {
+            None
+        };
+
+        issues.sort_by_key(|issue| (issue.line, issue.kind));
+        WhitespaceReport {
+            file_path: file_path.display().to_string(),
+            indent_style,
+            indent_width,
+            tab_indented_lines,
+            space_indented_lines,
+            issues,
+        }
+    }
+
+    /// Get recommendations for the reports of the analyzed files
+    pub fn get_recommendations(&self, reports: &[WhitespaceReport]) -> Vec<String> {
+        let has = |kind| {
+            reports
+                .iter()
+                .flat_map(|report| &report.issues)
+                .any(|issue| issue.kind == kind)
+        };
+        let mut recommendations = Vec::new();
+        if has(WhitespaceIssueKind::IndentStyle) || has(WhitespaceIssueKind::MixedIndentation) {
+            recommendations
+                .push("Indent each file consistently with either tabs or spaces".to_string());
+        }
+        if has(WhitespaceIssueKind::IndentWidth) {
+            recommendations.push("Use one indentation width throughout each file".to_string());
+        }
+        if has(WhitespaceIssueKind::TrailingWhitespace) {
+            recommendations.push(
+                "Strip trailing whitespace, e.g. with an editor setting or a pre-commit hook"
+                    .to_string(),
+            );
+        }
+        if !recommendations.is_empty() {
+            recommendations.push(
+                "Add an .editorconfig or a formatter to keep whitespace consistent".to_string(),
+            );
+        }
+        recommendations
+    }
+}
+
+impl Default for WhitespaceAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Indentation increases, in spaces, of lines opening a block
+///
+/// Only lines that follow a `:` or `{` and whose indentation and that of the
+/// previous line are all spaces count, which leaves continuation and
+/// alignment indents out.
+fn block_indent_steps(lines: &[IndentedLine]) -> Vec<(usize, usize)> {
+    lines
+        .iter()
+        .filter(|line| line.opens_block)
+        .filter(|line| {
+            line.indent.bytes().all(|b| b == b' ')
+                && line.previous_indent.bytes().all(|b| b == b' ')
+        })
+        .filter(|line| line.indent.len() > line.previous_indent.len())
+        .map(|line| (line.line, line.indent.len() - line.previous_indent.len()))
+        .collect()
+}
+
+/// Most frequent value, preferring the smaller one on ties
+fn most_common(values: impl Iterator<Item = usize>) -> Option<usize> {
+    let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(value, _)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(content: &str) -> WhitespaceReport {
+        WhitespaceAnalyzer::new().analyze_content(content, Path::new("sample.py"))
+    }
+
+    #[test]
+    fn test_reports_tabs_in_space_indented_file() {
+        let report = analyze(
+            "def load(path):\n    with open(path) as f:\n        data = f.read()\n\treturn data \n",
+        );
+
+        assert_eq!(report.indent_style, IndentStyle::Spaces);
+        assert_eq!(report.indent_width, Some(4));
+        assert_eq!(report.tab_indented_lines, 1);
+        assert_eq!(report.space_indented_lines, 2);
+        let found: Vec<(WhitespaceIssueKind, usize)> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.kind, issue.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (WhitespaceIssueKind::IndentStyle, 4),
+                (WhitespaceIssueKind::TrailingWhitespace, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_odd_block_width_and_space_before_tab() {
+        let report = analyze(
+            "def a():\n    x = 1\n    if x:\n        return x\n\n\ndef b():\n  return 2\n\n\ndef c():\n \tpass\n",
+        );
+
+        let kinds: Vec<(WhitespaceIssueKind, usize)> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.kind, issue.line))
+            .collect();
+        assert!(kinds.contains(&(WhitespaceIssueKind::IndentWidth, 8)));
+        assert!(kinds.contains(&(WhitespaceIssueKind::MixedIndentation, 12)));
+        assert_eq!(report.indent_width, Some(4));
+    }
+
+    #[test]
+    fn test_clean_file_has_no_findings() {
+        let report = analyze(
+            "class Greeter:\n    def greet(self, name):\n        message = (\n            \"Hello, \" + name\n        )\n        return message\r\n",
+        );
+
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert!(WhitespaceAnalyzer::new()
+            .get_recommendations(&[report])
+            .is_empty());
+    }
+}
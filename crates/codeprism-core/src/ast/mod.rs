@@ -66,6 +66,12 @@ pub enum NodeKind {
     Module,
     /// A class definition
     Class,
+    /// An interface declaration
+    Interface,
+    /// A type alias
+    TypeAlias,
+    /// An enum declaration
+    Enum,
     /// A function definition
     Function,
     /// A method definition
@@ -95,6 +101,9 @@ impl fmt::Display for NodeKind {
         match self {
             NodeKind::Module => write!(f, "Module"),
             NodeKind::Class => write!(f, "Class"),
+            NodeKind::Interface => write!(f, "Interface"),
+            NodeKind::TypeAlias => write!(f, "TypeAlias"),
+            NodeKind::Enum => write!(f, "Enum"),
             NodeKind::Function => write!(f, "Function"),
             NodeKind::Method => write!(f, "Method"),
             NodeKind::Parameter => write!(f, "Parameter"),
@@ -224,6 +233,12 @@ pub enum Language {
     Go,
     /// Rust
     Rust,
+    /// Ruby
+    Ruby,
+    /// Kotlin
+    Kotlin,
+    /// PHP
+    Php,
     /// C
     C,
     /// C++
@@ -242,6 +257,9 @@ impl Language {
             "java" => Language::Java,
             "go" => Language::Go,
             "rs" => Language::Rust,
+            "rb" | "rake" => Language::Ruby,
+            "kt" | "kts" => Language::Kotlin,
+            "php" => Language::Php,
             "c" | "h" => Language::C,
             "cpp" | "cc" | "cxx" | "hpp" | "hxx" => Language::Cpp,
             _ => Language::Unknown,
@@ -258,6 +276,9 @@ impl fmt::Display for Language {
             Language::Java => write!(f, "Java"),
             Language::Go => write!(f, "Go"),
             Language::Rust => write!(f, "Rust"),
+            Language::Ruby => write!(f, "Ruby"),
+            Language::Kotlin => write!(f, "Kotlin"),
+            Language::Php => write!(f, "PHP"),
             Language::C => write!(f, "C"),
             Language::Cpp => write!(f, "C++"),
             Language::Unknown => write!(f, "Unknown"),
@@ -549,6 +570,11 @@ mod tests {
         assert_eq!(Language::from_extension("mjs"), Language::JavaScript);
         assert_eq!(Language::from_extension("cjs"), Language::JavaScript);
         assert_eq!(Language::from_extension("tsx"), Language::TypeScript);
+        assert_eq!(Language::from_extension("rb"), Language::Ruby);
+        assert_eq!(Language::from_extension("rake"), Language::Ruby);
+        assert_eq!(Language::from_extension("kt"), Language::Kotlin);
+        assert_eq!(Language::from_extension("kts"), Language::Kotlin);
+        assert_eq!(Language::from_extension("php"), Language::Php);
 
         // C++ variations
         assert_eq!(Language::from_extension("cpp"), Language::Cpp);
@@ -33,6 +33,8 @@ impl CommentExtractor {
         );
         extractors.insert(Language::Python, Box::new(PythonCommentExtractor::new()));
         extractors.insert(Language::Java, Box::new(JavaCommentExtractor::new()));
+        extractors.insert(Language::Kotlin, Box::new(KotlinCommentExtractor::new()));
+        extractors.insert(Language::Php, Box::new(PhpCommentExtractor::new()));
         extractors.insert(Language::Rust, Box::new(RustCommentExtractor::new()));
         extractors.insert(Language::C, Box::new(CCommentExtractor::new()));
         extractors.insert(Language::Cpp, Box::new(CCommentExtractor::new()));
@@ -59,6 +61,118 @@ impl CommentExtractor {
         extractor.extract_comments(tree, source, file_path, ast_nodes)
     }
 
+    /// Extract comments from source code without a parse tree
+    pub fn extract_source_comments(
+        &self,
+        language: Language,
+        source: &str,
+        file_path: &Path,
+    ) -> Result<Vec<ContentChunk>> {
+        let extractor = self
+            .language_extractors
+            .get(&language)
+            .ok_or_else(|| anyhow!("No comment extractor for language: {:?}", language))?;
+
+        extractor.extract_source_comments(source, file_path)
+    }
+
+    /// Find the doc comment of the symbol defined at `span`
+    ///
+    /// Comments ending right above the definition are joined, skipping
+    /// attribute and decorator lines in between. Python definitions use the
+    /// docstring opening their body instead, falling back to comments above.
+    pub fn doc_comment(
+        &self,
+        language: Language,
+        source: &str,
+        file_path: &Path,
+        span: &Span,
+    ) -> Option<String> {
+        let mut comments = self
+            .extract_source_comments(language, source, file_path)
+            .ok()?;
+        comments.sort_by_key(|chunk| chunk.span.start_byte);
+
+        if language == Language::Python {
+            if let Some(docstring) = Self::python_docstring(&comments, source, span) {
+                return Some(docstring);
+            }
+        }
+
+        let lines: Vec<&str> = source.lines().collect();
+        let mut line = span.start_line;
+        while line > 1 {
+            let above = lines.get(line - 2).map(|l| l.trim()).unwrap_or_default();
+            if above.starts_with("#[") || above.starts_with('@') {
+                line -= 1;
+            } else {
+                break;
+            }
+        }
+
+        // Python docstrings above a definition belong to something else
+        let comments: Vec<&ContentChunk> = comments
+            .iter()
+            .filter(|chunk| language != Language::Python || !Self::is_documentation(chunk))
+            .filter(|chunk| {
+                source[..chunk.span.start_byte]
+                    .rsplit('\n')
+                    .next()
+                    .is_some_and(|before| before.trim().is_empty())
+            })
+            .collect();
+        let mut doc = Vec::new();
+        while let Some(chunk) = comments
+            .iter()
+            .rev()
+            .find(|chunk| chunk.span.end_line + 1 == line && chunk.span.start_line < line)
+        {
+            doc.push(
+                chunk
+                    .content
+                    .trim_start_matches(['/', '!', '*'])
+                    .trim()
+                    .to_string(),
+            );
+            line = chunk.span.start_line;
+        }
+        doc.reverse();
+        let doc = doc.join("\n");
+        (!doc.trim().is_empty()).then_some(doc)
+    }
+
+    /// Docstring that is the first statement of the Python definition at `span`
+    fn python_docstring(comments: &[ContentChunk], source: &str, span: &Span) -> Option<String> {
+        let docstring = comments.iter().find(|chunk| {
+            Self::is_documentation(chunk)
+                && chunk.span.start_byte > span.start_byte
+                && chunk.span.end_byte <= span.end_byte
+        })?;
+
+        // Only the header may come before it, so the last line ends in its colon
+        let header = source.get(span.start_byte..docstring.span.start_byte)?;
+        let header_lines: Vec<&str> = header
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let colons = header_lines
+            .iter()
+            .filter(|line| line.ends_with(':'))
+            .count();
+        (colons == 1 && header_lines.last()?.ends_with(':')).then(|| docstring.content.clone())
+    }
+
+    fn is_documentation(chunk: &ContentChunk) -> bool {
+        matches!(
+            chunk.content_type,
+            ContentType::Comment {
+                context: CommentContext::Documentation,
+                ..
+            }
+        )
+    }
+
     /// Check if a language is supported
     pub fn supports_language(&self, language: Language) -> bool {
         self.language_extractors.contains_key(&language)
@@ -79,13 +193,20 @@ impl Default for CommentExtractor {
 /// Trait for language-specific comment extraction
 pub trait LanguageCommentExtractor: Send + Sync {
     /// Extract comments from source code
+    ///
+    /// Defaults to scanning the source text alone.
     fn extract_comments(
         &self,
-        tree: &Tree,
+        _tree: &Tree,
         source: &str,
         file_path: &Path,
-        ast_nodes: &[NodeId],
-    ) -> Result<Vec<ContentChunk>>;
+        _ast_nodes: &[NodeId],
+    ) -> Result<Vec<ContentChunk>> {
+        self.extract_source_comments(source, file_path)
+    }
+
+    /// Extract comments from source code without a parse tree
+    fn extract_source_comments(&self, source: &str, file_path: &Path) -> Result<Vec<ContentChunk>>;
 
     /// Get the comment patterns for this language
     fn comment_patterns(&self) -> &CommentPatterns;
@@ -129,13 +250,7 @@ impl JavaScriptCommentExtractor {
 }
 
 impl LanguageCommentExtractor for JavaScriptCommentExtractor {
-    fn extract_comments(
-        &self,
-        _tree: &Tree,
-        source: &str,
-        file_path: &Path,
-        _ast_nodes: &[NodeId],
-    ) -> Result<Vec<ContentChunk>> {
+    fn extract_source_comments(&self, source: &str, file_path: &Path) -> Result<Vec<ContentChunk>> {
         let mut chunks = Vec::new();
         let mut chunk_index = 0;
 
@@ -284,13 +399,7 @@ impl PythonCommentExtractor {
 }
 
 impl LanguageCommentExtractor for PythonCommentExtractor {
-    fn extract_comments(
-        &self,
-        _tree: &Tree,
-        source: &str,
-        file_path: &Path,
-        _ast_nodes: &[NodeId],
-    ) -> Result<Vec<ContentChunk>> {
+    fn extract_source_comments(&self, source: &str, file_path: &Path) -> Result<Vec<ContentChunk>> {
         let mut chunks = Vec::new();
         let mut chunk_index = 0;
 
@@ -436,12 +545,10 @@ macro_rules! simple_comment_extractor {
         }
 
         impl LanguageCommentExtractor for $name {
-            fn extract_comments(
+            fn extract_source_comments(
                 &self,
-                _tree: &Tree,
                 source: &str,
                 file_path: &Path,
-                _ast_nodes: &[NodeId],
             ) -> Result<Vec<ContentChunk>> {
                 let mut chunks = Vec::new();
                 let single_line_regex =
@@ -558,6 +665,8 @@ macro_rules! simple_comment_extractor {
 
 // Generate simple extractors for other languages
 simple_comment_extractor!(JavaCommentExtractor, Java, "//", "/*", "*/");
+simple_comment_extractor!(KotlinCommentExtractor, Kotlin, "//", "/*", "*/");
+simple_comment_extractor!(PhpCommentExtractor, Php, "//", "/*", "*/");
 simple_comment_extractor!(RustCommentExtractor, Rust, "//", "/*", "*/");
 simple_comment_extractor!(CCommentExtractor, C, "//", "/*", "*/");
 
@@ -578,6 +687,63 @@ mod tests {
         assert!(supported.contains(&Language::Python));
     }
 
+    #[test]
+    fn test_doc_comment_above_definitions() {
+        let extractor = CommentExtractor::new();
+        let rust = "let x = 1; // not a doc\n/// Adds two numbers.\n///\n/// Wraps on overflow.\n#[inline]\npub fn add(a: u8, b: u8) -> u8 {\n    a.wrapping_add(b)\n}\n";
+        let add = Span::new(0, 0, 6, 8, 1, 2);
+        assert_eq!(
+            extractor
+                .doc_comment(Language::Rust, rust, Path::new("lib.rs"), &add)
+                .as_deref(),
+            Some("Adds two numbers.\n\nWraps on overflow.")
+        );
+
+        let js = "/**\n * Greets a user.\n * @param {string} name\n */\nfunction greet(name) {}\nlet y = 2; // trailing\nfunction bare() {}\n";
+        let greet = Span::new(0, 0, 5, 5, 1, 23);
+        assert_eq!(
+            extractor
+                .doc_comment(Language::JavaScript, js, Path::new("app.js"), &greet)
+                .as_deref(),
+            Some("Greets a user.\n@param {string} name")
+        );
+        // A trailing comment on the line above documents that line
+        let bare = Span::new(0, 0, 7, 7, 1, 19);
+        assert_eq!(
+            extractor.doc_comment(Language::JavaScript, js, Path::new("app.js"), &bare),
+            None
+        );
+    }
+
+    #[test]
+    fn test_python_docstring_must_open_the_body() {
+        let extractor = CommentExtractor::new();
+        let path = Path::new("app.py");
+        let source = "def outer(\n    a,\n):\n    x = a\n    def inner():\n        \"\"\"Inner docs.\"\"\"\n    return x\n\n\n# Says hello.\ndef hello():\n    '''Hello docs.'''\n";
+        let outer = Span::new(0, source.find("\n\n\n").unwrap(), 1, 7, 1, 13);
+        assert_eq!(
+            extractor.doc_comment(Language::Python, source, path, &outer),
+            None
+        );
+
+        let start = source.find("def hello").unwrap();
+        let hello = Span::new(start, source.len(), 11, 12, 1, 22);
+        assert_eq!(
+            extractor
+                .doc_comment(Language::Python, source, path, &hello)
+                .as_deref(),
+            Some("Hello docs.")
+        );
+        let without_docstring = source.replace("    '''Hello docs.'''\n", "    pass\n");
+        let hello = Span::new(start, without_docstring.len(), 11, 12, 1, 9);
+        assert_eq!(
+            extractor
+                .doc_comment(Language::Python, &without_docstring, path, &hello)
+                .as_deref(),
+            Some("Says hello.")
+        );
+    }
+
     #[test]
     fn test_javascript_comment_patterns() {
         let extractor = JavaScriptCommentExtractor::new();
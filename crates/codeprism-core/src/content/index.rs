@@ -5,7 +5,7 @@
 
 use super::{
     ChunkId, ContentChunk, ContentNode, ContentStats, ContentType, ContentUpdate,
-    ContentUpdateKind, SearchMatch, SearchQuery, SearchResult,
+    ContentUpdateKind, ContextLine, SearchMatch, SearchQuery, SearchResult,
 };
 
 use anyhow::Result;
@@ -440,52 +440,20 @@ impl ContentIndex {
         if let Some(regex) = regex {
             // Regex search
             for regex_match in regex.find_iter(&content) {
-                let line_info = self.calculate_line_info(&content, regex_match.start());
-                let search_match = SearchMatch {
-                    text: regex_match.as_str().to_string(),
-                    position: regex_match.start(),
-                    line_number: line_info.0,
-                    column_number: line_info.1,
-                    context_before: if query.include_context {
-                        self.get_context_before(&content, regex_match.start(), query.context_lines)
-                    } else {
-                        None
-                    },
-                    context_after: if query.include_context {
-                        self.get_context_after(&content, regex_match.end(), query.context_lines)
-                    } else {
-                        None
-                    },
-                };
-                matches.push(search_match);
+                matches.push(self.build_match(
+                    chunk,
+                    &content,
+                    regex_match.start(),
+                    regex_match.as_str(),
+                    query,
+                ));
             }
         } else {
             // Simple text search
             let mut start = 0;
             while let Some(pos) = content[start..].find(&search_term) {
                 let absolute_pos = start + pos;
-                let line_info = self.calculate_line_info(&content, absolute_pos);
-                let search_match = SearchMatch {
-                    text: search_term.clone(),
-                    position: absolute_pos,
-                    line_number: line_info.0,
-                    column_number: line_info.1,
-                    context_before: if query.include_context {
-                        self.get_context_before(&content, absolute_pos, query.context_lines)
-                    } else {
-                        None
-                    },
-                    context_after: if query.include_context {
-                        self.get_context_after(
-                            &content,
-                            absolute_pos + search_term.len(),
-                            query.context_lines,
-                        )
-                    } else {
-                        None
-                    },
-                };
-                matches.push(search_match);
+                matches.push(self.build_match(chunk, &content, absolute_pos, &search_term, query));
                 start = absolute_pos + 1;
             }
         }
@@ -493,68 +461,91 @@ impl ContentIndex {
         Ok(matches)
     }
 
-    /// Calculate line and column information for a position
+    /// Describe a match starting at byte `position` of `searched`
+    ///
+    /// `searched` is the chunk content as searched, possibly lowercased, so
+    /// the matched line and its context are read from the original content
+    /// by line number. Context stops at the first and last line.
+    fn build_match(
+        &self,
+        chunk: &ContentChunk,
+        searched: &str,
+        position: usize,
+        text: &str,
+        query: &SearchQuery,
+    ) -> SearchMatch {
+        let (line_number, column_number) = self.calculate_line_info(searched, position);
+        let lines: Vec<&str> = chunk.content.lines().collect();
+        let context_lines = if query.include_context {
+            query.context_lines
+        } else {
+            0
+        };
+
+        // Start of the matched line in the original content
+        let line_offset: usize = chunk
+            .content
+            .split_inclusive('\n')
+            .take(line_number - 1)
+            .map(str::len)
+            .sum();
+        let first = (line_number - 1).saturating_sub(context_lines);
+        let last = (line_number - 1 + context_lines).min(lines.len().saturating_sub(1));
+        let context = (first..=last)
+            .filter(|&index| index != line_number - 1 && index < lines.len())
+            .map(|index| ContextLine {
+                line_number: index + 1,
+                text: lines[index].to_string(),
+            })
+            .collect();
+
+        SearchMatch {
+            text: text.to_string(),
+            position,
+            line_number,
+            column_number,
+            context_before: self.get_context_before(&chunk.content, line_offset, context_lines),
+            context_after: self.get_context_after(&chunk.content, line_offset, context_lines),
+            matched_line: lines
+                .get(line_number - 1)
+                .copied()
+                .unwrap_or_default()
+                .to_string(),
+            context,
+        }
+    }
+
+    /// Calculate line and column information for a position, both 1-indexed
     fn calculate_line_info(&self, content: &str, position: usize) -> (usize, usize) {
         let before_position = &content[..position.min(content.len())];
-        let line_number = before_position.lines().count();
-        let column_number = before_position
-            .lines()
-            .last()
-            .map(|line| line.len() + 1)
-            .unwrap_or(1);
-        (line_number, column_number)
+        let line_start = before_position.rfind('\n').map_or(0, |newline| newline + 1);
+        let line_number = before_position.matches('\n').count() + 1;
+        (line_number, before_position.len() - line_start + 1)
     }
 
-    /// Get context lines before a position
+    /// Get context lines before the line containing a position
     fn get_context_before(
         &self,
         content: &str,
         position: usize,
         context_lines: usize,
     ) -> Option<String> {
-        if context_lines == 0 {
-            return None;
-        }
-
-        let lines: Vec<&str> = content.lines().collect();
         let (line_number, _) = self.calculate_line_info(content, position);
-
-        if line_number == 0 {
-            return None;
-        }
-
-        let start_line = line_number.saturating_sub(context_lines + 1);
-        let end_line = line_number.saturating_sub(1);
-
-        if start_line >= lines.len() || end_line >= lines.len() || start_line > end_line {
-            return None;
-        }
-
-        Some(lines[start_line..=end_line].join("\n"))
+        let line_index = line_number - 1;
+        let lines: Vec<&str> = content.lines().collect();
+        join_lines(&lines, line_index.saturating_sub(context_lines)..line_index)
     }
 
-    /// Get context lines after a position
+    /// Get context lines after the line containing a position
     fn get_context_after(
         &self,
         content: &str,
         position: usize,
         context_lines: usize,
     ) -> Option<String> {
-        if context_lines == 0 {
-            return None;
-        }
-
-        let lines: Vec<&str> = content.lines().collect();
         let (line_number, _) = self.calculate_line_info(content, position);
-
-        let start_line = line_number;
-        let end_line = (start_line + context_lines).min(lines.len().saturating_sub(1));
-
-        if start_line >= lines.len() || start_line > end_line {
-            return None;
-        }
-
-        Some(lines[start_line..=end_line].join("\n"))
+        let lines: Vec<&str> = content.lines().collect();
+        join_lines(&lines, line_number..line_number + context_lines)
     }
 
     /// Calculate relevance score for a search result
@@ -747,6 +738,13 @@ impl ContentUpdateListener for LoggingUpdateListener {
     }
 }
 
+/// Join the lines in `range`, clamped to the available lines
+fn join_lines(lines: &[&str], range: std::ops::Range<usize>) -> Option<String> {
+    let end = range.end.min(lines.len());
+    let start = range.start.min(end);
+    (start < end).then(|| lines[start..end].join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1200,6 +1198,53 @@ mod tests {
         assert!(search_match.context_after.is_some(), "Should have value");
     }
 
+    #[test]
+    fn test_search_context_is_clamped_at_file_edges() {
+        let index = ContentIndex::new();
+
+        let file_path = Path::new("notes.md");
+        let content = "Target at the top\nsecond\nthird\nfourth\nLast TARGET";
+        let chunk = create_test_chunk(
+            file_path,
+            content,
+            ContentType::Documentation {
+                format: DocumentFormat::Markdown,
+            },
+            1,
+        );
+        let _ = index.add_node(create_test_node(file_path, vec![chunk]));
+
+        let search_query = SearchQuery {
+            query: "target".to_string(),
+            context_lines: 2,
+            ..Default::default()
+        };
+        let results = index.search(&search_query).unwrap();
+        let matches = &results[0].matches;
+        assert_eq!(matches.len(), 2);
+
+        let first = &matches[0];
+        assert_eq!(first.line_number, 1);
+        assert_eq!(first.column_number, 1);
+        assert_eq!(first.matched_line, "Target at the top");
+        let context: Vec<(usize, &str)> = first
+            .context
+            .iter()
+            .map(|line| (line.line_number, line.text.as_str()))
+            .collect();
+        assert_eq!(context, vec![(2, "second"), (3, "third")]);
+        assert!(first.context_before.is_none());
+
+        let last = &matches[1];
+        assert_eq!(last.line_number, 5);
+        assert_eq!(last.column_number, 6);
+        assert_eq!(last.matched_line, "Last TARGET");
+        let context: Vec<usize> = last.context.iter().map(|line| line.line_number).collect();
+        assert_eq!(context, vec![3, 4]);
+        assert!(last.context_after.is_none());
+        assert_eq!(last.context_before.as_deref(), Some("third\nfourth"));
+    }
+
     #[test]
     fn test_search_case_sensitive() {
         let index = ContentIndex::new();
@@ -1547,6 +1592,8 @@ mod tests {
                 column_number: 1,
                 context_before: None,
                 context_after: None,
+                matched_line: "Test document with multiple test occurrences".to_string(),
+                context: Vec::new(),
             },
             SearchMatch {
                 text: "test".to_string(),
@@ -1555,6 +1602,8 @@ mod tests {
                 column_number: 31,
                 context_before: None,
                 context_after: None,
+                matched_line: "Test document with multiple test occurrences".to_string(),
+                context: Vec::new(),
             },
         ];
 
@@ -368,6 +368,54 @@ impl Default for SearchQuery {
     }
 }
 
+/// Which files the content index accepts
+///
+/// Content types match by variant, as in [`SearchQuery`], so
+/// `ContentType::Configuration { format: ConfigFormat::Json }` stands for every
+/// configuration format. Globs are matched against both the full path and the
+/// file name. Exclusions take precedence over inclusions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentIndexingConfig {
+    /// Content types to index; empty indexes every type
+    pub content_types: Vec<ContentType>,
+    /// Content types never indexed
+    pub exclude_content_types: Vec<ContentType>,
+    /// File globs to index; empty indexes every file
+    pub file_patterns: Vec<String>,
+    /// File globs never indexed
+    pub exclude_patterns: Vec<String>,
+}
+
+impl Default for ContentIndexingConfig {
+    /// Index everything except lockfiles and minified or bundled assets
+    fn default() -> Self {
+        Self {
+            content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
+            file_patterns: Vec::new(),
+            exclude_patterns: [
+                "*.min.js",
+                "*.min.css",
+                "*.bundle.js",
+                "*.map",
+                "package-lock.json",
+                "npm-shrinkwrap.json",
+                "yarn.lock",
+                "pnpm-lock.yaml",
+                "Cargo.lock",
+                "poetry.lock",
+                "Pipfile.lock",
+                "Gemfile.lock",
+                "composer.lock",
+                "go.sum",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
 /// Search result for a content match
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -396,6 +444,21 @@ pub struct SearchMatch {
     pub context_before: Option<String>,
     /// Context after the match
     pub context_after: Option<String>,
+    /// Full text of the line containing the match
+    #[serde(default)]
+    pub matched_line: String,
+    /// Up to `SearchQuery::context_lines` lines before and after the match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<ContextLine>,
+}
+
+/// A line surrounding a search match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextLine {
+    /// Line number (1-indexed)
+    pub line_number: usize,
+    /// Line text without the line terminator
+    pub text: String,
 }
 
 /// Update event for content changes
@@ -714,6 +777,8 @@ mod tests {
             column_number: 19,
             context_before: Some("Test content with ".to_string()),
             context_after: Some(" match".to_string()),
+            matched_line: "Test content with query match".to_string(),
+            context: Vec::new(),
         };
 
         let result = SearchResult {
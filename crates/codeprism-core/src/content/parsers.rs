@@ -62,7 +62,7 @@ impl DocumentParser {
     }
 
     /// Detect content type from file extension
-    fn detect_content_type(&self, file_path: &Path) -> Result<ContentType> {
+    pub(crate) fn detect_content_type(&self, file_path: &Path) -> Result<ContentType> {
         // Handle special files without extensions first
         if let Some(file_name) = file_path.file_name().and_then(|n| n.to_str()) {
             if file_name == ".env" {
@@ -7,12 +7,13 @@ use super::{
     extractors::CommentExtractor,
     index::{ContentIndex, ContentUpdateListener},
     parsers::DocumentParser,
-    CommentContext, ConfigFormat, ContentChunk, ContentNode, ContentStats, ContentType,
-    DocumentFormat, SearchQuery, SearchResult,
+    CommentContext, ConfigFormat, ContentChunk, ContentIndexingConfig, ContentNode, ContentStats,
+    ContentType, DocumentFormat, SearchQuery, SearchResult,
 };
 use crate::ast::{Language, NodeId};
 use crate::graph::GraphStore;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -28,17 +29,39 @@ pub struct ContentSearchManager {
     comment_extractor: CommentExtractor,
     /// Graph store reference for AST integration
     graph_store: Option<Arc<GraphStore>>,
+    /// Content types and files accepted for indexing
+    config: ContentIndexingConfig,
+    /// Compiled `config.file_patterns`; `None` accepts every file
+    include_globs: Option<GlobSet>,
+    /// Compiled `config.exclude_patterns`
+    exclude_globs: GlobSet,
 }
 
 impl ContentSearchManager {
-    /// Create a new content search manager
+    /// Create a new content search manager with the default indexing config
     pub fn new() -> Self {
-        Self {
+        Self::new_with_config(ContentIndexingConfig::default())
+            .expect("default content indexing globs are valid")
+    }
+
+    /// Create a content search manager that only indexes what `config` accepts
+    pub fn new_with_config(config: ContentIndexingConfig) -> Result<Self> {
+        let include_globs = if config.file_patterns.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&config.file_patterns)?)
+        };
+        let exclude_globs = build_glob_set(&config.exclude_patterns)?;
+
+        Ok(Self {
             index: Arc::new(ContentIndex::new()),
             document_parser: DocumentParser::new(),
             comment_extractor: CommentExtractor::new(),
             graph_store: None,
-        }
+            config,
+            include_globs,
+            exclude_globs,
+        })
     }
 
     /// Create with graph store integration
@@ -48,8 +71,55 @@ impl ContentSearchManager {
         manager
     }
 
+    /// Whether the indexing config accepts `file_path`
+    pub fn should_index(&self, file_path: &Path) -> bool {
+        let matches = |globs: &GlobSet| {
+            globs.is_match(file_path)
+                || file_path
+                    .file_name()
+                    .is_some_and(|file_name| globs.is_match(file_name))
+        };
+        if matches(&self.exclude_globs) {
+            return false;
+        }
+        if self
+            .include_globs
+            .as_ref()
+            .is_some_and(|globs| !matches(globs))
+        {
+            return false;
+        }
+
+        let content_type = self.content_type_of(file_path);
+        let listed = |types: &[ContentType]| {
+            types.iter().any(|listed| {
+                std::mem::discriminant(listed) == std::mem::discriminant(&content_type)
+            })
+        };
+        !listed(&self.config.exclude_content_types)
+            && (self.config.content_types.is_empty() || listed(&self.config.content_types))
+    }
+
+    /// Classify a file the way [`Self::index_file`] would
+    fn content_type_of(&self, file_path: &Path) -> ContentType {
+        match self.detect_language(file_path) {
+            Some(language) if self.is_source_code_language(language) => {
+                ContentType::Code { language }
+            }
+            _ => self
+                .document_parser
+                .detect_content_type(file_path)
+                .unwrap_or(ContentType::PlainText),
+        }
+    }
+
     /// Index a file's content
+    ///
+    /// Files rejected by the indexing config are skipped without error.
     pub fn index_file(&self, file_path: &Path, content: &str) -> Result<()> {
+        if !self.should_index(file_path) {
+            return Ok(());
+        }
         let language = self.detect_language(file_path);
 
         let content_node = match language {
@@ -75,6 +145,9 @@ impl ContentSearchManager {
         language: Language,
         ast_nodes: &[NodeId],
     ) -> Result<()> {
+        if !self.should_index(file_path) {
+            return Ok(());
+        }
         let mut content_node = self.index_source_file(file_path, content, language)?;
 
         // Extract comments from the parse tree
@@ -334,6 +407,9 @@ impl ContentSearchManager {
                 | Language::Cpp
                 | Language::C
                 | Language::Go
+                | Language::Ruby
+                | Language::Kotlin
+                | Language::Php
         )
     }
 
@@ -378,6 +454,15 @@ impl ContentSearchManager {
     }
 }
 
+/// Compile glob patterns, reporting the first invalid one
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).map_err(|e| anyhow!("Invalid glob '{pattern}': {e}"))?);
+    }
+    Ok(builder.build()?)
+}
+
 impl Default for ContentSearchManager {
     fn default() -> Self {
         Self::new()
@@ -658,6 +743,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_default_config_skips_minified_and_lockfiles() {
+        let manager = ContentSearchManager::new();
+
+        for file in ["dist/app.min.js", "web/package-lock.json"] {
+            manager.index_file(Path::new(file), "{\"a\":1}").unwrap();
+            assert!(manager.get_node(Path::new(file)).is_none(), "{file}");
+        }
+        manager
+            .index_file(Path::new("src/app.js"), "function app() {}")
+            .unwrap();
+        assert!(manager.get_node(Path::new("src/app.js")).is_some());
+    }
+
+    #[test]
+    fn test_config_filters_by_content_type_and_glob() {
+        let manager = ContentSearchManager::new_with_config(ContentIndexingConfig {
+            content_types: vec![
+                ContentType::Documentation {
+                    format: DocumentFormat::Markdown,
+                },
+                ContentType::Configuration {
+                    format: ConfigFormat::Json,
+                },
+            ],
+            exclude_patterns: vec!["docs/generated/**".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(manager.should_index(Path::new("docs/guide.rst")));
+        assert!(manager.should_index(Path::new("config.yaml")));
+        assert!(!manager.should_index(Path::new("docs/generated/api.md")));
+        assert!(!manager.should_index(Path::new("src/main.py")));
+        assert!(!manager.should_index(Path::new("notes.log")));
+
+        assert!(
+            ContentSearchManager::new_with_config(ContentIndexingConfig {
+                exclude_patterns: vec!["[".to_string()],
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_simple_search() {
         let manager = ContentSearchManager::new();
@@ -5,14 +5,41 @@
 
 use crate::ast::{Edge, EdgeKind, Node, NodeId, NodeKind};
 use crate::error::Result;
+use crate::patch::AstPatch;
 use dashmap::DashMap;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
 use regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 /// In-memory graph store for code intelligence
+///
+/// # Consistency
+///
+/// Lookups such as [`GraphStore::get_node`] never block and see each map
+/// entry as of the moment they read it, so a sequence of lookups running
+/// alongside an update may observe it half applied. Each mutation —
+/// [`add_node`](GraphStore::add_node), [`add_edge`](GraphStore::add_edge),
+/// [`remove_node`](GraphStore::remove_node), [`clear`](GraphStore::clear)
+/// and a whole [`apply_patch`](GraphStore::apply_patch) — is atomic with
+/// respect to [`GraphStore::snapshot`] and [`GraphStore::get_stats`]:
+/// a snapshot contains every mutation that finished before it was taken
+/// and nothing of one still in progress. Long-running queries that need a
+/// consistent view should run on a snapshot; updates proceed on the live
+/// store while they do.
+///
+/// A [`GraphQuery`] only sees a consistent graph when it is built on a
+/// snapshot. Queries on the live store, such as
+/// [`impact_set`](GraphQuery::impact_set),
+/// [`compute_centrality_with`](GraphQuery::compute_centrality_with),
+/// [`find_longest_call_chains`](GraphQuery::find_longest_call_chains) and
+/// [`extract_subgraph`](GraphQuery::extract_subgraph), may mix nodes and
+/// edges from before and after an update running alongside them, and can
+/// meet edges whose endpoint was just removed.
 #[derive(Debug)]
 pub struct GraphStore {
     /// All nodes indexed by their ID
@@ -27,6 +54,12 @@ pub struct GraphStore {
     symbol_index: Arc<DashMap<String, Vec<NodeId>>>,
     /// Index of nodes by kind
     kind_index: Arc<DashMap<NodeKind, Vec<NodeId>>>,
+    /// Root directories of registered repositories by repository ID
+    repositories: Arc<DashMap<String, PathBuf>>,
+    /// Incremented by every change to nodes or edges
+    generation: Arc<AtomicU64>,
+    /// Held for writing by each mutation and for reading by snapshots
+    update_lock: RwLock<()>,
 }
 
 impl GraphStore {
@@ -39,11 +72,67 @@ impl GraphStore {
             file_index: Arc::new(DashMap::new()),
             symbol_index: Arc::new(DashMap::new()),
             kind_index: Arc::new(DashMap::new()),
+            repositories: Arc::new(DashMap::new()),
+            generation: Arc::new(AtomicU64::new(0)),
+            update_lock: RwLock::new(()),
         }
     }
 
+    /// Copy the graph as of the last completed mutation
+    ///
+    /// The copy is independent of this store: later updates do not show in
+    /// it, and it keeps the generation it was taken at. Taking a snapshot
+    /// waits for a mutation in progress, then deep-copies every node, edge
+    /// list and index: it takes time and memory proportional to the whole
+    /// graph, so it suits queries that walk most of the graph anyway.
+    pub fn snapshot(&self) -> GraphStore {
+        let _guard = self.read_guard();
+        GraphStore {
+            nodes: Arc::new((*self.nodes).clone()),
+            outgoing_edges: Arc::new((*self.outgoing_edges).clone()),
+            incoming_edges: Arc::new((*self.incoming_edges).clone()),
+            file_index: Arc::new((*self.file_index).clone()),
+            symbol_index: Arc::new((*self.symbol_index).clone()),
+            kind_index: Arc::new((*self.kind_index).clone()),
+            repositories: Arc::new((*self.repositories).clone()),
+            generation: Arc::new(AtomicU64::new(self.generation())),
+            update_lock: RwLock::new(()),
+        }
+    }
+
+    // The lock guards no data, so a panic while it was held leaves nothing
+    // to recover and poisoning is ignored
+    fn read_guard(&self) -> RwLockReadGuard<'_, ()> {
+        self.update_lock
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write_guard(&self) -> RwLockWriteGuard<'_, ()> {
+        self.update_lock
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Counter that changes whenever nodes or edges are added or removed
+    ///
+    /// Results computed at one generation, such as search cursors, stay valid
+    /// while the generation is unchanged.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    fn bump_generation(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
     /// Add a node to the graph
     pub fn add_node(&self, node: Node) {
+        let _guard = self.write_guard();
+        self.insert_node(node);
+    }
+
+    fn insert_node(&self, node: Node) {
         let node_id = node.id;
 
         // Add to file index
@@ -63,10 +152,16 @@ impl GraphStore {
 
         // Add the node
         self.nodes.insert(node_id, node);
+        self.bump_generation();
     }
 
     /// Add an edge to the graph
     pub fn add_edge(&self, edge: Edge) {
+        let _guard = self.write_guard();
+        self.insert_edge(edge);
+    }
+
+    fn insert_edge(&self, edge: Edge) {
         // Add to outgoing edges
         self.outgoing_edges
             .entry(edge.source)
@@ -78,6 +173,7 @@ impl GraphStore {
             .entry(edge.target)
             .or_default()
             .push(edge);
+        self.bump_generation();
     }
 
     /// Get a node by ID
@@ -130,6 +226,7 @@ impl GraphStore {
 
     /// Get graph statistics
     pub fn get_stats(&self) -> GraphStats {
+        let _guard = self.read_guard();
         GraphStats {
             total_nodes: self.nodes.len(),
             total_edges: self.outgoing_edges.iter().map(|entry| entry.len()).sum(),
@@ -144,16 +241,24 @@ impl GraphStore {
 
     /// Clear all data from the graph
     pub fn clear(&self) {
+        let _guard = self.write_guard();
         self.nodes.clear();
         self.outgoing_edges.clear();
         self.incoming_edges.clear();
         self.file_index.clear();
         self.symbol_index.clear();
         self.kind_index.clear();
+        self.repositories.clear();
+        self.bump_generation();
     }
 
     /// Remove a node and all its edges
     pub fn remove_node(&self, node_id: &NodeId) -> Option<Node> {
+        let _guard = self.write_guard();
+        self.delete_node(node_id)
+    }
+
+    fn delete_node(&self, node_id: &NodeId) -> Option<Node> {
         if let Some((_, node)) = self.nodes.remove(node_id) {
             // Remove from indices
             if let Some(mut file_nodes) = self.file_index.get_mut(&node.file) {
@@ -181,12 +286,43 @@ impl GraphStore {
                 edges.retain(|edge| edge.source != *node_id);
             }
 
+            self.bump_generation();
             Some(node)
         } else {
             None
         }
     }
 
+    /// Apply a patch, performing deletions before additions
+    ///
+    /// Snapshots see either none or all of the patch.
+    pub fn apply_patch(&self, patch: &AstPatch) {
+        let _guard = self.write_guard();
+        for node_id in &patch.nodes_delete {
+            if let Ok(node_id) = NodeId::from_hex(node_id) {
+                self.delete_node(&node_id);
+            }
+        }
+
+        if !patch.edges_delete.is_empty() {
+            let deleted: HashSet<&str> = patch.edges_delete.iter().map(String::as_str).collect();
+            for mut edges in self.outgoing_edges.iter_mut() {
+                edges.retain(|edge| !deleted.contains(edge.id().as_str()));
+            }
+            for mut edges in self.incoming_edges.iter_mut() {
+                edges.retain(|edge| !deleted.contains(edge.id().as_str()));
+            }
+            self.bump_generation();
+        }
+
+        for node in &patch.nodes_add {
+            self.insert_node(node.clone());
+        }
+        for edge in &patch.edges_add {
+            self.insert_edge(edge.clone());
+        }
+    }
+
     /// Get all file paths in the index
     pub fn get_all_files(&self) -> Vec<PathBuf> {
         self.file_index
@@ -224,6 +360,42 @@ impl GraphStore {
             .map(|ids| ids.clone())
             .unwrap_or_default()
     }
+
+    /// Register the root directory of a repository whose files are in the graph
+    pub fn register_repository(&self, repo_id: impl Into<String>, root: PathBuf) {
+        let _guard = self.write_guard();
+        self.repositories.insert(repo_id.into(), root);
+    }
+
+    /// Forget a registered repository; its nodes stay in the graph
+    pub fn unregister_repository(&self, repo_id: &str) {
+        let _guard = self.write_guard();
+        self.repositories.remove(repo_id);
+    }
+
+    /// Root directory of a registered repository
+    pub fn repository_root(&self, repo_id: &str) -> Option<PathBuf> {
+        self.repositories.get(repo_id).map(|root| root.clone())
+    }
+
+    /// Registered repositories as `(repository ID, root)` pairs
+    pub fn repositories(&self) -> Vec<(String, PathBuf)> {
+        self.repositories
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    /// ID of the registered repository containing `file`
+    ///
+    /// When repository roots are nested the innermost one wins.
+    pub fn repository_of(&self, file: &Path) -> Option<String> {
+        self.repositories
+            .iter()
+            .filter(|entry| file.starts_with(entry.value()))
+            .max_by_key(|entry| entry.value().components().count())
+            .map(|entry| entry.key().clone())
+    }
 }
 
 impl Default for GraphStore {
@@ -263,25 +435,42 @@ impl GraphQuery {
         target: &NodeId,
         max_depth: Option<usize>,
     ) -> Result<Option<PathResult>> {
-        let max_depth = max_depth.unwrap_or(10);
+        Ok(self.bfs_path(*source, *target, max_depth.unwrap_or(10), &[]))
+    }
+
+    /// Find the shortest path between two nodes along edges of the given kinds
+    ///
+    /// Breadth-first search with no depth limit; an empty `edge_kinds`
+    /// follows edges of every kind. Returns `None` when `to` is unreachable.
+    pub fn shortest_path(
+        &self,
+        from: NodeId,
+        to: NodeId,
+        edge_kinds: &[EdgeKind],
+    ) -> Option<PathResult> {
+        self.bfs_path(from, to, usize::MAX, edge_kinds)
+    }
 
+    fn bfs_path(
+        &self,
+        source: NodeId,
+        target: NodeId,
+        max_depth: usize,
+        edge_kinds: &[EdgeKind],
+    ) -> Option<PathResult> {
         if source == target {
-            return Ok(Some(PathResult {
-                source: *source,
-                target: *target,
-                path: vec![*source],
+            return Some(PathResult {
+                source,
+                target,
+                path: vec![source],
                 distance: 0,
                 edges: Vec::new(),
-            }));
+            });
         }
 
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::new();
-        let mut parent = HashMap::new();
-        let mut edge_map = HashMap::new();
-
-        queue.push_back((*source, 0));
-        visited.insert(*source);
+        let mut queue = VecDeque::from([(source, 0)]);
+        let mut visited = HashSet::from([source]);
+        let mut parent_edge: HashMap<NodeId, Edge> = HashMap::new();
 
         while let Some((current, depth)) = queue.pop_front() {
             if depth >= max_depth {
@@ -289,47 +478,143 @@ impl GraphQuery {
             }
 
             for edge in self.graph.get_outgoing_edges(&current) {
-                if !visited.contains(&edge.target) {
-                    visited.insert(edge.target);
-                    parent.insert(edge.target, current);
-                    edge_map.insert(edge.target, edge.clone());
-                    queue.push_back((edge.target, depth + 1));
-
-                    if edge.target == *target {
-                        // Reconstruct path
-                        let mut path = Vec::new();
-                        let mut edges = Vec::new();
-                        let mut current_node = *target;
-
-                        // Build path from target back to source
-                        path.push(current_node);
-                        while let Some(&prev) = parent.get(&current_node) {
-                            if let Some(edge) = edge_map.get(&current_node) {
-                                edges.push(edge.clone());
-                            }
-                            current_node = prev;
-                            path.push(current_node);
-                        }
+                if !edge_kinds.is_empty() && !edge_kinds.contains(&edge.kind) {
+                    continue;
+                }
+                if !visited.insert(edge.target) {
+                    continue;
+                }
+                let next = edge.target;
+                parent_edge.insert(next, edge);
+                if next != target {
+                    queue.push_back((next, depth + 1));
+                    continue;
+                }
 
-                        // Reverse to get path from source to target
-                        path.reverse();
-                        edges.reverse();
+                // Walk the parent edges back from the target
+                let mut edges = Vec::new();
+                let mut current_node = target;
+                while let Some(edge) = parent_edge.get(&current_node) {
+                    edges.push(edge.clone());
+                    current_node = edge.source;
+                }
+                edges.reverse();
+
+                let mut path = vec![source];
+                path.extend(edges.iter().map(|edge| edge.target));
+                return Some(PathResult {
+                    source,
+                    target,
+                    distance: edges.len(),
+                    path,
+                    edges,
+                });
+            }
+        }
 
-                        let distance = path.len() - 1; // Number of edges, not nodes
+        None
+    }
 
-                        return Ok(Some(PathResult {
-                            source: *source,
-                            target: *target,
-                            path,
-                            distance,
-                            edges,
-                        }));
-                    }
+    /// Find import cycles
+    ///
+    /// Shorthand for [`GraphQuery::find_cycles_with_kinds`] over `Imports` edges.
+    pub fn find_cycles(&self) -> Vec<Vec<NodeId>> {
+        self.find_cycles_with_kinds(&[EdgeKind::Imports])
+    }
+
+    /// Find cycles formed by edges of the given kinds
+    ///
+    /// Strongly connected components are found with Tarjan's algorithm. Each
+    /// component is returned once, ordered along its edges so that every node
+    /// has an edge to the next and the last node has an edge back to the
+    /// first. Components that are not a single simple cycle are walked
+    /// through all their members, so a node may appear more than once.
+    /// Self-loops are reported as single-node cycles.
+    pub fn find_cycles_with_kinds(&self, kinds: &[EdgeKind]) -> Vec<Vec<NodeId>> {
+        let mut adjacency: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for entry in self.graph.outgoing_edges.iter() {
+            let mut targets: Vec<NodeId> = entry
+                .value()
+                .iter()
+                .filter(|edge| kinds.contains(&edge.kind))
+                .map(|edge| edge.target)
+                .collect();
+            if !targets.is_empty() {
+                targets.sort_by_key(|id| id.to_hex());
+                targets.dedup();
+                adjacency.insert(*entry.key(), targets);
+            }
+        }
+
+        let mut roots: Vec<NodeId> = adjacency.keys().copied().collect();
+        roots.sort_by_key(|id| id.to_hex());
+
+        let mut cycles: Vec<Vec<NodeId>> = strongly_connected_components(&roots, &adjacency)
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || adjacency
+                        .get(&component[0])
+                        .is_some_and(|targets| targets.contains(&component[0]))
+            })
+            .map(|component| order_cycle(&component, &adjacency))
+            .collect();
+        cycles.sort_by_key(|cycle| cycle[0].to_hex());
+        cycles
+    }
+
+    /// Extract the nodes within `radius` hops of `center` and the edges among them
+    ///
+    /// Edges of every kind are followed in both directions. Nodes are listed
+    /// in breadth-first order starting with `center`.
+    pub fn extract_subgraph(&self, center: &NodeId, radius: usize) -> Result<Subgraph> {
+        let center_node = self
+            .graph
+            .get_node(center)
+            .ok_or_else(|| crate::error::Error::node_not_found(center.to_hex()))?;
+
+        let mut nodes = vec![center_node];
+        let mut included = HashSet::from([*center]);
+        let mut queue = VecDeque::from([(*center, 0usize)]);
+        while let Some((node_id, distance)) = queue.pop_front() {
+            if distance == radius {
+                continue;
+            }
+            let neighbors = self
+                .graph
+                .get_outgoing_edges(&node_id)
+                .into_iter()
+                .map(|edge| edge.target)
+                .chain(
+                    self.graph
+                        .get_incoming_edges(&node_id)
+                        .into_iter()
+                        .map(|edge| edge.source),
+                );
+            for neighbor in neighbors {
+                if included.contains(&neighbor) {
+                    continue;
+                }
+                if let Some(node) = self.graph.get_node(&neighbor) {
+                    included.insert(neighbor);
+                    nodes.push(node);
+                    queue.push_back((neighbor, distance + 1));
                 }
             }
         }
 
-        Ok(None)
+        let edges = nodes
+            .iter()
+            .flat_map(|node| self.graph.get_outgoing_edges(&node.id))
+            .filter(|edge| included.contains(&edge.target))
+            .collect();
+
+        Ok(Subgraph {
+            center: *center,
+            radius,
+            nodes,
+            edges,
+        })
     }
 
     /// Find all references to a symbol (incoming edges)
@@ -352,138 +637,1250 @@ impl GraphQuery {
         Ok(references)
     }
 
-    /// Find all dependencies of a node (outgoing edges)
-    pub fn find_dependencies(
-        &self,
-        node_id: &NodeId,
-        dependency_type: DependencyType,
-    ) -> Result<Vec<SymbolDependency>> {
-        let mut dependencies = Vec::new();
+    /// Find the functions calling a symbol, one entry per caller
+    ///
+    /// Follows incoming `Calls` edges, looking through call-site nodes to the
+    /// function that contains them. Call-site nodes the parser could not
+    /// resolve are matched by name. Every call site is kept in `call_sites`.
+    pub fn find_callers(&self, target: &NodeId) -> Result<Vec<SymbolInfo>> {
+        let Some(target_node) = self.graph.get_node(target) else {
+            return Ok(Vec::new());
+        };
+
+        let mut sites: Vec<Node> = Vec::new();
+        let mut callers = CallGrouping::default();
+        for edge in self.graph.get_incoming_edges(target) {
+            if !matches!(edge.kind, EdgeKind::Calls) {
+                continue;
+            }
+            if let Some(source) = self.graph.get_node(&edge.source) {
+                match source.kind {
+                    NodeKind::Call => sites.push(source),
+                    NodeKind::Function | NodeKind::Method => {
+                        let location = ReferenceLocation {
+                            file: source.file.clone(),
+                            span: source.span.clone(),
+                        };
+                        callers.add(source, location);
+                    }
+                    _ => {}
+                }
+            }
+        }
 
-        for edge in self.graph.get_outgoing_edges(node_id) {
-            let include_edge = match dependency_type {
-                DependencyType::Direct => true,
-                DependencyType::Calls => matches!(edge.kind, EdgeKind::Calls),
-                DependencyType::Imports => matches!(edge.kind, EdgeKind::Imports),
-                DependencyType::Reads => matches!(edge.kind, EdgeKind::Reads),
-                DependencyType::Writes => matches!(edge.kind, EdgeKind::Writes),
+        sites.extend(
+            self.graph
+                .get_nodes_by_kind(NodeKind::Call)
+                .into_iter()
+                .filter(|call| {
+                    call_matches_name(&call.name, &target_node.name)
+                        && !self
+                            .graph
+                            .get_outgoing_edges(&call.id)
+                            .iter()
+                            .any(|edge| matches!(edge.kind, EdgeKind::Calls))
+                }),
+        );
+
+        for site in sites {
+            for edge in self.graph.get_incoming_edges(&site.id) {
+                if !matches!(edge.kind, EdgeKind::Calls) {
+                    continue;
+                }
+                if let Some(caller) = self.graph.get_node(&edge.source) {
+                    if caller.kind != NodeKind::Call {
+                        let location = ReferenceLocation {
+                            file: site.file.clone(),
+                            span: site.span.clone(),
+                        };
+                        callers.add(caller, location);
+                    }
+                }
+            }
+        }
+
+        Ok(callers.into_symbols(&self.graph))
+    }
+
+    /// Find the symbols a function calls, one entry per callee
+    ///
+    /// Call-site nodes are resolved through their own `Calls` edges, then by
+    /// name against known functions and methods. A call that resolves to
+    /// nothing in the graph, such as a library call, is reported as the
+    /// call-site node itself.
+    pub fn find_callees(&self, source: &NodeId) -> Result<Vec<SymbolInfo>> {
+        let Some(source_node) = self.graph.get_node(source) else {
+            return Ok(Vec::new());
+        };
+
+        let mut callees = CallGrouping::default();
+        for edge in self.graph.get_outgoing_edges(source) {
+            if !matches!(edge.kind, EdgeKind::Calls) {
+                continue;
+            }
+            let Some(target) = self.graph.get_node(&edge.target) else {
+                continue;
             };
 
-            if include_edge {
-                if let Some(target_node) = self.graph.get_node(&edge.target) {
-                    dependencies.push(SymbolDependency {
-                        target_node,
-                        edge_kind: edge.kind,
-                        dependency_type: dependency_type.clone(),
-                    });
+            match target.kind {
+                NodeKind::Call => {
+                    let location = ReferenceLocation {
+                        file: target.file.clone(),
+                        span: target.span.clone(),
+                    };
+                    let mut resolved: Vec<Node> = self
+                        .graph
+                        .get_outgoing_edges(&target.id)
+                        .iter()
+                        .filter(|edge| matches!(edge.kind, EdgeKind::Calls))
+                        .filter_map(|edge| self.graph.get_node(&edge.target))
+                        .collect();
+                    if resolved.is_empty() {
+                        resolved = self
+                            .graph
+                            .get_nodes_by_name(call_target_name(&target.name))
+                            .into_iter()
+                            .filter(|node| {
+                                matches!(node.kind, NodeKind::Function | NodeKind::Method)
+                            })
+                            .collect();
+                    }
+                    if resolved.is_empty() {
+                        resolved.push(target);
+                    }
+                    for callee in resolved {
+                        callees.add(callee, location.clone());
+                    }
+                }
+                NodeKind::Function | NodeKind::Method => {
+                    let location = ReferenceLocation {
+                        file: source_node.file.clone(),
+                        span: source_node.span.clone(),
+                    };
+                    callees.add(target, location);
                 }
+                _ => {}
             }
         }
 
-        Ok(dependencies)
+        Ok(callees.into_symbols(&self.graph))
     }
 
-    /// Search symbols by name pattern (regex or fuzzy)
-    pub fn search_symbols(
-        &self,
-        pattern: &str,
-        symbol_types: Option<Vec<NodeKind>>,
-        limit: Option<usize>,
-    ) -> Result<Vec<SymbolInfo>> {
-        let limit = limit.unwrap_or(50);
-        let mut results = Vec::new();
+    /// Link test functions to the production symbols they call
+    ///
+    /// Each function or method named like a test is expanded through
+    /// [`Self::find_callees`] up to `max_depth` calls deep. Helpers living in
+    /// test files are walked through but not recorded, so only production
+    /// symbols end up in the map.
+    pub fn test_coverage_map(&self, max_depth: usize) -> TestCoverageMap {
+        let mut tests: Vec<Node> = [NodeKind::Function, NodeKind::Method]
+            .into_iter()
+            .flat_map(|kind| self.graph.get_nodes_by_kind(kind))
+            .filter(is_test_function)
+            .collect();
+        tests.sort_by(|a, b| (&a.file, a.span.start_byte).cmp(&(&b.file, b.span.start_byte)));
+
+        let mut map = TestCoverageMap::default();
+        for test in tests {
+            map.tests_to_symbols.entry(test.id).or_default();
+            let mut visited = HashSet::from([test.id]);
+            let mut queue = VecDeque::from([(test.id, 0usize)]);
+            while let Some((node_id, depth)) = queue.pop_front() {
+                if depth == max_depth {
+                    continue;
+                }
+                for callee in self.find_callees(&node_id).unwrap_or_default() {
+                    let node = callee.node;
+                    if !matches!(
+                        node.kind,
+                        NodeKind::Function | NodeKind::Method | NodeKind::Class
+                    ) || is_test_function(&node)
+                        || !visited.insert(node.id)
+                    {
+                        continue;
+                    }
+                    if !is_test_path(&node.file) {
+                        map.record(test.id, node.id);
+                    }
+                    queue.push_back((node.id, depth + 1));
+                }
+            }
+        }
+        map
+    }
 
-        // Try to compile as regex first, fall back to substring search if invalid
-        let regex_result = regex::Regex::new(pattern);
-        let use_regex = regex_result.is_ok();
-        let regex = regex_result.ok();
+    /// Find functions, methods and classes not reachable from `roots`
+    ///
+    /// Reachability is computed by [`Self::reachable_from`]. Results are
+    /// ordered by file and position.
+    pub fn find_unreachable(&self, roots: &[NodeId]) -> Vec<NodeId> {
+        let reached = self.reachable_from(roots);
+        let mut unreachable: Vec<Node> = [NodeKind::Function, NodeKind::Method, NodeKind::Class]
+            .into_iter()
+            .flat_map(|kind| self.graph.get_nodes_by_kind(kind))
+            .filter(|node| !reached.contains(&node.id))
+            .collect();
+        unreachable.sort_by(|a, b| (&a.file, a.span.start_byte).cmp(&(&b.file, b.span.start_byte)));
+        unreachable.into_iter().map(|node| node.id).collect()
+    }
 
-        for entry in self.graph.symbol_index.iter() {
-            let symbol_name = entry.key();
+    /// Extract the subgraph around `center` keeping only nodes reachable from `roots`
+    ///
+    /// Like [`Self::extract_subgraph`], but nodes outside
+    /// [`Self::reachable_from`] and their edges are dropped, so dead code does
+    /// not appear in the export. Fails if `center` itself is unreachable.
+    pub fn extract_reachable_subgraph(
+        &self,
+        center: &NodeId,
+        radius: usize,
+        roots: &[NodeId],
+    ) -> Result<Subgraph> {
+        let reached = self.reachable_from(roots);
+        if !reached.contains(center) {
+            return Err(crate::error::Error::validation(
+                "center",
+                format!("{} is not reachable from the entry points", center.to_hex()),
+            ));
+        }
+        let mut subgraph = self.extract_subgraph(center, radius)?;
+        subgraph.nodes.retain(|node| reached.contains(&node.id));
+        subgraph
+            .edges
+            .retain(|edge| reached.contains(&edge.source) && reached.contains(&edge.target));
+        Ok(subgraph)
+    }
 
-            let matches = if use_regex {
-                // Use regex matching
-                regex.as_ref().unwrap().is_match(symbol_name)
-            } else {
-                // Fall back to case-insensitive substring search
-                symbol_name.to_lowercase().contains(&pattern.to_lowercase())
-            };
+    /// Every node reachable from `roots`, including the roots themselves
+    ///
+    /// The walk follows `Calls`, `Imports`, `Extends` and `RoutesTo` edges.
+    /// Call sites without a resolved target are matched by name against
+    /// known functions, methods and classes, as in [`Self::find_callees`].
+    pub fn reachable_from(&self, roots: &[NodeId]) -> HashSet<NodeId> {
+        let mut reached: HashSet<NodeId> = roots.iter().copied().collect();
+        let mut queue: VecDeque<NodeId> = roots.iter().copied().collect();
+
+        while let Some(node_id) = queue.pop_front() {
+            let mut next: Vec<NodeId> = self
+                .graph
+                .get_outgoing_edges(&node_id)
+                .into_iter()
+                .filter(|edge| {
+                    matches!(
+                        edge.kind,
+                        EdgeKind::Calls
+                            | EdgeKind::Imports
+                            | EdgeKind::Extends
+                            | EdgeKind::RoutesTo
+                    )
+                })
+                .map(|edge| edge.target)
+                .collect();
+
+            if next.is_empty() {
+                if let Some(node) = self.graph.get_node(&node_id) {
+                    if node.kind == NodeKind::Call {
+                        next.extend(
+                            self.graph
+                                .get_nodes_by_name(call_target_name(&node.name))
+                                .into_iter()
+                                .filter(|target| {
+                                    matches!(
+                                        target.kind,
+                                        NodeKind::Function | NodeKind::Method | NodeKind::Class
+                                    )
+                                })
+                                .map(|target| target.id),
+                        );
+                    }
+                }
+            }
 
-            if matches {
-                for node_id in entry.value() {
-                    if let Some(node) = self.graph.get_node(node_id) {
-                        // Filter by symbol types if specified
-                        if let Some(ref types) = symbol_types {
-                            if !types.contains(&node.kind) {
-                                continue;
-                            }
-                        }
+            for target in next {
+                if reached.insert(target) {
+                    queue.push_back(target);
+                }
+            }
+        }
+        reached
+    }
 
-                        results.push(SymbolInfo {
-                            node,
-                            references_count: self.graph.get_incoming_edges(node_id).len(),
-                            dependencies_count: self.graph.get_outgoing_edges(node_id).len(),
-                        });
+    /// Score functions and methods by their centrality in the call graph
+    ///
+    /// Shorthand for [`Self::compute_centrality_with`] using the default
+    /// bounds, returning only the scores.
+    pub fn compute_centrality(&self, metric: CentralityMetric) -> Vec<(NodeId, f64)> {
+        self.compute_centrality_with(metric, &CentralityOptions::default())
+            .scores
+    }
 
-                        if results.len() >= limit {
+    /// Score functions and methods by their centrality in the call graph
+    ///
+    /// The graph has one vertex per function or method and an edge from each
+    /// caller to each callee, resolved as in [`Self::find_callees`]. Scores are
+    /// normalized to `0.0..=1.0` and sorted highest first.
+    ///
+    /// Betweenness uses Brandes' algorithm. When the graph has more vertices
+    /// than `options.max_sources`, only that many evenly spaced sources are
+    /// expanded and the scores are extrapolated; the walk also stops early once
+    /// the time budget is spent or the cancel flag is set.
+    pub fn compute_centrality_with(
+        &self,
+        metric: CentralityMetric,
+        options: &CentralityOptions,
+    ) -> CentralityScores {
+        let (ids, callees) = self.call_adjacency();
+        let n = ids.len();
+        let (raw, sources_processed, complete) = match metric {
+            CentralityMetric::Betweenness => brandes_betweenness(&callees, options),
+            degree => {
+                let mut in_degree = vec![0usize; n];
+                for targets in &callees {
+                    for &target in targets {
+                        in_degree[target] += 1;
+                    }
+                }
+                let scores = (0..n)
+                    .map(|v| {
+                        let degree = match degree {
+                            CentralityMetric::InDegree => in_degree[v],
+                            CentralityMetric::OutDegree => callees[v].len(),
+                            _ => in_degree[v] + callees[v].len(),
+                        };
+                        degree as f64
+                    })
+                    .collect();
+                (scores, n, true)
+            }
+        };
+
+        let normalizer = match metric {
+            CentralityMetric::Betweenness => ((n.saturating_sub(1)) * (n.saturating_sub(2))) as f64,
+            CentralityMetric::Degree => (2 * n.saturating_sub(1)) as f64,
+            _ => n.saturating_sub(1) as f64,
+        };
+        let mut scores: Vec<(NodeId, f64)> = ids
+            .into_iter()
+            .zip(raw)
+            .map(|(id, score)| {
+                let score = if normalizer > 0.0 {
+                    score / normalizer
+                } else {
+                    0.0
+                };
+                (id, score)
+            })
+            .collect();
+        scores.sort_by(|a, b| {
+            b.1.total_cmp(&a.1)
+                .then_with(|| a.0.to_hex().cmp(&b.0.to_hex()))
+        });
+
+        CentralityScores {
+            scores,
+            sources_processed,
+            total_sources: n,
+            complete,
+        }
+    }
+
+    /// Find the longest acyclic call chains
+    ///
+    /// The call graph is the one of [`Self::compute_centrality_with`]. It is
+    /// walked depth first from every function nobody calls, then from any
+    /// function left unvisited because it is only reachable through a cycle.
+    /// A chain never revisits a symbol and stops at `options.max_depth` calls;
+    /// the whole search stops after `options.max_expansions` steps or once
+    /// the cancel flag is set, leaving the longest chains found so far. Each start contributes its longest
+    /// chain, and the `options.limit` longest are returned, longest first.
+    pub fn find_longest_call_chains(&self, options: &CallChainOptions) -> CallChains {
+        let (ids, callees) = self.call_adjacency();
+        let n = ids.len();
+
+        let mut in_degree = vec![0usize; n];
+        for targets in &callees {
+            for &target in targets {
+                in_degree[target] += 1;
+            }
+        }
+        let starts = (0..n)
+            .filter(|&v| in_degree[v] == 0)
+            .chain((0..n).filter(|&v| in_degree[v] > 0));
+
+        let mut visited = vec![false; n];
+        let mut on_path = vec![false; n];
+        let mut expansions = 0;
+        let mut complete = true;
+        let mut chains: Vec<Vec<usize>> = Vec::new();
+        for start in starts {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            on_path[start] = true;
+            // Each frame is a node on the path, the index of its next callee,
+            // and whether the path was ever extended past it
+            let mut stack = vec![(start, 0usize, false)];
+            let mut path = vec![start];
+            let mut longest: Vec<usize> = Vec::new();
+
+            while let Some((node, next, extended)) = stack.last_mut() {
+                let mut child = None;
+                if path.len() <= options.max_depth {
+                    while *next < callees[*node].len() {
+                        let candidate = callees[*node][*next];
+                        *next += 1;
+                        if !on_path[candidate] {
+                            child = Some(candidate);
                             break;
                         }
                     }
                 }
 
-                if results.len() >= limit {
-                    break;
+                let cancelled = options
+                    .cancel
+                    .as_ref()
+                    .is_some_and(|flag| flag.load(std::sync::atomic::Ordering::Relaxed));
+                match child {
+                    Some(child) if expansions < options.max_expansions && !cancelled => {
+                        expansions += 1;
+                        *extended = true;
+                        visited[child] = true;
+                        on_path[child] = true;
+                        stack.push((child, 0, false));
+                        path.push(child);
+                    }
+                    Some(_) => {
+                        complete = false;
+                        break;
+                    }
+                    None => {
+                        if !*extended && path.len() > longest.len() {
+                            longest.clone_from(&path);
+                        }
+                        on_path[*node] = false;
+                        stack.pop();
+                        path.pop();
+                    }
                 }
             }
+
+            if !complete && path.len() > longest.len() {
+                longest = path;
+            }
+            if longest.len() > 1 {
+                chains.push(longest);
+            }
+            if !complete {
+                break;
+            }
         }
 
-        Ok(results)
+        chains.sort_by_key(|chain| std::cmp::Reverse(chain.len()));
+        chains.truncate(options.limit);
+        CallChains {
+            chains: chains
+                .into_iter()
+                .map(|chain| CallChain {
+                    nodes: chain.into_iter().map(|v| ids[v]).collect(),
+                })
+                .collect(),
+            complete,
+        }
     }
 
-    /// Search symbols by name pattern with inheritance filters
-    pub fn search_symbols_with_inheritance(
+    /// Functions and methods sorted by id, with the distinct callees of each
+    ///
+    /// Callees are resolved as in [`Self::find_callees`] and given as indices
+    /// into the id list; calls a function makes to itself are dropped.
+    fn call_adjacency(&self) -> (Vec<NodeId>, Vec<Vec<usize>>) {
+        let mut ids: Vec<NodeId> = [NodeKind::Function, NodeKind::Method]
+            .into_iter()
+            .flat_map(|kind| self.graph.get_nodes_by_kind(kind))
+            .map(|node| node.id)
+            .collect();
+        ids.sort_by_key(|id| id.to_hex());
+        let index: HashMap<NodeId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut callees: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+        for (caller, id) in ids.iter().enumerate() {
+            let targets = &mut callees[caller];
+            for callee in self.find_callees(id).unwrap_or_default() {
+                if let Some(&target) = index.get(&callee.node.id) {
+                    if target != caller && !targets.contains(&target) {
+                        targets.push(target);
+                    }
+                }
+            }
+        }
+        (ids, callees)
+    }
+
+    /// Trace where a value goes, or where it came from, with default bounds
+    pub fn trace_data_flow(&self, start: NodeId, direction: FlowDirection) -> Vec<FlowStep> {
+        self.trace_data_flow_with(start, direction, &DataFlowOptions::default())
+    }
+
+    /// Trace data flow from a node along `Reads` and `Writes` edges
+    ///
+    /// Forward, a value flows to the nodes reading it and from a node to what
+    /// it writes; at a call site it flows into the parameters of the resolved
+    /// callee. Backward follows the same relations in reverse, stepping from a
+    /// parameter to the call sites of the function whose span encloses it,
+    /// including unresolved call sites matching the function by name. When a call site lists
+    /// its argument names in an `arguments` metadata array, only the parameter
+    /// at the matching position is bound; otherwise every parameter is.
+    ///
+    /// The walk is breadth first, visits each node once and stops at
+    /// `options.max_depth` steps from the start. Steps come in visiting order.
+    pub fn trace_data_flow_with(
         &self,
-        pattern: &str,
-        symbol_types: Option<Vec<NodeKind>>,
-        inheritance_filters: Option<Vec<InheritanceFilter>>,
-        limit: Option<usize>,
-    ) -> Result<Vec<SymbolInfo>> {
-        let mut results = self.search_symbols(pattern, symbol_types, limit)?;
+        start: NodeId,
+        direction: FlowDirection,
+        options: &DataFlowOptions,
+    ) -> Vec<FlowStep> {
+        let mut steps = Vec::new();
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([(start, 0usize)]);
 
-        if let Some(filters) = inheritance_filters {
-            results.retain(|symbol_info| {
-                filters
-                    .iter()
-                    .any(|filter| self.matches_inheritance_filter(&symbol_info.node, filter))
-            });
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= options.max_depth {
+                continue;
+            }
+            let next = match direction {
+                FlowDirection::Forward => self.flow_successors(&current),
+                FlowDirection::Backward => self.flow_predecessors(&current),
+            };
+            for (node, edge_kind) in next {
+                if !visited.insert(node.id) {
+                    continue;
+                }
+                queue.push_back((node.id, depth + 1));
+                steps.push(FlowStep {
+                    node: node.id,
+                    from: current,
+                    edge_kind,
+                    span: node.span,
+                    depth: depth + 1,
+                });
+            }
         }
 
-        Ok(results)
+        steps
     }
 
-    /// Check if a node matches an inheritance filter
-    fn matches_inheritance_filter(&self, node: &Node, filter: &InheritanceFilter) -> bool {
-        match filter {
-            InheritanceFilter::InheritsFrom(base_class) => {
-                self.inherits_from(&node.id, base_class).unwrap_or(false)
+    /// Symbols that could break when `target` changes
+    ///
+    /// Follows incoming `Calls`, `Reads`, `Extends` and `Implements` edges
+    /// transitively, so callers of callers and subclasses of subclasses are
+    /// included. Call sites stand for the function making the call, and
+    /// unresolved call sites count when their name matches a function. Each
+    /// symbol is reported once, at its shortest distance from the target.
+    /// `max_depth` of `None` follows the graph until no new symbol is found.
+    pub fn impact_set(&self, target: NodeId, max_depth: Option<usize>) -> ImpactReport {
+        let mut report = ImpactReport {
+            target,
+            hops: Vec::new(),
+            truncated: false,
+        };
+        let mut unresolved_calls: HashMap<String, Vec<Node>> = HashMap::new();
+        for call in self.graph.get_nodes_by_kind(NodeKind::Call) {
+            let resolved = self
+                .graph
+                .get_outgoing_edges(&call.id)
+                .iter()
+                .any(|edge| edge.kind == EdgeKind::Calls);
+            if !resolved {
+                unresolved_calls
+                    .entry(call_target_name(&call.name).to_string())
+                    .or_default()
+                    .push(call);
             }
-            InheritanceFilter::HasMetaclass(metaclass) => {
-                self.has_metaclass(&node.id, metaclass).unwrap_or(false)
+        }
+
+        let mut visited = HashSet::from([target]);
+        let mut frontier = vec![target];
+
+        while !frontier.is_empty() {
+            let depth = report.hops.len();
+            let mut hop = Vec::new();
+            for current in &frontier {
+                for (node, edge_kind) in self.impact_dependents(current, &unresolved_calls) {
+                    if visited.contains(&node.id) {
+                        continue;
+                    }
+                    if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                        report.truncated = true;
+                        break;
+                    }
+                    visited.insert(node.id);
+                    hop.push(ImpactedSymbol {
+                        node: node.id,
+                        from: *current,
+                        edge_kind,
+                        span: node.span,
+                    });
+                }
             }
-            InheritanceFilter::UsesMixin(mixin) => {
-                self.uses_mixin(&node.id, mixin).unwrap_or(false)
+            if hop.is_empty() {
+                break;
             }
+            frontier = hop.iter().map(|symbol| symbol.node).collect();
+            report.hops.push(hop);
         }
+
+        report
     }
 
-    /// Get comprehensive inheritance information for a class
+    /// Symbols depending on `node` through one impact edge
+    ///
+    /// `unresolved_calls` holds call sites without a resolved target by the
+    /// name they call.
+    fn impact_dependents(
+        &self,
+        node: &NodeId,
+        unresolved_calls: &HashMap<String, Vec<Node>>,
+    ) -> Vec<(Node, EdgeKind)> {
+        let mut sources: Vec<(Node, EdgeKind)> = self
+            .graph
+            .get_incoming_edges(node)
+            .into_iter()
+            .filter(|edge| {
+                matches!(
+                    edge.kind,
+                    EdgeKind::Calls | EdgeKind::Reads | EdgeKind::Extends | EdgeKind::Implements
+                )
+            })
+            .filter_map(|edge| Some((self.graph.get_node(&edge.source)?, edge.kind)))
+            .collect();
+        if let Some(function) = self
+            .graph
+            .get_node(node)
+            .filter(|n| matches!(n.kind, NodeKind::Function | NodeKind::Method))
+        {
+            if let Some(calls) = unresolved_calls.get(&function.name) {
+                sources.extend(calls.iter().map(|call| (call.clone(), EdgeKind::Calls)));
+            }
+        }
+
+        let mut dependents = Vec::new();
+        for (source, edge_kind) in sources {
+            if source.kind == NodeKind::Call {
+                // A call site affects the function containing it
+                dependents.extend(
+                    self.graph
+                        .get_incoming_edges(&source.id)
+                        .into_iter()
+                        .filter(|site_edge| site_edge.kind == EdgeKind::Calls)
+                        .filter_map(|site_edge| self.graph.get_node(&site_edge.source))
+                        .filter(|caller| caller.kind != NodeKind::Call)
+                        .map(|caller| (caller, edge_kind)),
+                );
+            } else if source.kind != NodeKind::Module {
+                // Mappers link modules to their top-level definitions with
+                // `Calls`; containment is not a dependency
+                dependents.push((source, edge_kind));
+            }
+        }
+        dependents
+    }
+
+    /// Nodes a value at `id` flows into, with the edge kind of each step
+    fn flow_successors(&self, id: &NodeId) -> Vec<(Node, EdgeKind)> {
+        let mut next: Vec<(Node, EdgeKind)> = Vec::new();
+        for edge in self.graph.get_incoming_edges(id) {
+            if edge.kind == EdgeKind::Reads {
+                next.extend(self.graph.get_node(&edge.source).map(|n| (n, edge.kind)));
+            }
+        }
+        for edge in self.graph.get_outgoing_edges(id) {
+            if edge.kind == EdgeKind::Writes {
+                next.extend(self.graph.get_node(&edge.target).map(|n| (n, edge.kind)));
+            }
+        }
+
+        // A call site reading the value passes it on as an argument
+        let Some(site) = self.graph.get_node(id) else {
+            return next;
+        };
+        let readers: Vec<Node> = next
+            .iter()
+            .filter(|(node, kind)| *kind == EdgeKind::Reads && node.kind == NodeKind::Call)
+            .map(|(node, _)| node.clone())
+            .collect();
+        for call in readers {
+            for callee in self.resolve_call_targets(&call) {
+                let parameters = self.parameters_of(&callee);
+                let position = argument_position(&call, &site.name);
+                for (index, parameter) in parameters.into_iter().enumerate() {
+                    if position.is_none_or(|position| position == index) {
+                        next.push((parameter, EdgeKind::Calls));
+                    }
+                }
+            }
+        }
+        next
+    }
+
+    /// Nodes the value at `id` came from, with the edge kind of each step
+    fn flow_predecessors(&self, id: &NodeId) -> Vec<(Node, EdgeKind)> {
+        let mut next: Vec<(Node, EdgeKind)> = Vec::new();
+        for edge in self.graph.get_incoming_edges(id) {
+            if edge.kind == EdgeKind::Writes {
+                next.extend(self.graph.get_node(&edge.source).map(|n| (n, edge.kind)));
+            }
+        }
+        for edge in self.graph.get_outgoing_edges(id) {
+            if edge.kind == EdgeKind::Reads {
+                next.extend(self.graph.get_node(&edge.target).map(|n| (n, edge.kind)));
+            }
+        }
+
+        // A parameter gets its value from the call sites of its function
+        let Some(parameter) = self.graph.get_node(id) else {
+            return next;
+        };
+        if parameter.kind != NodeKind::Parameter {
+            return next;
+        }
+        let functions = self
+            .graph
+            .get_nodes_in_file(&parameter.file)
+            .into_iter()
+            .filter(|node| {
+                matches!(node.kind, NodeKind::Function | NodeKind::Method)
+                    && spans_contain(&node.span, &parameter.span)
+            });
+        for function in functions {
+            for edge in self.graph.get_incoming_edges(&function.id) {
+                if edge.kind == EdgeKind::Calls {
+                    next.extend(self.graph.get_node(&edge.source).map(|n| (n, edge.kind)));
+                }
+            }
+            // Call sites the parser left unresolved are matched by name
+            next.extend(
+                self.graph
+                    .get_nodes_by_kind(NodeKind::Call)
+                    .into_iter()
+                    .filter(|call| {
+                        call_matches_name(&call.name, &function.name)
+                            && !self
+                                .graph
+                                .get_outgoing_edges(&call.id)
+                                .iter()
+                                .any(|edge| edge.kind == EdgeKind::Calls)
+                    })
+                    .map(|call| (call, EdgeKind::Calls)),
+            );
+        }
+        next
+    }
+
+    /// Functions and methods a call-site node resolves to
+    ///
+    /// Uses the call's own `Calls` edges, falling back to a name match as in
+    /// [`Self::find_callees`].
+    fn resolve_call_targets(&self, call: &Node) -> Vec<Node> {
+        let resolved: Vec<Node> = self
+            .graph
+            .get_outgoing_edges(&call.id)
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::Calls)
+            .filter_map(|edge| self.graph.get_node(&edge.target))
+            .filter(|node| matches!(node.kind, NodeKind::Function | NodeKind::Method))
+            .collect();
+        if !resolved.is_empty() {
+            return resolved;
+        }
+        self.graph
+            .get_nodes_by_name(call_target_name(&call.name))
+            .into_iter()
+            .filter(|node| matches!(node.kind, NodeKind::Function | NodeKind::Method))
+            .collect()
+    }
+
+    /// Parameters declared within a function's span, in declaration order
+    fn parameters_of(&self, function: &Node) -> Vec<Node> {
+        let mut parameters: Vec<Node> = self
+            .graph
+            .get_nodes_in_file(&function.file)
+            .into_iter()
+            .filter(|node| {
+                node.kind == NodeKind::Parameter && spans_contain(&function.span, &node.span)
+            })
+            .collect();
+        parameters.sort_by_key(|node| node.span.start_byte);
+        parameters
+    }
+
+    /// Find all dependencies of a node (outgoing edges)
+    pub fn find_dependencies(
+        &self,
+        node_id: &NodeId,
+        dependency_type: DependencyType,
+    ) -> Result<Vec<SymbolDependency>> {
+        let mut dependencies = Vec::new();
+
+        for edge in self.graph.get_outgoing_edges(node_id) {
+            let include_edge = match dependency_type {
+                DependencyType::Direct => true,
+                DependencyType::Calls => matches!(edge.kind, EdgeKind::Calls),
+                DependencyType::Imports => matches!(edge.kind, EdgeKind::Imports),
+                DependencyType::Reads => matches!(edge.kind, EdgeKind::Reads),
+                DependencyType::Writes => matches!(edge.kind, EdgeKind::Writes),
+            };
+
+            if include_edge {
+                if let Some(target_node) = self.graph.get_node(&edge.target) {
+                    dependencies.push(SymbolDependency {
+                        target_node,
+                        edge_kind: edge.kind,
+                        dependency_type: dependency_type.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(dependencies)
+    }
+
+    /// Definition, doc comment, call counts and inheritance of a symbol in one go
+    ///
+    /// The source snippet covers the symbol's span plus
+    /// [`EXPLAIN_CONTEXT_LINES`] lines on either side.
+    pub fn explain(&self, node: NodeId) -> Result<SymbolExplanation> {
+        self.explain_with_context(node, EXPLAIN_CONTEXT_LINES)
+    }
+
+    /// [`Self::explain`] with `context_lines` lines around the snippet
+    ///
+    /// The snippet and doc comment are read from the symbol's file and are
+    /// left out when it cannot be read.
+    pub fn explain_with_context(
+        &self,
+        node: NodeId,
+        context_lines: usize,
+    ) -> Result<SymbolExplanation> {
+        let node = self
+            .graph
+            .get_node(&node)
+            .ok_or_else(|| crate::error::Error::node_not_found(node.to_hex()))?;
+
+        let source = std::fs::read_to_string(&node.file).ok();
+        let snippet = source
+            .as_deref()
+            .map(|source| SourceSnippet::around(source, &node.span, context_lines));
+        let doc_comment = source.as_deref().and_then(|source| {
+            crate::content::extractors::CommentExtractor::new()
+                .doc_comment(node.lang, source, &node.file, &node.span)
+        });
+        let inheritance = if matches!(node.kind, NodeKind::Class | NodeKind::Interface) {
+            Some(self.get_inheritance_info(&node.id)?)
+        } else {
+            None
+        };
+
+        Ok(SymbolExplanation {
+            qualified_name: self.qualified_name(&node),
+            caller_count: self.find_callers(&node.id)?.len(),
+            callee_count: self.find_callees(&node.id)?.len(),
+            node,
+            snippet,
+            doc_comment,
+            inheritance,
+        })
+    }
+
+    /// Fully-qualified name of a node, e.g. `module.Class.save`
+    ///
+    /// Built from the modules, types and functions in the same file whose
+    /// spans enclose the node, outermost first.
+    pub fn qualified_name(&self, node: &Node) -> String {
+        let mut containers: Vec<Node> = self
+            .graph
+            .get_nodes_in_file(&node.file)
+            .into_iter()
+            .filter(|candidate| {
+                candidate.id != node.id
+                    && matches!(
+                        candidate.kind,
+                        NodeKind::Module
+                            | NodeKind::Class
+                            | NodeKind::Interface
+                            | NodeKind::Enum
+                            | NodeKind::Function
+                            | NodeKind::Method
+                    )
+                    && candidate.span.start_byte <= node.span.start_byte
+                    && candidate.span.end_byte >= node.span.end_byte
+            })
+            .collect();
+        // Wider spans enclose narrower ones; a module covers the whole file
+        containers.sort_by_key(|container| {
+            (
+                container.kind != NodeKind::Module,
+                std::cmp::Reverse(container.span.end_byte - container.span.start_byte),
+                container.span.start_byte,
+            )
+        });
+
+        containers
+            .iter()
+            .map(|container| container.name.as_str())
+            .chain(std::iter::once(node.name.as_str()))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Search symbols by name pattern (regex or fuzzy)
+    pub fn search_symbols(
+        &self,
+        pattern: &str,
+        symbol_types: Option<Vec<NodeKind>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<SymbolInfo>> {
+        self.search_symbols_in_repo(pattern, symbol_types, limit, None)
+    }
+
+    /// Search symbols by name pattern within one registered repository
+    ///
+    /// A `repo_id` of `None` searches the whole graph. The limit counts only
+    /// symbols inside the repository.
+    pub fn search_symbols_in_repo(
+        &self,
+        pattern: &str,
+        symbol_types: Option<Vec<NodeKind>>,
+        limit: Option<usize>,
+        repo_id: Option<&str>,
+    ) -> Result<Vec<SymbolInfo>> {
+        let limit = limit.unwrap_or(50);
+        if let Some(repo_id) = repo_id {
+            if self.graph.repository_root(repo_id).is_none() {
+                return Err(crate::error::Error::validation(
+                    "repo_id",
+                    format!("Unknown repository: {repo_id}"),
+                ));
+            }
+        }
+        let mut results = Vec::new();
+
+        // Try to compile as regex first, fall back to substring search if invalid
+        let regex_result = regex::Regex::new(pattern);
+        let use_regex = regex_result.is_ok();
+        let regex = regex_result.ok();
+
+        for entry in self.graph.symbol_index.iter() {
+            let symbol_name = entry.key();
+
+            let matches = if use_regex {
+                // Use regex matching
+                regex.as_ref().unwrap().is_match(symbol_name)
+            } else {
+                // Fall back to case-insensitive substring search
+                symbol_name.to_lowercase().contains(&pattern.to_lowercase())
+            };
+
+            if matches {
+                for node_id in entry.value() {
+                    if let Some(node) = self.graph.get_node(node_id) {
+                        // Filter by symbol types if specified
+                        if let Some(ref types) = symbol_types {
+                            if !types.contains(&node.kind) {
+                                continue;
+                            }
+                        }
+                        if repo_id.is_some()
+                            && self.graph.repository_of(&node.file).as_deref() != repo_id
+                        {
+                            continue;
+                        }
+
+                        results.push(SymbolInfo {
+                            node,
+                            references_count: self.graph.get_incoming_edges(node_id).len(),
+                            dependencies_count: self.graph.get_outgoing_edges(node_id).len(),
+                            call_sites: Vec::new(),
+                        });
+
+                        if results.len() >= limit {
+                            break;
+                        }
+                    }
+                }
+
+                if results.len() >= limit {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Search symbols one page at a time
+    ///
+    /// Matches are ordered by file, position and ID, so consecutive pages
+    /// never overlap. The returned cursor resumes after the page; it records
+    /// the graph generation and a hash of the query, and is rejected once the
+    /// graph has changed or when passed with a different pattern or filters.
+    pub fn search_symbols_paged(
+        &self,
+        pattern: &str,
+        symbol_types: Option<Vec<NodeKind>>,
+        inheritance_filters: Option<Vec<InheritanceFilter>>,
+        repo_id: Option<&str>,
+        limit: usize,
+        cursor: Option<&str>,
+    ) -> Result<SymbolPage> {
+        let generation = self.graph.generation();
+        let query = query_hash(&format!(
+            "{pattern}\0{symbol_types:?}\0{inheritance_filters:?}\0{repo_id:?}"
+        ));
+        let offset = match cursor {
+            Some(cursor) => {
+                let (cursor_generation, cursor_query, offset) =
+                    decode_cursor(cursor).ok_or_else(|| {
+                        crate::error::Error::validation(
+                            "cursor",
+                            format!("Invalid cursor: {cursor}"),
+                        )
+                    })?;
+                if cursor_query != query {
+                    return Err(crate::error::Error::validation(
+                        "cursor",
+                        "Cursor belongs to a different query; pass the same pattern and filters or restart without a cursor",
+                    ));
+                }
+                if cursor_generation != generation {
+                    return Err(crate::error::Error::validation(
+                        "cursor",
+                        "Cursor expired because the graph changed; restart the search without a cursor",
+                    ));
+                }
+                offset
+            }
+            None => 0,
+        };
+
+        if let Some(repo_id) = repo_id {
+            if self.graph.repository_root(repo_id).is_none() {
+                return Err(crate::error::Error::validation(
+                    "repo_id",
+                    format!("Unknown repository: {repo_id}"),
+                ));
+            }
+        }
+        let regex = regex::Regex::new(pattern).ok();
+        let lowercase_pattern = pattern.to_lowercase();
+
+        let mut matches: Vec<Node> = self
+            .graph
+            .symbol_index
+            .iter()
+            .filter(|entry| match &regex {
+                Some(regex) => regex.is_match(entry.key()),
+                None => entry.key().to_lowercase().contains(&lowercase_pattern),
+            })
+            .flat_map(|entry| entry.value().clone())
+            .filter_map(|node_id| self.graph.get_node(&node_id))
+            .filter(|node| {
+                symbol_types
+                    .as_ref()
+                    .is_none_or(|types| types.contains(&node.kind))
+            })
+            .filter(|node| {
+                repo_id.is_none() || self.graph.repository_of(&node.file).as_deref() == repo_id
+            })
+            .filter(|node| {
+                inheritance_filters.as_ref().is_none_or(|filters| {
+                    filters
+                        .iter()
+                        .any(|filter| self.matches_inheritance_filter(node, filter))
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            (&a.file, a.span.start_byte, a.id.to_hex()).cmp(&(
+                &b.file,
+                b.span.start_byte,
+                b.id.to_hex(),
+            ))
+        });
+
+        let total_matches = matches.len();
+        let end = offset.saturating_add(limit.max(1)).min(total_matches);
+        let symbols = matches
+            .into_iter()
+            .skip(offset)
+            .take(end.saturating_sub(offset))
+            .map(|node| SymbolInfo {
+                references_count: self.graph.get_incoming_edges(&node.id).len(),
+                dependencies_count: self.graph.get_outgoing_edges(&node.id).len(),
+                node,
+                call_sites: Vec::new(),
+            })
+            .collect();
+
+        Ok(SymbolPage {
+            symbols,
+            total_matches,
+            next_cursor: (end < total_matches).then(|| encode_cursor(generation, query, end)),
+        })
+    }
+
+    /// Search symbols by an approximate name, e.g. `usrsvc` for `UserService`
+    ///
+    /// Names match when the query's characters appear in them in order,
+    /// case-insensitively unless the query has uppercase letters. Results are
+    /// ranked by descending score, ties going to the shorter name, and capped
+    /// at `limit`.
+    pub fn fuzzy_search(
+        &self,
+        query: &str,
+        symbol_types: Option<Vec<NodeKind>>,
+        inheritance_filters: Option<Vec<InheritanceFilter>>,
+        repo_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<FuzzyMatch>> {
+        if query.trim().is_empty() {
+            return Err(crate::error::Error::validation(
+                "pattern",
+                "Fuzzy search needs a non-empty query",
+            ));
+        }
+        if let Some(repo_id) = repo_id {
+            if self.graph.repository_root(repo_id).is_none() {
+                return Err(crate::error::Error::validation(
+                    "repo_id",
+                    format!("Unknown repository: {repo_id}"),
+                ));
+            }
+        }
+        let matcher = SkimMatcherV2::default();
+
+        let mut matches: Vec<(Node, i64, Vec<usize>)> = self
+            .graph
+            .symbol_index
+            .iter()
+            .filter_map(|entry| {
+                let (score, positions) = matcher.fuzzy_indices(entry.key(), query)?;
+                Some((entry.value().clone(), score, positions))
+            })
+            .flat_map(|(node_ids, score, positions)| {
+                node_ids
+                    .into_iter()
+                    .map(move |node_id| (node_id, score, positions.clone()))
+            })
+            .filter_map(|(node_id, score, positions)| {
+                Some((self.graph.get_node(&node_id)?, score, positions))
+            })
+            .filter(|(node, _, _)| {
+                symbol_types
+                    .as_ref()
+                    .is_none_or(|types| types.contains(&node.kind))
+            })
+            .filter(|(node, _, _)| {
+                repo_id.is_none() || self.graph.repository_of(&node.file).as_deref() == repo_id
+            })
+            .filter(|(node, _, _)| {
+                inheritance_filters.as_ref().is_none_or(|filters| {
+                    filters
+                        .iter()
+                        .any(|filter| self.matches_inheritance_filter(node, filter))
+                })
+            })
+            .collect();
+        matches.sort_by(|(a, a_score, _), (b, b_score, _)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.name.chars().count().cmp(&b.name.chars().count()))
+                .then_with(|| {
+                    (&a.name, &a.file, a.span.start_byte).cmp(&(
+                        &b.name,
+                        &b.file,
+                        b.span.start_byte,
+                    ))
+                })
+        });
+        matches.truncate(limit);
+
+        Ok(matches
+            .into_iter()
+            .map(|(node, score, positions)| FuzzyMatch {
+                symbol: SymbolInfo {
+                    references_count: self.graph.get_incoming_edges(&node.id).len(),
+                    dependencies_count: self.graph.get_outgoing_edges(&node.id).len(),
+                    node,
+                    call_sites: Vec::new(),
+                },
+                score,
+                positions,
+            })
+            .collect())
+    }
+
+    /// Search symbols by name pattern with inheritance filters
+    pub fn search_symbols_with_inheritance(
+        &self,
+        pattern: &str,
+        symbol_types: Option<Vec<NodeKind>>,
+        inheritance_filters: Option<Vec<InheritanceFilter>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<SymbolInfo>> {
+        self.search_symbols_with_inheritance_in_repo(
+            pattern,
+            symbol_types,
+            inheritance_filters,
+            limit,
+            None,
+        )
+    }
+
+    /// Search symbols with inheritance filters within one registered repository
+    pub fn search_symbols_with_inheritance_in_repo(
+        &self,
+        pattern: &str,
+        symbol_types: Option<Vec<NodeKind>>,
+        inheritance_filters: Option<Vec<InheritanceFilter>>,
+        limit: Option<usize>,
+        repo_id: Option<&str>,
+    ) -> Result<Vec<SymbolInfo>> {
+        let mut results = self.search_symbols_in_repo(pattern, symbol_types, limit, repo_id)?;
+
+        if let Some(filters) = inheritance_filters {
+            results.retain(|symbol_info| {
+                filters
+                    .iter()
+                    .any(|filter| self.matches_inheritance_filter(&symbol_info.node, filter))
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Check if a node matches an inheritance filter
+    fn matches_inheritance_filter(&self, node: &Node, filter: &InheritanceFilter) -> bool {
+        match filter {
+            InheritanceFilter::InheritsFrom(base_class) => {
+                self.inherits_from(&node.id, base_class).unwrap_or(false)
+            }
+            InheritanceFilter::HasMetaclass(metaclass) => {
+                self.has_metaclass(&node.id, metaclass).unwrap_or(false)
+            }
+            InheritanceFilter::UsesMixin(mixin) => {
+                self.uses_mixin(&node.id, mixin).unwrap_or(false)
+            }
+        }
+    }
+
+    /// Get comprehensive inheritance information for a class
     pub fn get_inheritance_info(&self, node_id: &NodeId) -> Result<InheritanceInfo> {
         let node = self
             .graph
             .get_node(node_id)
             .ok_or_else(|| crate::error::Error::node_not_found(node_id.to_hex()))?;
 
-        if !matches!(node.kind, NodeKind::Class) {
+        if !matches!(node.kind, NodeKind::Class | NodeKind::Interface) {
             return Ok(InheritanceInfo::default());
         }
 
@@ -507,12 +1904,22 @@ impl GraphQuery {
         })
     }
 
-    /// Get direct base classes of a class
+    /// Get direct base classes of a class, including implemented interfaces
     pub fn get_base_classes(&self, node_id: &NodeId) -> Result<Vec<InheritanceRelation>> {
         let mut base_classes = Vec::new();
 
         for edge in self.graph.get_outgoing_edges(node_id) {
-            if matches!(edge.kind, EdgeKind::Extends) {
+            if matches!(edge.kind, EdgeKind::Implements) {
+                if let Some(interface_node) = self.graph.get_node(&edge.target) {
+                    base_classes.push(InheritanceRelation {
+                        class_name: interface_node.name.clone(),
+                        node_id: interface_node.id,
+                        relationship_type: "implements".to_string(),
+                        file: interface_node.file.clone(),
+                        span: interface_node.span.clone(),
+                    });
+                }
+            } else if matches!(edge.kind, EdgeKind::Extends) {
                 if let Some(parent_node) = self.graph.get_node(&edge.target) {
                     let is_metaclass = parent_node
                         .metadata
@@ -543,16 +1950,19 @@ impl GraphQuery {
         let mut subclasses = Vec::new();
 
         for edge in self.graph.get_incoming_edges(node_id) {
-            if matches!(edge.kind, EdgeKind::Extends) {
-                if let Some(child_node) = self.graph.get_node(&edge.source) {
-                    subclasses.push(InheritanceRelation {
-                        class_name: child_node.name.clone(),
-                        node_id: child_node.id,
-                        relationship_type: "extends".to_string(),
-                        file: child_node.file.clone(),
-                        span: child_node.span.clone(),
-                    });
-                }
+            let relationship_type = match edge.kind {
+                EdgeKind::Extends => "extends",
+                EdgeKind::Implements => "implements",
+                _ => continue,
+            };
+            if let Some(child_node) = self.graph.get_node(&edge.source) {
+                subclasses.push(InheritanceRelation {
+                    class_name: child_node.name.clone(),
+                    node_id: child_node.id,
+                    relationship_type: relationship_type.to_string(),
+                    file: child_node.file.clone(),
+                    span: child_node.span.clone(),
+                });
             }
         }
 
@@ -803,30 +2213,446 @@ pub struct PathResult {
     pub edges: Vec<Edge>,
 }
 
-/// Information about a symbol
+/// Induced subgraph around a node, see [`GraphQuery::extract_subgraph`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SymbolInfo {
-    /// The node representing the symbol
-    pub node: Node,
-    /// Number of references to this symbol
-    pub references_count: usize,
-    /// Number of dependencies from this symbol
-    pub dependencies_count: usize,
+pub struct Subgraph {
+    /// Node the subgraph was extracted around
+    pub center: NodeId,
+    /// Maximum number of hops from the center
+    pub radius: usize,
+    /// Nodes within the radius
+    pub nodes: Vec<Node>,
+    /// Edges whose source and target are both in the subgraph
+    pub edges: Vec<Edge>,
 }
 
-/// A reference to a symbol
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SymbolReference {
-    /// The node that references the symbol
-    pub source_node: Node,
-    /// Type of reference (edge kind)
-    pub edge_kind: EdgeKind,
-    /// Location of the reference
-    pub location: ReferenceLocation,
+/// Bidirectional map between tests and the production symbols they exercise,
+/// see [`GraphQuery::test_coverage_map`]
+#[derive(Debug, Clone, Default)]
+pub struct TestCoverageMap {
+    /// Production symbols called by each test, in discovery order
+    pub tests_to_symbols: HashMap<NodeId, Vec<NodeId>>,
+    /// Tests calling each production symbol
+    pub symbols_to_tests: HashMap<NodeId, Vec<NodeId>>,
 }
 
-/// Location of a reference
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl TestCoverageMap {
+    fn record(&mut self, test: NodeId, symbol: NodeId) {
+        self.tests_to_symbols.entry(test).or_default().push(symbol);
+        self.symbols_to_tests.entry(symbol).or_default().push(test);
+    }
+
+    /// Production symbols exercised by `test`
+    pub fn symbols_for_test(&self, test: &NodeId) -> &[NodeId] {
+        self.tests_to_symbols.get(test).map_or(&[], Vec::as_slice)
+    }
+
+    /// Tests exercising `symbol`
+    pub fn tests_for_symbol(&self, symbol: &NodeId) -> &[NodeId] {
+        self.symbols_to_tests.get(symbol).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Whether a function or method is named like a test (`test_x`, `testX`, `TestX`)
+pub fn is_test_function(node: &Node) -> bool {
+    if !matches!(node.kind, NodeKind::Function | NodeKind::Method) {
+        return false;
+    }
+    let name = node.name.as_str();
+    let rest = name
+        .strip_prefix("test")
+        .or_else(|| name.strip_prefix("Test"));
+    match rest {
+        Some(rest) => {
+            rest.is_empty() || rest.starts_with('_') || rest.starts_with(char::is_uppercase)
+        }
+        None => false,
+    }
+}
+
+/// Whether a file lives in a test directory or is named like a test file
+pub fn is_test_path(path: &std::path::Path) -> bool {
+    let in_test_dir = path.components().any(|component| {
+        matches!(
+            component.as_os_str().to_str(),
+            Some("test" | "tests" | "__tests__" | "spec" | "specs")
+        )
+    });
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    in_test_dir
+        || stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with(".test")
+        || stem.ends_with(".spec")
+        || stem == "conftest"
+}
+
+/// Information about a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolInfo {
+    /// The node representing the symbol
+    pub node: Node,
+    /// Number of references to this symbol
+    pub references_count: usize,
+    /// Number of dependencies from this symbol
+    pub dependencies_count: usize,
+    /// Call sites linking this symbol to the queried one, filled in by
+    /// [`GraphQuery::find_callers`] and [`GraphQuery::find_callees`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub call_sites: Vec<ReferenceLocation>,
+}
+
+/// One page of [`GraphQuery::search_symbols_paged`] results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolPage {
+    /// Symbols on this page
+    pub symbols: Vec<SymbolInfo>,
+    /// Matches across all pages
+    pub total_matches: usize,
+    /// Cursor for the next page, `None` once the results are exhausted
+    pub next_cursor: Option<String>,
+}
+
+/// A symbol found by [`GraphQuery::fuzzy_search`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzyMatch {
+    /// The matched symbol
+    pub symbol: SymbolInfo,
+    /// Match quality; higher is better
+    pub score: i64,
+    /// Character positions in the symbol name matched by the query
+    pub positions: Vec<usize>,
+}
+
+/// Short hash identifying a paged query, stored in its cursors
+fn query_hash(query: &str) -> u64 {
+    let hash = blake3::hash(query.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Encode a graph generation, query hash and result offset as an opaque cursor
+fn encode_cursor(generation: u64, query: u64, offset: usize) -> String {
+    format!("{generation:016x}{query:016x}{:016x}", offset as u64)
+}
+
+fn decode_cursor(cursor: &str) -> Option<(u64, u64, usize)> {
+    if cursor.len() != 48 || !cursor.is_ascii() {
+        return None;
+    }
+    let generation = u64::from_str_radix(&cursor[..16], 16).ok()?;
+    let query = u64::from_str_radix(&cursor[16..32], 16).ok()?;
+    let offset = u64::from_str_radix(&cursor[32..], 16).ok()?;
+    Some((generation, query, usize::try_from(offset).ok()?))
+}
+
+/// Centrality measure for [`GraphQuery::compute_centrality`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CentralityMetric {
+    /// Number of distinct callers and callees
+    Degree,
+    /// Number of distinct callers
+    InDegree,
+    /// Number of distinct callees
+    OutDegree,
+    /// Share of shortest call paths between other functions passing through a function
+    Betweenness,
+}
+
+/// Bounds on the work done by [`GraphQuery::compute_centrality_with`]
+#[derive(Debug, Clone)]
+pub struct CentralityOptions {
+    /// Most betweenness sources to expand before switching to sampling
+    pub max_sources: usize,
+    /// Wall-clock budget for betweenness; `None` runs to completion
+    pub time_budget: Option<std::time::Duration>,
+    /// Set from another thread to stop betweenness early
+    pub cancel: Option<Arc<std::sync::atomic::AtomicBool>>,
+}
+
+impl Default for CentralityOptions {
+    fn default() -> Self {
+        Self {
+            max_sources: 2000,
+            time_budget: Some(std::time::Duration::from_secs(30)),
+            cancel: None,
+        }
+    }
+}
+
+/// Centrality scores and how much of the graph they were computed from
+#[derive(Debug, Clone)]
+pub struct CentralityScores {
+    /// Scores sorted highest first
+    pub scores: Vec<(NodeId, f64)>,
+    /// Shortest-path sources expanded; all vertices for degree metrics
+    pub sources_processed: usize,
+    /// Sources a complete run would expand
+    pub total_sources: usize,
+    /// False when betweenness stopped early because it was cancelled or out of time
+    pub complete: bool,
+}
+
+impl CentralityScores {
+    /// Whether every vertex was used as a source, making the scores exact
+    pub fn is_exact(&self) -> bool {
+        self.complete && self.sources_processed == self.total_sources
+    }
+}
+
+/// Bounds on the search done by [`GraphQuery::find_longest_call_chains`]
+#[derive(Debug, Clone)]
+pub struct CallChainOptions {
+    /// Most calls in one chain
+    pub max_depth: usize,
+    /// Most call edges followed over the whole search
+    pub max_expansions: usize,
+    /// Most chains returned
+    pub limit: usize,
+    /// Set from another thread to stop the search early
+    pub cancel: Option<Arc<std::sync::atomic::AtomicBool>>,
+}
+
+impl Default for CallChainOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 50,
+            max_expansions: 200_000,
+            limit: 10,
+            cancel: None,
+        }
+    }
+}
+
+/// A path through the call graph visiting each symbol at most once
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallChain {
+    /// Functions and methods in call order, starting with the outermost caller
+    pub nodes: Vec<NodeId>,
+}
+
+impl CallChain {
+    /// Number of calls in the chain
+    pub fn length(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+}
+
+/// Longest call chains and whether the search covered the whole graph
+#[derive(Debug, Clone)]
+pub struct CallChains {
+    /// Chains sorted longest first
+    pub chains: Vec<CallChain>,
+    /// False when the expansion budget ran out before the search finished
+    pub complete: bool,
+}
+
+/// Which way [`GraphQuery::trace_data_flow`] follows a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowDirection {
+    /// Where the value goes
+    Forward,
+    /// Where the value came from
+    Backward,
+}
+
+/// Bounds on [`GraphQuery::trace_data_flow_with`]
+#[derive(Debug, Clone)]
+pub struct DataFlowOptions {
+    /// Most steps away from the start node
+    pub max_depth: usize,
+}
+
+impl Default for DataFlowOptions {
+    fn default() -> Self {
+        Self { max_depth: 10 }
+    }
+}
+
+/// One step of a data flow trace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowStep {
+    /// Node the value reaches
+    pub node: NodeId,
+    /// Node the step was taken from
+    pub from: NodeId,
+    /// `Reads` or `Writes` for assignments, `Calls` for argument bindings
+    pub edge_kind: EdgeKind,
+    /// Location of the node reached
+    pub span: crate::ast::Span,
+    /// Number of steps from the start node
+    pub depth: usize,
+}
+
+/// Symbols affected by a change, grouped by distance from the changed symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImpactReport {
+    /// The changed symbol
+    pub target: NodeId,
+    /// `hops[0]` depends on the target directly, `hops[1]` on those, and so on
+    pub hops: Vec<Vec<ImpactedSymbol>>,
+    /// True when `max_depth` stopped the search with symbols left unvisited
+    pub truncated: bool,
+}
+
+impl ImpactReport {
+    /// Number of affected symbols over all hops
+    pub fn total_affected(&self) -> usize {
+        self.hops.iter().map(Vec::len).sum()
+    }
+
+    /// Affected symbols with their distance from the target, nearest first
+    pub fn affected(&self) -> impl Iterator<Item = (usize, &ImpactedSymbol)> {
+        self.hops
+            .iter()
+            .enumerate()
+            .flat_map(|(index, hop)| hop.iter().map(move |symbol| (index + 1, symbol)))
+    }
+}
+
+/// Lines of context [`GraphQuery::explain`] shows around a definition
+pub const EXPLAIN_CONTEXT_LINES: usize = 3;
+
+/// A symbol with what is needed to understand it, see [`GraphQuery::explain`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolExplanation {
+    /// The symbol itself, including its definition span
+    pub node: Node,
+    /// Fully-qualified name, e.g. `module.Class.save`
+    pub qualified_name: String,
+    /// Source of the definition with a few lines of context
+    pub snippet: Option<SourceSnippet>,
+    /// Doc comment or docstring attached to the definition
+    pub doc_comment: Option<String>,
+    /// Distinct functions calling the symbol
+    pub caller_count: usize,
+    /// Distinct symbols the symbol calls
+    pub callee_count: usize,
+    /// Base classes and subclasses, for classes and interfaces
+    pub inheritance: Option<InheritanceInfo>,
+}
+
+/// A range of source lines, 1-based and inclusive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSnippet {
+    /// First line of the snippet
+    pub start_line: usize,
+    /// Last line of the snippet
+    pub end_line: usize,
+    /// The lines themselves
+    pub text: String,
+}
+
+impl SourceSnippet {
+    /// Lines of `span` in `source` with `context_lines` more on either side
+    pub fn around(source: &str, span: &crate::ast::Span, context_lines: usize) -> Self {
+        let lines: Vec<&str> = source.lines().collect();
+        let start_line = span.start_line.saturating_sub(context_lines).max(1);
+        let end_line = (span.end_line + context_lines)
+            .min(lines.len())
+            .max(start_line);
+        let text = lines
+            .get(start_line - 1..end_line)
+            .unwrap_or_default()
+            .join("\n");
+        Self {
+            start_line,
+            end_line,
+            text,
+        }
+    }
+}
+
+/// A symbol reached by [`GraphQuery::impact_set`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImpactedSymbol {
+    /// The affected symbol
+    pub node: NodeId,
+    /// Symbol one hop closer to the target that this one depends on
+    pub from: NodeId,
+    /// `Calls`, `Reads`, `Extends` or `Implements`
+    pub edge_kind: EdgeKind,
+    /// Location of the affected symbol
+    pub span: crate::ast::Span,
+}
+
+/// Call sites grouped by the symbol on the other end of the call
+#[derive(Default)]
+struct CallGrouping {
+    symbols: Vec<(Node, Vec<ReferenceLocation>)>,
+    positions: HashMap<NodeId, usize>,
+}
+
+impl CallGrouping {
+    fn add(&mut self, symbol: Node, location: ReferenceLocation) {
+        let index = *self.positions.entry(symbol.id).or_insert_with(|| {
+            self.symbols.push((symbol, Vec::new()));
+            self.symbols.len() - 1
+        });
+        let sites = &mut self.symbols[index].1;
+        if !sites
+            .iter()
+            .any(|site| site.file == location.file && site.span == location.span)
+        {
+            sites.push(location);
+        }
+    }
+
+    fn into_symbols(self, graph: &GraphStore) -> Vec<SymbolInfo> {
+        self.symbols
+            .into_iter()
+            .map(|(node, call_sites)| SymbolInfo {
+                references_count: graph.get_incoming_edges(&node.id).len(),
+                dependencies_count: graph.get_outgoing_edges(&node.id).len(),
+                node,
+                call_sites,
+            })
+            .collect()
+    }
+}
+
+/// Last segment of a call target such as `self.save` or `fs::read`
+fn call_target_name(call_name: &str) -> &str {
+    call_name.rsplit(['.', ':']).next().unwrap_or(call_name)
+}
+
+/// Whether `outer` encloses `inner`
+fn spans_contain(outer: &crate::ast::Span, inner: &crate::ast::Span) -> bool {
+    outer.start_byte <= inner.start_byte && inner.end_byte <= outer.end_byte
+}
+
+/// Position of a named argument in a call site's `arguments` metadata
+fn argument_position(call: &Node, argument: &str) -> Option<usize> {
+    call.metadata
+        .get("arguments")?
+        .as_array()?
+        .iter()
+        .position(|value| value.as_str() == Some(argument))
+}
+
+fn call_matches_name(call_name: &str, symbol_name: &str) -> bool {
+    call_target_name(call_name) == symbol_name
+}
+
+/// A reference to a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolReference {
+    /// The node that references the symbol
+    pub source_node: Node,
+    /// Type of reference (edge kind)
+    pub edge_kind: EdgeKind,
+    /// Location of the reference
+    pub location: ReferenceLocation,
+}
+
+/// Location of a reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReferenceLocation {
     /// File containing the reference
     pub file: PathBuf,
@@ -920,6 +2746,237 @@ pub struct DynamicAttribute {
     pub attribute_type: String,
 }
 
+/// Tarjan's strongly connected components with an explicit stack
+///
+/// Recursion is avoided so deep dependency chains cannot overflow the stack.
+/// Raw betweenness of every vertex with Brandes' algorithm
+///
+/// Returns the scores, the number of sources expanded and whether the run
+/// finished without being cancelled or running out of time. Scores from a
+/// partial or sampled run are scaled up to the full vertex count.
+fn brandes_betweenness(
+    adjacency: &[Vec<usize>],
+    options: &CentralityOptions,
+) -> (Vec<f64>, usize, bool) {
+    let n = adjacency.len();
+    let mut betweenness = vec![0.0; n];
+    let step = n.div_ceil(options.max_sources.max(1)).max(1);
+    let started = std::time::Instant::now();
+
+    let mut sigma = vec![0.0f64; n];
+    let mut distance = vec![usize::MAX; n];
+    let mut delta = vec![0.0f64; n];
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut order: Vec<usize> = Vec::with_capacity(n);
+    let mut queue = VecDeque::new();
+
+    let mut processed = 0;
+    let mut complete = true;
+    for source in (0..n).step_by(step) {
+        let cancelled = options
+            .cancel
+            .as_ref()
+            .is_some_and(|flag| flag.load(std::sync::atomic::Ordering::Relaxed));
+        let out_of_time = options
+            .time_budget
+            .is_some_and(|budget| started.elapsed() > budget);
+        if cancelled || out_of_time {
+            complete = false;
+            break;
+        }
+
+        // Reset only the vertices touched by the previous source
+        for &v in &order {
+            sigma[v] = 0.0;
+            distance[v] = usize::MAX;
+            delta[v] = 0.0;
+            predecessors[v].clear();
+        }
+        order.clear();
+
+        sigma[source] = 1.0;
+        distance[source] = 0;
+        queue.push_back(source);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for &w in &adjacency[v] {
+                if distance[w] == usize::MAX {
+                    distance[w] = distance[v] + 1;
+                    queue.push_back(w);
+                }
+                if distance[w] == distance[v] + 1 {
+                    sigma[w] += sigma[v];
+                    predecessors[w].push(v);
+                }
+            }
+        }
+
+        for &w in order.iter().rev() {
+            for &v in &predecessors[w] {
+                delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+            }
+            if w != source {
+                betweenness[w] += delta[w];
+            }
+        }
+        processed += 1;
+    }
+
+    if processed > 0 && processed < n {
+        let scale = n as f64 / processed as f64;
+        for score in &mut betweenness {
+            *score *= scale;
+        }
+    }
+    (betweenness, processed, complete)
+}
+
+fn strongly_connected_components(
+    roots: &[NodeId],
+    adjacency: &HashMap<NodeId, Vec<NodeId>>,
+) -> Vec<Vec<NodeId>> {
+    const NO_TARGETS: &[NodeId] = &[];
+
+    let mut index: HashMap<NodeId, usize> = HashMap::new();
+    let mut lowlink: HashMap<NodeId, usize> = HashMap::new();
+    let mut on_stack: HashSet<NodeId> = HashSet::new();
+    let mut stack: Vec<NodeId> = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for &root in roots {
+        if index.contains_key(&root) {
+            continue;
+        }
+
+        // Each frame is a node and the position of the next edge to visit
+        let mut call_stack: Vec<(NodeId, usize)> = vec![(root, 0)];
+        index.insert(root, next_index);
+        lowlink.insert(root, next_index);
+        next_index += 1;
+        stack.push(root);
+        on_stack.insert(root);
+
+        while let Some(&mut (node, ref mut edge_pos)) = call_stack.last_mut() {
+            let targets = adjacency.get(&node).map_or(NO_TARGETS, Vec::as_slice);
+
+            if let Some(&target) = targets.get(*edge_pos) {
+                *edge_pos += 1;
+                match index.get(&target).copied() {
+                    None => {
+                        index.insert(target, next_index);
+                        lowlink.insert(target, next_index);
+                        next_index += 1;
+                        stack.push(target);
+                        on_stack.insert(target);
+                        call_stack.push((target, 0));
+                    }
+                    Some(target_index) if on_stack.contains(&target) => {
+                        let low = lowlink[&node].min(target_index);
+                        lowlink.insert(node, low);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                let low = lowlink[&parent].min(lowlink[&node]);
+                lowlink.insert(parent, low);
+            }
+
+            if lowlink[&node] == index[&node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack.remove(&member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.reverse();
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Order a strongly connected component as a closed walk along its edges
+///
+/// Starts at the member with the smallest id and repeatedly takes the
+/// shortest path to the nearest member not yet visited.
+fn order_cycle(component: &[NodeId], adjacency: &HashMap<NodeId, Vec<NodeId>>) -> Vec<NodeId> {
+    let members: HashSet<NodeId> = component.iter().copied().collect();
+    let start = *component
+        .iter()
+        .min_by_key(|id| id.to_hex())
+        .expect("components are never empty");
+
+    let mut walk = vec![start];
+    let mut unvisited: HashSet<NodeId> = members.clone();
+    unvisited.remove(&start);
+    let mut current = start;
+
+    while !unvisited.is_empty() {
+        let path = shortest_path_within(current, &members, adjacency, |id| unvisited.contains(id));
+        let Some(path) = path else { break };
+        for id in &path[1..] {
+            unvisited.remove(id);
+        }
+        current = *path.last().unwrap();
+        walk.extend_from_slice(&path[1..]);
+    }
+
+    // Close the walk; the final hop back to `start` is implied
+    if current != start {
+        if let Some(path) = shortest_path_within(current, &members, adjacency, |id| *id == start) {
+            walk.extend_from_slice(&path[1..path.len() - 1]);
+        }
+    }
+
+    walk
+}
+
+/// Breadth-first search restricted to `members`, returning the path from
+/// `from` to the first node accepted by `is_goal`
+fn shortest_path_within(
+    from: NodeId,
+    members: &HashSet<NodeId>,
+    adjacency: &HashMap<NodeId, Vec<NodeId>>,
+    is_goal: impl Fn(&NodeId) -> bool,
+) -> Option<Vec<NodeId>> {
+    let mut parent: HashMap<NodeId, NodeId> = HashMap::new();
+    let mut visited = HashSet::from([from]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(node) = queue.pop_front() {
+        for &target in adjacency.get(&node).into_iter().flatten() {
+            if !members.contains(&target) {
+                continue;
+            }
+            if is_goal(&target) {
+                let mut path = vec![target, node];
+                let mut current = node;
+                while let Some(&prev) = parent.get(&current) {
+                    path.push(prev);
+                    current = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if visited.insert(target) {
+                parent.insert(target, node);
+                queue.push_back(target);
+            }
+        }
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -978,6 +3035,138 @@ mod tests {
         assert_eq!(incoming[0], edge);
     }
 
+    #[test]
+    fn test_repository_registration_waits_for_snapshots() {
+        let graph = Arc::new(GraphStore::new());
+        graph.register_repository("old", PathBuf::from("/repos/old"));
+
+        // Stand in for a snapshot in progress
+        let reading = graph.read_guard();
+        let writer = {
+            let graph = Arc::clone(&graph);
+            std::thread::spawn(move || {
+                graph.register_repository("new", PathBuf::from("/repos/new"));
+                graph.unregister_repository("old");
+            })
+        };
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(graph.repository_root("new").is_none());
+        assert!(graph.repository_root("old").is_some());
+
+        drop(reading);
+        writer.join().unwrap();
+        assert_eq!(
+            graph.repositories(),
+            vec![("new".to_string(), PathBuf::from("/repos/new"))]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_is_isolated_from_later_updates() {
+        let graph = GraphStore::new();
+        let first = create_test_node_with_span("first", NodeKind::Function, "a.py", 0, 10);
+        let second = create_test_node_with_span("second", NodeKind::Function, "a.py", 20, 30);
+        graph.add_node(first.clone());
+
+        let snapshot = graph.snapshot();
+        graph.add_node(second.clone());
+        graph.add_edge(Edge::new(first.id, second.id, EdgeKind::Calls));
+        graph.remove_node(&first.id);
+
+        assert_eq!(snapshot.get_stats().total_nodes, 1);
+        assert!(snapshot.get_node(&first.id).is_some());
+        assert!(snapshot.get_outgoing_edges(&first.id).is_empty());
+        assert!(snapshot.generation() < graph.generation());
+        assert_eq!(graph.get_stats().total_nodes, 1);
+        assert!(graph.get_node(&second.id).is_some());
+    }
+
+    #[test]
+    fn test_concurrent_readers_see_consistent_monotonic_snapshots() {
+        use std::sync::atomic::AtomicBool;
+
+        const ROUNDS: usize = 300;
+        let graph = Arc::new(GraphStore::new());
+        let done = Arc::new(AtomicBool::new(false));
+
+        // Each round adds a node linked to the previous one, then replaces
+        // it in a single patch, so the live count dips inside every patch
+        let writer = {
+            let graph = Arc::clone(&graph);
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let mut previous: Option<Node> = None;
+                for round in 0..ROUNDS {
+                    let file = format!("file{round}.py");
+                    let node = create_test_node_with_span("f", NodeKind::Function, &file, 0, 10);
+                    graph.add_node(node.clone());
+                    if let Some(previous) = &previous {
+                        graph.add_edge(Edge::new(node.id, previous.id, EdgeKind::Calls));
+                    }
+
+                    let replacement =
+                        create_test_node_with_span("g", NodeKind::Function, &file, 0, 20);
+                    let mut patch = AstPatch::new("repo".to_string(), round.to_string());
+                    patch.nodes_delete.push(node.id.to_hex());
+                    patch.nodes_add.push(replacement.clone());
+                    if let Some(previous) = &previous {
+                        patch.edges_add.push(Edge::new(
+                            replacement.id,
+                            previous.id,
+                            EdgeKind::Calls,
+                        ));
+                    }
+                    graph.apply_patch(&patch);
+                    previous = Some(replacement);
+                }
+                done.store(true, Ordering::SeqCst);
+            })
+        };
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let graph = Arc::clone(&graph);
+                let done = Arc::clone(&done);
+                std::thread::spawn(move || {
+                    let mut last_count = 0;
+                    let mut snapshots = 0;
+                    while !done.load(Ordering::SeqCst) || snapshots == 0 {
+                        let snapshot = graph.snapshot();
+                        let stats = snapshot.get_stats();
+                        assert!(stats.total_nodes >= last_count, "node count went backwards");
+                        last_count = stats.total_nodes;
+                        // Every round's file holds exactly one node, and every
+                        // edge joins two nodes of the same snapshot
+                        assert_eq!(stats.total_nodes, stats.total_files);
+                        for (_, ids) in snapshot.iter_file_index() {
+                            assert_eq!(ids.len(), 1);
+                        }
+                        for entry in snapshot.outgoing_edges.iter() {
+                            for edge in entry.value() {
+                                assert!(snapshot.get_node(&edge.source).is_some());
+                                assert!(snapshot.get_node(&edge.target).is_some());
+                            }
+                        }
+
+                        // Live lookups race the writer but must not panic
+                        let _ = graph.get_nodes_by_name("g");
+                        let _ = graph.get_stats();
+                        snapshots += 1;
+                    }
+                    snapshots
+                })
+            })
+            .collect();
+
+        writer.join().unwrap();
+        for reader in readers {
+            assert!(reader.join().unwrap() > 0);
+        }
+        let stats = graph.get_stats();
+        assert_eq!(stats.total_nodes, ROUNDS);
+        assert_eq!(stats.total_edges, ROUNDS - 1);
+    }
+
     #[test]
     fn test_graph_query_path_finding() {
         let graph = Arc::new(GraphStore::new());
@@ -1002,6 +3191,153 @@ mod tests {
         assert_eq!(path.path, vec![node1.id, node2.id, node3.id]);
     }
 
+    #[test]
+    fn test_explain_gathers_doc_snippet_calls_and_inheritance() {
+        let source = r#"import os
+
+
+class Base:
+    pass
+
+
+class Service(Base):
+    """Loads and saves records."""
+
+    def save(self, record):
+        # Persist the record
+        validate(record)
+        return record
+
+
+def validate(record):
+    """Check a record before saving."""
+    return record
+
+
+def main():
+    Service().save({})
+"#;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("service.py");
+        std::fs::write(&file, source).unwrap();
+
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let node = |name: &str, kind, start_line: usize, end_line: usize| {
+            let span = Span::new(
+                line_starts[start_line - 1],
+                line_starts[end_line] - 1,
+                start_line,
+                end_line,
+                1,
+                1,
+            );
+            Node::new(
+                "test_repo",
+                kind,
+                name.to_string(),
+                Language::Python,
+                file.clone(),
+                span,
+            )
+        };
+        let base = node("Base", NodeKind::Class, 4, 5);
+        let service = node("Service", NodeKind::Class, 8, 14);
+        let save = node("save", NodeKind::Method, 11, 14);
+        let validate = node("validate", NodeKind::Function, 17, 19);
+        let main = node("main", NodeKind::Function, 22, 23);
+
+        let graph = Arc::new(GraphStore::new());
+        for node in [&base, &service, &save, &validate, &main] {
+            graph.add_node(node.clone());
+        }
+        graph.add_edge(Edge::new(service.id, base.id, EdgeKind::Extends));
+        graph.add_edge(Edge::new(save.id, validate.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(main.id, save.id, EdgeKind::Calls));
+        let query = GraphQuery::new(graph);
+
+        let explained = query.explain(validate.id).unwrap();
+        assert_eq!(explained.node.id, validate.id);
+        assert_eq!(
+            explained.doc_comment.as_deref(),
+            Some("Check a record before saving.")
+        );
+        assert_eq!((explained.caller_count, explained.callee_count), (1, 0));
+        assert!(explained.inheritance.is_none());
+        let snippet = explained.snippet.unwrap();
+        assert_eq!((snippet.start_line, snippet.end_line), (14, 22));
+        assert!(snippet.text.starts_with("        return record\n"));
+        assert!(snippet.text.ends_with("def main():"));
+
+        // Comments inside the body are not documentation
+        let explained = query.explain_with_context(save.id, 0).unwrap();
+        assert_eq!(explained.doc_comment, None);
+        assert_eq!((explained.caller_count, explained.callee_count), (1, 1));
+        assert_eq!(explained.qualified_name, "Service.save");
+        assert_eq!(explained.snippet.unwrap().text.lines().count(), 4);
+
+        let explained = query.explain(service.id).unwrap();
+        assert_eq!(
+            explained.doc_comment.as_deref(),
+            Some("Loads and saves records.")
+        );
+        let inheritance = explained.inheritance.unwrap();
+        assert_eq!(inheritance.base_classes.len(), 1);
+        assert_eq!(inheritance.base_classes[0].class_name, "Base");
+
+        // Snippets stay within the file
+        let snippet = query.explain_with_context(main.id, 10).unwrap().snippet;
+        assert_eq!(snippet.unwrap().end_line, 23);
+
+        let missing = node("missing", NodeKind::Function, 1, 1);
+        assert!(query.explain(missing.id).is_err());
+    }
+
+    #[test]
+    fn test_shortest_path_follows_selected_edge_kinds() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let main = create_test_node_with_span("main", NodeKind::Function, "app.py", 0, 10);
+        let load = create_test_node_with_span("load", NodeKind::Function, "app.py", 20, 30);
+        let parse = create_test_node_with_span("parse", NodeKind::Function, "app.py", 40, 50);
+        let config = create_test_node_with_span("config", NodeKind::Module, "config.py", 0, 60);
+        for node in [&main, &load, &parse, &config] {
+            graph.add_node(node.clone());
+        }
+
+        graph.add_edge(Edge::new(main.id, load.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(load.id, parse.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(main.id, config.id, EdgeKind::Imports));
+        graph.add_edge(Edge::new(config.id, parse.id, EdgeKind::Reads));
+
+        let calls = query
+            .shortest_path(main.id, parse.id, &[EdgeKind::Calls])
+            .unwrap();
+        assert_eq!(calls.path, vec![main.id, load.id, parse.id]);
+        assert_eq!(calls.distance, 2);
+        assert!(calls.edges.iter().all(|e| e.kind == EdgeKind::Calls));
+
+        // Following every edge kind, both routes take two hops
+        let any = query.shortest_path(main.id, parse.id, &[]).unwrap();
+        assert_eq!(any.distance, 2);
+        assert_eq!(any.edges[0].source, main.id);
+        assert_eq!(any.edges[1].target, parse.id);
+
+        assert!(query
+            .shortest_path(main.id, config.id, &[EdgeKind::Calls])
+            .is_none());
+        assert!(query.shortest_path(parse.id, main.id, &[]).is_none());
+        assert_eq!(
+            query
+                .shortest_path(main.id, main.id, &[EdgeKind::Calls])
+                .unwrap()
+                .path,
+            vec![main.id]
+        );
+    }
+
     #[test]
     fn test_symbol_search() {
         let graph = Arc::new(GraphStore::new());
@@ -1065,4 +3401,763 @@ mod tests {
         let results = query.search_symbols("Agent", None, None).unwrap();
         assert_eq!(results.len(), 4, "Should have 4 items"); // All nodes containing "Agent"
     }
+
+    #[test]
+    fn test_search_symbols_paged_walks_all_matches_once() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+        for i in 0..5 {
+            graph.add_node(create_test_node_with_span(
+                &format!("handler_{i}"),
+                NodeKind::Function,
+                "handlers.py",
+                i * 20,
+                i * 20 + 10,
+            ));
+        }
+        graph.add_node(create_test_node(
+            "unrelated",
+            NodeKind::Function,
+            "other.py",
+        ));
+
+        let mut names = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = query
+                .search_symbols_paged("^handler_", None, None, None, 2, cursor.as_deref())
+                .unwrap();
+            assert_eq!(page.total_matches, 5);
+            names.extend(page.symbols.into_iter().map(|symbol| symbol.node.name));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(
+            names,
+            vec![
+                "handler_0",
+                "handler_1",
+                "handler_2",
+                "handler_3",
+                "handler_4"
+            ]
+        );
+
+        // A cursor is only accepted for the query that produced it
+        let page = query
+            .search_symbols_paged("^handler_", None, None, None, 2, None)
+            .unwrap();
+        let stale = page.next_cursor.unwrap();
+        let other_pattern =
+            query.search_symbols_paged("^handler", None, None, None, 2, Some(&stale));
+        assert!(other_pattern
+            .unwrap_err()
+            .to_string()
+            .contains("different query"));
+        assert!(query
+            .search_symbols_paged(
+                "^handler_",
+                Some(vec![NodeKind::Method]),
+                None,
+                None,
+                2,
+                Some(&stale)
+            )
+            .is_err());
+
+        // A cursor from before a graph change is rejected
+        graph.add_node(create_test_node(
+            "handler_new",
+            NodeKind::Function,
+            "new.py",
+        ));
+        assert!(query
+            .search_symbols_paged("^handler_", None, None, None, 2, Some(&stale))
+            .is_err());
+        assert!(query
+            .search_symbols_paged("^handler_", None, None, None, 2, Some("not-a-cursor"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_search_ranks_by_score_then_name_length() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+        for (i, name) in [
+            "UserService",
+            "UserServiceFactory",
+            "UnusedResolver",
+            "render_user",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            graph.add_node(create_test_node_with_span(
+                name,
+                NodeKind::Class,
+                "services.py",
+                i * 20,
+                i * 20 + 10,
+            ));
+        }
+
+        let matches = query.fuzzy_search("usrsvc", None, None, None, 10).unwrap();
+        let names: Vec<_> = matches
+            .iter()
+            .map(|found| found.symbol.node.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["UserService", "UserServiceFactory"]);
+        assert!(matches[0].score >= matches[1].score);
+        assert_eq!(matches[0].positions, vec![0, 1, 3, 4, 7, 9]);
+
+        // Equal scores go to the shorter name, and the limit caps the results
+        let matches = query
+            .fuzzy_search("UserService", None, None, None, 2)
+            .unwrap();
+        assert_eq!(matches[0].score, matches[1].score);
+        assert_eq!(matches[0].symbol.node.name, "UserService");
+        assert_eq!(
+            query
+                .fuzzy_search("UserService", None, None, None, 1)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert!(query
+            .fuzzy_search("usr", Some(vec![NodeKind::Function]), None, None, 10)
+            .unwrap()
+            .is_empty());
+        assert!(query.fuzzy_search("  ", None, None, None, 10).is_err());
+    }
+
+    #[test]
+    fn test_find_cycles_orders_members_and_handles_self_loops() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let a = create_test_node("a", NodeKind::Module, "a.py");
+        let b = create_test_node("b", NodeKind::Module, "b.py");
+        let c = create_test_node("c", NodeKind::Module, "c.py");
+        let d = create_test_node("d", NodeKind::Module, "d.py");
+        let e = create_test_node("e", NodeKind::Module, "e.py");
+        for node in [&a, &b, &c, &d, &e] {
+            graph.add_node(node.clone());
+        }
+
+        // a -> b -> c -> a, c -> d (not in the cycle), e imports itself
+        graph.add_edge(Edge::new(a.id, b.id, EdgeKind::Imports));
+        graph.add_edge(Edge::new(b.id, c.id, EdgeKind::Imports));
+        graph.add_edge(Edge::new(c.id, a.id, EdgeKind::Imports));
+        graph.add_edge(Edge::new(c.id, d.id, EdgeKind::Imports));
+        graph.add_edge(Edge::new(e.id, e.id, EdgeKind::Imports));
+        // Call edges are ignored unless requested
+        graph.add_edge(Edge::new(d.id, c.id, EdgeKind::Calls));
+
+        let cycles = query.find_cycles();
+        assert_eq!(cycles.len(), 2, "Should find the ring and the self-loop");
+
+        let ring = cycles.iter().find(|cycle| cycle.len() == 3).unwrap();
+        for (i, node) in ring.iter().enumerate() {
+            let next = ring[(i + 1) % ring.len()];
+            assert!(
+                graph
+                    .get_outgoing_edges(node)
+                    .iter()
+                    .any(|edge| edge.target == next && edge.kind == EdgeKind::Imports),
+                "Cycle must follow import edges"
+            );
+        }
+        assert!(cycles.contains(&vec![e.id]));
+
+        let with_calls = query.find_cycles_with_kinds(&[EdgeKind::Imports, EdgeKind::Calls]);
+        let merged = with_calls
+            .iter()
+            .find(|cycle| cycle.contains(&d.id))
+            .unwrap();
+        assert!(merged.contains(&a.id) && merged.contains(&c.id));
+    }
+
+    #[test]
+    fn test_find_cycles_on_deep_chain_does_not_overflow() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let nodes: Vec<Node> = (0..50_000)
+            .map(|i| {
+                create_test_node_with_span(&format!("m{i}"), NodeKind::Module, "deep.py", i, i + 1)
+            })
+            .collect();
+        for node in &nodes {
+            graph.add_node(node.clone());
+        }
+        for pair in nodes.windows(2) {
+            graph.add_edge(Edge::new(pair[0].id, pair[1].id, EdgeKind::Imports));
+        }
+        assert!(query.find_cycles().is_empty());
+
+        graph.add_edge(Edge::new(
+            nodes[nodes.len() - 1].id,
+            nodes[0].id,
+            EdgeKind::Imports,
+        ));
+        let cycles = query.find_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), nodes.len());
+    }
+
+    #[test]
+    fn test_find_callers_and_callees_group_call_sites() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let module = create_test_node_with_span("app", NodeKind::Module, "app.py", 0, 200);
+        let save = create_test_node_with_span("save", NodeKind::Function, "app.py", 0, 10);
+        let handler = create_test_node_with_span("handler", NodeKind::Function, "app.py", 20, 80);
+        let worker = create_test_node_with_span("worker", NodeKind::Function, "app.py", 100, 150);
+        let first_call = create_test_node_with_span("self.save", NodeKind::Call, "app.py", 30, 40);
+        let second_call = create_test_node_with_span("save", NodeKind::Call, "app.py", 50, 60);
+        let print_call = create_test_node_with_span("print", NodeKind::Call, "app.py", 60, 70);
+        let worker_call = create_test_node_with_span("save", NodeKind::Call, "app.py", 110, 120);
+        for node in [
+            &module,
+            &save,
+            &handler,
+            &worker,
+            &first_call,
+            &second_call,
+            &print_call,
+            &worker_call,
+        ] {
+            graph.add_node(node.clone());
+        }
+        // Parsers link modules to the functions they define with Calls edges too
+        graph.add_edge(Edge::new(module.id, save.id, EdgeKind::Calls));
+        for call in [&first_call, &second_call, &print_call] {
+            graph.add_edge(Edge::new(handler.id, call.id, EdgeKind::Calls));
+        }
+        graph.add_edge(Edge::new(worker.id, worker_call.id, EdgeKind::Calls));
+
+        let callers = query.find_callers(&save.id).unwrap();
+        assert_eq!(callers.len(), 2, "Module is not a caller: {callers:?}");
+        let from_handler = callers.iter().find(|c| c.node.id == handler.id).unwrap();
+        assert_eq!(from_handler.call_sites.len(), 2);
+        assert!(from_handler
+            .call_sites
+            .iter()
+            .any(|site| site.span == first_call.span));
+        let from_worker = callers.iter().find(|c| c.node.id == worker.id).unwrap();
+        assert_eq!(from_worker.call_sites[0].span, worker_call.span);
+
+        let callees = query.find_callees(&handler.id).unwrap();
+        assert_eq!(callees.len(), 2);
+        let to_save = callees.iter().find(|c| c.node.id == save.id).unwrap();
+        assert_eq!(to_save.call_sites.len(), 2);
+        let unresolved = callees.iter().find(|c| c.node.name == "print").unwrap();
+        assert_eq!(unresolved.node.kind, NodeKind::Call);
+        assert_eq!(unresolved.call_sites.len(), 1);
+
+        assert!(query.find_callees(&save.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_extract_subgraph_is_induced_by_radius() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        // a -> b -> c -> d, plus e -> b and c -> a
+        let nodes: Vec<Node> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                create_test_node_with_span(name, NodeKind::Function, "sub.py", i * 10, i * 10 + 5)
+            })
+            .collect();
+        for node in &nodes {
+            graph.add_node(node.clone());
+        }
+        let (a, b, c, d, e) = (&nodes[0], &nodes[1], &nodes[2], &nodes[3], &nodes[4]);
+        graph.add_edge(Edge::new(a.id, b.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(b.id, c.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(c.id, d.id, EdgeKind::Reads));
+        graph.add_edge(Edge::new(e.id, b.id, EdgeKind::Imports));
+        graph.add_edge(Edge::new(c.id, a.id, EdgeKind::Writes));
+
+        let subgraph = query.extract_subgraph(&b.id, 1).unwrap();
+        let names: HashSet<&str> = subgraph.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, HashSet::from(["a", "b", "c", "e"]));
+        assert_eq!(subgraph.nodes[0].id, b.id, "Center comes first");
+        assert_eq!(subgraph.edges.len(), 4, "c -> d leaves the subgraph");
+        assert!(!subgraph.edges.iter().any(|edge| edge.target == d.id));
+
+        let only_center = query.extract_subgraph(&d.id, 0).unwrap();
+        assert_eq!(only_center.nodes.len(), 1);
+        assert!(only_center.edges.is_empty());
+
+        assert_eq!(query.extract_subgraph(&b.id, 2).unwrap().nodes.len(), 5);
+    }
+
+    #[test]
+    fn test_coverage_map_links_tests_to_called_functions() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let test_fn = create_test_node_with_span(
+            "test_checkout",
+            NodeKind::Function,
+            "tests/test_cart.py",
+            0,
+            50,
+        );
+        let helper = create_test_node_with_span(
+            "make_cart",
+            NodeKind::Function,
+            "tests/test_cart.py",
+            60,
+            90,
+        );
+        let add_item = create_test_node_with_span("add_item", NodeKind::Function, "cart.py", 0, 40);
+        let total = create_test_node_with_span("total", NodeKind::Function, "cart.py", 50, 90);
+        let untested = create_test_node_with_span("clear", NodeKind::Function, "cart.py", 100, 120);
+        for node in [&test_fn, &helper, &add_item, &total, &untested] {
+            graph.add_node(node.clone());
+        }
+        graph.add_edge(Edge::new(test_fn.id, helper.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(test_fn.id, add_item.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(test_fn.id, total.id, EdgeKind::Calls));
+
+        let map = query.test_coverage_map(1);
+        let covered: HashSet<NodeId> = map.symbols_for_test(&test_fn.id).iter().copied().collect();
+        assert_eq!(covered, HashSet::from([add_item.id, total.id]));
+        assert_eq!(map.tests_for_symbol(&add_item.id), &[test_fn.id]);
+        assert_eq!(map.tests_for_symbol(&total.id), &[test_fn.id]);
+        assert!(map.tests_for_symbol(&untested.id).is_empty());
+        assert!(
+            map.tests_for_symbol(&helper.id).is_empty(),
+            "Test helpers are not production code"
+        );
+
+        // Deeper walks go through the helper to what it calls
+        graph.add_edge(Edge::new(helper.id, untested.id, EdgeKind::Calls));
+        assert!(query
+            .test_coverage_map(1)
+            .tests_for_symbol(&untested.id)
+            .is_empty());
+        assert_eq!(
+            query.test_coverage_map(2).tests_for_symbol(&untested.id),
+            &[test_fn.id]
+        );
+    }
+
+    #[test]
+    fn test_test_name_and_path_heuristics() {
+        for name in ["test_total", "testTotal", "TestTotal", "test"] {
+            assert!(is_test_function(&create_test_node(
+                name,
+                NodeKind::Function,
+                "a.py"
+            )));
+        }
+        for name in ["testament", "latest", "contest_entry"] {
+            assert!(!is_test_function(&create_test_node(
+                name,
+                NodeKind::Function,
+                "a.py"
+            )));
+        }
+        assert!(is_test_path(std::path::Path::new("src/__tests__/cart.js")));
+        assert!(is_test_path(std::path::Path::new("pkg/cart_test.go")));
+        assert!(is_test_path(std::path::Path::new("web/cart.spec.ts")));
+        assert!(!is_test_path(std::path::Path::new("src/contest.py")));
+    }
+
+    #[test]
+    fn test_find_unreachable_walks_calls_imports_and_extends() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let node = |name: &str, kind: NodeKind, start: usize| {
+            create_test_node_with_span(name, kind, "app.py", start, start + 5)
+        };
+        let main = node("main", NodeKind::Function, 0);
+        let call_site = node("service.run", NodeKind::Call, 10);
+        let run = node("run", NodeKind::Method, 20);
+        let service = node("Service", NodeKind::Class, 30);
+        let base = node("BaseService", NodeKind::Class, 40);
+        let orphan = node("legacy_export", NodeKind::Function, 50);
+        let orphan_caller = node("unused_helper", NodeKind::Function, 60);
+        for n in [
+            &main,
+            &call_site,
+            &run,
+            &service,
+            &base,
+            &orphan,
+            &orphan_caller,
+        ] {
+            graph.add_node(n.clone());
+        }
+        graph.add_edge(Edge::new(main.id, call_site.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(main.id, service.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(service.id, base.id, EdgeKind::Extends));
+        graph.add_edge(Edge::new(orphan_caller.id, orphan.id, EdgeKind::Calls));
+
+        // The unresolved call site reaches `run` by name
+        assert_eq!(
+            query.find_unreachable(&[main.id]),
+            vec![orphan.id, orphan_caller.id]
+        );
+        assert_eq!(query.find_unreachable(&[main.id, orphan_caller.id]), vec![]);
+        assert_eq!(query.find_unreachable(&[]).len(), 6);
+    }
+
+    #[test]
+    fn test_extract_reachable_subgraph_excludes_dead_code() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let node = |name: &str, kind: NodeKind, start: usize| {
+            create_test_node_with_span(name, kind, "app.py", start, start + 5)
+        };
+        let main = node("main", NodeKind::Function, 0);
+        let load = node("load", NodeKind::Function, 10);
+        let call_site = node("parse", NodeKind::Call, 20);
+        let parse = node("parse", NodeKind::Function, 30);
+        let config = node("Config", NodeKind::Class, 40);
+        let legacy = node("legacy_load", NodeKind::Function, 50);
+        for n in [&main, &load, &call_site, &parse, &config, &legacy] {
+            graph.add_node(n.clone());
+        }
+        graph.add_edge(Edge::new(main.id, load.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(load.id, call_site.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(load.id, config.id, EdgeKind::Calls));
+        graph.add_edge(Edge::new(legacy.id, load.id, EdgeKind::Calls));
+
+        let reached = query.reachable_from(&[main.id]);
+        assert_eq!(reached.len(), 5);
+
+        // `legacy_load` is one hop from `load` but unreachable from `main`
+        let full = query.extract_subgraph(&main.id, 5).unwrap();
+        assert!(full.nodes.iter().any(|n| n.id == legacy.id));
+
+        let live = query
+            .extract_reachable_subgraph(&main.id, 5, &[main.id])
+            .unwrap();
+        let ids: HashSet<NodeId> = live.nodes.iter().map(|n| n.id).collect();
+        assert_eq!(
+            ids,
+            HashSet::from([main.id, load.id, call_site.id, config.id])
+        );
+        assert!(!ids.contains(&legacy.id));
+        assert_eq!(live.edges.len(), 3);
+        assert!(live
+            .edges
+            .iter()
+            .all(|e| e.source != legacy.id && e.target != legacy.id));
+
+        assert!(query
+            .extract_reachable_subgraph(&legacy.id, 5, &[main.id])
+            .is_err());
+    }
+
+    #[test]
+    fn test_compute_centrality_ranks_bridge_function_highest() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let node = |name: &str, start: usize| {
+            create_test_node_with_span(name, NodeKind::Function, "app.py", start, start + 5)
+        };
+        // Two entry points feed `dispatch`, which fans out to two handlers
+        let cli = node("cli", 0);
+        let web = node("web", 10);
+        let dispatch = node("dispatch", 20);
+        let save = node("save", 30);
+        let notify = node("notify", 40);
+        for n in [&cli, &web, &dispatch, &save, &notify] {
+            graph.add_node(n.clone());
+        }
+        for (from, to) in [
+            (&cli, &dispatch),
+            (&web, &dispatch),
+            (&dispatch, &save),
+            (&dispatch, &notify),
+            (&save, &save),
+        ] {
+            graph.add_edge(Edge::new(from.id, to.id, EdgeKind::Calls));
+        }
+
+        let betweenness = query.compute_centrality(CentralityMetric::Betweenness);
+        assert_eq!(betweenness[0].0, dispatch.id);
+        // 4 of the 12 ordered pairs route through `dispatch`
+        assert!((betweenness[0].1 - 4.0 / 12.0).abs() < 1e-9);
+        assert!(betweenness[1..].iter().all(|(_, score)| *score == 0.0));
+
+        let in_degree = query.compute_centrality(CentralityMetric::InDegree);
+        assert_eq!(in_degree[0], (dispatch.id, 0.5));
+
+        let cancel = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let cancelled = query.compute_centrality_with(
+            CentralityMetric::Betweenness,
+            &CentralityOptions {
+                cancel: Some(cancel),
+                ..CentralityOptions::default()
+            },
+        );
+        assert!(!cancelled.is_exact());
+        assert_eq!(cancelled.sources_processed, 0);
+
+        let sampled = query.compute_centrality_with(
+            CentralityMetric::Betweenness,
+            &CentralityOptions {
+                max_sources: 2,
+                ..CentralityOptions::default()
+            },
+        );
+        assert!(sampled.complete);
+        assert_eq!(sampled.sources_processed, 2);
+        assert!(!sampled.is_exact());
+    }
+
+    #[test]
+    fn test_find_longest_call_chains_reports_known_longest_chain() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+
+        let function = |name: &str, start: usize| {
+            let node =
+                create_test_node_with_span(name, NodeKind::Function, "app.py", start, start + 5);
+            graph.add_node(node.clone());
+            node
+        };
+        let handler = function("handler", 0);
+        let service = function("service", 10);
+        let repository = function("repository", 20);
+        let query_builder = function("query_builder", 30);
+        let execute = function("execute", 40);
+        let log = function("log", 50);
+        let main = function("main", 60);
+        let parse_args = function("parse_args", 70);
+
+        // handler -> service -> repository -> query_builder -> execute, with a
+        // shortcut, a cycle back into service and a separate short chain
+        for (caller, callee) in [
+            (&handler, &service),
+            (&service, &repository),
+            (&repository, &query_builder),
+            (&query_builder, &execute),
+            (&execute, &service),
+            (&service, &log),
+            (&handler, &log),
+            (&main, &parse_args),
+        ] {
+            graph.add_edge(Edge::new(caller.id, callee.id, EdgeKind::Calls));
+        }
+
+        let result = query.find_longest_call_chains(&CallChainOptions::default());
+        assert!(result.complete);
+        let longest = &result.chains[0];
+        assert_eq!(longest.length(), 4);
+        assert_eq!(
+            longest.nodes,
+            vec![
+                handler.id,
+                service.id,
+                repository.id,
+                query_builder.id,
+                execute.id
+            ]
+        );
+        assert_eq!(result.chains.len(), 2);
+        assert_eq!(result.chains[1].nodes, vec![main.id, parse_args.id]);
+
+        let bounded = query.find_longest_call_chains(&CallChainOptions {
+            max_depth: 2,
+            ..CallChainOptions::default()
+        });
+        assert_eq!(bounded.chains[0].length(), 2);
+
+        let budgeted = query.find_longest_call_chains(&CallChainOptions {
+            max_expansions: 1,
+            ..CallChainOptions::default()
+        });
+        assert!(!budgeted.complete);
+        assert_eq!(budgeted.chains.len(), 1);
+
+        let cancelled = query.find_longest_call_chains(&CallChainOptions {
+            cancel: Some(Arc::new(std::sync::atomic::AtomicBool::new(true))),
+            ..CallChainOptions::default()
+        });
+        assert!(!cancelled.complete);
+        assert!(cancelled.chains.is_empty());
+    }
+
+    #[test]
+    fn test_impact_set_groups_dependents_by_distance() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+        let add = |name: &str, kind: NodeKind, start: usize| {
+            let node = create_test_node_with_span(name, kind, "app.py", start, start + 5);
+            graph.add_node(node.clone());
+            node
+        };
+
+        // parse() is called by load() directly and by report() through a call
+        // site; main() calls load(), which calls parse() back in a cycle
+        let parse = add("parse", NodeKind::Function, 0);
+        let load = add("load", NodeKind::Function, 10);
+        let report = add("report", NodeKind::Function, 20);
+        let site = add("parse", NodeKind::Call, 25);
+        let main = add("main", NodeKind::Function, 30);
+        let config = add("CONFIG", NodeKind::Variable, 40);
+        let base = add("Base", NodeKind::Class, 50);
+        let sub = add("Sub", NodeKind::Class, 60);
+        let leaf = add("Leaf", NodeKind::Class, 70);
+
+        for (source, target, kind) in [
+            (&load, &parse, EdgeKind::Calls),
+            (&report, &site, EdgeKind::Calls),
+            (&site, &parse, EdgeKind::Calls),
+            (&main, &load, EdgeKind::Calls),
+            (&parse, &load, EdgeKind::Calls),
+            (&parse, &config, EdgeKind::Reads),
+            (&sub, &base, EdgeKind::Extends),
+            (&leaf, &sub, EdgeKind::Implements),
+        ] {
+            graph.add_edge(Edge::new(source.id, target.id, kind));
+        }
+
+        let report_all = query.impact_set(parse.id, None);
+        let first: HashSet<_> = report_all.hops[0].iter().map(|s| s.node).collect();
+        assert_eq!(first, HashSet::from([load.id, report.id]));
+        assert_eq!(report_all.hops[1].len(), 1);
+        assert_eq!(report_all.hops[1][0].node, main.id);
+        assert_eq!(report_all.hops[1][0].from, load.id);
+        assert_eq!(report_all.hops.len(), 2);
+        assert_eq!(report_all.total_affected(), 3);
+        assert!(!report_all.truncated);
+        assert!(!report_all.affected().any(|(_, s)| s.node == parse.id));
+
+        let limited = query.impact_set(parse.id, Some(1));
+        assert_eq!(limited.hops.len(), 1);
+        assert!(limited.truncated);
+
+        // Reads reach the readers of a variable; inheritance reaches subclasses
+        let readers = query.impact_set(config.id, None);
+        assert_eq!(readers.affected().next().unwrap().1.node, parse.id);
+        assert_eq!(readers.total_affected(), 4);
+        let subclasses = query.impact_set(base.id, None);
+        let reached: Vec<_> = subclasses
+            .affected()
+            .map(|(distance, s)| (distance, s.node, s.edge_kind))
+            .collect();
+        assert_eq!(
+            reached,
+            vec![
+                (1, sub.id, EdgeKind::Extends),
+                (2, leaf.id, EdgeKind::Implements)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace_data_flow_through_assignments_and_calls() {
+        let graph = Arc::new(GraphStore::new());
+        let query = GraphQuery::new(graph.clone());
+        let add = |name: &str, kind: NodeKind, start: usize| {
+            let node = create_test_node_with_span(name, kind, "app.py", start, start + 5);
+            graph.add_node(node.clone());
+            node
+        };
+
+        // handler() { user_id = request.id; save(record, user_id) }
+        // save(record, key) { row = key }
+        let handler = add("handler", NodeKind::Function, 0);
+        let request_id = add("request.id", NodeKind::Variable, 10);
+        let user_id = add("user_id", NodeKind::Variable, 20);
+        let mut call = create_test_node_with_span("save", NodeKind::Call, "app.py", 30, 35);
+        call.metadata = serde_json::json!({ "arguments": ["record", "user_id"] });
+        graph.add_node(call.clone());
+        let save = create_test_node_with_span("save", NodeKind::Function, "app.py", 100, 130);
+        graph.add_node(save.clone());
+        let record = add("record", NodeKind::Parameter, 105);
+        let key = add("key", NodeKind::Parameter, 110);
+        let row = add("row", NodeKind::Variable, 120);
+
+        for (source, target, kind) in [
+            (&handler, &request_id, EdgeKind::Reads),
+            (&handler, &user_id, EdgeKind::Writes),
+            (&handler, &call, EdgeKind::Calls),
+            (&call, &user_id, EdgeKind::Reads),
+            (&key, &row, EdgeKind::Writes),
+            // A cycle back to the start must not be followed again
+            (&row, &user_id, EdgeKind::Writes),
+        ] {
+            graph.add_edge(Edge::new(source.id, target.id, kind));
+        }
+
+        let forward = query.trace_data_flow(user_id.id, FlowDirection::Forward);
+        let reached: Vec<_> = forward
+            .iter()
+            .map(|step| (step.node, step.edge_kind, step.depth))
+            .collect();
+        assert_eq!(
+            reached,
+            vec![
+                (call.id, EdgeKind::Reads, 1),
+                (key.id, EdgeKind::Calls, 1),
+                (row.id, EdgeKind::Writes, 2),
+            ]
+        );
+        assert_eq!(forward[1].from, user_id.id);
+        // Only the parameter bound to `user_id` by position is reached
+        assert!(!forward.iter().any(|step| step.node == record.id));
+        assert_eq!(forward[2].span, row.span);
+
+        let backward = query.trace_data_flow(row.id, FlowDirection::Backward);
+        let reached: Vec<_> = backward
+            .iter()
+            .map(|step| (step.node, step.edge_kind))
+            .collect();
+        assert_eq!(
+            reached,
+            vec![
+                (key.id, EdgeKind::Writes),
+                (call.id, EdgeKind::Calls),
+                (user_id.id, EdgeKind::Reads),
+                (handler.id, EdgeKind::Writes),
+                (request_id.id, EdgeKind::Reads),
+            ]
+        );
+
+        let bounded = query.trace_data_flow_with(
+            row.id,
+            FlowDirection::Backward,
+            &DataFlowOptions { max_depth: 1 },
+        );
+        assert_eq!(bounded.len(), 1);
+        assert_eq!(bounded[0].node, key.id);
+    }
+
+    #[test]
+    fn test_qualified_name_distinguishes_same_named_methods() {
+        let graph = Arc::new(GraphStore::new());
+        let module = create_test_node_with_span("models", NodeKind::Module, "models.py", 0, 200);
+        let user = create_test_node_with_span("User", NodeKind::Class, "models.py", 0, 90);
+        let user_save = create_test_node_with_span("save", NodeKind::Method, "models.py", 20, 80);
+        let order = create_test_node_with_span("Order", NodeKind::Class, "models.py", 100, 200);
+        let order_save =
+            create_test_node_with_span("save", NodeKind::Method, "models.py", 120, 180);
+        for node in [&module, &user, &user_save, &order, &order_save] {
+            graph.add_node(node.clone());
+        }
+
+        let query = GraphQuery::new(graph);
+        assert_eq!(query.qualified_name(&user_save), "models.User.save");
+        assert_eq!(query.qualified_name(&order_save), "models.Order.save");
+        assert_eq!(query.qualified_name(&order), "models.Order");
+        assert_eq!(query.qualified_name(&module), "models");
+    }
 }
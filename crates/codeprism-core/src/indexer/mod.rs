@@ -3,19 +3,21 @@
 //! This module provides functionality to process large numbers of discovered files
 //! in parallel, parse them, and build the code graph efficiently.
 
-use crate::ast::Edge;
+use crate::ast::{Edge, Node};
 use crate::error::{Error, Result};
 use crate::graph::GraphStore;
-use crate::linkers::SymbolResolver;
-use crate::parser::{ParseContext, ParserEngine};
+use crate::linkers::{ConfigLinks, ConfigReferenceLinker, FfiLinker, Linker, SymbolResolver};
+use crate::parser::{ParseContext, ParseResult, ParserEngine, TreeCache};
 use crate::patch::{AstPatch, PatchBuilder};
 use crate::scanner::{DiscoveredFile, ProgressReporter, ScanResult};
+use codeprism_utils::{ChangeEvent, ChangeKind};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Indexing statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +60,32 @@ pub struct IndexingResult {
     pub stats: IndexingStats,
     /// Files that failed to process
     pub failed_files: Vec<(PathBuf, Error)>,
+    /// Set when indexing stopped early at the memory limit
+    pub partial: Option<PartialIndexing>,
+}
+
+/// Why an indexing run stopped before every file was processed
+///
+/// The patches of a partial run are complete for every file they cover, so
+/// they can be applied as usual; the skipped files are simply missing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialIndexing {
+    /// Estimated memory held by indexing results when the run stopped
+    pub estimated_memory_bytes: usize,
+    /// Configured memory limit
+    pub memory_limit_bytes: usize,
+    /// Files that were never processed
+    pub files_skipped: usize,
+}
+
+impl PartialIndexing {
+    /// Warning describing the partial run, suitable for showing to users
+    pub fn message(&self) -> String {
+        format!(
+            "Indexing stopped near the memory limit ({} of {} bytes in use); {} files were not indexed",
+            self.estimated_memory_bytes, self.memory_limit_bytes, self.files_skipped
+        )
+    }
 }
 
 impl IndexingResult {
@@ -76,9 +104,15 @@ impl IndexingResult {
                 memory_stats: MemoryStats::default(),
             },
             failed_files: Vec::new(),
+            partial: None,
         }
     }
 
+    /// Whether indexing stopped before every file was processed
+    pub fn is_partial(&self) -> bool {
+        self.partial.is_some()
+    }
+
     /// Get total number of patches
     pub fn patch_count(&self) -> usize {
         self.patches.len()
@@ -97,9 +131,23 @@ impl IndexingResult {
         self.stats.edges_created += other.stats.edges_created;
         self.stats.error_count += other.stats.error_count;
         self.failed_files.extend(other.failed_files);
+        self.partial = self.partial.take().or(other.partial);
     }
 }
 
+/// Progress of an indexing run, reported after each batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchProgress {
+    /// Files processed so far, including ones that failed
+    pub files_processed: usize,
+    /// Files in the whole run
+    pub total_files: usize,
+    /// Batches completed so far
+    pub batches_completed: usize,
+    /// Time since the run started
+    pub elapsed: Duration,
+}
+
 /// Configuration for bulk indexing
 #[derive(Debug, Clone)]
 pub struct IndexingConfig {
@@ -114,9 +162,16 @@ pub struct IndexingConfig {
     /// Whether to continue on errors
     pub continue_on_error: bool,
     /// Memory limit in bytes (None = no limit)
+    ///
+    /// Checked between batches: once another batch would likely cross it,
+    /// indexing stops and the result is marked partial.
     pub memory_limit: Option<usize>,
     /// Whether to enable cross-file linking
     pub enable_cross_file_linking: bool,
+    /// Whether cross-file linking also follows FFI boundaries between languages
+    pub enable_ffi_linking: bool,
+    /// Whether code referenced from configuration files is linked into the graph
+    pub enable_config_linking: bool,
 }
 
 impl IndexingConfig {
@@ -130,6 +185,8 @@ impl IndexingConfig {
             continue_on_error: true,
             memory_limit: Some(4 * 1024 * 1024 * 1024), // 4GB instead of 1GB
             enable_cross_file_linking: true,
+            enable_ffi_linking: true,
+            enable_config_linking: true,
         }
     }
 }
@@ -138,6 +195,7 @@ impl IndexingConfig {
 pub struct BulkIndexer {
     config: IndexingConfig,
     parser_engine: Arc<ParserEngine>,
+    tree_cache: Option<Arc<TreeCache>>,
 }
 
 impl BulkIndexer {
@@ -146,9 +204,16 @@ impl BulkIndexer {
         Self {
             config,
             parser_engine,
+            tree_cache: None,
         }
     }
 
+    /// Keep parsed trees in `tree_cache` so later changes can be re-parsed incrementally
+    pub fn with_tree_cache(mut self, tree_cache: Arc<TreeCache>) -> Self {
+        self.tree_cache = Some(tree_cache);
+        self
+    }
+
     /// Index all files from a scan result
     pub async fn index_scan_result(
         &self,
@@ -182,7 +247,7 @@ impl BulkIndexer {
         }
 
         // Process files in batches
-        for batch in all_files.chunks(self.config.batch_size) {
+        for (batch_index, batch) in all_files.chunks(self.config.batch_size).enumerate() {
             let batch_result = self
                 .process_batch(
                     batch,
@@ -193,16 +258,24 @@ impl BulkIndexer {
                 )
                 .await?;
 
+            let batch_memory = self.estimate_memory_usage(&batch_result);
             indexing_result.merge(batch_result);
-
-            // Check memory limit
-            if let Some(limit) = self.config.memory_limit {
-                let current_memory = self.estimate_memory_usage(&indexing_result);
-                if current_memory > limit {
-                    return Err(Error::indexing(
-                        "Memory limit exceeded during bulk indexing",
-                    ));
-                }
+            let files_processed = processed_counter.load(Ordering::Relaxed);
+            progress_reporter.report_batch(&BatchProgress {
+                files_processed,
+                total_files: all_files.len(),
+                batches_completed: batch_index + 1,
+                elapsed: start_time.elapsed(),
+            });
+
+            if let Some(partial) = self.check_memory_limit(
+                &mut indexing_result,
+                batch_memory,
+                all_files.len() - files_processed,
+            ) {
+                tracing::warn!("{}", partial.message());
+                indexing_result.partial = Some(partial);
+                break;
             }
         }
 
@@ -231,6 +304,21 @@ impl BulkIndexer {
             }
         }
 
+        if self.config.enable_config_linking && !scan_result.config_files.is_empty() {
+            let links = self.link_config_references(&scan_result.config_files, &indexing_result);
+            if !links.edges.is_empty() {
+                tracing::info!("Linked {} configuration file references", links.edges.len());
+                indexing_result.stats.nodes_created += links.nodes.len();
+                indexing_result.stats.edges_created += links.edges.len();
+                indexing_result.patches.push(
+                    PatchBuilder::new(self.config.repo_id.clone(), self.config.commit_sha.clone())
+                        .add_nodes(links.nodes)
+                        .add_edges(links.edges)
+                        .build(),
+                );
+            }
+        }
+
         // Finalize statistics
         indexing_result.stats.duration_ms = start_time.elapsed().as_millis() as u64;
         indexing_result.stats.throughput = if indexing_result.stats.duration_ms > 0 {
@@ -274,6 +362,7 @@ impl BulkIndexer {
                     all_files.len(),
                 )
                 .await?;
+            let batch_memory = self.estimate_memory_usage(&batch_result);
 
             // Update statistics but don't accumulate all patches
             final_result.stats.files_processed += batch_result.stats.files_processed;
@@ -300,22 +389,27 @@ impl BulkIndexer {
 
             final_result.patches.extend(batch_result.patches);
 
-            // Check memory limit more frequently in streaming mode
-            if let Some(limit) = self.config.memory_limit {
-                let current_memory = self.estimate_memory_usage(&final_result);
-                if current_memory > limit {
-                    tracing::warn!(
-                        "Memory limit reached in streaming mode, clearing intermediate results"
-                    );
-                    // Clear old patches but keep statistics
-                    final_result.patches.clear();
-                }
-            }
-
             batch_count += 1;
+            let files_processed = processed_counter.load(Ordering::Relaxed);
+            progress_reporter.report_batch(&BatchProgress {
+                files_processed,
+                total_files: all_files.len(),
+                batches_completed: batch_count,
+                elapsed: start_time.elapsed(),
+            });
             if batch_count % 10 == 0 {
                 tracing::debug!("Processed {} batches in streaming mode", batch_count);
             }
+
+            if let Some(partial) = self.check_memory_limit(
+                &mut final_result,
+                batch_memory,
+                all_files.len() - files_processed,
+            ) {
+                tracing::warn!("{}", partial.message());
+                final_result.partial = Some(partial);
+                break;
+            }
         }
 
         // Finalize statistics
@@ -409,15 +503,8 @@ impl BulkIndexer {
             return Ok(None);
         }
 
-        // Create parse context
-        let context = ParseContext::new(
-            self.config.repo_id.clone(),
-            discovered_file.path.clone(),
-            content,
-        );
-
         // Parse the file
-        let parse_result = self.parser_engine.parse_file(context)?;
+        let parse_result = self.parse_with_cache(&discovered_file.path, content)?;
 
         // Create patch from parse result
         let mut patch_builder =
@@ -439,6 +526,102 @@ impl BulkIndexer {
         }
     }
 
+    /// Re-index a single file after a watcher change, returning only what changed
+    ///
+    /// Modified files reuse their cached tree so tree-sitter re-parses just the
+    /// edited region. The resulting nodes and edges are diffed against what
+    /// `graph` currently holds for the file.
+    pub fn reindex_file(
+        &self,
+        change: &ChangeEvent,
+        graph: &GraphStore,
+    ) -> Result<Option<AstPatch>> {
+        let patch = match &change.kind {
+            ChangeKind::Created | ChangeKind::Modified => self.diff_file(&change.path, graph)?,
+            ChangeKind::Deleted => self.diff_removed_file(&change.path, graph),
+            ChangeKind::Renamed { old, new } => {
+                let mut patch = self.diff_removed_file(old, graph);
+                patch.merge(self.diff_file(new, graph)?);
+                patch
+            }
+        };
+
+        if patch.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(patch))
+        }
+    }
+
+    /// Parse a file, starting from its cached tree when there is one
+    fn parse_with_cache(&self, path: &Path, content: String) -> Result<ParseResult> {
+        let mut context =
+            ParseContext::new(self.config.repo_id.clone(), path.to_path_buf(), content);
+
+        let Some(tree_cache) = &self.tree_cache else {
+            return self.parser_engine.parse_file(context);
+        };
+
+        if let Some(old_tree) = tree_cache.edited_tree(path, &context.content) {
+            context = context.with_old_tree(old_tree);
+        }
+        let content = context.content.clone();
+        let result = self.parser_engine.parse_file(context)?;
+        tree_cache.insert(path.to_path_buf(), result.tree.clone(), content);
+
+        Ok(result)
+    }
+
+    /// Diff the current contents of a file against the graph
+    fn diff_file(&self, path: &Path, graph: &GraphStore) -> Result<AstPatch> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Error::io(format!("Failed to read file {}: {}", path.display(), e)))?;
+
+        let (new_nodes, new_edges) = if content.trim().is_empty() {
+            (Vec::new(), Vec::new())
+        } else {
+            let parse_result = self.parse_with_cache(path, content)?;
+            (parse_result.nodes, parse_result.edges)
+        };
+
+        let (old_nodes, old_edges) = Self::file_graph(path, graph);
+        Ok(AstPatch::diff(
+            self.config.repo_id.clone(),
+            self.config.commit_sha.clone(),
+            &old_nodes,
+            &old_edges,
+            new_nodes,
+            new_edges,
+        ))
+    }
+
+    /// Diff a removed file against the graph, dropping its cached tree
+    fn diff_removed_file(&self, path: &Path, graph: &GraphStore) -> AstPatch {
+        if let Some(tree_cache) = &self.tree_cache {
+            tree_cache.remove(path);
+        }
+
+        let (old_nodes, old_edges) = Self::file_graph(path, graph);
+        AstPatch::diff(
+            self.config.repo_id.clone(),
+            self.config.commit_sha.clone(),
+            &old_nodes,
+            &old_edges,
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    /// Nodes of a file in the graph and the edges leaving them
+    fn file_graph(path: &Path, graph: &GraphStore) -> (Vec<Node>, Vec<Edge>) {
+        let nodes = graph.get_nodes_in_file(&path.to_path_buf());
+        let edges = nodes
+            .iter()
+            .flat_map(|node| graph.get_outgoing_edges(&node.id))
+            .collect();
+        (nodes, edges)
+    }
+
     /// Estimate memory usage of the indexing result
     fn estimate_memory_usage(&self, result: &IndexingResult) -> usize {
         let mut total = 0;
@@ -459,6 +642,31 @@ impl BulkIndexer {
         total
     }
 
+    /// Record memory use after a batch and stop before the next one would cross the limit
+    ///
+    /// The last batch's footprint is taken as the estimate for the next one.
+    fn check_memory_limit(
+        &self,
+        result: &mut IndexingResult,
+        batch_memory: usize,
+        files_remaining: usize,
+    ) -> Option<PartialIndexing> {
+        let current = self.estimate_memory_usage(result);
+        let memory_stats = &mut result.stats.memory_stats;
+        memory_stats.current_memory_bytes = current;
+        memory_stats.peak_memory_bytes = memory_stats.peak_memory_bytes.max(current);
+
+        let limit = self.config.memory_limit?;
+        if files_remaining == 0 || current.saturating_add(batch_memory) <= limit {
+            return None;
+        }
+        Some(PartialIndexing {
+            estimated_memory_bytes: current,
+            memory_limit_bytes: limit,
+            files_skipped: files_remaining,
+        })
+    }
+
     /// Perform cross-file symbol resolution
     fn resolve_cross_file_symbols(&self, indexing_result: &IndexingResult) -> Result<Vec<Edge>> {
         // Build a temporary graph store with all the nodes and edges from patches
@@ -476,7 +684,59 @@ impl BulkIndexer {
 
         // Create symbol resolver and resolve cross-file relationships
         let mut resolver = SymbolResolver::new(temp_graph);
-        resolver.resolve_all()
+        let mut edges = resolver.resolve_all()?;
+
+        if self.config.enable_ffi_linking {
+            let nodes: Vec<_> = indexing_result
+                .patches
+                .iter()
+                .flat_map(|patch| patch.nodes_add.iter().cloned())
+                .collect();
+            edges.extend(FfiLinker::new().find_edges(&nodes)?);
+        }
+
+        Ok(edges)
+    }
+
+    /// Resolve code references in configuration files and settings modules
+    fn link_config_references(
+        &self,
+        config_files: &[PathBuf],
+        indexing_result: &IndexingResult,
+    ) -> ConfigLinks {
+        // Lock files and generated manifests can be huge and never name code
+        const MAX_CONFIG_FILE_SIZE: u64 = 1024 * 1024;
+
+        let linker = ConfigReferenceLinker::new();
+        let nodes: Vec<Node> = indexing_result
+            .patches
+            .iter()
+            .flat_map(|patch| patch.nodes_add.iter().cloned())
+            .collect();
+
+        let config_files: Vec<PathBuf> = config_files
+            .iter()
+            .filter(|file| {
+                std::fs::metadata(file).is_ok_and(|meta| meta.len() <= MAX_CONFIG_FILE_SIZE)
+            })
+            .cloned()
+            .collect();
+        let settings_modules: HashSet<&PathBuf> = nodes
+            .iter()
+            .map(|node| &node.file)
+            .filter(|file| linker.is_settings_module(file))
+            .collect();
+        let sources: HashMap<PathBuf, String> = config_files
+            .iter()
+            .chain(settings_modules)
+            .filter_map(|file| {
+                std::fs::read_to_string(file)
+                    .ok()
+                    .map(|content| (file.clone(), content))
+            })
+            .collect();
+
+        linker.link(&self.config.repo_id, &nodes, &config_files, &sources)
     }
 
     /// Get indexing configuration
@@ -485,11 +745,16 @@ impl BulkIndexer {
     }
 }
 
+/// Callback invoked after each indexing batch
+///
+/// Batches run on worker threads, so callbacks must be safe to call from any thread.
+pub type BatchCallback = Arc<dyn Fn(&BatchProgress) + Send + Sync>;
+
 /// Indexing progress reporter that tracks detailed statistics
-#[derive(Debug)]
 pub struct IndexingProgressReporter {
     verbose: bool,
     last_report: std::sync::Mutex<Instant>,
+    on_batch: Option<BatchCallback>,
 }
 
 impl IndexingProgressReporter {
@@ -498,8 +763,27 @@ impl IndexingProgressReporter {
         Self {
             verbose,
             last_report: std::sync::Mutex::new(Instant::now()),
+            on_batch: None,
         }
     }
+
+    /// Call `callback` with the running totals after every batch
+    pub fn with_batch_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&BatchProgress) + Send + Sync + 'static,
+    {
+        self.on_batch = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl std::fmt::Debug for IndexingProgressReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexingProgressReporter")
+            .field("verbose", &self.verbose)
+            .field("on_batch", &self.on_batch.is_some())
+            .finish()
+    }
 }
 
 impl ProgressReporter for IndexingProgressReporter {
@@ -532,6 +816,12 @@ impl ProgressReporter for IndexingProgressReporter {
             eprintln!("Indexing error: {error}");
         }
     }
+
+    fn report_batch(&self, progress: &BatchProgress) {
+        if let Some(callback) = &self.on_batch {
+            callback(progress);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -559,6 +849,7 @@ mod tests {
             path,
             language,
             size: 100,
+            modified: None,
         }
     }
 
@@ -679,4 +970,182 @@ mod tests {
         let error = Error::indexing("test error");
         reporter.report_error(&error);
     }
+
+    #[tokio::test]
+    async fn test_batch_callback_fires_once_per_batch() {
+        let (indexer, temp_dir) = create_test_indexer();
+        let mut config = indexer.config().clone();
+        config.batch_size = 4;
+        config.enable_cross_file_linking = false;
+        let indexer = BulkIndexer::new(config, Arc::clone(&indexer.parser_engine));
+
+        let mut scan_result = ScanResult::new();
+        let files: Vec<_> = (0..10)
+            .map(|i| {
+                // Empty files are skipped without needing a parser
+                let path = temp_dir.path().join(format!("empty_{i}.js"));
+                std::fs::write(&path, "").unwrap();
+                create_test_discovered_file(path, Language::JavaScript)
+            })
+            .collect();
+        scan_result.total_files = files.len();
+        scan_result
+            .files_by_language
+            .insert(Language::JavaScript, files);
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&seen);
+        let reporter = IndexingProgressReporter::new(false).with_batch_callback(move |progress| {
+            recorded.lock().unwrap().push(progress.clone());
+        });
+
+        let result = indexer
+            .index_scan_result(&scan_result, Arc::new(reporter))
+            .await
+            .unwrap();
+        assert_eq!(result.stats.files_processed, 10);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 3, "10 files in batches of 4");
+        let processed: Vec<_> = seen.iter().map(|p| p.files_processed).collect();
+        assert_eq!(processed, vec![4, 8, 10]);
+        assert!(seen.iter().all(|p| p.total_files == 10));
+        assert_eq!(seen[2].batches_completed, 3);
+        assert!(seen[0].elapsed <= seen[2].elapsed);
+    }
+
+    /// Emits a module node and one function node per file
+    struct StubParser;
+
+    impl crate::parser::LanguageParser for StubParser {
+        fn language(&self) -> Language {
+            Language::JavaScript
+        }
+
+        fn parse(&self, context: &ParseContext) -> Result<ParseResult> {
+            use crate::ast::{EdgeKind, NodeKind, Span};
+
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_javascript::LANGUAGE.into())
+                .unwrap();
+            let span = Span::new(0, context.content.len(), 1, 1, 1, 1);
+            let module = Node::new(
+                &context.repo_id,
+                NodeKind::Module,
+                context.file_path.display().to_string(),
+                Language::JavaScript,
+                context.file_path.clone(),
+                span.clone(),
+            );
+            let function = Node::new(
+                &context.repo_id,
+                NodeKind::Function,
+                "run".to_string(),
+                Language::JavaScript,
+                context.file_path.clone(),
+                span,
+            );
+            Ok(ParseResult {
+                tree: parser.parse(&context.content, None).unwrap(),
+                edges: vec![Edge::new(module.id, function.id, EdgeKind::Calls)],
+                nodes: vec![module, function],
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_limit_stops_with_partial_but_valid_result() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = Arc::new(LanguageRegistry::new());
+        registry.register(Arc::new(StubParser));
+        let mut config = IndexingConfig::new("test_repo".to_string(), "abc123".to_string());
+        config.batch_size = 3;
+        // Room for the first batch of three files, nowhere near all four
+        config.memory_limit = Some(2_000);
+        let indexer = BulkIndexer::new(config, Arc::new(ParserEngine::new(registry)));
+
+        let mut scan_result = ScanResult::new();
+        let files: Vec<_> = (0..12)
+            .map(|i| {
+                let path = temp_dir.path().join(format!("module_{i}.js"));
+                std::fs::write(&path, "run();").unwrap();
+                create_test_discovered_file(path, Language::JavaScript)
+            })
+            .collect();
+        scan_result.total_files = files.len();
+        scan_result
+            .files_by_language
+            .insert(Language::JavaScript, files);
+
+        let result = indexer
+            .index_scan_result(&scan_result, Arc::new(IndexingProgressReporter::new(false)))
+            .await
+            .unwrap();
+
+        let partial = result.partial.clone().expect("run should stop early");
+        assert!(result.is_partial());
+        assert_eq!(partial.memory_limit_bytes, 2_000);
+        assert!(result.stats.files_processed > 0);
+        assert_eq!(result.stats.files_processed + partial.files_skipped, 12);
+        assert!(partial.message().contains("files were not indexed"));
+        assert!(result.stats.memory_stats.peak_memory_bytes <= 2_000);
+
+        // Every file that was indexed is complete and the patches apply cleanly
+        let graph = GraphStore::new();
+        for patch in &result.patches {
+            graph.apply_patch(patch);
+        }
+        let stats = graph.get_stats();
+        assert_eq!(stats.total_files, result.stats.files_processed);
+        assert_eq!(stats.total_nodes, 2 * result.stats.files_processed);
+        assert_eq!(stats.total_edges, result.stats.files_processed);
+    }
+
+    #[test]
+    fn test_reindex_deleted_file_removes_its_nodes() {
+        use crate::ast::{EdgeKind, NodeKind, Span};
+
+        let (indexer, temp_dir) = create_test_indexer();
+        let file = temp_dir.path().join("gone.js");
+        let graph = GraphStore::new();
+
+        let module = Node::new(
+            "test_repo",
+            NodeKind::Module,
+            "gone".to_string(),
+            Language::JavaScript,
+            file.clone(),
+            Span::new(0, 20, 1, 2, 1, 1),
+        );
+        let function = Node::new(
+            "test_repo",
+            NodeKind::Function,
+            "run".to_string(),
+            Language::JavaScript,
+            file.clone(),
+            Span::new(0, 10, 1, 1, 1, 11),
+        );
+        let edge = Edge::new(module.id, function.id, EdgeKind::Calls);
+        graph.add_node(module);
+        graph.add_node(function);
+        graph.add_edge(edge);
+
+        let change = ChangeEvent::new(
+            temp_dir.path().to_path_buf(),
+            file.clone(),
+            ChangeKind::Deleted,
+        );
+        let patch = indexer.reindex_file(&change, &graph).unwrap().unwrap();
+        assert_eq!(patch.nodes_delete.len(), 2);
+        assert_eq!(patch.edges_delete.len(), 1);
+        assert!(patch.nodes_add.is_empty());
+
+        graph.apply_patch(&patch);
+        assert!(graph.get_nodes_in_file(&file).is_empty());
+        assert_eq!(graph.get_stats().total_edges, 0);
+
+        // Nothing left to remove on a second delete
+        assert!(indexer.reindex_file(&change, &graph).unwrap().is_none());
+    }
 }
@@ -25,24 +25,32 @@ pub use ast::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
 pub use codeprism_utils::{ChangeEvent, ChangeKind, FileWatcher};
 pub use content::search::{ContentSearchManager, SearchQueryBuilder};
 pub use content::{
-    CommentContext, ConfigFormat, ContentChunk, ContentNode, ContentStats, ContentType,
-    DocumentFormat, SearchQuery, SearchResult,
+    CommentContext, ConfigFormat, ContentChunk, ContentIndexingConfig, ContentNode, ContentStats,
+    ContentType, DocumentFormat, SearchQuery, SearchResult,
 };
 pub use error::{Error, ErrorContext, ErrorSeverity, RecoveryStrategy, Result};
 pub use graph::{
-    DynamicAttribute, GraphQuery, GraphStore, InheritanceFilter, InheritanceInfo,
-    InheritanceRelation, PathResult, SymbolInfo,
+    is_test_function, is_test_path, CallChain, CallChainOptions, CallChains, CentralityMetric,
+    CentralityOptions, CentralityScores, DataFlowOptions, DynamicAttribute, FlowDirection,
+    FlowStep, FuzzyMatch, GraphQuery, GraphStore, ImpactReport, ImpactedSymbol, InheritanceFilter,
+    InheritanceInfo, InheritanceRelation, PathResult, SourceSnippet, Subgraph, SymbolExplanation,
+    SymbolInfo, SymbolPage, TestCoverageMap, EXPLAIN_CONTEXT_LINES,
 };
 pub use indexer::{
-    BulkIndexer, IndexingConfig, IndexingProgressReporter, IndexingResult, IndexingStats,
-    MemoryStats,
+    BatchCallback, BatchProgress, BulkIndexer, IndexingConfig, IndexingProgressReporter,
+    IndexingResult, IndexingStats, MemoryStats, PartialIndexing,
+};
+pub use linkers::{
+    ConfigReferenceLinker, FfiLinker, Linker, RestLinker, SqlLinker, SymbolResolver,
 };
-pub use linkers::{Linker, RestLinker, SqlLinker, SymbolResolver};
 pub use observability::{
     ComponentHealth, HealthCheckResult, HealthMonitor, HealthStatus as ObservabilityHealthStatus,
     MetricsCollector, MetricsSnapshot, OperationMetrics, OperationPerformance, PerformanceMonitor,
 };
-pub use parser::{LanguageParser, LanguageRegistry, ParseContext, ParseResult, ParserEngine};
+pub use parser::{
+    EntryPointDetector, LanguageParser, LanguageRegistry, ParseContext, ParseResult, ParserEngine,
+    ReparsePolicy, ReparseStats, TreeCache,
+};
 pub use patch::{AstPatch, PatchBuilder};
 pub use pipeline::{
     LoggingEventHandler, MonitoringPipeline, NoOpEventHandler, PipelineConfig, PipelineEvent,
@@ -51,8 +59,8 @@ pub use pipeline::{
 pub use repository::{HealthStatus, RepositoryConfig, RepositoryInfo, RepositoryManager};
 pub use resilience::{CircuitBreaker, CircuitState, ResilienceManager, RetryConfig};
 pub use scanner::{
-    DependencyMode, DiscoveredFile, NoOpProgressReporter, ProgressReporter, RepositoryScanner,
-    ScanResult,
+    DependencyMode, DiscoveredFile, LanguagePlan, NoOpProgressReporter, ProgressReporter,
+    RepositoryPlan, RepositoryScanner, ScanResult, SizeBucket,
 };
 
 /// Re-export commonly used types
@@ -66,11 +74,11 @@ pub mod prelude {
     pub use crate::error::{Error, ErrorContext, ErrorSeverity, RecoveryStrategy, Result};
     pub use crate::graph::{
         DynamicAttribute, GraphQuery, GraphStore, InheritanceFilter, InheritanceInfo,
-        InheritanceRelation, PathResult, SymbolInfo,
+        InheritanceRelation, PathResult, SourceSnippet, Subgraph, SymbolExplanation, SymbolInfo,
     };
     pub use crate::indexer::{
-        BulkIndexer, IndexingConfig, IndexingProgressReporter, IndexingResult, IndexingStats,
-        MemoryStats,
+        BatchCallback, BatchProgress, BulkIndexer, IndexingConfig, IndexingProgressReporter,
+        IndexingResult, IndexingStats, MemoryStats, PartialIndexing,
     };
     pub use crate::linkers::{Linker, RestLinker, SqlLinker, SymbolResolver};
     pub use crate::observability::{
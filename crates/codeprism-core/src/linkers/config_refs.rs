@@ -0,0 +1,379 @@
+//! Linker for code referenced from configuration files
+//!
+//! Frameworks wire code together through configuration: YAML files listing
+//! handler classes, `module:attribute` application factories in TOML or INI
+//! files, Django settings naming middleware by dotted path. The linker finds
+//! those references, resolves them against indexed symbols and records each
+//! one as an `Import` node in the referencing file with an `Imports` edge to
+//! the symbol, so reference queries include configuration usages.
+
+use crate::ast::{Edge, EdgeKind, Language, Node, NodeKind, Span};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Configuration for the config reference linker
+#[derive(Debug, Clone)]
+pub struct ConfigLinkerConfig {
+    /// Python module names treated as settings, whose string literals are scanned
+    pub settings_modules: HashSet<String>,
+}
+
+impl Default for ConfigLinkerConfig {
+    fn default() -> Self {
+        Self {
+            settings_modules: ["settings", "config", "conf"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// Nodes and edges created for resolved configuration references
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLinks {
+    /// Configuration reference nodes
+    pub nodes: Vec<Node>,
+    /// Edges from configuration references to the code they name
+    pub edges: Vec<Edge>,
+}
+
+/// Linker resolving dotted paths and import strings in configuration files
+pub struct ConfigReferenceLinker {
+    config: ConfigLinkerConfig,
+    reference: Regex,
+    string_literal: Regex,
+}
+
+impl ConfigReferenceLinker {
+    /// Create a linker with the default settings module names
+    pub fn new() -> Self {
+        Self::with_config(ConfigLinkerConfig::default())
+    }
+
+    /// Create a linker with a custom configuration
+    pub fn with_config(config: ConfigLinkerConfig) -> Self {
+        Self {
+            config,
+            reference: Regex::new(
+                r"(?P<module>[A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)(?::(?P<attr>[A-Za-z_]\w*))?",
+            )
+            .unwrap(),
+            string_literal: Regex::new(r#""([^"\n]*)"|'([^'\n]*)'"#).unwrap(),
+        }
+    }
+
+    /// Whether `file` is a Python settings module scanned for references
+    pub fn is_settings_module(&self, file: &Path) -> bool {
+        if file.extension().and_then(|ext| ext.to_str()) != Some("py") {
+            return false;
+        }
+        let in_settings_package = file
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| self.config.settings_modules.contains(name));
+        let stem = file.file_stem().and_then(|stem| stem.to_str());
+        in_settings_package || stem.is_some_and(|stem| self.config.settings_modules.contains(stem))
+    }
+
+    /// Link references in `config_files` and in settings modules among `nodes`
+    ///
+    /// `sources` must hold the contents of every file to scan; files missing
+    /// from it are skipped.
+    pub fn link(
+        &self,
+        repo_id: &str,
+        nodes: &[Node],
+        config_files: &[PathBuf],
+        sources: &HashMap<PathBuf, String>,
+    ) -> ConfigLinks {
+        let mut symbols: HashMap<&str, Vec<&Node>> = HashMap::new();
+        for node in nodes {
+            if matches!(
+                node.kind,
+                NodeKind::Class | NodeKind::Function | NodeKind::Interface | NodeKind::Enum
+            ) {
+                symbols.entry(node.name.as_str()).or_default().push(node);
+            }
+        }
+        if symbols.is_empty() {
+            return ConfigLinks::default();
+        }
+
+        let settings_modules: HashSet<&PathBuf> = nodes
+            .iter()
+            .map(|node| &node.file)
+            .filter(|file| self.is_settings_module(file))
+            .collect();
+
+        let mut links = ConfigLinks::default();
+        let mut seen = HashSet::new();
+        let files = config_files
+            .iter()
+            .map(|file| (file, Language::Unknown))
+            .chain(settings_modules.into_iter().map(|f| (f, Language::Python)));
+        for (file, lang) in files {
+            let Some(source) = sources.get(file) else {
+                continue;
+            };
+            for (start, end, path) in self.references(source, lang) {
+                let Some(target) = resolve(&symbols, &path) else {
+                    continue;
+                };
+                if !seen.insert((file.clone(), start, target.id)) {
+                    continue;
+                }
+                let mut import = Node::new(
+                    repo_id,
+                    NodeKind::Import,
+                    path.joined(),
+                    lang,
+                    file.clone(),
+                    span_of(source, start, end),
+                );
+                import.metadata = serde_json::json!({ "config_reference": true });
+                links
+                    .edges
+                    .push(Edge::new(import.id, target.id, EdgeKind::Imports));
+                links.nodes.push(import);
+            }
+        }
+        links
+    }
+
+    /// Candidate references with their byte ranges
+    ///
+    /// Python settings modules are only searched inside string literals, since
+    /// dotted names in code are attribute accesses rather than references.
+    fn references(&self, source: &str, lang: Language) -> Vec<(usize, usize, SymbolPath)> {
+        let regions: Vec<(usize, &str)> = if lang == Language::Python {
+            self.string_literal
+                .captures_iter(source)
+                .filter_map(|captures| captures.get(1).or_else(|| captures.get(2)))
+                .map(|content| (content.start(), content.as_str()))
+                .collect()
+        } else {
+            vec![(0, source)]
+        };
+
+        let mut references = Vec::new();
+        for (offset, text) in regions {
+            for captures in self.reference.captures_iter(text) {
+                let whole = captures.get(0).expect("match");
+                // Skip matches starting mid-token, like the `abc.d` in `9abc.d`
+                if text[..whole.start()]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+                {
+                    continue;
+                }
+                let module: Vec<&str> = captures["module"].split('.').collect();
+                let path = match captures.name("attr") {
+                    Some(attr) => SymbolPath {
+                        module: module.iter().map(|s| s.to_string()).collect(),
+                        name: attr.as_str().to_string(),
+                        separator: ':',
+                    },
+                    None if module.len() > 1 => SymbolPath {
+                        module: module[..module.len() - 1]
+                            .iter()
+                            .map(|s| s.to_string())
+                            .collect(),
+                        name: module[module.len() - 1].to_string(),
+                        separator: '.',
+                    },
+                    None => continue,
+                };
+                references.push((offset + whole.start(), offset + whole.end(), path));
+            }
+        }
+        references
+    }
+}
+
+impl Default for ConfigReferenceLinker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A symbol named by module path, as in `app.handlers.OrderHandler`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SymbolPath {
+    module: Vec<String>,
+    name: String,
+    /// `.` for dotted paths, `:` for `module:attribute` import strings
+    separator: char,
+}
+
+impl SymbolPath {
+    fn joined(&self) -> String {
+        format!("{}{}{}", self.module.join("."), self.separator, self.name)
+    }
+}
+
+/// The symbol whose defining file matches the referenced module
+fn resolve<'a>(symbols: &HashMap<&str, Vec<&'a Node>>, path: &SymbolPath) -> Option<&'a Node> {
+    symbols
+        .get(path.name.as_str())?
+        .iter()
+        .copied()
+        .find(|node| module_matches(&node.file, &path.module, &path.name))
+}
+
+/// Whether `file` defines the module `module`
+///
+/// Accepts `a/b.py` and `a/b/__init__.py` for `a.b`, and the one-class-per-file
+/// layout `a/b/Name.java` for `a.b.Name`.
+fn module_matches(file: &Path, module: &[String], name: &str) -> bool {
+    let stem = file.with_extension("");
+    let mut path: Vec<&str> = stem
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect();
+    if path.last() == Some(&"__init__") {
+        path.pop();
+    }
+    let module: Vec<&str> = module.iter().map(String::as_str).collect();
+    let class_file: Vec<&str> = module.iter().copied().chain([name]).collect();
+    path.ends_with(&module) || path.ends_with(&class_file)
+}
+
+/// Span covering `source[start..end]`
+fn span_of(source: &str, start: usize, end: usize) -> Span {
+    let position = |offset: usize| {
+        let before = &source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = offset - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        (line, column)
+    };
+    let (start_line, start_column) = position(start);
+    let (end_line, end_column) = position(end);
+    Span::new(start, end, start_line, end_line, start_column, end_column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(kind: NodeKind, name: &str, lang: Language, file: &str) -> Node {
+        Node::new(
+            "repo",
+            kind,
+            name.to_string(),
+            lang,
+            PathBuf::from(file),
+            Span::new(0, 1, 1, 1, 1, 2),
+        )
+    }
+
+    #[test]
+    fn test_yaml_handler_class_path_links_to_class() {
+        let handler = node(
+            NodeKind::Class,
+            "OrderHandler",
+            Language::Python,
+            "/repo/app/handlers.py",
+        );
+        let factory = node(
+            NodeKind::Function,
+            "create_app",
+            Language::Python,
+            "/repo/app/__init__.py",
+        );
+        // Same name in an unrelated module must not be picked up
+        let decoy = node(
+            NodeKind::Class,
+            "OrderHandler",
+            Language::Python,
+            "/repo/legacy/views.py",
+        );
+        let nodes = vec![handler.clone(), factory.clone(), decoy.clone()];
+
+        let config = PathBuf::from("/repo/config/routes.yaml");
+        let yaml = "\
+routes:
+  - path: /orders
+    handler: app.handlers.OrderHandler
+    timeout: 2.5
+  - path: /health
+    handler: \"external.lib.HealthCheck\"
+server:
+  factory: app:create_app
+  host: api.example.com
+";
+        let sources = HashMap::from([(config.clone(), yaml.to_string())]);
+
+        let links = ConfigReferenceLinker::new().link(
+            "repo",
+            &nodes,
+            std::slice::from_ref(&config),
+            &sources,
+        );
+        assert_eq!(links.edges.len(), 2);
+
+        let import = links
+            .nodes
+            .iter()
+            .find(|n| n.name == "app.handlers.OrderHandler")
+            .expect("handler reference node");
+        assert_eq!(import.kind, NodeKind::Import);
+        assert_eq!(import.file, config);
+        assert_eq!(import.span.start_line, 3);
+        assert_eq!(import.span.start_column, 14);
+        assert!(links.edges.iter().any(|e| e.source == import.id
+            && e.target == handler.id
+            && e.kind == EdgeKind::Imports));
+        assert!(links.edges.iter().all(|e| e.target != decoy.id));
+
+        let factory_ref = links
+            .nodes
+            .iter()
+            .find(|n| n.name == "app:create_app")
+            .expect("factory reference node");
+        assert!(links
+            .edges
+            .iter()
+            .any(|e| e.source == factory_ref.id && e.target == factory.id));
+    }
+
+    #[test]
+    fn test_settings_module_strings_are_scanned() {
+        let linker = ConfigReferenceLinker::new();
+        let middleware = node(
+            NodeKind::Class,
+            "AuditMiddleware",
+            Language::Python,
+            "/repo/shop/middleware.py",
+        );
+        let settings_module = node(
+            NodeKind::Module,
+            "settings",
+            Language::Python,
+            "/repo/shop/settings.py",
+        );
+        assert!(linker.is_settings_module(&settings_module.file));
+        assert!(!linker.is_settings_module(&middleware.file));
+
+        let settings = "\
+from shop.middleware import AuditMiddleware as shop_middleware_AuditMiddleware
+MIDDLEWARE = [
+    'django.middleware.security.SecurityMiddleware',
+    'shop.middleware.AuditMiddleware',
+]
+";
+        let sources = HashMap::from([(settings_module.file.clone(), settings.to_string())]);
+        let links = linker.link(
+            "repo",
+            &[middleware.clone(), settings_module],
+            &[],
+            &sources,
+        );
+        assert_eq!(links.nodes.len(), 1);
+        assert_eq!(links.nodes[0].span.start_line, 4);
+        assert_eq!(links.edges[0].target, middleware.id);
+    }
+}
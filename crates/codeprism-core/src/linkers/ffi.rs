@@ -0,0 +1,806 @@
+//! Linker for calls and imports that cross a foreign function interface
+//!
+//! Native code is exposed to other languages through binding frameworks:
+//! PyO3 or the CPython C API for Python extension modules, N-API for Node
+//! addons, `wasm-bindgen` for WebAssembly and plain `extern "C"` symbols loaded
+//! through `ctypes` or `cffi`. The linker finds both sides of each boundary in
+//! the source files and joins them with `Imports` and `Calls` edges.
+
+use super::Linker;
+use crate::ast::{Edge, EdgeKind, Language, Node, NodeId, NodeKind};
+use crate::error::Result;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Kinds of FFI boundary the linker can follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FfiBinding {
+    /// Python extension modules written with PyO3 or the CPython C API
+    PythonExtension,
+    /// Shared libraries loaded from Python through `ctypes` or `cffi`
+    CAbi,
+    /// Node.js native addons written with N-API, node-addon-api or napi-rs
+    NodeAddon,
+    /// WebAssembly modules generated by `wasm-bindgen`
+    WasmBindgen,
+}
+
+impl FfiBinding {
+    /// All supported bindings
+    pub const ALL: [FfiBinding; 4] = [
+        FfiBinding::PythonExtension,
+        FfiBinding::CAbi,
+        FfiBinding::NodeAddon,
+        FfiBinding::WasmBindgen,
+    ];
+}
+
+/// Configuration for the FFI linker
+#[derive(Debug, Clone)]
+pub struct FfiLinkerConfig {
+    /// Bindings to detect; boundaries of other kinds are ignored
+    pub bindings: HashSet<FfiBinding>,
+}
+
+impl Default for FfiLinkerConfig {
+    fn default() -> Self {
+        Self {
+            bindings: FfiBinding::ALL.into_iter().collect(),
+        }
+    }
+}
+
+/// A native module exposed to another language
+#[derive(Debug)]
+struct NativeModule {
+    binding: FfiBinding,
+    /// Normalized name the module is imported or loaded by
+    name: String,
+    /// Node standing for the module as a whole
+    node: Option<NodeId>,
+    /// Exported names mapped to the native functions implementing them
+    exports: HashMap<String, NodeId>,
+}
+
+/// Compiled patterns for both sides of the supported boundaries
+struct FfiPatterns {
+    pymodule: Regex,
+    rust_export: Regex,
+    extern_c: Regex,
+    attribute_name: Regex,
+    py_init: Regex,
+    py_method_def: Regex,
+    node_module: Regex,
+    napi_function: Regex,
+    python_library_load: Regex,
+    js_module_ref: Regex,
+}
+
+impl FfiPatterns {
+    fn new() -> Self {
+        Self {
+            pymodule: Regex::new(
+                r#"#\[pymodule(?:\([^)]*\))?\]((?:\s*#\[[^\]]*\])*)\s*(?:pub(?:\([^)]*\))?\s+)?fn\s+(\w+)"#,
+            )
+            .unwrap(),
+            rust_export: Regex::new(
+                r#"#\[(pyfunction|wasm_bindgen|napi)(?:\(([^)]*)\))?\]((?:\s*#\[[^\]]*\])*)\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+"C"\s+)?fn\s+(\w+)"#,
+            )
+            .unwrap(),
+            extern_c: Regex::new(
+                r#"#\[(?:unsafe\()?no_mangle\)?\]\s*(?:#\[[^\]]*\]\s*)*pub\s+(?:unsafe\s+)?extern\s+"C"\s+fn\s+(\w+)"#,
+            )
+            .unwrap(),
+            attribute_name: Regex::new(r#"(?:js_)?name\s*=\s*"?(\w+)"?"#).unwrap(),
+            py_init: Regex::new(r"PyInit_(\w+)").unwrap(),
+            py_method_def: Regex::new(
+                r#"\{\s*"(\w+)"\s*,\s*(?:\(\s*PyCFunction\s*\)\s*)?(\w+)\s*,"#,
+            )
+            .unwrap(),
+            node_module: Regex::new(r"\b(?:NAPI_MODULE|NODE_API_MODULE|NODE_MODULE)\s*\(\s*(\w+)")
+                .unwrap(),
+            napi_function: Regex::new(
+                r#"(?:napi_create_function\s*\([^,]*,\s*"(\w+)"\s*,[^,]*,\s*(\w+)|"(\w+)"\s*\)?\s*,\s*Napi::Function::New\s*\(\s*\w+\s*,\s*(\w+))"#,
+            )
+            .unwrap(),
+            python_library_load: Regex::new(
+                r#"(?:CDLL|WinDLL|PyDLL|LoadLibrary|dlopen)\s*\(\s*[rbf]?["']([^"']+)["']"#,
+            )
+            .unwrap(),
+            js_module_ref: Regex::new(
+                r#"(?:require|import)\s*\(\s*["']([^"']+)["']|from\s+["']([^"']+)["']|bindings\s*\(\s*["']([^"']+)["']"#,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Linker creating cross-language edges at FFI boundaries
+pub struct FfiLinker {
+    config: FfiLinkerConfig,
+    patterns: FfiPatterns,
+}
+
+impl FfiLinker {
+    /// Create a linker detecting every supported binding
+    pub fn new() -> Self {
+        Self::with_config(FfiLinkerConfig::default())
+    }
+
+    /// Create a linker with a custom configuration
+    pub fn with_config(config: FfiLinkerConfig) -> Self {
+        Self {
+            config,
+            patterns: FfiPatterns::new(),
+        }
+    }
+
+    fn enabled(&self, binding: FfiBinding) -> bool {
+        self.config.bindings.contains(&binding)
+    }
+
+    /// Link nodes using the given file contents
+    ///
+    /// Files missing from `sources` are skipped.
+    pub fn find_edges_with_sources(
+        &self,
+        nodes: &[Node],
+        sources: &HashMap<PathBuf, String>,
+    ) -> Vec<Edge> {
+        let mut files: HashMap<&Path, Vec<&Node>> = HashMap::new();
+        for node in nodes {
+            files.entry(node.file.as_path()).or_default().push(node);
+        }
+
+        let mut modules = Vec::new();
+        for (file, file_nodes) in &files {
+            let Some(source) = sources.get(*file) else {
+                continue;
+            };
+            match file_nodes[0].lang {
+                Language::Rust => modules.extend(self.rust_modules(file, file_nodes, source)),
+                Language::C | Language::Cpp => {
+                    modules.extend(self.c_modules(file, file_nodes, source))
+                }
+                _ => {}
+            }
+        }
+        if modules.is_empty() {
+            return Vec::new();
+        }
+
+        let mut edges = Vec::new();
+        let mut seen = HashSet::new();
+        for (file, file_nodes) in &files {
+            let Some(source) = sources.get(*file) else {
+                continue;
+            };
+            let linked = match file_nodes[0].lang {
+                Language::Python => self.link_python(file_nodes, source, &modules),
+                Language::JavaScript | Language::TypeScript => {
+                    self.link_javascript(file_nodes, source, &modules)
+                }
+                _ => continue,
+            };
+            for edge in linked {
+                if seen.insert((edge.source, edge.target, edge.kind)) {
+                    edges.push(edge);
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// Native modules defined by a Rust source file
+    fn rust_modules(&self, file: &Path, nodes: &[&Node], source: &str) -> Vec<NativeModule> {
+        let crate_name = crate_name(file).unwrap_or_else(|| file_stem(file));
+        let module_node = module_node(nodes);
+
+        let mut python_names = Vec::new();
+        for captures in self.patterns.pymodule.captures_iter(source) {
+            let name = captures
+                .get(1)
+                .and_then(|attrs| self.patterns.attribute_name.captures(attrs.as_str()))
+                .map(|name| name[1].to_string())
+                .unwrap_or_else(|| captures[2].to_string());
+            python_names.push(name);
+        }
+        if python_names.is_empty() {
+            python_names.push(crate_name.clone());
+        }
+
+        let mut exports: HashMap<FfiBinding, HashMap<String, NodeId>> = HashMap::new();
+        for captures in self.patterns.rust_export.captures_iter(source) {
+            let function = &captures[4];
+            let Some(definition) = function_node(nodes, function) else {
+                continue;
+            };
+
+            let binding = match &captures[1] {
+                "pyfunction" => FfiBinding::PythonExtension,
+                "wasm_bindgen" => FfiBinding::WasmBindgen,
+                _ => FfiBinding::NodeAddon,
+            };
+            let renamed = [captures.get(2), captures.get(3)]
+                .into_iter()
+                .flatten()
+                .find_map(|attrs| self.patterns.attribute_name.captures(attrs.as_str()))
+                .map(|name| name[1].to_string());
+
+            let names = exports.entry(binding).or_default();
+            match renamed {
+                Some(name) => {
+                    names.insert(name, definition);
+                }
+                None => {
+                    names.insert(function.to_string(), definition);
+                    // napi-rs exposes snake_case functions in camelCase
+                    if binding == FfiBinding::NodeAddon {
+                        names.insert(camel_case(function), definition);
+                    }
+                }
+            }
+        }
+
+        for captures in self.patterns.extern_c.captures_iter(source) {
+            if let Some(definition) = function_node(nodes, &captures[1]) {
+                exports
+                    .entry(FfiBinding::CAbi)
+                    .or_default()
+                    .insert(captures[1].to_string(), definition);
+            }
+        }
+
+        let mut modules = Vec::new();
+        for (binding, names) in exports {
+            if !self.enabled(binding) {
+                continue;
+            }
+            let module_names = if binding == FfiBinding::PythonExtension {
+                python_names.clone()
+            } else {
+                vec![crate_name.clone()]
+            };
+            for name in module_names {
+                modules.push(NativeModule {
+                    binding,
+                    name: normalize_name(&name),
+                    node: module_node,
+                    exports: names.clone(),
+                });
+            }
+        }
+
+        // A #[pymodule] without #[pyfunction]s in the same file is still importable
+        if self.enabled(FfiBinding::PythonExtension)
+            && !modules
+                .iter()
+                .any(|module| module.binding == FfiBinding::PythonExtension)
+        {
+            for captures in self.patterns.pymodule.captures_iter(source) {
+                let init = function_node(nodes, &captures[2]);
+                modules.push(NativeModule {
+                    binding: FfiBinding::PythonExtension,
+                    name: normalize_name(&captures[2]),
+                    node: module_node.or(init),
+                    exports: HashMap::new(),
+                });
+            }
+        }
+
+        modules
+    }
+
+    /// Native modules defined by a C or C++ source file
+    fn c_modules(&self, file: &Path, nodes: &[&Node], source: &str) -> Vec<NativeModule> {
+        let module_node = module_node(nodes);
+        let mut modules = Vec::new();
+
+        if self.enabled(FfiBinding::PythonExtension) {
+            if let Some(captures) = self.patterns.py_init.captures(source) {
+                let exports = self
+                    .patterns
+                    .py_method_def
+                    .captures_iter(source)
+                    .filter_map(|method| {
+                        function_node(nodes, &method[2]).map(|id| (method[1].to_string(), id))
+                    })
+                    .collect();
+                modules.push(NativeModule {
+                    binding: FfiBinding::PythonExtension,
+                    name: normalize_name(&captures[1]),
+                    node: module_node
+                        .or_else(|| function_node(nodes, &format!("PyInit_{}", &captures[1]))),
+                    exports,
+                });
+            }
+        }
+
+        if self.enabled(FfiBinding::NodeAddon) {
+            if let Some(captures) = self.patterns.node_module.captures(source) {
+                let exports = self
+                    .patterns
+                    .napi_function
+                    .captures_iter(source)
+                    .filter_map(|export| {
+                        let name = export.get(1).or_else(|| export.get(3))?;
+                        let function = export.get(2).or_else(|| export.get(4))?;
+                        function_node(nodes, function.as_str())
+                            .map(|id| (name.as_str().to_string(), id))
+                    })
+                    .collect();
+                modules.push(NativeModule {
+                    binding: FfiBinding::NodeAddon,
+                    name: normalize_name(&captures[1]),
+                    node: module_node,
+                    exports,
+                });
+            }
+        }
+
+        if self.enabled(FfiBinding::CAbi) {
+            // Every function in a C file is callable once its library is loaded
+            let exports = nodes
+                .iter()
+                .filter(|node| node.kind == NodeKind::Function)
+                .map(|node| (node.name.clone(), node.id))
+                .collect();
+            modules.push(NativeModule {
+                binding: FfiBinding::CAbi,
+                name: library_name(&file_stem(file)),
+                node: module_node,
+                exports,
+            });
+        }
+
+        modules
+    }
+
+    /// Edges from a Python file into the native modules it imports or loads
+    fn link_python(&self, nodes: &[&Node], source: &str, modules: &[NativeModule]) -> Vec<Edge> {
+        let mut edges = Vec::new();
+        let mut used: Vec<&NativeModule> = Vec::new();
+
+        for import in nodes.iter().filter(|node| node.kind == NodeKind::Import) {
+            let candidates = [
+                normalize_name(&import.name),
+                normalize_name(last_segment(&import.name)),
+            ];
+            for module in modules.iter().filter(|module| {
+                module.binding == FfiBinding::PythonExtension && candidates.contains(&module.name)
+            }) {
+                if let Some(target) = module.node {
+                    edges.push(Edge::new(import.id, target, EdgeKind::Imports));
+                }
+                used.push(module);
+            }
+        }
+
+        let file_module = module_node(nodes);
+        for captures in self.patterns.python_library_load.captures_iter(source) {
+            let library = library_name(last_path_component(&captures[1]));
+            for module in modules
+                .iter()
+                .filter(|module| module.binding == FfiBinding::CAbi && module.name == library)
+            {
+                if let (Some(source), Some(target)) = (file_module, module.node) {
+                    edges.push(Edge::new(source, target, EdgeKind::Imports));
+                }
+                used.push(module);
+            }
+        }
+
+        edges.extend(call_edges(nodes, &used));
+        edges
+    }
+
+    /// Edges from a JavaScript or TypeScript file into native addons and WASM modules
+    fn link_javascript(
+        &self,
+        nodes: &[&Node],
+        source: &str,
+        modules: &[NativeModule],
+    ) -> Vec<Edge> {
+        let mut edges = Vec::new();
+        let mut used: Vec<&NativeModule> = Vec::new();
+        let file_module = module_node(nodes);
+
+        let mut specifiers: Vec<(String, Option<NodeId>)> = nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Import)
+            .map(|node| (node.name.clone(), Some(node.id)))
+            .collect();
+        for captures in self.patterns.js_module_ref.captures_iter(source) {
+            if let Some(specifier) = (1..=3).find_map(|group| captures.get(group)) {
+                specifiers.push((specifier.as_str().to_string(), None));
+            }
+        }
+
+        for (specifier, import_node) in specifiers {
+            let name = js_module_name(&specifier);
+            for module in modules.iter().filter(|module| {
+                matches!(
+                    module.binding,
+                    FfiBinding::NodeAddon | FfiBinding::WasmBindgen
+                ) && module.name == name
+            }) {
+                if let (Some(source), Some(target)) = (import_node.or(file_module), module.node) {
+                    edges.push(Edge::new(source, target, EdgeKind::Imports));
+                }
+                used.push(module);
+            }
+        }
+
+        edges.extend(call_edges(nodes, &used));
+        edges
+    }
+}
+
+impl Default for FfiLinker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Linker for FfiLinker {
+    fn name(&self) -> &str {
+        "FFI"
+    }
+
+    fn find_edges(&self, nodes: &[Node]) -> Result<Vec<Edge>> {
+        let files: HashSet<&PathBuf> = nodes
+            .iter()
+            .filter(|node| {
+                matches!(
+                    node.lang,
+                    Language::Rust
+                        | Language::C
+                        | Language::Cpp
+                        | Language::Python
+                        | Language::JavaScript
+                        | Language::TypeScript
+                )
+            })
+            .map(|node| &node.file)
+            .collect();
+
+        let sources = files
+            .into_iter()
+            .filter_map(|file| {
+                std::fs::read_to_string(file)
+                    .ok()
+                    .map(|content| (file.clone(), content))
+            })
+            .collect();
+
+        Ok(self.find_edges_with_sources(nodes, &sources))
+    }
+}
+
+/// `Calls` edges from call sites to the native functions they reach
+fn call_edges(nodes: &[&Node], modules: &[&NativeModule]) -> Vec<Edge> {
+    if modules.is_empty() {
+        return Vec::new();
+    }
+
+    nodes
+        .iter()
+        .filter(|node| node.kind == NodeKind::Call)
+        .filter_map(|call| {
+            let name = last_segment(&call.name);
+            modules
+                .iter()
+                .find_map(|module| module.exports.get(name))
+                .map(|target| Edge::new(call.id, *target, EdgeKind::Calls))
+        })
+        .collect()
+}
+
+fn module_node(nodes: &[&Node]) -> Option<NodeId> {
+    nodes
+        .iter()
+        .find(|node| node.kind == NodeKind::Module)
+        .map(|node| node.id)
+}
+
+fn function_node(nodes: &[&Node], name: &str) -> Option<NodeId> {
+    nodes
+        .iter()
+        .find(|node| {
+            matches!(node.kind, NodeKind::Function | NodeKind::Method) && node.name == name
+        })
+        .map(|node| node.id)
+}
+
+/// Name of the crate owning a Rust file, read from the nearest `Cargo.toml`
+///
+/// The `[lib]` name wins over the package name since it names the artifact.
+fn crate_name(file: &Path) -> Option<String> {
+    let manifest = file
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| manifest.is_file())?;
+    let content = std::fs::read_to_string(manifest).ok()?;
+
+    let mut section = "";
+    let mut package_name = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            section = line;
+            continue;
+        }
+        let Some(value) = line
+            .strip_prefix("name")
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix('='))
+        else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').to_string();
+        match section {
+            "[lib]" => return Some(value),
+            "[package]" => package_name = Some(value),
+            _ => {}
+        }
+    }
+    package_name
+}
+
+fn file_stem(file: &Path) -> String {
+    file.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Module names compare case-insensitively with `-` and `_` treated alike
+fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase().replace('-', "_")
+}
+
+fn last_segment(name: &str) -> &str {
+    name.rsplit(['.', ':']).next().unwrap_or(name)
+}
+
+fn last_path_component(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Library name from a shared object file name, e.g. `libfoo.so.1` -> `foo`
+fn library_name(file_name: &str) -> String {
+    let base = file_name.split('.').next().unwrap_or(file_name);
+    let base = base
+        .strip_prefix("lib")
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(base);
+    normalize_name(base)
+}
+
+/// Module name a JS specifier refers to, e.g. `./pkg/hello_wasm.js` -> `hello_wasm`
+fn js_module_name(specifier: &str) -> String {
+    let file = last_path_component(specifier);
+    let base = file.split('.').next().unwrap_or(file);
+    let base = base.strip_suffix("_bg").unwrap_or(base);
+    normalize_name(base)
+}
+
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = !result.is_empty();
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Span;
+
+    fn node(kind: NodeKind, name: &str, lang: Language, file: &Path, start: usize) -> Node {
+        Node::new(
+            "repo",
+            kind,
+            name.to_string(),
+            lang,
+            file.to_path_buf(),
+            Span::new(start, start + 1, 1, 1, 1, 2),
+        )
+    }
+
+    fn has_edge(edges: &[Edge], source: &Node, target: &Node, kind: EdgeKind) -> bool {
+        edges
+            .iter()
+            .any(|e| e.source == source.id && e.target == target.id && e.kind == kind)
+    }
+
+    #[test]
+    fn test_python_import_of_pyo3_extension_is_linked() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let ext_dir = temp_dir.path().join("fastmath");
+        std::fs::create_dir_all(ext_dir.join("src")).unwrap();
+        std::fs::write(
+            ext_dir.join("Cargo.toml"),
+            "[package]\nname = \"fastmath-rs\"\n\n[lib]\nname = \"fastmath\"\n",
+        )
+        .unwrap();
+
+        let rust_file = ext_dir.join("src/lib.rs");
+        let python_file = temp_dir.path().join("app.py");
+        let rust_source = r#"
+use pyo3::prelude::*;
+
+#[pyfunction]
+fn dot(a: Vec<f64>, b: Vec<f64>) -> f64 { a.iter().zip(b).map(|(x, y)| x * y).sum() }
+
+#[pymodule]
+fn fastmath(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(dot, m)?)
+}
+"#;
+        let python_source = "import fastmath\n\nprint(fastmath.dot([1.0], [2.0]))\n";
+
+        let rust_module = node(NodeKind::Module, "lib", Language::Rust, &rust_file, 0);
+        let dot = node(NodeKind::Function, "dot", Language::Rust, &rust_file, 10);
+        let init = node(
+            NodeKind::Function,
+            "fastmath",
+            Language::Rust,
+            &rust_file,
+            20,
+        );
+        let py_module = node(NodeKind::Module, "app", Language::Python, &python_file, 0);
+        let import = node(
+            NodeKind::Import,
+            "fastmath",
+            Language::Python,
+            &python_file,
+            1,
+        );
+        let call = node(
+            NodeKind::Call,
+            "fastmath.dot",
+            Language::Python,
+            &python_file,
+            30,
+        );
+        let unrelated = node(NodeKind::Call, "print", Language::Python, &python_file, 25);
+
+        let nodes = vec![
+            rust_module.clone(),
+            dot.clone(),
+            init,
+            py_module,
+            import.clone(),
+            call.clone(),
+            unrelated,
+        ];
+        let sources = HashMap::from([
+            (rust_file, rust_source.to_string()),
+            (python_file, python_source.to_string()),
+        ]);
+
+        let edges = FfiLinker::new().find_edges_with_sources(&nodes, &sources);
+        assert!(has_edge(&edges, &import, &rust_module, EdgeKind::Imports));
+        assert!(has_edge(&edges, &call, &dot, EdgeKind::Calls));
+        assert_eq!(edges.len(), 2);
+
+        // Disabled bindings are not linked
+        let config = FfiLinkerConfig {
+            bindings: HashSet::from([FfiBinding::WasmBindgen]),
+        };
+        assert!(FfiLinker::with_config(config)
+            .find_edges_with_sources(&nodes, &sources)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_ctypes_library_load_links_to_c_functions() {
+        let c_file = PathBuf::from("native/vecops.c");
+        let python_file = PathBuf::from("vec.py");
+        let python_source = r#"
+import ctypes
+lib = ctypes.CDLL("./build/libvecops.so")
+lib.vec_norm(ptr, 3)
+"#;
+        let c_module = node(NodeKind::Module, "vecops", Language::C, &c_file, 0);
+        let norm = node(NodeKind::Function, "vec_norm", Language::C, &c_file, 5);
+        let py_module = node(NodeKind::Module, "vec", Language::Python, &python_file, 0);
+        let call = node(
+            NodeKind::Call,
+            "lib.vec_norm",
+            Language::Python,
+            &python_file,
+            40,
+        );
+
+        let nodes = vec![
+            c_module.clone(),
+            norm.clone(),
+            py_module.clone(),
+            call.clone(),
+        ];
+        let sources = HashMap::from([
+            (
+                c_file,
+                "double vec_norm(double *v, int n) { return 0; }".to_string(),
+            ),
+            (python_file, python_source.to_string()),
+        ]);
+
+        let edges = FfiLinker::new().find_edges_with_sources(&nodes, &sources);
+        assert!(has_edge(&edges, &py_module, &c_module, EdgeKind::Imports));
+        assert!(has_edge(&edges, &call, &norm, EdgeKind::Calls));
+    }
+
+    #[test]
+    fn test_wasm_bindgen_exports_are_linked_from_javascript() {
+        // No Cargo.toml above the file, so the module is named after the file
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("hello_wasm.rs");
+        let js_file = temp_dir.path().join("web/main.js");
+        let rust_source = r#"
+#[wasm_bindgen(js_name = greetUser)]
+pub fn greet(name: &str) -> String { format!("hi {name}") }
+"#;
+        let js_source = "import init, { greetUser } from './pkg/hello_wasm.js';\ngreetUser('x');\n";
+
+        let rust_module = node(
+            NodeKind::Module,
+            "hello_wasm",
+            Language::Rust,
+            &rust_file,
+            0,
+        );
+        let greet = node(NodeKind::Function, "greet", Language::Rust, &rust_file, 5);
+        let js_module = node(NodeKind::Module, "main", Language::JavaScript, &js_file, 0);
+        let call = node(
+            NodeKind::Call,
+            "greetUser",
+            Language::JavaScript,
+            &js_file,
+            60,
+        );
+
+        let nodes = vec![
+            rust_module.clone(),
+            greet.clone(),
+            js_module.clone(),
+            call.clone(),
+        ];
+        let sources = HashMap::from([
+            (rust_file, rust_source.to_string()),
+            (js_file, js_source.to_string()),
+        ]);
+
+        let edges = FfiLinker::new().find_edges_with_sources(&nodes, &sources);
+        assert!(has_edge(
+            &edges,
+            &js_module,
+            &rust_module,
+            EdgeKind::Imports
+        ));
+        assert!(has_edge(&edges, &call, &greet, EdgeKind::Calls));
+    }
+
+    #[test]
+    fn test_name_helpers() {
+        assert_eq!(library_name("libvecops.so.1"), "vecops");
+        assert_eq!(library_name("vecops.dll"), "vecops");
+        assert_eq!(js_module_name("./pkg/hello_wasm_bg.wasm"), "hello_wasm");
+        assert_eq!(js_module_name("./build/Release/addon.node"), "addon");
+        assert_eq!(camel_case("add_numbers"), "addNumbers");
+    }
+}
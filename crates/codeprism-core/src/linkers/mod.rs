@@ -3,8 +3,12 @@
 use crate::ast::{Edge, Node};
 use crate::error::Result;
 
+pub mod config_refs;
+pub mod ffi;
 pub mod symbol_resolver;
 
+pub use config_refs::{ConfigLinkerConfig, ConfigLinks, ConfigReferenceLinker};
+pub use ffi::{FfiBinding, FfiLinker, FfiLinkerConfig};
 pub use symbol_resolver::SymbolResolver;
 
 /// Trait for cross-language linkers
@@ -6,8 +6,9 @@
 use crate::ast::{Edge, EdgeKind, Node, NodeId, NodeKind};
 use crate::error::Result;
 use crate::graph::GraphStore;
+use std::cmp::Reverse;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Symbol resolver for cross-file linking
@@ -52,9 +53,115 @@ impl SymbolResolver {
         // Resolve inheritance relationships
         new_edges.extend(self.resolve_inheritance()?);
 
+        // Resolve extension functions to the types they extend
+        new_edges.extend(self.resolve_extension_receivers());
+
+        // Resolve includes of files to the files' modules
+        new_edges.extend(self.resolve_file_includes());
+
         Ok(new_edges)
     }
 
+    /// Link imports to the modules and classes they name in other repositories
+    ///
+    /// Uses the repositories registered with the graph. Imports already linked
+    /// to their target are skipped, so this can run again after a repository
+    /// is added.
+    pub fn resolve_cross_repo_imports(&self) -> Vec<Edge> {
+        self.graph
+            .get_nodes_by_kind(NodeKind::Import)
+            .iter()
+            .filter_map(|import_node| {
+                let target = self.resolve_cross_repo_import(import_node)?;
+                let linked = self
+                    .graph
+                    .get_outgoing_edges(&import_node.id)
+                    .iter()
+                    .any(|edge| edge.kind == EdgeKind::Imports && edge.target == target);
+                (!linked).then(|| Edge::new(import_node.id, target, EdgeKind::Imports))
+            })
+            .collect()
+    }
+
+    /// Resolve an import to the `Module` or `Class` it names in another repository
+    ///
+    /// The import path is compared with each module's path relative to its
+    /// repository root, with or without the repository ID in front, so both
+    /// `utils.Helper` and `shared.utils.Helper` match `utils.py` of repository
+    /// `shared`. Relative imports and imports that match a module of their own
+    /// repository are not cross-repository and resolve to `None`.
+    pub fn resolve_cross_repo_import(&self, import_node: &Node) -> Option<NodeId> {
+        let import_repo = self.graph.repository_of(&import_node.file)?;
+        let import_path = Self::normalize_import_path(&import_node.name)?;
+        let roots: HashMap<String, std::path::PathBuf> =
+            self.graph.repositories().into_iter().collect();
+
+        let mut target = None;
+        for module in self.graph.get_nodes_by_kind(NodeKind::Module) {
+            let Some(repo_id) = self.graph.repository_of(&module.file) else {
+                continue;
+            };
+            let Some(module_path) = Self::module_path_in_repo(&module.file, &roots[&repo_id])
+            else {
+                continue;
+            };
+
+            for candidate in [module_path.clone(), format!("{repo_id}.{module_path}")] {
+                let found = if import_path == candidate {
+                    Some(module.id)
+                } else if let Some(class_name) = import_path
+                    .strip_prefix(candidate.as_str())
+                    .and_then(|rest| rest.strip_prefix('.'))
+                {
+                    self.graph
+                        .get_nodes_in_file(&module.file)
+                        .into_iter()
+                        .find(|node| node.kind == NodeKind::Class && node.name == class_name)
+                        .map(|node| node.id)
+                } else {
+                    None
+                };
+
+                match found {
+                    // The import names a module of its own repository
+                    Some(_) if repo_id == import_repo => return None,
+                    Some(id) => target = target.or(Some(id)),
+                    None => {}
+                }
+            }
+        }
+        target
+    }
+
+    /// Dotted form of an import path, `None` for relative imports
+    fn normalize_import_path(import_name: &str) -> Option<String> {
+        let path = import_name.trim().trim_matches(|c| c == '"' || c == '\'');
+        if path.is_empty() || path.starts_with('.') {
+            return None;
+        }
+        Some(
+            path.replace("::", ".")
+                .replace(['/', '\\'], ".")
+                .trim_matches('.')
+                .to_string(),
+        )
+    }
+
+    /// Dotted module path of `file` relative to its repository `root`
+    ///
+    /// Package entry files (`__init__`, `index`, `mod`) stand for their directory.
+    fn module_path_in_repo(file: &Path, root: &Path) -> Option<String> {
+        let relative = file.strip_prefix(root).ok()?.with_extension("");
+        let mut parts: Vec<&str> = relative
+            .components()
+            .filter_map(|component| component.as_os_str().to_str())
+            .collect();
+        if parts.len() > 1 && matches!(parts.last(), Some(&("__init__" | "index" | "mod"))) {
+            parts.pop();
+        }
+        (!parts.is_empty()).then(|| parts.join("."))
+    }
+
     /// Build indices of available symbols for resolution
     fn build_symbol_indices(&mut self) -> Result<()> {
         // Get all nodes and organize by module
@@ -337,6 +444,70 @@ impl SymbolResolver {
         Ok(edges)
     }
 
+    /// Link extension functions to the type named by their `extension_receiver`
+    ///
+    /// Parsers link receivers declared in the same file themselves; this
+    /// covers receivers declared in other files. The edge is a `Reads` edge,
+    /// since an extension depends on its receiver without inheriting from it.
+    fn resolve_extension_receivers(&self) -> Vec<Edge> {
+        [NodeKind::Function, NodeKind::Method]
+            .into_iter()
+            .flat_map(|kind| self.graph.get_nodes_by_kind(kind))
+            .filter_map(|function| {
+                let receiver = function.metadata["extension_receiver"].as_str()?;
+                let linked = self
+                    .graph
+                    .get_outgoing_edges(&function.id)
+                    .iter()
+                    .any(|edge| {
+                        edge.kind == EdgeKind::Reads
+                            && self
+                                .graph
+                                .get_node(&edge.target)
+                                .is_some_and(|target| target.name == receiver)
+                    });
+                if linked {
+                    return None;
+                }
+                let target = self
+                    .resolve_base_class_name(receiver, &function.file)
+                    .or_else(|| self.find_interface_by_name(receiver))?;
+                Some(Edge::new(function.id, target, EdgeKind::Reads))
+            })
+            .collect()
+    }
+
+    /// Link imports naming a file, such as C/C++ `#include "util.h"`, to
+    /// the module node of that file
+    ///
+    /// Parsers record the path in `resolved_path` metadata when they can
+    /// locate the file; imports without it are left alone.
+    fn resolve_file_includes(&self) -> Vec<Edge> {
+        self.graph
+            .get_nodes_by_kind(NodeKind::Import)
+            .into_iter()
+            .filter_map(|import| {
+                let path = PathBuf::from(import.metadata["resolved_path"].as_str()?);
+                let module = self
+                    .graph
+                    .get_nodes_in_file(&path)
+                    .into_iter()
+                    .filter(|node| node.kind == NodeKind::Module)
+                    .min_by_key(|node| (node.span.start_byte, Reverse(node.span.end_byte)))?;
+                Some(Edge::new(import.id, module.id, EdgeKind::Imports))
+            })
+            .collect()
+    }
+
+    /// Find an interface by name
+    fn find_interface_by_name(&self, name: &str) -> Option<NodeId> {
+        self.graph
+            .get_nodes_by_kind(NodeKind::Interface)
+            .into_iter()
+            .find(|node| node.name == name)
+            .map(|node| node.id)
+    }
+
     /// Resolve a base class name to its actual class node
     fn resolve_base_class_name(
         &self,
@@ -415,4 +586,205 @@ mod tests {
             ("rustic_ai.core.guild".to_string(), "Agent".to_string())
         );
     }
+
+    #[test]
+    fn test_extension_receivers_resolve_across_files() {
+        use crate::ast::{Language, Span};
+
+        let graph = Arc::new(GraphStore::new());
+        let node = |kind, name: &str, file: &str, receiver: Option<&str>| {
+            let line = graph.get_stats().total_nodes + 1;
+            let node = Node::new(
+                "repo",
+                kind,
+                name.to_string(),
+                Language::Kotlin,
+                PathBuf::from(file),
+                Span::new(line * 10, line * 10 + 5, line, line, 1, 6),
+            )
+            .with_metadata(serde_json::json!({ "extension_receiver": receiver }));
+            graph.add_node(node.clone());
+            node
+        };
+        let circle = node(NodeKind::Class, "Circle", "Circle.kt", None);
+        let shape = node(NodeKind::Interface, "Shape", "Shape.kt", None);
+        let diameter = node(NodeKind::Function, "diameter", "Ext.kt", Some("Circle"));
+        let describe = node(NodeKind::Function, "describe", "Util.kt", Some("Shape"));
+        let second = node(NodeKind::Function, "second", "Util.kt", Some("List"));
+        node(NodeKind::Function, "main", "Main.kt", None);
+
+        let resolver = SymbolResolver::new(Arc::clone(&graph));
+        let mut edges: Vec<_> = resolver
+            .resolve_extension_receivers()
+            .into_iter()
+            .map(|edge| (edge.source, edge.target, edge.kind))
+            .collect();
+        edges.sort_by_key(|(source, _, _)| *source == describe.id);
+        assert_eq!(
+            edges,
+            vec![
+                (diameter.id, circle.id, EdgeKind::Reads),
+                (describe.id, shape.id, EdgeKind::Reads),
+            ]
+        );
+        assert!(!edges.iter().any(|(source, _, _)| *source == second.id));
+
+        // Receivers already linked by the parser are not linked twice
+        graph.add_edge(Edge::new(diameter.id, circle.id, EdgeKind::Reads));
+        assert_eq!(resolver.resolve_extension_receivers().len(), 1);
+    }
+
+    #[test]
+    fn test_file_includes_resolve_to_included_module() {
+        use crate::ast::{Language, Span};
+
+        let graph = Arc::new(GraphStore::new());
+        let node = |kind, name: &str, file: &str, start: usize, end: usize, metadata| {
+            let node = Node::new(
+                "repo",
+                kind,
+                name.to_string(),
+                Language::C,
+                PathBuf::from(file),
+                Span::new(start, end, 1, 1, 1, 1),
+            )
+            .with_metadata(metadata);
+            graph.add_node(node.clone());
+            node
+        };
+        let header = node(
+            NodeKind::Module,
+            "log",
+            "/repo/include/log.h",
+            0,
+            80,
+            serde_json::json!({}),
+        );
+        // A namespace spanning the whole header is also a module, but not the file's
+        node(
+            NodeKind::Module,
+            "logging",
+            "/repo/include/log.h",
+            0,
+            79,
+            serde_json::json!({}),
+        );
+        let include = node(
+            NodeKind::Import,
+            "../include/log.h",
+            "/repo/src/main.c",
+            0,
+            26,
+            serde_json::json!({ "resolved_path": "/repo/include/log.h" }),
+        );
+        node(
+            NodeKind::Import,
+            "stdio.h",
+            "/repo/src/main.c",
+            27,
+            45,
+            serde_json::json!({ "resolved_path": null }),
+        );
+
+        let resolver = SymbolResolver::new(graph);
+        let edges: Vec<_> = resolver
+            .resolve_file_includes()
+            .into_iter()
+            .map(|edge| (edge.source, edge.target, edge.kind))
+            .collect();
+        assert_eq!(edges, vec![(include.id, header.id, EdgeKind::Imports)]);
+    }
+
+    #[test]
+    fn test_cross_repo_import_resolves_to_other_repository() {
+        use crate::ast::{Language, Span};
+
+        let graph = Arc::new(GraphStore::new());
+        graph.register_repository("app", PathBuf::from("/ws/app"));
+        graph.register_repository("shared", PathBuf::from("/ws/shared"));
+
+        let span = |line| Span::new(line * 10, line * 10 + 5, line, line, 1, 5);
+        let node = |repo: &str, kind, name: &str, file: &str, line| {
+            let node = Node::new(
+                repo,
+                kind,
+                name.to_string(),
+                Language::Python,
+                PathBuf::from(file),
+                span(line),
+            );
+            graph.add_node(node.clone());
+            node
+        };
+        // Both repositories define a `utils` module
+        let app_utils = node("app", NodeKind::Module, "utils", "/ws/app/utils.py", 1);
+        let shared_utils = node(
+            "shared",
+            NodeKind::Module,
+            "utils",
+            "/ws/shared/utils.py",
+            1,
+        );
+        let helper = node(
+            "shared",
+            NodeKind::Class,
+            "Helper",
+            "/ws/shared/utils.py",
+            2,
+        );
+        let local = node("app", NodeKind::Import, "utils", "/ws/app/main.py", 1);
+        let qualified = node(
+            "app",
+            NodeKind::Import,
+            "shared.utils",
+            "/ws/app/main.py",
+            2,
+        );
+        let class_import = node(
+            "app",
+            NodeKind::Import,
+            "shared.utils.Helper",
+            "/ws/app/main.py",
+            3,
+        );
+
+        let resolver = SymbolResolver::new(Arc::clone(&graph));
+        assert_eq!(resolver.resolve_cross_repo_import(&local), None);
+        assert_eq!(
+            resolver.resolve_cross_repo_import(&qualified),
+            Some(shared_utils.id)
+        );
+        assert_eq!(
+            resolver.resolve_cross_repo_import(&class_import),
+            Some(helper.id)
+        );
+
+        let edges = resolver.resolve_cross_repo_imports();
+        assert_eq!(edges.len(), 2);
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+        assert!(resolver.resolve_cross_repo_imports().is_empty());
+
+        // Symbol search scoped to one repository
+        let query = crate::graph::GraphQuery::new(Arc::clone(&graph));
+        let in_app = query
+            .search_symbols_in_repo("utils", None, None, Some("app"))
+            .unwrap();
+        assert_eq!(in_app.len(), 4);
+        assert!(in_app.iter().all(|s| s.node.file.starts_with("/ws/app")));
+        let app_modules = query
+            .search_symbols_in_repo("utils", Some(vec![NodeKind::Module]), None, Some("app"))
+            .unwrap();
+        assert_eq!(app_modules.len(), 1);
+        assert_eq!(app_modules[0].node.id, app_utils.id);
+        let in_shared = query
+            .search_symbols_in_repo("^utils$", None, None, Some("shared"))
+            .unwrap();
+        assert_eq!(in_shared.len(), 1);
+        assert_eq!(in_shared[0].node.id, shared_utils.id);
+        assert!(query
+            .search_symbols_in_repo("utils", None, None, Some("missing"))
+            .is_err());
+    }
 }
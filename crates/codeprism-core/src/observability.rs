@@ -25,6 +25,10 @@ struct Metrics {
     operation_latencies: HashMap<String, Vec<Duration>>,
     /// Success/failure rates
     operation_success_rates: HashMap<String, (u64, u64)>, // (success, total)
+    /// Timeouts by operation
+    operation_timeouts: HashMap<String, u64>,
+    /// Time spent waiting for a concurrency slot, by operation
+    operation_queue_waits: HashMap<String, Vec<Duration>>,
     /// Resource usage tracking
     resource_usage: HashMap<String, u64>,
     /// Start time for uptime calculation
@@ -38,6 +42,8 @@ impl Default for Metrics {
             error_severity_counts: HashMap::new(),
             operation_latencies: HashMap::new(),
             operation_success_rates: HashMap::new(),
+            operation_timeouts: HashMap::new(),
+            operation_queue_waits: HashMap::new(),
             resource_usage: HashMap::new(),
             start_time: Instant::now(),
         }
@@ -118,6 +124,48 @@ impl MetricsCollector {
         );
     }
 
+    /// Record an operation that was cancelled after exceeding its time limit
+    ///
+    /// The timeout also counts as a failed execution of the operation.
+    pub fn record_timeout(&self, operation: &str, timeout: Duration) {
+        self.record_error(&Error::timeout(operation, timeout), Some(operation));
+        let mut metrics = self.metrics.lock().unwrap();
+        *metrics
+            .operation_timeouts
+            .entry(operation.to_string())
+            .or_insert(0) += 1;
+    }
+
+    /// Number of timeouts recorded for an operation
+    pub fn get_timeout_count(&self, operation: &str) -> u64 {
+        let metrics = self.metrics.lock().unwrap();
+        metrics
+            .operation_timeouts
+            .get(operation)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Record how long an operation waited for a concurrency slot before it ran
+    pub fn record_queue_wait(&self, operation: &str, wait: Duration) {
+        let mut metrics = self.metrics.lock().unwrap();
+        metrics
+            .operation_queue_waits
+            .entry(operation.to_string())
+            .or_default()
+            .push(wait);
+    }
+
+    /// Average time an operation waited for a concurrency slot
+    pub fn get_average_queue_wait(&self, operation: &str) -> Option<Duration> {
+        let metrics = self.metrics.lock().unwrap();
+        let waits = metrics.operation_queue_waits.get(operation)?;
+        if waits.is_empty() {
+            return None;
+        }
+        Some(waits.iter().sum::<Duration>() / waits.len() as u32)
+    }
+
     /// Record resource usage
     pub fn record_resource_usage(&self, resource: &str, usage: u64) {
         let mut metrics = self.metrics.lock().unwrap();
@@ -189,6 +237,18 @@ impl MetricsCollector {
                     total_count: *total,
                     error_rate,
                     average_latency_ms: avg_latency.map(|d| d.as_millis() as u64),
+                    timeout_count: metrics
+                        .operation_timeouts
+                        .get(operation)
+                        .copied()
+                        .unwrap_or(0),
+                    average_queue_wait_ms: metrics
+                        .operation_queue_waits
+                        .get(operation)
+                        .filter(|waits| !waits.is_empty())
+                        .map(|waits| {
+                            (waits.iter().sum::<Duration>() / waits.len() as u32).as_millis() as u64
+                        }),
                 },
             );
         }
@@ -233,10 +293,15 @@ pub struct OperationMetrics {
     pub error_rate: f64,
     /// Average latency in milliseconds
     pub average_latency_ms: Option<u64>,
+    /// Number of executions cancelled for exceeding their time limit
+    pub timeout_count: u64,
+    /// Average wait for a concurrency slot in milliseconds
+    pub average_queue_wait_ms: Option<u64>,
 }
 
 /// Health check status
 #[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     /// All systems functioning normally
     Healthy,
@@ -553,6 +618,41 @@ impl PerformanceMonitor {
         result
     }
 
+    /// Record an operation cancelled after exceeding its time limit
+    pub fn record_timeout(&self, operation_name: &str, timeout: Duration) {
+        self.metrics_collector
+            .record_timeout(operation_name, timeout);
+        warn!(
+            operation = operation_name,
+            timeout_ms = timeout.as_millis(),
+            "Operation timed out"
+        );
+    }
+
+    /// Number of timeouts recorded for an operation
+    pub fn timeout_count(&self, operation_name: &str) -> u64 {
+        self.metrics_collector.get_timeout_count(operation_name)
+    }
+
+    /// Record how long an operation waited for a concurrency slot
+    pub fn record_queue_wait(&self, operation_name: &str, wait: Duration) {
+        self.metrics_collector
+            .record_queue_wait(operation_name, wait);
+        if !wait.is_zero() {
+            debug!(
+                operation = operation_name,
+                wait_ms = wait.as_millis(),
+                "Operation waited for a concurrency slot"
+            );
+        }
+    }
+
+    /// Average time an operation waited for a concurrency slot
+    pub fn average_queue_wait(&self, operation_name: &str) -> Option<Duration> {
+        self.metrics_collector
+            .get_average_queue_wait(operation_name)
+    }
+
     /// Get performance metrics for an operation
     pub fn get_operation_performance(&self, operation: &str) -> Option<OperationPerformance> {
         let error_rate = self.metrics_collector.get_error_rate(operation);
@@ -638,6 +738,41 @@ mod tests {
         assert!(perf.average_latency >= Duration::from_millis(10));
     }
 
+    #[test]
+    fn test_record_timeout_counts_as_failure() {
+        let monitor = PerformanceMonitor::new(MetricsCollector::new());
+        monitor.record_timeout("analyze_complexity", Duration::from_secs(5));
+        monitor.record_timeout("analyze_complexity", Duration::from_secs(5));
+
+        assert_eq!(monitor.timeout_count("analyze_complexity"), 2);
+        assert_eq!(monitor.timeout_count("search_symbols"), 0);
+        let snapshot = monitor.metrics_collector.get_metrics_snapshot();
+        let metrics = &snapshot.operation_metrics["analyze_complexity"];
+        assert_eq!(metrics.timeout_count, 2);
+        assert_eq!(metrics.error_rate, 1.0);
+    }
+
+    #[test]
+    fn test_record_queue_wait_averages_per_operation() {
+        let monitor = PerformanceMonitor::new(MetricsCollector::new());
+        monitor.record_queue_wait("analyze_complexity", Duration::from_millis(100));
+        monitor.record_queue_wait("analyze_complexity", Duration::from_millis(300));
+        monitor
+            .metrics_collector
+            .record_success("analyze_complexity", Duration::from_millis(50));
+
+        assert_eq!(
+            monitor.average_queue_wait("analyze_complexity"),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(monitor.average_queue_wait("search_symbols"), None);
+        let snapshot = monitor.metrics_collector.get_metrics_snapshot();
+        assert_eq!(
+            snapshot.operation_metrics["analyze_complexity"].average_queue_wait_ms,
+            Some(200)
+        );
+    }
+
     #[test]
     fn test_metrics_snapshot() {
         let collector = MetricsCollector::new();
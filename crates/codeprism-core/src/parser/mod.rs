@@ -1,11 +1,13 @@
 //! Parser engine for incremental parsing
 
-use crate::ast::{Language, Node};
+use crate::ast::{Language, Node, NodeId};
 use crate::error::{Error, Result};
+use crate::graph::GraphStore;
 use dashmap::DashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tree_sitter::Tree;
+use tree_sitter::{InputEdit, Point, Tree};
 
 /// Parser context for incremental parsing
 #[derive(Debug, Clone)]
@@ -47,6 +49,19 @@ pub trait LanguageParser: Send + Sync {
     fn parse(&self, context: &ParseContext) -> Result<ParseResult>;
 }
 
+/// Finds the nodes execution of a program in one language can start from
+///
+/// Reachability analyses treat the returned nodes as roots, so code that is
+/// only reachable from, say, a Python `if __name__ == "__main__":` block or an
+/// exported JavaScript function is not reported as dead.
+pub trait EntryPointDetector: Send + Sync {
+    /// Get the language this detector handles
+    fn language(&self) -> Language;
+
+    /// Candidate roots among the nodes of this language in `graph`
+    fn detect(&self, graph: &GraphStore) -> Vec<NodeId>;
+}
+
 /// Result of parsing a file
 #[derive(Debug)]
 pub struct ParseResult {
@@ -61,6 +76,7 @@ pub struct ParseResult {
 /// Registry for language parsers
 pub struct LanguageRegistry {
     parsers: DashMap<Language, Arc<dyn LanguageParser>>,
+    entry_point_detectors: DashMap<Language, Arc<dyn EntryPointDetector>>,
 }
 
 impl LanguageRegistry {
@@ -68,6 +84,7 @@ impl LanguageRegistry {
     pub fn new() -> Self {
         Self {
             parsers: DashMap::new(),
+            entry_point_detectors: DashMap::new(),
         }
     }
 
@@ -87,6 +104,34 @@ impl LanguageRegistry {
         let lang = Language::from_extension(ext);
         self.get(lang)
     }
+
+    /// Register an entry point detector, replacing any for the same language
+    pub fn register_entry_point_detector(&self, detector: Arc<dyn EntryPointDetector>) {
+        let lang = detector.language();
+        self.entry_point_detectors.insert(lang, detector);
+    }
+
+    /// Get the entry point detector for a language
+    pub fn entry_point_detector(&self, language: Language) -> Option<Arc<dyn EntryPointDetector>> {
+        self.entry_point_detectors
+            .get(&language)
+            .map(|d| Arc::clone(&*d))
+    }
+
+    /// Entry points found by every registered detector, without duplicates
+    pub fn detect_entry_points(&self, graph: &GraphStore) -> Vec<NodeId> {
+        let detectors: Vec<_> = self
+            .entry_point_detectors
+            .iter()
+            .map(|d| Arc::clone(d.value()))
+            .collect();
+        let mut seen = std::collections::HashSet::new();
+        detectors
+            .iter()
+            .flat_map(|detector| detector.detect(graph))
+            .filter(|id| seen.insert(*id))
+            .collect()
+    }
 }
 
 impl Default for LanguageRegistry {
@@ -95,12 +140,50 @@ impl Default for LanguageRegistry {
     }
 }
 
+/// When to parse a file a second time because the first tree looks degraded
+///
+/// Some grammars occasionally produce a tree riddled with `ERROR` nodes for
+/// input that parses cleanly on a second, fresh attempt. A single re-parse is
+/// made when the share of source inside error nodes exceeds `max_error_ratio`
+/// (see [`error_ratio`]), and the tree with fewer errors is kept, so genuine
+/// syntax errors still come through.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ReparsePolicy {
+    /// Whether degraded trees are re-parsed at all
+    pub enabled: bool,
+    /// Share of source bytes inside `ERROR` nodes above which a tree is re-parsed
+    pub max_error_ratio: f64,
+}
+
+impl Default for ReparsePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_error_ratio: 0.3,
+        }
+    }
+}
+
+/// Re-parse counters of a [`ParserEngine`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReparseStats {
+    /// Files parsed a second time because of a degraded tree
+    pub retried: usize,
+    /// Retries whose tree had fewer errors and replaced the first result
+    pub improved: usize,
+}
+
 /// Main parser engine
 pub struct ParserEngine {
     /// Language registry
     registry: Arc<LanguageRegistry>,
     /// Cache of parsed trees
     tree_cache: DashMap<PathBuf, Tree>,
+    /// Handling of degraded trees
+    reparse_policy: ReparsePolicy,
+    reparse_retried: AtomicUsize,
+    reparse_improved: AtomicUsize,
 }
 
 impl ParserEngine {
@@ -109,11 +192,52 @@ impl ParserEngine {
         Self {
             registry,
             tree_cache: DashMap::new(),
+            reparse_policy: ReparsePolicy::default(),
+            reparse_retried: AtomicUsize::new(0),
+            reparse_improved: AtomicUsize::new(0),
+        }
+    }
+
+    /// Registry the engine resolves parsers from
+    pub fn registry(&self) -> &Arc<LanguageRegistry> {
+        &self.registry
+    }
+
+    /// Set how degraded trees are retried
+    pub fn with_reparse_policy(mut self, policy: ReparsePolicy) -> Self {
+        self.reparse_policy = policy;
+        self
+    }
+
+    /// How degraded trees are retried
+    pub fn reparse_policy(&self) -> ReparsePolicy {
+        self.reparse_policy
+    }
+
+    /// Number of re-parses made so far and how many of them were kept
+    pub fn reparse_stats(&self) -> ReparseStats {
+        ReparseStats {
+            retried: self.reparse_retried.load(Ordering::Relaxed),
+            improved: self.reparse_improved.load(Ordering::Relaxed),
         }
     }
 
     /// Parse a file
     pub fn parse_file(&self, context: ParseContext) -> Result<ParseResult> {
+        let result = self.parse_detached(&context)?;
+
+        // Cache the tree
+        self.tree_cache
+            .insert(context.file_path.clone(), result.tree.clone());
+
+        Ok(result)
+    }
+
+    /// Parse a file without caching its tree
+    ///
+    /// For content that is not the file's current version, such as an older
+    /// revision, whose tree must not seed later incremental parses.
+    pub fn parse_detached(&self, context: &ParseContext) -> Result<ParseResult> {
         // Detect language from file extension
         let ext = context
             .file_path
@@ -127,12 +251,33 @@ impl ParserEngine {
             .get_by_extension(ext)
             .ok_or_else(|| Error::unsupported_language(ext.to_string()))?;
 
-        // Parse the file
-        let result = parser.parse(&context)?;
-
-        // Cache the tree
-        self.tree_cache
-            .insert(context.file_path.clone(), result.tree.clone());
+        // Parse the file, retrying once from scratch if the tree looks degraded
+        let mut result = parser.parse(context)?;
+        if self.reparse_policy.enabled {
+            let ratio = error_ratio(&result.tree);
+            if ratio > self.reparse_policy.max_error_ratio {
+                self.reparse_retried.fetch_add(1, Ordering::Relaxed);
+                let mut fresh = context.clone();
+                fresh.old_tree = None;
+                match parser.parse(&fresh) {
+                    Ok(retry) if error_ratio(&retry.tree) < ratio => {
+                        self.reparse_improved.fetch_add(1, Ordering::Relaxed);
+                        tracing::debug!(
+                            "Re-parse of {} reduced error ratio from {:.2}",
+                            context.file_path.display(),
+                            ratio
+                        );
+                        result = retry;
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::debug!(
+                        "Re-parse of {} failed, keeping first result: {}",
+                        context.file_path.display(),
+                        e
+                    ),
+                }
+            }
+        }
 
         Ok(result)
     }
@@ -160,11 +305,188 @@ impl ParserEngine {
     }
 }
 
+impl std::fmt::Debug for ParserEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParserEngine")
+            .field("cached_trees", &self.tree_cache.len())
+            .field("reparse_policy", &self.reparse_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Share of source bytes (0.0 to 1.0) covered by `ERROR` nodes
+///
+/// Nested error nodes are only counted once, through their outermost ancestor.
+pub fn error_ratio(tree: &Tree) -> f64 {
+    let root = tree.root_node();
+    let total = root.end_byte() - root.start_byte();
+    if !root.has_error() || total == 0 {
+        return 0.0;
+    }
+
+    let mut error_bytes = 0usize;
+    let mut cursor = root.walk();
+    loop {
+        let node = cursor.node();
+        let descend = if node.is_error() {
+            error_bytes += node.end_byte() - node.start_byte();
+            false
+        } else {
+            node.has_error()
+        };
+        if (descend && cursor.goto_first_child()) || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                return error_bytes as f64 / total as f64;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+}
+
+/// A parsed tree together with the source it was parsed from
+#[derive(Debug, Clone)]
+struct CachedTree {
+    tree: Tree,
+    content: String,
+}
+
+/// Cache of parsed trees used for incremental re-parsing of changed files
+///
+/// Tree-sitter can only reuse an old tree after it has been told which bytes
+/// changed, so the source of each tree is kept to compute that edit.
+#[derive(Debug, Default)]
+pub struct TreeCache {
+    entries: DashMap<PathBuf, CachedTree>,
+    /// Lookups that found a cached tree
+    hits: AtomicU64,
+    /// Lookups for files with no cached tree
+    misses: AtomicU64,
+}
+
+impl TreeCache {
+    /// Create an empty tree cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache the tree parsed from `content` for a file
+    pub fn insert(&self, path: PathBuf, tree: Tree, content: String) {
+        self.entries.insert(path, CachedTree { tree, content });
+    }
+
+    /// Get the cached tree for a file, edited to line up with `new_content`
+    ///
+    /// The returned tree can be passed as [`ParseContext::old_tree`] so the
+    /// parser only re-parses the changed region.
+    pub fn edited_tree(&self, path: &Path, new_content: &str) -> Option<Tree> {
+        let Some(cached) = self.entries.get(path) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        let mut tree = cached.tree.clone();
+        if let Some(edit) = compute_input_edit(&cached.content, new_content) {
+            tree.edit(&edit);
+        }
+        Some(tree)
+    }
+
+    /// Check whether a tree is cached for a file
+    pub fn contains(&self, path: &Path) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    /// Remove the cached tree for a file
+    pub fn remove(&self, path: &Path) {
+        self.entries.remove(path);
+    }
+
+    /// Remove all cached trees
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    /// Number of cached trees
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of [`Self::edited_tree`] lookups so far
+    pub fn lookups(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed) + self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Fraction of lookups that found a cached tree, `None` before the first lookup
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.lookups();
+        (lookups > 0).then(|| self.hits.load(Ordering::Relaxed) as f64 / lookups as f64)
+    }
+}
+
+/// Compute the single edit that turns `old` into `new`
+///
+/// The edit spans everything between the longest common prefix and suffix of
+/// the two texts. Returns `None` when the texts are identical.
+pub fn compute_input_edit(old: &str, new: &str) -> Option<InputEdit> {
+    if old == new {
+        return None;
+    }
+
+    let old_bytes = old.as_bytes();
+    let new_bytes = new.as_bytes();
+
+    let prefix = old_bytes
+        .iter()
+        .zip(new_bytes)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let max_suffix = old_bytes.len().min(new_bytes.len()) - prefix;
+    let suffix = old_bytes
+        .iter()
+        .rev()
+        .zip(new_bytes.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_end_byte = old_bytes.len() - suffix;
+    let new_end_byte = new_bytes.len() - suffix;
+
+    Some(InputEdit {
+        start_byte: prefix,
+        old_end_byte,
+        new_end_byte,
+        start_position: point_at(old_bytes, prefix),
+        old_end_position: point_at(old_bytes, old_end_byte),
+        new_end_position: point_at(new_bytes, new_end_byte),
+    })
+}
+
+/// Row and byte column of a byte offset
+fn point_at(text: &[u8], byte: usize) -> Point {
+    let before = &text[..byte];
+    let row = before.iter().filter(|&&b| b == b'\n').count();
+    let column = match before.iter().rposition(|&b| b == b'\n') {
+        Some(newline) => byte - newline - 1,
+        None => byte,
+    };
+    Point { row, column }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ast::{Edge, EdgeKind, NodeKind, Span};
-    use std::sync::atomic::{AtomicUsize, Ordering};
 
     // Mock parser for testing
     struct MockParser {
@@ -280,6 +602,47 @@ mod tests {
         );
     }
 
+    struct MainDetector;
+
+    impl EntryPointDetector for MainDetector {
+        fn language(&self) -> Language {
+            Language::JavaScript
+        }
+
+        fn detect(&self, graph: &GraphStore) -> Vec<NodeId> {
+            graph
+                .get_nodes_by_name("main")
+                .into_iter()
+                .map(|node| node.id)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_registry_runs_entry_point_detectors() {
+        let registry = LanguageRegistry::new();
+        let graph = GraphStore::new();
+        let main = Node::new(
+            "repo",
+            NodeKind::Function,
+            "main".to_string(),
+            Language::JavaScript,
+            PathBuf::from("index.js"),
+            Span::new(0, 10, 1, 1, 1, 11),
+        );
+        graph.add_node(main.clone());
+        assert!(registry.detect_entry_points(&graph).is_empty());
+
+        registry.register_entry_point_detector(Arc::new(MainDetector));
+        // Re-registering replaces the detector instead of running it twice
+        registry.register_entry_point_detector(Arc::new(MainDetector));
+        assert!(registry
+            .entry_point_detector(Language::JavaScript)
+            .is_some());
+        assert!(registry.entry_point_detector(Language::Python).is_none());
+        assert_eq!(registry.detect_entry_points(&graph), vec![main.id]);
+    }
+
     #[test]
     fn test_parse_context() {
         let context = ParseContext::new(
@@ -385,6 +748,23 @@ mod tests {
         assert_eq!(parser.parse_count(), 2); // Both parses executed
     }
 
+    #[test]
+    fn test_parse_detached_leaves_tree_cache_alone() {
+        let registry = Arc::new(LanguageRegistry::new());
+        registry.register(Arc::new(MockParser::new(Language::JavaScript)));
+
+        let engine = ParserEngine::new(registry);
+        let context = ParseContext::new(
+            "test_repo".to_string(),
+            PathBuf::from("test.js"),
+            "function old() {}".to_string(),
+        );
+        let result = engine.parse_detached(&context).unwrap();
+
+        assert_eq!(result.nodes.len(), 2);
+        assert!(engine.tree_cache.is_empty());
+    }
+
     #[test]
     fn test_parser_engine_cache_management() {
         let registry = Arc::new(LanguageRegistry::new());
@@ -473,4 +853,119 @@ mod tests {
             assert!(!result.nodes.is_empty(), "Should not be empty");
         }
     }
+
+    #[test]
+    fn test_compute_input_edit() {
+        assert!(compute_input_edit("same", "same").is_none());
+
+        let edit =
+            compute_input_edit("let a = 1;\nlet b = 2;\n", "let a = 1;\nlet bc = 2;\n").unwrap();
+        assert_eq!(edit.start_byte, 16);
+        assert_eq!(edit.old_end_byte, 16);
+        assert_eq!(edit.new_end_byte, 17);
+        assert_eq!(edit.start_position, Point { row: 1, column: 5 });
+        assert_eq!(edit.new_end_position, Point { row: 1, column: 6 });
+    }
+
+    #[test]
+    fn test_tree_cache_incremental_reparse_matches_full_parse() {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_javascript::LANGUAGE.into())
+            .unwrap();
+
+        let path = PathBuf::from("app.js");
+        let old_content = "function a() { return 1; }\nfunction b() {}\n";
+        let new_content = "function a() { return 42; }\nfunction b() {}\nfunction c() {}\n";
+
+        let cache = TreeCache::new();
+        assert!(cache.edited_tree(&path, new_content).is_none());
+        cache.insert(
+            path.clone(),
+            parser.parse(old_content, None).unwrap(),
+            old_content.to_string(),
+        );
+
+        let old_tree = cache.edited_tree(&path, new_content).unwrap();
+        let incremental = parser.parse(new_content, Some(&old_tree)).unwrap();
+        let full = parser.parse(new_content, None).unwrap();
+        assert_eq!(
+            incremental.root_node().to_sexp(),
+            full.root_node().to_sexp()
+        );
+        assert_eq!(cache.lookups(), 2);
+        assert_eq!(cache.hit_rate(), Some(0.5));
+
+        cache.remove(&path);
+        assert!(cache.is_empty());
+    }
+
+    /// Returns a degraded tree on its first call and a clean one afterwards
+    struct FlakyParser {
+        calls: AtomicUsize,
+    }
+
+    impl LanguageParser for FlakyParser {
+        fn language(&self) -> Language {
+            Language::JavaScript
+        }
+
+        fn parse(&self, context: &ParseContext) -> Result<ParseResult> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let source = if call == 0 {
+                ")) function { ( ] let = ;; }}"
+            } else {
+                context.content.as_str()
+            };
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_javascript::LANGUAGE.into())
+                .unwrap();
+            Ok(ParseResult {
+                tree: parser.parse(source, None).unwrap(),
+                nodes: Vec::new(),
+                edges: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_degraded_tree_is_reparsed_and_clean_result_kept() {
+        let registry = Arc::new(LanguageRegistry::new());
+        let flaky = Arc::new(FlakyParser {
+            calls: AtomicUsize::new(0),
+        });
+        registry.register(flaky.clone());
+        let context = ParseContext::new(
+            "test_repo".to_string(),
+            PathBuf::from("app.js"),
+            "function ok() { return 1; }".to_string(),
+        );
+
+        let engine = ParserEngine::new(registry.clone());
+        let result = engine.parse_file(context.clone()).unwrap();
+        assert_eq!(error_ratio(&result.tree), 0.0);
+        assert!(!result.tree.root_node().has_error());
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            engine.reparse_stats(),
+            ReparseStats {
+                retried: 1,
+                improved: 1
+            }
+        );
+
+        // Clean trees are not retried, and the retry can be switched off
+        engine.parse_file(context.clone()).unwrap();
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+
+        flaky.calls.store(0, Ordering::SeqCst);
+        let engine = ParserEngine::new(registry).with_reparse_policy(ReparsePolicy {
+            enabled: false,
+            ..ReparsePolicy::default()
+        });
+        let result = engine.parse_file(context).unwrap();
+        assert!(error_ratio(&result.tree) > ReparsePolicy::default().max_error_ratio);
+        assert_eq!(engine.reparse_stats(), ReparseStats::default());
+    }
 }
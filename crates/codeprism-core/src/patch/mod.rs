@@ -2,6 +2,7 @@
 
 use crate::ast::{Edge, Node};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// AST patch containing changes to apply
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +64,57 @@ impl AstPatch {
             self.timestamp_ms = other.timestamp_ms;
         }
     }
+
+    /// Create a patch holding only the differences between two parses of a file
+    ///
+    /// Node IDs are derived from kind and byte span, so nodes are matched by ID
+    /// and a node whose name or signature changed in place is deleted and
+    /// re-added. Edges are matched by [`Edge::id`].
+    pub fn diff(
+        repo: String,
+        commit: String,
+        old_nodes: &[Node],
+        old_edges: &[Edge],
+        new_nodes: Vec<Node>,
+        new_edges: Vec<Edge>,
+    ) -> Self {
+        let mut patch = Self::new(repo, commit);
+
+        let old_by_id: HashMap<_, _> = old_nodes.iter().map(|node| (node.id, node)).collect();
+        let new_ids: HashSet<_> = new_nodes.iter().map(|node| node.id).collect();
+
+        for node in old_nodes {
+            if !new_ids.contains(&node.id) {
+                patch.nodes_delete.push(node.id.to_hex());
+            }
+        }
+        for node in new_nodes {
+            match old_by_id.get(&node.id) {
+                Some(old) if old.name == node.name && old.signature == node.signature => {}
+                Some(_) => {
+                    patch.nodes_delete.push(node.id.to_hex());
+                    patch.nodes_add.push(node);
+                }
+                None => patch.nodes_add.push(node),
+            }
+        }
+
+        let old_edge_ids: HashSet<_> = old_edges.iter().map(Edge::id).collect();
+        let new_edge_ids: HashSet<_> = new_edges.iter().map(Edge::id).collect();
+
+        for edge in old_edges {
+            let edge_id = edge.id();
+            if !new_edge_ids.contains(&edge_id) {
+                patch.edges_delete.push(edge_id);
+            }
+        }
+        patch.edges_add = new_edges
+            .into_iter()
+            .filter(|edge| !old_edge_ids.contains(&edge.id()))
+            .collect();
+
+        patch
+    }
 }
 
 /// Builder for creating AST patches
@@ -342,4 +394,53 @@ mod tests {
         assert_eq!(patch.edges_delete.len(), 50, "Should have 50 items");
         assert_eq!(patch.operation_count(), 200);
     }
+
+    #[test]
+    fn test_patch_diff_only_contains_changes() {
+        let node_at = |name: &str, start: usize| {
+            Node::new(
+                "test_repo",
+                NodeKind::Function,
+                name.to_string(),
+                Language::JavaScript,
+                PathBuf::from("test.js"),
+                Span::new(start, start + 10, 1, 1, 1, 11),
+            )
+        };
+
+        let kept = node_at("kept", 0);
+        let removed = node_at("removed", 20);
+        let renamed_old = node_at("before", 40);
+        let renamed_new = node_at("after", 40);
+        let added = node_at("added", 60);
+        let old_edge = create_test_edge(&kept, &removed);
+        let new_edge = create_test_edge(&kept, &added);
+
+        let patch = AstPatch::diff(
+            "test_repo".to_string(),
+            "abc123".to_string(),
+            &[kept.clone(), removed.clone(), renamed_old],
+            std::slice::from_ref(&old_edge),
+            vec![kept.clone(), renamed_new.clone(), added.clone()],
+            vec![new_edge.clone()],
+        );
+
+        let added_names: Vec<_> = patch.nodes_add.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(added_names, vec!["after", "added"]);
+        assert!(patch.nodes_delete.contains(&removed.id.to_hex()));
+        assert!(patch.nodes_delete.contains(&renamed_new.id.to_hex()));
+        assert!(!patch.nodes_delete.contains(&kept.id.to_hex()));
+        assert_eq!(patch.edges_delete, vec![old_edge.id()]);
+        assert_eq!(patch.edges_add, vec![new_edge]);
+
+        let unchanged = AstPatch::diff(
+            "test_repo".to_string(),
+            "abc123".to_string(),
+            std::slice::from_ref(&kept),
+            &[],
+            vec![kept.clone()],
+            vec![],
+        );
+        assert!(unchanged.is_empty());
+    }
 }
@@ -5,7 +5,7 @@
 
 use crate::error::{Error, Result};
 use crate::indexer::{BulkIndexer, IndexingConfig, IndexingResult, IndexingStats};
-use crate::parser::{LanguageRegistry, ParserEngine};
+use crate::parser::{LanguageRegistry, ParserEngine, ReparsePolicy, TreeCache};
 use crate::scanner::{NoOpProgressReporter, ProgressReporter, RepositoryScanner};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -71,6 +71,12 @@ impl RepositoryConfig {
         self
     }
 
+    /// Set the largest file to index, in bytes
+    pub fn with_max_file_size(mut self, bytes: usize) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
     /// Add a metadata entry
     pub fn with_metadata(mut self, key: String, value: String) -> Self {
         self.metadata.insert(key, value);
@@ -161,6 +167,7 @@ pub struct RepositoryManager {
     scanner: RepositoryScanner,
     parser_engine: Arc<ParserEngine>,
     repositories: HashMap<String, RepositoryInfo>,
+    tree_cache: Option<Arc<TreeCache>>,
 }
 
 impl RepositoryManager {
@@ -173,6 +180,7 @@ impl RepositoryManager {
             scanner,
             parser_engine,
             repositories: HashMap::new(),
+            tree_cache: None,
         }
     }
 
@@ -204,9 +212,23 @@ impl RepositoryManager {
             scanner,
             parser_engine,
             repositories: HashMap::new(),
+            tree_cache: None,
         }
     }
 
+    /// Keep the trees parsed while indexing in `tree_cache` for incremental re-parsing
+    pub fn set_tree_cache(&mut self, tree_cache: Arc<TreeCache>) {
+        self.tree_cache = Some(tree_cache);
+    }
+
+    /// Retry degraded trees while indexing according to `policy`
+    pub fn set_reparse_policy(&mut self, policy: ReparsePolicy) {
+        self.parser_engine = Arc::new(
+            ParserEngine::new(Arc::clone(self.parser_engine.registry()))
+                .with_reparse_policy(policy),
+        );
+    }
+
     /// Register a repository
     pub fn register_repository(&mut self, config: RepositoryConfig) -> Result<()> {
         // Validate repository path exists
@@ -258,7 +280,9 @@ impl RepositoryManager {
 
         let progress = progress_reporter.unwrap_or_else(|| Arc::new(NoOpProgressReporter));
 
-        // Step 1: Scan repository
+        // Step 1: Scan repository, skipping files over the configured size
+        self.scanner
+            .set_max_file_size(repo_info.config.max_file_size);
         let scan_result = self
             .scanner
             .scan_repository(&repo_info.config.root_path, Arc::clone(&progress))
@@ -279,7 +303,10 @@ impl RepositoryManager {
             format!("scan-{}", chrono::Utc::now().timestamp()),
         );
 
-        let indexer = BulkIndexer::new(indexing_config, Arc::clone(&self.parser_engine));
+        let mut indexer = BulkIndexer::new(indexing_config, Arc::clone(&self.parser_engine));
+        if let Some(tree_cache) = &self.tree_cache {
+            indexer = indexer.with_tree_cache(Arc::clone(tree_cache));
+        }
         let indexing_result = indexer.index_scan_result(&scan_result, progress).await?;
 
         // Update repository info with indexing results
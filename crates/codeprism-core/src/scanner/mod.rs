@@ -2,6 +2,7 @@
 
 use crate::ast::Language;
 use crate::error::{Error, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use rayon::prelude::*;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -18,6 +19,8 @@ pub struct DiscoveredFile {
     pub language: Language,
     /// File size in bytes
     pub size: usize,
+    /// Last modification time, when the filesystem reports one
+    pub modified: Option<std::time::SystemTime>,
 }
 
 /// Repository scan result
@@ -31,6 +34,8 @@ pub struct ScanResult {
     pub duration_ms: u64,
     /// Errors encountered during scan
     pub errors: Vec<Error>,
+    /// Configuration files (YAML, TOML, INI) that may reference code
+    pub config_files: Vec<PathBuf>,
 }
 
 impl ScanResult {
@@ -41,6 +46,7 @@ impl ScanResult {
             files_by_language: std::collections::HashMap::new(),
             duration_ms: 0,
             errors: Vec::new(),
+            config_files: Vec::new(),
         }
     }
 
@@ -69,6 +75,129 @@ impl Default for ScanResult {
     }
 }
 
+/// Rough average of source bytes per graph node, used to estimate index size
+const ESTIMATED_BYTES_PER_NODE: usize = 60;
+
+/// Files of one language in a [`RepositoryPlan`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguagePlan {
+    /// Language of the files
+    pub language: Language,
+    /// Number of files
+    pub file_count: usize,
+    /// Combined size in bytes
+    pub total_bytes: usize,
+    /// Rough number of graph nodes indexing the files would create
+    pub estimated_nodes: usize,
+}
+
+/// Files whose size falls in a range, in a [`RepositoryPlan`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeBucket {
+    /// Smallest size in bytes
+    pub min_bytes: usize,
+    /// Size in bytes the files stay below, `None` for the last bucket
+    pub max_bytes: Option<usize>,
+    /// Number of files
+    pub file_count: usize,
+    /// Combined size in bytes
+    pub total_bytes: usize,
+}
+
+/// What indexing a repository would process, found without parsing any file
+#[derive(Debug, Clone)]
+pub struct RepositoryPlan {
+    /// Repository root
+    pub root_path: PathBuf,
+    /// Files that would be parsed
+    pub total_files: usize,
+    /// Combined size of those files in bytes
+    pub total_bytes: usize,
+    /// Rough number of graph nodes indexing would create
+    pub estimated_nodes: usize,
+    /// Files per language, most files first
+    pub languages: Vec<LanguagePlan>,
+    /// Files per size range, smallest range first
+    pub size_buckets: Vec<SizeBucket>,
+    /// The largest files, largest first
+    pub largest_files: Vec<DiscoveredFile>,
+    /// Discovered files the indexer would skip, such as files of unknown
+    /// language or filtered dependency files
+    pub skipped_files: usize,
+}
+
+impl RepositoryPlan {
+    /// Number of largest files kept in a plan
+    pub const LARGEST_FILES: usize = 10;
+
+    /// Upper bounds of the size buckets in bytes
+    const BUCKET_BOUNDS: [usize; 3] = [1024, 10 * 1024, 100 * 1024];
+
+    fn from_files(root_path: PathBuf, files: Vec<DiscoveredFile>, skipped_files: usize) -> Self {
+        let mut languages: Vec<LanguagePlan> = Vec::new();
+        let mut size_buckets: Vec<SizeBucket> = std::iter::once(0)
+            .chain(Self::BUCKET_BOUNDS)
+            .zip(Self::BUCKET_BOUNDS.map(Some).into_iter().chain([None]))
+            .map(|(min_bytes, max_bytes)| SizeBucket {
+                min_bytes,
+                max_bytes,
+                file_count: 0,
+                total_bytes: 0,
+            })
+            .collect();
+
+        for file in &files {
+            match languages
+                .iter_mut()
+                .find(|plan| plan.language == file.language)
+            {
+                Some(plan) => {
+                    plan.file_count += 1;
+                    plan.total_bytes += file.size;
+                }
+                None => languages.push(LanguagePlan {
+                    language: file.language,
+                    file_count: 1,
+                    total_bytes: file.size,
+                    estimated_nodes: 0,
+                }),
+            }
+            if let Some(bucket) = size_buckets
+                .iter_mut()
+                .find(|bucket| bucket.max_bytes.is_none_or(|max| file.size < max))
+            {
+                bucket.file_count += 1;
+                bucket.total_bytes += file.size;
+            }
+        }
+        for plan in &mut languages {
+            // Every file has at least its module node
+            plan.estimated_nodes =
+                (plan.total_bytes / ESTIMATED_BYTES_PER_NODE).max(plan.file_count);
+        }
+        languages.sort_by(|a, b| {
+            b.file_count
+                .cmp(&a.file_count)
+                .then_with(|| a.language.to_string().cmp(&b.language.to_string()))
+        });
+
+        let mut largest_files = files;
+        largest_files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        largest_files.truncate(Self::LARGEST_FILES);
+
+        Self {
+            root_path,
+            total_files: languages.iter().map(|plan| plan.file_count).sum(),
+            total_bytes: languages.iter().map(|plan| plan.total_bytes).sum(),
+            estimated_nodes: languages.iter().map(|plan| plan.estimated_nodes).sum(),
+            languages,
+            size_buckets,
+            largest_files,
+            skipped_files,
+        }
+    }
+}
+
 /// Progress reporter for scan operations
 pub trait ProgressReporter: Send + Sync {
     /// Report progress with current file count and estimated total
@@ -79,6 +208,9 @@ pub trait ProgressReporter: Send + Sync {
 
     /// Report an error
     fn report_error(&self, error: &Error);
+
+    /// Report that an indexing batch finished
+    fn report_batch(&self, _progress: &crate::indexer::BatchProgress) {}
 }
 
 /// No-op progress reporter
@@ -105,8 +237,17 @@ pub enum DependencyMode {
 /// Repository scanner for discovering source files
 pub struct RepositoryScanner {
     supported_extensions: std::collections::HashSet<String>,
+    config_extensions: HashSet<String>,
     exclude_dirs: HashSet<String>,
     dependency_mode: DependencyMode,
+    /// Largest file to index in bytes; `None` uses the per-mode defaults
+    max_file_size: Option<usize>,
+    /// Repo-relative paths to keep; `None` keeps everything
+    include_globs: Option<GlobSet>,
+    /// Repo-relative paths to skip, taking precedence over `include_globs`
+    exclude_globs: Option<GlobSet>,
+    /// Source patterns of `exclude_globs`, kept so later calls can add to them
+    exclude_glob_patterns: Vec<String>,
 }
 
 impl RepositoryScanner {
@@ -121,6 +262,7 @@ impl RepositoryScanner {
                 "java", // Java
                 "go",   // Go
                 "rs",   // Rust
+                "php",  // PHP
                 "c", "h", // C
                 "cpp", "cc", "cxx", "hpp", "hxx", // C++
             ]
@@ -140,8 +282,16 @@ impl RepositoryScanner {
 
         Self {
             supported_extensions,
+            config_extensions: ["yaml", "yml", "toml", "ini", "cfg"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
             exclude_dirs,
             dependency_mode: DependencyMode::Exclude,
+            max_file_size: None,
+            include_globs: None,
+            exclude_globs: None,
+            exclude_glob_patterns: Vec::new(),
         }
     }
 
@@ -171,6 +321,85 @@ impl RepositoryScanner {
         self
     }
 
+    /// Skip files larger than `bytes` instead of the per-mode defaults
+    pub fn with_max_file_size(mut self, bytes: usize) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Replace the file size limit; `None` restores the per-mode defaults
+    pub fn set_max_file_size(&mut self, bytes: Option<usize>) {
+        self.max_file_size = bytes;
+    }
+
+    /// Largest file that will be indexed, in bytes
+    pub fn max_file_size(&self) -> usize {
+        self.max_file_size.unwrap_or(match self.dependency_mode {
+            DependencyMode::Smart => 20 * 1024 * 1024, // 20MB for dependencies
+            _ => 10 * 1024 * 1024,                     // 10MB for regular files
+        })
+    }
+
+    /// Only discover files whose repo-relative path matches one of `patterns`
+    ///
+    /// Patterns starting with `!` are negated and skip matching files instead,
+    /// as in `["src/**/*.py", "!src/**/test_*.py"]`.
+    pub fn with_include_globs<I, S>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let (negated, included): (Vec<String>, Vec<String>) = patterns
+            .into_iter()
+            .map(|pattern| pattern.as_ref().to_string())
+            .partition(|pattern| pattern.starts_with('!'));
+        self.include_globs = Some(build_glob_set(&included)?);
+        if !negated.is_empty() {
+            let negated: Vec<&str> = negated.iter().map(|p| &p[1..]).collect();
+            self = self.with_exclude_globs(negated)?;
+        }
+        Ok(self)
+    }
+
+    /// Skip files and directories whose repo-relative path matches one of `patterns`
+    ///
+    /// Exclusions win over include globs and apply in addition to the
+    /// directory names given to [`Self::with_exclude_dirs`].
+    pub fn with_exclude_globs<I, S>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.exclude_glob_patterns.extend(
+            patterns
+                .into_iter()
+                .map(|pattern| pattern.as_ref().to_string()),
+        );
+        self.exclude_globs = Some(build_glob_set(&self.exclude_glob_patterns)?);
+        Ok(self)
+    }
+
+    /// Whether a repo-relative path passes the include and exclude globs
+    fn matches_globs(&self, rel_path: &Path) -> bool {
+        if self
+            .exclude_globs
+            .as_ref()
+            .is_some_and(|globs| globs.is_match(rel_path))
+        {
+            return false;
+        }
+        self.include_globs
+            .as_ref()
+            .is_none_or(|globs| globs.is_match(rel_path))
+    }
+
+    /// Set the extensions of configuration files collected for cross-referencing
+    pub fn with_config_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.config_extensions.clear();
+        self.config_extensions.extend(extensions);
+        self
+    }
+
     /// Scan a repository directory and discover source files
     pub async fn scan_repository<P: AsRef<Path>>(
         &self,
@@ -181,7 +410,7 @@ impl RepositoryScanner {
         let start_time = std::time::Instant::now();
 
         // Discover files
-        let discovered_paths = self.discover_files(repo_path)?;
+        let (discovered_paths, config_files) = self.walk_repository(repo_path)?;
         progress_reporter.report_progress(discovered_paths.len(), Some(discovered_paths.len()));
 
         // Process files in parallel
@@ -224,6 +453,7 @@ impl RepositoryScanner {
             }
         }
 
+        result.config_files = config_files;
         result.duration_ms = start_time.elapsed().as_millis() as u64;
         progress_reporter.report_complete(&result);
         Ok(result)
@@ -231,6 +461,78 @@ impl RepositoryScanner {
 
     /// Discover all potential files in the repository
     pub fn discover_files<P: AsRef<Path>>(&self, repo_path: P) -> Result<Vec<PathBuf>> {
+        Ok(self.walk_repository(repo_path)?.0)
+    }
+
+    /// Discover source files along with their language, size and
+    /// modification time, without parsing them
+    pub fn discover_files_with_metadata<P: AsRef<Path>>(
+        &self,
+        repo_path: P,
+    ) -> Result<Vec<DiscoveredFile>> {
+        let mut files: Vec<DiscoveredFile> = self
+            .discover_files(repo_path)?
+            .par_iter()
+            .filter_map(|path| self.process_file(path).ok().flatten())
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Whether discovering `repo_path` would pick up `file_path`
+    ///
+    /// Applies the directory excludes, globs and extension filter to the path
+    /// alone, so it also answers for files that were just deleted.
+    pub fn would_discover<P: AsRef<Path>, R: AsRef<Path>>(
+        &self,
+        file_path: P,
+        repo_path: R,
+    ) -> bool {
+        let (file_path, repo_path) = (file_path.as_ref(), repo_path.as_ref());
+        let Ok(rel_path) = file_path.strip_prefix(repo_path) else {
+            return false;
+        };
+
+        let mut dir = repo_path.to_path_buf();
+        for component in rel_path.parent().into_iter().flat_map(Path::components) {
+            dir.push(component);
+            let rel_dir = dir.strip_prefix(repo_path).unwrap_or(&dir);
+            let excluded_by_glob = self
+                .exclude_globs
+                .as_ref()
+                .is_some_and(|globs| globs.is_match(rel_dir));
+            if excluded_by_glob || self.should_exclude_directory(&dir, repo_path) {
+                return false;
+            }
+        }
+
+        self.matches_globs(rel_path) && self.should_include_file(file_path)
+    }
+
+    /// Plan an index of the repository: the files it would parse, grouped by
+    /// language and size, found with the same filters but without parsing
+    pub fn plan_repository<P: AsRef<Path>>(&self, repo_path: P) -> Result<RepositoryPlan> {
+        let repo_path = repo_path.as_ref();
+        let discovered = self.discover_files(repo_path)?;
+        let discovered_count = discovered.len();
+        let files: Vec<DiscoveredFile> = discovered
+            .par_iter()
+            .filter_map(|path| self.process_file(path).ok().flatten())
+            .collect();
+        let skipped_files = discovered_count - files.len();
+
+        Ok(RepositoryPlan::from_files(
+            repo_path.to_path_buf(),
+            files,
+            skipped_files,
+        ))
+    }
+
+    /// Walk the repository, returning source files and configuration files
+    fn walk_repository<P: AsRef<Path>>(
+        &self,
+        repo_path: P,
+    ) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
         let repo_path = repo_path.as_ref();
 
         if !repo_path.exists() {
@@ -248,13 +550,20 @@ impl RepositoryScanner {
         }
 
         let mut files = Vec::new();
+        let mut config_files = Vec::new();
         let walker = WalkDir::new(repo_path)
             .follow_links(false)
             .into_iter()
             .filter_entry(|e| {
                 // Filter out excluded directories during walking for efficiency
                 if e.path().is_dir() {
-                    !self.should_exclude_directory(e.path(), repo_path)
+                    let rel_path = e.path().strip_prefix(repo_path).unwrap_or(e.path());
+                    let excluded_by_glob = e.depth() > 0
+                        && self
+                            .exclude_globs
+                            .as_ref()
+                            .is_some_and(|globs| globs.is_match(rel_path));
+                    !excluded_by_glob && !self.should_exclude_directory(e.path(), repo_path)
                 } else {
                     true
                 }
@@ -270,9 +579,26 @@ impl RepositoryScanner {
                         continue;
                     }
 
+                    if !self.matches_globs(path.strip_prefix(repo_path).unwrap_or(path)) {
+                        continue;
+                    }
+
                     // Check if it's a file we might be interested in
                     if self.should_include_file(path) {
+                        // Check the size from metadata so oversized files are never read
+                        let size = entry.metadata().map(|m| m.len() as usize).unwrap_or(0);
+                        if size > self.max_file_size() {
+                            tracing::warn!(
+                                "Skipping {}: {} bytes exceeds the {} byte file size limit",
+                                path.display(),
+                                size,
+                                self.max_file_size()
+                            );
+                            continue;
+                        }
                         files.push(path.to_path_buf());
+                    } else if self.is_config_file(path) {
+                        config_files.push(path.to_path_buf());
                     }
                 }
                 Err(e) => {
@@ -282,7 +608,15 @@ impl RepositoryScanner {
             }
         }
 
-        Ok(files)
+        Ok((files, config_files))
+    }
+
+    /// Check if a file is a configuration file that may reference code
+    fn is_config_file(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.config_extensions.contains(&ext.to_lowercase()))
     }
 
     /// Check if a directory should be excluded from scanning
@@ -422,13 +756,7 @@ impl RepositoryScanner {
 
         let file_size = metadata.len() as usize;
 
-        // Check file size limit - be more lenient for dependency files in smart mode
-        let size_limit = match self.dependency_mode {
-            DependencyMode::Smart => 20 * 1024 * 1024, // 20MB for dependencies
-            _ => 10 * 1024 * 1024,                     // 10MB for regular files
-        };
-
-        if file_size > size_limit {
+        if file_size > self.max_file_size() {
             return Ok(None); // Skip large files
         }
 
@@ -464,6 +792,7 @@ impl RepositoryScanner {
             path: file_path.to_path_buf(),
             language,
             size: file_size,
+            modified: metadata.modified().ok(),
         }))
     }
 
@@ -557,3 +886,182 @@ impl Default for RepositoryScanner {
         Self::new()
     }
 }
+
+/// Compile glob patterns, reporting the first invalid one
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|e| Error::config("glob", format!("Invalid glob '{pattern}': {e}")))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| Error::config("glob", format!("Failed to compile globs: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_files_over_size_limit_are_not_indexed() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("small.py"), "def ok():\n    pass\n").unwrap();
+        std::fs::write(temp_dir.path().join("generated.py"), "x = 1\n".repeat(1024)).unwrap();
+
+        let scanner = RepositoryScanner::new().with_max_file_size(1024);
+        let discovered = scanner.discover_files(temp_dir.path()).unwrap();
+        assert_eq!(discovered, vec![temp_dir.path().join("small.py")]);
+
+        let result = scanner
+            .scan_repository(temp_dir.path(), Arc::new(NoOpProgressReporter))
+            .await
+            .unwrap();
+        assert_eq!(result.total_files, 1);
+        assert_eq!(result.all_files()[0].path, temp_dir.path().join("small.py"));
+
+        // The default limit keeps both
+        let discovered = RepositoryScanner::new()
+            .discover_files(temp_dir.path())
+            .unwrap();
+        assert_eq!(discovered.len(), 2);
+    }
+
+    #[test]
+    fn test_discover_files_with_metadata() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("app.py"), "x = 1\n").unwrap();
+        std::fs::write(root.join("main.go"), "package main\n").unwrap();
+        std::fs::write(root.join("notes.txt"), "not source\n").unwrap();
+
+        let files = RepositoryScanner::new()
+            .discover_files_with_metadata(root)
+            .unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, root.join("app.py"));
+        assert_eq!(files[0].language, Language::Python);
+        assert_eq!(files[0].size, 6);
+        assert!(files[0].modified.is_some());
+        assert_eq!(files[1].language, Language::Go);
+    }
+
+    #[test]
+    fn test_plan_groups_files_without_parsing() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("app.py"), "x = 1\n".repeat(400)).unwrap();
+        std::fs::write(root.join("util.py"), "def f():\n    pass\n").unwrap();
+        std::fs::write(root.join("main.go"), "package main\n").unwrap();
+        std::fs::create_dir(root.join("node_modules")).unwrap();
+        std::fs::write(root.join("node_modules/lib.js"), "module.exports = 1;\n").unwrap();
+
+        let plan = RepositoryScanner::new().plan_repository(root).unwrap();
+
+        assert_eq!(plan.total_files, 3);
+        assert_eq!(plan.total_bytes, 2400 + 18 + 13);
+        assert_eq!(plan.languages[0].language, Language::Python);
+        assert_eq!(plan.languages[0].file_count, 2);
+        assert_eq!(plan.languages[0].estimated_nodes, 2418 / 60);
+        assert_eq!(plan.languages[1].language, Language::Go);
+        assert_eq!(plan.languages[1].estimated_nodes, 1);
+        let counts: Vec<usize> = plan.size_buckets.iter().map(|b| b.file_count).collect();
+        assert_eq!(counts, vec![2, 1, 0, 0]);
+        assert_eq!(plan.size_buckets[3].max_bytes, None);
+        assert_eq!(plan.largest_files[0].path, root.join("app.py"));
+        assert_eq!(plan.estimated_nodes, 2418 / 60 + 1);
+    }
+
+    #[test]
+    fn test_would_discover_matches_the_walk_filters() {
+        let root = Path::new("/repo");
+        let scanner = RepositoryScanner::new()
+            .with_exclude_globs(["**/generated/**"])
+            .unwrap();
+
+        assert!(scanner.would_discover(root.join("src/app.py"), root));
+        assert!(!scanner.would_discover(root.join("node_modules/lib/index.js"), root));
+        assert!(!scanner.would_discover(root.join(".git/HEAD"), root));
+        assert!(!scanner.would_discover(root.join("src/generated/models.py"), root));
+        assert!(!scanner.would_discover(root.join("notes.txt"), root));
+        assert!(!scanner.would_discover("/elsewhere/app.py", root));
+    }
+
+    #[test]
+    fn test_exclude_globs_skip_nested_generated_directories() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for file in [
+            "src/app.py",
+            "src/generated/models.py",
+            "src/api/generated/v1/client.py",
+            "src/api/handlers.py",
+            "generated_notes.py",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "x = 1\n").unwrap();
+        }
+
+        let scanner = RepositoryScanner::new()
+            .with_exclude_globs(["**/generated/**"])
+            .unwrap();
+        let mut discovered = scanner.discover_files(root).unwrap();
+        discovered.sort();
+        assert_eq!(
+            discovered,
+            vec![
+                root.join("generated_notes.py"),
+                root.join("src/api/handlers.py"),
+                root.join("src/app.py"),
+            ]
+        );
+
+        // Name-based excludes keep working alongside globs
+        let scanner = RepositoryScanner::with_exclude_dirs(vec!["api".to_string()])
+            .with_exclude_globs(["**/generated/**"])
+            .unwrap();
+        let mut discovered = scanner.discover_files(root).unwrap();
+        discovered.sort();
+        assert_eq!(
+            discovered,
+            vec![root.join("generated_notes.py"), root.join("src/app.py")]
+        );
+
+        assert!(RepositoryScanner::new()
+            .with_exclude_globs(["src/["])
+            .is_err());
+    }
+
+    #[test]
+    fn test_negated_include_glob_is_excluded() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for file in [
+            "src/service.py",
+            "src/pkg/test_service.py",
+            "src/pkg/util.py",
+            "scripts/deploy.py",
+            "src/generated/schema.py",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "x = 1\n").unwrap();
+        }
+
+        // Excludes win even when the include glob matches
+        let scanner = RepositoryScanner::new()
+            .with_exclude_globs(["src/generated/**"])
+            .unwrap()
+            .with_include_globs(["src/**/*.py", "!**/test_*.py"])
+            .unwrap();
+        let mut discovered = scanner.discover_files(root).unwrap();
+        discovered.sort();
+        assert_eq!(
+            discovered,
+            vec![root.join("src/pkg/util.py"), root.join("src/service.py")]
+        );
+    }
+}
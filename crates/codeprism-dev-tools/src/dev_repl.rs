@@ -1,9 +1,12 @@
 //! Interactive development REPL for parser development
 
-use crate::{AstVisualizer, GraphVizExporter, ParserValidator, PerformanceProfiler};
+use crate::{AstDiff, AstVisualizer, GraphVizExporter, ParserValidator, PerformanceProfiler};
 use anyhow::Result;
+use codeprism_core::ParserEngine;
 use colored::Colorize;
 use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
 
 /// Interactive development REPL
 #[derive(Debug)]
@@ -15,6 +18,7 @@ pub struct DevRepl {
     validator: Option<ParserValidator>,
     profiler: Option<PerformanceProfiler>,
     exporter: Option<GraphVizExporter>,
+    parser_engine: Option<Arc<ParserEngine>>,
     prompt: String,
 }
 
@@ -42,6 +46,12 @@ pub enum ReplCommand {
         old_source: String,
         new_source: String,
     },
+    /// Diff a file between a git revision and another revision or the working tree
+    Diff {
+        file_path: String,
+        old_revision: String,
+        new_revision: Option<String>,
+    },
     Profile {
         command: String,
     },
@@ -95,6 +105,7 @@ impl DevRepl {
             validator: None,
             profiler: None,
             exporter: None,
+            parser_engine: None,
             prompt: "codeprism> ".to_string(),
         })
     }
@@ -114,6 +125,11 @@ impl DevRepl {
         self.profiler = Some(profiler);
     }
 
+    /// Set the parser engine used by `diff`
+    pub fn set_parser_engine(&mut self, engine: Arc<ParserEngine>) {
+        self.parser_engine = Some(engine);
+    }
+
     /// Set the GraphViz exporter
     pub fn set_exporter(&mut self, exporter: GraphVizExporter) {
         self.exporter = Some(exporter);
@@ -178,6 +194,10 @@ impl DevRepl {
             "  {} <old> <new>      - Compare two code snippets",
             "compare".cyan()
         );
+        println!(
+            "  {} <file> <rev> [rev] - Diff a file's AST between git revisions",
+            "diff".cyan()
+        );
         println!(
             "  {} <cmd>            - Profile parsing performance",
             "profile".cyan()
@@ -313,6 +333,19 @@ impl DevRepl {
                     }
                 }
             }
+            "diff" => {
+                if (3..=4).contains(&parts.len()) {
+                    ReplCommand::Diff {
+                        file_path: parts[1].to_string(),
+                        old_revision: parts[2].to_string(),
+                        new_revision: parts.get(3).map(|rev| rev.to_string()),
+                    }
+                } else {
+                    ReplCommand::Unknown {
+                        input: input.to_string(),
+                    }
+                }
+            }
             "profile" => {
                 if parts.len() > 1 {
                     ReplCommand::Profile {
@@ -346,6 +379,14 @@ impl DevRepl {
                 old_source,
                 new_source,
             } => self.handle_compare(&old_source, &new_source).await,
+            ReplCommand::Diff {
+                file_path,
+                old_revision,
+                new_revision,
+            } => {
+                self.handle_diff(&file_path, &old_revision, new_revision.as_deref())
+                    .await
+            }
             ReplCommand::Profile { command } => self.handle_profile(&command).await,
             ReplCommand::Help => self.handle_help().await,
             ReplCommand::Clear => self.handle_clear().await,
@@ -482,6 +523,53 @@ impl DevRepl {
         }
     }
 
+    /// Handle diff command
+    ///
+    /// Without a new revision the file is compared against the working tree.
+    async fn handle_diff(
+        &self,
+        file_path: &str,
+        old_revision: &str,
+        new_revision: Option<&str>,
+    ) -> ReplResult {
+        let Some(engine) = &self.parser_engine else {
+            return ReplResult {
+                success: false,
+                output: String::new(),
+                error: Some("No parser engine configured for diff".to_string()),
+            };
+        };
+
+        let path = Path::new(file_path);
+        let report = crate::read_file_at_revision(path, old_revision).and_then(|old_source| {
+            let new_source = match new_revision {
+                Some(revision) => crate::read_file_at_revision(path, revision)?,
+                None => std::fs::read_to_string(path)?,
+            };
+            AstDiff::new().compare_sources(engine, path, &old_source, &new_source)
+        });
+
+        match report {
+            Ok(report) => {
+                let mut output = report.format_report();
+                output.push_str("\n## Differences by type:\n");
+                for (label, count) in report.counts_by_type() {
+                    output.push_str(&format!("- {label}: {count}\n"));
+                }
+                ReplResult {
+                    success: true,
+                    output,
+                    error: None,
+                }
+            }
+            Err(e) => ReplResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to diff '{file_path}': {e}")),
+            },
+        }
+    }
+
     /// Handle profile command
     async fn handle_profile(&mut self, command: &str) -> ReplResult {
         ReplResult {
@@ -586,6 +674,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_diff_command() {
+        let repl = DevRepl::new(None).unwrap();
+
+        match repl.parse_command("diff src/app.py HEAD~1") {
+            ReplCommand::Diff {
+                file_path,
+                old_revision,
+                new_revision,
+            } => {
+                assert_eq!(file_path, "src/app.py");
+                assert_eq!(old_revision, "HEAD~1");
+                assert_eq!(new_revision, None);
+            }
+            _ => panic!("Expected diff command"),
+        }
+        assert!(matches!(
+            repl.parse_command("diff src/app.py v1 v2"),
+            ReplCommand::Diff { new_revision: Some(ref rev), .. } if rev == "v2"
+        ));
+        assert!(matches!(
+            repl.parse_command("diff src/app.py"),
+            ReplCommand::Unknown { .. }
+        ));
+    }
+
     #[test]
     fn test_parse_show_command() {
         let repl = DevRepl::new(None).unwrap();
@@ -1,9 +1,10 @@
 //! AST diff comparison utilities for parser development
 
-use anyhow::Result;
-use codeprism_core::ParseResult;
+use anyhow::{anyhow, Result};
+use codeprism_core::{ParseContext, ParseResult, ParserEngine};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
 
 /// AST diff analyzer for comparing parse results
 #[derive(Debug, Clone)]
@@ -52,6 +53,13 @@ pub enum DiffType {
         new_type: String,
         changes: Vec<String>,
     },
+    /// A node with the same name and kind at a different span
+    NodeMoved {
+        node_name: String,
+        node_type: String,
+        old_location: String,
+        new_location: String,
+    },
     EdgeAdded {
         source: String,
         target: String,
@@ -74,6 +82,22 @@ pub enum DiffType {
     },
 }
 
+impl DiffType {
+    /// Snake-case name of the difference type, such as `node_added`
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffType::NodeAdded { .. } => "node_added",
+            DiffType::NodeRemoved { .. } => "node_removed",
+            DiffType::NodeModified { .. } => "node_modified",
+            DiffType::NodeMoved { .. } => "node_moved",
+            DiffType::EdgeAdded { .. } => "edge_added",
+            DiffType::EdgeRemoved { .. } => "edge_removed",
+            DiffType::EdgeModified { .. } => "edge_modified",
+            DiffType::StructuralChange { .. } => "structural_change",
+        }
+    }
+}
+
 /// Impact level of structural changes
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum StructuralImpact {
@@ -99,6 +123,7 @@ pub struct DiffStatistics {
     pub nodes_added: usize,
     pub nodes_removed: usize,
     pub nodes_modified: usize,
+    pub nodes_moved: usize,
     pub edges_added: usize,
     pub edges_removed: usize,
     pub edges_modified: usize,
@@ -154,12 +179,12 @@ impl AstDiff {
         // Determine if this is a significant change
         let is_significant_change = similarity_score < self.config.similarity_threshold;
 
-        // Generate summary
-        let summary = self.generate_summary(&statistics, similarity_score);
-
         statistics.total_differences = differences.len();
         statistics.similarity_percentage = similarity_score * 100.0;
 
+        // Generate summary
+        let summary = self.generate_summary(&statistics, similarity_score);
+
         Ok(DiffReport {
             differences,
             statistics,
@@ -169,6 +194,188 @@ impl AstDiff {
         })
     }
 
+    /// Parse two versions of a file and compare them
+    ///
+    /// The language is picked from the extension of `file_path`. Neither
+    /// tree is cached by `engine`, so an old revision never seeds an
+    /// incremental parse of the current file.
+    pub fn compare_sources(
+        &self,
+        engine: &ParserEngine,
+        file_path: &Path,
+        old_source: &str,
+        new_source: &str,
+    ) -> Result<DiffReport> {
+        let parse = |source: &str| {
+            let context = ParseContext::new(
+                "diff".to_string(),
+                file_path.to_path_buf(),
+                source.to_string(),
+            );
+            engine
+                .parse_detached(&context)
+                .map_err(|e| anyhow!("Failed to parse {}: {}", file_path.display(), e))
+        };
+        let old_result = parse(old_source)?;
+        let new_result = parse(new_source)?;
+        self.compare(&old_result, &new_result, new_source)
+    }
+
+    /// Compare two syntax subtrees, such as two function definitions
+    ///
+    /// Children are aligned by their source text, so an inserted or deleted
+    /// statement does not shift every statement after it, and differences are
+    /// reported at statement granularity. The roots' `name` fields are not
+    /// compared, so differently named functions can be diffed. The similarity
+    /// score is the share of syntax nodes in unchanged subtrees.
+    pub fn compare_subtrees(
+        &self,
+        old_node: tree_sitter::Node,
+        old_source: &str,
+        new_node: tree_sitter::Node,
+        new_source: &str,
+    ) -> DiffReport {
+        let old = SubtreeSide {
+            root: old_node,
+            source: old_source,
+        };
+        let new = SubtreeSide {
+            root: new_node,
+            source: new_source,
+        };
+        let mut differences = Vec::new();
+        let mut statistics = DiffStatistics::default();
+        let mut matched = 0;
+
+        self.compare_subtree_pair(
+            &old,
+            old_node,
+            &new,
+            new_node,
+            &mut differences,
+            &mut statistics,
+            &mut matched,
+        );
+
+        let total = count_named_nodes(old_node).max(count_named_nodes(new_node));
+        let similarity_score = if total == 0 {
+            1.0
+        } else {
+            (matched as f64 / total as f64).min(1.0)
+        };
+
+        statistics.total_differences = differences.len();
+        statistics.similarity_percentage = similarity_score * 100.0;
+        let summary = self.generate_summary(&statistics, similarity_score);
+
+        DiffReport {
+            differences,
+            statistics,
+            similarity_score,
+            is_significant_change: similarity_score < self.config.similarity_threshold,
+            summary,
+        }
+    }
+
+    /// Diff two aligned nodes of the same kind
+    #[allow(clippy::too_many_arguments)]
+    fn compare_subtree_pair(
+        &self,
+        old: &SubtreeSide,
+        old_node: tree_sitter::Node,
+        new: &SubtreeSide,
+        new_node: tree_sitter::Node,
+        differences: &mut Vec<DiffType>,
+        statistics: &mut DiffStatistics,
+        matched: &mut usize,
+    ) {
+        let is_root = old_node == old.root;
+        if !is_root && old.text(old_node) == new.text(new_node) {
+            *matched += count_named_nodes(old_node);
+            return;
+        }
+
+        let old_children = old.children(old_node);
+        let new_children = new.children(new_node);
+        let is_leaf_statement = is_statement(&old_node) && !contains_block(&old_node);
+        if !is_root && (is_leaf_statement || old_children.is_empty() || new_children.is_empty()) {
+            differences.push(DiffType::NodeModified {
+                node_name: old.text(old_node),
+                old_type: old_node.kind().to_string(),
+                new_type: new_node.kind().to_string(),
+                changes: vec![
+                    format!(
+                        "Text changed: '{}' -> '{}'",
+                        old.text(old_node),
+                        new.text(new_node)
+                    ),
+                    format!(
+                        "Lines {} -> {}",
+                        line_range(&old_node),
+                        line_range(&new_node)
+                    ),
+                ],
+            });
+            statistics.nodes_modified += 1;
+            return;
+        }
+
+        *matched += 1;
+        if is_root {
+            // The ignored name counts as matched
+            if let Some(name) = old_node.child_by_field_name("name") {
+                *matched += count_named_nodes(name);
+            }
+        }
+
+        let old_texts: Vec<String> = old_children.iter().map(|n| old.text(*n)).collect();
+        let new_texts: Vec<String> = new_children.iter().map(|n| new.text(*n)).collect();
+        let mut pairs = longest_common_subsequence(&old_texts, &new_texts);
+        pairs.push((old_children.len(), new_children.len()));
+
+        let (mut old_start, mut new_start) = (0, 0);
+        for (old_end, new_end) in pairs {
+            let old_gap = &old_children[old_start..old_end];
+            let new_gap = &new_children[new_start..new_end];
+            for i in 0..old_gap.len().max(new_gap.len()) {
+                match (old_gap.get(i), new_gap.get(i)) {
+                    (Some(o), Some(n)) if o.kind() == n.kind() => self.compare_subtree_pair(
+                        old,
+                        *o,
+                        new,
+                        *n,
+                        differences,
+                        statistics,
+                        matched,
+                    ),
+                    (o, n) => {
+                        if let Some(o) = o {
+                            differences.push(DiffType::NodeRemoved {
+                                node_name: old.text(*o),
+                                node_type: o.kind().to_string(),
+                                location: Some(line_range(o)),
+                            });
+                            statistics.nodes_removed += 1;
+                        }
+                        if let Some(n) = n {
+                            differences.push(DiffType::NodeAdded {
+                                node_name: new.text(*n),
+                                node_type: n.kind().to_string(),
+                                location: Some(line_range(n)),
+                            });
+                            statistics.nodes_added += 1;
+                        }
+                    }
+                }
+            }
+            if old_end < old_children.len() {
+                *matched += count_named_nodes(old_children[old_end]);
+            }
+            old_start = old_end + 1;
+            new_start = new_end + 1;
+        }
+    }
+
     /// Compare node lists
     fn compare_nodes(
         &self,
@@ -191,28 +398,57 @@ impl AstDiff {
         let old_keys: HashSet<_> = old_node_map.keys().collect();
         let new_keys: HashSet<_> = new_node_map.keys().collect();
 
+        let by_position = |nodes: &mut Vec<&codeprism_core::Node>| {
+            nodes.sort_by_key(|n| (n.span.start_byte, n.span.end_byte, n.name.clone()))
+        };
+        let mut added: Vec<_> = new_keys
+            .difference(&old_keys)
+            .filter_map(|key| new_node_map.get(*key).copied())
+            .collect();
+        let mut removed: Vec<_> = old_keys
+            .difference(&new_keys)
+            .filter_map(|key| old_node_map.get(*key).copied())
+            .collect();
+        by_position(&mut added);
+        by_position(&mut removed);
+
+        // A removed node with an added counterpart of the same name and kind moved
+        removed.retain(|old_node| {
+            let Some(index) = added
+                .iter()
+                .position(|n| n.name == old_node.name && n.kind == old_node.kind)
+            else {
+                return true;
+            };
+            let new_node = added.remove(index);
+            differences.push(DiffType::NodeMoved {
+                node_name: old_node.name.clone(),
+                node_type: format!("{:?}", old_node.kind),
+                old_location: format!("{}:{}", old_node.span.start_byte, old_node.span.end_byte),
+                new_location: format!("{}:{}", new_node.span.start_byte, new_node.span.end_byte),
+            });
+            statistics.nodes_moved += 1;
+            false
+        });
+
         // Find added nodes
-        for key in new_keys.difference(&old_keys) {
-            if let Some(node) = new_node_map.get(*key) {
-                differences.push(DiffType::NodeAdded {
-                    node_name: node.name.clone(),
-                    node_type: format!("{:?}", node.kind),
-                    location: Some(format!("{}:{}", node.span.start_byte, node.span.end_byte)),
-                });
-                statistics.nodes_added += 1;
-            }
+        for node in added {
+            differences.push(DiffType::NodeAdded {
+                node_name: node.name.clone(),
+                node_type: format!("{:?}", node.kind),
+                location: Some(format!("{}:{}", node.span.start_byte, node.span.end_byte)),
+            });
+            statistics.nodes_added += 1;
         }
 
         // Find removed nodes
-        for key in old_keys.difference(&new_keys) {
-            if let Some(node) = old_node_map.get(*key) {
-                differences.push(DiffType::NodeRemoved {
-                    node_name: node.name.clone(),
-                    node_type: format!("{:?}", node.kind),
-                    location: Some(format!("{}:{}", node.span.start_byte, node.span.end_byte)),
-                });
-                statistics.nodes_removed += 1;
-            }
+        for node in removed {
+            differences.push(DiffType::NodeRemoved {
+                node_name: node.name.clone(),
+                node_type: format!("{:?}", node.kind),
+                location: Some(format!("{}:{}", node.span.start_byte, node.span.end_byte)),
+            });
+            statistics.nodes_removed += 1;
         }
 
         // Find modified nodes (same key but different properties)
@@ -419,6 +655,7 @@ impl AstDiff {
         let total_changes = statistics.nodes_added
             + statistics.nodes_removed
             + statistics.nodes_modified
+            + statistics.nodes_moved
             + statistics.edges_added
             + statistics.edges_removed
             + statistics.edges_modified;
@@ -450,6 +687,9 @@ impl AstDiff {
         if statistics.nodes_modified > 0 {
             parts.push(format!("{} nodes modified", statistics.nodes_modified));
         }
+        if statistics.nodes_moved > 0 {
+            parts.push(format!("{} nodes moved", statistics.nodes_moved));
+        }
         if statistics.edges_added > 0 {
             parts.push(format!("{} edges added", statistics.edges_added));
         }
@@ -475,7 +715,159 @@ impl Default for AstDiff {
     }
 }
 
+/// Read a file as it exists at a git revision, with `git show`
+///
+/// The revision is resolved in the repository containing the file, and the
+/// path is taken relative to the file's directory, so `file_path` may be
+/// absolute or relative to the current directory.
+pub fn read_file_at_revision(file_path: &Path, revision: &str) -> Result<String> {
+    use std::process::Command;
+
+    // `git show` would read a leading dash as an option
+    if revision.starts_with('-') {
+        return Err(anyhow!("Invalid revision: {}", revision));
+    }
+    let file_name = file_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("Not a file path: {}", file_path.display()))?;
+    let directory = match file_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .arg("show")
+        .arg(format!("{revision}:./{file_name}"))
+        .output()
+        .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git show {}:{} failed: {}",
+            revision,
+            file_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// One side of a subtree comparison
+struct SubtreeSide<'a> {
+    root: tree_sitter::Node<'a>,
+    source: &'a str,
+}
+
+impl<'a> SubtreeSide<'a> {
+    /// Source text of a node with whitespace runs collapsed
+    fn text(&self, node: tree_sitter::Node) -> String {
+        self.source
+            .get(node.byte_range())
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Named children to align, skipping the root's name
+    fn children(&self, node: tree_sitter::Node<'a>) -> Vec<tree_sitter::Node<'a>> {
+        let name = if node == self.root {
+            node.child_by_field_name("name")
+        } else {
+            None
+        };
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .filter(|child| Some(*child) != name)
+            .collect()
+    }
+}
+
+/// Node kinds holding a sequence of statements
+const BLOCK_KINDS: &[&str] = &[
+    "block",
+    "statement_block",
+    "compound_statement",
+    "body_statement",
+    "declaration_list",
+    "class_body",
+    "function_body",
+    "statements",
+];
+
+/// Whether a node is a statement within a block
+fn is_statement(node: &tree_sitter::Node) -> bool {
+    node.kind().ends_with("_statement")
+        || node
+            .parent()
+            .is_some_and(|parent| BLOCK_KINDS.contains(&parent.kind()))
+}
+
+/// Whether a node contains a block, like an `if` or a loop
+fn contains_block(node: &tree_sitter::Node) -> bool {
+    let mut cursor = node.walk();
+    let children: Vec<_> = node.named_children(&mut cursor).collect();
+    children
+        .iter()
+        .any(|child| BLOCK_KINDS.contains(&child.kind()) || contains_block(child))
+}
+
+fn count_named_nodes(node: tree_sitter::Node) -> usize {
+    let mut cursor = node.walk();
+    let children: Vec<_> = node.named_children(&mut cursor).collect();
+    1 + children.into_iter().map(count_named_nodes).sum::<usize>()
+}
+
+/// 1-based line range of a node, as `start-end`
+fn line_range(node: &tree_sitter::Node) -> String {
+    format!(
+        "{}-{}",
+        node.start_position().row + 1,
+        node.end_position().row + 1
+    )
+}
+
+/// Index pairs of a longest common subsequence of two sequences
+fn longest_common_subsequence(old: &[String], new: &[String]) -> Vec<(usize, usize)> {
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
 impl DiffReport {
+    /// Number of differences of each type, keyed by [`DiffType::label`]
+    pub fn counts_by_type(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for difference in &self.differences {
+            *counts.entry(difference.label()).or_insert(0) += 1;
+        }
+        counts
+    }
+
     /// Format the diff report for display
     pub fn format_report(&self) -> String {
         let mut output = String::new();
@@ -512,6 +904,7 @@ impl DiffReport {
             "- Nodes modified: {}\n",
             self.statistics.nodes_modified
         ));
+        output.push_str(&format!("- Nodes moved: {}\n", self.statistics.nodes_moved));
         output.push_str(&format!("- Edges added: {}\n", self.statistics.edges_added));
         output.push_str(&format!(
             "- Edges removed: {}\n",
@@ -570,6 +963,16 @@ impl DiffReport {
                     changes.join(", ")
                 )
             }
+            DiffType::NodeMoved {
+                node_name,
+                node_type,
+                old_location,
+                new_location,
+            } => {
+                format!(
+                    "Moved node '{node_name}' ({node_type}) from {old_location} to {new_location}"
+                )
+            }
             DiffType::EdgeAdded {
                 source,
                 target,
@@ -657,4 +1060,155 @@ mod tests {
 
         assert_eq!(key1, key2); // Should be same since IDs are ignored
     }
+
+    #[test]
+    fn test_compare_subtrees_reports_single_changed_statement() {
+        let source = "def load(path):\n    data = read(path)\n    rows = parse(data)\n    limit = 10\n    rows.sort()\n    return rows[:limit]\n\n\ndef load_more(path):\n    data = read(path)\n    rows = parse(data)\n    limit = 20\n    rows.sort()\n    return rows[:limit]\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let root = tree.root_node();
+        let (first, second) = (root.named_child(0).unwrap(), root.named_child(1).unwrap());
+
+        let report = AstDiff::new().compare_subtrees(first, source, second, source);
+
+        assert_eq!(report.differences.len(), 1, "{:?}", report.differences);
+        match &report.differences[0] {
+            DiffType::NodeModified {
+                node_name,
+                old_type,
+                changes,
+                ..
+            } => {
+                assert_eq!(node_name, "limit = 10");
+                assert_eq!(old_type, "expression_statement");
+                assert!(changes.contains(&"Lines 4-4 -> 12-12".to_string()));
+            }
+            other => panic!("unexpected difference {other:?}"),
+        }
+        assert!(
+            report.similarity_score > 0.8 && report.similarity_score < 1.0,
+            "similarity was {}",
+            report.similarity_score
+        );
+        assert!(!report.is_significant_change);
+    }
+
+    /// Python parser producing one node per function definition
+    struct FunctionParser;
+
+    impl codeprism_core::LanguageParser for FunctionParser {
+        fn language(&self) -> codeprism_core::Language {
+            codeprism_core::Language::Python
+        }
+
+        fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+            let mut parser = tree_sitter::Parser::new();
+            parser
+                .set_language(&tree_sitter_python::LANGUAGE.into())
+                .unwrap();
+            let tree = parser.parse(&context.content, None).unwrap();
+            let root = tree.root_node();
+            let mut cursor = root.walk();
+            let nodes: Vec<_> = root
+                .named_children(&mut cursor)
+                .filter_map(|function| {
+                    let name = function.child_by_field_name("name")?;
+                    Some(codeprism_core::Node::new(
+                        &context.repo_id,
+                        NodeKind::Function,
+                        context.content[name.byte_range()].to_string(),
+                        codeprism_core::Language::Python,
+                        context.file_path.clone(),
+                        Span::new(
+                            function.start_byte(),
+                            function.end_byte(),
+                            function.start_position().row + 1,
+                            function.end_position().row + 1,
+                            function.start_position().column + 1,
+                            function.end_position().column + 1,
+                        ),
+                    ))
+                })
+                .collect();
+            drop(cursor);
+            Ok(ParseResult {
+                tree,
+                nodes,
+                edges: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_compare_sources_classifies_added_removed_and_moved_nodes() {
+        let registry = std::sync::Arc::new(codeprism_core::LanguageRegistry::new());
+        registry.register(std::sync::Arc::new(FunctionParser));
+        let engine = ParserEngine::new(registry);
+
+        let old_source = "def keep():\n    pass\n\n\ndef gone():\n    pass\n";
+        let new_source = "def added():\n    pass\n\n\ndef keep():\n    pass\n";
+        let report = AstDiff::new()
+            .compare_sources(&engine, Path::new("app.py"), old_source, new_source)
+            .unwrap();
+
+        let counts = report.counts_by_type();
+        assert_eq!(counts.get("node_moved"), Some(&1));
+        assert_eq!(counts.get("node_added"), Some(&1));
+        assert_eq!(counts.get("node_removed"), Some(&1));
+        assert_eq!(report.statistics.nodes_moved, 1);
+        assert!(report.differences.iter().any(|difference| matches!(
+            difference,
+            DiffType::NodeMoved { node_name, old_location, .. }
+                if node_name == "keep" && old_location == "0:20"
+        )));
+        assert!(
+            report.summary.contains("1 nodes moved"),
+            "{}",
+            report.summary
+        );
+
+        let unchanged = AstDiff::new()
+            .compare_sources(&engine, Path::new("app.py"), old_source, old_source)
+            .unwrap();
+        assert!(unchanged.differences.is_empty());
+        assert!(AstDiff::new()
+            .compare_sources(&engine, Path::new("app.rb"), old_source, new_source)
+            .is_err());
+    }
+
+    #[test]
+    fn test_read_file_at_revision() {
+        use std::process::Command;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+
+        git(&["init", "-q"]);
+        std::fs::create_dir(dir.join("src")).unwrap();
+        let file = dir.join("src/app.py");
+        std::fs::write(&file, "first\n").unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "first"]);
+        std::fs::write(&file, "second\n").unwrap();
+        git(&["commit", "-q", "-am", "second"]);
+
+        assert_eq!(read_file_at_revision(&file, "HEAD~1").unwrap(), "first\n");
+        assert_eq!(read_file_at_revision(&file, "HEAD").unwrap(), "second\n");
+        assert!(read_file_at_revision(&file, "no-such-revision").is_err());
+        let error = read_file_at_revision(&file, "--output=/tmp/out").unwrap_err();
+        assert!(error.to_string().contains("Invalid revision"));
+    }
 }
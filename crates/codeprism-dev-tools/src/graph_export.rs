@@ -0,0 +1,301 @@
+//! Graph export for external graph tools
+//!
+//! Produces GraphML for Gephi and yEd, and Cytoscape JSON for Cytoscape and
+//! cytoscape.js. Nodes carry their kind, name, file and span as attributes;
+//! edges carry their kind.
+
+use anyhow::{bail, Result};
+use codeprism_core::{Edge, Node};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Output format of a graph export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphExportFormat {
+    /// GraphML XML
+    GraphMl,
+    /// Cytoscape elements JSON
+    CytoscapeJson,
+}
+
+impl FromStr for GraphExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "graphml" => Ok(Self::GraphMl),
+            "cytoscape" | "cytoscape_json" | "cyjs" => Ok(Self::CytoscapeJson),
+            other => bail!("Unknown graph export format '{other}'. Use 'graphml' or 'cytoscape'"),
+        }
+    }
+}
+
+/// Node attributes as (GraphML key, value) pairs
+const NODE_ATTRIBUTES: &[(&str, &str)] = &[
+    ("name", "string"),
+    ("kind", "string"),
+    ("file", "string"),
+    ("language", "string"),
+    ("start_line", "int"),
+    ("end_line", "int"),
+    ("start_column", "int"),
+    ("end_column", "int"),
+    ("start_byte", "int"),
+    ("end_byte", "int"),
+];
+
+/// Exporter for code graphs in formats read by graph analysis tools
+#[derive(Debug, Clone)]
+pub struct GraphExporter {
+    graph_name: String,
+}
+
+impl GraphExporter {
+    /// Create an exporter naming the graph `codeprism`
+    pub fn new() -> Self {
+        Self::with_name("codeprism")
+    }
+
+    /// Create an exporter with a graph name
+    pub fn with_name(graph_name: impl Into<String>) -> Self {
+        Self {
+            graph_name: graph_name.into(),
+        }
+    }
+
+    /// Export nodes and edges in the given format
+    ///
+    /// Edges whose source or target is not among `nodes` are dropped, since
+    /// both formats require edge endpoints to be declared nodes.
+    pub fn export(&self, nodes: &[Node], edges: &[Edge], format: GraphExportFormat) -> String {
+        match format {
+            GraphExportFormat::GraphMl => self.to_graphml(nodes, edges),
+            GraphExportFormat::CytoscapeJson => self.to_cytoscape_json(nodes, edges),
+        }
+    }
+
+    /// Export as a GraphML document
+    pub fn to_graphml(&self, nodes: &[Node], edges: &[Edge]) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+             http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n",
+        );
+        // Gephi shows the `label` attribute as the node label
+        xml.push_str(
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        );
+        for (key, attr_type) in NODE_ATTRIBUTES {
+            let _ = writeln!(
+                xml,
+                "  <key id=\"{key}\" for=\"node\" attr.name=\"{key}\" attr.type=\"{attr_type}\"/>"
+            );
+        }
+        xml.push_str(
+            "  <key id=\"edge_kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+        );
+        let _ = writeln!(
+            xml,
+            "  <graph id=\"{}\" edgedefault=\"directed\">",
+            escape_xml(&self.graph_name)
+        );
+
+        for node in nodes {
+            let _ = writeln!(xml, "    <node id=\"{}\">", node.id.to_hex());
+            let _ = writeln!(
+                xml,
+                "      <data key=\"label\">{}</data>",
+                escape_xml(&node.name)
+            );
+            for (key, value) in node_attributes(node) {
+                let text = match value {
+                    serde_json::Value::String(text) => text,
+                    other => other.to_string(),
+                };
+                let _ = writeln!(
+                    xml,
+                    "      <data key=\"{key}\">{}</data>",
+                    escape_xml(&text)
+                );
+            }
+            xml.push_str("    </node>\n");
+        }
+
+        for (index, edge) in connected_edges(nodes, edges).into_iter().enumerate() {
+            let _ = writeln!(
+                xml,
+                "    <edge id=\"e{index}\" source=\"{}\" target=\"{}\">",
+                edge.source.to_hex(),
+                edge.target.to_hex()
+            );
+            let _ = writeln!(xml, "      <data key=\"edge_kind\">{:?}</data>", edge.kind);
+            xml.push_str("    </edge>\n");
+        }
+
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+
+    /// Export as Cytoscape JSON (`{"data": ..., "elements": {"nodes", "edges"}}`)
+    pub fn to_cytoscape_json(&self, nodes: &[Node], edges: &[Edge]) -> String {
+        let nodes_json: Vec<_> = nodes
+            .iter()
+            .map(|node| {
+                let mut data = serde_json::Map::new();
+                data.insert("id".to_string(), node.id.to_hex().into());
+                for (key, value) in node_attributes(node) {
+                    data.insert(key.to_string(), value);
+                }
+                serde_json::json!({ "data": data })
+            })
+            .collect();
+        let edges_json: Vec<_> = connected_edges(nodes, edges)
+            .into_iter()
+            .enumerate()
+            .map(|(index, edge)| {
+                serde_json::json!({
+                    "data": {
+                        "id": format!("e{index}"),
+                        "source": edge.source.to_hex(),
+                        "target": edge.target.to_hex(),
+                        "kind": format!("{:?}", edge.kind)
+                    }
+                })
+            })
+            .collect();
+
+        let document = serde_json::json!({
+            "format_version": "1.0",
+            "generated_by": "codeprism",
+            "data": { "name": self.graph_name },
+            "elements": { "nodes": nodes_json, "edges": edges_json }
+        });
+        serde_json::to_string_pretty(&document).unwrap_or_default()
+    }
+}
+
+impl Default for GraphExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Attribute values of a node, in [`NODE_ATTRIBUTES`] order
+fn node_attributes(node: &Node) -> Vec<(&'static str, serde_json::Value)> {
+    vec![
+        ("name", node.name.clone().into()),
+        ("kind", format!("{:?}", node.kind).into()),
+        ("file", node.file.display().to_string().into()),
+        ("language", format!("{:?}", node.lang).into()),
+        ("start_line", node.span.start_line.into()),
+        ("end_line", node.span.end_line.into()),
+        ("start_column", node.span.start_column.into()),
+        ("end_column", node.span.end_column.into()),
+        ("start_byte", node.span.start_byte.into()),
+        ("end_byte", node.span.end_byte.into()),
+    ]
+}
+
+/// Edges with both endpoints among `nodes`
+fn connected_edges<'a>(nodes: &[Node], edges: &'a [Edge]) -> Vec<&'a Edge> {
+    let ids: HashSet<_> = nodes.iter().map(|node| node.id).collect();
+    edges
+        .iter()
+        .filter(|edge| ids.contains(&edge.source) && ids.contains(&edge.target))
+        .collect()
+}
+
+/// Escape text for XML content and attribute values
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::{EdgeKind, Language, NodeId, NodeKind, Span};
+    use std::path::PathBuf;
+
+    fn node(name: &str, kind: NodeKind, line: usize) -> Node {
+        let file = PathBuf::from("src/ops.cpp");
+        let span = Span::new(line * 10, line * 10 + 8, line, line, 1, 9);
+        Node {
+            id: NodeId::new("test_repo", &file, &span, &kind),
+            kind,
+            name: name.to_string(),
+            file,
+            span,
+            lang: Language::Rust,
+            metadata: Default::default(),
+            signature: Default::default(),
+        }
+    }
+
+    fn graph() -> (Vec<Node>, Vec<Edge>) {
+        let caller = node("operator<<", NodeKind::Function, 1);
+        let callee = node("Vec<T> & \"quoted\"", NodeKind::Function, 2);
+        let missing = node("missing", NodeKind::Function, 3);
+        let edges = vec![
+            Edge::new(caller.id, callee.id, EdgeKind::Calls),
+            Edge::new(caller.id, missing.id, EdgeKind::Calls),
+        ];
+        (vec![caller, callee], edges)
+    }
+
+    #[test]
+    fn test_graphml_escapes_names_and_keeps_attributes() {
+        let (nodes, edges) = graph();
+        let xml = GraphExporter::new().export(&nodes, &edges, GraphExportFormat::GraphMl);
+
+        assert!(xml.contains("<data key=\"label\">operator&lt;&lt;</data>"));
+        assert!(xml.contains("<data key=\"name\">Vec&lt;T&gt; &amp; &quot;quoted&quot;</data>"));
+        assert!(!xml.contains("operator<<"));
+        assert!(xml.contains("<data key=\"kind\">Function</data>"));
+        assert!(xml.contains("<data key=\"file\">src/ops.cpp</data>"));
+        assert!(xml.contains("<data key=\"start_line\">2</data>"));
+        assert!(xml.contains("<data key=\"edge_kind\">Calls</data>"));
+        // The edge to a node outside the export is dropped
+        assert_eq!(xml.matches("<edge ").count(), 1);
+    }
+
+    #[test]
+    fn test_cytoscape_json_lists_elements() {
+        let (nodes, edges) = graph();
+        let json: serde_json::Value = serde_json::from_str(
+            &GraphExporter::with_name("demo").export(&nodes, &edges, "cytoscape".parse().unwrap()),
+        )
+        .unwrap();
+
+        assert_eq!(json["data"]["name"], "demo");
+        let exported_nodes = json["elements"]["nodes"].as_array().unwrap();
+        assert_eq!(exported_nodes.len(), 2);
+        assert_eq!(exported_nodes[0]["data"]["id"], nodes[0].id.to_hex());
+        assert_eq!(exported_nodes[1]["data"]["name"], "Vec<T> & \"quoted\"");
+        assert_eq!(exported_nodes[1]["data"]["end_byte"], 28);
+        let exported_edges = json["elements"]["edges"].as_array().unwrap();
+        assert_eq!(exported_edges.len(), 1);
+        assert_eq!(exported_edges[0]["data"]["source"], nodes[0].id.to_hex());
+        assert_eq!(exported_edges[0]["data"]["target"], nodes[1].id.to_hex());
+        assert_eq!(exported_edges[0]["data"]["kind"], "Calls");
+    }
+
+    #[test]
+    fn test_unknown_format_is_rejected() {
+        assert!("dot".parse::<GraphExportFormat>().is_err());
+    }
+}
@@ -10,6 +10,7 @@
 //! - **Development REPL**: Interactive parser development environment
 //! - **Performance Profiling**: Real-time parsing performance metrics
 //! - **Diff Comparison**: Compare AST changes between parser versions
+//! - **Graph Export**: Export code graphs as GraphML and Cytoscape JSON
 //!
 //! # Example
 //!
@@ -31,6 +32,7 @@ use std::path::Path;
 pub mod ast_visualizer;
 pub mod dev_repl;
 pub mod diff_comparison;
+pub mod graph_export;
 pub mod graphviz_export;
 pub mod parser_validator;
 pub mod performance_profiler;
@@ -38,7 +40,8 @@ pub mod performance_profiler;
 // Re-export main types for convenience
 pub use ast_visualizer::{AstVisualizer, VisualizationFormat};
 pub use dev_repl::{DevRepl, ReplCommand, ReplResult};
-pub use diff_comparison::{AstDiff, DiffReport, DiffType};
+pub use diff_comparison::{read_file_at_revision, AstDiff, DiffReport, DiffType};
+pub use graph_export::{GraphExportFormat, GraphExporter};
 pub use graphviz_export::{EdgeStyle, GraphVizExporter, GraphVizOptions, NodeStyle};
 pub use parser_validator::{ParserValidator, ValidationError, ValidationReport};
 pub use performance_profiler::{MetricType, PerformanceProfiler, ProfilingReport};
@@ -0,0 +1,77 @@
+//! Adapter to integrate C/C++ parser with codeprism
+
+use crate::parser::{CppParser, ParseContext as CppParseContext};
+use crate::types as cpp_types;
+
+/// Adapter that implements codeprism's LanguageParser trait
+pub struct CppLanguageParser {
+    parser: std::sync::Mutex<CppParser>,
+}
+
+impl CppLanguageParser {
+    /// Create a new C/C++ language parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: std::sync::Mutex::new(CppParser::new()),
+        }
+    }
+
+    /// Create an adapter that skips subtrees deeper than `max_depth` levels
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        let mut parser = CppParser::new();
+        parser.set_max_depth(max_depth);
+        Self {
+            parser: std::sync::Mutex::new(parser),
+        }
+    }
+}
+
+impl Default for CppLanguageParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Since we can't import codeprism types directly, we'll need to define a conversion
+// trait that the caller can implement
+pub trait ParseResultConverter {
+    type Node;
+    type Edge;
+    type ParseResult;
+
+    fn convert_node(node: cpp_types::Node) -> Self::Node;
+    fn convert_edge(edge: cpp_types::Edge) -> Self::Edge;
+    fn create_parse_result(
+        tree: tree_sitter::Tree,
+        nodes: Vec<Self::Node>,
+        edges: Vec<Self::Edge>,
+    ) -> Self::ParseResult;
+}
+
+/// Parse a file and return the result in our internal types
+pub fn parse_file(
+    parser: &CppLanguageParser,
+    repo_id: &str,
+    file_path: std::path::PathBuf,
+    content: String,
+    old_tree: Option<tree_sitter::Tree>,
+) -> Result<
+    (
+        tree_sitter::Tree,
+        Vec<cpp_types::Node>,
+        Vec<cpp_types::Edge>,
+    ),
+    crate::error::Error,
+> {
+    let context = CppParseContext {
+        repo_id: repo_id.to_string(),
+        file_path,
+        old_tree,
+        content,
+    };
+
+    let mut parser = parser.parser.lock().unwrap();
+    let result = parser.parse(&context)?;
+
+    Ok((result.tree, result.nodes, result.edges))
+}
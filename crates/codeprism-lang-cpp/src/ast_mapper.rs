@@ -0,0 +1,722 @@
+//! AST mapping from tree-sitter C/C++ CST to Universal AST
+
+use crate::error::Result;
+use crate::types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Component, PathBuf};
+use tree_sitter::{Node as TSNode, Tree};
+
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// Declarators wrapping the `function_declarator` of a definition, as in
+/// `int *make(void)` or `Widget &get()`
+const WRAPPING_DECLARATORS: &[&str] = &[
+    "pointer_declarator",
+    "reference_declarator",
+    "parenthesized_declarator",
+    "attributed_declarator",
+];
+
+/// Maps tree-sitter C/C++ CST to Universal AST
+pub struct AstMapper {
+    /// Repository ID
+    repo_id: String,
+    /// File path
+    file_path: PathBuf,
+    /// Language
+    language: Language,
+    /// Source content
+    content: String,
+    /// Collected nodes
+    nodes: Vec<Node>,
+    /// Collected edges
+    edges: Vec<Edge>,
+    /// Node ID mappings (tree-sitter node ID -> Universal AST node ID)
+    node_mappings: HashMap<usize, NodeId>,
+    /// Classes, structs, unions and enums declared in the file, by name
+    types: HashMap<String, NodeId>,
+    /// Base classes named by each declaration, linked once all types are known
+    bases: Vec<(NodeId, String)>,
+    /// Macro guarding the whole file against repeated inclusion
+    include_guard: Option<String>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
+}
+
+impl AstMapper {
+    /// Create a new AST mapper
+    pub fn new(repo_id: &str, file_path: PathBuf, language: Language, content: &str) -> Self {
+        Self {
+            repo_id: repo_id.to_string(),
+            file_path,
+            language,
+            content: content.to_string(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_mappings: HashMap::new(),
+            types: HashMap::new(),
+            bases: Vec::new(),
+            include_guard: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Extract nodes and edges from the tree
+    pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
+        let root = tree.root_node();
+
+        // Create module node for the file
+        let module_span = Span::from_node(&root);
+        let file_name = self
+            .file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        self.include_guard = self.find_include_guard(&root);
+        let module_node = Node::new(
+            &self.repo_id,
+            NodeKind::Module,
+            file_name,
+            self.language,
+            self.file_path.clone(),
+            module_span,
+        )
+        .with_metadata(json!({
+            "type": "translation_unit",
+            "file_path": self.file_path.display().to_string(),
+            "include_guard": self.include_guard
+        }));
+
+        let module_id = module_node.id;
+        self.nodes.push(module_node);
+        self.node_mappings.insert(root.id(), module_id);
+
+        // Process all child nodes
+        self.process_node(&root, Some(module_id), 0)?;
+
+        self.resolve_local_calls();
+        self.link_bases();
+        self.mark_entry_points();
+
+        self.record_depth_truncation();
+
+        Ok((self.nodes, self.edges))
+    }
+
+    /// Flag `main` functions with `"entry_point": true`
+    fn mark_entry_points(&mut self) {
+        for node in &mut self.nodes {
+            if node.kind == NodeKind::Function && node.name == "main" {
+                node.metadata["entry_point"] = json!(true);
+            }
+        }
+    }
+
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = json!({});
+            }
+            module.metadata["depth_truncation"] = json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
+    /// Process a tree-sitter node recursively
+    fn process_node(
+        &mut self,
+        ts_node: &TSNode,
+        parent_id: Option<NodeId>,
+        depth: usize,
+    ) -> Result<Option<NodeId>> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(None);
+        }
+
+        let universal_node = match ts_node.kind() {
+            "preproc_include" => self.process_include(ts_node)?,
+            "preproc_function_def" => self.process_macro(ts_node)?,
+            "namespace_definition" => self.process_namespace(ts_node)?,
+            "class_specifier" | "struct_specifier" | "union_specifier" | "enum_specifier" => {
+                self.process_type_declaration(ts_node)?
+            }
+            "function_definition" => self.process_function(ts_node)?,
+            "call_expression" | "new_expression" => self.process_call(ts_node)?,
+            _ => {
+                // For unhandled node types, still process children
+                None
+            }
+        };
+
+        // Add edge from parent to this node
+        if let (Some(parent), Some(node_id)) = (parent_id, &universal_node) {
+            let kind = match self.nodes.last().map(|node| node.kind) {
+                Some(NodeKind::Import) => EdgeKind::Imports,
+                Some(NodeKind::Call) => EdgeKind::Calls,
+                _ => EdgeKind::Contains,
+            };
+            self.edges.push(Edge::new(parent, *node_id, kind));
+        }
+
+        // Process children
+        let mut cursor = ts_node.walk();
+        for child in ts_node.children(&mut cursor) {
+            let child_parent = universal_node.or(parent_id);
+            self.process_node(&child, child_parent, depth + 1)?;
+        }
+
+        Ok(universal_node)
+    }
+
+    /// Get the text content of a node
+    fn node_text(&self, node: &TSNode) -> String {
+        node.utf8_text(self.content.as_bytes())
+            .unwrap_or("")
+            .to_string()
+    }
+
+    /// Get the text of a named field of a node
+    fn field_text(&self, node: &TSNode, field: &str) -> Option<String> {
+        node.child_by_field_name(field)
+            .filter(|n| !n.is_missing())
+            .map(|n| self.node_text(&n))
+    }
+
+    /// Push a node and remember its tree-sitter mapping
+    fn push_node(&mut self, ts_node: &TSNode, node: Node) -> NodeId {
+        let node_id = node.id;
+        self.nodes.push(node);
+        self.node_mappings.insert(ts_node.id(), node_id);
+        node_id
+    }
+
+    /// Last segment of a possibly qualified name, e.g. `Widget` for `ui::Widget<int>`
+    fn short_name(name: &str) -> &str {
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name).trim()
+    }
+
+    /// Collapse runs of whitespace, so multi-line signatures read on one line
+    fn single_line(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Macro of an `#ifndef X` / `#define X` pair wrapping the whole file
+    fn find_include_guard(&self, root: &TSNode) -> Option<String> {
+        let mut cursor = root.walk();
+        let top_level: Vec<_> = root
+            .named_children(&mut cursor)
+            .filter(|child| child.kind() != "comment")
+            .collect();
+        match top_level.as_slice() {
+            [guard] if self.is_include_guard(guard) => self.field_text(guard, "name"),
+            _ => None,
+        }
+    }
+
+    /// Whether a conditional is `#ifndef X` immediately followed by `#define X`
+    fn is_include_guard(&self, ts_node: &TSNode) -> bool {
+        if ts_node.kind() != "preproc_ifdef"
+            || ts_node.child(0).map(|token| token.kind()) != Some("#ifndef")
+            || ts_node.child_by_field_name("alternative").is_some()
+        {
+            return false;
+        }
+        let name = self.field_text(ts_node, "name");
+        let mut cursor = ts_node.walk();
+        let define = ts_node
+            .named_children(&mut cursor)
+            .filter(|child| child.kind() != "comment")
+            .nth(1);
+        define.is_some_and(|define| {
+            define.kind() == "preproc_def" && self.field_text(&define, "name") == name
+        })
+    }
+
+    /// Preprocessor conditions an `#include` sits under, outermost first
+    ///
+    /// Each condition is the directive selecting the branch, e.g.
+    /// `#ifdef _WIN32` or `#elif defined(__linux__)`; an `#else` branch also
+    /// names the directive that opened its chain. Include guards are skipped.
+    fn preprocessor_conditions(&self, ts_node: &TSNode) -> Vec<String> {
+        let mut conditions = Vec::new();
+        let mut child = *ts_node;
+        while let Some(parent) = child.parent() {
+            let is_alternative = parent
+                .child_by_field_name("alternative")
+                .is_some_and(|alternative| alternative.id() == child.id());
+            // A branch inside another's `alternative` is not under that branch's condition
+            if !is_alternative {
+                if let Some(condition) = self.branch_condition(&parent) {
+                    conditions.push(condition);
+                }
+            }
+            child = parent;
+        }
+        conditions.reverse();
+        conditions
+    }
+
+    /// The directive opening a conditional branch, `None` for other nodes
+    fn branch_condition(&self, ts_node: &TSNode) -> Option<String> {
+        let directive = || ts_node.child(0).map(|token| self.node_text(&token));
+        match ts_node.kind() {
+            "preproc_ifdef" if self.is_include_guard(ts_node) => None,
+            "preproc_ifdef" | "preproc_elifdef" => Some(format!(
+                "{} {}",
+                directive()?,
+                self.field_text(ts_node, "name")?
+            )),
+            "preproc_if" | "preproc_elif" => Some(format!(
+                "{} {}",
+                directive()?,
+                self.field_text(ts_node, "condition")?
+            )),
+            "preproc_else" => {
+                // Walk back up the chain of alternatives to the opening `#if`
+                let mut head = *ts_node;
+                while let Some(parent) = head.parent() {
+                    if !parent.kind().starts_with("preproc_") {
+                        break;
+                    }
+                    head = parent;
+                    if matches!(parent.kind(), "preproc_if" | "preproc_ifdef") {
+                        break;
+                    }
+                }
+                match self.branch_condition(&head) {
+                    Some(opening) if head.id() != ts_node.id() => {
+                        Some(format!("#else ({opening})"))
+                    }
+                    _ => Some("#else".to_string()),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolve a quoted include against the including file's directory
+    ///
+    /// Returns the normalized path only when a file exists there; system
+    /// includes and includes found through the compiler's search path stay
+    /// unresolved.
+    fn resolve_include(&self, include_path: &str) -> Option<PathBuf> {
+        let directory = self.file_path.parent()?;
+        let mut resolved = PathBuf::new();
+        for component in directory.join(include_path).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !resolved.pop() {
+                        resolved.push(component);
+                    }
+                }
+                other => resolved.push(other),
+            }
+        }
+        resolved.is_file().then_some(resolved)
+    }
+
+    /// Process an `#include` directive
+    fn process_include(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let Some(path) = ts_node.child_by_field_name("path") else {
+            return Ok(None);
+        };
+        let text = self.node_text(&path);
+        let (include_path, include_type) = match path.kind() {
+            "system_lib_string" => (text.trim_matches(['<', '>']).to_string(), "system"),
+            "string_literal" => (text.trim_matches('"').to_string(), "local"),
+            // `#include CONFIG_HEADER` names the header through a macro
+            _ => (text, "macro"),
+        };
+        let resolved_path = (include_type == "local")
+            .then(|| self.resolve_include(&include_path))
+            .flatten();
+        let conditions = self.preprocessor_conditions(ts_node);
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Import,
+            include_path.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "import_path": include_path,
+            "import_type": include_type,
+            "resolved_path": resolved_path.map(|path| path.display().to_string()),
+            "conditional": !conditions.is_empty(),
+            "conditions": conditions,
+            "type": "preproc_include"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process a function-like macro such as `#define SQUARE(x) ((x) * (x))`
+    ///
+    /// Macros have no core equivalent, so they become `Unknown` nodes that
+    /// calls through the macro can still link to.
+    fn process_macro(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let parameters = self
+            .field_text(ts_node, "parameters")
+            .unwrap_or_else(|| "()".to_string());
+        let body = self
+            .field_text(ts_node, "value")
+            .map(|body| body.trim().to_string());
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Unknown,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_signature(format!("#define {name}{parameters}"))
+        .with_metadata(json!({
+            "macro": true,
+            "macro_kind": "function",
+            "parameters": parameters,
+            "body": body,
+            "type": "preproc_function_def"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Names of the namespaces enclosing a node, outermost first
+    fn enclosing_namespaces(&self, ts_node: &TSNode) -> Vec<String> {
+        let mut namespaces = Vec::new();
+        let mut current = ts_node.parent();
+        while let Some(node) = current {
+            if node.kind() == "namespace_definition" {
+                if let Some(name) = self.field_text(&node, "name") {
+                    namespaces.push(name);
+                }
+            }
+            current = node.parent();
+        }
+        namespaces.reverse();
+        namespaces
+    }
+
+    /// `::`-joined path of the enclosing namespaces, `None` at file scope
+    fn namespace_path(&self, ts_node: &TSNode) -> Option<String> {
+        let namespaces = self.enclosing_namespaces(ts_node);
+        (!namespaces.is_empty()).then(|| namespaces.join("::"))
+    }
+
+    /// Name prefixed with its enclosing namespaces and class, e.g. `ui::Widget::draw`
+    fn qualified_name(&self, ts_node: &TSNode, class_name: Option<&str>, name: &str) -> String {
+        let mut segments = self.enclosing_namespaces(ts_node);
+        segments.extend(class_name.map(str::to_string));
+        segments.push(name.to_string());
+        segments.join("::")
+    }
+
+    /// Parameters of a `template <...>` declaration wrapping a node
+    fn template_parameters(&self, ts_node: &TSNode) -> Option<String> {
+        ts_node
+            .parent()
+            .filter(|parent| parent.kind() == "template_declaration")
+            .and_then(|template| self.field_text(&template, "parameters"))
+    }
+
+    /// Process a namespace definition
+    fn process_namespace(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self
+            .field_text(ts_node, "name")
+            .unwrap_or_else(|| "(anonymous)".to_string());
+        let namespace = self.qualified_name(ts_node, None, &name);
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Namespace,
+            name,
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "namespace": namespace,
+            "type": "namespace_definition"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Name of a type declaration, falling back to the `typedef` naming an
+    /// anonymous one as in `typedef struct { ... } pair_t;`
+    fn type_name(&self, ts_node: &TSNode) -> Option<String> {
+        self.field_text(ts_node, "name").or_else(|| {
+            ts_node
+                .parent()
+                .filter(|parent| parent.kind() == "type_definition")
+                .and_then(|typedef| self.field_text(&typedef, "declarator"))
+        })
+    }
+
+    /// Process class, struct, union and enum declarations with a body
+    ///
+    /// Forward declarations and elaborated type references such as
+    /// `struct node *next;` are skipped.
+    fn process_type_declaration(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        if ts_node.child_by_field_name("body").is_none() {
+            return Ok(None);
+        }
+        let Some(name) = self.type_name(ts_node) else {
+            return Ok(None);
+        };
+        let kind = match ts_node.kind() {
+            "class_specifier" => NodeKind::Class,
+            "enum_specifier" => NodeKind::Enum,
+            _ => NodeKind::Struct,
+        };
+        let keyword = ts_node.kind().trim_end_matches("_specifier");
+
+        let mut bases = Vec::new();
+        let mut cursor = ts_node.walk();
+        if let Some(clause) = ts_node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "base_class_clause")
+        {
+            let mut cursor = clause.walk();
+            bases.extend(
+                clause
+                    .named_children(&mut cursor)
+                    .filter(|child| {
+                        matches!(
+                            child.kind(),
+                            "type_identifier" | "qualified_identifier" | "template_type"
+                        )
+                    })
+                    .map(|child| Self::short_name(&self.node_text(&child)).to_string()),
+            );
+        }
+
+        let node = Node::new(
+            &self.repo_id,
+            kind,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "class_name": name,
+            "keyword": keyword,
+            "namespace": self.namespace_path(ts_node),
+            "qualified_name": self.qualified_name(ts_node, None, &name),
+            "extends": bases,
+            "template_parameters": self.template_parameters(ts_node),
+            "type": ts_node.kind()
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        self.types.entry(name).or_insert(node_id);
+        self.bases
+            .extend(bases.into_iter().map(|base| (node_id, base)));
+        Ok(Some(node_id))
+    }
+
+    /// The `function_declarator` of a definition, looking through pointer
+    /// and reference declarators
+    fn function_declarator<'a>(ts_node: &TSNode<'a>) -> Option<TSNode<'a>> {
+        let mut declarator = ts_node.child_by_field_name("declarator")?;
+        while WRAPPING_DECLARATORS.contains(&declarator.kind()) {
+            declarator = declarator.child_by_field_name("declarator")?;
+        }
+        (declarator.kind() == "function_declarator").then_some(declarator)
+    }
+
+    /// Name of the class, struct or union whose body a node sits in
+    fn enclosing_type_name(&self, ts_node: &TSNode) -> Option<String> {
+        let mut current = ts_node.parent();
+        while let Some(node) = current {
+            match node.kind() {
+                "class_specifier" | "struct_specifier" | "union_specifier" => {
+                    return self.type_name(&node);
+                }
+                // Local classes inside a function body do not make it a method
+                "function_definition" | "namespace_definition" => return None,
+                _ => current = node.parent(),
+            }
+        }
+        None
+    }
+
+    /// Process function definitions, including member functions defined in a
+    /// class body or out of line as `void Widget::draw() { ... }`
+    ///
+    /// Prototypes without a body are not indexed.
+    fn process_function(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let Some(declarator) = Self::function_declarator(ts_node) else {
+            return Ok(None);
+        };
+        let full_name = self
+            .field_text(&declarator, "declarator")
+            .unwrap_or_default();
+        let (scope, name) = match full_name.rsplit_once("::") {
+            Some((scope, name)) => (Some(scope.to_string()), name.to_string()),
+            None => (None, full_name.clone()),
+        };
+        // Qualified by the enclosing class, or by the scope of an out-of-line definition
+        let scope = self.enclosing_type_name(ts_node).or(scope);
+        let class_name = scope
+            .as_deref()
+            .map(|scope| Self::short_name(scope).to_string());
+        let parameters = self
+            .field_text(&declarator, "parameters")
+            .unwrap_or_else(|| "()".to_string());
+        let return_type = self.field_text(ts_node, "type");
+        let signature = match ts_node.child_by_field_name("body") {
+            Some(body) => Self::single_line(&self.content[ts_node.start_byte()..body.start_byte()]),
+            None => Self::single_line(&self.node_text(ts_node)),
+        };
+        let mut cursor = ts_node.walk();
+        let storage_class: Vec<String> = ts_node
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "storage_class_specifier")
+            .map(|child| self.node_text(&child))
+            .collect();
+
+        let node = Node::new(
+            &self.repo_id,
+            if class_name.is_some() {
+                NodeKind::Method
+            } else {
+                NodeKind::Function
+            },
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_signature(signature)
+        .with_metadata(json!({
+            "function_name": name,
+            "class_name": class_name,
+            "namespace": self.namespace_path(ts_node),
+            "qualified_name": self.qualified_name(ts_node, scope.as_deref(), &name),
+            "is_static": storage_class.iter().any(|class| class == "static"),
+            "storage_class": storage_class,
+            "parameters": parameters,
+            "return_type": return_type,
+            "template_parameters": self.template_parameters(ts_node),
+            "type": "function_definition"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process function calls and `new` expressions
+    ///
+    /// Calls through function pointers or other expressions without a name
+    /// are skipped.
+    fn process_call(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let is_constructor = ts_node.kind() == "new_expression";
+        let (name, callee) = if is_constructor {
+            let Some(class) = self.field_text(ts_node, "type") else {
+                return Ok(None);
+            };
+            (Self::short_name(&class).to_string(), class)
+        } else {
+            let Some(function) = ts_node.child_by_field_name("function") else {
+                return Ok(None);
+            };
+            let callee = self.node_text(&function);
+            let name = match function.kind() {
+                "identifier" | "qualified_identifier" | "template_function" => {
+                    Self::short_name(&callee).to_string()
+                }
+                "field_expression" => self.field_text(&function, "field").unwrap_or_default(),
+                _ => return Ok(None),
+            };
+            (name, callee)
+        };
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Call,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "function_name": name,
+            "callee": callee,
+            "is_constructor": is_constructor,
+            "type": ts_node.kind()
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Link calls to functions, methods and function-like macros, and `new`
+    /// expressions to classes, declared in the same file
+    fn resolve_local_calls(&mut self) {
+        let mut definitions: HashMap<&str, NodeId> = HashMap::new();
+        for node in &self.nodes {
+            let is_macro = node.kind == NodeKind::Unknown && node.metadata["macro"] == true;
+            if matches!(node.kind, NodeKind::Function | NodeKind::Method) || is_macro {
+                definitions.entry(node.name.as_str()).or_insert(node.id);
+            }
+        }
+
+        let resolved: Vec<Edge> = self
+            .nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Call)
+            .filter_map(|call| {
+                let target = if call.metadata["is_constructor"] == true {
+                    self.types.get(&call.name)
+                } else {
+                    definitions.get(call.name.as_str())
+                };
+                target.map(|target| Edge::new(call.id, *target, EdgeKind::Calls))
+            })
+            .collect();
+
+        self.edges.extend(resolved);
+    }
+
+    /// Link base classes declared in the same file
+    ///
+    /// Bases declared in headers stay unlinked here; their names remain in the
+    /// `extends` metadata.
+    fn link_bases(&mut self) {
+        for (source, base) in &self.bases {
+            if let Some(target) = self.types.get(base) {
+                self.edges
+                    .push(Edge::new(*source, *target, EdgeKind::Extends));
+            }
+        }
+    }
+}
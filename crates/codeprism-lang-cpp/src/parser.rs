@@ -0,0 +1,113 @@
+//! C/C++ parser implementation
+
+use crate::ast_mapper::{AstMapper, DEFAULT_MAX_DEPTH};
+use crate::error::{Error, Result};
+use crate::types::{Edge, Language, Node};
+use std::path::{Path, PathBuf};
+use tree_sitter::{Parser, Tree};
+
+/// Parse context for C/C++ files
+#[derive(Debug, Clone)]
+pub struct ParseContext {
+    /// Repository ID
+    pub repo_id: String,
+    /// File path being parsed
+    pub file_path: PathBuf,
+    /// Previous tree for incremental parsing
+    pub old_tree: Option<Tree>,
+    /// File content
+    pub content: String,
+}
+
+/// Parse result containing nodes and edges
+#[derive(Debug)]
+pub struct ParseResult {
+    /// The parsed tree
+    pub tree: Tree,
+    /// Extracted nodes
+    pub nodes: Vec<Node>,
+    /// Extracted edges
+    pub edges: Vec<Edge>,
+}
+
+/// C/C++ parser
+pub struct CppParser {
+    /// Tree-sitter parser for C
+    c_parser: Parser,
+    /// Tree-sitter parser for C++
+    cpp_parser: Parser,
+    /// Deepest tree level converted into nodes
+    max_depth: usize,
+}
+
+impl CppParser {
+    /// Create a new C/C++ parser
+    pub fn new() -> Self {
+        let mut c_parser = Parser::new();
+        c_parser
+            .set_language(&tree_sitter_c::LANGUAGE.into())
+            .expect("Failed to load C grammar");
+
+        let mut cpp_parser = Parser::new();
+        cpp_parser
+            .set_language(&tree_sitter_cpp::LANGUAGE.into())
+            .expect("Failed to load C++ grammar");
+
+        Self {
+            c_parser,
+            cpp_parser,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels when extracting nodes
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Get the language for a file based on its extension
+    ///
+    /// `.h` headers are treated as C, matching `Language::from_extension` in
+    /// codeprism-core; C++ headers should use `.hpp` or `.hxx`.
+    pub fn detect_language(path: &Path) -> Language {
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("c") | Some("h") => Language::C,
+            _ => Language::Cpp,
+        }
+    }
+
+    /// Parse a C or C++ file
+    pub fn parse(&mut self, context: &ParseContext) -> Result<ParseResult> {
+        let language = Self::detect_language(&context.file_path);
+
+        // Select the appropriate parser
+        let parser = match language {
+            Language::C => &mut self.c_parser,
+            Language::Cpp => &mut self.cpp_parser,
+        };
+
+        // Parse the file
+        let tree = parser
+            .parse(&context.content, context.old_tree.as_ref())
+            .ok_or_else(|| Error::parse(&context.file_path, "Failed to parse file"))?;
+
+        // Extract nodes and edges
+        let mapper = AstMapper::new(
+            &context.repo_id,
+            context.file_path.clone(),
+            language,
+            &context.content,
+        )
+        .with_max_depth(self.max_depth);
+
+        let (nodes, edges) = mapper.extract(&tree)?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+impl Default for CppParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
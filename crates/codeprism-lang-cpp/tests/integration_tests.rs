@@ -0,0 +1,262 @@
+//! Integration tests for C/C++ parser
+
+use codeprism_lang_cpp::{CppParser, EdgeKind, Language, NodeKind, ParseContext, ParseResult};
+use std::path::{Path, PathBuf};
+
+fn parse_path(file_path: PathBuf, code: &str) -> ParseResult {
+    let mut parser = CppParser::new();
+    let context = ParseContext {
+        repo_id: "test-repo".to_string(),
+        file_path,
+        old_tree: None,
+        content: code.to_string(),
+    };
+    parser.parse(&context).expect("Failed to parse C/C++ file")
+}
+
+fn parse(file_name: &str, code: &str) -> ParseResult {
+    parse_path(PathBuf::from(file_name), code)
+}
+
+fn has_edge(result: &ParseResult, source: &str, target: &str, kind: EdgeKind) -> bool {
+    let ids = |name: &str| {
+        result
+            .nodes
+            .iter()
+            .filter(|n| n.name == name)
+            .map(|n| n.id)
+            .collect::<Vec<_>>()
+    };
+    let (sources, targets) = (ids(source), ids(target));
+    result
+        .edges
+        .iter()
+        .any(|e| sources.contains(&e.source) && targets.contains(&e.target) && e.kind == kind)
+}
+
+#[test]
+fn test_detect_language() {
+    assert_eq!(CppParser::detect_language(Path::new("main.c")), Language::C);
+    assert_eq!(CppParser::detect_language(Path::new("util.h")), Language::C);
+    for file in ["widget.cpp", "widget.cc", "widget.hpp"] {
+        assert_eq!(CppParser::detect_language(Path::new(file)), Language::Cpp);
+    }
+}
+
+#[test]
+fn test_parse_cpp_classes_namespaces_and_methods() {
+    let cpp_code = r#"
+#include <vector>
+
+namespace ui {
+namespace detail {
+
+class Base {};
+
+class Widget : public Base, private ui::Mixin {
+public:
+    static int count() { return helper(1); }
+    void draw() const;
+};
+
+}  // namespace detail
+
+void detail::Widget::draw() const {
+    helper(2);
+    this->count();
+}
+}  // namespace ui
+
+template <typename T>
+T max_of(T a, T b) { return a > b ? a : b; }
+
+int helper(int v) { return v; }
+
+ui::detail::Widget *make() { return new ui::detail::Widget(); }
+"#;
+
+    let result = parse("widget.cpp", cpp_code);
+    let find = |kind, name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.kind == kind && n.name == name)
+            .unwrap_or_else(|| panic!("no {kind:?} named {name}"))
+    };
+
+    assert_eq!(find(NodeKind::Module, "widget").lang, Language::Cpp);
+    assert_eq!(
+        find(NodeKind::Namespace, "detail").metadata["namespace"],
+        "ui::detail"
+    );
+
+    let widget = find(NodeKind::Class, "Widget");
+    assert_eq!(widget.metadata["qualified_name"], "ui::detail::Widget");
+    assert_eq!(
+        widget.metadata["extends"],
+        serde_json::json!(["Base", "Mixin"])
+    );
+    assert!(has_edge(&result, "Widget", "Base", EdgeKind::Extends));
+
+    let count = find(NodeKind::Method, "count");
+    assert_eq!(count.metadata["class_name"], "Widget");
+    assert_eq!(count.metadata["is_static"], true);
+    assert_eq!(count.signature.as_deref(), Some("static int count()"));
+
+    // Out-of-line definitions are methods of the class named by their scope
+    let draw = find(NodeKind::Method, "draw");
+    assert_eq!(draw.metadata["class_name"], "Widget");
+    assert_eq!(draw.metadata["qualified_name"], "ui::detail::Widget::draw");
+
+    let max_of = find(NodeKind::Function, "max_of");
+    assert_eq!(max_of.metadata["template_parameters"], "<typename T>");
+    assert_eq!(
+        find(NodeKind::Function, "make").metadata["return_type"],
+        "ui::detail::Widget"
+    );
+
+    assert!(has_edge(&result, "helper", "helper", EdgeKind::Calls));
+    assert!(has_edge(&result, "count", "count", EdgeKind::Calls));
+    let constructor = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Call && n.metadata["is_constructor"] == true)
+        .unwrap();
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.source == constructor.id && e.target == widget.id && e.kind == EdgeKind::Calls));
+}
+
+#[test]
+fn test_parse_c_structs_typedefs_and_macros() {
+    let c_code = r#"
+#define SQUARE(x) ((x) * (x))
+#define LIMIT 10
+
+struct node { int value; struct node *next; };
+typedef struct { int a; int b; } pair_t;
+
+static int *make(void) { return 0; }
+
+int area(int side) { make(); return SQUARE(side); }
+"#;
+
+    let result = parse("shapes.c", c_code);
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    assert_eq!(find("node").kind, NodeKind::Struct);
+    assert_eq!(find("node").lang, Language::C);
+    assert_eq!(find("pair_t").kind, NodeKind::Struct);
+    assert_eq!(find("make").kind, NodeKind::Function);
+    assert_eq!(
+        find("make").metadata["storage_class"],
+        serde_json::json!(["static"])
+    );
+
+    // Function-like macros are kept as unknown nodes; object-like ones are not
+    let square = find("SQUARE");
+    assert_eq!(square.kind, NodeKind::Unknown);
+    assert_eq!(square.metadata["macro"], true);
+    assert_eq!(square.signature.as_deref(), Some("#define SQUARE(x)"));
+    assert!(!result.nodes.iter().any(|n| n.name == "LIMIT"));
+    assert!(has_edge(&result, "SQUARE", "SQUARE", EdgeKind::Calls));
+    assert!(has_edge(&result, "make", "make", EdgeKind::Calls));
+}
+
+#[test]
+fn test_includes_record_conditions_and_resolve_relative_paths() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let temp_dir = temp_dir.path();
+    std::fs::create_dir_all(temp_dir.join("src")).unwrap();
+    std::fs::create_dir_all(temp_dir.join("include")).unwrap();
+    std::fs::write(temp_dir.join("include/log.h"), "void log(void);\n").unwrap();
+
+    let header = r#"#ifndef WIDGET_H
+#define WIDGET_H
+
+#include <stdio.h>
+#include "../include/log.h"
+#include "missing.h"
+
+#ifdef _WIN32
+#include "win/compat.h"
+#elif defined(__linux__)
+#include "posix/compat.h"
+#else
+#include "other/compat.h"
+#endif
+
+#endif
+"#;
+    let result = parse_path(temp_dir.join("src/widget.h"), header);
+    let module = &result.nodes[0];
+    assert_eq!(module.metadata["include_guard"], "WIDGET_H");
+
+    let includes: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Import)
+        .collect();
+    let names: Vec<_> = includes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "stdio.h",
+            "../include/log.h",
+            "missing.h",
+            "win/compat.h",
+            "posix/compat.h",
+            "other/compat.h",
+        ]
+    );
+    assert!(includes
+        .iter()
+        .all(|n| has_edge(&result, "widget", &n.name, EdgeKind::Imports)));
+
+    assert_eq!(includes[0].metadata["import_type"], "system");
+    assert_eq!(
+        includes[0].metadata["resolved_path"],
+        serde_json::Value::Null
+    );
+    // The include guard is not a condition
+    assert_eq!(includes[0].metadata["conditional"], false);
+    assert_eq!(
+        includes[1].metadata["resolved_path"],
+        temp_dir.join("include/log.h").display().to_string()
+    );
+    assert_eq!(
+        includes[2].metadata["resolved_path"],
+        serde_json::Value::Null
+    );
+
+    assert_eq!(
+        includes[3].metadata["conditions"],
+        serde_json::json!(["#ifdef _WIN32"])
+    );
+    assert_eq!(
+        includes[4].metadata["conditions"],
+        serde_json::json!(["#elif defined(__linux__)"])
+    );
+    assert_eq!(
+        includes[5].metadata["conditions"],
+        serde_json::json!(["#else (#ifdef _WIN32)"])
+    );
+}
+
+#[test]
+fn test_depth_limit_truncates_instead_of_overflowing() {
+    let nested = format!("int x = {}1{};\n", "(".repeat(2000), ")".repeat(2000));
+    let mut parser = CppParser::new();
+    parser.set_max_depth(64);
+    let context = ParseContext {
+        repo_id: "test-repo".to_string(),
+        file_path: PathBuf::from("deep.c"),
+        old_tree: None,
+        content: nested,
+    };
+
+    let result = parser.parse(&context).unwrap();
+    let module = &result.nodes[0];
+    assert_eq!(module.metadata["depth_truncation"]["max_depth"], 64);
+}
@@ -0,0 +1,550 @@
+//! AST mapping from tree-sitter Go CST to Universal AST
+
+use crate::error::Result;
+use crate::types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tree_sitter::{Node as TSNode, Tree};
+
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// Maps tree-sitter Go CST to Universal AST
+pub struct AstMapper {
+    /// Repository ID
+    repo_id: String,
+    /// File path
+    file_path: PathBuf,
+    /// Language
+    language: Language,
+    /// Source content
+    content: String,
+    /// Collected nodes
+    nodes: Vec<Node>,
+    /// Collected edges
+    edges: Vec<Edge>,
+    /// Node ID mappings (tree-sitter node ID -> Universal AST node ID)
+    node_mappings: HashMap<usize, NodeId>,
+    /// Method names declared for each receiver type
+    methods_by_receiver: HashMap<String, HashSet<String>>,
+    /// Method sets declared by each interface
+    interface_methods: HashMap<NodeId, HashSet<String>>,
+    /// Struct node IDs by type name
+    structs: HashMap<String, NodeId>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
+}
+
+impl AstMapper {
+    /// Create a new AST mapper
+    pub fn new(repo_id: &str, file_path: PathBuf, language: Language, content: &str) -> Self {
+        Self {
+            repo_id: repo_id.to_string(),
+            file_path,
+            language,
+            content: content.to_string(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_mappings: HashMap::new(),
+            methods_by_receiver: HashMap::new(),
+            interface_methods: HashMap::new(),
+            structs: HashMap::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Extract nodes and edges from the tree
+    pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
+        let root = tree.root_node();
+
+        // Create module node for the file
+        let module_span = Span::from_node(&root);
+        let file_name = self
+            .file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let module_node = Node::new(
+            &self.repo_id,
+            NodeKind::Module,
+            file_name,
+            self.language,
+            self.file_path.clone(),
+            module_span,
+        )
+        .with_metadata(json!({
+            "type": "source_file",
+            "file_path": self.file_path.display().to_string()
+        }));
+
+        let module_id = module_node.id;
+        self.nodes.push(module_node);
+        self.node_mappings.insert(root.id(), module_id);
+
+        // Process all child nodes
+        self.process_node(&root, Some(module_id), 0)?;
+
+        self.resolve_local_calls();
+        self.link_interface_implementations();
+        self.mark_entry_points();
+
+        self.record_depth_truncation();
+
+        Ok((self.nodes, self.edges))
+    }
+
+    /// Flag the nodes execution can start from with `"entry_point": true`
+    ///
+    /// These are `main` in package `main`, `init` functions, and exported
+    /// functions and methods, which other packages may call.
+    fn mark_entry_points(&mut self) {
+        let package_main = self
+            .nodes
+            .iter()
+            .any(|node| node.kind == NodeKind::Package && node.name == "main");
+        for node in &mut self.nodes {
+            let entry_point = match node.kind {
+                NodeKind::Function => {
+                    (package_main && node.name == "main")
+                        || node.name == "init"
+                        || Self::is_exported(&node.name)
+                }
+                NodeKind::Method => Self::is_exported(&node.name),
+                _ => false,
+            };
+            if entry_point {
+                node.metadata["entry_point"] = json!(true);
+            }
+        }
+    }
+
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = json!({});
+            }
+            module.metadata["depth_truncation"] = json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
+    /// Process a tree-sitter node recursively
+    fn process_node(
+        &mut self,
+        ts_node: &TSNode,
+        parent_id: Option<NodeId>,
+        depth: usize,
+    ) -> Result<Option<NodeId>> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(None);
+        }
+
+        let universal_node = match ts_node.kind() {
+            "package_clause" => self.process_package_clause(ts_node)?,
+            "import_spec" => self.process_import_spec(ts_node)?,
+            "function_declaration" => self.process_function_declaration(ts_node)?,
+            "method_declaration" => self.process_method_declaration(ts_node)?,
+            "type_spec" => self.process_type_spec(ts_node)?,
+            "call_expression" => self.process_call_expression(ts_node)?,
+            _ => {
+                // For unhandled node types, still process children
+                None
+            }
+        };
+
+        // Add edge from parent to this node
+        if let (Some(parent), Some(node_id)) = (parent_id, &universal_node) {
+            let kind = match ts_node.kind() {
+                "import_spec" => EdgeKind::Imports,
+                "call_expression" => EdgeKind::Calls,
+                _ => EdgeKind::Contains,
+            };
+            self.edges.push(Edge::new(parent, *node_id, kind));
+        }
+
+        // Process children
+        let mut cursor = ts_node.walk();
+        for child in ts_node.children(&mut cursor) {
+            let child_parent = universal_node.or(parent_id);
+            self.process_node(&child, child_parent, depth + 1)?;
+        }
+
+        Ok(universal_node)
+    }
+
+    /// Get the text content of a node
+    fn node_text(&self, node: &TSNode) -> String {
+        node.utf8_text(self.content.as_bytes())
+            .unwrap_or("")
+            .to_string()
+    }
+
+    /// Get the text of a named field of a node
+    fn field_text(&self, node: &TSNode, field: &str) -> Option<String> {
+        node.child_by_field_name(field).map(|n| self.node_text(&n))
+    }
+
+    /// Go exports identifiers that start with an upper-case letter
+    fn is_exported(name: &str) -> bool {
+        name.chars().next().is_some_and(|c| c.is_uppercase())
+    }
+
+    /// Push a node and remember its tree-sitter mapping
+    fn push_node(&mut self, ts_node: &TSNode, node: Node) -> NodeId {
+        let node_id = node.id;
+        self.nodes.push(node);
+        self.node_mappings.insert(ts_node.id(), node_id);
+        node_id
+    }
+
+    /// Process package clause
+    fn process_package_clause(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let mut cursor = ts_node.walk();
+        let package_name = ts_node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "package_identifier")
+            .map(|child| self.node_text(&child))
+            .unwrap_or_default();
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Package,
+            package_name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "package_name": package_name,
+            "type": "package_clause"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process a single import spec (grouped imports yield one spec each)
+    fn process_import_spec(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let import_path = self
+            .field_text(ts_node, "path")
+            .map(|path| path.trim_matches(|c| c == '"' || c == '`').to_string())
+            .unwrap_or_default();
+        let alias = self.field_text(ts_node, "name");
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Import,
+            import_path.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "import_path": import_path,
+            "alias": alias,
+            "is_blank": alias.as_deref() == Some("_"),
+            "is_dot": alias.as_deref() == Some("."),
+            "type": "import_spec"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process function declaration
+    fn process_function_declaration(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let parameters = self.field_text(ts_node, "parameters").unwrap_or_default();
+        let result = self.field_text(ts_node, "result");
+        let signature = Self::build_signature(None, &name, &parameters, result.as_deref());
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Function,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_signature(signature)
+        .with_metadata(json!({
+            "function_name": name,
+            "is_exported": Self::is_exported(&name),
+            "parameters": parameters,
+            "result": result,
+            "type": "function_declaration"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process method declaration (a function with a receiver)
+    fn process_method_declaration(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let receiver = self.field_text(ts_node, "receiver").unwrap_or_default();
+        let receiver_type = ts_node
+            .child_by_field_name("receiver")
+            .and_then(|receiver| self.base_type_name(&receiver))
+            .unwrap_or_default();
+        let pointer_receiver = receiver.contains('*');
+        let parameters = self.field_text(ts_node, "parameters").unwrap_or_default();
+        let result = self.field_text(ts_node, "result");
+        let signature =
+            Self::build_signature(Some(&receiver), &name, &parameters, result.as_deref());
+
+        self.methods_by_receiver
+            .entry(receiver_type.clone())
+            .or_default()
+            .insert(name.clone());
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Method,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_signature(signature)
+        .with_metadata(json!({
+            "method_name": name,
+            "receiver_type": receiver_type,
+            "pointer_receiver": pointer_receiver,
+            "is_exported": Self::is_exported(&name),
+            "parameters": parameters,
+            "result": result,
+            "type": "method_declaration"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process type spec, keeping struct and interface definitions
+    fn process_type_spec(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let Some(type_node) = ts_node.child_by_field_name("type") else {
+            return Ok(None);
+        };
+
+        let span = Span::from_node(ts_node);
+        let node_id = match type_node.kind() {
+            "struct_type" => {
+                let fields = self.extract_struct_fields(&type_node);
+                let node = Node::new(
+                    &self.repo_id,
+                    NodeKind::Struct,
+                    name.clone(),
+                    self.language,
+                    self.file_path.clone(),
+                    span,
+                )
+                .with_metadata(json!({
+                    "struct_name": name,
+                    "is_exported": Self::is_exported(&name),
+                    "fields": fields,
+                    "type": "struct_type"
+                }));
+                let node_id = self.push_node(ts_node, node);
+                self.structs.insert(name, node_id);
+                node_id
+            }
+            "interface_type" => {
+                let methods = self.extract_interface_methods(&type_node);
+                let node = Node::new(
+                    &self.repo_id,
+                    NodeKind::Interface,
+                    name.clone(),
+                    self.language,
+                    self.file_path.clone(),
+                    span,
+                )
+                .with_metadata(json!({
+                    "interface_name": name,
+                    "is_exported": Self::is_exported(&name),
+                    "methods": methods,
+                    "type": "interface_type"
+                }));
+                let node_id = self.push_node(ts_node, node);
+                self.interface_methods
+                    .insert(node_id, methods.into_iter().collect());
+                node_id
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(node_id))
+    }
+
+    /// Process call expression, including calls launched with `go`
+    fn process_call_expression(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let Some(function) = ts_node.child_by_field_name("function") else {
+            return Ok(None);
+        };
+
+        let callee = self.node_text(&function);
+        let name = match function.kind() {
+            "selector_expression" => self.field_text(&function, "field").unwrap_or_default(),
+            _ => callee.clone(),
+        };
+        let is_goroutine = ts_node
+            .parent()
+            .is_some_and(|parent| parent.kind() == "go_statement");
+        let is_deferred = ts_node
+            .parent()
+            .is_some_and(|parent| parent.kind() == "defer_statement");
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Call,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "function_name": name,
+            "callee": callee,
+            "is_goroutine": is_goroutine,
+            "is_deferred": is_deferred,
+            "type": "call_expression"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Find the name of the type a receiver or type expression refers to
+    fn base_type_name(&self, node: &TSNode) -> Option<String> {
+        if node.kind() == "type_identifier" {
+            return Some(self.node_text(node));
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        children.iter().find_map(|child| self.base_type_name(child))
+    }
+
+    /// Extract field names of a struct type (embedded types use the type name)
+    fn extract_struct_fields(&self, struct_type: &TSNode) -> Vec<String> {
+        let mut fields = Vec::new();
+        let mut stack = vec![*struct_type];
+
+        while let Some(node) = stack.pop() {
+            if node.kind() == "field_declaration" {
+                let mut cursor = node.walk();
+                let names: Vec<_> = node
+                    .children_by_field_name("name", &mut cursor)
+                    .map(|name| self.node_text(&name))
+                    .collect();
+                if names.is_empty() {
+                    if let Some(embedded) = self.base_type_name(&node) {
+                        fields.push(embedded);
+                    }
+                } else {
+                    fields.extend(names);
+                }
+                continue;
+            }
+
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+
+        fields.sort();
+        fields
+    }
+
+    /// Extract method names declared by an interface type
+    fn extract_interface_methods(&self, interface_type: &TSNode) -> Vec<String> {
+        let mut cursor = interface_type.walk();
+        let mut methods: Vec<String> = interface_type
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "method_elem")
+            .filter_map(|method| self.field_text(&method, "name"))
+            .collect();
+        methods.sort();
+        methods
+    }
+
+    /// Link calls to functions and methods declared in the same file
+    fn resolve_local_calls(&mut self) {
+        let mut definitions: HashMap<&str, NodeId> = HashMap::new();
+        for node in &self.nodes {
+            if matches!(node.kind, NodeKind::Function | NodeKind::Method) {
+                definitions.entry(node.name.as_str()).or_insert(node.id);
+            }
+        }
+
+        let resolved: Vec<Edge> = self
+            .nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Call)
+            .filter_map(|call| {
+                definitions
+                    .get(call.name.as_str())
+                    .map(|target| Edge::new(call.id, *target, EdgeKind::Calls))
+            })
+            .collect();
+
+        self.edges.extend(resolved);
+    }
+
+    /// Add `Implements` edges for structs whose method set covers an interface
+    ///
+    /// Only methods and types declared in the same file are considered. Empty
+    /// interfaces are skipped since every type satisfies them.
+    fn link_interface_implementations(&mut self) {
+        for (struct_name, struct_id) in &self.structs {
+            let Some(methods) = self.methods_by_receiver.get(struct_name) else {
+                continue;
+            };
+
+            for (interface_id, interface_methods) in &self.interface_methods {
+                if !interface_methods.is_empty() && interface_methods.is_subset(methods) {
+                    self.edges
+                        .push(Edge::new(*struct_id, *interface_id, EdgeKind::Implements));
+                }
+            }
+        }
+    }
+
+    /// Build a Go-style signature for a function or method
+    fn build_signature(
+        receiver: Option<&str>,
+        name: &str,
+        parameters: &str,
+        result: Option<&str>,
+    ) -> String {
+        let mut signature = String::from("func ");
+        if let Some(receiver) = receiver {
+            signature.push_str(receiver);
+            signature.push(' ');
+        }
+        signature.push_str(name);
+        signature.push_str(parameters);
+        if let Some(result) = result {
+            signature.push(' ');
+            signature.push_str(result);
+        }
+        signature
+    }
+}
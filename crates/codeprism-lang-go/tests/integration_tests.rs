@@ -0,0 +1,197 @@
+//! Integration tests for Go parser
+
+use codeprism_lang_go::{EdgeKind, GoParser, Language, NodeKind, ParseContext, ParseResult};
+use std::path::{Path, PathBuf};
+
+fn parse(file_name: &str, code: &str) -> ParseResult {
+    let mut parser = GoParser::new();
+    let context = ParseContext {
+        repo_id: "test-repo".to_string(),
+        file_path: PathBuf::from(file_name),
+        old_tree: None,
+        content: code.to_string(),
+    };
+    parser.parse(&context).expect("Failed to parse Go file")
+}
+
+#[test]
+fn test_detect_language() {
+    assert_eq!(
+        GoParser::detect_language(Path::new("main.go")),
+        Language::Go
+    );
+}
+
+#[test]
+fn test_parse_functions_methods_and_imports() {
+    let go_code = r#"
+package shapes
+
+import (
+    "fmt"
+    m "math"
+)
+
+type Circle struct {
+    Radius float64
+}
+
+func (c *Circle) Area() float64 {
+    return m.Pi * c.Radius * c.Radius
+}
+
+func Describe(c *Circle) string {
+    return fmt.Sprintf("circle %f", c.Area())
+}
+"#;
+
+    let result = parse("shapes.go", go_code);
+
+    let package = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Package)
+        .expect("Should have a package node");
+    assert_eq!(package.name, "shapes");
+
+    let imports: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Import)
+        .map(|n| n.name.as_str())
+        .collect();
+    assert_eq!(imports, vec!["fmt", "math"]);
+    let import_edges = result
+        .edges
+        .iter()
+        .filter(|e| e.kind == EdgeKind::Imports)
+        .count();
+    assert_eq!(import_edges, 2);
+
+    let method = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Method)
+        .expect("Should have a method node");
+    assert_eq!(method.name, "Area");
+    assert_eq!(method.metadata["receiver_type"], "Circle");
+    assert_eq!(method.metadata["pointer_receiver"], true);
+    assert_eq!(
+        method.signature.as_deref(),
+        Some("func (c *Circle) Area() float64")
+    );
+
+    let function = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Function)
+        .expect("Should have a function node");
+    assert_eq!(function.name, "Describe");
+    assert_eq!(function.metadata["is_exported"], true);
+
+    let circle = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Struct)
+        .expect("Should have a struct node");
+    assert_eq!(circle.name, "Circle");
+}
+
+#[test]
+fn test_struct_implements_interface() {
+    let go_code = r#"
+package io
+
+type Reader interface {
+    Read(p []byte) (int, error)
+}
+
+type Closer interface {
+    Close() error
+}
+
+type File struct{}
+
+func (f *File) Read(p []byte) (int, error) { return 0, nil }
+
+type Buffer struct{}
+
+func (b Buffer) Close() error { return nil }
+"#;
+
+    let result = parse("io.go", go_code);
+    let id_of = |name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.name == name && matches!(n.kind, NodeKind::Struct | NodeKind::Interface))
+            .map(|n| n.id)
+            .unwrap()
+    };
+
+    let implements: Vec<_> = result
+        .edges
+        .iter()
+        .filter(|e| e.kind == EdgeKind::Implements)
+        .map(|e| (e.source, e.target))
+        .collect();
+
+    assert_eq!(implements.len(), 2);
+    assert!(implements.contains(&(id_of("File"), id_of("Reader"))));
+    assert!(implements.contains(&(id_of("Buffer"), id_of("Closer"))));
+}
+
+#[test]
+fn test_go_statement_produces_calls_edge() {
+    let go_code = r#"
+package main
+
+func worker(id int) {}
+
+func main() {
+    go worker(1)
+    defer cleanup()
+}
+
+func cleanup() {}
+"#;
+
+    let result = parse("main.go", go_code);
+
+    let goroutine = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Call && n.name == "worker")
+        .expect("Should have a call node for the goroutine");
+    assert_eq!(goroutine.metadata["is_goroutine"], true);
+
+    let main_id = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Function && n.name == "main")
+        .map(|n| n.id)
+        .unwrap();
+    let worker_id = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Function && n.name == "worker")
+        .map(|n| n.id)
+        .unwrap();
+
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.kind == EdgeKind::Calls && e.source == main_id && e.target == goroutine.id));
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.kind == EdgeKind::Calls && e.source == goroutine.id && e.target == worker_id));
+
+    let deferred = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Call && n.name == "cleanup")
+        .unwrap();
+    assert_eq!(deferred.metadata["is_deferred"], true);
+    assert_eq!(deferred.metadata["is_goroutine"], false);
+}
@@ -7,6 +7,12 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use tree_sitter::{Node as TSNode, Tree};
 
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
 /// Maps tree-sitter Java CST to Universal AST
 pub struct AstMapper {
     /// Repository ID
@@ -23,6 +29,10 @@ pub struct AstMapper {
     edges: Vec<Edge>,
     /// Node ID mappings (tree-sitter node ID -> Universal AST node ID)
     node_mappings: HashMap<usize, NodeId>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
 }
 
 impl AstMapper {
@@ -36,9 +46,17 @@ impl AstMapper {
             nodes: Vec::new(),
             edges: Vec::new(),
             node_mappings: HashMap::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
         }
     }
 
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     /// Extract nodes and edges from the tree
     pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
         let root = tree.root_node();
@@ -70,17 +88,56 @@ impl AstMapper {
         self.node_mappings.insert(root.id(), module_id);
 
         // Process all child nodes
-        self.process_node(&root, Some(module_id))?;
+        self.process_node(&root, Some(module_id), 0)?;
+
+        self.mark_entry_points();
+        self.record_depth_truncation();
 
         Ok((self.nodes, self.edges))
     }
 
+    /// Flag `public static void main` methods with `"entry_point": true`
+    fn mark_entry_points(&mut self) {
+        for node in &mut self.nodes {
+            if node.kind == NodeKind::Method
+                && node.name == "main"
+                && node.metadata["visibility"] == "public"
+                && node.metadata["is_static"] == true
+                && node.metadata["return_type"] == "void"
+            {
+                node.metadata["entry_point"] = json!(true);
+            }
+        }
+    }
+
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = json!({});
+            }
+            module.metadata["depth_truncation"] = json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
     /// Process a tree-sitter node recursively
     fn process_node(
         &mut self,
         ts_node: &TSNode,
         parent_id: Option<NodeId>,
+        depth: usize,
     ) -> Result<Option<NodeId>> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(None);
+        }
+
         let node_kind = ts_node.kind();
 
         let universal_node = match node_kind {
@@ -135,7 +192,7 @@ impl AstMapper {
         let mut cursor = ts_node.walk();
         for child in ts_node.children(&mut cursor) {
             let child_parent = universal_node.or(parent_id);
-            self.process_node(&child, child_parent)?;
+            self.process_node(&child, child_parent, depth + 1)?;
         }
 
         Ok(universal_node)
@@ -9,6 +9,7 @@ mod types;
 
 pub use adapter::{parse_file, JavaLanguageParser, ParseResultConverter};
 pub use analysis::{JavaAnalysisResult, JavaAnalyzer};
+pub use ast_mapper::DEFAULT_MAX_DEPTH;
 pub use error::{Error, Result};
 pub use parser::{JavaParser, ParseContext, ParseResult};
 pub use types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
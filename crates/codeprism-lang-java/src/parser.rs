@@ -1,6 +1,6 @@
 //! Java parser implementation
 
-use crate::ast_mapper::AstMapper;
+use crate::ast_mapper::{AstMapper, DEFAULT_MAX_DEPTH};
 use crate::error::{Error, Result};
 use crate::types::{Edge, Language, Node};
 use std::path::{Path, PathBuf};
@@ -34,6 +34,8 @@ pub struct ParseResult {
 pub struct JavaParser {
     /// Tree-sitter parser for Java
     parser: Parser,
+    /// Deepest tree level converted into nodes
+    max_depth: usize,
 }
 
 impl JavaParser {
@@ -44,7 +46,15 @@ impl JavaParser {
             .set_language(&tree_sitter_java::LANGUAGE.into())
             .expect("Failed to load Java grammar");
 
-        Self { parser }
+        Self {
+            parser,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels when extracting nodes
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
     }
 
     /// Get the language for a file based on its extension
@@ -72,7 +82,8 @@ impl JavaParser {
             context.file_path.clone(),
             language,
             &context.content,
-        );
+        )
+        .with_max_depth(self.max_depth);
 
         let (nodes, edges) = mapper.extract(&tree)?;
 
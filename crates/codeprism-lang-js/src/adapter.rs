@@ -15,6 +15,15 @@ impl JavaScriptLanguageParser {
             parser: std::sync::Mutex::new(JavaScriptParser::new()),
         }
     }
+
+    /// Create an adapter that skips subtrees deeper than `max_depth` levels
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        let mut parser = JavaScriptParser::new();
+        parser.set_max_depth(max_depth);
+        Self {
+            parser: std::sync::Mutex::new(parser),
+        }
+    }
 }
 
 impl Default for JavaScriptLanguageParser {
@@ -6,6 +6,12 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use tree_sitter::{Tree, TreeCursor};
 
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
 /// AST mapper for JavaScript/TypeScript
 pub struct AstMapper {
     repo_id: String,
@@ -16,6 +22,13 @@ pub struct AstMapper {
     edges: Vec<Edge>,
     /// Map from tree-sitter node ID to our NodeId for edge creation
     node_map: HashMap<usize, crate::types::NodeId>,
+    /// TypeScript `extends`/`implements` targets, resolved by name once the
+    /// whole file has been visited
+    heritage: Vec<(crate::types::NodeId, String, EdgeKind)>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
 }
 
 impl AstMapper {
@@ -29,9 +42,18 @@ impl AstMapper {
             nodes: Vec::new(),
             edges: Vec::new(),
             node_map: HashMap::new(),
+            heritage: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
         }
     }
 
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     /// Extract nodes and edges from the tree
     pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
         let mut cursor = tree.walk();
@@ -41,11 +63,30 @@ impl AstMapper {
         self.nodes.push(module_node);
 
         // Walk the tree and extract nodes
-        self.walk_tree(&mut cursor)?;
+        self.walk_tree(&mut cursor, 0)?;
+        self.resolve_heritage();
+
+        self.record_depth_truncation();
 
         Ok((self.nodes, self.edges))
     }
 
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = serde_json::json!({});
+            }
+            module.metadata["depth_truncation"] = serde_json::json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
     /// Create a module node for the file
     fn create_module_node(&mut self, cursor: &TreeCursor) -> Result<Node> {
         let root = cursor.node();
@@ -72,12 +113,16 @@ impl AstMapper {
     }
 
     /// Walk the tree and extract nodes
-    fn walk_tree(&mut self, cursor: &mut TreeCursor) -> Result<()> {
+    fn walk_tree(&mut self, cursor: &mut TreeCursor, depth: usize) -> Result<()> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(());
+        }
         self.visit_node(cursor)?;
 
         if cursor.goto_first_child() {
             loop {
-                self.walk_tree(cursor)?;
+                self.walk_tree(cursor, depth + 1)?;
                 if !cursor.goto_next_sibling() {
                     break;
                 }
@@ -105,10 +150,21 @@ impl AstMapper {
             }
 
             // Class declarations
-            "class_declaration" | "class" => {
+            "class_declaration" | "class" | "abstract_class_declaration" => {
                 self.handle_class(cursor)?;
             }
 
+            // TypeScript type declarations
+            "interface_declaration" => {
+                self.handle_type_declaration(cursor, NodeKind::Interface)?;
+            }
+            "type_alias_declaration" => {
+                self.handle_type_declaration(cursor, NodeKind::TypeAlias)?;
+            }
+            "enum_declaration" => {
+                self.handle_type_declaration(cursor, NodeKind::Enum)?;
+            }
+
             // Variable declarations
             "variable_declaration" | "lexical_declaration" => {
                 self.handle_variable_declaration(cursor)?;
@@ -207,10 +263,109 @@ impl AstMapper {
                 .push(Edge::new(module_id, class_node.id, EdgeKind::Calls));
         }
 
+        if self.language == Language::TypeScript {
+            self.collect_class_heritage(&node, class_node.id);
+        }
+
         self.nodes.push(class_node);
         Ok(())
     }
 
+    /// Handle TypeScript interface, type alias and enum declarations
+    fn handle_type_declaration(&mut self, cursor: &TreeCursor, kind: NodeKind) -> Result<()> {
+        let node = cursor.node();
+        let span = Span::from_node(&node);
+        let name = self.extract_class_name(&node)?;
+
+        let type_node = Node::new(
+            &self.repo_id,
+            kind,
+            name,
+            self.language,
+            self.file_path.clone(),
+            span,
+        );
+
+        self.node_map.insert(node.id(), type_node.id);
+
+        if let Some(module_id) = self.find_module_node_id() {
+            self.edges
+                .push(Edge::new(module_id, type_node.id, EdgeKind::Calls));
+        }
+
+        // interface A extends B, C
+        if kind == NodeKind::Interface {
+            let mut child_cursor = node.walk();
+            for child in node.children(&mut child_cursor) {
+                if child.kind() == "extends_type_clause" {
+                    let mut type_cursor = child.walk();
+                    for parent_type in child.children_by_field_name("type", &mut type_cursor) {
+                        let name = self.heritage_type_name(&parent_type);
+                        self.heritage.push((type_node.id, name, EdgeKind::Extends));
+                    }
+                }
+            }
+        }
+
+        self.nodes.push(type_node);
+        Ok(())
+    }
+
+    /// Record the `extends` and `implements` clauses of a TypeScript class
+    fn collect_class_heritage(&mut self, node: &tree_sitter::Node, class_id: crate::types::NodeId) {
+        let mut cursor = node.walk();
+        let Some(heritage) = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "class_heritage")
+        else {
+            return;
+        };
+
+        let mut heritage_cursor = heritage.walk();
+        for clause in heritage.children(&mut heritage_cursor) {
+            let edge_kind = match clause.kind() {
+                "extends_clause" => EdgeKind::Extends,
+                "implements_clause" => EdgeKind::Implements,
+                _ => continue,
+            };
+            let mut clause_cursor = clause.walk();
+            for target in clause.named_children(&mut clause_cursor) {
+                if target.kind() == "type_arguments" {
+                    continue;
+                }
+                let name = self.heritage_type_name(&target);
+                self.heritage.push((class_id, name, edge_kind));
+            }
+        }
+    }
+
+    /// Bare name of a heritage target such as `Base<T>` or `ns.Base`
+    fn heritage_type_name(&self, node: &tree_sitter::Node) -> String {
+        let name_node = if node.kind() == "generic_type" {
+            node.child_by_field_name("name").unwrap_or(*node)
+        } else {
+            *node
+        };
+        let text = self.get_node_text(&name_node);
+        let text = text.split('<').next().unwrap_or(&text);
+        text.rsplit('.').next().unwrap_or(text).trim().to_string()
+    }
+
+    /// Link recorded heritage clauses to classes and interfaces in this file
+    ///
+    /// Targets declared in other files are left for cross-file linking.
+    fn resolve_heritage(&mut self) {
+        for (source, name, kind) in std::mem::take(&mut self.heritage) {
+            if let Some(target) = self
+                .nodes
+                .iter()
+                .find(|n| n.name == name && matches!(n.kind, NodeKind::Class | NodeKind::Interface))
+            {
+                self.edges.push(Edge::new(source, target.id, kind));
+            }
+        }
+    }
+
     /// Handle variable declarations
     fn handle_variable_declaration(&mut self, cursor: &TreeCursor) -> Result<()> {
         let node = cursor.node();
@@ -594,7 +749,7 @@ impl AstMapper {
 
         // Parse return type annotation (TypeScript specific)
         if let Some(return_type_node) = node.child_by_field_name("return_type") {
-            let return_type = self.get_node_text(&return_type_node);
+            let return_type = self.type_annotation_text(&return_type_node);
             signature_parts.push(format!(": {return_type}"));
         }
 
@@ -632,8 +787,8 @@ impl AstMapper {
                         }
 
                         if let Some(type_node) = child.child_by_field_name("type") {
-                            let type_text = self.get_node_text(&type_node);
-                            param_parts.push(format!(": {type_text}"));
+                            param_parts
+                                .push(format!(": {}", self.type_annotation_text(&type_node)));
                         }
 
                         if !param_parts.is_empty() {
@@ -693,6 +848,14 @@ impl AstMapper {
         params.join(", ")
     }
 
+    /// Type named by a `type_annotation` node, without its leading `:`
+    fn type_annotation_text(&self, node: &tree_sitter::Node) -> String {
+        self.get_node_text(node)
+            .trim_start_matches(':')
+            .trim()
+            .to_string()
+    }
+
     /// Get text content of a node
     fn get_node_text(&self, node: &tree_sitter::Node) -> String {
         node.utf8_text(self.source.as_bytes())
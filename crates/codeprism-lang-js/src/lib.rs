@@ -5,6 +5,7 @@ mod analysis;
 mod ast_mapper;
 mod error;
 mod parser;
+mod promises;
 mod types;
 
 pub use adapter::{parse_file, JavaScriptLanguageParser, ParseResultConverter};
@@ -13,8 +14,10 @@ pub use analysis::{
     ModernFeatureType, ModernJsFeatureInfo, NodeJsPatternInfo, NodePatternType, PropsInfo,
     ReactComponentInfo, RouteInfo, StateManagementInfo,
 };
+pub use ast_mapper::DEFAULT_MAX_DEPTH;
 pub use error::{Error, Result};
 pub use parser::{JavaScriptParser, ParseContext, ParseResult};
+pub use promises::{PromiseAnalyzer, PromiseIssue, PromiseIssueKind};
 pub use types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
 
 // Re-export the parser for registration
@@ -1,6 +1,6 @@
 //! JavaScript/TypeScript parser implementation
 
-use crate::ast_mapper::AstMapper;
+use crate::ast_mapper::{AstMapper, DEFAULT_MAX_DEPTH};
 use crate::error::{Error, Result};
 use crate::types::{Edge, Language, Node};
 use std::path::{Path, PathBuf};
@@ -36,6 +36,8 @@ pub struct JavaScriptParser {
     js_parser: Parser,
     /// Tree-sitter parser for TypeScript
     ts_parser: Parser,
+    /// Deepest tree level converted into nodes
+    max_depth: usize,
 }
 
 impl JavaScriptParser {
@@ -54,9 +56,15 @@ impl JavaScriptParser {
         Self {
             js_parser,
             ts_parser,
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
+    /// Skip subtrees deeper than `max_depth` levels when extracting nodes
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
     /// Get the language for a file based on its extension
     pub fn detect_language(path: &Path) -> Language {
         match path.extension().and_then(|s| s.to_str()) {
@@ -86,7 +94,8 @@ impl JavaScriptParser {
             context.file_path.clone(),
             language,
             &context.content,
-        );
+        )
+        .with_max_depth(self.max_depth);
 
         let (nodes, edges) = mapper.extract(&tree)?;
 
@@ -334,4 +343,64 @@ mod tests {
             println!("  {:?}", edge.kind);
         }
     }
+
+    #[test]
+    fn test_typescript_declarations_and_heritage() {
+        use crate::types::{EdgeKind, NodeKind};
+
+        let mut parser = JavaScriptParser::new();
+        let source = r#"
+interface Shape { area(): number; }
+interface Named extends Shape { name: string; }
+type Id = string | number;
+enum Color { Red, Green }
+abstract class Base {}
+class Square extends Base implements Named, Shape {
+    name = "square";
+    area(): number { return 1; }
+}
+"#;
+        let result = parser
+            .parse(&ParseContext {
+                repo_id: "test_repo".to_string(),
+                file_path: PathBuf::from("shapes.ts"),
+                old_tree: None,
+                content: source.to_string(),
+            })
+            .unwrap();
+
+        let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+        assert_eq!(find("Shape").kind, NodeKind::Interface);
+        assert_eq!(find("Id").kind, NodeKind::TypeAlias);
+        assert_eq!(find("Color").kind, NodeKind::Enum);
+        assert_eq!(find("Base").kind, NodeKind::Class);
+
+        let has_edge = |source: &str, target: &str, kind: EdgeKind| {
+            result.edges.iter().any(|e| {
+                e.source == find(source).id && e.target == find(target).id && e.kind == kind
+            })
+        };
+        assert!(has_edge("Named", "Shape", EdgeKind::Extends));
+        assert!(has_edge("Square", "Base", EdgeKind::Extends));
+        assert!(has_edge("Square", "Named", EdgeKind::Implements));
+        assert!(has_edge("Square", "Shape", EdgeKind::Implements));
+
+        // Plain JavaScript keeps its previous output
+        let js = parser
+            .parse(&ParseContext {
+                repo_id: "test_repo".to_string(),
+                file_path: PathBuf::from("shapes.js"),
+                old_tree: None,
+                content: "class Base {}\nclass Square extends Base {}\n".to_string(),
+            })
+            .unwrap();
+        assert!(js.nodes.iter().all(|n| !matches!(
+            n.kind,
+            NodeKind::Interface | NodeKind::TypeAlias | NodeKind::Enum
+        )));
+        assert!(js
+            .edges
+            .iter()
+            .all(|e| !matches!(e.kind, EdgeKind::Extends | EdgeKind::Implements)));
+    }
 }
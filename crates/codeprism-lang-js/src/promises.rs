@@ -0,0 +1,406 @@
+//! Promise misuse detection for JavaScript/TypeScript
+//!
+//! Finds the async footguns specific to JavaScript: promises that are created
+//! and dropped without being awaited, `forEach` called with an async callback
+//! (the loop does not wait for the callbacks), and promise chains without a
+//! rejection handler.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::{Node as TsNode, Parser};
+
+/// Promise combinators and APIs known to return a promise
+const PROMISE_FUNCTIONS: &[&str] = &["fetch"];
+const PROMISE_STATICS: &[&str] = &["all", "allSettled", "any", "race"];
+
+/// Kind of promise misuse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromiseIssueKind {
+    /// A promise-returning call whose result is neither awaited, returned nor stored
+    DroppedPromise,
+    /// `forEach` with an async callback, which does not wait for the callbacks
+    AsyncForEach,
+    /// A `.then()` chain with no `.catch()` or rejection handler
+    UnhandledRejection,
+}
+
+/// A promise misuse found in a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromiseIssue {
+    pub kind: PromiseIssueKind,
+    pub file_path: String,
+    /// 1-based line of the offending expression
+    pub line: usize,
+    /// 1-based column of the offending expression
+    pub column: usize,
+    /// Innermost named function containing the expression
+    pub function: Option<String>,
+    pub snippet: String,
+    pub message: String,
+}
+
+/// Tree-sitter based promise misuse detector
+pub struct PromiseAnalyzer {
+    js_parser: Parser,
+    ts_parser: Parser,
+    tsx_parser: Parser,
+}
+
+impl PromiseAnalyzer {
+    pub fn new() -> Self {
+        let parser = |language: tree_sitter::Language| {
+            let mut parser = Parser::new();
+            parser
+                .set_language(&language)
+                .expect("Failed to load JavaScript/TypeScript grammar");
+            parser
+        };
+        Self {
+            js_parser: parser(tree_sitter_javascript::LANGUAGE.into()),
+            ts_parser: parser(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+            tsx_parser: parser(tree_sitter_typescript::LANGUAGE_TSX.into()),
+        }
+    }
+
+    /// Whether `path` is a JavaScript or TypeScript source file
+    pub fn supports(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts")
+        )
+    }
+
+    /// Read and analyze a file
+    pub fn analyze_file(&mut self, path: &Path) -> Result<Vec<PromiseIssue>> {
+        let source = std::fs::read_to_string(path)?;
+        self.analyze_source(&source, path)
+    }
+
+    /// Analyze `source`, using `path` to pick the grammar and label findings
+    pub fn analyze_source(&mut self, source: &str, path: &Path) -> Result<Vec<PromiseIssue>> {
+        let parser = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ts" | "mts" | "cts") => &mut self.ts_parser,
+            Some("tsx") => &mut self.tsx_parser,
+            _ => &mut self.js_parser,
+        };
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| Error::parse(path, "Failed to parse file"))?;
+
+        let mut async_functions = HashSet::new();
+        collect_async_functions(tree.root_node(), source, &mut async_functions);
+
+        let mut scan = Scan {
+            source,
+            file_path: path.display().to_string(),
+            async_functions,
+            issues: Vec::new(),
+        };
+        scan.visit(tree.root_node());
+        scan.issues.sort_by_key(|issue| (issue.line, issue.column));
+        Ok(scan.issues)
+    }
+}
+
+impl Default for PromiseAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Names of functions and methods declared `async` in the file
+fn collect_async_functions(node: TsNode, source: &str, names: &mut HashSet<String>) {
+    if is_async(node) {
+        if let Some(name) = declared_name(node, source) {
+            names.insert(name);
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_async_functions(child, source, names);
+    }
+}
+
+/// Whether a function-like node carries the `async` keyword
+fn is_async(node: TsNode) -> bool {
+    if !matches!(
+        node.kind(),
+        "function_declaration"
+            | "function_expression"
+            | "function"
+            | "arrow_function"
+            | "method_definition"
+    ) {
+        return false;
+    }
+    let mut cursor = node.walk();
+    let is_async = node
+        .children(&mut cursor)
+        .any(|child| child.kind() == "async");
+    is_async
+}
+
+/// Name of a function, method, or the variable or property it is assigned to
+fn declared_name(node: TsNode, source: &str) -> Option<String> {
+    if let Some(name) = node.child_by_field_name("name") {
+        return Some(text(name, source).to_string());
+    }
+    let parent = node.parent()?;
+    let name = match parent.kind() {
+        "variable_declarator" => parent.child_by_field_name("name")?,
+        "pair" => parent.child_by_field_name("key")?,
+        "assignment_expression" => {
+            let left = parent.child_by_field_name("left")?;
+            left.child_by_field_name("property").unwrap_or(left)
+        }
+        _ => return None,
+    };
+    Some(text(name, source).to_string())
+}
+
+fn text<'a>(node: TsNode, source: &'a str) -> &'a str {
+    &source[node.byte_range()]
+}
+
+struct Scan<'a> {
+    source: &'a str,
+    file_path: String,
+    async_functions: HashSet<String>,
+    issues: Vec<PromiseIssue>,
+}
+
+impl Scan<'_> {
+    fn visit(&mut self, node: TsNode) {
+        match node.kind() {
+            "expression_statement" => {
+                if let Some(call) = node
+                    .named_child(0)
+                    .filter(|n| n.kind() == "call_expression")
+                {
+                    self.check_statement_call(call);
+                }
+            }
+            "call_expression" => self.check_for_each(node),
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.visit(child);
+        }
+    }
+
+    /// A call used as a statement, so its result is discarded
+    fn check_statement_call(&mut self, call: TsNode) {
+        let mut handled = false;
+        let mut chained = false;
+        let mut current = call;
+        // Walk a chain like `load().then(a).then(b)` from the outside in
+        while let Some(method) = chain_method(current, self.source) {
+            match method {
+                "catch" => handled = true,
+                "then" => {
+                    chained = true;
+                    // `.then(onFulfilled, onRejected)` handles rejection itself
+                    let arguments = current
+                        .child_by_field_name("arguments")
+                        .map_or(0, |args| args.named_child_count());
+                    handled |= arguments >= 2;
+                }
+                "finally" => chained = true,
+                _ => break,
+            }
+            let Some(receiver) = current
+                .child_by_field_name("function")
+                .and_then(|callee| callee.child_by_field_name("object"))
+            else {
+                break;
+            };
+            if receiver.kind() != "call_expression" {
+                break;
+            }
+            current = receiver;
+        }
+
+        if chained {
+            if !handled {
+                self.report(
+                    call,
+                    PromiseIssueKind::UnhandledRejection,
+                    "Promise chain has no .catch() or rejection handler; a rejection will go unhandled",
+                );
+            }
+        } else if self.returns_promise(call) {
+            self.report(
+                call,
+                PromiseIssueKind::DroppedPromise,
+                "Promise is neither awaited, returned nor stored; add `await`, return it, or handle it with .catch()",
+            );
+        }
+    }
+
+    /// `items.forEach(async (item) => ...)`
+    fn check_for_each(&mut self, call: TsNode) {
+        if chain_method(call, self.source) != Some("forEach") {
+            return;
+        }
+        let callback = call
+            .child_by_field_name("arguments")
+            .and_then(|args| args.named_child(0));
+        if callback.is_some_and(is_async) {
+            self.report(
+                call,
+                PromiseIssueKind::AsyncForEach,
+                "forEach does not wait for async callbacks; use for...of with await, or Promise.all with map",
+            );
+        }
+    }
+
+    fn returns_promise(&self, call: TsNode) -> bool {
+        let Some(callee) = call.child_by_field_name("function") else {
+            return false;
+        };
+        match callee.kind() {
+            "identifier" => {
+                let name = text(callee, self.source);
+                self.async_functions.contains(name) || PROMISE_FUNCTIONS.contains(&name)
+            }
+            "member_expression" => {
+                let Some(property) = callee.child_by_field_name("property") else {
+                    return false;
+                };
+                let property = text(property, self.source);
+                let is_promise_static = callee
+                    .child_by_field_name("object")
+                    .is_some_and(|object| text(object, self.source) == "Promise")
+                    && PROMISE_STATICS.contains(&property);
+                is_promise_static || self.async_functions.contains(property)
+            }
+            _ => false,
+        }
+    }
+
+    fn report(&mut self, node: TsNode, kind: PromiseIssueKind, message: &str) {
+        let snippet = text(node, self.source);
+        let snippet = snippet.lines().next().unwrap_or(snippet).trim();
+        self.issues.push(PromiseIssue {
+            kind,
+            file_path: self.file_path.clone(),
+            line: node.start_position().row + 1,
+            column: node.start_position().column + 1,
+            function: enclosing_function(node, self.source),
+            snippet: snippet.to_string(),
+            message: message.to_string(),
+        });
+    }
+}
+
+/// Method name of a call like `promise.then(...)`
+fn chain_method<'a>(call: TsNode, source: &'a str) -> Option<&'a str> {
+    let callee = call.child_by_field_name("function")?;
+    if callee.kind() != "member_expression" {
+        return None;
+    }
+    Some(text(callee.child_by_field_name("property")?, source))
+}
+
+/// Name of the innermost named function around `node`
+fn enclosing_function(node: TsNode, source: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if matches!(
+            candidate.kind(),
+            "function_declaration"
+                | "function_expression"
+                | "function"
+                | "arrow_function"
+                | "method_definition"
+        ) {
+            if let Some(name) = declared_name(candidate, source) {
+                return Some(name);
+            }
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_express_handler_dropped_promise_is_flagged() {
+        let source = r#"
+const express = require('express');
+const app = express();
+
+async function saveOrder(order) {
+  return db.insert(order);
+}
+
+app.post('/orders', async (req, res) => {
+  saveOrder(req.body);
+  res.status(202).send();
+});
+
+app.put('/orders/:id', async (req, res) => {
+  await saveOrder(req.body);
+  const pending = saveOrder(req.body);
+  res.send(await pending);
+});
+"#;
+        let issues = PromiseAnalyzer::new()
+            .analyze_source(source, Path::new("routes/orders.js"))
+            .unwrap();
+
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].kind, PromiseIssueKind::DroppedPromise);
+        assert_eq!(issues[0].line, 10);
+        assert_eq!(issues[0].column, 3);
+        assert_eq!(issues[0].snippet, "saveOrder(req.body)");
+        assert_eq!(issues[0].file_path, "routes/orders.js");
+    }
+
+    #[test]
+    fn test_async_for_each_and_unhandled_chains() {
+        let source = r#"
+class Notifier {
+  async send(user: User): Promise<void> {}
+
+  notifyAll(users: User[]) {
+    users.forEach(async (user) => {
+      await this.send(user);
+    });
+    users.forEach((user) => console.log(user));
+    fetch('/audit').then((res) => res.json());
+    fetch('/audit').then(log).catch(report);
+    fetch('/audit').then(log, report);
+    void this.send(users[0]);
+    this.send(users[0]);
+  }
+}
+"#;
+        let issues = PromiseAnalyzer::new()
+            .analyze_source(source, Path::new("notifier.ts"))
+            .unwrap();
+
+        let found: Vec<(PromiseIssueKind, usize)> = issues
+            .iter()
+            .map(|issue| (issue.kind, issue.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (PromiseIssueKind::AsyncForEach, 6),
+                (PromiseIssueKind::UnhandledRejection, 10),
+                (PromiseIssueKind::DroppedPromise, 14),
+            ]
+        );
+        assert!(issues
+            .iter()
+            .all(|issue| issue.function.as_deref() == Some("notifyAll")));
+    }
+}
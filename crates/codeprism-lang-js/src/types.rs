@@ -48,6 +48,12 @@ pub enum NodeKind {
     Module,
     /// A class definition
     Class,
+    /// A TypeScript interface declaration
+    Interface,
+    /// A TypeScript type alias
+    TypeAlias,
+    /// A TypeScript enum declaration
+    Enum,
     /// A function definition
     Function,
     /// A method definition
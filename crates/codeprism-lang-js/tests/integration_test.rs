@@ -114,6 +114,13 @@ fn test_parse_typescript() {
         .iter()
         .any(|n| n.name == "createUser" && matches!(n.kind, codeprism_lang_js::NodeKind::Method)));
 
+    // Signatures carry each parameter and return annotation once
+    let get_user = result.nodes.iter().find(|n| n.name == "getUser").unwrap();
+    assert_eq!(
+        get_user.signature.as_deref(),
+        Some("getUser(id: number): Promise<User | null>")
+    );
+
     // Should find function calls
     let calls: Vec<_> = result
         .nodes
@@ -0,0 +1,77 @@
+//! Adapter to integrate Kotlin parser with codeprism
+
+use crate::parser::{KotlinParser, ParseContext as KotlinParseContext};
+use crate::types as kotlin_types;
+
+/// Adapter that implements codeprism's LanguageParser trait
+pub struct KotlinLanguageParser {
+    parser: std::sync::Mutex<KotlinParser>,
+}
+
+impl KotlinLanguageParser {
+    /// Create a new Kotlin language parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: std::sync::Mutex::new(KotlinParser::new()),
+        }
+    }
+
+    /// Create an adapter that skips subtrees deeper than `max_depth` levels
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        let mut parser = KotlinParser::new();
+        parser.set_max_depth(max_depth);
+        Self {
+            parser: std::sync::Mutex::new(parser),
+        }
+    }
+}
+
+impl Default for KotlinLanguageParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Since we can't import codeprism types directly, we'll need to define a conversion
+// trait that the caller can implement
+pub trait ParseResultConverter {
+    type Node;
+    type Edge;
+    type ParseResult;
+
+    fn convert_node(node: kotlin_types::Node) -> Self::Node;
+    fn convert_edge(edge: kotlin_types::Edge) -> Self::Edge;
+    fn create_parse_result(
+        tree: tree_sitter::Tree,
+        nodes: Vec<Self::Node>,
+        edges: Vec<Self::Edge>,
+    ) -> Self::ParseResult;
+}
+
+/// Parse a file and return the result in our internal types
+pub fn parse_file(
+    parser: &KotlinLanguageParser,
+    repo_id: &str,
+    file_path: std::path::PathBuf,
+    content: String,
+    old_tree: Option<tree_sitter::Tree>,
+) -> Result<
+    (
+        tree_sitter::Tree,
+        Vec<kotlin_types::Node>,
+        Vec<kotlin_types::Edge>,
+    ),
+    crate::error::Error,
+> {
+    let context = KotlinParseContext {
+        repo_id: repo_id.to_string(),
+        file_path,
+        old_tree,
+        content,
+    };
+
+    let mut parser = parser.parser.lock().unwrap();
+    let result = parser.parse(&context)?;
+
+    Ok((result.tree, result.nodes, result.edges))
+}
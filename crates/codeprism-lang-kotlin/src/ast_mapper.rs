@@ -0,0 +1,671 @@
+//! AST mapping from tree-sitter Kotlin CST to Universal AST
+
+use crate::error::Result;
+use crate::types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tree_sitter::{Node as TSNode, Tree};
+
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// Node kinds that spell out a type, e.g. an extension receiver or return type
+const TYPE_KINDS: &[&str] = &[
+    "user_type",
+    "nullable_type",
+    "non_nullable_type",
+    "parenthesized_type",
+    "function_type",
+];
+
+/// Maps tree-sitter Kotlin CST to Universal AST
+pub struct AstMapper {
+    /// Repository ID
+    repo_id: String,
+    /// File path
+    file_path: PathBuf,
+    /// Language
+    language: Language,
+    /// Source content
+    content: String,
+    /// Collected nodes
+    nodes: Vec<Node>,
+    /// Collected edges
+    edges: Vec<Edge>,
+    /// Node ID mappings (tree-sitter node ID -> Universal AST node ID)
+    node_mappings: HashMap<usize, NodeId>,
+    /// Classes, interfaces and objects declared in the file, by name
+    types: HashMap<String, NodeId>,
+    /// Supertypes named by each declaration, linked once all types are known
+    supertypes: Vec<(NodeId, String, EdgeKind)>,
+    /// Extension functions and the name of the type they extend
+    extensions: Vec<(NodeId, String)>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
+}
+
+impl AstMapper {
+    /// Create a new AST mapper
+    pub fn new(repo_id: &str, file_path: PathBuf, language: Language, content: &str) -> Self {
+        Self {
+            repo_id: repo_id.to_string(),
+            file_path,
+            language,
+            content: content.to_string(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_mappings: HashMap::new(),
+            types: HashMap::new(),
+            supertypes: Vec::new(),
+            extensions: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Extract nodes and edges from the tree
+    pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
+        let root = tree.root_node();
+
+        // Create module node for the file
+        let module_span = Span::from_node(&root);
+        let file_name = self
+            .file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let is_script = self.file_path.extension().is_some_and(|ext| ext == "kts");
+
+        let module_node = Node::new(
+            &self.repo_id,
+            NodeKind::Module,
+            file_name,
+            self.language,
+            self.file_path.clone(),
+            module_span,
+        )
+        .with_metadata(json!({
+            "type": "source_file",
+            "file_path": self.file_path.display().to_string(),
+            "is_script": is_script
+        }));
+
+        let module_id = module_node.id;
+        self.nodes.push(module_node);
+        self.node_mappings.insert(root.id(), module_id);
+
+        // Process all child nodes
+        self.process_node(&root, Some(module_id), 0)?;
+
+        self.resolve_local_calls();
+        self.link_type_references();
+        self.mark_entry_points();
+
+        self.record_depth_truncation();
+
+        Ok((self.nodes, self.edges))
+    }
+
+    /// Flag top-level `main` functions with `"entry_point": true`
+    fn mark_entry_points(&mut self) {
+        for node in &mut self.nodes {
+            if node.kind == NodeKind::Function && node.name == "main" {
+                node.metadata["entry_point"] = json!(true);
+            }
+        }
+    }
+
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = json!({});
+            }
+            module.metadata["depth_truncation"] = json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
+    /// Process a tree-sitter node recursively
+    fn process_node(
+        &mut self,
+        ts_node: &TSNode,
+        parent_id: Option<NodeId>,
+        depth: usize,
+    ) -> Result<Option<NodeId>> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(None);
+        }
+
+        let universal_node = match ts_node.kind() {
+            "package_header" => self.process_package_header(ts_node)?,
+            "import" => self.process_import(ts_node)?,
+            "class_declaration" => self.process_class_declaration(ts_node)?,
+            "object_declaration" | "companion_object" => self.process_object(ts_node)?,
+            "function_declaration" => self.process_function_declaration(ts_node)?,
+            "call_expression" => self.process_call_expression(ts_node)?,
+            _ => {
+                // For unhandled node types, still process children
+                None
+            }
+        };
+
+        // Add edge from parent to this node
+        if let (Some(parent), Some(node_id)) = (parent_id, &universal_node) {
+            let kind = match ts_node.kind() {
+                "import" => EdgeKind::Imports,
+                "call_expression" => EdgeKind::Calls,
+                _ => EdgeKind::Contains,
+            };
+            self.edges.push(Edge::new(parent, *node_id, kind));
+        }
+
+        // Process children
+        let mut cursor = ts_node.walk();
+        for child in ts_node.children(&mut cursor) {
+            let child_parent = universal_node.or(parent_id);
+            self.process_node(&child, child_parent, depth + 1)?;
+        }
+
+        Ok(universal_node)
+    }
+
+    /// Get the text content of a node
+    fn node_text(&self, node: &TSNode) -> String {
+        node.utf8_text(self.content.as_bytes())
+            .unwrap_or("")
+            .to_string()
+    }
+
+    /// Get the text of a named field of a node
+    fn field_text(&self, node: &TSNode, field: &str) -> Option<String> {
+        node.child_by_field_name(field)
+            .filter(|n| !n.is_missing())
+            .map(|n| self.node_text(&n))
+    }
+
+    /// First child of the given kind, including anonymous tokens like `interface`
+    fn child_of_kind<'a>(node: &TSNode<'a>, kind: &str) -> Option<TSNode<'a>> {
+        let mut cursor = node.walk();
+        let child = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == kind);
+        child
+    }
+
+    /// Push a node and remember its tree-sitter mapping
+    fn push_node(&mut self, ts_node: &TSNode, node: Node) -> NodeId {
+        let node_id = node.id;
+        self.nodes.push(node);
+        self.node_mappings.insert(ts_node.id(), node_id);
+        node_id
+    }
+
+    /// Modifier keywords (`data`, `private`, `override`, ...) and annotations
+    fn modifiers(&self, ts_node: &TSNode) -> (Vec<String>, Vec<String>) {
+        let mut modifiers = Vec::new();
+        let mut annotations = Vec::new();
+        if let Some(list) = Self::child_of_kind(ts_node, "modifiers") {
+            let mut cursor = list.walk();
+            for child in list.named_children(&mut cursor) {
+                if child.kind() == "annotation" {
+                    annotations.push(self.node_text(&child));
+                } else {
+                    modifiers.push(self.node_text(&child));
+                }
+            }
+        }
+        (modifiers, annotations)
+    }
+
+    /// Declared visibility; Kotlin declarations are public by default
+    fn visibility(modifiers: &[String]) -> &'static str {
+        ["private", "protected", "internal"]
+            .into_iter()
+            .find(|visibility| modifiers.iter().any(|m| m == visibility))
+            .unwrap_or("public")
+    }
+
+    /// Simple name of the type a type expression refers to, without
+    /// qualifiers, type arguments or nullability
+    fn type_name(&self, node: &TSNode) -> Option<String> {
+        match node.kind() {
+            "user_type" => {
+                let mut cursor = node.walk();
+                let name = node
+                    .named_children(&mut cursor)
+                    .filter(|child| matches!(child.kind(), "identifier" | "type_identifier"))
+                    .last()
+                    .map(|child| self.node_text(&child));
+                name
+            }
+            "identifier" | "type_identifier" => Some(self.node_text(node)),
+            _ => {
+                let mut cursor = node.walk();
+                let name = node
+                    .named_children(&mut cursor)
+                    .find_map(|child| self.type_name(&child));
+                name
+            }
+        }
+    }
+
+    /// Process package header
+    fn process_package_header(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let package_name = Self::child_of_kind(ts_node, "qualified_identifier")
+            .or_else(|| Self::child_of_kind(ts_node, "identifier"))
+            .map(|name| self.node_text(&name))
+            .unwrap_or_default();
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Package,
+            package_name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "package_name": package_name,
+            "type": "package_header"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process an import directive, e.g. `import a.b.C as D` or `import a.b.*`
+    fn process_import(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let Some(path) = Self::child_of_kind(ts_node, "qualified_identifier")
+            .or_else(|| Self::child_of_kind(ts_node, "identifier"))
+        else {
+            return Ok(None);
+        };
+        let import_path = self.node_text(&path);
+        let is_wildcard = Self::child_of_kind(ts_node, "*").is_some();
+        let alias = Self::child_of_kind(ts_node, "as")
+            .and_then(|keyword| keyword.next_named_sibling())
+            .map(|alias| self.node_text(&alias));
+        let name = if is_wildcard {
+            format!("{import_path}.*")
+        } else {
+            import_path.clone()
+        };
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Import,
+            name,
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "import_path": import_path,
+            "alias": alias,
+            "is_wildcard": is_wildcard,
+            "type": "import"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process class, data class, enum class and interface declarations
+    fn process_class_declaration(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let (modifiers, annotations) = self.modifiers(ts_node);
+        let kind = if Self::child_of_kind(ts_node, "interface").is_some() {
+            NodeKind::Interface
+        } else if modifiers.iter().any(|m| m == "data") {
+            NodeKind::DataClass
+        } else if modifiers.iter().any(|m| m == "enum") {
+            NodeKind::EnumClass
+        } else {
+            NodeKind::Class
+        };
+        let supertypes = self.supertypes(ts_node, kind == NodeKind::Interface);
+        let properties = self.constructor_properties(ts_node);
+
+        let node = Node::new(
+            &self.repo_id,
+            kind,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "class_name": name,
+            "modifiers": modifiers,
+            "annotations": annotations,
+            "visibility": Self::visibility(&modifiers),
+            "supertypes": supertypes.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            "properties": properties,
+            "type": "class_declaration"
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        self.register_type(node_id, name, supertypes);
+        Ok(Some(node_id))
+    }
+
+    /// Process `object` declarations and companion objects
+    fn process_object(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let is_companion = ts_node.kind() == "companion_object";
+        let name = self
+            .field_text(ts_node, "name")
+            .unwrap_or_else(|| "Companion".to_string());
+        let (modifiers, annotations) = self.modifiers(ts_node);
+        let supertypes = self.supertypes(ts_node, false);
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Object,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "object_name": name,
+            "is_companion": is_companion,
+            "modifiers": modifiers,
+            "annotations": annotations,
+            "visibility": Self::visibility(&modifiers),
+            "supertypes": supertypes.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            "type": ts_node.kind()
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        if is_companion {
+            // Companion names repeat across classes and are never referenced bare
+            self.supertypes.extend(
+                supertypes
+                    .into_iter()
+                    .map(|(name, kind)| (node_id, name, kind)),
+            );
+        } else {
+            self.register_type(node_id, name, supertypes);
+        }
+        Ok(Some(node_id))
+    }
+
+    /// Remember a declared type and the supertypes it names
+    fn register_type(
+        &mut self,
+        node_id: NodeId,
+        name: String,
+        supertypes: Vec<(String, EdgeKind)>,
+    ) {
+        self.types.entry(name).or_insert(node_id);
+        self.supertypes.extend(
+            supertypes
+                .into_iter()
+                .map(|(name, kind)| (node_id, name, kind)),
+        );
+    }
+
+    /// Supertypes listed after `:`
+    ///
+    /// A supertype called with constructor arguments is the superclass; the
+    /// others are interfaces, which an interface itself extends.
+    fn supertypes(&self, ts_node: &TSNode, is_interface: bool) -> Vec<(String, EdgeKind)> {
+        let Some(list) = Self::child_of_kind(ts_node, "delegation_specifiers") else {
+            return Vec::new();
+        };
+
+        let mut cursor = list.walk();
+        let specifiers: Vec<_> = list.named_children(&mut cursor).collect();
+        specifiers
+            .iter()
+            .filter_map(|specifier| {
+                let mut cursor = specifier.walk();
+                let supertype = specifier.named_children(&mut cursor).next()?;
+                let kind = if supertype.kind() == "constructor_invocation" || is_interface {
+                    EdgeKind::Extends
+                } else {
+                    EdgeKind::Implements
+                };
+                Some((self.type_name(&supertype)?, kind))
+            })
+            .collect()
+    }
+
+    /// Properties declared with `val`/`var` in the primary constructor
+    fn constructor_properties(&self, ts_node: &TSNode) -> Vec<String> {
+        let Some(parameters) = Self::child_of_kind(ts_node, "primary_constructor")
+            .and_then(|constructor| Self::child_of_kind(&constructor, "class_parameters"))
+        else {
+            return Vec::new();
+        };
+
+        let mut cursor = parameters.walk();
+        let properties = parameters
+            .named_children(&mut cursor)
+            .filter(|parameter| {
+                Self::child_of_kind(parameter, "val").is_some()
+                    || Self::child_of_kind(parameter, "var").is_some()
+            })
+            .filter_map(|parameter| {
+                Self::child_of_kind(&parameter, "identifier").map(|name| self.node_text(&name))
+            })
+            .collect();
+        properties
+    }
+
+    /// Process function declarations, including extension functions
+    fn process_function_declaration(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let mut name = None;
+        let mut receiver = None;
+        let mut type_parameters = None;
+        let mut parameters = String::new();
+        let mut return_type = None;
+        let mut seen_parameters = false;
+
+        let mut cursor = ts_node.walk();
+        for (index, child) in ts_node.children(&mut cursor).enumerate() {
+            if ts_node.field_name_for_child(index as u32) == Some("name") {
+                if !child.is_missing() {
+                    name = Some(self.node_text(&child));
+                }
+                continue;
+            }
+            match child.kind() {
+                "type_parameters" => type_parameters = Some(self.node_text(&child)),
+                "function_value_parameters" => {
+                    parameters = self.node_text(&child);
+                    seen_parameters = true;
+                }
+                kind if TYPE_KINDS.contains(&kind) => {
+                    if seen_parameters {
+                        return_type = Some(self.node_text(&child));
+                    } else {
+                        receiver = Some(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // The grammar reads `fun Circle.area()` as a qualified receiver type
+        // `Circle.area` with a missing name, so split the name back off
+        let (name, receiver) = match (name.filter(|n| !n.is_empty()), receiver) {
+            (Some(name), receiver) => {
+                let receiver = receiver.and_then(|receiver| {
+                    Some((self.node_text(&receiver), self.type_name(&receiver)?))
+                });
+                (name, receiver)
+            }
+            (None, Some(receiver)) => {
+                let mut cursor = receiver.walk();
+                let parts: Vec<_> = receiver
+                    .named_children(&mut cursor)
+                    .filter(|child| child.kind() == "identifier")
+                    .collect();
+                match parts.as_slice() {
+                    [.., receiver_type, function_name] => {
+                        let receiver_text = self.content
+                            [receiver.start_byte()..receiver_type.end_byte()]
+                            .to_string();
+                        (
+                            self.node_text(function_name),
+                            Some((receiver_text, self.node_text(receiver_type))),
+                        )
+                    }
+                    _ => (String::new(), None),
+                }
+            }
+            (None, None) => (String::new(), None),
+        };
+
+        let kind = match ts_node.parent().map(|parent| parent.kind()) {
+            Some("class_body" | "enum_class_body") => NodeKind::Method,
+            _ => NodeKind::Function,
+        };
+        let (modifiers, annotations) = self.modifiers(ts_node);
+
+        let mut signature = String::from("fun ");
+        if let Some(type_parameters) = &type_parameters {
+            signature.push_str(type_parameters);
+            signature.push(' ');
+        }
+        if let Some((receiver_text, _)) = &receiver {
+            signature.push_str(receiver_text);
+            signature.push('.');
+        }
+        signature.push_str(&name);
+        signature.push_str(&parameters);
+        if let Some(return_type) = &return_type {
+            signature.push_str(": ");
+            signature.push_str(return_type);
+        }
+
+        let extension_receiver = receiver.map(|(_, receiver_type)| receiver_type);
+        let node = Node::new(
+            &self.repo_id,
+            kind,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_signature(signature)
+        .with_metadata(json!({
+            "function_name": name,
+            "is_extension": extension_receiver.is_some(),
+            "extension_receiver": extension_receiver,
+            "modifiers": modifiers,
+            "annotations": annotations,
+            "visibility": Self::visibility(&modifiers),
+            "parameters": parameters,
+            "return_type": return_type,
+            "type": "function_declaration"
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        if let Some(receiver_type) = extension_receiver {
+            self.extensions.push((node_id, receiver_type));
+        }
+        Ok(Some(node_id))
+    }
+
+    /// Process call expressions, including constructor calls
+    fn process_call_expression(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let Some(function) = ts_node.named_child(0) else {
+            return Ok(None);
+        };
+
+        let callee = self.node_text(&function);
+        let name = match function.kind() {
+            "navigation_expression" => function
+                .named_child(function.named_child_count().saturating_sub(1))
+                .map(|member| {
+                    self.node_text(&member)
+                        .trim_start_matches(['?', '.'])
+                        .to_string()
+                })
+                .unwrap_or_default(),
+            _ => callee.clone(),
+        };
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Call,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "function_name": name,
+            "callee": callee,
+            "type": "call_expression"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Link calls to functions, and constructor calls to classes, declared in
+    /// the same file
+    fn resolve_local_calls(&mut self) {
+        let mut definitions: HashMap<&str, NodeId> = HashMap::new();
+        for node in &self.nodes {
+            if matches!(node.kind, NodeKind::Function | NodeKind::Method) {
+                definitions.entry(node.name.as_str()).or_insert(node.id);
+            }
+        }
+        for (name, type_id) in &self.types {
+            definitions.entry(name.as_str()).or_insert(*type_id);
+        }
+
+        let resolved: Vec<Edge> = self
+            .nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Call)
+            .filter_map(|call| {
+                definitions
+                    .get(call.name.as_str())
+                    .map(|target| Edge::new(call.id, *target, EdgeKind::Calls))
+            })
+            .collect();
+
+        self.edges.extend(resolved);
+    }
+
+    /// Link supertypes and extension receivers to types declared in the same file
+    ///
+    /// Types declared elsewhere stay unlinked here; extension functions keep
+    /// their receiver in the `extension_receiver` metadata so it can be
+    /// resolved across files.
+    fn link_type_references(&mut self) {
+        for (source, type_name, kind) in &self.supertypes {
+            if let Some(target) = self.types.get(type_name) {
+                self.edges.push(Edge::new(*source, *target, *kind));
+            }
+        }
+        for (function, receiver_type) in &self.extensions {
+            if let Some(target) = self.types.get(receiver_type) {
+                self.edges
+                    .push(Edge::new(*function, *target, EdgeKind::ExtensionOf));
+            }
+        }
+    }
+}
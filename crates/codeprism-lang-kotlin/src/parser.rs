@@ -0,0 +1,98 @@
+//! Kotlin parser implementation
+
+use crate::ast_mapper::{AstMapper, DEFAULT_MAX_DEPTH};
+use crate::error::{Error, Result};
+use crate::types::{Edge, Language, Node};
+use std::path::{Path, PathBuf};
+use tree_sitter::{Parser, Tree};
+
+/// Parse context for Kotlin files
+#[derive(Debug, Clone)]
+pub struct ParseContext {
+    /// Repository ID
+    pub repo_id: String,
+    /// File path being parsed
+    pub file_path: PathBuf,
+    /// Previous tree for incremental parsing
+    pub old_tree: Option<Tree>,
+    /// File content
+    pub content: String,
+}
+
+/// Parse result containing nodes and edges
+#[derive(Debug)]
+pub struct ParseResult {
+    /// The parsed tree
+    pub tree: Tree,
+    /// Extracted nodes
+    pub nodes: Vec<Node>,
+    /// Extracted edges
+    pub edges: Vec<Edge>,
+}
+
+/// Kotlin parser
+pub struct KotlinParser {
+    /// Tree-sitter parser for Kotlin
+    parser: Parser,
+    /// Deepest tree level converted into nodes
+    max_depth: usize,
+}
+
+impl KotlinParser {
+    /// Create a new Kotlin parser
+    pub fn new() -> Self {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_kotlin_ng::LANGUAGE.into())
+            .expect("Failed to load Kotlin grammar");
+
+        Self {
+            parser,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels when extracting nodes
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Get the language for a file based on its extension
+    pub fn detect_language(path: &Path) -> Language {
+        // `.kt` sources and `.kts` scripts share one grammar
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("kt" | "kts") => Language::Kotlin,
+            _ => Language::Kotlin,
+        }
+    }
+
+    /// Parse a Kotlin file
+    pub fn parse(&mut self, context: &ParseContext) -> Result<ParseResult> {
+        let language = Self::detect_language(&context.file_path);
+
+        // Parse the file
+        let tree = self
+            .parser
+            .parse(&context.content, context.old_tree.as_ref())
+            .ok_or_else(|| Error::parse(&context.file_path, "Failed to parse file"))?;
+
+        // Extract nodes and edges
+        let mapper = AstMapper::new(
+            &context.repo_id,
+            context.file_path.clone(),
+            language,
+            &context.content,
+        )
+        .with_max_depth(self.max_depth);
+
+        let (nodes, edges) = mapper.extract(&tree)?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+impl Default for KotlinParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,156 @@
+//! Integration tests for Kotlin parser
+
+use codeprism_lang_kotlin::{
+    EdgeKind, KotlinParser, Language, NodeKind, ParseContext, ParseResult,
+};
+use std::path::{Path, PathBuf};
+
+fn parse(file_name: &str, code: &str) -> ParseResult {
+    let mut parser = KotlinParser::new();
+    let context = ParseContext {
+        repo_id: "test-repo".to_string(),
+        file_path: PathBuf::from(file_name),
+        old_tree: None,
+        content: code.to_string(),
+    };
+    parser.parse(&context).expect("Failed to parse Kotlin file")
+}
+
+#[test]
+fn test_detect_language() {
+    assert_eq!(
+        KotlinParser::detect_language(Path::new("Main.kt")),
+        Language::Kotlin
+    );
+    assert_eq!(
+        KotlinParser::detect_language(Path::new("build.gradle.kts")),
+        Language::Kotlin
+    );
+}
+
+#[test]
+fn test_parse_classes_objects_and_imports() {
+    let kotlin_code = r#"
+package com.example.shapes
+
+import kotlin.math.PI
+import com.example.util.*
+import com.example.Logger as Log
+
+interface Shape {
+    fun area(): Double
+}
+
+open class Base
+
+data class Circle(val radius: Double) : Base(), Shape {
+    override fun area(): Double = PI * radius * radius
+
+    companion object {
+        fun unit() = Circle(1.0)
+    }
+}
+
+enum class Color { RED, GREEN }
+
+object Registry {
+    private fun register(shape: Shape) {
+        println(shape.area())
+    }
+}
+"#;
+
+    let result = parse("Shapes.kt", kotlin_code);
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    assert_eq!(find("com.example.shapes").kind, NodeKind::Package);
+
+    let imports: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Import)
+        .collect();
+    let names: Vec<_> = imports.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["kotlin.math.PI", "com.example.util.*", "com.example.Logger"]
+    );
+    assert_eq!(imports[1].metadata["is_wildcard"], true);
+    assert_eq!(imports[2].metadata["alias"], "Log");
+
+    assert_eq!(find("Shape").kind, NodeKind::Interface);
+    assert_eq!(find("Color").kind, NodeKind::EnumClass);
+    assert_eq!(find("Registry").kind, NodeKind::Object);
+    assert_eq!(find("Companion").metadata["is_companion"], true);
+
+    let circle = find("Circle");
+    assert_eq!(circle.kind, NodeKind::DataClass);
+    assert_eq!(circle.metadata["properties"][0], "radius");
+    assert!(result.edges.iter().any(|e| e.source == circle.id
+        && e.target == find("Base").id
+        && e.kind == EdgeKind::Extends));
+    assert!(result.edges.iter().any(|e| e.source == circle.id
+        && e.target == find("Shape").id
+        && e.kind == EdgeKind::Implements));
+
+    let register = find("register");
+    assert_eq!(register.kind, NodeKind::Method);
+    assert_eq!(register.metadata["visibility"], "private");
+    assert_eq!(
+        register.signature.as_deref(),
+        Some("fun register(shape: Shape)")
+    );
+}
+
+#[test]
+fn test_extension_functions_attach_to_receiver() {
+    let kotlin_code = r#"
+class Circle(val radius: Double)
+
+fun Circle.diameter(): Double = radius * 2
+
+fun <T> List<T>.second(): T = this[1]
+
+fun main() {
+    Circle(2.0).diameter()
+}
+"#;
+
+    let result = parse("Extensions.kt", kotlin_code);
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+    let diameter = find("diameter");
+    assert_eq!(diameter.kind, NodeKind::Function);
+    assert_eq!(diameter.metadata["is_extension"], true);
+    assert_eq!(diameter.metadata["extension_receiver"], "Circle");
+    assert_eq!(
+        diameter.signature.as_deref(),
+        Some("fun Circle.diameter(): Double")
+    );
+    assert!(result.edges.iter().any(|e| e.source == diameter.id
+        && e.target == find("Circle").id
+        && e.kind == EdgeKind::ExtensionOf));
+
+    // Receivers declared elsewhere are only recorded for cross-file resolution
+    let second = find("second");
+    assert_eq!(second.metadata["extension_receiver"], "List");
+    assert_eq!(
+        second.signature.as_deref(),
+        Some("fun <T> List<T>.second(): T")
+    );
+    assert!(!result
+        .edges
+        .iter()
+        .any(|e| e.source == second.id && e.kind == EdgeKind::ExtensionOf));
+
+    assert_eq!(find("main").metadata["is_extension"], false);
+    let call = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Call && n.name == "diameter")
+        .expect("Should have a call to diameter");
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.source == call.id && e.target == diameter.id && e.kind == EdgeKind::Calls));
+}
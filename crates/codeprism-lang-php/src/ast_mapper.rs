@@ -0,0 +1,824 @@
+//! AST mapping from tree-sitter PHP CST to Universal AST
+
+use crate::error::Result;
+use crate::types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tree_sitter::{Node as TSNode, Tree};
+
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// `Route::` facade methods that declare a route for the HTTP method they name
+const ROUTE_METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "options", "any"];
+
+/// Expressions that pull another file in
+const REQUIRE_KINDS: &[&str] = &[
+    "require_expression",
+    "require_once_expression",
+    "include_expression",
+    "include_once_expression",
+];
+
+/// Maps tree-sitter PHP CST to Universal AST
+pub struct AstMapper {
+    /// Repository ID
+    repo_id: String,
+    /// File path
+    file_path: PathBuf,
+    /// Language
+    language: Language,
+    /// Source content
+    content: String,
+    /// Collected nodes
+    nodes: Vec<Node>,
+    /// Collected edges
+    edges: Vec<Edge>,
+    /// Node ID mappings (tree-sitter node ID -> Universal AST node ID)
+    node_mappings: HashMap<usize, NodeId>,
+    /// Namespace declared most recently, applied to the declarations after it
+    namespace: Option<String>,
+    /// Classes, interfaces, traits and enums declared in the file, by name
+    types: HashMap<String, NodeId>,
+    /// Supertypes and traits named by each declaration, linked once all types are known
+    supertypes: Vec<(NodeId, String, EdgeKind)>,
+    /// Methods declared in the file, by declaring type and method name
+    methods: HashMap<(String, String), NodeId>,
+    /// Routes and the controller and action handling them
+    routes: Vec<(NodeId, Option<String>, String)>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
+}
+
+impl AstMapper {
+    /// Create a new AST mapper
+    pub fn new(repo_id: &str, file_path: PathBuf, language: Language, content: &str) -> Self {
+        Self {
+            repo_id: repo_id.to_string(),
+            file_path,
+            language,
+            content: content.to_string(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_mappings: HashMap::new(),
+            namespace: None,
+            types: HashMap::new(),
+            supertypes: Vec::new(),
+            methods: HashMap::new(),
+            routes: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Extract nodes and edges from the tree
+    pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
+        let root = tree.root_node();
+
+        // Create module node for the file
+        let module_span = Span::from_node(&root);
+        let file_name = self
+            .file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let module_node = Node::new(
+            &self.repo_id,
+            NodeKind::Module,
+            file_name,
+            self.language,
+            self.file_path.clone(),
+            module_span,
+        )
+        .with_metadata(json!({
+            "type": "program",
+            "file_path": self.file_path.display().to_string()
+        }));
+
+        let module_id = module_node.id;
+        self.nodes.push(module_node);
+        self.node_mappings.insert(root.id(), module_id);
+
+        // Process all child nodes
+        self.process_node(&root, Some(module_id), 0)?;
+
+        self.resolve_local_calls();
+        self.link_type_references();
+        self.link_routes();
+        if self.runs_code(&root) {
+            self.nodes[0].metadata["entry_point"] = json!(true);
+        }
+
+        self.record_depth_truncation();
+
+        Ok((self.nodes, self.edges))
+    }
+
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = json!({});
+            }
+            module.metadata["depth_truncation"] = json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
+    /// Process a tree-sitter node recursively
+    fn process_node(
+        &mut self,
+        ts_node: &TSNode,
+        parent_id: Option<NodeId>,
+        depth: usize,
+    ) -> Result<Option<NodeId>> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(None);
+        }
+
+        let universal_node = match ts_node.kind() {
+            "namespace_definition" => self.process_namespace(ts_node)?,
+            "namespace_use_clause" => self.process_use_clause(ts_node)?,
+            kind if REQUIRE_KINDS.contains(&kind) => self.process_require(ts_node)?,
+            "class_declaration"
+            | "interface_declaration"
+            | "trait_declaration"
+            | "enum_declaration" => self.process_type_declaration(ts_node)?,
+            "use_declaration" => {
+                self.process_trait_use(ts_node, parent_id);
+                None
+            }
+            "function_definition" | "method_declaration" => self.process_function(ts_node)?,
+            "scoped_call_expression" => match self.process_route(ts_node)? {
+                Some(route) => Some(route),
+                None => self.process_call(ts_node)?,
+            },
+            "function_call_expression"
+            | "member_call_expression"
+            | "nullsafe_member_call_expression"
+            | "object_creation_expression" => self.process_call(ts_node)?,
+            _ => {
+                // For unhandled node types, still process children
+                None
+            }
+        };
+
+        // Add edge from parent to this node
+        if let (Some(parent), Some(node_id)) = (parent_id, &universal_node) {
+            let kind = match self.nodes.last().map(|node| node.kind) {
+                Some(NodeKind::Import) => EdgeKind::Imports,
+                Some(NodeKind::Call) => EdgeKind::Calls,
+                _ => EdgeKind::Contains,
+            };
+            self.edges.push(Edge::new(parent, *node_id, kind));
+        }
+
+        // Process children
+        let mut cursor = ts_node.walk();
+        for child in ts_node.children(&mut cursor) {
+            let child_parent = universal_node.or(parent_id);
+            self.process_node(&child, child_parent, depth + 1)?;
+        }
+
+        Ok(universal_node)
+    }
+
+    /// Get the text content of a node
+    fn node_text(&self, node: &TSNode) -> String {
+        node.utf8_text(self.content.as_bytes())
+            .unwrap_or("")
+            .to_string()
+    }
+
+    /// Get the text of a named field of a node
+    fn field_text(&self, node: &TSNode, field: &str) -> Option<String> {
+        node.child_by_field_name(field)
+            .filter(|n| !n.is_missing())
+            .map(|n| self.node_text(&n))
+    }
+
+    /// First child of the given kind, including anonymous tokens like `static`
+    fn child_of_kind<'a>(node: &TSNode<'a>, kind: &str) -> Option<TSNode<'a>> {
+        let mut cursor = node.walk();
+        let child = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == kind);
+        child
+    }
+
+    /// Whether a file or namespace body runs code at the top level
+    ///
+    /// Such files are entry points, flagged on the module node with
+    /// `"entry_point": true`. Files that only declare types, functions and
+    /// constants, import names and include other files do not run code.
+    fn runs_code(&self, statements: &TSNode) -> bool {
+        let mut cursor = statements.walk();
+        let runs_code =
+            statements
+                .named_children(&mut cursor)
+                .any(|statement| match statement.kind() {
+                    "php_tag"
+                    | "comment"
+                    | "namespace_use_declaration"
+                    | "class_declaration"
+                    | "interface_declaration"
+                    | "trait_declaration"
+                    | "enum_declaration"
+                    | "function_definition"
+                    | "const_declaration"
+                    | "declare_statement" => false,
+                    "namespace_definition" => statement
+                        .child_by_field_name("body")
+                        .is_some_and(|body| self.runs_code(&body)),
+                    "expression_statement" => !statement
+                        .named_child(0)
+                        .is_some_and(|expression| REQUIRE_KINDS.contains(&expression.kind())),
+                    // Inline HTML outside the PHP tags is output
+                    "text" | "text_interpolation" => !self
+                        .node_text(&statement)
+                        .replace("?>", "")
+                        .replace("<?php", "")
+                        .trim()
+                        .is_empty(),
+                    _ => true,
+                });
+        runs_code
+    }
+
+    /// Push a node and remember its tree-sitter mapping
+    fn push_node(&mut self, ts_node: &TSNode, node: Node) -> NodeId {
+        let node_id = node.id;
+        self.nodes.push(node);
+        self.node_mappings.insert(ts_node.id(), node_id);
+        node_id
+    }
+
+    /// Last segment of a possibly namespaced name, e.g. `User` for `\App\Models\User`
+    fn short_name(name: &str) -> &str {
+        name.rsplit('\\').next().unwrap_or(name)
+    }
+
+    /// Type names listed by a `base_clause` or `class_interface_clause`
+    fn clause_names(&self, clause: &TSNode) -> Vec<String> {
+        let mut cursor = clause.walk();
+        let names = clause
+            .named_children(&mut cursor)
+            .filter(|child| matches!(child.kind(), "name" | "qualified_name"))
+            .map(|child| Self::short_name(&self.node_text(&child)).to_string())
+            .collect();
+        names
+    }
+
+    /// Contents of a string literal, `None` for other expressions
+    fn string_value(&self, node: &TSNode) -> Option<String> {
+        match node.kind() {
+            "string" | "encapsed_string" => {
+                let text = self.node_text(node);
+                Some(text.trim_matches(['\'', '"']).to_string())
+            }
+            // `argument` and `array_element_initializer` wrap the value
+            "argument" | "array_element_initializer" => node
+                .named_child(0)
+                .and_then(|value| self.string_value(&value)),
+            _ => None,
+        }
+    }
+
+    /// Arguments of a call, unwrapped from their `argument` nodes
+    fn call_arguments<'a>(ts_node: &TSNode<'a>) -> Vec<TSNode<'a>> {
+        let Some(arguments) = ts_node.child_by_field_name("arguments") else {
+            return Vec::new();
+        };
+        let mut cursor = arguments.walk();
+        let values = arguments
+            .named_children(&mut cursor)
+            .filter(|argument| argument.kind() == "argument")
+            .filter_map(|argument| argument.named_child(0))
+            .collect();
+        values
+    }
+
+    /// Process a namespace declaration
+    fn process_namespace(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        self.namespace = Some(name.clone()).filter(|name| !name.is_empty());
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Namespace,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "namespace": name,
+            "type": "namespace_definition"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process one clause of a `use` import, e.g. `App\Models\User as Model`
+    ///
+    /// Clauses inside a group (`use App\{A, B}`) are prefixed with the group's
+    /// namespace.
+    fn process_use_clause(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let Some(path) = Self::child_of_kind(ts_node, "qualified_name")
+            .or_else(|| Self::child_of_kind(ts_node, "name"))
+        else {
+            return Ok(None);
+        };
+        let mut import_path = self.node_text(&path);
+        let declaration = ts_node
+            .parent()
+            .filter(|parent| parent.kind() == "namespace_use_group")
+            .and_then(|group| group.parent());
+        if let Some(prefix) = declaration
+            .as_ref()
+            .and_then(|declaration| Self::child_of_kind(declaration, "namespace_name"))
+        {
+            import_path = format!("{}\\{import_path}", self.node_text(&prefix));
+        }
+        let import_path = import_path.trim_start_matches('\\').to_string();
+        let alias = self.field_text(ts_node, "alias");
+        // `use function` and `use const` set the type on the clause or the declaration
+        let import_type = self
+            .field_text(ts_node, "type")
+            .or_else(|| {
+                declaration
+                    .or_else(|| ts_node.parent())
+                    .and_then(|declaration| self.field_text(&declaration, "type"))
+            })
+            .unwrap_or_else(|| "class".to_string());
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Import,
+            import_path.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "import_path": import_path,
+            "alias": alias,
+            "import_type": import_type,
+            "type": "namespace_use_clause"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process `require`, `require_once`, `include` and `include_once`
+    ///
+    /// The import is named by the last string literal in the path expression,
+    /// so `__DIR__ . '/bootstrap.php'` imports `/bootstrap.php`.
+    fn process_require(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let Some(expression) = ts_node.named_child(0) else {
+            return Ok(None);
+        };
+        let expression_text = self.node_text(&expression);
+        let path = self
+            .last_string(&expression)
+            .unwrap_or_else(|| expression_text.clone());
+        let keyword = ts_node.kind().trim_end_matches("_expression");
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Import,
+            path.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "import_path": path,
+            "expression": expression_text,
+            "import_type": keyword,
+            "type": ts_node.kind()
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Last string literal in an expression, searching depth first
+    fn last_string(&self, node: &TSNode) -> Option<String> {
+        if let Some(value) = self.string_value(node) {
+            return Some(value);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.named_children(&mut cursor).collect();
+        children
+            .iter()
+            .rev()
+            .find_map(|child| self.last_string(child))
+    }
+
+    /// Process class, interface, trait and enum declarations
+    fn process_type_declaration(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let kind = match ts_node.kind() {
+            "interface_declaration" => NodeKind::Interface,
+            "trait_declaration" => NodeKind::Trait,
+            "enum_declaration" => NodeKind::Enum,
+            _ => NodeKind::Class,
+        };
+        let modifiers = self.modifiers(ts_node);
+
+        let mut supertypes = Vec::new();
+        if let Some(clause) = Self::child_of_kind(ts_node, "base_clause") {
+            supertypes.extend(
+                self.clause_names(&clause)
+                    .into_iter()
+                    .map(|name| (name, EdgeKind::Extends)),
+            );
+        }
+        if let Some(clause) = Self::child_of_kind(ts_node, "class_interface_clause") {
+            supertypes.extend(
+                self.clause_names(&clause)
+                    .into_iter()
+                    .map(|name| (name, EdgeKind::Implements)),
+            );
+        }
+        let named = |edge_kind| {
+            supertypes
+                .iter()
+                .filter(|(_, kind)| *kind == edge_kind)
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let node = Node::new(
+            &self.repo_id,
+            kind,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "class_name": name,
+            "namespace": self.namespace,
+            "qualified_name": self.qualified_name(&name),
+            "modifiers": modifiers,
+            "extends": named(EdgeKind::Extends),
+            "implements": named(EdgeKind::Implements),
+            "type": ts_node.kind()
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        self.types.entry(name).or_insert(node_id);
+        self.supertypes.extend(
+            supertypes
+                .into_iter()
+                .map(|(name, kind)| (node_id, name, kind)),
+        );
+        Ok(Some(node_id))
+    }
+
+    /// Name prefixed with the current namespace, e.g. `App\Models\User`
+    fn qualified_name(&self, name: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{namespace}\\{name}"),
+            None => name.to_string(),
+        }
+    }
+
+    /// Modifier keywords (`abstract`, `final`, `public`, `static`, ...)
+    fn modifiers(&self, ts_node: &TSNode) -> Vec<String> {
+        let mut cursor = ts_node.walk();
+        let modifiers = ts_node
+            .named_children(&mut cursor)
+            .filter(|child| child.kind().ends_with("_modifier"))
+            .map(|child| self.node_text(&child))
+            .collect();
+        modifiers
+    }
+
+    /// Record the traits a class pulls in with `use A, B;`
+    fn process_trait_use(&mut self, ts_node: &TSNode, parent_id: Option<NodeId>) {
+        let Some(class_id) = parent_id else {
+            return;
+        };
+        let mut cursor = ts_node.walk();
+        let traits: Vec<String> = ts_node
+            .named_children(&mut cursor)
+            .filter(|child| matches!(child.kind(), "name" | "qualified_name"))
+            .map(|child| Self::short_name(&self.node_text(&child)).to_string())
+            .collect();
+
+        if let Some(class) = self.nodes.iter_mut().find(|node| node.id == class_id) {
+            if let Some(object) = class.metadata.as_object_mut() {
+                let used = object.entry("traits").or_insert_with(|| json!([]));
+                if let Some(used) = used.as_array_mut() {
+                    used.extend(traits.iter().map(|name| json!(name)));
+                }
+            }
+        }
+        self.supertypes.extend(
+            traits
+                .into_iter()
+                .map(|name| (class_id, name, EdgeKind::UsesTrait)),
+        );
+    }
+
+    /// Process function definitions and method declarations
+    fn process_function(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let parameters = self
+            .field_text(ts_node, "parameters")
+            .unwrap_or_else(|| "()".to_string());
+        let return_type = self.field_text(ts_node, "return_type");
+        let modifiers = self.modifiers(ts_node);
+        let is_method = ts_node.kind() == "method_declaration";
+        let declaring_type = is_method
+            .then(|| self.enclosing_type_name(ts_node))
+            .flatten();
+
+        let mut signature = format!("function {name}{parameters}");
+        if let Some(return_type) = &return_type {
+            signature.push_str(": ");
+            signature.push_str(return_type);
+        }
+        let visibility = ["private", "protected"]
+            .into_iter()
+            .find(|visibility| modifiers.iter().any(|m| m == visibility))
+            .unwrap_or("public");
+
+        let node = Node::new(
+            &self.repo_id,
+            if is_method {
+                NodeKind::Method
+            } else {
+                NodeKind::Function
+            },
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_signature(signature)
+        .with_metadata(json!({
+            "function_name": name,
+            "class_name": declaring_type,
+            "namespace": self.namespace,
+            "modifiers": modifiers,
+            "visibility": visibility,
+            "is_static": modifiers.iter().any(|m| m == "static"),
+            "parameters": parameters,
+            "return_type": return_type,
+            "type": ts_node.kind()
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        if let Some(declaring_type) = declaring_type {
+            self.methods
+                .entry((declaring_type, name))
+                .or_insert(node_id);
+        }
+        Ok(Some(node_id))
+    }
+
+    /// Name of the class, interface, trait or enum a declaration sits in
+    fn enclosing_type_name(&self, ts_node: &TSNode) -> Option<String> {
+        let mut current = ts_node.parent();
+        while let Some(node) = current {
+            if matches!(
+                node.kind(),
+                "class_declaration"
+                    | "interface_declaration"
+                    | "trait_declaration"
+                    | "enum_declaration"
+            ) {
+                return self.field_text(&node, "name");
+            }
+            current = node.parent();
+        }
+        None
+    }
+
+    /// Process function, method, static and constructor calls
+    fn process_call(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let is_constructor = ts_node.kind() == "object_creation_expression";
+        let (name, callee) = match ts_node.kind() {
+            "function_call_expression" => {
+                let callee = self.field_text(ts_node, "function").unwrap_or_default();
+                (Self::short_name(&callee).to_string(), callee)
+            }
+            "object_creation_expression" => {
+                let Some(class) = ts_node
+                    .named_children(&mut ts_node.walk())
+                    .find(|child| matches!(child.kind(), "name" | "qualified_name"))
+                else {
+                    // Anonymous classes and `new $class` have no name to link
+                    return Ok(None);
+                };
+                let callee = self.node_text(&class);
+                (Self::short_name(&callee).to_string(), callee)
+            }
+            _ => {
+                let name = self.field_text(ts_node, "name").unwrap_or_default();
+                let receiver = self
+                    .field_text(ts_node, "object")
+                    .or_else(|| self.field_text(ts_node, "scope"))
+                    .unwrap_or_default();
+                let separator = if ts_node.kind() == "scoped_call_expression" {
+                    "::"
+                } else {
+                    "->"
+                };
+                (name.clone(), format!("{receiver}{separator}{name}"))
+            }
+        };
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Call,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "function_name": name,
+            "callee": callee,
+            "is_constructor": is_constructor,
+            "type": ts_node.kind()
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process a Laravel route declaration such as `Route::get('/users', ...)`
+    ///
+    /// Returns `None` for static calls that do not declare a route.
+    fn process_route(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let scope = self.field_text(ts_node, "scope").unwrap_or_default();
+        let method = self.field_text(ts_node, "name").unwrap_or_default();
+        if Self::short_name(&scope) != "Route" {
+            return Ok(None);
+        }
+
+        let arguments = Self::call_arguments(ts_node);
+        let (http_methods, rest) = match method.as_str() {
+            "match" => {
+                let Some((methods, rest)) = arguments.split_first() else {
+                    return Ok(None);
+                };
+                let mut cursor = methods.walk();
+                let methods: Vec<String> = methods
+                    .named_children(&mut cursor)
+                    .filter_map(|element| self.string_value(&element))
+                    .map(|method| method.to_uppercase())
+                    .collect();
+                (methods, rest)
+            }
+            verb if ROUTE_METHODS.contains(&verb) => (vec![verb.to_uppercase()], &arguments[..]),
+            _ => return Ok(None),
+        };
+        let Some(path) = rest.first().and_then(|path| self.string_value(path)) else {
+            return Ok(None);
+        };
+        let handler = rest.get(1);
+        let action = handler.and_then(|handler| self.route_action(handler));
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Route,
+            format!("{} {path}", http_methods.join("|")),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "framework": "laravel",
+            "path": path,
+            "methods": http_methods,
+            "handler": handler.map(|handler| self.node_text(handler)),
+            "controller": action.as_ref().and_then(|(controller, _)| controller.clone()),
+            "action": action.as_ref().map(|(_, action)| action.clone()),
+            "type": "route"
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        if let Some((controller, action)) = action {
+            self.routes.push((node_id, controller, action));
+        }
+        Ok(Some(node_id))
+    }
+
+    /// Controller and action named by a route handler
+    ///
+    /// Understands `[UserController::class, 'index']`, `'UserController@index'`
+    /// and the name of a plain function.
+    fn route_action(&self, handler: &TSNode) -> Option<(Option<String>, String)> {
+        if handler.kind() == "array_creation_expression" {
+            let mut cursor = handler.walk();
+            let elements: Vec<_> = handler.named_children(&mut cursor).collect();
+            let [controller, action] = elements.as_slice() else {
+                return None;
+            };
+            let controller = controller
+                .named_child(0)
+                .filter(|access| access.kind() == "class_constant_access_expression")
+                .and_then(|access| access.named_child(0))
+                .map(|class| Self::short_name(&self.node_text(&class)).to_string())?;
+            return Some((Some(controller), self.string_value(action)?));
+        }
+
+        let handler = self.string_value(handler)?;
+        match handler.split_once('@') {
+            Some((controller, action)) => Some((
+                Some(Self::short_name(controller).to_string()),
+                action.to_string(),
+            )),
+            None => Some((None, handler)),
+        }
+    }
+
+    /// Link calls to functions and methods, and `new` expressions to classes,
+    /// declared in the same file
+    fn resolve_local_calls(&mut self) {
+        let mut definitions: HashMap<&str, NodeId> = HashMap::new();
+        for node in &self.nodes {
+            if matches!(node.kind, NodeKind::Function | NodeKind::Method) {
+                definitions.entry(node.name.as_str()).or_insert(node.id);
+            }
+        }
+        let types: HashMap<&str, NodeId> = self
+            .types
+            .iter()
+            .map(|(name, id)| (name.as_str(), *id))
+            .collect();
+
+        let resolved: Vec<Edge> = self
+            .nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Call)
+            .filter_map(|call| {
+                let target = if call.metadata["is_constructor"] == true {
+                    types.get(call.name.as_str())
+                } else {
+                    definitions.get(call.name.as_str())
+                };
+                target.map(|target| Edge::new(call.id, *target, EdgeKind::Calls))
+            })
+            .collect();
+
+        self.edges.extend(resolved);
+    }
+
+    /// Link supertypes and used traits to types declared in the same file
+    ///
+    /// Types declared elsewhere stay unlinked here; their names remain in the
+    /// `extends`, `implements` and `traits` metadata.
+    fn link_type_references(&mut self) {
+        for (source, type_name, kind) in &self.supertypes {
+            if let Some(target) = self.types.get(type_name) {
+                self.edges.push(Edge::new(*source, *target, *kind));
+            }
+        }
+    }
+
+    /// Link routes to the controller methods or functions that handle them
+    fn link_routes(&mut self) {
+        for (route, controller, action) in &self.routes {
+            let target = match controller {
+                Some(controller) => self
+                    .methods
+                    .get(&(controller.clone(), action.clone()))
+                    .copied(),
+                None => self
+                    .nodes
+                    .iter()
+                    .find(|node| node.kind == NodeKind::Function && &node.name == action)
+                    .map(|node| node.id),
+            };
+            if let Some(target) = target {
+                self.edges
+                    .push(Edge::new(*route, target, EdgeKind::RoutesTo));
+            }
+        }
+    }
+}
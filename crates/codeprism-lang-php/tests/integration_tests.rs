@@ -0,0 +1,225 @@
+//! Integration tests for PHP parser
+
+use codeprism_lang_php::{EdgeKind, Language, NodeKind, ParseContext, ParseResult, PhpParser};
+use std::path::{Path, PathBuf};
+
+fn parse(file_name: &str, code: &str) -> ParseResult {
+    let mut parser = PhpParser::new();
+    let context = ParseContext {
+        repo_id: "test-repo".to_string(),
+        file_path: PathBuf::from(file_name),
+        old_tree: None,
+        content: code.to_string(),
+    };
+    parser.parse(&context).expect("Failed to parse PHP file")
+}
+
+fn has_edge(result: &ParseResult, source: &str, target: &str, kind: EdgeKind) -> bool {
+    let id = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap().id;
+    let (source, target) = (id(source), id(target));
+    result
+        .edges
+        .iter()
+        .any(|e| e.source == source && e.target == target && e.kind == kind)
+}
+
+#[test]
+fn test_detect_language() {
+    assert_eq!(
+        PhpParser::detect_language(Path::new("index.php")),
+        Language::Php
+    );
+}
+
+#[test]
+fn test_parse_class_file_round_trip() {
+    let php_code = r#"<?php
+namespace App\Models;
+
+use App\Contracts\{Auditable, Loggable as Log};
+use function App\helpers\format_name;
+require_once __DIR__ . '/bootstrap.php';
+
+interface Named { public function name(): string; }
+
+trait HasTimestamps
+{
+    public function touch(): void {}
+}
+
+abstract class Model {}
+
+final class User extends Model implements Named, \JsonSerializable
+{
+    use HasTimestamps;
+
+    public function name(): string
+    {
+        $this->touch();
+        return format_name(static::label());
+    }
+
+    private static function label(): string { return 'user'; }
+}
+
+function make_user(): User { return new User(); }
+"#;
+
+    let result = parse("User.php", php_code);
+    let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+    let find_kind = |kind, name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.kind == kind && n.name == name)
+            .unwrap()
+    };
+
+    assert_eq!(find("User").kind, NodeKind::Module);
+    assert_eq!(find("App\\Models").kind, NodeKind::Namespace);
+
+    let imports: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Import)
+        .collect();
+    let names: Vec<_> = imports.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "App\\Contracts\\Auditable",
+            "App\\Contracts\\Loggable",
+            "App\\helpers\\format_name",
+            "/bootstrap.php",
+        ]
+    );
+    assert_eq!(imports[1].metadata["alias"], "Log");
+    assert_eq!(imports[2].metadata["import_type"], "function");
+    assert_eq!(imports[3].metadata["import_type"], "require_once");
+
+    let class = find_kind(NodeKind::Class, "User");
+    assert_eq!(class.metadata["qualified_name"], "App\\Models\\User");
+    assert_eq!(class.metadata["modifiers"], serde_json::json!(["final"]));
+    assert_eq!(
+        class.metadata["implements"],
+        serde_json::json!(["Named", "JsonSerializable"])
+    );
+    assert_eq!(
+        class.metadata["traits"],
+        serde_json::json!(["HasTimestamps"])
+    );
+    assert_eq!(find("HasTimestamps").kind, NodeKind::Trait);
+    assert_eq!(find("Named").kind, NodeKind::Interface);
+
+    let class_edge = |target: &str, kind| {
+        let target = find(target).id;
+        result
+            .edges
+            .iter()
+            .any(|e| e.source == class.id && e.target == target && e.kind == kind)
+    };
+    assert!(class_edge("Model", EdgeKind::Extends));
+    assert!(class_edge("Named", EdgeKind::Implements));
+    assert!(class_edge("HasTimestamps", EdgeKind::UsesTrait));
+
+    let label = find_kind(NodeKind::Method, "label");
+    assert_eq!(label.metadata["visibility"], "private");
+    assert_eq!(label.metadata["is_static"], true);
+    assert_eq!(label.metadata["class_name"], "User");
+    assert_eq!(label.signature.as_deref(), Some("function label(): string"));
+    assert_eq!(find("make_user").kind, NodeKind::Function);
+
+    // `new User()` links to the class, `$this->touch()` to the trait method
+    let constructor = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Call && n.metadata["is_constructor"] == true)
+        .unwrap();
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.source == constructor.id && e.target == class.id && e.kind == EdgeKind::Calls));
+    let touch_call = find_kind(NodeKind::Call, "touch").id;
+    let touch = find_kind(NodeKind::Method, "touch").id;
+    assert!(result
+        .edges
+        .iter()
+        .any(|e| e.source == touch_call && e.target == touch && e.kind == EdgeKind::Calls));
+}
+
+#[test]
+fn test_laravel_routes_link_to_handlers() {
+    let php_code = r#"<?php
+use Illuminate\Support\Facades\Route;
+
+class UserController
+{
+    public function index() {}
+    public function store() {}
+}
+
+function health() {}
+
+Route::get('/users', [UserController::class, 'index'])->name('users.index');
+Route::post("/users", 'UserController@store');
+Route::match(['get', 'head'], '/health', 'health');
+Route::any('/fallback', function () { return abort(404); });
+Cache::get('users');
+"#;
+
+    let result = parse("web.php", php_code);
+    let routes: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Route)
+        .collect();
+    let names: Vec<_> = routes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "GET /users",
+            "POST /users",
+            "GET|HEAD /health",
+            "ANY /fallback"
+        ]
+    );
+    assert_eq!(routes[0].metadata["framework"], "laravel");
+    assert_eq!(routes[0].metadata["controller"], "UserController");
+    assert_eq!(routes[1].metadata["action"], "store");
+
+    assert!(has_edge(&result, "GET /users", "index", EdgeKind::RoutesTo));
+    assert!(has_edge(
+        &result,
+        "POST /users",
+        "store",
+        EdgeKind::RoutesTo
+    ));
+    assert!(has_edge(
+        &result,
+        "GET|HEAD /health",
+        "health",
+        EdgeKind::RoutesTo
+    ));
+    // Other facades stay ordinary calls
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.kind == NodeKind::Call && n.metadata["callee"] == "Cache::get"));
+}
+
+#[test]
+fn test_depth_limit_truncates_instead_of_overflowing() {
+    let nested = format!("<?php\n$x = {}1{};\n", "(".repeat(2000), ")".repeat(2000));
+    let mut parser = PhpParser::new();
+    parser.set_max_depth(64);
+    let context = ParseContext {
+        repo_id: "test-repo".to_string(),
+        file_path: PathBuf::from("deep.php"),
+        old_tree: None,
+        content: nested,
+    };
+
+    let result = parser.parse(&context).unwrap();
+    let module = &result.nodes[0];
+    assert_eq!(module.metadata["depth_truncation"]["max_depth"], 64);
+}
@@ -15,6 +15,15 @@ impl PythonLanguageParser {
             parser: std::sync::Mutex::new(PythonParser::new()),
         }
     }
+
+    /// Create an adapter that skips subtrees deeper than `max_depth` levels
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        let mut parser = PythonParser::new();
+        parser.set_max_depth(max_depth);
+        Self {
+            parser: std::sync::Mutex::new(parser),
+        }
+    }
 }
 
 impl Default for PythonLanguageParser {
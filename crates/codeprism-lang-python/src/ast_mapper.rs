@@ -7,6 +7,12 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use tree_sitter::{Tree, TreeCursor};
 
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
 /// AST mapper for Python
 pub struct AstMapper {
     repo_id: String,
@@ -17,6 +23,10 @@ pub struct AstMapper {
     edges: Vec<Edge>,
     /// Map from tree-sitter node ID to our NodeId for edge creation
     node_map: HashMap<usize, crate::types::NodeId>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
 }
 
 impl AstMapper {
@@ -30,9 +40,17 @@ impl AstMapper {
             nodes: Vec::new(),
             edges: Vec::new(),
             node_map: HashMap::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
         }
     }
 
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     /// Extract nodes and edges from the tree
     pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
         let mut cursor = tree.walk();
@@ -42,11 +60,29 @@ impl AstMapper {
         self.nodes.push(module_node);
 
         // Walk the tree and extract nodes
-        self.walk_tree(&mut cursor)?;
+        self.walk_tree(&mut cursor, 0)?;
+
+        self.record_depth_truncation();
 
         Ok((self.nodes, self.edges))
     }
 
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = serde_json::json!({});
+            }
+            module.metadata["depth_truncation"] = serde_json::json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
     /// Create a module node for the file
     fn create_module_node(&mut self, cursor: &TreeCursor) -> Result<Node> {
         let root = cursor.node();
@@ -73,12 +109,16 @@ impl AstMapper {
     }
 
     /// Walk the tree and extract nodes
-    fn walk_tree(&mut self, cursor: &mut TreeCursor) -> Result<()> {
+    fn walk_tree(&mut self, cursor: &mut TreeCursor, depth: usize) -> Result<()> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(());
+        }
         self.visit_node(cursor)?;
 
         if cursor.goto_first_child() {
             loop {
-                self.walk_tree(cursor)?;
+                self.walk_tree(cursor, depth + 1)?;
                 if !cursor.goto_next_sibling() {
                     break;
                 }
@@ -640,7 +680,8 @@ impl AstMapper {
                         // Parameter with type annotation: param: Type
                         let mut param_parts = Vec::new();
 
-                        if let Some(name_node) = child.child_by_field_name("pattern") {
+                        // The name (or *args/**kwargs pattern) is the first, unnamed-field child
+                        if let Some(name_node) = child.named_child(0) {
                             param_parts.push(self.get_node_text(&name_node));
                         }
 
@@ -676,7 +717,7 @@ impl AstMapper {
                         // Parameter with both type and default: param: Type = default
                         let mut param_parts = Vec::new();
 
-                        if let Some(name_node) = child.child_by_field_name("pattern") {
+                        if let Some(name_node) = child.child_by_field_name("name") {
                             param_parts.push(self.get_node_text(&name_node));
                         }
 
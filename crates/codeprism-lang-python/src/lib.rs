@@ -5,12 +5,15 @@ mod analysis;
 mod ast_mapper;
 mod error;
 mod parser;
+mod patterns;
 mod types;
 
 pub use adapter::{parse_file, ParseResultConverter, PythonLanguageParser};
 pub use analysis::PythonAnalyzer;
+pub use ast_mapper::DEFAULT_MAX_DEPTH;
 pub use error::{Error, Result};
 pub use parser::{ParseContext, ParseResult, PythonParser};
+pub use patterns::{DetectedPattern, PythonPatternType, RouteInfo};
 pub use types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
 
 // Re-export the parser for registration
@@ -1,6 +1,6 @@
 //! Python parser implementation
 
-use crate::ast_mapper::AstMapper;
+use crate::ast_mapper::{AstMapper, DEFAULT_MAX_DEPTH};
 use crate::error::{Error, Result};
 use crate::types::{Edge, Language, Node};
 use std::path::{Path, PathBuf};
@@ -34,6 +34,8 @@ pub struct ParseResult {
 pub struct PythonParser {
     /// Tree-sitter parser for Python
     parser: Parser,
+    /// Deepest tree level converted into nodes
+    max_depth: usize,
 }
 
 impl PythonParser {
@@ -44,7 +46,15 @@ impl PythonParser {
             .set_language(&tree_sitter_python::LANGUAGE.into())
             .expect("Failed to load Python grammar");
 
-        Self { parser }
+        Self {
+            parser,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels when extracting nodes
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
     }
 
     /// Get the language for a file based on its extension
@@ -72,7 +82,8 @@ impl PythonParser {
             context.file_path.clone(),
             language,
             &context.content,
-        );
+        )
+        .with_max_depth(self.max_depth);
 
         let (nodes, edges) = mapper.extract(&tree)?;
 
@@ -126,6 +137,36 @@ mod tests {
             .any(|n| matches!(n.kind, crate::types::NodeKind::Function)));
     }
 
+    #[test]
+    fn test_depth_limit_skips_deeply_nested_expressions() {
+        let mut parser = PythonParser::new();
+        parser.set_max_depth(64);
+        let depth = 5_000;
+        let context = ParseContext {
+            repo_id: "test_repo".to_string(),
+            file_path: PathBuf::from("generated.py"),
+            old_tree: None,
+            content: format!(
+                "value = {}1{}\n\ndef after():\n    return value\n",
+                "(".repeat(depth),
+                ")".repeat(depth)
+            ),
+        };
+
+        let result = parser.parse(&context).unwrap();
+
+        let module = result
+            .nodes
+            .iter()
+            .find(|n| matches!(n.kind, crate::types::NodeKind::Module))
+            .unwrap();
+        assert_eq!(module.metadata["depth_truncation"]["max_depth"], 64);
+        assert!(result
+            .nodes
+            .iter()
+            .any(|n| matches!(n.kind, crate::types::NodeKind::Function) && n.name == "after"));
+    }
+
     #[test]
     fn test_parse_class() {
         let mut parser = PythonParser::new();
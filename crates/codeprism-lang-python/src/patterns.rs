@@ -0,0 +1,710 @@
+//! Structural pattern detection for Python
+//!
+//! Recognizes idioms that are visible in the shape of the code rather than in
+//! a single line: singletons implemented through `__new__`, dataclasses,
+//! context managers, Flask/FastAPI route handlers and click/Typer commands.
+
+use crate::analysis::PythonAnalyzer;
+use crate::error::{Error, Result};
+use crate::types::Span;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tree_sitter::{Node as TsNode, Parser};
+
+/// Decorator attributes that register a route on a Flask or FastAPI app/router
+const ROUTE_METHODS: &[&str] = &[
+    "route", "get", "post", "put", "delete", "patch", "head", "options",
+];
+
+/// Kind of structural pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PythonPatternType {
+    /// A class that controls its own instantiation through `__new__`
+    Singleton,
+    /// A class decorated with `@dataclass`
+    Dataclass,
+    /// A class with `__enter__`/`__exit__`, or a `@contextmanager` function
+    ContextManager,
+    /// A Flask or FastAPI route handler
+    Route,
+    /// A click or Typer command-line command
+    CliCommand,
+}
+
+impl PythonPatternType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Singleton => "singleton",
+            Self::Dataclass => "dataclass",
+            Self::ContextManager => "context_manager",
+            Self::Route => "route",
+            Self::CliCommand => "cli_command",
+        }
+    }
+}
+
+impl std::str::FromStr for PythonPatternType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "singleton" => Ok(Self::Singleton),
+            "dataclass" => Ok(Self::Dataclass),
+            "context_manager" => Ok(Self::ContextManager),
+            "route" => Ok(Self::Route),
+            "cli_command" => Ok(Self::CliCommand),
+            other => Err(Error::generic(&format!("Unknown pattern type: {other}"))),
+        }
+    }
+}
+
+/// Route registered by a framework decorator such as `@app.route("/x")`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteInfo {
+    pub framework: String,
+    /// Route path from the decorator's first argument, if it is a string literal
+    pub path: Option<String>,
+    /// HTTP methods, upper-cased
+    pub methods: Vec<String>,
+}
+
+/// A structural pattern found in a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedPattern {
+    pub pattern_type: PythonPatternType,
+    /// Name of the class or function implementing the pattern
+    pub name: String,
+    pub file_path: String,
+    /// Span of the definition, including its decorators
+    pub span: Span,
+    /// Confidence in the match, from 0.0 to 1.0
+    pub confidence: f32,
+    /// Source facts the match was based on
+    pub evidence: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route: Option<RouteInfo>,
+}
+
+impl PythonAnalyzer {
+    /// Read a file and detect structural patterns in it
+    pub fn detect_patterns_in_file(&self, path: &Path) -> Result<Vec<DetectedPattern>> {
+        let source = std::fs::read_to_string(path)?;
+        self.detect_patterns(&source, path)
+    }
+
+    /// Detect singletons, dataclasses, context managers, route handlers and
+    /// CLI commands in `source`
+    pub fn detect_patterns(&self, source: &str, path: &Path) -> Result<Vec<DetectedPattern>> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .map_err(|e| Error::tree_sitter(path, &e.to_string()))?;
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| Error::parse(path, "Failed to parse file"))?;
+
+        let mut scan = Scan {
+            source,
+            file_path: path.display().to_string(),
+            framework: imported_framework(source),
+            cli_framework: imported_cli_framework(source),
+            patterns: Vec::new(),
+        };
+        scan.visit(tree.root_node());
+        scan.patterns
+            .sort_by_key(|pattern| (pattern.span.start_byte, pattern.pattern_type.as_str()));
+        Ok(scan.patterns)
+    }
+
+    /// Names of the functions called inside `if __name__ == "__main__":` blocks
+    ///
+    /// These are what runs when the module is executed as a script. Method
+    /// calls are reported by their attribute name, as in the call graph.
+    pub fn main_guard_calls(&self, source: &str, path: &Path) -> Result<Vec<String>> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .map_err(|e| Error::tree_sitter(path, &e.to_string()))?;
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| Error::parse(path, "Failed to parse file"))?;
+
+        let mut calls = Vec::new();
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        for statement in root.named_children(&mut cursor) {
+            if statement.kind() == "if_statement" && is_main_guard(statement, source) {
+                if let Some(body) = statement.child_by_field_name("consequence") {
+                    collect_calls(body, source, &mut calls);
+                }
+            }
+        }
+        Ok(calls)
+    }
+}
+
+/// Web framework imported by the module, used to attribute route decorators
+fn imported_framework(source: &str) -> Option<&'static str> {
+    source.lines().map(str::trim).find_map(|line| {
+        let module = line
+            .strip_prefix("from ")
+            .or_else(|| line.strip_prefix("import "))?;
+        if module.starts_with("fastapi") {
+            Some("FastAPI")
+        } else if module.starts_with("flask") {
+            Some("Flask")
+        } else {
+            None
+        }
+    })
+}
+
+/// Command-line framework imported by the module, used to attribute
+/// `@<group>.command()` decorators
+fn imported_cli_framework(source: &str) -> Option<&'static str> {
+    source.lines().map(str::trim).find_map(|line| {
+        let module = line
+            .strip_prefix("from ")
+            .or_else(|| line.strip_prefix("import "))?;
+        if module.starts_with("click") {
+            Some("click")
+        } else if module.starts_with("typer") {
+            Some("Typer")
+        } else {
+            None
+        }
+    })
+}
+
+fn text<'a>(node: TsNode, source: &'a str) -> &'a str {
+    &source[node.byte_range()]
+}
+
+/// Value of a string literal without its prefix and quotes
+fn string_value(node: TsNode, source: &str) -> Option<String> {
+    if node.kind() != "string" {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let value = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "string_content")
+        .map(|child| text(child, source))
+        .collect();
+    Some(value)
+}
+
+struct Scan<'a> {
+    source: &'a str,
+    file_path: String,
+    framework: Option<&'static str>,
+    cli_framework: Option<&'static str>,
+    patterns: Vec<DetectedPattern>,
+}
+
+impl Scan<'_> {
+    fn visit(&mut self, node: TsNode) {
+        match node.kind() {
+            "class_definition" => self.check_class(node),
+            "function_definition" => self.check_function(node),
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.visit(child);
+        }
+    }
+
+    fn check_class(&mut self, class: TsNode) {
+        let Some(name) = class.child_by_field_name("name") else {
+            return;
+        };
+        let name = text(name, self.source).to_string();
+        let decorators = decorators(class);
+
+        if let Some(decorator) = decorators.iter().find(|decorator| {
+            matches!(
+                self.callee(**decorator),
+                "dataclass" | "dataclasses.dataclass"
+            )
+        }) {
+            self.report(
+                class,
+                PythonPatternType::Dataclass,
+                &name,
+                1.0,
+                vec![text(*decorator, self.source).to_string()],
+                None,
+            );
+        }
+
+        let methods = self.method_names(class);
+        if let Some(new) = methods.iter().find(|(method, _)| method == "__new__") {
+            let body = text(new.1, self.source);
+            let caches_instance = body.contains("cls._instance")
+                || body.contains("cls.__instance")
+                || body.contains("cls._instances");
+            let (confidence, evidence) = if caches_instance {
+                (0.9, "__new__ caches and returns a single instance on cls")
+            } else {
+                (0.5, "__new__ overrides instance creation")
+            };
+            self.report(
+                class,
+                PythonPatternType::Singleton,
+                &name,
+                confidence,
+                vec![evidence.to_string()],
+                None,
+            );
+        }
+
+        let has = |method: &str| methods.iter().any(|(name, _)| name == method);
+        for (enter, exit) in [("__enter__", "__exit__"), ("__aenter__", "__aexit__")] {
+            if has(enter) && has(exit) {
+                self.report(
+                    class,
+                    PythonPatternType::ContextManager,
+                    &name,
+                    1.0,
+                    vec![format!("defines {enter} and {exit}")],
+                    None,
+                );
+                break;
+            }
+        }
+    }
+
+    fn check_function(&mut self, function: TsNode) {
+        let Some(name) = function.child_by_field_name("name") else {
+            return;
+        };
+        let name = text(name, self.source).to_string();
+
+        for decorator in decorators(function) {
+            let callee = self.callee(decorator);
+            let evidence = vec![text(decorator, self.source).to_string()];
+            let short = callee.rsplit('.').next().unwrap_or(callee);
+            if matches!(short, "contextmanager" | "asynccontextmanager") {
+                self.report(
+                    function,
+                    PythonPatternType::ContextManager,
+                    &name,
+                    1.0,
+                    evidence,
+                    None,
+                );
+            } else if let Some((route, confidence)) = self.route(decorator, callee) {
+                self.report(
+                    function,
+                    PythonPatternType::Route,
+                    &name,
+                    confidence,
+                    evidence,
+                    Some(route),
+                );
+            } else if let Some(confidence) = self.cli_command(callee) {
+                self.report(
+                    function,
+                    PythonPatternType::CliCommand,
+                    &name,
+                    confidence,
+                    evidence,
+                    None,
+                );
+            }
+        }
+    }
+
+    /// Confidence that a decorator like `@click.command()` or
+    /// `@cli.command("sync")` registers a command-line command
+    fn cli_command(&self, callee: &str) -> Option<f32> {
+        let (target, method) = callee.rsplit_once('.')?;
+        if !matches!(method, "command" | "group") {
+            return None;
+        }
+        match (target, self.cli_framework) {
+            ("click", _) => Some(1.0),
+            (_, Some(_)) => Some(0.85),
+            (_, None) => Some(0.5),
+        }
+    }
+
+    /// Route registered by a decorator like `@app.route("/x", methods=["POST"])`
+    /// or `@router.get("/items/{id}")`
+    fn route(&self, decorator: TsNode, callee: &str) -> Option<(RouteInfo, f32)> {
+        let (_, method) = callee.rsplit_once('.')?;
+        if !ROUTE_METHODS.contains(&method) {
+            return None;
+        }
+        let arguments = decorator
+            .named_child(0)
+            .filter(|expression| expression.kind() == "call")?
+            .child_by_field_name("arguments")?;
+
+        let mut path = None;
+        let mut methods = Vec::new();
+        let mut cursor = arguments.walk();
+        for argument in arguments.named_children(&mut cursor) {
+            match argument.kind() {
+                "string" if path.is_none() => path = string_value(argument, self.source),
+                "keyword_argument" => {
+                    let keyword = argument
+                        .child_by_field_name("name")
+                        .map(|name| text(name, self.source));
+                    let Some(value) = argument.child_by_field_name("value") else {
+                        continue;
+                    };
+                    match keyword {
+                        Some("path" | "rule") if path.is_none() => {
+                            path = string_value(value, self.source)
+                        }
+                        Some("methods") => {
+                            let mut cursor = value.walk();
+                            methods.extend(
+                                value
+                                    .named_children(&mut cursor)
+                                    .filter_map(|item| string_value(item, self.source))
+                                    .map(|item| item.to_ascii_uppercase()),
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let framework = self.framework.unwrap_or(if method == "route" {
+            "Flask"
+        } else {
+            "FastAPI"
+        });
+        if methods.is_empty() {
+            methods.push(if method == "route" {
+                "GET".to_string()
+            } else {
+                method.to_ascii_uppercase()
+            });
+        }
+        let confidence = match (self.framework.is_some(), path.is_some()) {
+            (true, true) => 0.95,
+            (true, false) | (false, true) => 0.75,
+            (false, false) => 0.5,
+        };
+        Some((
+            RouteInfo {
+                framework: framework.to_string(),
+                path,
+                methods,
+            },
+            confidence,
+        ))
+    }
+
+    /// Dotted name of a decorator, without its call arguments
+    fn callee(&self, decorator: TsNode) -> &str {
+        let Some(expression) = decorator.named_child(0) else {
+            return "";
+        };
+        let callee = if expression.kind() == "call" {
+            expression
+                .child_by_field_name("function")
+                .unwrap_or(expression)
+        } else {
+            expression
+        };
+        text(callee, self.source)
+    }
+
+    /// Methods defined directly in a class body, with their nodes
+    fn method_names<'t>(&self, class: TsNode<'t>) -> Vec<(String, TsNode<'t>)> {
+        let Some(body) = class.child_by_field_name("body") else {
+            return Vec::new();
+        };
+        let mut cursor = body.walk();
+        body.named_children(&mut cursor)
+            .filter_map(|statement| match statement.kind() {
+                "function_definition" => Some(statement),
+                "decorated_definition" => statement.child_by_field_name("definition"),
+                _ => None,
+            })
+            .filter_map(|function| {
+                let name = function.child_by_field_name("name")?;
+                Some((text(name, self.source).to_string(), function))
+            })
+            .collect()
+    }
+
+    fn report(
+        &mut self,
+        definition: TsNode,
+        pattern_type: PythonPatternType,
+        name: &str,
+        confidence: f32,
+        evidence: Vec<String>,
+        route: Option<RouteInfo>,
+    ) {
+        // Report the decorated form so the span covers the decorators too
+        let node = definition
+            .parent()
+            .filter(|parent| parent.kind() == "decorated_definition")
+            .unwrap_or(definition);
+        self.patterns.push(DetectedPattern {
+            pattern_type,
+            name: name.to_string(),
+            file_path: self.file_path.clone(),
+            span: Span::from_node(&node),
+            confidence,
+            evidence,
+            route,
+        });
+    }
+}
+
+/// Decorators applied to a class or function definition
+fn decorators(definition: TsNode) -> Vec<TsNode> {
+    let Some(parent) = definition
+        .parent()
+        .filter(|parent| parent.kind() == "decorated_definition")
+    else {
+        return Vec::new();
+    };
+    let mut cursor = parent.walk();
+    let decorators = parent
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "decorator")
+        .collect();
+    decorators
+}
+
+/// Whether an `if` statement is `if __name__ == "__main__":`, in either order
+fn is_main_guard(statement: TsNode, source: &str) -> bool {
+    let Some(condition) = statement.child_by_field_name("condition") else {
+        return false;
+    };
+    if condition.kind() != "comparison_operator" || !text(condition, source).contains("==") {
+        return false;
+    }
+    let mut cursor = condition.walk();
+    let operands: Vec<_> = condition.named_children(&mut cursor).collect();
+    let is_name = |node: &TsNode| node.kind() == "identifier" && text(*node, source) == "__name__";
+    let is_main = |node: &TsNode| string_value(*node, source).as_deref() == Some("__main__");
+    matches!(operands.as_slice(), [a, b] if (is_name(a) && is_main(b)) || (is_main(a) && is_name(b)))
+}
+
+/// Call targets in `node`, in source order and without duplicates
+fn collect_calls(node: TsNode, source: &str, calls: &mut Vec<String>) {
+    if node.kind() == "call" {
+        let target =
+            node.child_by_field_name("function")
+                .and_then(|function| match function.kind() {
+                    "identifier" => Some(function),
+                    "attribute" => function.child_by_field_name("attribute"),
+                    _ => None,
+                });
+        if let Some(target) = target {
+            let name = text(target, source).to_string();
+            if !calls.contains(&name) {
+                calls.push(name);
+            }
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_calls(child, source, calls);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(source: &str) -> Vec<DetectedPattern> {
+        PythonAnalyzer::new()
+            .detect_patterns(source, Path::new("app.py"))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_main_guard_calls() {
+        let source = r#"
+import sys
+
+def main(argv):
+    return run(argv)
+
+def run(argv):
+    pass
+
+def unused():
+    pass
+
+setup()
+
+if __name__ == "__main__":
+    cli = build_parser()
+    sys.exit(main(sys.argv))
+
+if "__main__" == __name__:
+    main([])
+"#;
+        let calls = PythonAnalyzer::new()
+            .main_guard_calls(source, Path::new("tool.py"))
+            .unwrap();
+        assert_eq!(calls, vec!["build_parser", "exit", "main"]);
+
+        let library = "def helper():\n    pass\n\nif DEBUG:\n    helper()\n";
+        assert!(PythonAnalyzer::new()
+            .main_guard_calls(library, Path::new("lib.py"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_class_patterns() {
+        let source = r#"
+from dataclasses import dataclass, field
+
+@dataclass(frozen=True)
+class Point:
+    x: int
+    y: int
+
+class Config:
+    _instance = None
+
+    def __new__(cls, *args, **kwargs):
+        if cls._instance is None:
+            cls._instance = super().__new__(cls)
+        return cls._instance
+
+class Transaction:
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc, tb):
+        return False
+
+class Plain:
+    def __enter__(self):
+        return self
+"#;
+        let patterns = detect(source);
+        let found: Vec<(PythonPatternType, &str, usize)> = patterns
+            .iter()
+            .map(|p| (p.pattern_type, p.name.as_str(), p.span.start_line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (PythonPatternType::Dataclass, "Point", 4),
+                (PythonPatternType::Singleton, "Config", 9),
+                (PythonPatternType::ContextManager, "Transaction", 17),
+            ]
+        );
+        assert_eq!(patterns[0].evidence, vec!["@dataclass(frozen=True)"]);
+        assert_eq!(patterns[1].confidence, 0.9);
+    }
+
+    #[test]
+    fn test_route_decorators_extract_path() {
+        let source = r#"
+from flask import Flask
+from contextlib import contextmanager
+
+app = Flask(__name__)
+
+@app.route("/orders/<int:order_id>", methods=["GET", "post"])
+def get_order(order_id):
+    return {}
+
+@contextmanager
+def session_scope():
+    yield
+
+@app.errorhandler(404)
+def not_found(error):
+    return "", 404
+"#;
+        let patterns = detect(source);
+        assert_eq!(patterns.len(), 2, "{patterns:?}");
+
+        let route = &patterns[0];
+        assert_eq!(route.pattern_type, PythonPatternType::Route);
+        assert_eq!(route.name, "get_order");
+        assert_eq!(route.span.start_line, 7);
+        assert_eq!(
+            route.route,
+            Some(RouteInfo {
+                framework: "Flask".to_string(),
+                path: Some("/orders/<int:order_id>".to_string()),
+                methods: vec!["GET".to_string(), "POST".to_string()],
+            })
+        );
+        assert_eq!(patterns[1].pattern_type, PythonPatternType::ContextManager);
+        assert_eq!(patterns[1].name, "session_scope");
+    }
+
+    #[test]
+    fn test_fastapi_router_routes() {
+        let source = r#"
+from fastapi import APIRouter
+
+router = APIRouter()
+
+@router.post("/items/{item_id}", status_code=201)
+async def create_item(item_id: int):
+    return {}
+"#;
+        let patterns = detect(source);
+        assert_eq!(patterns.len(), 1);
+        let route = patterns[0].route.as_ref().unwrap();
+        assert_eq!(route.framework, "FastAPI");
+        assert_eq!(route.path.as_deref(), Some("/items/{item_id}"));
+        assert_eq!(route.methods, vec!["POST"]);
+        assert_eq!(patterns[0].confidence, 0.95);
+    }
+
+    #[test]
+    fn test_pattern_type_from_str() {
+        assert_eq!(
+            "context-manager".parse::<PythonPatternType>().unwrap(),
+            PythonPatternType::ContextManager
+        );
+        assert!("observer".parse::<PythonPatternType>().is_err());
+    }
+
+    #[test]
+    fn test_cli_command_decorators() {
+        let source = r#"
+import click
+
+@click.group()
+def cli():
+    pass
+
+@cli.command("sync")
+@click.option("--force", is_flag=True)
+def sync(force):
+    pass
+
+def helper():
+    pass
+"#;
+        let patterns = detect(source);
+        let found: Vec<(PythonPatternType, &str, f32)> = patterns
+            .iter()
+            .map(|p| (p.pattern_type, p.name.as_str(), p.confidence))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (PythonPatternType::CliCommand, "cli", 1.0),
+                (PythonPatternType::CliCommand, "sync", 0.85),
+            ]
+        );
+        assert_eq!(patterns[1].evidence, vec![r#"@cli.command("sync")"#]);
+    }
+}
@@ -53,6 +53,26 @@ fn test_parse_simple_python_file() {
     assert!(!calls.is_empty(), "Should not be empty");
 }
 
+#[test]
+fn test_function_signatures_keep_annotated_parameter_names() {
+    let mut parser = PythonParser::new();
+    let context = ParseContext {
+        repo_id: "test_repo".to_string(),
+        file_path: PathBuf::from("typed.py"),
+        old_tree: None,
+        content: "def scale(factor: float, unit: str = \"m\", *args: int, **kwargs) -> float:\n    return factor\n"
+            .to_string(),
+    };
+
+    let result = parser.parse(&context).unwrap();
+
+    let scale = result.nodes.iter().find(|n| n.name == "scale").unwrap();
+    assert_eq!(
+        scale.signature.as_deref(),
+        Some("scale(factor: float, unit: str = \"m\", *args: int, **kwargs) -> float")
+    );
+}
+
 #[test]
 fn test_parse_class_example() {
     let mut parser = PythonParser::new();
@@ -0,0 +1,388 @@
+//! Ruby-specific code analysis module
+
+use crate::error::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Metaprogramming construct found in Ruby code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaprogrammingInfo {
+    pub construct_type: MetaprogrammingType,
+    pub line: usize,
+    pub snippet: String,
+    pub impact: String,
+}
+
+/// Types of Ruby metaprogramming constructs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetaprogrammingType {
+    DefineMethod,
+    MethodMissing,
+    DynamicSend,
+    InstanceVariableAccess,
+    ClassEval,
+    InstanceEval,
+}
+
+/// Ruby framework detected in a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubyFrameworkInfo {
+    pub name: String,
+    pub confidence: f32,
+    pub features_used: Vec<String>,
+}
+
+/// Security issue found in Ruby code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubySecurityIssue {
+    pub issue_type: RubySecurityIssueType,
+    pub line: usize,
+    pub description: String,
+    pub recommendation: String,
+}
+
+/// Types of Ruby security issues
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RubySecurityIssueType {
+    CodeInjection,
+    CommandInjection,
+    SqlInjection,
+    UnsafeDeserialization,
+    CrossSiteScripting,
+    MassAssignment,
+}
+
+#[derive(Debug, Clone)]
+struct MetaprogrammingPattern {
+    construct_type: MetaprogrammingType,
+    pattern: Regex,
+    impact: &'static str,
+}
+
+#[derive(Debug, Clone)]
+struct FrameworkPattern {
+    feature: &'static str,
+    pattern: Regex,
+}
+
+#[derive(Debug, Clone)]
+struct SecurityPattern {
+    issue_type: RubySecurityIssueType,
+    pattern: Regex,
+    description: &'static str,
+    recommendation: &'static str,
+}
+
+/// Ruby-specific code analyzer
+pub struct RubyAnalyzer {
+    metaprogramming_patterns: Vec<MetaprogrammingPattern>,
+    framework_patterns: Vec<(&'static str, Vec<FrameworkPattern>)>,
+    security_patterns: Vec<SecurityPattern>,
+}
+
+impl RubyAnalyzer {
+    /// Create a new Ruby analyzer
+    pub fn new() -> Self {
+        let metaprogramming = |construct_type, pattern: &str, impact| MetaprogrammingPattern {
+            construct_type,
+            pattern: Regex::new(pattern).unwrap(),
+            impact,
+        };
+        let feature = |feature, pattern: &str| FrameworkPattern {
+            feature,
+            pattern: Regex::new(pattern).unwrap(),
+        };
+        let security = |issue_type, pattern: &str, description, recommendation| SecurityPattern {
+            issue_type,
+            pattern: Regex::new(pattern).unwrap(),
+            description,
+            recommendation,
+        };
+
+        Self {
+            metaprogramming_patterns: vec![
+                metaprogramming(
+                    MetaprogrammingType::DefineMethod,
+                    r"\bdefine_(?:singleton_)?method\b",
+                    "Methods are created at runtime and invisible to static lookup",
+                ),
+                metaprogramming(
+                    MetaprogrammingType::MethodMissing,
+                    r"\bdef\s+(?:self\.)?method_missing\b",
+                    "Calls to undefined methods are intercepted dynamically",
+                ),
+                metaprogramming(
+                    MetaprogrammingType::DynamicSend,
+                    r"\.(?:public_)?send\(|\.__send__\(",
+                    "Method names are chosen at runtime",
+                ),
+                metaprogramming(
+                    MetaprogrammingType::InstanceVariableAccess,
+                    r"\binstance_variable_(?:get|set)\b",
+                    "Object state is read or written by name",
+                ),
+                metaprogramming(
+                    MetaprogrammingType::ClassEval,
+                    r"\b(?:class|module)_(?:eval|exec)\b",
+                    "Class bodies are reopened at runtime",
+                ),
+                metaprogramming(
+                    MetaprogrammingType::InstanceEval,
+                    r"\binstance_(?:eval|exec)\b",
+                    "Blocks run with a different self",
+                ),
+            ],
+            framework_patterns: vec![
+                (
+                    "Rails",
+                    vec![
+                        feature(
+                            "ActiveRecord models",
+                            r"<\s*(?:ApplicationRecord|ActiveRecord::Base)\b",
+                        ),
+                        feature(
+                            "Controllers",
+                            r"<\s*(?:ApplicationController|ActionController::\w+)\b",
+                        ),
+                        feature("Routing", r"\.routes\.draw\b"),
+                        feature(
+                            "Associations",
+                            r"^\s*(?:has_many|has_one|belongs_to|has_and_belongs_to_many)\s",
+                        ),
+                        feature("Validations", r"^\s*validates?(?:_\w+)?\s"),
+                        feature("Callbacks", r"^\s*(?:before|after|around)_\w+\s"),
+                    ],
+                ),
+                (
+                    "RSpec",
+                    vec![
+                        feature("Example groups", r"^\s*(?:RSpec\.)?describe\s"),
+                        feature("Examples", r"^\s*it\s"),
+                        feature("Expectations", r"\bexpect\(.*\)\.(?:to|not_to)\b"),
+                    ],
+                ),
+                (
+                    "Sinatra",
+                    vec![
+                        feature("Application", r"<\s*Sinatra::Base\b"),
+                        feature("Routes", r#"^\s*(?:get|post|put|patch|delete)\s+['"]/"#),
+                    ],
+                ),
+            ],
+            security_patterns: vec![
+                security(
+                    RubySecurityIssueType::CodeInjection,
+                    r"\beval\s*[(\s]",
+                    "eval executes arbitrary Ruby code",
+                    "Replace eval with explicit dispatch or public_send on an allow-list",
+                ),
+                security(
+                    RubySecurityIssueType::CommandInjection,
+                    r#"(?:\bsystem|\bexec|%x|`)[^\n]*#\{"#,
+                    "Shell command built with string interpolation",
+                    "Pass command arguments separately, e.g. system(\"cmd\", arg)",
+                ),
+                security(
+                    RubySecurityIssueType::SqlInjection,
+                    r#"\b(?:where|find_by_sql|order|joins|execute)\s*\(?\s*"[^"]*#\{"#,
+                    "SQL fragment built with string interpolation",
+                    "Use placeholders, e.g. where(\"name = ?\", name)",
+                ),
+                security(
+                    RubySecurityIssueType::UnsafeDeserialization,
+                    r"\b(?:Marshal\.load|YAML\.load)\b",
+                    "Deserializing untrusted data can instantiate arbitrary objects",
+                    "Use YAML.safe_load or JSON.parse for untrusted input",
+                ),
+                security(
+                    RubySecurityIssueType::CrossSiteScripting,
+                    r"\.html_safe\b|\braw\s*\(",
+                    "Output marked as safe bypasses HTML escaping",
+                    "Sanitize the content or let the view escape it",
+                ),
+                security(
+                    RubySecurityIssueType::MassAssignment,
+                    r"\bparams\.permit!",
+                    "All request parameters are permitted for mass assignment",
+                    "List the permitted attributes explicitly",
+                ),
+            ],
+        }
+    }
+
+    /// Find metaprogramming constructs that hide behaviour from static analysis
+    pub fn analyze_metaprogramming(&self, content: &str) -> Result<Vec<MetaprogrammingInfo>> {
+        let mut constructs = Vec::new();
+        for (index, line) in Self::code_lines(content) {
+            for pattern in &self.metaprogramming_patterns {
+                if pattern.pattern.is_match(line) {
+                    constructs.push(MetaprogrammingInfo {
+                        construct_type: pattern.construct_type.clone(),
+                        line: index + 1,
+                        snippet: line.trim().to_string(),
+                        impact: pattern.impact.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(constructs)
+    }
+
+    /// Detect Ruby frameworks used in the code
+    pub fn analyze_frameworks(&self, content: &str) -> Result<Vec<RubyFrameworkInfo>> {
+        let mut frameworks = Vec::new();
+        for (name, features) in &self.framework_patterns {
+            let features_used: Vec<String> = features
+                .iter()
+                .filter(|feature| {
+                    Self::code_lines(content).any(|(_, line)| feature.pattern.is_match(line))
+                })
+                .map(|feature| feature.feature.to_string())
+                .collect();
+
+            if !features_used.is_empty() {
+                frameworks.push(RubyFrameworkInfo {
+                    name: name.to_string(),
+                    confidence: features_used.len() as f32 / features.len() as f32,
+                    features_used,
+                });
+            }
+        }
+        Ok(frameworks)
+    }
+
+    /// Find common security issues in Ruby and Rails code
+    pub fn analyze_security(&self, content: &str) -> Result<Vec<RubySecurityIssue>> {
+        let mut issues = Vec::new();
+        for (index, line) in Self::code_lines(content) {
+            for pattern in &self.security_patterns {
+                if pattern.pattern.is_match(line) {
+                    issues.push(RubySecurityIssue {
+                        issue_type: pattern.issue_type.clone(),
+                        line: index + 1,
+                        description: pattern.description.to_string(),
+                        recommendation: pattern.recommendation.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Get Ruby-specific recommendations
+    pub fn get_ruby_recommendations(
+        &self,
+        metaprogramming: &[MetaprogrammingInfo],
+        security_issues: &[RubySecurityIssue],
+    ) -> Vec<String> {
+        let mut recommendations = Vec::new();
+
+        if metaprogramming
+            .iter()
+            .any(|m| m.construct_type == MetaprogrammingType::MethodMissing)
+        {
+            recommendations.push("Define respond_to_missing? alongside method_missing".to_string());
+        }
+        if metaprogramming.len() > 5 {
+            recommendations.push(
+                "Heavy metaprogramming detected; prefer explicit methods where possible"
+                    .to_string(),
+            );
+        }
+
+        let mut seen = Vec::new();
+        for issue in security_issues {
+            if !seen.contains(&issue.issue_type) {
+                seen.push(issue.issue_type.clone());
+                recommendations.push(issue.recommendation.clone());
+            }
+        }
+
+        recommendations
+    }
+
+    /// Lines with their zero-based index, skipping full-line comments
+    fn code_lines(content: &str) -> impl Iterator<Item = (usize, &str)> {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim_start().starts_with('#'))
+    }
+}
+
+impl Default for RubyAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metaprogramming_analysis() {
+        let analyzer = RubyAnalyzer::new();
+        let code = r#"
+class Proxy
+  # define_method in a comment is ignored
+  def method_missing(name, *args)
+    @target.public_send(name, *args)
+  end
+
+  %w[a b].each { |n| define_method(n) { n } }
+end
+"#;
+        let constructs = analyzer.analyze_metaprogramming(code).unwrap();
+        let types: Vec<_> = constructs.iter().map(|c| &c.construct_type).collect();
+
+        assert!(types.contains(&&MetaprogrammingType::MethodMissing));
+        assert!(types.contains(&&MetaprogrammingType::DynamicSend));
+        assert!(types.contains(&&MetaprogrammingType::DefineMethod));
+        assert_eq!(constructs.len(), 3);
+
+        let recommendations = analyzer.get_ruby_recommendations(&constructs, &[]);
+        assert!(recommendations
+            .iter()
+            .any(|r| r.contains("respond_to_missing?")));
+    }
+
+    #[test]
+    fn test_framework_detection() {
+        let analyzer = RubyAnalyzer::new();
+        let code = r#"
+class User < ApplicationRecord
+  has_many :posts
+  validates :email, presence: true
+end
+"#;
+        let frameworks = analyzer.analyze_frameworks(code).unwrap();
+
+        assert_eq!(frameworks.len(), 1);
+        assert_eq!(frameworks[0].name, "Rails");
+        assert!(frameworks[0]
+            .features_used
+            .contains(&"ActiveRecord models".to_string()));
+        assert!(frameworks[0]
+            .features_used
+            .contains(&"Associations".to_string()));
+    }
+
+    #[test]
+    fn test_security_analysis() {
+        let analyzer = RubyAnalyzer::new();
+        let code = r#"
+User.where("name = '#{params[:name]}'")
+system("rm -rf #{path}")
+config = YAML.load(input)
+User.where("name = ?", name)
+"#;
+        let issues = analyzer.analyze_security(code).unwrap();
+        let types: Vec<_> = issues.iter().map(|i| &i.issue_type).collect();
+
+        assert!(types.contains(&&RubySecurityIssueType::SqlInjection));
+        assert!(types.contains(&&RubySecurityIssueType::CommandInjection));
+        assert!(types.contains(&&RubySecurityIssueType::UnsafeDeserialization));
+        assert_eq!(issues.len(), 3);
+    }
+}
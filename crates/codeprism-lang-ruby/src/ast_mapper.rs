@@ -0,0 +1,861 @@
+//! AST mapping from tree-sitter Ruby CST to Universal AST
+
+use crate::error::Result;
+use crate::types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tree_sitter::{Node as TSNode, Tree};
+
+/// Visibility modifiers that can precede method definitions
+const VISIBILITY_MODIFIERS: &[&str] = &["private", "protected", "public"];
+
+/// Rails routing DSL methods that declare routes
+const ROUTE_METHODS: &[&str] = &[
+    "get",
+    "post",
+    "put",
+    "patch",
+    "delete",
+    "match",
+    "root",
+    "resources",
+    "resource",
+];
+
+/// Default actions generated by `resources`
+const RESOURCES_ACTIONS: &[&str] = &[
+    "index", "show", "new", "create", "edit", "update", "destroy",
+];
+
+/// Default actions generated by the singular `resource`
+const RESOURCE_ACTIONS: &[&str] = &["show", "new", "create", "edit", "update", "destroy"];
+
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// Maps tree-sitter Ruby CST to Universal AST
+pub struct AstMapper {
+    /// Repository ID
+    repo_id: String,
+    /// File path
+    file_path: PathBuf,
+    /// Language
+    language: Language,
+    /// Source content
+    content: String,
+    /// Collected nodes
+    nodes: Vec<Node>,
+    /// Collected edges
+    edges: Vec<Edge>,
+    /// Node ID mappings (tree-sitter node ID -> Universal AST node ID)
+    node_mappings: HashMap<usize, NodeId>,
+    /// Superclass and mixin references, resolved against same-file declarations
+    heritage: Vec<(NodeId, String, EdgeKind)>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
+}
+
+impl AstMapper {
+    /// Create a new AST mapper
+    pub fn new(repo_id: &str, file_path: PathBuf, language: Language, content: &str) -> Self {
+        Self {
+            repo_id: repo_id.to_string(),
+            file_path,
+            language,
+            content: content.to_string(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            node_mappings: HashMap::new(),
+            heritage: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Extract nodes and edges from the tree
+    pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
+        let root = tree.root_node();
+
+        // Create module node for the file
+        let module_span = Span::from_node(&root);
+        let file_name = self
+            .file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let module_node = Node::new(
+            &self.repo_id,
+            NodeKind::Module,
+            file_name,
+            self.language,
+            self.file_path.clone(),
+            module_span,
+        )
+        .with_metadata(json!({
+            "type": "program",
+            "file_path": self.file_path.display().to_string()
+        }));
+
+        let module_id = module_node.id;
+        self.nodes.push(module_node);
+        self.node_mappings.insert(root.id(), module_id);
+
+        // Process all child nodes
+        self.process_node(&root, Some(module_id), 0)?;
+
+        self.resolve_local_calls();
+        self.resolve_heritage();
+        self.mark_entry_points(&root);
+
+        self.record_depth_truncation();
+
+        Ok((self.nodes, self.edges))
+    }
+
+    /// Flag the module node with `"entry_point": true` when the file runs code
+    /// at the top level
+    ///
+    /// Scripts, including `if __FILE__ == $0` blocks, execute when loaded;
+    /// files that only define classes, modules and methods and require other
+    /// files do not.
+    fn mark_entry_points(&mut self, root: &TSNode) {
+        let mut cursor = root.walk();
+        let runs_code = root.named_children(&mut cursor).any(|statement| {
+            let is_require = statement.kind() == "call"
+                && statement.child_by_field_name("receiver").is_none()
+                && matches!(
+                    self.field_text(&statement, "method").as_deref(),
+                    Some("require" | "require_relative")
+                );
+            !is_require
+                && !matches!(
+                    statement.kind(),
+                    "class" | "module" | "method" | "singleton_method" | "comment"
+                )
+        });
+        if let Some(module) = self.nodes.first_mut().filter(|_| runs_code) {
+            module.metadata["entry_point"] = json!(true);
+        }
+    }
+
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = json!({});
+            }
+            module.metadata["depth_truncation"] = json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
+    /// Process a tree-sitter node recursively
+    fn process_node(
+        &mut self,
+        ts_node: &TSNode,
+        parent_id: Option<NodeId>,
+        depth: usize,
+    ) -> Result<Option<NodeId>> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(None);
+        }
+
+        let universal_node = match ts_node.kind() {
+            "class" => self.process_class(ts_node)?,
+            "module" => self.process_module(ts_node)?,
+            "method" => self.process_method(ts_node, false)?,
+            "singleton_method" => self.process_method(ts_node, true)?,
+            "call" => self.process_call(ts_node, parent_id)?,
+            _ => {
+                // For unhandled node types, still process children
+                None
+            }
+        };
+
+        // Add edge from parent to this node
+        if let (Some(parent), Some(node_id)) = (parent_id, &universal_node) {
+            let kind = match self.nodes.last().map(|node| node.kind) {
+                Some(NodeKind::Import) => EdgeKind::Imports,
+                Some(NodeKind::Call) => EdgeKind::Calls,
+                _ => EdgeKind::Contains,
+            };
+            self.edges.push(Edge::new(parent, *node_id, kind));
+        }
+
+        // Process children
+        let mut cursor = ts_node.walk();
+        for child in ts_node.children(&mut cursor) {
+            let child_parent = universal_node.or(parent_id);
+            self.process_node(&child, child_parent, depth + 1)?;
+        }
+
+        Ok(universal_node)
+    }
+
+    /// Get the text content of a node
+    fn node_text(&self, node: &TSNode) -> String {
+        node.utf8_text(self.content.as_bytes())
+            .unwrap_or("")
+            .to_string()
+    }
+
+    /// Get the text of a named field of a node
+    fn field_text(&self, node: &TSNode, field: &str) -> Option<String> {
+        node.child_by_field_name(field).map(|n| self.node_text(&n))
+    }
+
+    /// Push a node and remember its tree-sitter mapping
+    fn push_node(&mut self, ts_node: &TSNode, node: Node) -> NodeId {
+        let node_id = node.id;
+        self.nodes.push(node);
+        self.node_mappings.insert(ts_node.id(), node_id);
+        node_id
+    }
+
+    /// Value of a plain string, symbol or hash key, without quotes or colons
+    ///
+    /// Interpolated strings return `None` since their value is not static.
+    fn literal_value(&self, node: &TSNode) -> Option<String> {
+        match node.kind() {
+            "string" => {
+                let mut cursor = node.walk();
+                let mut value = String::new();
+                for child in node.named_children(&mut cursor) {
+                    match child.kind() {
+                        "string_content" => value.push_str(&self.node_text(&child)),
+                        _ => return None,
+                    }
+                }
+                Some(value)
+            }
+            "simple_symbol" | "hash_key_symbol" => {
+                Some(self.node_text(node).trim_start_matches(':').to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Named arguments of a call, split into positional values and `key: value` pairs
+    fn call_arguments<'t>(
+        &self,
+        call: &TSNode<'t>,
+    ) -> (Vec<TSNode<'t>>, Vec<(String, TSNode<'t>)>) {
+        let mut positional = Vec::new();
+        let mut options = Vec::new();
+        let Some(arguments) = call.child_by_field_name("arguments") else {
+            return (positional, options);
+        };
+
+        let mut cursor = arguments.walk();
+        for argument in arguments.named_children(&mut cursor) {
+            if argument.kind() != "pair" {
+                positional.push(argument);
+                continue;
+            }
+            let key = argument
+                .child_by_field_name("key")
+                .and_then(|key| self.literal_value(&key));
+            if let (Some(key), Some(value)) = (key, argument.child_by_field_name("value")) {
+                options.push((key, value));
+            }
+        }
+
+        (positional, options)
+    }
+
+    /// Names of the enclosing classes and modules, outermost first
+    fn enclosing_namespace(&self, ts_node: &TSNode) -> Vec<String> {
+        let mut namespace = Vec::new();
+        let mut parent = ts_node.parent();
+        while let Some(node) = parent {
+            if matches!(node.kind(), "class" | "module") {
+                if let Some(name) = self.field_text(&node, "name") {
+                    namespace.push(name);
+                }
+            }
+            parent = node.parent();
+        }
+        namespace.reverse();
+        namespace
+    }
+
+    /// Qualify a class or module name with its enclosing namespace
+    fn qualified_name(&self, ts_node: &TSNode, name: &str) -> String {
+        let mut parts = self.enclosing_namespace(ts_node);
+        parts.push(name.to_string());
+        parts.join("::")
+    }
+
+    /// Process class definition
+    fn process_class(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let qualified_name = self.qualified_name(ts_node, &name);
+        let superclass = ts_node
+            .child_by_field_name("superclass")
+            .and_then(|superclass| superclass.named_child(0))
+            .map(|expression| self.node_text(&expression));
+        let mixins = self.collect_mixins(ts_node);
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Class,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "class_name": name,
+            "qualified_name": qualified_name,
+            "superclass": superclass,
+            "mixins": mixins.iter().map(|(kind, module)| json!({
+                "kind": kind,
+                "module": module
+            })).collect::<Vec<_>>(),
+            "type": "class"
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        if let Some(superclass) = superclass {
+            self.heritage.push((node_id, superclass, EdgeKind::Extends));
+        }
+        for (_, module) in mixins {
+            self.heritage.push((node_id, module, EdgeKind::Includes));
+        }
+
+        Ok(Some(node_id))
+    }
+
+    /// Process module definition
+    fn process_module(&mut self, ts_node: &TSNode) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let qualified_name = self.qualified_name(ts_node, &name);
+        let mixins = self.collect_mixins(ts_node);
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::RubyModule,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "module_name": name,
+            "qualified_name": qualified_name,
+            "mixins": mixins.iter().map(|(kind, module)| json!({
+                "kind": kind,
+                "module": module
+            })).collect::<Vec<_>>(),
+            "type": "module"
+        }));
+
+        let node_id = self.push_node(ts_node, node);
+        for (_, module) in mixins {
+            self.heritage.push((node_id, module, EdgeKind::Includes));
+        }
+
+        Ok(Some(node_id))
+    }
+
+    /// Collect `include`, `extend` and `prepend` calls directly in a class or module body
+    fn collect_mixins(&self, ts_node: &TSNode) -> Vec<(String, String)> {
+        let Some(body) = ts_node.child_by_field_name("body") else {
+            return Vec::new();
+        };
+
+        let mut mixins = Vec::new();
+        let mut cursor = body.walk();
+        for statement in body.named_children(&mut cursor) {
+            if statement.kind() != "call" || statement.child_by_field_name("receiver").is_some() {
+                continue;
+            }
+            let method = self.field_text(&statement, "method").unwrap_or_default();
+            if !matches!(method.as_str(), "include" | "extend" | "prepend") {
+                continue;
+            }
+            let (positional, _) = self.call_arguments(&statement);
+            for module in positional {
+                if matches!(module.kind(), "constant" | "scope_resolution") {
+                    mixins.push((method.clone(), self.node_text(&module)));
+                }
+            }
+        }
+        mixins
+    }
+
+    /// Process method definition (`def name` or `def self.name`)
+    fn process_method(&mut self, ts_node: &TSNode, singleton: bool) -> Result<Option<NodeId>> {
+        let name = self.field_text(ts_node, "name").unwrap_or_default();
+        let parameters = self.field_text(ts_node, "parameters").unwrap_or_default();
+        let object = self.field_text(ts_node, "object");
+        let in_singleton_class = self.in_singleton_class(ts_node);
+        let is_singleton = singleton || in_singleton_class;
+        let visibility = if singleton {
+            "public"
+        } else {
+            self.method_visibility(ts_node)
+        };
+
+        let owner = self.enclosing_namespace(ts_node).join("::");
+        let separator = if is_singleton { "." } else { "#" };
+        let qualified_name = if owner.is_empty() {
+            name.clone()
+        } else {
+            format!("{owner}{separator}{name}")
+        };
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Method,
+            name.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_signature(Self::build_signature(object.as_deref(), &name, &parameters))
+        .with_metadata(json!({
+            "method_name": name,
+            "qualified_name": qualified_name,
+            "visibility": visibility,
+            "is_singleton": is_singleton,
+            "parameters": parameters,
+            "type": "method"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Whether a definition sits inside `class << self`
+    fn in_singleton_class(&self, ts_node: &TSNode) -> bool {
+        let mut parent = ts_node.parent();
+        while let Some(node) = parent {
+            match node.kind() {
+                "singleton_class" => return true,
+                "class" | "module" => return false,
+                _ => parent = node.parent(),
+            }
+        }
+        false
+    }
+
+    /// Determine the visibility of an instance method
+    fn method_visibility(&self, ts_node: &TSNode) -> &'static str {
+        // `private def foo` passes the definition as the modifier's argument
+        if let Some(call) = ts_node
+            .parent()
+            .filter(|parent| parent.kind() == "argument_list")
+            .and_then(|arguments| arguments.parent())
+        {
+            let method = self.field_text(&call, "method").unwrap_or_default();
+            if let Some(visibility) = VISIBILITY_MODIFIERS.iter().find(|m| **m == method) {
+                return visibility;
+            }
+        }
+
+        // A bare `private` applies to every later definition in the same body
+        let mut sibling = ts_node.prev_named_sibling();
+        while let Some(node) = sibling {
+            if node.kind() == "identifier" {
+                let text = self.node_text(&node);
+                if let Some(visibility) = VISIBILITY_MODIFIERS.iter().find(|m| **m == text) {
+                    return visibility;
+                }
+            }
+            sibling = node.prev_named_sibling();
+        }
+
+        "public"
+    }
+
+    /// Process a method call, recognising imports, attributes and routes
+    fn process_call(
+        &mut self,
+        ts_node: &TSNode,
+        parent_id: Option<NodeId>,
+    ) -> Result<Option<NodeId>> {
+        let method = self.field_text(ts_node, "method").unwrap_or_default();
+        let receiver = self.field_text(ts_node, "receiver");
+
+        if receiver.is_none() {
+            match method.as_str() {
+                "require" | "require_relative" => return self.process_require(ts_node, &method),
+                "attr_reader" | "attr_writer" | "attr_accessor" => {
+                    self.process_attributes(ts_node, &method, parent_id);
+                    return Ok(None);
+                }
+                // Mixins are recorded on the enclosing class or module
+                "include" | "extend" | "prepend" => return Ok(None),
+                // Visibility modifiers are reflected in method metadata
+                m if VISIBILITY_MODIFIERS.contains(&m) => return Ok(None),
+                _ if ROUTE_METHODS.contains(&method.as_str()) && self.in_routes_block(ts_node) => {
+                    return self.process_route(ts_node, &method);
+                }
+                _ => {}
+            }
+        }
+
+        let has_block = ts_node.child_by_field_name("block").is_some();
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Call,
+            method.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "method_name": method,
+            "receiver": receiver,
+            "has_block": has_block,
+            "type": "call"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Process `require` and `require_relative`
+    fn process_require(&mut self, ts_node: &TSNode, method: &str) -> Result<Option<NodeId>> {
+        let (positional, _) = self.call_arguments(ts_node);
+        let Some(argument) = positional.first() else {
+            return Ok(None);
+        };
+        let is_static = self.literal_value(argument).is_some();
+        let import_path = self
+            .literal_value(argument)
+            .unwrap_or_else(|| self.node_text(argument));
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Import,
+            import_path.clone(),
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "import_path": import_path,
+            "is_relative": method == "require_relative",
+            "is_static": is_static,
+            "type": method
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Create one attribute node per symbol passed to an `attr_*` call
+    fn process_attributes(&mut self, ts_node: &TSNode, method: &str, parent_id: Option<NodeId>) {
+        let readable = method != "attr_writer";
+        let writable = method != "attr_reader";
+        let (positional, _) = self.call_arguments(ts_node);
+
+        for argument in positional {
+            let Some(name) = self.literal_value(&argument) else {
+                continue;
+            };
+            let node = Node::new(
+                &self.repo_id,
+                NodeKind::Attribute,
+                name.clone(),
+                self.language,
+                self.file_path.clone(),
+                Span::from_node(&argument),
+            )
+            .with_metadata(json!({
+                "attribute_name": name,
+                "accessor": method,
+                "readable": readable,
+                "writable": writable,
+                "type": "attribute"
+            }));
+
+            let node_id = self.push_node(&argument, node);
+            if let Some(parent) = parent_id {
+                self.edges
+                    .push(Edge::new(parent, node_id, EdgeKind::Contains));
+            }
+        }
+    }
+
+    /// Whether a call sits in a Rails routes file or `routes.draw` block
+    fn in_routes_block(&self, ts_node: &TSNode) -> bool {
+        if self.file_path.file_name().and_then(|name| name.to_str()) == Some("routes.rb") {
+            return true;
+        }
+
+        let mut parent = ts_node.parent();
+        while let Some(node) = parent {
+            if node.kind() == "call" && self.field_text(&node, "method").as_deref() == Some("draw")
+            {
+                return true;
+            }
+            parent = node.parent();
+        }
+        false
+    }
+
+    /// Path prefix and controller namespace from enclosing `namespace`/`scope` blocks
+    fn route_scope(&self, ts_node: &TSNode) -> (Vec<String>, Vec<String>) {
+        let mut path = Vec::new();
+        let mut modules = Vec::new();
+        let mut parent = ts_node.parent();
+        while let Some(node) = parent {
+            if node.kind() == "call" {
+                let method = self.field_text(&node, "method").unwrap_or_default();
+                let (positional, options) = self.call_arguments(&node);
+                let first = positional.first().and_then(|arg| self.literal_value(arg));
+                let option = |key: &str| {
+                    options
+                        .iter()
+                        .find(|(name, _)| name == key)
+                        .and_then(|(_, value)| self.literal_value(value))
+                };
+                match method.as_str() {
+                    "namespace" => {
+                        if let Some(name) = first {
+                            path.push(name.clone());
+                            modules.push(name);
+                        }
+                    }
+                    "scope" => {
+                        if let Some(prefix) = first.or_else(|| option("path")) {
+                            path.push(prefix);
+                        }
+                        if let Some(module) = option("module") {
+                            modules.push(module);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            parent = node.parent();
+        }
+        path.reverse();
+        modules.reverse();
+        (path, modules)
+    }
+
+    /// Process a Rails routing DSL call into a route node
+    fn process_route(&mut self, ts_node: &TSNode, method: &str) -> Result<Option<NodeId>> {
+        let (positional, options) = self.call_arguments(ts_node);
+        let option = |key: &str| {
+            options
+                .iter()
+                .find(|(name, _)| name == key)
+                .and_then(|(_, value)| self.literal_value(value))
+        };
+
+        // `get "/x" => "users#index"` passes the path as a string-keyed pair
+        let hash_rocket = ts_node
+            .child_by_field_name("arguments")
+            .and_then(|arguments| {
+                let mut cursor = arguments.walk();
+                let pair = arguments
+                    .named_children(&mut cursor)
+                    .find(|arg| arg.kind() == "pair")?;
+                let key = pair.child_by_field_name("key")?;
+                let value = pair.child_by_field_name("value")?;
+                (key.kind() == "string")
+                    .then(|| (self.literal_value(&key), self.literal_value(&value)))
+            });
+
+        let (raw_path, target) = match method {
+            "root" => (
+                Some(String::new()),
+                positional
+                    .first()
+                    .and_then(|arg| self.literal_value(arg))
+                    .or_else(|| option("to")),
+            ),
+            _ => match hash_rocket {
+                Some((path, target)) if positional.is_empty() => (path, target),
+                _ => (
+                    positional.first().and_then(|arg| self.literal_value(arg)),
+                    option("to"),
+                ),
+            },
+        };
+        let Some(raw_path) = raw_path else {
+            return Ok(None);
+        };
+
+        let (prefix, modules) = self.route_scope(ts_node);
+        let path = Self::join_route_path(&prefix, &raw_path);
+        let is_resource = matches!(method, "resources" | "resource");
+
+        let (controller, action) = match target.as_deref().and_then(|t| t.split_once('#')) {
+            Some((controller, action)) => (Some(controller.to_string()), Some(action.to_string())),
+            None if is_resource => (option("controller").or(Some(raw_path.clone())), None),
+            None => (option("controller"), option("action")),
+        };
+        let controller = controller.map(|controller| {
+            let mut parts = modules.clone();
+            parts.push(controller);
+            parts.join("/")
+        });
+
+        let http_method = match method {
+            "root" => Some("GET".to_string()),
+            "match" => Some(
+                options
+                    .iter()
+                    .find(|(name, _)| name == "via")
+                    .map(|(_, via)| {
+                        self.literal_value(via)
+                            .unwrap_or_else(|| self.node_text(via))
+                            .to_uppercase()
+                    })
+                    .unwrap_or_else(|| "ANY".to_string()),
+            ),
+            _ if is_resource => None,
+            verb => Some(verb.to_uppercase()),
+        };
+        let actions = is_resource.then(|| self.resource_actions(method, &options));
+
+        let name = match &http_method {
+            Some(verb) => format!("{verb} {path}"),
+            None => path.clone(),
+        };
+
+        let node = Node::new(
+            &self.repo_id,
+            NodeKind::Route,
+            name,
+            self.language,
+            self.file_path.clone(),
+            Span::from_node(ts_node),
+        )
+        .with_metadata(json!({
+            "dsl_method": method,
+            "http_method": http_method,
+            "path": path,
+            "controller": controller,
+            "action": action,
+            "actions": actions,
+            "route_name": option("as"),
+            "framework": "rails",
+            "type": "route"
+        }));
+
+        Ok(Some(self.push_node(ts_node, node)))
+    }
+
+    /// Actions generated by `resources`/`resource`, honouring `only:` and `except:`
+    fn resource_actions(&self, method: &str, options: &[(String, TSNode)]) -> Vec<String> {
+        let defaults = if method == "resources" {
+            RESOURCES_ACTIONS
+        } else {
+            RESOURCE_ACTIONS
+        };
+        let listed = |key: &str| -> Option<Vec<String>> {
+            let (_, value) = options.iter().find(|(name, _)| name == key)?;
+            if let Some(single) = self.literal_value(value) {
+                return Some(vec![single]);
+            }
+            let mut cursor = value.walk();
+            Some(
+                value
+                    .named_children(&mut cursor)
+                    .filter_map(|item| self.literal_value(&item))
+                    .collect(),
+            )
+        };
+
+        let mut actions: Vec<String> = defaults.iter().map(|a| a.to_string()).collect();
+        if let Some(only) = listed("only") {
+            actions.retain(|action| only.contains(action));
+        }
+        if let Some(except) = listed("except") {
+            actions.retain(|action| !except.contains(action));
+        }
+        actions
+    }
+
+    /// Join scope prefixes and a route path into a single absolute path
+    fn join_route_path(prefix: &[String], path: &str) -> String {
+        let segments: Vec<&str> = prefix
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(path))
+            .map(|segment| segment.trim_matches('/'))
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        format!("/{}", segments.join("/"))
+    }
+
+    /// Link calls to methods declared in the same file
+    fn resolve_local_calls(&mut self) {
+        let mut definitions: HashMap<&str, NodeId> = HashMap::new();
+        for node in &self.nodes {
+            if node.kind == NodeKind::Method {
+                definitions.entry(node.name.as_str()).or_insert(node.id);
+            }
+        }
+
+        let resolved: Vec<Edge> = self
+            .nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Call)
+            .filter_map(|call| {
+                definitions
+                    .get(call.name.as_str())
+                    .map(|target| Edge::new(call.id, *target, EdgeKind::Calls))
+            })
+            .collect();
+
+        self.edges.extend(resolved);
+    }
+
+    /// Add superclass and mixin edges for classes and modules declared in the same file
+    fn resolve_heritage(&mut self) {
+        let last_segment = |name: &str| name.rsplit("::").next().unwrap_or(name).to_string();
+        for (source, name, kind) in std::mem::take(&mut self.heritage) {
+            let wanted = last_segment(&name);
+            if let Some(target) = self.nodes.iter().find(|node| {
+                matches!(node.kind, NodeKind::Class | NodeKind::RubyModule)
+                    && node.id != source
+                    && last_segment(&node.name) == wanted
+            }) {
+                self.edges.push(Edge::new(source, target.id, kind));
+            }
+        }
+    }
+
+    /// Build a Ruby-style signature for a method
+    fn build_signature(object: Option<&str>, name: &str, parameters: &str) -> String {
+        let mut signature = String::from("def ");
+        if let Some(object) = object {
+            signature.push_str(object);
+            signature.push('.');
+        }
+        signature.push_str(name);
+        if parameters.starts_with('(') {
+            signature.push_str(parameters);
+        } else if !parameters.is_empty() {
+            signature.push('(');
+            signature.push_str(parameters);
+            signature.push(')');
+        }
+        signature
+    }
+}
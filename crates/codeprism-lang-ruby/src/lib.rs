@@ -0,0 +1,23 @@
+//! Ruby language support for codeprism
+
+mod adapter;
+mod analysis;
+mod ast_mapper;
+mod error;
+mod parser;
+mod types;
+
+pub use adapter::{parse_file, ParseResultConverter, RubyLanguageParser};
+pub use analysis::{
+    MetaprogrammingInfo, MetaprogrammingType, RubyAnalyzer, RubyFrameworkInfo, RubySecurityIssue,
+    RubySecurityIssueType,
+};
+pub use ast_mapper::DEFAULT_MAX_DEPTH;
+pub use error::{Error, Result};
+pub use parser::{ParseContext, ParseResult, RubyParser};
+pub use types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
+
+// Re-export the parser for registration
+pub fn create_parser() -> RubyLanguageParser {
+    RubyLanguageParser::new()
+}
@@ -0,0 +1,227 @@
+//! Integration tests for Ruby parser
+
+use codeprism_lang_ruby::{EdgeKind, Language, NodeKind, ParseContext, ParseResult, RubyParser};
+use std::path::{Path, PathBuf};
+
+fn parse(file_name: &str, code: &str) -> ParseResult {
+    let mut parser = RubyParser::new();
+    let context = ParseContext {
+        repo_id: "test-repo".to_string(),
+        file_path: PathBuf::from(file_name),
+        old_tree: None,
+        content: code.to_string(),
+    };
+    parser.parse(&context).expect("Failed to parse Ruby file")
+}
+
+#[test]
+fn test_detect_language() {
+    assert_eq!(
+        RubyParser::detect_language(Path::new("user.rb")),
+        Language::Ruby
+    );
+}
+
+#[test]
+fn test_parse_modules_classes_methods_and_imports() {
+    let ruby_code = r#"
+require "json"
+require_relative "../lib/base"
+
+module Accounts
+  class User < Base
+    include Comparable
+
+    attr_accessor :name, :email
+    attr_reader :id
+
+    def self.find(id)
+      new
+    end
+
+    def to_json
+      JSON.generate(serialize(compact: true))
+    end
+
+    private
+
+    def serialize(compact: false)
+      { name: name }
+    end
+  end
+end
+"#;
+
+    let result = parse("user.rb", ruby_code);
+
+    let imports: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Import)
+        .map(|n| {
+            (
+                n.name.as_str(),
+                n.metadata["is_relative"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(imports, vec![("json", false), ("../lib/base", true)]);
+    let import_edges = result
+        .edges
+        .iter()
+        .filter(|e| e.kind == EdgeKind::Imports)
+        .count();
+    assert_eq!(import_edges, 2);
+
+    let module = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::RubyModule)
+        .expect("Should have a module node");
+    assert_eq!(module.name, "Accounts");
+
+    let class = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Class)
+        .expect("Should have a class node");
+    assert_eq!(class.name, "User");
+    assert_eq!(class.metadata["qualified_name"], "Accounts::User");
+    assert_eq!(class.metadata["superclass"], "Base");
+    assert_eq!(class.metadata["mixins"][0]["module"], "Comparable");
+
+    let attributes: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Attribute)
+        .map(|n| (n.name.as_str(), n.metadata["writable"].as_bool().unwrap()))
+        .collect();
+    assert_eq!(
+        attributes,
+        vec![("name", true), ("email", true), ("id", false)]
+    );
+
+    let method = |name: &str| {
+        result
+            .nodes
+            .iter()
+            .find(|n| n.kind == NodeKind::Method && n.name == name)
+            .unwrap_or_else(|| panic!("Should have method {name}"))
+    };
+    assert_eq!(method("find").metadata["is_singleton"], true);
+    assert_eq!(
+        method("find").metadata["qualified_name"],
+        "Accounts::User.find"
+    );
+    assert_eq!(
+        method("find").signature.as_deref(),
+        Some("def self.find(id)")
+    );
+    assert_eq!(method("to_json").metadata["visibility"], "public");
+    assert_eq!(method("serialize").metadata["visibility"], "private");
+
+    // The local call to `serialize` resolves to its definition
+    let call = result
+        .nodes
+        .iter()
+        .find(|n| n.kind == NodeKind::Call && n.name == "serialize")
+        .expect("Should have a call node");
+    assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Calls
+        && e.source == call.id
+        && e.target == method("serialize").id));
+}
+
+#[test]
+fn test_superclass_and_mixins_link_same_file_declarations() {
+    let ruby_code = r#"
+module Auditable
+end
+
+class Record
+end
+
+class Invoice < Record
+  include Auditable
+end
+"#;
+
+    let result = parse("invoice.rb", ruby_code);
+    let id_of = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap().id;
+
+    assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Extends
+        && e.source == id_of("Invoice")
+        && e.target == id_of("Record")));
+    assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Includes
+        && e.source == id_of("Invoice")
+        && e.target == id_of("Auditable")));
+}
+
+#[test]
+fn test_rails_routes_produce_route_nodes() {
+    let ruby_code = r#"
+Rails.application.routes.draw do
+  root "pages#home"
+  get "/health", to: "status#show", as: :health
+  post "login" => "sessions#create"
+
+  namespace :admin do
+    resources :users, only: [:index, :show]
+  end
+
+  match "/search", to: "search#index", via: :get
+end
+"#;
+
+    let result = parse("config/routes.rb", ruby_code);
+    let routes: Vec<_> = result
+        .nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Route)
+        .collect();
+    let names: Vec<_> = routes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "GET /",
+            "GET /health",
+            "POST /login",
+            "/admin/users",
+            "GET /search"
+        ]
+    );
+
+    let health = routes[1];
+    assert_eq!(health.metadata["controller"], "status");
+    assert_eq!(health.metadata["action"], "show");
+    assert_eq!(health.metadata["route_name"], "health");
+
+    let login = routes[2];
+    assert_eq!(login.metadata["controller"], "sessions");
+    assert_eq!(login.metadata["action"], "create");
+
+    let users = routes[3];
+    assert_eq!(users.metadata["controller"], "admin/users");
+    assert_eq!(
+        users.metadata["actions"],
+        serde_json::json!(["index", "show"])
+    );
+}
+
+#[test]
+fn test_http_verb_calls_outside_routes_are_plain_calls() {
+    let ruby_code = r#"
+class Client
+  def fetch
+    get "/users"
+  end
+end
+"#;
+
+    let result = parse("client.rb", ruby_code);
+
+    assert!(!result.nodes.iter().any(|n| n.kind == NodeKind::Route));
+    assert!(result
+        .nodes
+        .iter()
+        .any(|n| n.kind == NodeKind::Call && n.name == "get"));
+}
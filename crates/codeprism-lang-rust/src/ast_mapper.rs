@@ -55,6 +55,12 @@ pub enum ConstraintSource {
     Inferred, // Inferred by borrow checker
 }
 
+/// How deep the tree walk descends by default before skipping a subtree
+///
+/// Keeps generated code with thousands of nested expressions from
+/// overflowing the stack of the recursive walk.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
 /// AST mapper for Rust
 pub struct AstMapper {
     repo_id: String,
@@ -65,6 +71,10 @@ pub struct AstMapper {
     edges: Vec<Edge>,
     /// Map from tree-sitter node ID to our NodeId for edge creation
     node_map: HashMap<usize, crate::types::NodeId>,
+    /// Deepest tree level walked; deeper subtrees are skipped
+    max_depth: usize,
+    /// Subtrees skipped for lying deeper than `max_depth`
+    truncated_subtrees: usize,
 }
 
 impl AstMapper {
@@ -78,9 +88,17 @@ impl AstMapper {
             nodes: Vec::new(),
             edges: Vec::new(),
             node_map: HashMap::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            truncated_subtrees: 0,
         }
     }
 
+    /// Skip subtrees deeper than `max_depth` levels below the root
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     /// Extract nodes and edges from the tree
     pub fn extract(mut self, tree: &Tree) -> Result<(Vec<Node>, Vec<Edge>)> {
         let mut cursor = tree.walk();
@@ -90,11 +108,46 @@ impl AstMapper {
         self.nodes.push(module_node);
 
         // Walk the tree and extract nodes
-        self.walk_tree(&mut cursor)?;
+        self.walk_tree(&mut cursor, 0)?;
+
+        self.mark_entry_points();
+        self.record_depth_truncation();
 
         Ok((self.nodes, self.edges))
     }
 
+    /// Flag `main` and `pub` functions and methods with `"entry_point": true`
+    ///
+    /// Restricted visibilities such as `pub(crate)` are not entry points.
+    fn mark_entry_points(&mut self) {
+        for node in &mut self.nodes {
+            let entry_point = match node.kind {
+                NodeKind::Function => node.name == "main" || node.metadata["visibility"] == "pub",
+                NodeKind::Method => node.metadata["visibility"] == "pub",
+                _ => false,
+            };
+            if entry_point {
+                node.metadata["entry_point"] = serde_json::json!(true);
+            }
+        }
+    }
+
+    /// Note on the module node how many subtrees the depth limit skipped
+    fn record_depth_truncation(&mut self) {
+        if self.truncated_subtrees == 0 {
+            return;
+        }
+        if let Some(module) = self.nodes.first_mut() {
+            if !module.metadata.is_object() {
+                module.metadata = serde_json::json!({});
+            }
+            module.metadata["depth_truncation"] = serde_json::json!({
+                "max_depth": self.max_depth,
+                "skipped_subtrees": self.truncated_subtrees,
+            });
+        }
+    }
+
     /// Create a module node for the file
     fn create_module_node(&mut self, cursor: &TreeCursor) -> Result<Node> {
         let root = cursor.node();
@@ -121,12 +174,16 @@ impl AstMapper {
     }
 
     /// Walk the tree and extract nodes
-    fn walk_tree(&mut self, cursor: &mut TreeCursor) -> Result<()> {
+    fn walk_tree(&mut self, cursor: &mut TreeCursor, depth: usize) -> Result<()> {
+        if depth > self.max_depth {
+            self.truncated_subtrees += 1;
+            return Ok(());
+        }
         self.visit_node(cursor)?;
 
         if cursor.goto_first_child() {
             loop {
-                self.walk_tree(cursor)?;
+                self.walk_tree(cursor, depth + 1)?;
                 if !cursor.goto_next_sibling() {
                     break;
                 }
@@ -830,7 +887,11 @@ impl AstMapper {
         let mut metadata = serde_json::Map::new();
 
         // Check for visibility modifier
-        if let Some(vis_node) = node.child_by_field_name("visibility_modifier") {
+        let mut cursor = node.walk();
+        let visibility = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "visibility_modifier");
+        if let Some(vis_node) = visibility {
             metadata.insert(
                 "visibility".to_string(),
                 serde_json::Value::String(self.get_node_text(&vis_node)),
@@ -1383,7 +1444,11 @@ impl AstMapper {
         let mut metadata = serde_json::Map::new();
 
         // Check for visibility modifier
-        if let Some(vis_node) = node.child_by_field_name("visibility_modifier") {
+        let mut cursor = node.walk();
+        let visibility = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "visibility_modifier");
+        if let Some(vis_node) = visibility {
             metadata.insert(
                 "visibility".to_string(),
                 serde_json::Value::String(self.get_node_text(&vis_node)),
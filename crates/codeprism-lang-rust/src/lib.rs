@@ -14,6 +14,7 @@ pub use analysis::{
     PerformanceIssue, PerformanceIssueType, RiskLevel, RustAnalysisResult, RustAnalyzer,
     SafetyIssue, SafetyIssueType, Severity, TraitImplementation, UnsafeType, UnsafeUsage,
 };
+pub use ast_mapper::DEFAULT_MAX_DEPTH;
 pub use error::{Error, Result};
 pub use parser::{ParseContext, ParseResult, RustParser};
 pub use types::{Edge, EdgeKind, Language, Node, NodeId, NodeKind, Span};
@@ -1,6 +1,6 @@
 //! Rust parser implementation
 
-use crate::ast_mapper::AstMapper;
+use crate::ast_mapper::{AstMapper, DEFAULT_MAX_DEPTH};
 use crate::error::{Error, Result};
 use crate::types::{Edge, Language, Node};
 use std::path::{Path, PathBuf};
@@ -34,6 +34,8 @@ pub struct ParseResult {
 pub struct RustParser {
     /// Tree-sitter parser for Rust
     parser: Parser,
+    /// Deepest tree level converted into nodes
+    max_depth: usize,
 }
 
 impl RustParser {
@@ -44,7 +46,15 @@ impl RustParser {
             .set_language(&tree_sitter_rust::LANGUAGE.into())
             .expect("Failed to load Rust grammar");
 
-        Self { parser }
+        Self {
+            parser,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Skip subtrees deeper than `max_depth` levels when extracting nodes
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
     }
 
     /// Get the language for a file based on its extension
@@ -72,7 +82,8 @@ impl RustParser {
             context.file_path.clone(),
             language,
             &context.content,
-        );
+        )
+        .with_max_depth(self.max_depth);
 
         let (nodes, edges) = mapper.extract(&tree)?;
 
@@ -172,6 +183,35 @@ mod tests {
             .any(|n| matches!(n.kind, crate::types::NodeKind::Impl)));
     }
 
+    #[test]
+    fn test_visibility_and_entry_points() {
+        let mut parser = RustParser::new();
+        let context = ParseContext {
+            repo_id: "test_repo".to_string(),
+            file_path: PathBuf::from("main.rs"),
+            old_tree: None,
+            content: "fn main() {}\npub fn run() {}\npub(crate) fn internal() {}\nfn helper() {}\npub trait Plugin {}\ntrait Private {}"
+                .to_string(),
+        };
+
+        let result = parser.parse(&context).unwrap();
+        let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+
+        assert_eq!(find("run").metadata["visibility"], "pub");
+        assert_eq!(find("internal").metadata["visibility"], "pub(crate)");
+        assert!(find("helper").metadata.get("visibility").is_none());
+        assert_eq!(find("Plugin").metadata["visibility"], "pub");
+        assert!(find("Private").metadata.get("visibility").is_none());
+
+        let entry_points: Vec<_> = result
+            .nodes
+            .iter()
+            .filter(|n| n.metadata["entry_point"] == true)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(entry_points, vec!["main", "run"]);
+    }
+
     #[test]
     fn test_parse_use_statements() {
         let mut parser = RustParser::new();
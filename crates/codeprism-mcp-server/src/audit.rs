@@ -0,0 +1,225 @@
+//! Audit log of tool calls for compliance
+//!
+//! When [`SecurityConfig::enable_audit_log`] is set, every tool call appends
+//! one JSON line to the configured file: when it ran, which tool, with which
+//! parameters, against which repository, for which client, and how it ended.
+//!
+//! Parameters are redacted before they are written. Values under keys that
+//! name credentials are replaced, and values under keys that carry source
+//! text, as well as any overly long string, are reduced to their size, so
+//! neither secrets nor file contents reach the log.
+
+use crate::config::SecurityConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Placeholder written instead of a credential
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Parameter keys whose values are credentials, matched case-insensitively
+/// as substrings
+const SECRET_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "credential",
+    "authorization",
+    "private_key",
+];
+
+/// Parameter keys whose values are source text, matched exactly
+const CONTENT_KEYS: &[&str] = &[
+    "content", "contents", "source", "code", "text", "body", "diff", "patch",
+];
+
+/// Longest string parameter written as is
+const MAX_LOGGED_STRING: usize = 512;
+
+/// How a tool call ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStatus {
+    /// The tool returned a result
+    Success,
+    /// The tool returned an error result
+    Error,
+    /// The call was cancelled for exceeding its time limit
+    Timeout,
+    /// The call failed at the protocol level
+    Failed,
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub tool: String,
+    /// Call arguments after [`redact_parameters`]
+    pub parameters: serde_json::Value,
+    /// Repository the server was analyzing, if one was initialized
+    pub repo_id: Option<String>,
+    /// Client name reported during MCP initialization
+    pub client: Option<String>,
+    pub status: AuditStatus,
+}
+
+/// Append-only JSONL audit log
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    /// Open `path` for appending, creating it and its directory if needed
+    pub fn open(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// The audit log configured by `security`, or `None` when auditing is off
+    pub fn from_config(security: &SecurityConfig) -> crate::Result<Option<Self>> {
+        if !security.enable_audit_log {
+            return Ok(None);
+        }
+        let Some(path) = &security.audit_log_path else {
+            return Err(crate::Error::server_init(
+                "Audit logging is enabled but no audit_log_path is set",
+            ));
+        };
+        Self::open(path).map(Some).map_err(|e| {
+            crate::Error::server_init(format!(
+                "Failed to open audit log {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// File the entries are appended to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `entry` as one JSON line
+    pub fn record(&self, entry: &AuditEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(line.as_bytes())?;
+        file.flush()
+    }
+}
+
+/// Copy of tool call arguments that is safe to write to the audit log
+pub fn redact_parameters(parameters: &serde_json::Value) -> serde_json::Value {
+    match parameters {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| {
+                let lower = key.to_ascii_lowercase();
+                let value = if SECRET_KEYS.iter().any(|secret| lower.contains(secret)) {
+                    serde_json::Value::String(REDACTED.to_string())
+                } else if CONTENT_KEYS.contains(&lower.as_str()) {
+                    omitted(value)
+                } else {
+                    redact_parameters(value)
+                };
+                (key.clone(), value)
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items.iter().map(redact_parameters).collect(),
+        serde_json::Value::String(text) if text.len() > MAX_LOGGED_STRING => omitted(parameters),
+        other => other.clone(),
+    }
+}
+
+/// Size placeholder for a value that must not be logged
+fn omitted(value: &serde_json::Value) -> serde_json::Value {
+    let bytes = match value {
+        serde_json::Value::String(text) => text.len(),
+        other => other.to_string().len(),
+    };
+    serde_json::Value::String(format!("[OMITTED {bytes} bytes]"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_parameters_hides_secrets_and_contents() {
+        let parameters = json!({
+            "symbol_id": "abc123",
+            "api_key": "sk-live-1234",
+            "auth": { "Authorization": "Bearer xyz", "user": "ci" },
+            "content": "fn main() {}",
+            "files": ["src/lib.rs", "x".repeat(MAX_LOGGED_STRING + 1)],
+            "limit": 10
+        });
+
+        let redacted = redact_parameters(&parameters);
+
+        assert_eq!(redacted["symbol_id"], "abc123");
+        assert_eq!(redacted["api_key"], REDACTED);
+        assert_eq!(redacted["auth"]["Authorization"], REDACTED);
+        assert_eq!(redacted["auth"]["user"], "ci");
+        assert_eq!(redacted["content"], "[OMITTED 12 bytes]");
+        assert_eq!(redacted["files"][0], "src/lib.rs");
+        assert_eq!(redacted["files"][1], "[OMITTED 513 bytes]");
+        assert_eq!(redacted["limit"], 10);
+        assert!(!redacted.to_string().contains("sk-live"));
+    }
+
+    #[test]
+    fn test_audit_log_appends_json_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let log = AuditLog::open(temp_dir.path().join("logs/audit.jsonl")).unwrap();
+        for tool in ["search_symbols", "find_callers"] {
+            log.record(&AuditEntry {
+                timestamp: Utc::now(),
+                tool: tool.to_string(),
+                parameters: json!({}),
+                repo_id: Some("repo".to_string()),
+                client: None,
+                status: AuditStatus::Success,
+            })
+            .unwrap();
+        }
+
+        let written = std::fs::read_to_string(log.path()).unwrap();
+        let entries: Vec<AuditEntry> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].tool, "find_callers");
+
+        let disabled = SecurityConfig {
+            enable_audit_log: false,
+            ..crate::Config::default().profile.security
+        };
+        assert!(AuditLog::from_config(&disabled).unwrap().is_none());
+        let missing_path = SecurityConfig {
+            enable_audit_log: true,
+            audit_log_path: None,
+            ..disabled
+        };
+        assert!(AuditLog::from_config(&missing_path).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Cooperative cancellation of tool calls past their time limit
+//!
+//! Most tool handlers are synchronous, so aborting their task cannot stop
+//! them: abort only takes effect at an await point. Calls therefore run on
+//! a blocking thread with a [`CancellationToken`] installed for that thread,
+//! and long-running loops poll [`is_cancelled`] to stop early once the
+//! dispatcher gives up on the call.
+//!
+//! The per-file analyze_* scans, find_hotspots (betweenness centrality),
+//! find_longest_call_chains and find_similar_functions stop early; the
+//! graph queries handed to codeprism-core and codeprism-analysis receive the
+//! flag from [`current_flag`]. Queries that are linear in the graph size,
+//! such as trace_path, find_import_cycles, analyze_impact and
+//! extract_subgraph, are not checked and always run to completion.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Cancellation flag shared between a tool call and its dispatcher
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: Arc<AtomicBool>,
+    notify: Notify,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+impl CancellationToken {
+    /// Create a token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the call holding this token to stop
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// Whether [`Self::cancel`] has been called
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the token is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Run `f` with this token as the current thread's token
+    ///
+    /// The previous token, if any, is restored when `f` returns.
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<CancellationToken>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+}
+
+/// Flag of the current thread's token, for code outside this crate
+///
+/// None outside [`CancellationToken::scope`].
+pub fn current_flag() -> Option<Arc<AtomicBool>> {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .map(|token| token.inner.cancelled.clone())
+    })
+}
+
+/// Whether the tool call running on this thread has been cancelled
+///
+/// Always false outside [`CancellationToken::scope`].
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_installs_and_restores_the_token() {
+        let token = CancellationToken::new();
+        assert!(!is_cancelled());
+        token.scope(|| {
+            assert!(!is_cancelled());
+            token.cancel();
+            assert!(is_cancelled());
+            assert!(current_flag().unwrap().load(Ordering::SeqCst));
+        });
+        assert!(!is_cancelled());
+        assert!(current_flag().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_wakes_waiters() {
+        let token = CancellationToken::new();
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        tokio::task::yield_now().await;
+        token.cancel();
+        waiter.await.unwrap();
+        // Already cancelled tokens return at once
+        token.cancelled().await;
+    }
+}
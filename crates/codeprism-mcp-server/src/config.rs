@@ -4,7 +4,10 @@
 //! dynamic tool enablement, performance monitoring, and production-ready features.
 //! Ported from legacy codeprism-mcp and adapted for rust-sdk architecture.
 
+use crate::frameworks::FrameworkRule;
 use crate::Result;
+use codeprism_analysis::SecretScanConfig;
+use codeprism_core::ReparsePolicy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -49,6 +52,19 @@ pub struct ServerSettings {
     pub exclude_dirs: Vec<String>,
     /// File extensions to include
     pub include_extensions: Option<Vec<String>>,
+    /// Globs, relative to the repository root, of files that stay indexed and
+    /// searchable but are left out of `analyze_*` tools unless a call sets
+    /// `include_excluded`, e.g. `migrations/**` or `**/fixtures/*`
+    #[serde(default)]
+    pub analysis_exclude: Vec<String>,
+    /// Entropy threshold and ignore-list of the `secrets` category of
+    /// `analyze_security`
+    #[serde(default)]
+    pub secret_scan: SecretScanConfig,
+    /// Rules teaching the parsers about framework routes, handlers and entry
+    /// points, such as an internal `@endpoint("/x")` decorator
+    #[serde(default)]
+    pub framework_rules: Vec<FrameworkRule>,
     /// Dependency scanning mode
     pub dependency_mode: DependencyMode,
     /// Default timeout for operations
@@ -57,8 +73,41 @@ pub struct ServerSettings {
     pub max_concurrent_operations: usize,
     /// Enable streaming responses
     pub enable_streaming: bool,
+    /// Populate the graph file-by-file in the background so tools can use the partial graph
+    #[serde(default)]
+    pub streaming_index: bool,
+    /// When indexing parses a file again because its first tree is dominated
+    /// by `ERROR` nodes
+    #[serde(default)]
+    pub reparse: ReparsePolicy,
+    /// Keep the graph current by re-indexing files as they change on disk
+    #[serde(default)]
+    pub watch_repository: bool,
+    /// Add fully-qualified names (`module.Class.method`) next to short names in symbol output
+    #[serde(default)]
+    pub qualified_names: bool,
+    /// How long shutdown waits for in-flight tool calls before exiting
+    #[serde(default = "default_shutdown_timeout")]
+    pub shutdown_timeout: Duration,
     /// Maximum response size in bytes
     pub max_response_size: usize,
+    /// Largest part, in bytes, returned when reading a file resource
+    #[serde(default = "default_resource_chunk_size")]
+    pub resource_chunk_size: usize,
+    /// Repository indexed when the server starts; readiness waits for its graph
+    #[serde(default)]
+    pub warm_up_repository: Option<PathBuf>,
+    /// Address serving `/healthz` and `/readyz` over HTTP, off when unset
+    #[serde(default)]
+    pub health_addr: Option<std::net::SocketAddr>,
+}
+
+fn default_shutdown_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_resource_chunk_size() -> usize {
+    64 * 1024
 }
 
 /// Dependency scanning modes
@@ -83,6 +132,105 @@ pub struct ToolsConfig {
     pub tool_configs: HashMap<String, ToolConfig>,
     /// Conditional enablement rules
     pub enablement_rules: Vec<EnablementRule>,
+    /// Time limits per tool category
+    #[serde(default)]
+    pub timeouts: ToolTimeoutConfig,
+    /// Response truncation limits per tool category
+    #[serde(default)]
+    pub response_limits: ResponseLimitConfig,
+}
+
+/// Time limits for tool calls by category
+///
+/// A tool call running longer than its limit is cancelled and answered with
+/// a timeout error. A `timeout` in the tool's own [`ToolConfig`] takes
+/// precedence, and tools without a category use `default_timeout`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolTimeoutConfig {
+    /// Limit for navigation tools
+    pub navigation: Duration,
+    /// Limit for search tools
+    pub search: Duration,
+    /// Limit for analysis tools
+    pub analysis: Duration,
+    /// Limit for workflow tools
+    pub workflow: Duration,
+}
+
+impl Default for ToolTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            navigation: Duration::from_secs(15),
+            search: Duration::from_secs(30),
+            analysis: Duration::from_secs(120),
+            workflow: Duration::from_secs(120),
+        }
+    }
+}
+
+impl ToolTimeoutConfig {
+    /// Limit for a tool category, `None` for categories without their own limit
+    pub fn for_category(&self, category: &ToolCategory) -> Option<Duration> {
+        match category {
+            ToolCategory::CoreNavigation => Some(self.navigation),
+            ToolCategory::SearchDiscovery => Some(self.search),
+            ToolCategory::Analysis => Some(self.analysis),
+            ToolCategory::Workflow => Some(self.workflow),
+            ToolCategory::Experimental => None,
+        }
+    }
+}
+
+/// How much of a tool response is returned before it is truncated
+///
+/// Arrays keep their first `max_array_items` items followed by a
+/// `{"_truncated": true, ...}` marker, and strings are cut after
+/// `max_string_bytes`; see [`crate::response::truncate_response`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseLimits {
+    /// Items kept in each array
+    pub max_array_items: usize,
+    /// Bytes kept of each string
+    pub max_string_bytes: usize,
+}
+
+impl Default for ResponseLimits {
+    fn default() -> Self {
+        Self {
+            max_array_items: 500,
+            max_string_bytes: 16 * 1024,
+        }
+    }
+}
+
+/// Response truncation limits by tool category
+///
+/// Experimental tools and tools without a category use `other`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResponseLimitConfig {
+    /// Limits for navigation tools
+    pub navigation: ResponseLimits,
+    /// Limits for search tools
+    pub search: ResponseLimits,
+    /// Limits for analysis tools
+    pub analysis: ResponseLimits,
+    /// Limits for workflow tools
+    pub workflow: ResponseLimits,
+    /// Limits for all other tools
+    pub other: ResponseLimits,
+}
+
+impl ResponseLimitConfig {
+    /// Limits for a tool category, `other` for tools without one
+    pub fn for_category(&self, category: Option<&ToolCategory>) -> ResponseLimits {
+        match category {
+            Some(ToolCategory::CoreNavigation) => self.navigation,
+            Some(ToolCategory::SearchDiscovery) => self.search,
+            Some(ToolCategory::Analysis) => self.analysis,
+            Some(ToolCategory::Workflow) => self.workflow,
+            Some(ToolCategory::Experimental) | None => self.other,
+        }
+    }
 }
 
 /// Tool categories for organization
@@ -175,6 +323,48 @@ pub struct MonitoringConfig {
     pub metrics_export_path: Option<PathBuf>,
     /// Performance alerting thresholds
     pub alert_thresholds: AlertThresholds,
+    /// Caps on tool calls running at once
+    #[serde(default)]
+    pub concurrency: ConcurrencyLimits,
+}
+
+/// Caps on tool calls running at once
+///
+/// Analysis and workflow tools share the `expensive` limit and all other
+/// tools the `cheap` one, so a burst of heavy analyses cannot starve
+/// navigation. A call over its limit waits up to `queue_timeout` for a slot
+/// and is then rejected as busy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyLimits {
+    /// Navigation, search and other inexpensive calls running at once
+    pub cheap: usize,
+    /// Analysis and workflow calls running at once
+    pub expensive: usize,
+    /// How long a call waits for a free slot; zero rejects it at once
+    pub queue_timeout: Duration,
+}
+
+impl Default for ConcurrencyLimits {
+    fn default() -> Self {
+        Self {
+            cheap: 32,
+            expensive: 4,
+            queue_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ConcurrencyLimits {
+    /// Whether calls to `tool_name` count against the `expensive` limit
+    ///
+    /// True for analysis and workflow tools, including names
+    /// [`tool_category`] does not know.
+    pub fn is_expensive(tool_name: &str) -> bool {
+        matches!(
+            tool_category(tool_name),
+            Some(ToolCategory::Analysis | ToolCategory::Workflow)
+        )
+    }
 }
 
 /// Performance alerting thresholds
@@ -207,6 +397,26 @@ pub struct SecurityConfig {
     pub validate_paths: bool,
     /// Rate limiting configuration
     pub rate_limiting: RateLimitConfig,
+    /// Tools that may be listed and called, every tool when unset
+    #[serde(default)]
+    pub enabled_tools: Option<Vec<String>>,
+    /// Tools hidden from `tools/list` and rejected by `tools/call`
+    #[serde(default)]
+    pub disabled_tools: Vec<String>,
+}
+
+impl SecurityConfig {
+    /// Whether `tool_name` passes the allowlist and denylist
+    ///
+    /// The denylist wins when a tool appears in both.
+    pub fn allows_tool(&self, tool_name: &str) -> bool {
+        if self.disabled_tools.iter().any(|tool| tool == tool_name) {
+            return false;
+        }
+        self.enabled_tools
+            .as_ref()
+            .is_none_or(|tools| tools.iter().any(|tool| tool == tool_name))
+    }
 }
 
 /// Rate limiting configuration
@@ -297,11 +507,22 @@ impl ConfigProfileManager {
                         "ts".to_string(),
                         "rs".to_string(),
                     ]),
+                    analysis_exclude: Vec::new(),
+                    secret_scan: SecretScanConfig::default(),
+                    framework_rules: Vec::new(),
                     dependency_mode: DependencyMode::Exclude,
                     default_timeout: Duration::from_secs(30),
                     max_concurrent_operations: 4,
                     enable_streaming: true,
+                    streaming_index: false,
+                    reparse: ReparsePolicy::default(),
+                    watch_repository: false,
+                    qualified_names: false,
+                    shutdown_timeout: Duration::from_secs(10),
                     max_response_size: 50_000,
+                    resource_chunk_size: 32 * 1024,
+                    warm_up_repository: None,
+                    health_addr: None,
                 },
                 tools: ToolsConfig {
                     enabled_categories: vec![
@@ -311,6 +532,13 @@ impl ConfigProfileManager {
                     disabled_tools: vec!["analyze_transitive_dependencies".to_string()],
                     tool_configs: HashMap::new(),
                     enablement_rules: vec![],
+                    response_limits: ResponseLimitConfig::default(),
+                    timeouts: ToolTimeoutConfig {
+                        navigation: Duration::from_secs(10),
+                        search: Duration::from_secs(15),
+                        analysis: Duration::from_secs(30),
+                        workflow: Duration::from_secs(30),
+                    },
                 },
                 monitoring: MonitoringConfig {
                     enabled: true,
@@ -326,6 +554,7 @@ impl ConfigProfileManager {
                         max_error_rate: 0.1,
                         min_success_rate: 0.9,
                     },
+                    concurrency: ConcurrencyLimits::default(),
                 },
                 security: SecurityConfig {
                     enable_audit_log: false,
@@ -340,6 +569,8 @@ impl ConfigProfileManager {
                         max_concurrent: 10,
                         burst_size: 20,
                     },
+                    enabled_tools: None,
+                    disabled_tools: vec![],
                 },
                 caching: CachingConfig {
                     enabled: true,
@@ -376,11 +607,22 @@ impl ConfigProfileManager {
                         "build".to_string(),
                     ],
                     include_extensions: None, // Include all supported extensions
+                    analysis_exclude: Vec::new(),
+                    secret_scan: SecretScanConfig::default(),
+                    framework_rules: Vec::new(),
                     dependency_mode: DependencyMode::Smart,
                     default_timeout: Duration::from_secs(120),
                     max_concurrent_operations: 12,
                     enable_streaming: true,
+                    streaming_index: false,
+                    reparse: ReparsePolicy::default(),
+                    watch_repository: false,
+                    qualified_names: false,
+                    shutdown_timeout: Duration::from_secs(30),
                     max_response_size: 150_000,
+                    resource_chunk_size: 128 * 1024,
+                    warm_up_repository: None,
+                    health_addr: None,
                 },
                 tools: ToolsConfig {
                     enabled_categories: vec![
@@ -398,6 +640,8 @@ impl ConfigProfileManager {
                             tools: vec!["find_duplicates".to_string()],
                         }],
                     }],
+                    timeouts: ToolTimeoutConfig::default(),
+                    response_limits: ResponseLimitConfig::default(),
                 },
                 monitoring: MonitoringConfig {
                     enabled: true,
@@ -413,6 +657,11 @@ impl ConfigProfileManager {
                         max_error_rate: 0.05,
                         min_success_rate: 0.95,
                     },
+                    concurrency: ConcurrencyLimits {
+                        cheap: 64,
+                        expensive: 12,
+                        ..ConcurrencyLimits::default()
+                    },
                 },
                 security: SecurityConfig {
                     enable_audit_log: true,
@@ -431,6 +680,8 @@ impl ConfigProfileManager {
                         max_concurrent: 15,
                         burst_size: 50,
                     },
+                    enabled_tools: None,
+                    disabled_tools: vec![],
                 },
                 caching: CachingConfig {
                     enabled: true,
@@ -468,11 +719,22 @@ impl ConfigProfileManager {
                         "coverage".to_string(),
                     ],
                     include_extensions: None,
+                    analysis_exclude: Vec::new(),
+                    secret_scan: SecretScanConfig::default(),
+                    framework_rules: Vec::new(),
                     dependency_mode: DependencyMode::Smart,
                     default_timeout: Duration::from_secs(300),
                     max_concurrent_operations: 24,
                     enable_streaming: true,
+                    streaming_index: false,
+                    reparse: ReparsePolicy::default(),
+                    watch_repository: false,
+                    qualified_names: false,
+                    shutdown_timeout: Duration::from_secs(60),
                     max_response_size: 500_000,
+                    resource_chunk_size: 256 * 1024,
+                    warm_up_repository: None,
+                    health_addr: None,
                 },
                 tools: ToolsConfig {
                     enabled_categories: vec![
@@ -484,6 +746,13 @@ impl ConfigProfileManager {
                     disabled_tools: vec![],
                     tool_configs: HashMap::new(),
                     enablement_rules: vec![],
+                    response_limits: ResponseLimitConfig::default(),
+                    timeouts: ToolTimeoutConfig {
+                        navigation: Duration::from_secs(30),
+                        search: Duration::from_secs(60),
+                        analysis: Duration::from_secs(300),
+                        workflow: Duration::from_secs(300),
+                    },
                 },
                 monitoring: MonitoringConfig {
                     enabled: true,
@@ -499,6 +768,11 @@ impl ConfigProfileManager {
                         max_error_rate: 0.02,
                         min_success_rate: 0.98,
                     },
+                    concurrency: ConcurrencyLimits {
+                        cheap: 128,
+                        expensive: 24,
+                        ..ConcurrencyLimits::default()
+                    },
                 },
                 security: SecurityConfig {
                     enable_audit_log: true,
@@ -518,6 +792,8 @@ impl ConfigProfileManager {
                         max_concurrent: 30,
                         burst_size: 100,
                     },
+                    enabled_tools: None,
+                    disabled_tools: vec![],
                 },
                 caching: CachingConfig {
                     enabled: true,
@@ -663,6 +939,18 @@ impl ConfigProfileManager {
             }
         }
 
+        if let Ok(timeout) = std::env::var("CODEPRISM_SHUTDOWN_TIMEOUT_SECS") {
+            if let Ok(secs) = timeout.parse::<u64>() {
+                profile.settings.shutdown_timeout = Duration::from_secs(secs);
+            }
+        }
+
+        if let Ok(chunk_size) = std::env::var("CODEPRISM_RESOURCE_CHUNK_SIZE") {
+            if let Ok(size) = chunk_size.parse::<usize>() {
+                profile.settings.resource_chunk_size = size;
+            }
+        }
+
         if let Ok(enable_cache) = std::env::var("CODEPRISM_ENABLE_CACHE") {
             profile.caching.enabled = enable_cache.to_lowercase() == "true";
         }
@@ -671,6 +959,37 @@ impl ConfigProfileManager {
             profile.caching.cache_dir = PathBuf::from(cache_dir);
         }
 
+        if let Ok(streaming_index) = std::env::var("CODEPRISM_STREAMING_INDEX") {
+            profile.settings.streaming_index = streaming_index.to_lowercase() == "true";
+        }
+
+        if let Ok(watch_repository) = std::env::var("CODEPRISM_WATCH_REPOSITORY") {
+            profile.settings.watch_repository = watch_repository.to_lowercase() == "true";
+        }
+
+        if let Ok(qualified_names) = std::env::var("CODEPRISM_QUALIFIED_NAMES") {
+            profile.settings.qualified_names = qualified_names.to_lowercase() == "true";
+        }
+
+        if let Ok(repository) = std::env::var("CODEPRISM_WARM_UP_REPOSITORY") {
+            profile.settings.warm_up_repository = Some(PathBuf::from(repository));
+        }
+
+        if let Ok(disabled_tools) = std::env::var("CODEPRISM_DISABLED_TOOLS") {
+            profile.security.disabled_tools = disabled_tools
+                .split(',')
+                .map(str::trim)
+                .filter(|tool| !tool.is_empty())
+                .map(String::from)
+                .collect();
+        }
+
+        if let Ok(health_addr) = std::env::var("CODEPRISM_HEALTH_ADDR") {
+            if let Ok(addr) = health_addr.parse() {
+                profile.settings.health_addr = Some(addr);
+            }
+        }
+
         profile.name = format!("{profile_name}_env");
         profile.description = format!("Environment-configured {profile_name} profile");
 
@@ -733,12 +1052,57 @@ impl Config {
             ));
         }
 
+        let concurrency = &self.profile.monitoring.concurrency;
+        if concurrency.cheap == 0 || concurrency.expensive == 0 {
+            return Err(crate::Error::server_init(
+                "Concurrent tool call limits must be greater than 0",
+            ));
+        }
+
         if self.profile.settings.max_file_size_mb == 0 {
             return Err(crate::Error::server_init(
                 "Max file size must be greater than 0",
             ));
         }
 
+        if self.profile.settings.resource_chunk_size == 0 {
+            return Err(crate::Error::server_init(
+                "Resource chunk size must be greater than 0",
+            ));
+        }
+
+        let max_error_ratio = self.profile.settings.reparse.max_error_ratio;
+        if !(0.0..=1.0).contains(&max_error_ratio) {
+            return Err(crate::Error::server_init(format!(
+                "Re-parse max_error_ratio must be between 0 and 1, got {max_error_ratio}"
+            )));
+        }
+
+        for pattern in &self.profile.settings.analysis_exclude {
+            if let Err(e) = glob::Pattern::new(pattern) {
+                return Err(crate::Error::server_init(format!(
+                    "Invalid analysis_exclude pattern '{pattern}': {e}"
+                )));
+            }
+        }
+
+        for pattern in &self.profile.settings.secret_scan.ignore_patterns {
+            if let Err(e) = regex::Regex::new(pattern) {
+                return Err(crate::Error::server_init(format!(
+                    "Invalid secret_scan ignore pattern '{pattern}': {e}"
+                )));
+            }
+        }
+
+        for rule in &self.profile.settings.framework_rules {
+            if let Err(e) = glob::Pattern::new(&rule.callee) {
+                return Err(crate::Error::server_init(format!(
+                    "Invalid callee pattern '{}' in framework rule '{}': {e}",
+                    rule.callee, rule.name
+                )));
+            }
+        }
+
         // Return validation warnings
         self.manager.validate_profile(&self.profile)
     }
@@ -756,20 +1120,7 @@ impl Config {
         }
 
         // Check if tool category is enabled
-        let tool_category = match tool_name {
-            "trace_path" | "find_dependencies" | "find_references" | "explain_symbol"
-            | "search_symbols" => Some(ToolCategory::CoreNavigation),
-            "search_content" | "find_patterns" | "semantic_search" | "search_by_type"
-            | "advanced_search" => Some(ToolCategory::SearchDiscovery),
-            "analyze_complexity"
-            | "analyze_control_flow"
-            | "analyze_code_quality"
-            | "analyze_performance" => Some(ToolCategory::Analysis),
-            "provide_guidance" | "optimize_code" | "batch_process" | "workflow_automation" => {
-                Some(ToolCategory::Workflow)
-            }
-            _ => None,
-        };
+        let tool_category = tool_category(tool_name);
 
         if let Some(category) = tool_category {
             self.profile.tools.enabled_categories.contains(&category)
@@ -778,6 +1129,35 @@ impl Config {
         }
     }
 
+    /// Time limit for a call to `tool_name`
+    ///
+    /// A timeout in the tool's own configuration wins over its category's
+    /// limit; tools without either use the default timeout.
+    pub fn tool_timeout(&self, tool_name: &str) -> Duration {
+        let tools = &self.profile.tools;
+        tools
+            .tool_configs
+            .get(tool_name)
+            .and_then(|config| config.timeout)
+            .or_else(|| {
+                tool_category(tool_name).and_then(|category| tools.timeouts.for_category(&category))
+            })
+            .unwrap_or(self.profile.settings.default_timeout)
+    }
+
+    /// Caps on tool calls running at once
+    pub fn concurrency_limits(&self) -> &ConcurrencyLimits {
+        &self.profile.monitoring.concurrency
+    }
+
+    /// Truncation limits for the response of `tool_name`
+    pub fn response_limits(&self, tool_name: &str) -> ResponseLimits {
+        self.profile
+            .tools
+            .response_limits
+            .for_category(tool_category(tool_name).as_ref())
+    }
+
     /// Get tool-specific configuration
     pub fn get_tool_config(&self, tool_name: &str) -> Option<&ToolConfig> {
         self.profile.tools.tool_configs.get(tool_name)
@@ -855,6 +1235,73 @@ pub struct AnalysisConfigCompat {
     pub cache_ttl_secs: u64,
 }
 
+/// Category of a tool, `None` for the utility tools
+///
+/// Names not listed here count as analysis tools, so a tool added without a
+/// category gets the long timeout and the expensive concurrency limit rather
+/// than the navigation ones.
+pub fn tool_category(tool_name: &str) -> Option<ToolCategory> {
+    match tool_name {
+        "ping"
+        | "version"
+        | "system_info"
+        | "health_check"
+        | "get_repository_info"
+        | "plan_index"
+        | "save_graph_snapshot" => None,
+        "trace_path" | "find_dependencies" | "find_import_cycles" | "find_references"
+        | "find_callers" | "find_callees" | "extract_subgraph" | "test_coverage_map"
+        | "explain_symbol" | "search_symbols" | "find_entrypoints" => {
+            Some(ToolCategory::CoreNavigation)
+        }
+        "search_content" | "find_patterns" | "find_files" | "semantic_search"
+        | "search_by_type" | "advanced_search" => Some(ToolCategory::SearchDiscovery),
+        "provide_guidance" | "optimize_code" | "batch_process" | "workflow_automation" => {
+            Some(ToolCategory::Workflow)
+        }
+        "analyze_api_leaks"
+        | "analyze_code_quality"
+        | "analyze_complexity"
+        | "analyze_control_flow"
+        | "analyze_data_clumps"
+        | "analyze_dependencies"
+        | "analyze_error_style_consistency"
+        | "analyze_global_state"
+        | "analyze_import_order"
+        | "analyze_infinite_loops"
+        | "analyze_javascript"
+        | "analyze_logical_coupling"
+        | "analyze_null_safety"
+        | "analyze_performance"
+        | "analyze_promises"
+        | "analyze_security"
+        | "analyze_type_coverage"
+        | "analyze_whitespace"
+        | "check_api_stability"
+        | "compare_symbols"
+        | "detect_patterns"
+        | "diff_api_surface"
+        | "diff_files"
+        | "export_complexity_heatmap"
+        | "export_graph"
+        | "find_churned_untested_files"
+        | "find_commented_code"
+        | "find_hotspots"
+        | "find_impact"
+        | "find_longest_call_chains"
+        | "find_orphans"
+        | "find_similar_functions"
+        | "find_unused_code"
+        | "find_variable_heavy_functions"
+        | "list_tech_debt"
+        | "review_diff_findings"
+        | "specialized_analysis"
+        | "trace_data_flow" => Some(ToolCategory::Analysis),
+        // Unknown tools are assumed to be expensive
+        _ => Some(ToolCategory::Analysis),
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         let manager = ConfigProfileManager::new();
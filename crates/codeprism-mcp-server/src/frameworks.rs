@@ -0,0 +1,560 @@
+//! Rule-based framework detection
+//!
+//! The language parsers recognize a fixed set of frameworks. Framework rules
+//! from the server settings describe any other: a decorator or call whose
+//! callee matches a glob such as `endpoint` or `*.route` registers a route or
+//! marks a handler or entry point. Rules run over each file's syntax tree
+//! right after its parser, so what they find is indexed like any other node.
+//! Decorators are matched in Python and TypeScript, calls in every language.
+
+use codeprism_core::{
+    Edge, EdgeKind, Language, LanguageParser, Node, NodeId, NodeKind, ParseContext, ParseResult,
+    Span,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tree_sitter::Node as TsNode;
+
+/// Syntax a framework rule matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameworkPattern {
+    /// A decorator on a function, method or class, with or without arguments
+    Decorator,
+    /// A call, such as `app.get("/x", handler)`
+    Call,
+}
+
+/// What a match of a framework rule defines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameworkRole {
+    /// A route whose path is the first string argument; the `Route` node
+    /// routes to the decorated definition or to the functions passed in
+    Route,
+    /// A request or event handler
+    Handler,
+    /// A place where execution starts
+    Entrypoint,
+}
+
+impl FrameworkRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FrameworkRole::Route => "route",
+            FrameworkRole::Handler => "handler",
+            FrameworkRole::Entrypoint => "entrypoint",
+        }
+    }
+}
+
+/// A framework construct taught to the server through configuration
+///
+/// Handlers and entry points are the decorated definitions, or the functions
+/// passed to a matching call by name; they get `framework` and
+/// `framework_role` metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameworkRule {
+    /// Rule name, recorded as `framework` on what a match produces
+    pub name: String,
+    /// Syntax the rule matches
+    pub pattern: FrameworkPattern,
+    /// Glob matched against the callee as written, e.g. `endpoint` or `*.route`
+    pub callee: String,
+    /// What a match defines
+    pub role: FrameworkRole,
+    /// HTTP method put before the path in route names, e.g. `GET`
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Languages the rule applies to, all when empty
+    #[serde(default)]
+    pub languages: Vec<Language>,
+}
+
+impl FrameworkRule {
+    /// Create a rule applying to all languages
+    pub fn new(
+        name: impl Into<String>,
+        pattern: FrameworkPattern,
+        callee: impl Into<String>,
+        role: FrameworkRole,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            pattern,
+            callee: callee.into(),
+            role,
+            method: None,
+            languages: Vec::new(),
+        }
+    }
+
+    /// Set the HTTP method of the routes the rule registers
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+
+    /// Restrict the rule to some languages
+    pub fn with_languages(mut self, languages: Vec<Language>) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// Whether the rule applies to files of a language
+    pub fn applies_to(&self, language: Language) -> bool {
+        self.languages.is_empty() || self.languages.contains(&language)
+    }
+}
+
+/// Framework rules ready for matching
+#[derive(Debug, Default)]
+pub struct FrameworkDetector {
+    rules: Vec<(FrameworkRule, glob::Pattern)>,
+}
+
+/// A decorator or call matching a rule
+struct Match<'tree> {
+    rule: usize,
+    /// The decorator or call
+    site: TsNode<'tree>,
+    path: Option<String>,
+    targets: Vec<Target<'tree>>,
+}
+
+/// Definition a match applies to
+#[derive(Clone)]
+enum Target<'tree> {
+    /// The definition declared at a name node, for decorators
+    Definition(TsNode<'tree>),
+    /// A function of the file passed to a call by name
+    Named(String),
+}
+
+impl FrameworkDetector {
+    /// Compile rules; rules with an invalid callee glob are skipped
+    ///
+    /// [`Config::validate`](crate::config::Config::validate) rejects invalid
+    /// globs before the server starts.
+    pub fn new(rules: &[FrameworkRule]) -> Self {
+        Self {
+            rules: rules
+                .iter()
+                .filter_map(|rule| {
+                    let pattern = glob::Pattern::new(&rule.callee).ok()?;
+                    Some((rule.clone(), pattern))
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether no rule applies to files of a language
+    pub fn is_empty_for(&self, language: Language) -> bool {
+        !self.rules.iter().any(|(rule, _)| rule.applies_to(language))
+    }
+
+    /// Add what the rules find in a parsed file to its parse result
+    pub fn apply(&self, language: Language, context: &ParseContext, result: &mut ParseResult) {
+        if self.is_empty_for(language) {
+            return;
+        }
+        let source = context.content.as_bytes();
+        let tree = result.tree.clone();
+        let mut matches = Vec::new();
+        let mut cursor = tree.walk();
+        'walk: loop {
+            self.match_node(language, cursor.node(), source, &mut matches);
+            if cursor.goto_first_child() || cursor.goto_next_sibling() {
+                continue;
+            }
+            loop {
+                if !cursor.goto_parent() {
+                    break 'walk;
+                }
+                if cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+
+        let mut seen: HashSet<NodeId> = result.nodes.iter().map(|node| node.id).collect();
+        for found in matches {
+            let rule = &self.rules[found.rule].0;
+            let targets: Vec<NodeId> = found
+                .targets
+                .iter()
+                .filter_map(|target| find_target(&result.nodes, target, source))
+                .collect();
+
+            if rule.role == FrameworkRole::Route {
+                let Some(path) = found.path else {
+                    continue;
+                };
+                let name = match &rule.method {
+                    Some(method) => format!("{} {path}", method.to_uppercase()),
+                    None => path.clone(),
+                };
+                let route = Node::new(
+                    &context.repo_id,
+                    NodeKind::Route,
+                    name,
+                    language,
+                    context.file_path.clone(),
+                    span_of(found.site),
+                )
+                .with_metadata(serde_json::json!({
+                    "framework": rule.name,
+                    "path": path,
+                    "method": rule.method.as_ref().map(|method| method.to_uppercase()),
+                }));
+                if !seen.insert(route.id) {
+                    continue;
+                }
+                for &target in &targets {
+                    result
+                        .edges
+                        .push(Edge::new(route.id, target, EdgeKind::RoutesTo));
+                }
+                result.nodes.push(route);
+            } else {
+                // Parsers may emit a definition more than once
+                for node in result
+                    .nodes
+                    .iter_mut()
+                    .filter(|node| targets.contains(&node.id))
+                {
+                    if !node.metadata.is_object() {
+                        node.metadata = serde_json::json!({});
+                    }
+                    node.metadata["framework"] = serde_json::json!(rule.name);
+                    node.metadata["framework_role"] = serde_json::json!(rule.role.as_str());
+                }
+            }
+        }
+    }
+
+    fn match_node<'tree>(
+        &self,
+        language: Language,
+        node: TsNode<'tree>,
+        source: &[u8],
+        matches: &mut Vec<Match<'tree>>,
+    ) {
+        let (pattern, callee, arguments, targets) = match node.kind() {
+            "decorator" => {
+                let Some(expression) = node.named_child(0) else {
+                    return;
+                };
+                let (callee, arguments) = match expression.child_by_field_name("function") {
+                    Some(function) => (function, expression.child_by_field_name("arguments")),
+                    None => (expression, None),
+                };
+                let targets: Vec<Target<'_>> = decorated_name(node)
+                    .map(Target::Definition)
+                    .into_iter()
+                    .collect();
+                (
+                    FrameworkPattern::Decorator,
+                    text(callee, source),
+                    arguments,
+                    targets,
+                )
+            }
+            "call" | "call_expression" | "method_invocation" => {
+                // The call of `@endpoint("/x")` is matched as the decorator
+                if node
+                    .parent()
+                    .is_some_and(|parent| parent.kind() == "decorator")
+                {
+                    return;
+                }
+                let Some(callee) = call_callee(node, source) else {
+                    return;
+                };
+                let arguments = call_arguments(node);
+                let targets = arguments
+                    .map(|arguments| {
+                        let mut cursor = arguments.walk();
+                        arguments
+                            .named_children(&mut cursor)
+                            .filter(|argument| argument.kind() == "identifier")
+                            .map(|argument| Target::Named(text(argument, source)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (FrameworkPattern::Call, callee, arguments, targets)
+            }
+            _ => return,
+        };
+
+        for (index, (rule, glob)) in self.rules.iter().enumerate() {
+            if rule.pattern == pattern && rule.applies_to(language) && glob.matches(&callee) {
+                matches.push(Match {
+                    rule: index,
+                    site: node,
+                    path: arguments.and_then(|arguments| first_string(arguments, source)),
+                    targets: targets.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Parser running framework rules after a language's own parser
+pub struct FrameworkRulesParser {
+    inner: Arc<dyn LanguageParser>,
+    detector: Arc<FrameworkDetector>,
+}
+
+impl FrameworkRulesParser {
+    /// Wrap a language parser
+    pub fn new(inner: Arc<dyn LanguageParser>, detector: Arc<FrameworkDetector>) -> Self {
+        Self { inner, detector }
+    }
+}
+
+impl LanguageParser for FrameworkRulesParser {
+    fn language(&self) -> Language {
+        self.inner.language()
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let mut result = self.inner.parse(context)?;
+        self.detector
+            .apply(self.inner.language(), context, &mut result);
+        Ok(result)
+    }
+}
+
+fn text(node: TsNode<'_>, source: &[u8]) -> String {
+    node.utf8_text(source).unwrap_or_default().to_string()
+}
+
+fn span_of(node: TsNode<'_>) -> Span {
+    let start = node.start_position();
+    let end = node.end_position();
+    Span::new(
+        node.start_byte(),
+        node.end_byte(),
+        start.row + 1,
+        end.row + 1,
+        start.column + 1,
+        end.column + 1,
+    )
+}
+
+/// Callee of a call as written, e.g. `app.get` or `router.route`
+fn call_callee(call: TsNode<'_>, source: &[u8]) -> Option<String> {
+    if let Some(function) = call.child_by_field_name("function") {
+        return Some(text(function, source));
+    }
+    // Ruby and Java name the receiver and method separately
+    if let Some(method) = call
+        .child_by_field_name("method")
+        .or_else(|| call.child_by_field_name("name"))
+    {
+        return Some(
+            match call
+                .child_by_field_name("receiver")
+                .or_else(|| call.child_by_field_name("object"))
+            {
+                Some(receiver) => format!("{}.{}", text(receiver, source), text(method, source)),
+                None => text(method, source),
+            },
+        );
+    }
+    call.named_child(0).map(|callee| text(callee, source))
+}
+
+fn call_arguments(call: TsNode<'_>) -> Option<TsNode<'_>> {
+    if let Some(arguments) = call.child_by_field_name("arguments") {
+        return Some(arguments);
+    }
+    // Kotlin wraps the arguments in a call suffix
+    let mut cursor = call.walk();
+    let suffix = call
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "call_suffix")?;
+    let mut cursor = suffix.walk();
+    let arguments = suffix
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "value_arguments");
+    arguments
+}
+
+/// Value of the first string literal argument
+///
+/// Arguments wrapped in an argument node, as in Kotlin, are looked into.
+fn first_string(arguments: TsNode<'_>, source: &[u8]) -> Option<String> {
+    let mut cursor = arguments.walk();
+    for argument in arguments.named_children(&mut cursor) {
+        if let Some(value) = string_value(argument, source) {
+            return Some(value);
+        }
+        let mut inner = argument.walk();
+        let nested = argument
+            .named_children(&mut inner)
+            .find_map(|child| string_value(child, source));
+        if nested.is_some() {
+            return nested;
+        }
+    }
+    None
+}
+
+fn string_value(node: TsNode<'_>, source: &[u8]) -> Option<String> {
+    if !matches!(
+        node.kind(),
+        "string" | "string_literal" | "interpreted_string_literal" | "raw_string_literal"
+    ) {
+        return None;
+    }
+    let literal = text(node, source);
+    // Skip prefixes such as Python's `r` or `f`
+    let start = literal.find(['"', '\'', '`'])?;
+    if !literal[..start].chars().all(char::is_alphabetic) {
+        return None;
+    }
+    let quote = literal[start..].chars().next()?;
+    Some(literal[start..].trim_matches(quote).to_string())
+}
+
+/// Name node of the definition a decorator applies to
+///
+/// Python wraps decorated definitions in a `decorated_definition`; in
+/// TypeScript decorators are children of the class or method itself.
+fn decorated_name(decorator: TsNode<'_>) -> Option<TsNode<'_>> {
+    let parent = decorator.parent()?;
+    parent
+        .child_by_field_name("definition")
+        .unwrap_or(parent)
+        .child_by_field_name("name")
+}
+
+/// Id of the function, method or class node a match applies to
+fn find_target(nodes: &[Node], target: &Target<'_>, source: &[u8]) -> Option<NodeId> {
+    let (name, declared_at) = match target {
+        Target::Definition(name) => (text(*name, source), Some(*name)),
+        Target::Named(name) => (name.clone(), None),
+    };
+    nodes
+        .iter()
+        .filter(|node| {
+            matches!(
+                node.kind,
+                NodeKind::Function | NodeKind::Method | NodeKind::Class
+            ) && node.name == name
+        })
+        .filter(|node| {
+            declared_at.is_none_or(|at| {
+                node.span.start_byte <= at.start_byte() && at.end_byte() <= node.span.end_byte
+            })
+        })
+        .min_by_key(|node| (node.span.len(), node.span.start_byte))
+        .map(|node| node.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::language_registry;
+    use std::path::PathBuf;
+
+    fn parse(rules: &[FrameworkRule], file: &str, content: &str) -> ParseResult {
+        let registry = language_registry(rules);
+        let extension = file.rsplit('.').next().unwrap();
+        let parser = registry.get_by_extension(extension).unwrap();
+        let context =
+            ParseContext::new("repo".to_string(), PathBuf::from(file), content.to_string());
+        parser.parse(&context).unwrap()
+    }
+
+    fn routes(result: &ParseResult) -> Vec<&Node> {
+        result
+            .nodes
+            .iter()
+            .filter(|node| node.kind == NodeKind::Route)
+            .collect()
+    }
+
+    fn routes_to<'a>(result: &'a ParseResult, route: &Node) -> Vec<&'a str> {
+        result
+            .edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::RoutesTo && edge.source == route.id)
+            .filter_map(|edge| result.nodes.iter().find(|node| node.id == edge.target))
+            .map(|node| node.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_custom_decorator_rule_produces_route() {
+        #[derive(Deserialize)]
+        struct Settings {
+            framework_rules: Vec<FrameworkRule>,
+        }
+        let rules = toml::from_str::<Settings>(
+            r#"
+[[framework_rules]]
+name = "internal-web"
+pattern = "decorator"
+callee = "endpoint"
+role = "route"
+languages = ["python"]
+"#,
+        )
+        .unwrap()
+        .framework_rules;
+        let source = "@endpoint(\"/x\")\ndef handle(request):\n    return request\n\n\n@other(\"/y\")\ndef skip():\n    pass\n";
+
+        let result = parse(&rules, "views.py", source);
+
+        let found = routes(&result);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "/x");
+        assert_eq!(found[0].metadata["path"], "/x");
+        assert_eq!(found[0].metadata["framework"], "internal-web");
+        assert_eq!(found[0].span.start_line, 1);
+        assert_eq!(routes_to(&result, found[0]), vec!["handle"]);
+
+        // Rules only run for their languages
+        let go = parse(&rules, "main.go", "package main\n\nfunc main() {}\n");
+        assert!(routes(&go).is_empty());
+    }
+
+    #[test]
+    fn test_call_rules_route_to_and_mark_passed_functions() {
+        let rules = vec![
+            FrameworkRule::new(
+                "router",
+                FrameworkPattern::Call,
+                "*.add",
+                FrameworkRole::Route,
+            )
+            .with_method("get"),
+            FrameworkRule::new(
+                "jobs",
+                FrameworkPattern::Call,
+                "schedule",
+                FrameworkRole::Entrypoint,
+            ),
+        ];
+        let source = "function show(req) { return req; }\nfunction nightly() {}\nrouter.add('/items', show);\nschedule(nightly);\n";
+
+        let result = parse(&rules, "routes.js", source);
+
+        let found = routes(&result);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "GET /items");
+        assert_eq!(found[0].metadata["method"], "GET");
+        assert_eq!(routes_to(&result, found[0]), vec!["show"]);
+        let nightly = result
+            .nodes
+            .iter()
+            .find(|node| node.name == "nightly")
+            .unwrap();
+        assert_eq!(nightly.metadata["framework_role"], "entrypoint");
+        assert_eq!(nightly.metadata["framework"], "jobs");
+    }
+}
@@ -0,0 +1,427 @@
+//! Streaming repository indexing
+//!
+//! In streaming mode the graph store is populated one file at a time in the
+//! background, so graph tools can answer from the partial graph while indexing
+//! is still running. Their responses carry an `indexing_in_progress` flag and
+//! the completion fraction until the last file has been indexed.
+//!
+//! Bulk indexing runs report each finished batch through [`ProgressNotifier`],
+//! which forwards it as `notifications/progress` to a client that supplied a
+//! progress token. A run that stopped early at the memory limit leaves a
+//! warning that is attached to every tool response.
+
+use codeprism_core::{BatchProgress, ContentSearchManager, GraphStore, ParseContext, ParserEngine};
+use rmcp::model::{ProgressNotificationParam, ProgressToken};
+use rmcp::{Peer, RoleServer};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+
+/// Progress of a streaming index run shared between the indexer and the tools
+#[derive(Debug, Default)]
+pub struct IndexingStatus {
+    in_progress: AtomicBool,
+    graph_built: AtomicBool,
+    files_total: AtomicUsize,
+    files_indexed: AtomicUsize,
+    last_finished: Mutex<Option<chrono::DateTime<chrono::Utc>>>,
+    warning: Mutex<Option<String>>,
+}
+
+impl IndexingStatus {
+    /// Create a status with no indexing running
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the start of an indexing run over `files_total` files
+    pub fn begin(&self, files_total: usize) {
+        self.files_total.store(files_total, Ordering::SeqCst);
+        self.files_indexed.store(0, Ordering::SeqCst);
+        self.in_progress.store(true, Ordering::SeqCst);
+    }
+
+    /// Record that one more file has been indexed
+    pub fn file_indexed(&self) {
+        self.files_indexed.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Mark the indexing run as finished
+    ///
+    /// The first finished run means an initial graph has been built.
+    pub fn finish(&self) {
+        self.in_progress.store(false, Ordering::SeqCst);
+        self.graph_built.store(true, Ordering::SeqCst);
+        *self.last_finished.lock().unwrap_or_else(|e| e.into_inner()) = Some(chrono::Utc::now());
+    }
+
+    /// When the most recent indexing run finished
+    pub fn last_finished(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        *self.last_finished.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record or clear a problem with the last indexing run, such as a partial index
+    pub fn set_warning(&self, warning: Option<String>) {
+        *self.warning.lock().unwrap_or_else(|e| e.into_inner()) = warning;
+    }
+
+    /// Warning left by the last indexing run, if any
+    pub fn warning(&self) -> Option<String> {
+        self.warning
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Whether an indexing run has completed since the server started
+    pub fn is_graph_built(&self) -> bool {
+        self.graph_built.load(Ordering::SeqCst)
+    }
+
+    /// Whether an indexing run is currently in progress
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress.load(Ordering::SeqCst)
+    }
+
+    /// Number of files indexed so far in the current run
+    pub fn files_indexed(&self) -> usize {
+        self.files_indexed.load(Ordering::SeqCst)
+    }
+
+    /// Number of files the current run will index
+    pub fn files_total(&self) -> usize {
+        self.files_total.load(Ordering::SeqCst)
+    }
+
+    /// Fraction of files indexed, between 0.0 and 1.0
+    pub fn completion(&self) -> f64 {
+        if !self.is_in_progress() {
+            return 1.0;
+        }
+        let total = self.files_total();
+        if total == 0 {
+            return 0.0;
+        }
+        (self.files_indexed() as f64 / total as f64).min(1.0)
+    }
+
+    /// Add the in-progress flag and completion to a tool response object
+    ///
+    /// Once indexing has finished only an `indexing_warning` is added, and only
+    /// when the run left one.
+    pub fn annotate(&self, response: &mut serde_json::Value) {
+        let Some(object) = response.as_object_mut() else {
+            return;
+        };
+        if let Some(warning) = self.warning() {
+            object.insert(
+                "indexing_warning".to_string(),
+                serde_json::Value::String(warning),
+            );
+        }
+        if self.is_in_progress() {
+            object.insert(
+                "indexing_in_progress".to_string(),
+                serde_json::Value::Bool(true),
+            );
+            object.insert(
+                "indexing_progress".to_string(),
+                serde_json::json!({
+                    "completion": self.completion(),
+                    "files_indexed": self.files_indexed(),
+                    "files_total": self.files_total(),
+                }),
+            );
+        }
+    }
+}
+
+/// Sends bulk indexing progress to the client as MCP progress notifications
+///
+/// Nothing is sent until a client has attached with a progress token. Batches
+/// complete on indexing threads, so notifications are spawned onto the
+/// current Tokio runtime instead of being awaited.
+#[derive(Default)]
+pub struct ProgressNotifier {
+    target: Mutex<Option<(Peer<RoleServer>, ProgressToken)>>,
+}
+
+impl ProgressNotifier {
+    /// Create a notifier with no client attached
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send subsequent progress to `peer` under `token`
+    pub fn attach(&self, peer: Peer<RoleServer>, token: ProgressToken) {
+        *self.target.lock().unwrap() = Some((peer, token));
+    }
+
+    /// Whether a client will receive progress notifications
+    pub fn is_attached(&self) -> bool {
+        self.target.lock().unwrap().is_some()
+    }
+
+    /// Forward one batch update to the attached client, if any
+    pub fn notify(&self, progress: &BatchProgress) {
+        let Some((peer, token)) = self.target.lock().unwrap().clone() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No runtime available for progress notification");
+            return;
+        };
+
+        let param = progress_notification(token, progress);
+        runtime.spawn(async move {
+            if let Err(e) = peer.notify_progress(param).await {
+                debug!("Failed to send progress notification: {}", e);
+            }
+        });
+    }
+}
+
+/// Build the `notifications/progress` payload for a batch update
+pub fn progress_notification(
+    token: ProgressToken,
+    progress: &BatchProgress,
+) -> ProgressNotificationParam {
+    ProgressNotificationParam {
+        progress_token: token,
+        progress: progress.files_processed as u32,
+        total: Some(progress.total_files as u32),
+        message: Some(format!(
+            "Indexed {}/{} files in {:.1}s",
+            progress.files_processed,
+            progress.total_files,
+            progress.elapsed.as_secs_f64()
+        )),
+    }
+}
+
+/// Indexes files one at a time into a shared graph store
+pub struct StreamingIndexer {
+    repo_id: String,
+    parser_engine: ParserEngine,
+    graph_store: Arc<GraphStore>,
+    content_search: Arc<ContentSearchManager>,
+    status: Arc<IndexingStatus>,
+}
+
+impl StreamingIndexer {
+    /// Create a streaming indexer writing into `graph_store` and `content_search`
+    pub fn new(
+        repo_id: String,
+        parser_engine: ParserEngine,
+        graph_store: Arc<GraphStore>,
+        content_search: Arc<ContentSearchManager>,
+        status: Arc<IndexingStatus>,
+    ) -> Self {
+        Self {
+            repo_id,
+            parser_engine,
+            graph_store,
+            content_search,
+            status,
+        }
+    }
+
+    /// Parse a single file and add its nodes and edges to the graph store
+    ///
+    /// Files without a registered parser are only added to the content index.
+    /// The file counts as indexed either way so progress keeps converging.
+    pub fn index_file(&self, file_path: &Path) {
+        match std::fs::read_to_string(file_path) {
+            Ok(content) => {
+                if let Err(e) = self.content_search.index_file(file_path, &content) {
+                    debug!("Failed to index content for {}: {}", file_path.display(), e);
+                }
+
+                let context =
+                    ParseContext::new(self.repo_id.clone(), file_path.to_path_buf(), content);
+                match self.parser_engine.parse_file(context) {
+                    Ok(result) => {
+                        for node in result.nodes {
+                            self.graph_store.add_node(node);
+                        }
+                        for edge in result.edges {
+                            self.graph_store.add_edge(edge);
+                        }
+                    }
+                    Err(e) => debug!("Skipping graph update for {}: {}", file_path.display(), e),
+                }
+            }
+            Err(e) => debug!("Failed to read {}: {}", file_path.display(), e),
+        }
+
+        self.status.file_indexed();
+    }
+
+    /// Index all files in order and mark the run as finished
+    pub fn run(&self, files: &[PathBuf]) {
+        for file_path in files {
+            self.index_file(file_path);
+        }
+        self.status.finish();
+
+        info!(
+            "Streaming indexing completed: {} files, {} nodes in graph",
+            self.status.files_indexed(),
+            self.graph_store.get_stats().total_nodes
+        );
+    }
+}
+
+/// Outcome of [`index_repository_content`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentIndexCounts {
+    pub indexed_count: usize,
+    pub error_count: usize,
+}
+
+/// Read and index file contents with at most `concurrency` files in flight
+///
+/// The semaphore bounds open file handles as well as spawned tasks, so large
+/// repositories cannot exhaust file descriptors. Files that cannot be read as
+/// UTF-8 text (binaries, permission errors) and empty files are skipped
+/// without counting as errors.
+pub async fn index_repository_content(
+    content_search: Arc<ContentSearchManager>,
+    files: Vec<PathBuf>,
+    concurrency: usize,
+) -> ContentIndexCounts {
+    let permits = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
+
+    for file_path in files {
+        let Ok(permit) = Arc::clone(&permits).acquire_owned().await else {
+            break;
+        };
+        let content_search = Arc::clone(&content_search);
+        tasks.spawn(async move {
+            let _permit = permit;
+            let content = match tokio::fs::read_to_string(&file_path).await {
+                Ok(content) if !content.is_empty() => content,
+                Ok(_) => return None,
+                Err(e) => {
+                    debug!("Skipping content for {}: {}", file_path.display(), e);
+                    return None;
+                }
+            };
+            match content_search.index_file(&file_path, &content) {
+                Ok(()) => Some(true),
+                Err(e) => {
+                    warn!("Failed to index content for {}: {}", file_path.display(), e);
+                    Some(false)
+                }
+            }
+        });
+    }
+
+    let mut counts = ContentIndexCounts::default();
+    while let Some(outcome) = tasks.join_next().await {
+        match outcome {
+            Ok(Some(true)) => counts.indexed_count += 1,
+            Ok(Some(false)) => counts.error_count += 1,
+            Ok(None) => {}
+            Err(e) => {
+                warn!("Content indexing task failed: {}", e);
+                counts.error_count += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::NumberOrString;
+    use serde_json::json;
+
+    #[test]
+    fn test_progress_notification_reports_file_counts() {
+        let token = ProgressToken(NumberOrString::Number(7));
+        let param = progress_notification(
+            token.clone(),
+            &BatchProgress {
+                files_processed: 60,
+                total_files: 200,
+                batches_completed: 2,
+                elapsed: std::time::Duration::from_millis(1500),
+            },
+        );
+        assert_eq!(param.progress_token, token);
+        assert_eq!(param.progress, 60);
+        assert_eq!(param.total, Some(200));
+        assert_eq!(
+            param.message.as_deref(),
+            Some("Indexed 60/200 files in 1.5s")
+        );
+
+        // Without an attached client updates are dropped
+        let notifier = ProgressNotifier::new();
+        assert!(!notifier.is_attached());
+        notifier.notify(&BatchProgress {
+            files_processed: 1,
+            total_files: 1,
+            batches_completed: 1,
+            elapsed: std::time::Duration::ZERO,
+        });
+    }
+
+    #[test]
+    fn test_status_annotates_only_while_in_progress() {
+        let status = IndexingStatus::new();
+        let mut response = json!({ "status": "success" });
+        status.annotate(&mut response);
+        assert!(response.get("indexing_in_progress").is_none());
+
+        status.begin(4);
+        status.file_indexed();
+        let mut response = json!({ "status": "success" });
+        status.annotate(&mut response);
+        assert_eq!(response["indexing_in_progress"], true);
+        assert_eq!(response["indexing_progress"]["completion"], 0.25);
+        assert_eq!(response["indexing_progress"]["files_total"], 4);
+
+        status.finish();
+        assert_eq!(status.completion(), 1.0);
+
+        status.set_warning(Some("Indexing stopped near the memory limit".to_string()));
+        let mut response = json!({ "status": "success" });
+        status.annotate(&mut response);
+        assert!(response.get("indexing_in_progress").is_none());
+        assert_eq!(
+            response["indexing_warning"],
+            "Indexing stopped near the memory limit"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_content_indexing_skips_binary_and_empty_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = Vec::new();
+        for i in 0..8 {
+            let path = dir.path().join(format!("notes_{i}.md"));
+            std::fs::write(&path, format!("# Note {i}\nSome text")).unwrap();
+            files.push(path);
+        }
+        let binary = dir.path().join("image.bin");
+        std::fs::write(&binary, [0xff, 0xfe, 0x00, 0x81]).unwrap();
+        let empty = dir.path().join("empty.md");
+        std::fs::write(&empty, "").unwrap();
+        files.extend([binary, empty, dir.path().join("missing.md")]);
+
+        let content_search = Arc::new(ContentSearchManager::new());
+        let counts = index_repository_content(Arc::clone(&content_search), files, 3).await;
+        assert_eq!(
+            counts,
+            ContentIndexCounts {
+                indexed_count: 8,
+                error_count: 0,
+            }
+        );
+    }
+}
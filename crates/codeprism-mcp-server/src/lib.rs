@@ -7,9 +7,22 @@
 //! # Architecture
 //!
 //! The server is organized into several modules:
+//! - `audit`: JSONL audit log of tool calls
+//! - `cancellation`: Cooperative cancellation of timed-out tool calls
 //! - `server`: Core MCP server implementation
 //! - `tools`: MCP tool implementations (core, search, analysis, workflow)
 //! - `config`: Configuration management
+//! - `indexing`: Streaming repository indexing with partial results
+//! - `lsp`: Language Server Protocol bridge (requires the `lsp` feature)
+//! - `monitoring`: Per-request tracing spans and request-tagged metrics
+//! - `parsers`: Language parser adapters for the language registry
+//! - `readiness`: Liveness and readiness probes for orchestrators
+//! - `schema_export`: Tool/resource/prompt schema export for client codegen
+//! - `shutdown`: Graceful shutdown draining in-flight tool calls
+//! - `subgraph`: Reproducer subgraphs in the storage graph format
+//! - `throttle`: Concurrency caps on tool calls
+//! - `transport`: Stdio transport with JSON-RPC batch support
+//! - `workspace`: Multi-repository workspace files
 //! - `error`: Error types and handling
 //!
 //! # Usage
@@ -17,11 +30,27 @@
 //! The server can be run as a standalone binary or embedded in other applications.
 //! It supports stdio transport for communication with MCP clients.
 
+pub mod audit;
+pub mod cancellation;
 pub mod config;
 pub mod error;
+pub mod frameworks;
+pub mod indexing;
+#[cfg(feature = "lsp")]
+pub mod lsp;
+pub mod monitoring;
+pub mod parsers;
+pub mod readiness;
+pub mod resources;
 pub mod response;
+pub mod schema_export;
 pub mod server;
+pub mod shutdown;
+pub mod subgraph;
+pub mod throttle;
 pub mod tools;
+pub mod transport;
+pub mod workspace;
 
 #[cfg(test)]
 mod integration_test;
@@ -0,0 +1,514 @@
+//! Language Server Protocol bridge
+//!
+//! Serves `textDocument/definition`, `textDocument/references` and
+//! `workspace/symbol` from the same graph the MCP tools query, so editors can
+//! reuse the index without a second parser. Messages use the LSP base
+//! protocol (`Content-Length` framed JSON-RPC) over any reader/writer pair.
+//!
+//! LSP positions are zero-based while graph spans are one-based. Columns are
+//! translated one-to-one, which is exact for ASCII source; the UTF-16 offsets
+//! LSP uses can drift from tree-sitter's byte columns on lines with
+//! multi-byte characters.
+
+use codeprism_core::{GraphQuery, GraphStore, Node, NodeKind, Span};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::{debug, info};
+
+/// JSON-RPC error code for an unsupported method
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for malformed request parameters
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// Maximum number of results returned for `workspace/symbol`
+const WORKSPACE_SYMBOL_LIMIT: usize = 100;
+
+/// Answers LSP requests from a graph store
+pub struct LspBridge {
+    graph: Arc<GraphStore>,
+    query: GraphQuery,
+}
+
+impl LspBridge {
+    pub fn new(graph: Arc<GraphStore>) -> Self {
+        Self {
+            query: GraphQuery::new(Arc::clone(&graph)),
+            graph,
+        }
+    }
+
+    /// Handle one incoming message, returning the response for requests
+    pub fn handle(&self, message: &Value) -> Option<Value> {
+        let method = message.get("method")?.as_str()?;
+        // Notifications (`initialized`, `textDocument/didOpen`, ...) need no answer
+        let id = message.get("id")?.clone();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "definitionProvider": true,
+                    "referencesProvider": true,
+                    "workspaceSymbolProvider": true,
+                },
+                "serverInfo": {
+                    "name": crate::SERVER_NAME,
+                    "version": crate::VERSION,
+                }
+            })),
+            "shutdown" => Ok(Value::Null),
+            "textDocument/definition" => self.definition(&params),
+            "textDocument/references" => self.references(&params),
+            "workspace/symbol" => Ok(self.workspace_symbol(&params)),
+            _ => Err((METHOD_NOT_FOUND, format!("Unsupported method: {method}"))),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message }
+            }),
+        })
+    }
+
+    fn definition(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let definitions = match self.node_at(params)? {
+            Some(node) => self.definitions_of(&node),
+            None => Vec::new(),
+        };
+        Ok(Value::Array(
+            definitions
+                .iter()
+                .map(|node| location(&node.file, &node.span))
+                .collect(),
+        ))
+    }
+
+    fn references(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let include_declaration = params
+            .pointer("/context/includeDeclaration")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let Some(node) = self.node_at(params)? else {
+            return Ok(json!([]));
+        };
+
+        let mut seen = HashSet::new();
+        let mut locations = Vec::new();
+        let mut push = |file: &Path, span: &Span| {
+            if seen.insert((file.to_path_buf(), span.start_byte, span.end_byte)) {
+                locations.push(location(file, span));
+            }
+        };
+
+        for definition in self.definitions_of(&node) {
+            if include_declaration {
+                push(&definition.file, &definition.span);
+            }
+            for caller in self.query.find_callers(&definition.id).unwrap_or_default() {
+                for site in &caller.call_sites {
+                    push(&site.file, &site.span);
+                }
+            }
+            // Calls edges are covered by the call sites above; the rest are
+            // reads, writes, imports and inheritance
+            for reference in self
+                .query
+                .find_references(&definition.id)
+                .unwrap_or_default()
+            {
+                if reference.edge_kind != codeprism_core::EdgeKind::Calls {
+                    push(&reference.location.file, &reference.location.span);
+                }
+            }
+        }
+
+        Ok(Value::Array(locations))
+    }
+
+    fn workspace_symbol(&self, params: &Value) -> Value {
+        let query = params.get("query").and_then(Value::as_str).unwrap_or("");
+        let pattern = format!("(?i){}", regex::escape(query));
+        let kinds = vec![
+            NodeKind::Module,
+            NodeKind::Class,
+            NodeKind::Interface,
+            NodeKind::TypeAlias,
+            NodeKind::Enum,
+            NodeKind::Function,
+            NodeKind::Method,
+        ];
+        let symbols = self
+            .query
+            .search_symbols(&pattern, Some(kinds), Some(WORKSPACE_SYMBOL_LIMIT))
+            .unwrap_or_default();
+
+        Value::Array(
+            symbols
+                .iter()
+                .map(|symbol| {
+                    json!({
+                        "name": symbol.node.name,
+                        "kind": symbol_kind(&symbol.node.kind),
+                        "location": location(&symbol.node.file, &symbol.node.span),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Innermost node whose span contains the request's position
+    fn node_at(&self, params: &Value) -> Result<Option<Node>, (i64, String)> {
+        let invalid = || {
+            (
+                INVALID_PARAMS,
+                "Expected textDocument and position".to_string(),
+            )
+        };
+        let uri = params
+            .pointer("/textDocument/uri")
+            .and_then(Value::as_str)
+            .ok_or_else(invalid)?;
+        let line = params
+            .pointer("/position/line")
+            .and_then(Value::as_u64)
+            .ok_or_else(invalid)? as usize
+            + 1;
+        let column = params
+            .pointer("/position/character")
+            .and_then(Value::as_u64)
+            .ok_or_else(invalid)? as usize
+            + 1;
+        let path = path_from_uri(uri)
+            .ok_or_else(|| (INVALID_PARAMS, format!("Unsupported document URI: {uri}")))?;
+
+        let node = self
+            .graph
+            .get_nodes_in_file(&path)
+            .into_iter()
+            .filter(|node| span_contains(&node.span, line, column))
+            .min_by_key(|node| node.span.end_byte - node.span.start_byte);
+        Ok(node)
+    }
+
+    /// Definitions a node refers to; definition nodes resolve to themselves
+    fn definitions_of(&self, node: &Node) -> Vec<Node> {
+        if !matches!(node.kind, NodeKind::Call | NodeKind::Import) {
+            return vec![node.clone()];
+        }
+
+        let linked: Vec<Node> = self
+            .graph
+            .get_outgoing_edges(&node.id)
+            .iter()
+            .filter_map(|edge| self.graph.get_node(&edge.target))
+            .filter(is_definition)
+            .collect();
+        if !linked.is_empty() {
+            return linked;
+        }
+
+        // Unresolved call sites and imports are matched by their last name segment
+        let name = node
+            .name
+            .rsplit(['.', ':', '/'])
+            .next()
+            .unwrap_or(&node.name);
+        self.graph
+            .get_nodes_by_name(name)
+            .into_iter()
+            .filter(is_definition)
+            .collect()
+    }
+}
+
+fn is_definition(node: &Node) -> bool {
+    matches!(
+        node.kind,
+        NodeKind::Module
+            | NodeKind::Class
+            | NodeKind::Interface
+            | NodeKind::TypeAlias
+            | NodeKind::Enum
+            | NodeKind::Function
+            | NodeKind::Method
+    )
+}
+
+/// Whether one-based `line`/`column` falls within `span` (end exclusive)
+fn span_contains(span: &Span, line: usize, column: usize) -> bool {
+    (span.start_line, span.start_column) <= (line, column)
+        && (line, column) < (span.end_line, span.end_column)
+}
+
+/// LSP `SymbolKind` for a node kind
+fn symbol_kind(kind: &NodeKind) -> u32 {
+    match kind {
+        NodeKind::Module => 2,
+        NodeKind::Class => 5,
+        NodeKind::Method => 6,
+        NodeKind::Enum => 10,
+        NodeKind::Interface => 11,
+        NodeKind::Function => 12,
+        NodeKind::TypeAlias => 26,
+        _ => 13,
+    }
+}
+
+fn location(file: &Path, span: &Span) -> Value {
+    json!({
+        "uri": uri_from_path(file),
+        "range": {
+            "start": {
+                "line": span.start_line.saturating_sub(1),
+                "character": span.start_column.saturating_sub(1),
+            },
+            "end": {
+                "line": span.end_line.saturating_sub(1),
+                "character": span.end_column.saturating_sub(1),
+            },
+        }
+    })
+}
+
+/// Convert a `file://` URI to a path, decoding percent escapes
+pub fn path_from_uri(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+/// Convert a path to a `file://` URI, percent-encoding reserved characters
+pub fn uri_from_path(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+/// Serve LSP messages from `input` until the client sends `exit` or closes it
+pub async fn serve<R, W>(bridge: &LspBridge, input: R, mut output: W) -> std::io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut reader = BufReader::new(input);
+    while let Some(body) = read_message(&mut reader).await? {
+        let message: Value = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                debug!("Ignoring malformed LSP message: {}", e);
+                continue;
+            }
+        };
+        if message.get("method").and_then(Value::as_str) == Some("exit") {
+            break;
+        }
+        if let Some(response) = bridge.handle(&message) {
+            write_message(&mut output, &response).await?;
+        }
+    }
+    info!("LSP bridge stopped");
+    Ok(())
+}
+
+/// Serve LSP over stdin/stdout
+pub async fn serve_stdio(graph: Arc<GraphStore>) -> std::io::Result<()> {
+    let bridge = LspBridge::new(graph);
+    serve(&bridge, tokio::io::stdin(), tokio::io::stdout()).await
+}
+
+async fn read_message<R: AsyncRead + Unpin>(
+    reader: &mut BufReader<R>,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let Some(length) = content_length else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "LSP message without Content-Length header",
+        ));
+    };
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(body))
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &Value,
+) -> std::io::Result<()> {
+    let body = message.to_string();
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(body.as_bytes()).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::{Edge, EdgeKind, Language};
+
+    /// `def greet(): ...` on line 1 and a `greet()` call inside `main` on line 5
+    fn sample_graph(file: &Path) -> (Arc<GraphStore>, Node, Node) {
+        let graph = Arc::new(GraphStore::new());
+        let node = |kind, name: &str, span| {
+            Node::new(
+                "repo",
+                kind,
+                name.to_string(),
+                Language::Python,
+                file.to_path_buf(),
+                span,
+            )
+        };
+        let greet = node(NodeKind::Function, "greet", Span::new(0, 30, 1, 2, 1, 20));
+        let main = node(NodeKind::Function, "main", Span::new(40, 70, 4, 5, 1, 12));
+        let call = node(NodeKind::Call, "greet", Span::new(61, 68, 5, 5, 5, 12));
+        for n in [&greet, &main, &call] {
+            graph.add_node(n.clone());
+        }
+        graph.add_edge(Edge::new(main.id, call.id, EdgeKind::Calls));
+        (graph, greet, call)
+    }
+
+    #[tokio::test]
+    async fn test_definition_request_over_base_protocol() {
+        let file = PathBuf::from("/work/my app/hello.py");
+        let (graph, _, _) = sample_graph(&file);
+        let bridge = LspBridge::new(graph);
+
+        let (client, server) = tokio::io::duplex(8192);
+        let (server_in, server_out) = tokio::io::split(server);
+        let server_task = tokio::spawn(async move { serve(&bridge, server_in, server_out).await });
+
+        let (client_in, mut client_out) = tokio::io::split(client);
+        let uri = uri_from_path(&file);
+        assert_eq!(uri, "file:///work/my%20app/hello.py");
+        // Cursor on `greet` in `greet()`: line 5, column 6 in one-based terms
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/definition",
+            "params": {
+                "textDocument": { "uri": uri },
+                "position": { "line": 4, "character": 5 }
+            }
+        });
+        write_message(&mut client_out, &request).await.unwrap();
+        write_message(
+            &mut client_out,
+            &json!({"jsonrpc": "2.0", "method": "exit"}),
+        )
+        .await
+        .unwrap();
+
+        let mut reader = BufReader::new(client_in);
+        let response: Value =
+            serde_json::from_slice(&read_message(&mut reader).await.unwrap().unwrap()).unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(
+            response["result"],
+            json!([{
+                "uri": "file:///work/my%20app/hello.py",
+                "range": {
+                    "start": { "line": 0, "character": 0 },
+                    "end": { "line": 1, "character": 19 }
+                }
+            }])
+        );
+        server_task.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_references_and_workspace_symbols() {
+        let file = PathBuf::from("/work/hello.py");
+        let (graph, greet, call) = sample_graph(&file);
+        let bridge = LspBridge::new(graph);
+        let uri = uri_from_path(&file);
+
+        let response = bridge
+            .handle(&json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/references",
+                "params": {
+                    "textDocument": { "uri": uri },
+                    "position": { "line": 0, "character": 4 },
+                    "context": { "includeDeclaration": true }
+                }
+            }))
+            .unwrap();
+        let references = response["result"].as_array().unwrap();
+        assert_eq!(references.len(), 2, "Declaration plus one call site");
+        assert_eq!(
+            references[0]["range"]["start"]["line"],
+            greet.span.start_line - 1
+        );
+        assert_eq!(
+            references[1]["range"]["start"]["character"],
+            call.span.start_column - 1
+        );
+
+        let response = bridge
+            .handle(&json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "workspace/symbol",
+                "params": { "query": "GRE" }
+            }))
+            .unwrap();
+        let symbols = response["result"].as_array().unwrap();
+        assert_eq!(symbols.len(), 1, "Call sites are not symbols");
+        assert_eq!(symbols[0]["name"], "greet");
+        assert_eq!(symbols[0]["kind"], 12);
+
+        let unknown = bridge
+            .handle(&json!({"jsonrpc": "2.0", "id": 4, "method": "textDocument/hover"}))
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+        assert!(bridge
+            .handle(&json!({"jsonrpc": "2.0", "method": "initialized"}))
+            .is_none());
+    }
+}
@@ -36,6 +36,19 @@ struct Cli {
     /// Validate configuration and exit
     #[arg(long)]
     validate_config: bool,
+
+    /// Workspace file listing the repositories to index; reloaded when it changes
+    #[arg(short, long, value_name = "FILE")]
+    workspace: Option<std::path::PathBuf>,
+
+    /// Serve the index over the Language Server Protocol on stdio instead of MCP
+    #[cfg(feature = "lsp")]
+    #[arg(long)]
+    lsp: bool,
+
+    /// Write tool, resource, and prompt JSON schemas plus an OpenRPC document to DIR and exit
+    #[arg(long, value_name = "DIR")]
+    export_schemas: Option<std::path::PathBuf>,
 }
 
 #[tokio::main]
@@ -60,11 +73,48 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(output_dir) = cli.export_schemas.as_deref() {
+        let server = CodePrismMcpServer::new(config).await?;
+        let summary = codeprism_mcp_server::schema_export::export_schemas(&server, output_dir)?;
+        info!(
+            "Exported schemas for {} tools to {}",
+            summary.tools_exported,
+            output_dir.display()
+        );
+        return Ok(());
+    }
+
     // Check what mode to run in
+    #[cfg(feature = "lsp")]
+    if cli.lsp {
+        info!("Starting LSP bridge mode");
+        let server = CodePrismMcpServer::new(config).await?;
+        if let Some(workspace_file) = cli.workspace {
+            let workspace =
+                codeprism_mcp_server::workspace::WorkspaceConfig::from_file(&workspace_file)
+                    .await?;
+            server.apply_workspace(workspace)?;
+        }
+        codeprism_mcp_server::lsp::serve_stdio(server.graph_store()).await?;
+        return Ok(());
+    }
+
     if cli.mcp {
         // Run as MCP server
         info!("Starting MCP server mode");
         let server = CodePrismMcpServer::new(config).await?;
+        if let Some(workspace_file) = cli.workspace {
+            let workspace =
+                codeprism_mcp_server::workspace::WorkspaceConfig::from_file(&workspace_file)
+                    .await?;
+            let changes = server.apply_workspace(workspace)?;
+            info!(
+                "Loaded workspace {} with {} repositories",
+                workspace_file.display(),
+                changes.added.len()
+            );
+            let _workspace_watch = server.watch_workspace(workspace_file)?;
+        }
         server.run().await?;
     } else {
         // Show usage information when no mode is specified
@@ -0,0 +1,183 @@
+//! Request correlation for logs and metrics
+//!
+//! Every tool call runs inside a [`request_span`] whose `request_id` field is
+//! the JSON-RPC request id, so each log line emitted while the call runs can
+//! be traced back to the request that caused it. Messages without an id,
+//! such as notifications, get a generated UUID instead. The same id tags the
+//! metrics [`MonitoringMiddleware`] records for the call.
+
+use rmcp::model::{CallToolResult, RequestId};
+use rmcp::ErrorData as McpError;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, Instrument};
+
+/// Name of the span opened around each request
+pub const REQUEST_SPAN_NAME: &str = "mcp_request";
+
+/// How many finished requests [`MonitoringMiddleware`] keeps by default
+pub const DEFAULT_RECENT_REQUESTS: usize = 1000;
+
+/// Correlation id for a message: its JSON-RPC id, or a new UUID without one
+pub fn correlation_id(id: Option<&RequestId>) -> String {
+    match id {
+        Some(id) => id.to_string(),
+        None => uuid::Uuid::new_v4().to_string(),
+    }
+}
+
+/// Span tagging everything logged during a request with its `request_id`
+pub fn request_span(request_id: &str, method: &str) -> tracing::Span {
+    tracing::info_span!(REQUEST_SPAN_NAME, request_id = %request_id, method = %method)
+}
+
+/// Duration and outcome of one request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestMetrics {
+    pub request_id: String,
+    /// Tool name, or the MCP method for other requests
+    pub method: String,
+    pub duration: Duration,
+    pub success: bool,
+}
+
+/// Runs requests inside their span and records per-request metrics
+#[derive(Debug)]
+pub struct MonitoringMiddleware {
+    recent: Mutex<VecDeque<RequestMetrics>>,
+    capacity: usize,
+}
+
+impl MonitoringMiddleware {
+    /// Create a middleware keeping the last [`DEFAULT_RECENT_REQUESTS`] requests
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_RECENT_REQUESTS)
+    }
+
+    /// Create a middleware keeping the last `capacity` requests
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            recent: Mutex::new(VecDeque::with_capacity(
+                capacity.min(DEFAULT_RECENT_REQUESTS),
+            )),
+            capacity,
+        }
+    }
+
+    /// Run `call` inside the span for `request_id` and record how it went
+    ///
+    /// Tasks spawned by `call` only carry the span if they are spawned with
+    /// it, e.g. through [`tracing::Instrument::in_current_span`].
+    pub async fn observe<F>(
+        &self,
+        request_id: &str,
+        method: &str,
+        call: F,
+    ) -> Result<CallToolResult, McpError>
+    where
+        F: Future<Output = Result<CallToolResult, McpError>>,
+    {
+        let span = request_span(request_id, method);
+        let start = Instant::now();
+        let outcome = call.instrument(span.clone()).await;
+        let metrics = RequestMetrics {
+            request_id: request_id.to_string(),
+            method: method.to_string(),
+            duration: start.elapsed(),
+            success: matches!(&outcome, Ok(result) if result.is_error != Some(true)),
+        };
+        span.in_scope(|| {
+            debug!(
+                duration_ms = metrics.duration.as_millis() as u64,
+                success = metrics.success,
+                "Request finished"
+            )
+        });
+        self.record(metrics);
+        outcome
+    }
+
+    /// Metrics of a finished request, if it is still among the recent ones
+    pub fn request(&self, request_id: &str) -> Option<RequestMetrics> {
+        self.recent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .rev()
+            .find(|metrics| metrics.request_id == request_id)
+            .cloned()
+    }
+
+    /// Recently finished requests, oldest first
+    pub fn recent_requests(&self) -> Vec<RequestMetrics> {
+        self.recent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    fn record(&self, metrics: RequestMetrics) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        if recent.len() == self.capacity {
+            recent.pop_front();
+        }
+        recent.push_back(metrics);
+    }
+}
+
+impl Default for MonitoringMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlation_id_prefers_jsonrpc_id() {
+        assert_eq!(correlation_id(Some(&RequestId::Number(7))), "7");
+        assert_eq!(
+            correlation_id(Some(&RequestId::String("req-1".into()))),
+            "req-1"
+        );
+
+        let generated = correlation_id(None);
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+        assert_ne!(generated, correlation_id(None));
+    }
+
+    #[tokio::test]
+    async fn test_observe_keeps_recent_requests() {
+        let middleware = MonitoringMiddleware::with_capacity(2);
+        for (id, failed) in [("1", false), ("2", true), ("3", false)] {
+            middleware
+                .observe(id, "ping", async move {
+                    if failed {
+                        Ok(CallToolResult::error(vec![]))
+                    } else {
+                        Ok(CallToolResult::success(vec![]))
+                    }
+                })
+                .await
+                .unwrap();
+        }
+
+        let ids: Vec<_> = middleware
+            .recent_requests()
+            .into_iter()
+            .map(|metrics| metrics.request_id)
+            .collect();
+        assert_eq!(ids, vec!["2", "3"]);
+        assert!(!middleware.request("2").unwrap().success);
+        assert!(middleware.request("1").is_none());
+    }
+}
@@ -0,0 +1,1969 @@
+//! Language parser adapters registered with the server's language registry
+//!
+//! The language crates define their own AST types to stay independent of
+//! `codeprism-core`; the adapters here convert their output into core types.
+
+use crate::frameworks::{FrameworkDetector, FrameworkRule, FrameworkRulesParser};
+use codeprism_core::{
+    Edge, EdgeKind, EntryPointDetector, GraphStore, Language, LanguageParser, LanguageRegistry,
+    Node, NodeId, NodeKind, ParseContext, ParseResult, Span,
+};
+use codeprism_lang_cpp as cpp;
+use codeprism_lang_go as go;
+use codeprism_lang_java as java;
+use codeprism_lang_js as js;
+use codeprism_lang_kotlin as kotlin;
+use codeprism_lang_php as php;
+use codeprism_lang_python as python;
+use codeprism_lang_ruby as ruby;
+use codeprism_lang_rust as rust;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Adapter exposing the Go parser through [`LanguageParser`]
+pub struct GoParserAdapter {
+    parser: go::GoLanguageParser,
+}
+
+impl GoParserAdapter {
+    /// Create a new Go parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: go::create_parser(),
+        }
+    }
+
+    fn convert_id(id: go::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: go::NodeKind) -> NodeKind {
+        match kind {
+            go::NodeKind::Module | go::NodeKind::Package => NodeKind::Module,
+            go::NodeKind::Function => NodeKind::Function,
+            go::NodeKind::Method => NodeKind::Method,
+            go::NodeKind::Parameter => NodeKind::Parameter,
+            go::NodeKind::Variable => NodeKind::Variable,
+            go::NodeKind::Call => NodeKind::Call,
+            go::NodeKind::Import => NodeKind::Import,
+            go::NodeKind::Literal => NodeKind::Literal,
+            // Structs and interfaces are the closest Go equivalents of classes
+            go::NodeKind::Struct | go::NodeKind::Interface => NodeKind::Class,
+            go::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: go::Node) -> codeprism_core::Result<Node> {
+        let mut metadata = node.metadata;
+        if let Some(object) = metadata.as_object_mut() {
+            object.insert(
+                "go_kind".to_string(),
+                serde_json::to_value(node.kind).unwrap_or_default(),
+            );
+        }
+
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang: Language::Go,
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata,
+        })
+    }
+
+    /// Containment has no core equivalent and is dropped
+    fn convert_edge(edge: go::Edge, file: &Path) -> codeprism_core::Result<Option<Edge>> {
+        let kind = match edge.kind {
+            go::EdgeKind::Calls => EdgeKind::Calls,
+            go::EdgeKind::Reads => EdgeKind::Reads,
+            go::EdgeKind::Writes => EdgeKind::Writes,
+            go::EdgeKind::Imports => EdgeKind::Imports,
+            go::EdgeKind::Implements => EdgeKind::Implements,
+            go::EdgeKind::Contains => return Ok(None),
+        };
+
+        Ok(Some(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        )))
+    }
+}
+
+impl Default for GoParserAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageParser for GoParserAdapter {
+    fn language(&self) -> Language {
+        Language::Go
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = go::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| Self::convert_edge(edge, &context.file_path).transpose())
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Adapter exposing the JavaScript/TypeScript parser through [`LanguageParser`]
+///
+/// The registry keys parsers by language, so one adapter is registered per
+/// language; the underlying parser picks the grammar from the file extension.
+pub struct JavaScriptParserAdapter {
+    parser: js::JavaScriptLanguageParser,
+    language: Language,
+}
+
+impl JavaScriptParserAdapter {
+    /// Adapter for `.js`, `.mjs` and `.cjs` files
+    pub fn javascript() -> Self {
+        Self {
+            parser: js::create_parser(),
+            language: Language::JavaScript,
+        }
+    }
+
+    /// Adapter for `.ts` and `.tsx` files
+    pub fn typescript() -> Self {
+        Self {
+            parser: js::create_parser(),
+            language: Language::TypeScript,
+        }
+    }
+
+    fn convert_id(id: js::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: js::NodeKind) -> NodeKind {
+        match kind {
+            js::NodeKind::Module => NodeKind::Module,
+            js::NodeKind::Class => NodeKind::Class,
+            js::NodeKind::Interface => NodeKind::Interface,
+            js::NodeKind::TypeAlias => NodeKind::TypeAlias,
+            js::NodeKind::Enum => NodeKind::Enum,
+            js::NodeKind::Function => NodeKind::Function,
+            js::NodeKind::Method => NodeKind::Method,
+            js::NodeKind::Parameter => NodeKind::Parameter,
+            js::NodeKind::Variable => NodeKind::Variable,
+            js::NodeKind::Call => NodeKind::Call,
+            js::NodeKind::Import => NodeKind::Import,
+            js::NodeKind::Literal => NodeKind::Literal,
+            js::NodeKind::Route => NodeKind::Route,
+            js::NodeKind::SqlQuery => NodeKind::SqlQuery,
+            js::NodeKind::Event => NodeKind::Event,
+            js::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: js::Node) -> codeprism_core::Result<Node> {
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang: match node.lang {
+                js::Language::JavaScript => Language::JavaScript,
+                js::Language::TypeScript => Language::TypeScript,
+            },
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata: node.metadata,
+        })
+    }
+
+    fn convert_edge(edge: js::Edge, file: &Path) -> codeprism_core::Result<Edge> {
+        let kind = match edge.kind {
+            js::EdgeKind::Calls => EdgeKind::Calls,
+            js::EdgeKind::Reads => EdgeKind::Reads,
+            js::EdgeKind::Writes => EdgeKind::Writes,
+            js::EdgeKind::Imports => EdgeKind::Imports,
+            js::EdgeKind::Emits => EdgeKind::Emits,
+            js::EdgeKind::RoutesTo => EdgeKind::RoutesTo,
+            js::EdgeKind::Raises => EdgeKind::Raises,
+            js::EdgeKind::Extends => EdgeKind::Extends,
+            js::EdgeKind::Implements => EdgeKind::Implements,
+        };
+
+        Ok(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        ))
+    }
+}
+
+impl LanguageParser for JavaScriptParserAdapter {
+    fn language(&self) -> Language {
+        self.language
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = js::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .map(|edge| Self::convert_edge(edge, &context.file_path))
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Adapter exposing the Python parser through [`LanguageParser`]
+pub struct PythonParserAdapter {
+    parser: python::PythonLanguageParser,
+}
+
+impl PythonParserAdapter {
+    /// Create a new Python parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: python::create_parser(),
+        }
+    }
+
+    fn convert_id(id: python::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: python::NodeKind) -> NodeKind {
+        match kind {
+            python::NodeKind::Module => NodeKind::Module,
+            python::NodeKind::Class => NodeKind::Class,
+            python::NodeKind::Function => NodeKind::Function,
+            python::NodeKind::Method => NodeKind::Method,
+            python::NodeKind::Parameter => NodeKind::Parameter,
+            python::NodeKind::Variable => NodeKind::Variable,
+            python::NodeKind::Call => NodeKind::Call,
+            python::NodeKind::Import => NodeKind::Import,
+            python::NodeKind::Literal => NodeKind::Literal,
+            python::NodeKind::Route => NodeKind::Route,
+            python::NodeKind::SqlQuery => NodeKind::SqlQuery,
+            python::NodeKind::Event => NodeKind::Event,
+            python::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: python::Node) -> codeprism_core::Result<Node> {
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang: Language::Python,
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata: node.metadata,
+        })
+    }
+
+    fn convert_edge(edge: python::Edge, file: &Path) -> codeprism_core::Result<Edge> {
+        let kind = match edge.kind {
+            python::EdgeKind::Calls => EdgeKind::Calls,
+            python::EdgeKind::Reads => EdgeKind::Reads,
+            python::EdgeKind::Writes => EdgeKind::Writes,
+            python::EdgeKind::Imports => EdgeKind::Imports,
+            python::EdgeKind::Emits => EdgeKind::Emits,
+            python::EdgeKind::RoutesTo => EdgeKind::RoutesTo,
+            python::EdgeKind::Raises => EdgeKind::Raises,
+            python::EdgeKind::Extends => EdgeKind::Extends,
+            python::EdgeKind::Implements => EdgeKind::Implements,
+        };
+
+        Ok(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        ))
+    }
+}
+
+impl Default for PythonParserAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageParser for PythonParserAdapter {
+    fn language(&self) -> Language {
+        Language::Python
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = python::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .map(|edge| Self::convert_edge(edge, &context.file_path))
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Adapter exposing the Ruby parser through [`LanguageParser`]
+pub struct RubyParserAdapter {
+    parser: ruby::RubyLanguageParser,
+}
+
+impl RubyParserAdapter {
+    /// Create a new Ruby parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: ruby::create_parser(),
+        }
+    }
+
+    fn convert_id(id: ruby::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: ruby::NodeKind) -> NodeKind {
+        match kind {
+            ruby::NodeKind::Module | ruby::NodeKind::RubyModule => NodeKind::Module,
+            ruby::NodeKind::Class => NodeKind::Class,
+            ruby::NodeKind::Method => NodeKind::Method,
+            ruby::NodeKind::Parameter => NodeKind::Parameter,
+            // Attribute accessors expose instance variables
+            ruby::NodeKind::Variable | ruby::NodeKind::Attribute => NodeKind::Variable,
+            ruby::NodeKind::Call => NodeKind::Call,
+            ruby::NodeKind::Import => NodeKind::Import,
+            ruby::NodeKind::Literal => NodeKind::Literal,
+            ruby::NodeKind::Route => NodeKind::Route,
+            ruby::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: ruby::Node) -> codeprism_core::Result<Node> {
+        let mut metadata = node.metadata;
+        if let Some(object) = metadata.as_object_mut() {
+            object.insert(
+                "ruby_kind".to_string(),
+                serde_json::to_value(node.kind).unwrap_or_default(),
+            );
+        }
+
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang: Language::Ruby,
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata,
+        })
+    }
+
+    /// Containment has no core equivalent and is dropped
+    fn convert_edge(edge: ruby::Edge, file: &Path) -> codeprism_core::Result<Option<Edge>> {
+        let kind = match edge.kind {
+            ruby::EdgeKind::Calls => EdgeKind::Calls,
+            ruby::EdgeKind::Reads => EdgeKind::Reads,
+            ruby::EdgeKind::Writes => EdgeKind::Writes,
+            ruby::EdgeKind::Imports => EdgeKind::Imports,
+            ruby::EdgeKind::Extends => EdgeKind::Extends,
+            // Mixins are the closest Ruby equivalent of implementing an interface
+            ruby::EdgeKind::Includes => EdgeKind::Implements,
+            ruby::EdgeKind::Contains => return Ok(None),
+        };
+
+        Ok(Some(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        )))
+    }
+}
+
+impl Default for RubyParserAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageParser for RubyParserAdapter {
+    fn language(&self) -> Language {
+        Language::Ruby
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = ruby::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| Self::convert_edge(edge, &context.file_path).transpose())
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Adapter exposing the Kotlin parser through [`LanguageParser`]
+pub struct KotlinParserAdapter {
+    parser: kotlin::KotlinLanguageParser,
+}
+
+impl KotlinParserAdapter {
+    /// Create a new Kotlin parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: kotlin::create_parser(),
+        }
+    }
+
+    fn convert_id(id: kotlin::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: kotlin::NodeKind) -> NodeKind {
+        match kind {
+            kotlin::NodeKind::Module | kotlin::NodeKind::Package => NodeKind::Module,
+            // Objects are singleton classes
+            kotlin::NodeKind::Class | kotlin::NodeKind::DataClass | kotlin::NodeKind::Object => {
+                NodeKind::Class
+            }
+            kotlin::NodeKind::Interface => NodeKind::Interface,
+            kotlin::NodeKind::EnumClass => NodeKind::Enum,
+            kotlin::NodeKind::Function => NodeKind::Function,
+            kotlin::NodeKind::Method => NodeKind::Method,
+            kotlin::NodeKind::Call => NodeKind::Call,
+            kotlin::NodeKind::Import => NodeKind::Import,
+            kotlin::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: kotlin::Node) -> codeprism_core::Result<Node> {
+        let mut metadata = node.metadata;
+        if let Some(object) = metadata.as_object_mut() {
+            object.insert(
+                "kotlin_kind".to_string(),
+                serde_json::to_value(node.kind).unwrap_or_default(),
+            );
+        }
+
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang: Language::Kotlin,
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata,
+        })
+    }
+
+    /// Containment has no core equivalent and is dropped
+    fn convert_edge(edge: kotlin::Edge, file: &Path) -> codeprism_core::Result<Option<Edge>> {
+        let kind = match edge.kind {
+            kotlin::EdgeKind::Calls => EdgeKind::Calls,
+            kotlin::EdgeKind::Imports => EdgeKind::Imports,
+            kotlin::EdgeKind::Extends => EdgeKind::Extends,
+            kotlin::EdgeKind::Implements => EdgeKind::Implements,
+            // An extension depends on its receiver without inheriting from it
+            kotlin::EdgeKind::ExtensionOf => EdgeKind::Reads,
+            kotlin::EdgeKind::Contains => return Ok(None),
+        };
+
+        Ok(Some(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        )))
+    }
+}
+
+impl Default for KotlinParserAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageParser for KotlinParserAdapter {
+    fn language(&self) -> Language {
+        Language::Kotlin
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = kotlin::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| Self::convert_edge(edge, &context.file_path).transpose())
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Adapter exposing the PHP parser through [`LanguageParser`]
+pub struct PhpParserAdapter {
+    parser: php::PhpLanguageParser,
+}
+
+impl PhpParserAdapter {
+    /// Create a new PHP parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: php::create_parser(),
+        }
+    }
+
+    fn convert_id(id: php::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: php::NodeKind) -> NodeKind {
+        match kind {
+            php::NodeKind::Module | php::NodeKind::Namespace => NodeKind::Module,
+            // Traits are mixed into classes and hold methods like one
+            php::NodeKind::Class | php::NodeKind::Trait => NodeKind::Class,
+            php::NodeKind::Interface => NodeKind::Interface,
+            php::NodeKind::Enum => NodeKind::Enum,
+            php::NodeKind::Function => NodeKind::Function,
+            php::NodeKind::Method => NodeKind::Method,
+            php::NodeKind::Call => NodeKind::Call,
+            php::NodeKind::Import => NodeKind::Import,
+            php::NodeKind::Route => NodeKind::Route,
+            php::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: php::Node) -> codeprism_core::Result<Node> {
+        let mut metadata = node.metadata;
+        if let Some(object) = metadata.as_object_mut() {
+            object.insert(
+                "php_kind".to_string(),
+                serde_json::to_value(node.kind).unwrap_or_default(),
+            );
+        }
+
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang: Language::Php,
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata,
+        })
+    }
+
+    /// Containment has no core equivalent and is dropped
+    fn convert_edge(edge: php::Edge, file: &Path) -> codeprism_core::Result<Option<Edge>> {
+        let kind = match edge.kind {
+            php::EdgeKind::Calls => EdgeKind::Calls,
+            php::EdgeKind::Imports => EdgeKind::Imports,
+            php::EdgeKind::Extends => EdgeKind::Extends,
+            php::EdgeKind::Implements => EdgeKind::Implements,
+            php::EdgeKind::RoutesTo => EdgeKind::RoutesTo,
+            // A class using a trait takes on its methods like an implementation
+            php::EdgeKind::UsesTrait => EdgeKind::Implements,
+            php::EdgeKind::Contains => return Ok(None),
+        };
+
+        Ok(Some(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        )))
+    }
+}
+
+impl Default for PhpParserAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageParser for PhpParserAdapter {
+    fn language(&self) -> Language {
+        Language::Php
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = php::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| Self::convert_edge(edge, &context.file_path).transpose())
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Adapter exposing the C/C++ parser through [`LanguageParser`]
+pub struct CppParserAdapter {
+    parser: cpp::CppLanguageParser,
+    language: Language,
+}
+
+impl CppParserAdapter {
+    /// Adapter for `.c` and `.h` files
+    pub fn c() -> Self {
+        Self {
+            parser: cpp::create_parser(),
+            language: Language::C,
+        }
+    }
+
+    /// Adapter for `.cpp`, `.cc`, `.cxx`, `.hpp` and `.hxx` files
+    pub fn cpp() -> Self {
+        Self {
+            parser: cpp::create_parser(),
+            language: Language::Cpp,
+        }
+    }
+
+    fn convert_id(id: cpp::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: cpp::NodeKind) -> NodeKind {
+        match kind {
+            cpp::NodeKind::Module | cpp::NodeKind::Namespace => NodeKind::Module,
+            // Structs and unions hold fields and member functions like a class
+            cpp::NodeKind::Class | cpp::NodeKind::Struct => NodeKind::Class,
+            cpp::NodeKind::Enum => NodeKind::Enum,
+            cpp::NodeKind::Function => NodeKind::Function,
+            cpp::NodeKind::Method => NodeKind::Method,
+            cpp::NodeKind::Call => NodeKind::Call,
+            cpp::NodeKind::Import => NodeKind::Import,
+            cpp::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: cpp::Node) -> codeprism_core::Result<Node> {
+        let lang = match node.lang {
+            cpp::Language::C => Language::C,
+            cpp::Language::Cpp => Language::Cpp,
+        };
+        let mut metadata = node.metadata;
+        if let Some(object) = metadata.as_object_mut() {
+            object.insert(
+                "cpp_kind".to_string(),
+                serde_json::to_value(node.kind).unwrap_or_default(),
+            );
+        }
+
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang,
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata,
+        })
+    }
+
+    /// Containment has no core equivalent and is dropped
+    fn convert_edge(edge: cpp::Edge, file: &Path) -> codeprism_core::Result<Option<Edge>> {
+        let kind = match edge.kind {
+            cpp::EdgeKind::Calls => EdgeKind::Calls,
+            cpp::EdgeKind::Imports => EdgeKind::Imports,
+            cpp::EdgeKind::Extends => EdgeKind::Extends,
+            cpp::EdgeKind::Contains => return Ok(None),
+        };
+
+        Ok(Some(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        )))
+    }
+}
+
+impl LanguageParser for CppParserAdapter {
+    fn language(&self) -> Language {
+        self.language
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = cpp::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| Self::convert_edge(edge, &context.file_path).transpose())
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Adapter exposing the Rust parser through [`LanguageParser`]
+pub struct RustParserAdapter {
+    parser: rust::RustLanguageParser,
+}
+
+impl RustParserAdapter {
+    /// Create a new Rust parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: rust::create_parser(),
+        }
+    }
+
+    fn convert_id(id: rust::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: rust::NodeKind) -> NodeKind {
+        match kind {
+            rust::NodeKind::Module | rust::NodeKind::Mod => NodeKind::Module,
+            // Structs and unions carry fields and `impl` methods like a class
+            rust::NodeKind::Class | rust::NodeKind::Struct | rust::NodeKind::Union => {
+                NodeKind::Class
+            }
+            rust::NodeKind::Trait => NodeKind::Interface,
+            rust::NodeKind::Enum => NodeKind::Enum,
+            rust::NodeKind::TypeAlias | rust::NodeKind::AssociatedType => NodeKind::TypeAlias,
+            rust::NodeKind::Function => NodeKind::Function,
+            rust::NodeKind::Method => NodeKind::Method,
+            rust::NodeKind::Parameter => NodeKind::Parameter,
+            rust::NodeKind::Variable
+            | rust::NodeKind::Const
+            | rust::NodeKind::Static
+            | rust::NodeKind::AssociatedConst
+            | rust::NodeKind::Field => NodeKind::Variable,
+            rust::NodeKind::Call | rust::NodeKind::Macro => NodeKind::Call,
+            rust::NodeKind::Import | rust::NodeKind::Use => NodeKind::Import,
+            rust::NodeKind::Literal => NodeKind::Literal,
+            rust::NodeKind::Route => NodeKind::Route,
+            rust::NodeKind::SqlQuery => NodeKind::SqlQuery,
+            rust::NodeKind::Event => NodeKind::Event,
+            rust::NodeKind::Impl
+            | rust::NodeKind::Lifetime
+            | rust::NodeKind::Pub
+            | rust::NodeKind::Variant
+            | rust::NodeKind::Attribute
+            | rust::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: rust::Node) -> codeprism_core::Result<Node> {
+        let mut metadata = node.metadata;
+        if let Some(object) = metadata.as_object_mut() {
+            object.insert(
+                "rust_kind".to_string(),
+                serde_json::to_value(node.kind).unwrap_or_default(),
+            );
+        }
+
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang: Language::Rust,
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata,
+        })
+    }
+
+    /// Containment, ownership and lifetime relations have no core equivalent
+    /// and are dropped
+    fn convert_edge(edge: rust::Edge, file: &Path) -> codeprism_core::Result<Option<Edge>> {
+        let kind = match edge.kind {
+            rust::EdgeKind::Calls | rust::EdgeKind::Expands => EdgeKind::Calls,
+            rust::EdgeKind::Reads => EdgeKind::Reads,
+            rust::EdgeKind::Writes => EdgeKind::Writes,
+            rust::EdgeKind::Imports | rust::EdgeKind::Uses => EdgeKind::Imports,
+            rust::EdgeKind::Emits => EdgeKind::Emits,
+            rust::EdgeKind::RoutesTo => EdgeKind::RoutesTo,
+            rust::EdgeKind::Raises => EdgeKind::Raises,
+            rust::EdgeKind::Extends => EdgeKind::Extends,
+            // A derive generates an implementation of the derived trait
+            rust::EdgeKind::Implements
+            | rust::EdgeKind::ImplementsTrait
+            | rust::EdgeKind::Derives => EdgeKind::Implements,
+            rust::EdgeKind::Constrains
+            | rust::EdgeKind::Owns
+            | rust::EdgeKind::Borrows
+            | rust::EdgeKind::BorrowsMut
+            | rust::EdgeKind::Outlives
+            | rust::EdgeKind::Bounds
+            | rust::EdgeKind::Binds
+            | rust::EdgeKind::Contains => return Ok(None),
+        };
+
+        Ok(Some(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        )))
+    }
+}
+
+impl Default for RustParserAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageParser for RustParserAdapter {
+    fn language(&self) -> Language {
+        Language::Rust
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = rust::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| Self::convert_edge(edge, &context.file_path).transpose())
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Adapter exposing the Java parser through [`LanguageParser`]
+pub struct JavaParserAdapter {
+    parser: java::JavaLanguageParser,
+}
+
+impl JavaParserAdapter {
+    /// Create a new Java parser adapter
+    pub fn new() -> Self {
+        Self {
+            parser: java::create_parser(),
+        }
+    }
+
+    fn convert_id(id: java::NodeId, file: &Path) -> codeprism_core::Result<NodeId> {
+        NodeId::from_hex(&id.to_hex())
+            .map_err(|e| codeprism_core::Error::parse(file, format!("Invalid node id: {e}")))
+    }
+
+    fn convert_kind(kind: java::NodeKind) -> NodeKind {
+        match kind {
+            java::NodeKind::Module | java::NodeKind::Package => NodeKind::Module,
+            java::NodeKind::Class => NodeKind::Class,
+            // Annotation types are declared with `@interface`
+            java::NodeKind::Interface | java::NodeKind::Annotation => NodeKind::Interface,
+            java::NodeKind::Enum => NodeKind::Enum,
+            java::NodeKind::Function => NodeKind::Function,
+            java::NodeKind::Method | java::NodeKind::Constructor => NodeKind::Method,
+            java::NodeKind::Parameter => NodeKind::Parameter,
+            java::NodeKind::Variable | java::NodeKind::Field => NodeKind::Variable,
+            java::NodeKind::Call => NodeKind::Call,
+            java::NodeKind::Import => NodeKind::Import,
+            java::NodeKind::Literal => NodeKind::Literal,
+            java::NodeKind::Route => NodeKind::Route,
+            java::NodeKind::SqlQuery => NodeKind::SqlQuery,
+            java::NodeKind::Event => NodeKind::Event,
+            java::NodeKind::StaticBlock
+            | java::NodeKind::InstanceBlock
+            | java::NodeKind::TryBlock
+            | java::NodeKind::CatchClause
+            | java::NodeKind::FinallyClause
+            | java::NodeKind::ThrowStatement
+            | java::NodeKind::Lambda
+            | java::NodeKind::MethodReference
+            | java::NodeKind::TypeParameter
+            | java::NodeKind::WildcardType
+            | java::NodeKind::ArrayCreation
+            | java::NodeKind::SynchronizedBlock
+            | java::NodeKind::AssertStatement
+            | java::NodeKind::Unknown => NodeKind::Unknown,
+        }
+    }
+
+    fn convert_node(node: java::Node) -> codeprism_core::Result<Node> {
+        let mut metadata = node.metadata;
+        if let Some(object) = metadata.as_object_mut() {
+            object.insert(
+                "java_kind".to_string(),
+                serde_json::to_value(node.kind).unwrap_or_default(),
+            );
+        }
+
+        Ok(Node {
+            id: Self::convert_id(node.id, &node.file)?,
+            kind: Self::convert_kind(node.kind),
+            name: node.name,
+            lang: Language::Java,
+            file: node.file,
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.signature,
+            metadata,
+        })
+    }
+
+    /// Containment and relations without a core equivalent are dropped
+    fn convert_edge(edge: java::Edge, file: &Path) -> codeprism_core::Result<Option<Edge>> {
+        let kind = match edge.kind {
+            java::EdgeKind::Calls | java::EdgeKind::Instantiates => EdgeKind::Calls,
+            java::EdgeKind::Reads | java::EdgeKind::Accesses | java::EdgeKind::StaticAccess => {
+                EdgeKind::Reads
+            }
+            java::EdgeKind::Writes => EdgeKind::Writes,
+            java::EdgeKind::Imports | java::EdgeKind::ImportsPackage => EdgeKind::Imports,
+            java::EdgeKind::Emits => EdgeKind::Emits,
+            java::EdgeKind::RoutesTo => EdgeKind::RoutesTo,
+            java::EdgeKind::Raises | java::EdgeKind::Throws => EdgeKind::Raises,
+            java::EdgeKind::Extends => EdgeKind::Extends,
+            java::EdgeKind::Implements | java::EdgeKind::ImplementsInterface => {
+                EdgeKind::Implements
+            }
+            java::EdgeKind::Annotates
+            | java::EdgeKind::TypeParameterBinds
+            | java::EdgeKind::Catches
+            | java::EdgeKind::Overrides
+            | java::EdgeKind::Casts
+            | java::EdgeKind::Synchronizes
+            | java::EdgeKind::Captures
+            | java::EdgeKind::Contains => return Ok(None),
+        };
+
+        Ok(Some(Edge::new(
+            Self::convert_id(edge.source, file)?,
+            Self::convert_id(edge.target, file)?,
+            kind,
+        )))
+    }
+}
+
+impl Default for JavaParserAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageParser for JavaParserAdapter {
+    fn language(&self) -> Language {
+        Language::Java
+    }
+
+    fn parse(&self, context: &ParseContext) -> codeprism_core::Result<ParseResult> {
+        let (tree, nodes, edges) = java::parse_file(
+            &self.parser,
+            &context.repo_id,
+            context.file_path.clone(),
+            context.content.clone(),
+            context.old_tree.clone(),
+        )
+        .map_err(|e| codeprism_core::Error::parse(&context.file_path, e.to_string()))?;
+
+        let nodes = nodes
+            .into_iter()
+            .map(Self::convert_node)
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| Self::convert_edge(edge, &context.file_path).transpose())
+            .collect::<codeprism_core::Result<Vec<_>>>()?;
+
+        Ok(ParseResult { tree, nodes, edges })
+    }
+}
+
+/// Python entry points: the functions called from `if __name__ == "__main__":`
+/// blocks
+pub struct PythonEntryPointDetector {
+    analyzer: python::PythonAnalyzer,
+}
+
+impl PythonEntryPointDetector {
+    /// Create a new Python entry point detector
+    pub fn new() -> Self {
+        Self {
+            analyzer: python::PythonAnalyzer::new(),
+        }
+    }
+}
+
+impl Default for PythonEntryPointDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntryPointDetector for PythonEntryPointDetector {
+    fn language(&self) -> Language {
+        Language::Python
+    }
+
+    fn detect(&self, graph: &GraphStore) -> Vec<NodeId> {
+        let mut roots = Vec::new();
+        for module in graph.get_nodes_by_kind(NodeKind::Module) {
+            if module.lang != Language::Python {
+                continue;
+            }
+            // Only modules with a guard are worth parsing a second time
+            let Ok(source) = std::fs::read_to_string(&module.file) else {
+                continue;
+            };
+            if !source.contains("__main__") {
+                continue;
+            }
+            let Ok(calls) = self.analyzer.main_guard_calls(&source, &module.file) else {
+                continue;
+            };
+            let callable = |node: &Node| {
+                node.lang == Language::Python
+                    && matches!(node.kind, NodeKind::Function | NodeKind::Class)
+            };
+            for name in calls {
+                let candidates: Vec<_> = graph
+                    .get_nodes_by_name(&name)
+                    .into_iter()
+                    .filter(callable)
+                    .collect();
+                // Prefer the module's own definition over imported namesakes
+                let local: Vec<_> = candidates
+                    .iter()
+                    .filter(|node| node.file == module.file)
+                    .map(|node| node.id)
+                    .collect();
+                if local.is_empty() {
+                    roots.extend(candidates.iter().map(|node| node.id));
+                } else {
+                    roots.extend(local);
+                }
+            }
+        }
+        roots
+    }
+}
+
+/// JavaScript/TypeScript entry points: exported members and their definitions
+pub struct JavaScriptEntryPointDetector {
+    language: Language,
+}
+
+impl JavaScriptEntryPointDetector {
+    /// Detector for JavaScript files
+    pub fn javascript() -> Self {
+        Self {
+            language: Language::JavaScript,
+        }
+    }
+
+    /// Detector for TypeScript files
+    pub fn typescript() -> Self {
+        Self {
+            language: Language::TypeScript,
+        }
+    }
+}
+
+impl EntryPointDetector for JavaScriptEntryPointDetector {
+    fn language(&self) -> Language {
+        self.language
+    }
+
+    fn detect(&self, graph: &GraphStore) -> Vec<NodeId> {
+        let mut roots = Vec::new();
+        for file in graph.get_all_files() {
+            let nodes = graph.get_nodes_in_file(&file);
+            let is_export = |node: &Node| {
+                node.metadata["is_export"] == true || node.metadata["exported"] == true
+            };
+            for export in nodes
+                .iter()
+                .filter(|node| node.lang == self.language && is_export(node))
+            {
+                roots.push(export.id);
+                // Export nodes stand beside the declaration they name
+                roots.extend(
+                    nodes
+                        .iter()
+                        .filter(|node| {
+                            node.name == export.name
+                                && !is_export(node)
+                                && matches!(
+                                    node.kind,
+                                    NodeKind::Function
+                                        | NodeKind::Class
+                                        | NodeKind::Variable
+                                        | NodeKind::Interface
+                                        | NodeKind::Enum
+                                )
+                        })
+                        .map(|node| node.id),
+                );
+            }
+        }
+        roots
+    }
+}
+
+/// Entry points flagged by a language crate while parsing, with
+/// `"entry_point": true` in the node metadata
+///
+/// Each crate decides what execution starts from in its language: `main`
+/// functions, exported or `pub` items, or script files with top-level code.
+pub struct FlaggedEntryPointDetector {
+    language: Language,
+}
+
+impl FlaggedEntryPointDetector {
+    /// Detector for the entry points flagged in `language` files
+    pub fn new(language: Language) -> Self {
+        Self { language }
+    }
+}
+
+impl EntryPointDetector for FlaggedEntryPointDetector {
+    fn language(&self) -> Language {
+        self.language
+    }
+
+    fn detect(&self, graph: &GraphStore) -> Vec<NodeId> {
+        [NodeKind::Module, NodeKind::Function, NodeKind::Method]
+            .into_iter()
+            .flat_map(|kind| graph.get_nodes_by_kind(kind))
+            .filter(|node| node.lang == self.language && node.metadata["entry_point"] == true)
+            .map(|node| node.id)
+            .collect()
+    }
+}
+
+/// Build the language registry with all available parser adapters
+pub fn default_language_registry() -> LanguageRegistry {
+    language_registry(&[])
+}
+
+/// Build the language registry, running framework rules after each parser
+/// they apply to
+pub fn language_registry(framework_rules: &[FrameworkRule]) -> LanguageRegistry {
+    let parsers: [Arc<dyn LanguageParser>; 11] = [
+        Arc::new(GoParserAdapter::new()),
+        Arc::new(JavaScriptParserAdapter::javascript()),
+        Arc::new(JavaScriptParserAdapter::typescript()),
+        Arc::new(RubyParserAdapter::new()),
+        Arc::new(PythonParserAdapter::new()),
+        Arc::new(KotlinParserAdapter::new()),
+        Arc::new(PhpParserAdapter::new()),
+        Arc::new(CppParserAdapter::c()),
+        Arc::new(CppParserAdapter::cpp()),
+        Arc::new(RustParserAdapter::new()),
+        Arc::new(JavaParserAdapter::new()),
+    ];
+    let detector = Arc::new(FrameworkDetector::new(framework_rules));
+
+    let registry = LanguageRegistry::new();
+    for parser in parsers {
+        if detector.is_empty_for(parser.language()) {
+            registry.register(parser);
+        } else {
+            registry.register(Arc::new(FrameworkRulesParser::new(
+                parser,
+                Arc::clone(&detector),
+            )));
+        }
+    }
+
+    let detectors: [Arc<dyn EntryPointDetector>; 3] = [
+        Arc::new(PythonEntryPointDetector::new()),
+        Arc::new(JavaScriptEntryPointDetector::javascript()),
+        Arc::new(JavaScriptEntryPointDetector::typescript()),
+    ];
+    for detector in detectors {
+        registry.register_entry_point_detector(detector);
+    }
+    for language in [
+        Language::Go,
+        Language::Ruby,
+        Language::Kotlin,
+        Language::Php,
+        Language::C,
+        Language::Cpp,
+        Language::Rust,
+        Language::Java,
+    ] {
+        registry.register_entry_point_detector(Arc::new(FlaggedEntryPointDetector::new(language)));
+    }
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_registry_resolves_go_files() {
+        let registry = default_language_registry();
+        let parser = registry
+            .get_by_extension("go")
+            .expect("Go parser should be registered");
+        assert_eq!(parser.language(), Language::Go);
+    }
+
+    #[test]
+    fn test_go_adapter_converts_nodes_and_edges() {
+        let adapter = GoParserAdapter::new();
+        let context = ParseContext::new(
+            "repo".to_string(),
+            PathBuf::from("shapes.go"),
+            r#"
+package shapes
+
+type Shape interface {
+    Area() float64
+}
+
+type Square struct{ Side float64 }
+
+func (s Square) Area() float64 { return s.Side * s.Side }
+
+func run(s Square) { go s.Area() }
+"#
+            .to_string(),
+        );
+
+        let result = adapter.parse(&context).unwrap();
+
+        let square = result.nodes.iter().find(|n| n.name == "Square").unwrap();
+        let shape = result.nodes.iter().find(|n| n.name == "Shape").unwrap();
+        assert_eq!(square.kind, NodeKind::Class);
+        assert_eq!(square.lang, Language::Go);
+        assert_eq!(square.metadata["go_kind"], "struct");
+        assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Implements
+            && e.source == square.id
+            && e.target == shape.id));
+        assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Calls));
+    }
+
+    #[test]
+    fn test_typescript_adapter_maps_declarations() {
+        let registry = default_language_registry();
+        let parser = registry
+            .get_by_extension("ts")
+            .expect("TypeScript parser should be registered");
+        let context = ParseContext::new(
+            "repo".to_string(),
+            PathBuf::from("shapes.ts"),
+            r#"
+interface Shape { area(): number; }
+type Id = string;
+enum Color { Red }
+class Square implements Shape { area() { return 1; } }
+"#
+            .to_string(),
+        );
+
+        let result = parser.parse(&context).unwrap();
+
+        let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+        assert_eq!(find("Shape").kind, NodeKind::Interface);
+        assert_eq!(find("Id").kind, NodeKind::TypeAlias);
+        assert_eq!(find("Color").kind, NodeKind::Enum);
+        assert_eq!(find("Square").lang, Language::TypeScript);
+        assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Implements
+            && e.source == find("Square").id
+            && e.target == find("Shape").id));
+        assert!(registry.get_by_extension("js").is_some());
+    }
+
+    #[test]
+    fn test_ruby_adapter_maps_routes_and_mixins() {
+        let registry = default_language_registry();
+        let parser = registry
+            .get_by_extension("rb")
+            .expect("Ruby parser should be registered");
+        let context = ParseContext::new(
+            "repo".to_string(),
+            PathBuf::from("app.rb"),
+            r#"
+module Auditable; end
+
+class Invoice
+  include Auditable
+  attr_reader :total
+end
+
+Rails.application.routes.draw do
+  get "/invoices", to: "invoices#index"
+end
+"#
+            .to_string(),
+        );
+
+        let result = parser.parse(&context).unwrap();
+
+        let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+        assert_eq!(find("Auditable").kind, NodeKind::Module);
+        assert_eq!(find("Auditable").metadata["ruby_kind"], "ruby_module");
+        assert_eq!(find("total").kind, NodeKind::Variable);
+        assert_eq!(find("GET /invoices").kind, NodeKind::Route);
+        assert_eq!(find("Invoice").lang, Language::Ruby);
+        assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Implements
+            && e.source == find("Invoice").id
+            && e.target == find("Auditable").id));
+    }
+
+    #[test]
+    fn test_python_adapter_maps_classes_and_calls() {
+        let registry = default_language_registry();
+        let parser = registry
+            .get_by_extension("py")
+            .expect("Python parser should be registered");
+        let context = ParseContext::new(
+            "repo".to_string(),
+            PathBuf::from("shapes.py"),
+            r#"
+class Shape:
+    pass
+
+class Square(Shape):
+    def area(self):
+        return compute(self)
+
+def compute(shape):
+    return 1
+"#
+            .to_string(),
+        );
+
+        let result = parser.parse(&context).unwrap();
+
+        let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+        assert_eq!(find("Square").kind, NodeKind::Class);
+        assert_eq!(find("area").kind, NodeKind::Method);
+        assert_eq!(find("compute").lang, Language::Python);
+        // Base classes are recorded as nodes at the reference site
+        assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Extends
+            && e.source == find("Square").id
+            && result
+                .nodes
+                .iter()
+                .any(|n| n.id == e.target && n.name == "Shape")));
+        assert!(result.edges.iter().any(|e| e.kind == EdgeKind::Calls));
+    }
+
+    #[test]
+    fn test_kotlin_adapter_maps_kinds_and_extension_edges() {
+        let registry = default_language_registry();
+        for ext in ["kt", "kts"] {
+            assert!(registry.get_by_extension(ext).is_some());
+        }
+        let parser = registry.get_by_extension("kt").unwrap();
+        let context = ParseContext::new(
+            "repo".to_string(),
+            PathBuf::from("Shapes.kt"),
+            r#"
+data class Circle(val radius: Double)
+
+object Registry
+
+enum class Color { RED }
+
+fun Circle.diameter() = radius * 2
+"#
+            .to_string(),
+        );
+
+        let result = parser.parse(&context).unwrap();
+
+        let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+        let circle = find("Circle");
+        assert_eq!(circle.kind, NodeKind::Class);
+        assert_eq!(circle.lang, Language::Kotlin);
+        assert_eq!(circle.metadata["kotlin_kind"], "data_class");
+        assert_eq!(find("Registry").kind, NodeKind::Class);
+        assert_eq!(find("Color").kind, NodeKind::Enum);
+        assert!(result.edges.iter().any(|e| e.source == find("diameter").id
+            && e.target == circle.id
+            && e.kind == EdgeKind::Reads));
+    }
+
+    #[test]
+    fn test_php_adapter_maps_traits_and_routes() {
+        let registry = default_language_registry();
+        let parser = registry.get_by_extension("php").unwrap();
+        let context = ParseContext::new(
+            "repo".to_string(),
+            PathBuf::from("routes.php"),
+            r#"<?php
+trait Audited {}
+
+class PostController
+{
+    use Audited;
+
+    public function show() {}
+}
+
+Route::get('/posts/{id}', [PostController::class, 'show']);
+"#
+            .to_string(),
+        );
+
+        let result = parser.parse(&context).unwrap();
+
+        let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+        let controller = find("PostController");
+        assert_eq!(controller.lang, Language::Php);
+        assert_eq!(find("Audited").kind, NodeKind::Class);
+        assert_eq!(find("Audited").metadata["php_kind"], "trait");
+        let route = find("GET /posts/{id}");
+        assert_eq!(route.kind, NodeKind::Route);
+        let has_edge = |source: NodeId, target: NodeId, kind| {
+            result
+                .edges
+                .iter()
+                .any(|e| e.source == source && e.target == target && e.kind == kind)
+        };
+        assert!(has_edge(
+            controller.id,
+            find("Audited").id,
+            EdgeKind::Implements
+        ));
+        assert!(has_edge(route.id, find("show").id, EdgeKind::RoutesTo));
+    }
+
+    #[test]
+    fn test_cpp_adapters_split_c_and_cpp_files() {
+        let registry = default_language_registry();
+        let parse = |file: &str, content: &str| {
+            let extension = file.rsplit('.').next().unwrap();
+            let parser = registry.get_by_extension(extension).unwrap();
+            parser
+                .parse(&ParseContext::new(
+                    "repo".to_string(),
+                    PathBuf::from(file),
+                    content.to_string(),
+                ))
+                .unwrap()
+        };
+
+        let result = parse(
+            "shape.cc",
+            r#"#include "shape.h"
+namespace geo {
+struct Shape { virtual double area() const; };
+class Circle : public Shape {
+    double area() const { return scale(1.0); }
+};
+}
+#define scale(x) ((x) * 2)
+"#,
+        );
+        let find = |name: &str| result.nodes.iter().find(|n| n.name == name).unwrap();
+        let circle = find("Circle");
+        assert_eq!(circle.lang, Language::Cpp);
+        assert_eq!(circle.kind, NodeKind::Class);
+        assert_eq!(find("Shape").metadata["cpp_kind"], "struct");
+        assert_eq!(find("geo").kind, NodeKind::Module);
+        assert!(result
+            .nodes
+            .iter()
+            .any(|n| n.name == "scale" && n.kind == NodeKind::Unknown));
+        assert_eq!(find("shape.h").kind, NodeKind::Import);
+        assert!(result.edges.iter().any(|e| e.source == circle.id
+            && e.target == find("Shape").id
+            && e.kind == EdgeKind::Extends));
+
+        let result = parse("main.c", "int main(void) { return 0; }\n");
+        let main = result
+            .nodes
+            .iter()
+            .find(|n| n.kind == NodeKind::Function)
+            .unwrap();
+        assert_eq!(main.name, "main");
+        assert_eq!(main.lang, Language::C);
+    }
+
+    #[test]
+    fn test_deeply_nested_expression_is_truncated_not_fatal() {
+        let registry = default_language_registry();
+        let parser = registry.get_by_extension("js").unwrap();
+        let depth = 20_000;
+        let content = format!(
+            "const nested = {}1{};\nfunction after() {{ return nested; }}\n",
+            "[".repeat(depth),
+            "]".repeat(depth)
+        );
+        let context = ParseContext::new("repo".to_string(), PathBuf::from("generated.js"), content);
+
+        let result = parser.parse(&context).unwrap();
+
+        let module = result
+            .nodes
+            .iter()
+            .find(|n| n.kind == NodeKind::Module)
+            .unwrap();
+        let truncation = &module.metadata["depth_truncation"];
+        assert_eq!(truncation["max_depth"], js::DEFAULT_MAX_DEPTH);
+        assert!(truncation["skipped_subtrees"].as_u64().unwrap() >= 1);
+        // Code outside the nested expression is still indexed
+        assert!(result
+            .nodes
+            .iter()
+            .any(|n| n.kind == NodeKind::Function && n.name == "after"));
+    }
+
+    fn graph_of(results: impl IntoIterator<Item = ParseResult>) -> GraphStore {
+        let graph = GraphStore::new();
+        for result in results {
+            result
+                .nodes
+                .into_iter()
+                .for_each(|node| graph.add_node(node));
+            result
+                .edges
+                .into_iter()
+                .for_each(|edge| graph.add_edge(edge));
+        }
+        graph
+    }
+
+    fn detected_names(detector: &dyn EntryPointDetector, graph: &GraphStore) -> Vec<String> {
+        let mut names: Vec<_> = detector
+            .detect(graph)
+            .iter()
+            .filter_map(|id| graph.get_node(id))
+            .map(|node| format!("{:?} {}", node.kind, node.name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Entry points detected in a single parsed file, as `"Kind name"`
+    fn detected_in(file: &str, source: &str) -> Vec<String> {
+        let registry = default_language_registry();
+        let extension = Path::new(file).extension().unwrap().to_str().unwrap();
+        let parser = registry.get_by_extension(extension).unwrap();
+        let result = parser
+            .parse(&ParseContext::new(
+                "repo".to_string(),
+                PathBuf::from(file),
+                source.to_string(),
+            ))
+            .unwrap();
+        let graph = graph_of([result]);
+        let detector = registry.entry_point_detector(parser.language()).unwrap();
+        detected_names(&*detector, &graph)
+    }
+
+    #[test]
+    fn test_go_entry_points_are_main_init_and_exported_functions() {
+        let source = r#"
+package main
+
+type Server struct{}
+
+func main() { helper() }
+func init() {}
+func Exported() {}
+func helper() {}
+func (s *Server) Run() {}
+func (s *Server) stop() {}
+"#;
+        assert_eq!(
+            detected_in("main.go", source),
+            vec![
+                "Function Exported",
+                "Function init",
+                "Function main",
+                "Method Run"
+            ]
+        );
+        // `main` only starts programs in package main
+        assert!(detected_in("lib.go", "package lib\n\nfunc main() {}\n").is_empty());
+    }
+
+    #[test]
+    fn test_rust_entry_points_are_main_and_pub_functions() {
+        let source = r#"
+fn main() { run(); }
+
+pub fn run() { helper(); }
+
+fn helper() {}
+
+pub(crate) fn internal() {}
+
+pub struct Config;
+
+impl Config {
+    pub fn load() -> Self { Config }
+    fn validate(&self) {}
+}
+"#;
+        assert_eq!(
+            detected_in("main.rs", source),
+            vec!["Function main", "Function run", "Method load"]
+        );
+    }
+
+    #[test]
+    fn test_java_entry_points_are_public_static_void_main() {
+        let source = r#"
+public class App {
+    public static void main(String[] args) { new App().start(); }
+    public void start() {}
+}
+
+class Other {
+    public void main(String[] args) {}
+    static void main() {}
+}
+"#;
+        assert_eq!(detected_in("App.java", source), vec!["Method main"]);
+    }
+
+    #[test]
+    fn test_kotlin_entry_points_are_top_level_main() {
+        let source = r#"
+fun main() { App().main() }
+fun helper() {}
+
+class App {
+    fun main() {}
+}
+"#;
+        assert_eq!(detected_in("App.kt", source), vec!["Function main"]);
+    }
+
+    #[test]
+    fn test_c_and_cpp_entry_points_are_main() {
+        let c = "static int helper(void) { return 0; }\nint main(void) { return helper(); }\n";
+        assert_eq!(detected_in("main.c", c), vec!["Function main"]);
+
+        let cpp = r#"
+class App {
+public:
+    int run() { return 0; }
+};
+
+int main() { return App().run(); }
+"#;
+        assert_eq!(detected_in("main.cpp", cpp), vec!["Function main"]);
+    }
+
+    #[test]
+    fn test_ruby_entry_points_are_scripts() {
+        let script = r#"
+require "optparse"
+
+def run
+end
+
+run if __FILE__ == $PROGRAM_NAME
+"#;
+        assert_eq!(detected_in("tool.rb", script), vec!["Module tool"]);
+
+        let library = "require \"json\"\n\nclass Parser\n  def parse; end\nend\n";
+        assert!(detected_in("parser.rb", library).is_empty());
+    }
+
+    #[test]
+    fn test_php_entry_points_are_scripts() {
+        let script = r#"<?php
+require __DIR__ . '/vendor/autoload.php';
+
+$app = new App();
+$app->run();
+"#;
+        assert_eq!(detected_in("index.php", script), vec!["Module index"]);
+
+        let library = r#"<?php
+namespace App\Models;
+
+use App\Support\Model;
+
+class User extends Model {}
+"#;
+        assert!(detected_in("User.php", library).is_empty());
+    }
+
+    #[test]
+    fn test_python_entry_points_follow_main_guard() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let registry = default_language_registry();
+        let parser = registry.get_by_extension("py").unwrap();
+        let files = [
+            (
+                "tool.py",
+                r#"
+from helpers import configure
+
+def main():
+    configure()
+
+def unused():
+    pass
+
+if __name__ == "__main__":
+    configure()
+    main()
+"#,
+            ),
+            (
+                "helpers.py",
+                "def configure():\n    pass\n\ndef main():\n    pass\n",
+            ),
+        ];
+        let results = files.iter().map(|(name, source)| {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, source).unwrap();
+            parser
+                .parse(&ParseContext::new(
+                    "repo".to_string(),
+                    path,
+                    source.to_string(),
+                ))
+                .unwrap()
+        });
+        let graph = graph_of(results.collect::<Vec<_>>());
+
+        let detector = registry.entry_point_detector(Language::Python).unwrap();
+        let roots = detector.detect(&graph);
+        let found: Vec<_> = roots
+            .iter()
+            .filter_map(|id| graph.get_node(id))
+            .map(|node| (node.name, node.file.file_name().unwrap().to_owned()))
+            .collect();
+        // `configure` is imported; `main` resolves to the module's own definition
+        assert_eq!(
+            found,
+            vec![
+                ("configure".to_string(), "helpers.py".into()),
+                ("main".to_string(), "tool.py".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_javascript_entry_points_are_exported_members() {
+        let registry = default_language_registry();
+        let parser = registry.get_by_extension("js").unwrap();
+        let source = r#"
+export function publicApi() { return helper(); }
+function helper() { return 1; }
+function unused() {}
+export const VERSION = "1.0";
+"#;
+        let result = parser
+            .parse(&ParseContext::new(
+                "repo".to_string(),
+                PathBuf::from("index.js"),
+                source.to_string(),
+            ))
+            .unwrap();
+        let graph = graph_of([result]);
+
+        let detector = registry.entry_point_detector(Language::JavaScript).unwrap();
+        let names = detected_names(&*detector, &graph);
+        assert!(
+            names.contains(&"Function publicApi".to_string()),
+            "{names:?}"
+        );
+        assert!(names.iter().any(|name| name.ends_with(" VERSION")));
+        assert!(!names
+            .iter()
+            .any(|name| name.ends_with(" helper") || name.ends_with(" unused")));
+        // Nothing is exported from TypeScript files here
+        let typescript = registry.entry_point_detector(Language::TypeScript).unwrap();
+        assert!(typescript.detect(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_registered_parsers_confirm_commented_out_code() {
+        let analyzer = codeprism_analysis::CommentedCodeAnalyzer::new()
+            .with_language_registry(Arc::new(default_language_registry()));
+        let flagged = |language: &str, code: &str| {
+            analyzer
+                .analyze_content(code, language, None, 2)
+                .unwrap()
+                .len()
+        };
+
+        // Pseudo-code passes the line heuristic but not the Rust parser
+        let steps = "// Steps:\n// load config;\n// validate every input;\n// return result;\n";
+        assert_eq!(flagged("rust", steps), 0);
+
+        // Statements only valid inside a function body still parse
+        let body = "// let total = items.len();\n// if total > 0 {\n//     return total;\n// }\n";
+        assert_eq!(flagged("rust", body), 1);
+
+        let kotlin = "// val total = items.size\n// println(total);\n// return total;\n";
+        assert_eq!(flagged("kotlin", kotlin), 1);
+        assert_eq!(flagged("kotlin", steps), 0);
+
+        let php = "// $total = count($items);\n// echo $total;\n// return $total;\n";
+        assert_eq!(flagged("php", php), 1);
+        assert_eq!(flagged("php", steps), 0);
+    }
+}
@@ -0,0 +1,428 @@
+//! Liveness and readiness probes for orchestrators
+//!
+//! Kubernetes, systemd and similar supervisors should not route requests to
+//! the server before its graph is built. [`Readiness`] combines the indexing
+//! status with the [`HealthMonitor`] component checks; it is exposed to MCP
+//! clients as an experimental `readiness` capability in the `initialize`
+//! result and, when `health_addr` is configured, over HTTP as `/healthz`
+//! (liveness) and `/readyz` (readiness). The full component report behind
+//! the `health_check` tool is served there as `/health`.
+
+use crate::indexing::IndexingStatus;
+use codeprism_core::{
+    ComponentHealth, HealthCheckResult, HealthMonitor, ObservabilityHealthStatus,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tracing::{debug, info};
+
+/// Readiness of the server at one point in time
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    /// Whether the server should receive traffic
+    pub ready: bool,
+    /// Component health, including an `indexing` check
+    pub health: HealthCheckResult,
+}
+
+/// Decides whether the server is ready to serve requests
+///
+/// With a warm-up repository configured, the server becomes ready once the
+/// first indexing run completes and stays ready through later re-indexing.
+/// Without one, it is ready whenever no indexing run is in progress. An
+/// unhealthy component makes the server unready either way.
+pub struct Readiness {
+    indexing: Arc<IndexingStatus>,
+    health: Arc<HealthMonitor>,
+    requires_index: bool,
+}
+
+impl Readiness {
+    pub fn new(
+        indexing: Arc<IndexingStatus>,
+        health: Arc<HealthMonitor>,
+        requires_index: bool,
+    ) -> Self {
+        Self {
+            indexing,
+            health,
+            requires_index,
+        }
+    }
+
+    /// Component health without the indexing check, for liveness probes
+    pub fn health(&self) -> HealthCheckResult {
+        self.health.health_check()
+    }
+
+    /// Current readiness with the indexing check added to the health report
+    pub fn report(&self) -> ReadinessReport {
+        let indexed = if self.requires_index {
+            self.indexing.is_graph_built()
+        } else {
+            !self.indexing.is_in_progress()
+        };
+
+        let mut health = self.health.health_check();
+        let mut metrics = HashMap::new();
+        metrics.insert(
+            "in_progress".to_string(),
+            self.indexing.is_in_progress().into(),
+        );
+        metrics.insert("completion".to_string(), self.indexing.completion().into());
+        metrics.insert(
+            "files_indexed".to_string(),
+            self.indexing.files_indexed().into(),
+        );
+        metrics.insert(
+            "files_total".to_string(),
+            self.indexing.files_total().into(),
+        );
+        metrics.insert(
+            "last_indexed_at".to_string(),
+            self.indexing
+                .last_finished()
+                .map(|time| time.to_rfc3339())
+                .into(),
+        );
+        health.checks.insert(
+            "indexing".to_string(),
+            ComponentHealth {
+                status: if indexed {
+                    ObservabilityHealthStatus::Healthy
+                } else {
+                    ObservabilityHealthStatus::Degraded
+                },
+                message: if indexed {
+                    "Code graph is built".to_string()
+                } else {
+                    "Initial code graph is still being built".to_string()
+                },
+                metrics: Some(metrics),
+            },
+        );
+
+        ReadinessReport {
+            ready: indexed && health.status != ObservabilityHealthStatus::Unhealthy,
+            health,
+        }
+    }
+
+    /// Readiness as an MCP capability object
+    pub fn capability(&self) -> serde_json::Map<String, serde_json::Value> {
+        let report = self.report();
+        let mut capability = serde_json::Map::new();
+        capability.insert("ready".to_string(), report.ready.into());
+        capability.insert(
+            "indexing_complete".to_string(),
+            (report.health.checks["indexing"].status == ObservabilityHealthStatus::Healthy).into(),
+        );
+        capability
+    }
+}
+
+/// Produces the full component health report served as `/health`
+pub type HealthSource =
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = HealthCheckResult> + Send>> + Send + Sync>;
+
+fn severity(status: &ObservabilityHealthStatus) -> u8 {
+    match status {
+        ObservabilityHealthStatus::Healthy => 0,
+        ObservabilityHealthStatus::Degraded => 1,
+        ObservabilityHealthStatus::Unhealthy => 2,
+    }
+}
+
+/// Add a component check to a health report, worsening its overall status to match
+pub fn add_component(report: &mut HealthCheckResult, name: &str, component: ComponentHealth) {
+    if severity(&component.status) > severity(&report.status) {
+        report.status = component.status.clone();
+        report.overall_message = match report.status {
+            ObservabilityHealthStatus::Healthy => "All systems operational",
+            ObservabilityHealthStatus::Degraded => "Some systems experiencing issues",
+            ObservabilityHealthStatus::Unhealthy => "Critical systems failing",
+        }
+        .to_string();
+    }
+    report.checks.insert(name.to_string(), component);
+}
+
+/// A health report as JSON, naming the components that are not healthy
+///
+/// `degraded` components still serve requests with reduced quality, while
+/// `unhealthy` ones are failing; the overall `status` is the worst of them.
+pub fn health_payload(report: &HealthCheckResult) -> serde_json::Value {
+    let failing = |status: ObservabilityHealthStatus| {
+        let mut names: Vec<&str> = report
+            .checks
+            .iter()
+            .filter(|(_, check)| check.status == status)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        names.sort_unstable();
+        names
+    };
+    let components: std::collections::BTreeMap<_, _> = report.checks.iter().collect();
+
+    serde_json::json!({
+        "status": report.status,
+        "healthy": report.status == ObservabilityHealthStatus::Healthy,
+        "live": report.status != ObservabilityHealthStatus::Unhealthy,
+        "message": report.overall_message,
+        "timestamp": report.timestamp.to_rfc3339(),
+        "degraded_components": failing(ObservabilityHealthStatus::Degraded),
+        "unhealthy_components": failing(ObservabilityHealthStatus::Unhealthy),
+        "components": components,
+    })
+}
+
+/// Answer `GET /healthz`, `GET /readyz` and `GET /health` on `listener` until
+/// the task is aborted
+///
+/// All return a JSON body; the status is 200 when live or ready and 503
+/// otherwise, and `/health` is 503 only when a component is unhealthy. Any
+/// other path gets a 404.
+pub async fn serve_probes(listener: TcpListener, readiness: Arc<Readiness>, health: HealthSource) {
+    if let Ok(addr) = listener.local_addr() {
+        info!("Serving /healthz, /readyz and /health on http://{}", addr);
+    }
+    loop {
+        let (mut stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                debug!("Failed to accept probe connection: {}", e);
+                continue;
+            }
+        };
+        let readiness = Arc::clone(&readiness);
+        let health = Arc::clone(&health);
+        tokio::spawn(async move {
+            let mut buffer = [0u8; 1024];
+            let read = match stream.read(&mut buffer).await {
+                Ok(read) => read,
+                Err(e) => {
+                    debug!("Failed to read probe request from {}: {}", peer, e);
+                    return;
+                }
+            };
+            let request = String::from_utf8_lossy(&buffer[..read]).into_owned();
+            let response = if request_target(&request) == ("GET", "/health") {
+                let report = health().await;
+                http_response(
+                    report.status != ObservabilityHealthStatus::Unhealthy,
+                    health_payload(&report).to_string(),
+                )
+            } else {
+                probe_response(&request, &readiness)
+            };
+            if let Err(e) = stream.write_all(response.as_bytes()).await {
+                debug!("Failed to answer probe request from {}: {}", peer, e);
+            }
+            let _ = stream.shutdown().await;
+        });
+    }
+}
+
+/// Method and path from the request line
+fn request_target(request: &str) -> (&str, &str) {
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+    (method, path)
+}
+
+/// HTTP response for one probe request
+fn probe_response(request: &str, readiness: &Readiness) -> String {
+    let (ok, body) = match request_target(request) {
+        ("GET", "/healthz") => {
+            let health = readiness.health();
+            (
+                health.status != ObservabilityHealthStatus::Unhealthy,
+                serde_json::to_string(&health),
+            )
+        }
+        ("GET", "/readyz") => {
+            let report = readiness.report();
+            (report.ready, serde_json::to_string(&report))
+        }
+        _ => {
+            return "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .to_string()
+        }
+    };
+    http_response(ok, body.unwrap_or_else(|_| "{}".to_string()))
+}
+
+/// A JSON response that is 200 when `ok` and 503 otherwise
+fn http_response(ok: bool, body: String) -> String {
+    let status = if ok {
+        "200 OK"
+    } else {
+        "503 Service Unavailable"
+    };
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::MetricsCollector;
+
+    fn readiness(requires_index: bool) -> (Arc<IndexingStatus>, Readiness) {
+        let status = Arc::new(IndexingStatus::new());
+        let readiness = Readiness::new(
+            Arc::clone(&status),
+            Arc::new(HealthMonitor::new(MetricsCollector::new())),
+            requires_index,
+        );
+        (status, readiness)
+    }
+
+    async fn get(addr: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[test]
+    fn test_ready_only_after_initial_index_completes() {
+        let (status, readiness) = readiness(true);
+        assert!(!readiness.report().ready);
+
+        status.begin(10);
+        status.file_indexed();
+        let report = readiness.report();
+        assert!(!report.ready);
+        assert_eq!(
+            report.health.checks["indexing"].status,
+            ObservabilityHealthStatus::Degraded
+        );
+        assert_eq!(readiness.capability()["ready"], false);
+
+        status.finish();
+        assert!(readiness.report().ready);
+        assert_eq!(readiness.capability()["indexing_complete"], true);
+
+        // Re-indexing later does not take the server out of rotation
+        status.begin(1);
+        assert!(readiness.report().ready);
+    }
+
+    #[test]
+    fn test_ready_without_warm_up_unless_indexing() {
+        let (status, readiness) = readiness(false);
+        assert!(readiness.report().ready);
+        status.begin(3);
+        assert!(!readiness.report().ready);
+        status.finish();
+        assert!(readiness.report().ready);
+    }
+
+    #[tokio::test]
+    async fn test_probe_endpoints_follow_indexing() {
+        let (status, readiness) = readiness(true);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let readiness = Arc::new(readiness);
+        let source = Arc::clone(&readiness);
+        let health: HealthSource = Arc::new(move || {
+            let mut report = source.health();
+            add_component(
+                &mut report,
+                "storage",
+                ComponentHealth {
+                    status: ObservabilityHealthStatus::Unhealthy,
+                    message: "unreachable".to_string(),
+                    metrics: None,
+                },
+            );
+            Box::pin(async move { report })
+        });
+        let server = tokio::spawn(serve_probes(listener, readiness, health));
+
+        status.begin(2);
+        let response = get(addr, "/readyz").await;
+        assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+        assert!(response.contains("\"ready\":false"));
+        assert!(get(addr, "/healthz").await.starts_with("HTTP/1.1 200"));
+
+        status.finish();
+        let response = get(addr, "/readyz").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("\"ready\":true"));
+        assert!(get(addr, "/metrics").await.starts_with("HTTP/1.1 404"));
+
+        let response = get(addr, "/health").await;
+        assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+        assert!(response.contains("\"unhealthy_components\":[\"storage\"]"));
+
+        server.abort();
+    }
+
+    #[test]
+    fn test_health_payload_names_failing_components() {
+        let (status, readiness) = readiness(true);
+        status.begin(4);
+        let mut report = readiness.health();
+        add_component(
+            &mut report,
+            "cache",
+            ComponentHealth {
+                status: ObservabilityHealthStatus::Healthy,
+                message: "ok".to_string(),
+                metrics: None,
+            },
+        );
+        add_component(
+            &mut report,
+            "indexing",
+            readiness.report().health.checks["indexing"].clone(),
+        );
+
+        let payload = health_payload(&report);
+        assert_eq!(payload["status"], "degraded");
+        assert_eq!(payload["healthy"], false);
+        assert_eq!(payload["live"], true);
+        assert_eq!(payload["message"], "Some systems experiencing issues");
+        assert_eq!(
+            payload["degraded_components"],
+            serde_json::json!(["indexing"])
+        );
+        assert_eq!(payload["unhealthy_components"], serde_json::json!([]));
+        assert_eq!(payload["components"]["cache"]["status"], "healthy");
+
+        add_component(
+            &mut report,
+            "storage",
+            ComponentHealth {
+                status: ObservabilityHealthStatus::Unhealthy,
+                message: "unreachable".to_string(),
+                metrics: None,
+            },
+        );
+        let payload = health_payload(&report);
+        assert_eq!(payload["status"], "unhealthy");
+        assert_eq!(payload["live"], false);
+        assert_eq!(
+            payload["unhealthy_components"],
+            serde_json::json!(["storage"])
+        );
+    }
+}
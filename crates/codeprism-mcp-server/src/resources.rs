@@ -0,0 +1,258 @@
+//! `codeprism://file/` resources with ranged reads
+//!
+//! A repository file is addressed as `codeprism://file/<relative path>`. An
+//! optional `?range=START-END` query selects the bytes `START..END`; `END`
+//! may be left out to read to the end of the file. Reads are split into parts
+//! of at most the configured chunk size, each carrying the URI of its own
+//! range, so a client can fetch a large file incrementally by requesting the
+//! range that starts where the previous part ended.
+//!
+//! Clients can subscribe to a file resource, or to a directory path to cover
+//! every file under it. [`ResourceSubscriptions`] tracks the subscribed URIs
+//! so the server knows which resources a file change updates.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// URI prefix of repository file resources
+pub const FILE_RESOURCE_PREFIX: &str = "codeprism://file/";
+
+/// Byte range requested in a resource URI, end exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: usize,
+    /// `None` reads to the end of the file
+    pub end: Option<usize>,
+}
+
+/// A parsed `codeprism://file/` URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileResourceUri {
+    /// Path relative to the repository root
+    pub path: String,
+    pub range: Option<ByteRange>,
+}
+
+impl FileResourceUri {
+    /// Parse a file resource URI, rejecting other schemes and bad ranges
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let rest = uri
+            .strip_prefix(FILE_RESOURCE_PREFIX)
+            .ok_or_else(|| format!("Unsupported resource URI: {uri}"))?;
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (rest, None),
+        };
+        if path.is_empty() {
+            return Err(format!("Resource URI names no file: {uri}"));
+        }
+
+        let mut range = None;
+        for pair in query.into_iter().flat_map(|query| query.split('&')) {
+            let Some(value) = pair.strip_prefix("range=") else {
+                continue;
+            };
+            range = Some(parse_range(value).ok_or_else(|| {
+                format!("Invalid range '{value}'. Expected START-END or START- in bytes.")
+            })?);
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            range,
+        })
+    }
+}
+
+fn parse_range(value: &str) -> Option<ByteRange> {
+    let (start, end) = value.split_once('-')?;
+    let start = start.parse().ok()?;
+    let end = if end.is_empty() {
+        None
+    } else {
+        Some(end.parse().ok()?)
+    };
+    if end.is_some_and(|end| end < start) {
+        return None;
+    }
+    Some(ByteRange { start, end })
+}
+
+/// URI of a file resource, with a range query when one is given
+pub fn file_uri(path: &str, range: Option<(usize, usize)>) -> String {
+    match range {
+        Some((start, end)) => format!("{FILE_RESOURCE_PREFIX}{path}?range={start}-{end}"),
+        None => format!("{FILE_RESOURCE_PREFIX}{path}"),
+    }
+}
+
+/// Split the requested range of `content` into parts of at most `chunk_size` bytes
+///
+/// The range is clamped to the content and every boundary is moved back to a
+/// character boundary, so parts are valid UTF-8 and concatenate to the
+/// selected text. A part is never empty unless the range is.
+pub fn chunk_ranges(content: &str, range: ByteRange, chunk_size: usize) -> Vec<(usize, usize)> {
+    let floor = |mut index: usize| {
+        index = index.min(content.len());
+        while !content.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    };
+    let start = floor(range.start);
+    let end = floor(range.end.unwrap_or(content.len())).max(start);
+    let chunk_size = chunk_size.max(1);
+
+    let mut parts = Vec::new();
+    let mut from = start;
+    while from < end {
+        let mut to = floor((from + chunk_size).min(end));
+        if to == from {
+            // A character wider than the chunk size goes out whole
+            to = (from + 1..=end)
+                .find(|&index| content.is_char_boundary(index))
+                .unwrap_or(end);
+        }
+        parts.push((from, to));
+        from = to;
+    }
+    if parts.is_empty() {
+        parts.push((start, end));
+    }
+    parts
+}
+
+/// Resource URIs clients have subscribed to, with the paths they cover
+#[derive(Debug, Default)]
+pub struct ResourceSubscriptions {
+    paths: Mutex<BTreeMap<String, PathBuf>>,
+}
+
+impl ResourceSubscriptions {
+    /// Create an empty subscription set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe `uri` to changes at or under `path`
+    ///
+    /// Returns false when the URI was already subscribed.
+    pub fn subscribe(&self, uri: impl Into<String>, path: PathBuf) -> bool {
+        self.lock().insert(uri.into(), path).is_none()
+    }
+
+    /// Drop the subscription for `uri`, returning whether there was one
+    pub fn unsubscribe(&self, uri: &str) -> bool {
+        self.lock().remove(uri).is_some()
+    }
+
+    /// Number of subscribed URIs
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no URI is subscribed
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Subscribed URIs whose path is `changed` or a directory containing it
+    pub fn affected_by(&self, changed: &Path) -> Vec<String> {
+        self.lock()
+            .iter()
+            .filter(|(_, path)| changed.starts_with(path))
+            .map(|(uri, _)| uri.clone())
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, PathBuf>> {
+        self.paths.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_uri_with_and_without_range() {
+        let uri = FileResourceUri::parse("codeprism://file/src/main.rs").unwrap();
+        assert_eq!(uri.path, "src/main.rs");
+        assert_eq!(uri.range, None);
+
+        let uri = FileResourceUri::parse("codeprism://file/src/main.rs?range=10-20").unwrap();
+        assert_eq!(
+            uri.range,
+            Some(ByteRange {
+                start: 10,
+                end: Some(20)
+            })
+        );
+        let uri = FileResourceUri::parse("codeprism://file/a.py?range=5-").unwrap();
+        assert_eq!(
+            uri.range,
+            Some(ByteRange {
+                start: 5,
+                end: None
+            })
+        );
+
+        assert!(FileResourceUri::parse("file:///etc/passwd").is_err());
+        assert!(FileResourceUri::parse("codeprism://file/a.py?range=20-10").is_err());
+        assert!(FileResourceUri::parse("codeprism://file/a.py?range=abc").is_err());
+    }
+
+    #[test]
+    fn test_chunk_ranges_respect_char_boundaries() {
+        let content = "aé€😀b";
+        let whole = ByteRange {
+            start: 0,
+            end: None,
+        };
+        let parts = chunk_ranges(content, whole, 2);
+        let joined: String = parts
+            .iter()
+            .map(|&(start, end)| &content[start..end])
+            .collect();
+        assert_eq!(joined, content);
+        assert!(parts.iter().all(|&(start, end)| end > start));
+
+        // Out-of-range requests are clamped
+        let tail = ByteRange {
+            start: 100,
+            end: Some(200),
+        };
+        assert_eq!(
+            chunk_ranges(content, tail, 4),
+            vec![(content.len(), content.len())]
+        );
+    }
+
+    #[test]
+    fn test_subscriptions_match_files_and_directories() {
+        let subscriptions = ResourceSubscriptions::new();
+        assert!(subscriptions.subscribe(
+            "codeprism://file/src/lib.rs",
+            PathBuf::from("/repo/src/lib.rs")
+        ));
+        assert!(subscriptions.subscribe("codeprism://file/docs", PathBuf::from("/repo/docs")));
+        assert!(!subscriptions.subscribe("codeprism://file/docs", PathBuf::from("/repo/docs")));
+
+        assert_eq!(
+            subscriptions.affected_by(Path::new("/repo/src/lib.rs")),
+            vec!["codeprism://file/src/lib.rs"]
+        );
+        assert_eq!(
+            subscriptions.affected_by(Path::new("/repo/docs/guide/intro.md")),
+            vec!["codeprism://file/docs"]
+        );
+        assert!(subscriptions
+            .affected_by(Path::new("/repo/src/lib.rs.bak"))
+            .is_empty());
+
+        assert!(subscriptions.unsubscribe("codeprism://file/docs"));
+        assert!(!subscriptions.unsubscribe("codeprism://file/docs"));
+        assert_eq!(subscriptions.len(), 1);
+    }
+}
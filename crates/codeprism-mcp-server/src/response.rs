@@ -4,8 +4,10 @@
 //! unstructured content (JSON as text) and structured content (direct JSON access)
 //! according to the MCP 2025-06-18 specification.
 
-use rmcp::model::{CallToolResult, Content};
+use crate::config::ResponseLimits;
+use rmcp::model::{CallToolResult, Content, JsonObject, RawContent, Tool};
 use serde_json::Value;
+use std::sync::Arc;
 use tracing::warn;
 
 /// Create a dual-format response containing both unstructured and structured content
@@ -115,6 +117,219 @@ fn add_structured_content(data: &Value) -> Result<Content, Box<dyn std::error::E
     }
 }
 
+/// Argument accepted by every tool to return only parts of its result
+pub const RESPONSE_FIELDS_ARGUMENT: &str = "response_fields";
+
+/// Remove `response_fields` from tool call arguments
+///
+/// The tools never see the argument; the selected fields are applied to
+/// their result afterwards with [`select_response_fields`]. Each entry must
+/// be a JSON Pointer (RFC 6901): empty, or starting with `/`.
+pub fn take_response_fields(
+    arguments: &mut Option<JsonObject>,
+) -> Result<Option<Vec<String>>, String> {
+    let Some(value) = arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(RESPONSE_FIELDS_ARGUMENT))
+    else {
+        return Ok(None);
+    };
+
+    let pointers: Vec<String> = serde_json::from_value(value)
+        .map_err(|_| format!("{RESPONSE_FIELDS_ARGUMENT} must be an array of JSON Pointers"))?;
+    if let Some(invalid) = pointers
+        .iter()
+        .find(|pointer| !pointer.is_empty() && !pointer.starts_with('/'))
+    {
+        return Err(format!(
+            "Invalid JSON Pointer '{invalid}' in {RESPONSE_FIELDS_ARGUMENT}: pointers must start with '/'"
+        ));
+    }
+    Ok(Some(pointers))
+}
+
+/// Reduce a tool result to the values at the given JSON Pointers
+///
+/// The result becomes an object mapping each pointer to its value. Error
+/// results are returned unchanged; a pointer that matches nothing in the
+/// result is an error.
+pub fn select_response_fields(
+    result: CallToolResult,
+    pointers: &[String],
+) -> Result<CallToolResult, String> {
+    if result.is_error == Some(true) {
+        return Ok(result);
+    }
+    let data: Value = result
+        .content
+        .first()
+        .and_then(|content| content.as_text())
+        .and_then(|text| serde_json::from_str(&text.text).ok())
+        .ok_or_else(|| format!("{RESPONSE_FIELDS_ARGUMENT} requires a JSON tool result"))?;
+
+    let mut selected = serde_json::Map::new();
+    for pointer in pointers {
+        let value = data.pointer(pointer).ok_or_else(|| {
+            format!("JSON Pointer '{pointer}' does not match any field of the result")
+        })?;
+        selected.insert(pointer.clone(), value.clone());
+    }
+
+    Ok(CallToolResult::success(vec![Content::text(
+        serde_json::to_string_pretty(&selected)
+            .unwrap_or_else(|_| "Error formatting response".to_string()),
+    )]))
+}
+
+/// Add the `response_fields` argument to a tool's input schema
+pub fn with_response_fields_schema(mut tool: Tool) -> Tool {
+    let schema = Arc::make_mut(&mut tool.input_schema);
+    let properties = schema
+        .entry("properties")
+        .or_insert_with(|| Value::Object(serde_json::Map::new()));
+    if let Some(properties) = properties.as_object_mut() {
+        properties.insert(
+            RESPONSE_FIELDS_ARGUMENT.to_string(),
+            serde_json::json!({
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "Return only these fields of the result, as JSON Pointers (e.g. /graph_statistics/total_files)"
+            }),
+        );
+    }
+    tool
+}
+
+/// Top-level fields that are never truncated, so clients can always tell
+/// whether a call succeeded
+const PRESERVED_FIELDS: &[&str] = &[
+    "status", "success", "error", "errors", "is_error", "message",
+];
+
+/// Truncate long arrays and strings in a tool result
+///
+/// Arrays longer than `limits.max_array_items` keep their first items and
+/// end with a marker object:
+///
+/// ```json
+/// { "_truncated": true, "total": 1200, "shown": 500, "omitted": 700 }
+/// ```
+///
+/// Strings longer than `limits.max_string_bytes` are cut at a character
+/// boundary and end with a `... [N more bytes]` note. When anything was cut
+/// from an object result, a top-level `_truncation` entry lists the JSON
+/// Pointers of the truncated values.
+///
+/// Error results, non-JSON text and top-level status fields such as
+/// `status` and `error` are left unchanged. Every truncated text content is
+/// re-serialized, so the result is always valid JSON.
+pub fn truncate_response(mut result: CallToolResult, limits: &ResponseLimits) -> CallToolResult {
+    if result.is_error == Some(true) {
+        return result;
+    }
+    for content in &mut result.content {
+        let RawContent::Text(text) = &mut content.raw else {
+            continue;
+        };
+        let Ok(mut data) = serde_json::from_str::<Value>(&text.text) else {
+            continue;
+        };
+
+        let mut truncated = Vec::new();
+        match &mut data {
+            Value::Object(object) => {
+                for (key, value) in object.iter_mut() {
+                    if !PRESERVED_FIELDS.contains(&key.as_str()) {
+                        truncate_value(
+                            value,
+                            &format!("/{}", escape_pointer(key)),
+                            limits,
+                            &mut truncated,
+                        );
+                    }
+                }
+                if !truncated.is_empty() {
+                    object.insert(
+                        "_truncation".to_string(),
+                        serde_json::json!({
+                            "truncated": true,
+                            "paths": truncated,
+                            "max_array_items": limits.max_array_items,
+                            "max_string_bytes": limits.max_string_bytes,
+                        }),
+                    );
+                }
+            }
+            value => truncate_value(value, "", limits, &mut truncated),
+        }
+        if truncated.is_empty() {
+            continue;
+        }
+
+        let serialized = if text.text.contains('\n') {
+            serde_json::to_string_pretty(&data)
+        } else {
+            serde_json::to_string(&data)
+        };
+        if let Ok(serialized) = serialized {
+            text.text = serialized;
+        }
+    }
+    result
+}
+
+/// Truncate `value` in place, recording the JSON Pointer of every cut value
+fn truncate_value(
+    value: &mut Value,
+    pointer: &str,
+    limits: &ResponseLimits,
+    truncated: &mut Vec<String>,
+) {
+    match value {
+        Value::Array(items) => {
+            let total = items.len();
+            if total > limits.max_array_items {
+                items.truncate(limits.max_array_items);
+                truncated.push(pointer.to_string());
+            }
+            for (index, item) in items.iter_mut().enumerate() {
+                truncate_value(item, &format!("{pointer}/{index}"), limits, truncated);
+            }
+            if total > limits.max_array_items {
+                let shown = limits.max_array_items;
+                items.push(serde_json::json!({
+                    "_truncated": true,
+                    "total": total,
+                    "shown": shown,
+                    "omitted": total - shown,
+                }));
+            }
+        }
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                let pointer = format!("{pointer}/{}", escape_pointer(key));
+                truncate_value(value, &pointer, limits, truncated);
+            }
+        }
+        Value::String(text) if text.len() > limits.max_string_bytes => {
+            let mut end = limits.max_string_bytes;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            let omitted = text.len() - end;
+            text.truncate(end);
+            text.push_str(&format!("... [{omitted} more bytes]"));
+            truncated.push(pointer.to_string());
+        }
+        _ => {}
+    }
+}
+
+/// Escape an object key for use in a JSON Pointer (RFC 6901)
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,4 +500,128 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_take_response_fields_validates_pointers() {
+        let mut arguments = Some(
+            json!({ "target": "src", "response_fields": ["/stats/total_files", ""] })
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        let pointers = take_response_fields(&mut arguments).unwrap().unwrap();
+        assert_eq!(pointers, vec!["/stats/total_files", ""]);
+        assert!(!arguments.unwrap().contains_key(RESPONSE_FIELDS_ARGUMENT));
+
+        let mut missing_slash = Some(
+            json!({ "response_fields": ["stats/total_files"] })
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        assert!(take_response_fields(&mut missing_slash).is_err());
+        assert_eq!(take_response_fields(&mut None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_select_response_fields_returns_only_selected_values() {
+        let result = create_dual_response(&json!({
+            "status": "success",
+            "stats": { "total_files": 42, "languages": ["rust"] }
+        }));
+        let selected =
+            select_response_fields(result.clone(), &["/stats/total_files".to_string()]).unwrap();
+        assert_eq!(selected.content.len(), 1);
+        let value: Value =
+            serde_json::from_str(&selected.content[0].as_text().unwrap().text).unwrap();
+        assert_eq!(value, json!({ "/stats/total_files": 42 }));
+
+        assert!(select_response_fields(result, &["/stats/missing".to_string()]).is_err());
+    }
+
+    fn text_json(result: &CallToolResult, index: usize) -> Value {
+        serde_json::from_str(&result.content[index].as_text().unwrap().text).unwrap()
+    }
+
+    #[test]
+    fn test_truncate_response_limits_arrays_with_marker() {
+        let limits = ResponseLimits {
+            max_array_items: 2,
+            max_string_bytes: 1024,
+        };
+        let result = create_dual_response(&json!({
+            "status": "success",
+            "errors": ["a", "b", "c"],
+            "symbols": [
+                { "name": "one", "references": [1, 2, 3, 4] },
+                { "name": "two" },
+                { "name": "three" },
+                { "name": "four" }
+            ]
+        }));
+
+        let truncated = truncate_response(result, &limits);
+        // Both the text and the structured content are truncated
+        for index in 0..truncated.content.len() {
+            let value = text_json(&truncated, index);
+            let symbols = value["symbols"].as_array().unwrap();
+            assert_eq!(symbols.len(), 3);
+            assert_eq!(
+                symbols[2],
+                json!({ "_truncated": true, "total": 4, "shown": 2, "omitted": 2 })
+            );
+            assert_eq!(symbols[0]["references"][2]["total"], 4);
+            assert_eq!(value["errors"], json!(["a", "b", "c"]));
+            assert_eq!(value["status"], "success");
+            assert_eq!(
+                value["_truncation"]["paths"],
+                json!(["/symbols", "/symbols/0/references"])
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncate_response_cuts_strings_at_char_boundary() {
+        let limits = ResponseLimits {
+            max_array_items: 10,
+            max_string_bytes: 5,
+        };
+        let result = create_dual_response(&json!({
+            "message": "a message longer than the cap",
+            "source": "abcdé\"xyz",
+            "name": "short"
+        }));
+
+        let value = text_json(&truncate_response(result, &limits), 0);
+        // 'é' spans bytes 4..6, so the cut moves back to byte 4
+        assert_eq!(value["source"], "abcd... [6 more bytes]");
+        assert_eq!(value["name"], "short");
+        assert_eq!(value["message"], "a message longer than the cap");
+        assert_eq!(value["_truncation"]["paths"], json!(["/source"]));
+
+        let plain = truncate_response(
+            CallToolResult::success(vec![Content::text(r#"["abcdefgh"]"#)]),
+            &limits,
+        );
+        assert_eq!(text_json(&plain, 0), json!(["abcde... [3 more bytes]"]));
+    }
+
+    #[test]
+    fn test_truncate_response_leaves_errors_and_small_results_unchanged() {
+        let limits = ResponseLimits {
+            max_array_items: 1,
+            max_string_bytes: 4,
+        };
+        let error = create_error_response("Something went wrong", Some("FAILED"));
+        assert_eq!(
+            truncate_response(error.clone(), &limits).content,
+            error.content
+        );
+
+        let small = create_dual_response(&json!({ "items": [1] }));
+        assert_eq!(
+            truncate_response(small.clone(), &limits).content,
+            small.content
+        );
+    }
 }
@@ -0,0 +1,409 @@
+//! Export of tool, resource, and prompt schemas for client code generation
+//!
+//! Writes one JSON Schema file per tool input and output, the resource and prompt
+//! method schemas, and an aggregate OpenRPC document describing every method.
+
+use crate::{CodePrismMcpServer, Error, Result};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// OpenRPC specification version used for the aggregate document
+pub const OPENRPC_VERSION: &str = "1.2.6";
+
+/// Files written by [`export_schemas`]
+#[derive(Debug, Clone, Default)]
+pub struct SchemaExportSummary {
+    /// Per-tool input and output schema files
+    pub tool_files: Vec<PathBuf>,
+    /// Resource method schema file
+    pub resources_file: PathBuf,
+    /// Prompt method schema file
+    pub prompts_file: PathBuf,
+    /// Aggregate OpenRPC document
+    pub openrpc_file: PathBuf,
+    /// Number of tools exported
+    pub tools_exported: usize,
+}
+
+/// Schema of the result returned by every tool call
+///
+/// Tools return a `CallToolResult` whose text content holds the JSON response.
+fn tool_output_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "CallToolResult",
+        "type": "object",
+        "properties": {
+            "content": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "type": { "type": "string", "enum": ["text"] },
+                        "text": {
+                            "type": "string",
+                            "description": "JSON encoded tool response"
+                        }
+                    },
+                    "required": ["type", "text"]
+                }
+            },
+            "isError": { "type": "boolean" }
+        },
+        "required": ["content"]
+    })
+}
+
+fn resource_schemas() -> Value {
+    json!({
+        "resources/list": {
+            "params": {
+                "type": "object",
+                "properties": { "cursor": { "type": "string" } }
+            },
+            "result": {
+                "type": "object",
+                "properties": {
+                    "resources": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "uri": { "type": "string" },
+                                "name": { "type": "string" },
+                                "description": { "type": "string" },
+                                "mimeType": { "type": "string" }
+                            },
+                            "required": ["uri", "name"]
+                        }
+                    },
+                    "nextCursor": { "type": "string" }
+                },
+                "required": ["resources"]
+            }
+        },
+        "resources/read": {
+            "params": {
+                "type": "object",
+                "properties": { "uri": { "type": "string" } },
+                "required": ["uri"]
+            },
+            "result": {
+                "type": "object",
+                "properties": {
+                    "contents": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "uri": { "type": "string" },
+                                "mimeType": { "type": "string" },
+                                "text": { "type": "string" },
+                                "blob": { "type": "string" }
+                            },
+                            "required": ["uri"]
+                        }
+                    }
+                },
+                "required": ["contents"]
+            }
+        }
+    })
+}
+
+fn prompt_schemas() -> Value {
+    json!({
+        "prompts/list": {
+            "params": {
+                "type": "object",
+                "properties": { "cursor": { "type": "string" } }
+            },
+            "result": {
+                "type": "object",
+                "properties": {
+                    "prompts": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string" },
+                                "description": { "type": "string" },
+                                "arguments": {
+                                    "type": "array",
+                                    "items": {
+                                        "type": "object",
+                                        "properties": {
+                                            "name": { "type": "string" },
+                                            "description": { "type": "string" },
+                                            "required": { "type": "boolean" }
+                                        },
+                                        "required": ["name"]
+                                    }
+                                }
+                            },
+                            "required": ["name"]
+                        }
+                    },
+                    "nextCursor": { "type": "string" }
+                },
+                "required": ["prompts"]
+            }
+        },
+        "prompts/get": {
+            "params": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "arguments": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" }
+                    }
+                },
+                "required": ["name"]
+            },
+            "result": {
+                "type": "object",
+                "properties": {
+                    "description": { "type": "string" },
+                    "messages": { "type": "array", "items": { "type": "object" } }
+                },
+                "required": ["messages"]
+            }
+        }
+    })
+}
+
+/// Convert an object schema into OpenRPC by-name parameters
+fn openrpc_params(schema: &Value) -> Vec<Value> {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| {
+                    json!({
+                        "name": name,
+                        "required": required.contains(&name.as_str()),
+                        "schema": property
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Build the aggregate OpenRPC document for the server
+pub fn build_openrpc_document(server: &CodePrismMcpServer) -> Value {
+    let mut methods = Vec::new();
+
+    for tool in server.list_tools() {
+        let input_schema = Value::Object(tool.input_schema.as_ref().clone());
+        methods.push(json!({
+            "name": tool.name,
+            "summary": tool.description.clone().unwrap_or_default(),
+            "tags": [{ "name": "tool" }],
+            "paramStructure": "by-name",
+            "params": openrpc_params(&input_schema),
+            "result": { "name": "result", "schema": tool_output_schema() }
+        }));
+    }
+
+    for (tag, schemas) in [
+        ("resource", resource_schemas()),
+        ("prompt", prompt_schemas()),
+    ] {
+        if let Some(schemas) = schemas.as_object() {
+            for (name, schema) in schemas {
+                methods.push(json!({
+                    "name": name,
+                    "tags": [{ "name": tag }],
+                    "paramStructure": "by-name",
+                    "params": openrpc_params(&schema["params"]),
+                    "result": { "name": "result", "schema": schema["result"] }
+                }));
+            }
+        }
+    }
+
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {
+            "title": crate::SERVER_NAME,
+            "version": crate::VERSION,
+            "description": format!("MCP protocol {}", crate::MCP_VERSION)
+        },
+        "methods": methods
+    })
+}
+
+/// Check that a document has the structure required by the OpenRPC specification
+pub fn validate_openrpc_document(document: &Value) -> Result<()> {
+    let invalid = |msg: String| Err(Error::protocol(format!("Invalid OpenRPC document: {msg}")));
+
+    if document.get("openrpc").and_then(|v| v.as_str()).is_none() {
+        return invalid("missing 'openrpc' version".to_string());
+    }
+    let info = match document.get("info") {
+        Some(info) => info,
+        None => return invalid("missing 'info'".to_string()),
+    };
+    if info.get("title").and_then(|v| v.as_str()).is_none()
+        || info.get("version").and_then(|v| v.as_str()).is_none()
+    {
+        return invalid("'info' requires 'title' and 'version'".to_string());
+    }
+    let methods = match document.get("methods").and_then(|v| v.as_array()) {
+        Some(methods) => methods,
+        None => return invalid("missing 'methods' array".to_string()),
+    };
+
+    let mut names = std::collections::HashSet::new();
+    for method in methods {
+        let name = match method.get("name").and_then(|v| v.as_str()) {
+            Some(name) => name,
+            None => return invalid("method without 'name'".to_string()),
+        };
+        if !names.insert(name) {
+            return invalid(format!("duplicate method '{name}'"));
+        }
+        let params = match method.get("params").and_then(|v| v.as_array()) {
+            Some(params) => params,
+            None => return invalid(format!("method '{name}' has no 'params' array")),
+        };
+        for param in params {
+            if param.get("name").and_then(|v| v.as_str()).is_none() || param.get("schema").is_none()
+            {
+                return invalid(format!(
+                    "method '{name}' has a parameter without 'name' or 'schema'"
+                ));
+            }
+        }
+        let result = method.get("result");
+        if result.and_then(|r| r.get("name")).is_none()
+            || result.and_then(|r| r.get("schema")).is_none()
+        {
+            return invalid(format!("method '{name}' has an invalid 'result'"));
+        }
+    }
+
+    Ok(())
+}
+
+fn write_json(path: &Path, value: &Value) -> Result<()> {
+    std::fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// Write all tool, resource, and prompt schemas to `output_dir`
+///
+/// Layout:
+/// - `tools/<tool>.input.json` and `tools/<tool>.output.json`
+/// - `resources.json` and `prompts.json`
+/// - `openrpc.json`
+pub fn export_schemas(
+    server: &CodePrismMcpServer,
+    output_dir: &Path,
+) -> Result<SchemaExportSummary> {
+    let tools_dir = output_dir.join("tools");
+    std::fs::create_dir_all(&tools_dir)?;
+
+    let mut summary = SchemaExportSummary::default();
+    let output_schema = tool_output_schema();
+
+    for tool in server.list_tools() {
+        let input_path = tools_dir.join(format!("{}.input.json", tool.name));
+        let output_path = tools_dir.join(format!("{}.output.json", tool.name));
+
+        write_json(
+            &input_path,
+            &Value::Object(tool.input_schema.as_ref().clone()),
+        )?;
+        write_json(&output_path, &output_schema)?;
+
+        summary.tool_files.push(input_path);
+        summary.tool_files.push(output_path);
+        summary.tools_exported += 1;
+    }
+
+    summary.resources_file = output_dir.join("resources.json");
+    write_json(&summary.resources_file, &resource_schemas())?;
+
+    summary.prompts_file = output_dir.join("prompts.json");
+    write_json(&summary.prompts_file, &prompt_schemas())?;
+
+    let document = build_openrpc_document(server);
+    validate_openrpc_document(&document)?;
+    summary.openrpc_file = output_dir.join("openrpc.json");
+    write_json(&summary.openrpc_file, &document)?;
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[tokio::test]
+    async fn test_export_writes_schema_files() {
+        let server = CodePrismMcpServer::new(Config::default()).await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let summary = export_schemas(&server, temp_dir.path()).unwrap();
+
+        assert!(summary.tools_exported > 0, "Should export registered tools");
+        for path in &summary.tool_files {
+            assert!(path.exists(), "Missing schema file {}", path.display());
+        }
+        assert!(temp_dir.path().join("tools/ping.input.json").exists());
+        assert!(temp_dir.path().join("tools/ping.output.json").exists());
+        assert!(summary.resources_file.exists());
+        assert!(summary.prompts_file.exists());
+        assert!(summary.openrpc_file.exists());
+    }
+
+    #[tokio::test]
+    async fn test_openrpc_document_lists_all_tools() {
+        let server = CodePrismMcpServer::new(Config::default()).await.unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let summary = export_schemas(&server, temp_dir.path()).unwrap();
+
+        let document: Value =
+            serde_json::from_str(&std::fs::read_to_string(&summary.openrpc_file).unwrap()).unwrap();
+        validate_openrpc_document(&document).unwrap();
+
+        let method_names: Vec<&str> = document["methods"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|m| m["name"].as_str())
+            .collect();
+        for tool in server.list_tools() {
+            assert!(
+                method_names.contains(&tool.name.as_ref()),
+                "OpenRPC document should list tool {}",
+                tool.name
+            );
+        }
+        assert!(method_names.contains(&"resources/read"));
+        assert!(method_names.contains(&"prompts/get"));
+    }
+
+    #[test]
+    fn test_validation_rejects_malformed_document() {
+        assert!(validate_openrpc_document(&json!({ "openrpc": "1.2.6" })).is_err());
+        assert!(validate_openrpc_document(&json!({
+            "openrpc": "1.2.6",
+            "info": { "title": "t", "version": "1" },
+            "methods": [{ "name": "a", "params": [] }]
+        }))
+        .is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Graceful shutdown with in-flight request draining
+//!
+//! Tool calls run in their own tasks, so when the client closes stdin the
+//! serve loop can finish while handlers are still computing. The server
+//! tracks those calls here, refuses new ones once shutdown starts, and waits
+//! for the running ones up to a timeout before the process exits.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Shutdown flag and in-flight request count for one server instance
+#[derive(Debug, Default)]
+pub struct ShutdownState {
+    shutdown_requested: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+/// Outcome of draining in-flight requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrainReport {
+    /// Requests that finished before the timeout
+    pub drained: usize,
+    /// Requests still running when the timeout elapsed
+    pub aborted: usize,
+}
+
+/// Marks a request as in flight until dropped
+#[derive(Debug)]
+pub struct InFlightGuard {
+    state: Arc<ShutdownState>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.state.idle.notify_waiters();
+        }
+    }
+}
+
+impl ShutdownState {
+    /// Create a state accepting requests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop accepting new requests
+    pub fn request_shutdown(&self) {
+        self.shutdown_requested.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether shutdown has been requested
+    pub fn is_shutdown_requested(&self) -> bool {
+        self.shutdown_requested.load(Ordering::SeqCst)
+    }
+
+    /// Number of requests currently running
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Register a new request, or `None` once shutdown has been requested
+    pub fn begin_request(self: &Arc<Self>) -> Option<InFlightGuard> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlightGuard {
+            state: Arc::clone(self),
+        };
+        // Checked after registering so `drain` never misses a request it let through
+        if self.is_shutdown_requested() {
+            return None;
+        }
+        Some(guard)
+    }
+
+    /// Request shutdown and wait up to `timeout` for running requests to finish
+    pub async fn drain(&self, timeout: Duration) -> DrainReport {
+        self.request_shutdown();
+        let pending = self.in_flight();
+
+        let wait_idle = async {
+            loop {
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    break;
+                }
+                idle.await;
+            }
+        };
+        let _ = tokio::time::timeout(timeout, wait_idle).await;
+
+        let aborted = self.in_flight().min(pending);
+        DrainReport {
+            drained: pending - aborted,
+            aborted,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_running_requests() {
+        let state = Arc::new(ShutdownState::new());
+        let guard = state.begin_request().unwrap();
+
+        let finisher = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(guard);
+        });
+        let report = state.drain(Duration::from_secs(5)).await;
+        finisher.await.unwrap();
+
+        assert_eq!(
+            report,
+            DrainReport {
+                drained: 1,
+                aborted: 0
+            }
+        );
+        assert!(
+            state.begin_request().is_none(),
+            "New requests are refused after shutdown"
+        );
+        assert_eq!(state.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drain_reports_requests_past_the_timeout_as_aborted() {
+        let state = Arc::new(ShutdownState::new());
+        drop(state.begin_request().unwrap());
+        let _stuck = state.begin_request().unwrap();
+
+        let report = state.drain(Duration::from_millis(10)).await;
+        assert_eq!(
+            report,
+            DrainReport {
+                drained: 0,
+                aborted: 1
+            }
+        );
+    }
+}
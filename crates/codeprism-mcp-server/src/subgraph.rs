@@ -0,0 +1,199 @@
+//! Reproducer subgraphs in the storage graph format
+//!
+//! A subgraph extracted around a symbol is converted to a
+//! [`SerializableGraph`] so it can be attached to a bug report and loaded into
+//! a fresh [`GraphStore`] to reproduce an analysis without the original
+//! repository. Fields without a slot in the storage types (language,
+//! signature, metadata) travel as node attributes. Whole-graph snapshots use
+//! the same format.
+
+use crate::{Error, Result};
+use codeprism_core::{
+    Edge, EdgeKind, GraphStore, Language, Node, NodeId, NodeKind, Span, Subgraph,
+};
+use codeprism_storage::{SerializableEdge, SerializableGraph, SerializableNode, SerializableSpan};
+
+const LANGUAGE_ATTRIBUTE: &str = "language";
+const SIGNATURE_ATTRIBUTE: &str = "signature";
+const METADATA_ATTRIBUTE: &str = "metadata";
+
+/// Convert an extracted subgraph into a storage graph
+pub fn to_serializable(repo_id: &str, subgraph: &Subgraph) -> SerializableGraph {
+    graph_to_serializable(repo_id, &subgraph.nodes, &subgraph.edges)
+}
+
+/// Convert a set of nodes and the edges between them into a storage graph
+pub fn graph_to_serializable(repo_id: &str, nodes: &[Node], edges: &[Edge]) -> SerializableGraph {
+    let mut graph = SerializableGraph::new(repo_id.to_string());
+
+    for node in nodes {
+        let mut serialized = SerializableNode::new(
+            node.id.to_hex(),
+            node.name.clone(),
+            enum_name(&node.kind),
+            node.file.clone(),
+            SerializableSpan {
+                start_byte: node.span.start_byte,
+                end_byte: node.span.end_byte,
+                start_line: node.span.start_line,
+                end_line: node.span.end_line,
+                start_column: node.span.start_column,
+                end_column: node.span.end_column,
+            },
+        );
+        serialized.add_attribute(LANGUAGE_ATTRIBUTE.to_string(), enum_name(&node.lang));
+        if let Some(signature) = &node.signature {
+            serialized.add_attribute(SIGNATURE_ATTRIBUTE.to_string(), signature.clone());
+        }
+        if !node.metadata.is_null() {
+            serialized.add_attribute(METADATA_ATTRIBUTE.to_string(), node.metadata.to_string());
+        }
+        graph.add_node(serialized);
+    }
+
+    for edge in edges {
+        graph.add_edge(SerializableEdge::new(
+            edge.source.to_hex(),
+            edge.target.to_hex(),
+            enum_name(&edge.kind),
+        ));
+    }
+
+    graph
+}
+
+/// Load a storage graph into `store`, returning the number of nodes added
+///
+/// Node ids are kept, so ids taken from the original report resolve in the
+/// reloaded graph.
+pub fn load_into(graph: &SerializableGraph, store: &GraphStore) -> Result<usize> {
+    for node in nodes_from_serializable(graph)? {
+        store.add_node(node);
+    }
+
+    for edge in &graph.edges {
+        let kind: EdgeKind = parse_enum(&edge.kind, "edge kind")?;
+        store.add_edge(Edge::new(
+            parse_id(&edge.source)?,
+            parse_id(&edge.target)?,
+            kind,
+        ));
+    }
+
+    Ok(graph.nodes.len())
+}
+
+/// Rebuild the nodes of a storage graph
+pub fn nodes_from_serializable(graph: &SerializableGraph) -> Result<Vec<Node>> {
+    let mut nodes = Vec::with_capacity(graph.nodes.len());
+    for node in &graph.nodes {
+        let kind: NodeKind = parse_enum(&node.kind, "node kind")?;
+        let lang: Language = match node.attributes.get(LANGUAGE_ATTRIBUTE) {
+            Some(lang) => parse_enum(lang, "language")?,
+            None => Language::Unknown,
+        };
+        let metadata = match node.attributes.get(METADATA_ATTRIBUTE) {
+            Some(metadata) => serde_json::from_str(metadata)?,
+            None => serde_json::Value::Null,
+        };
+
+        nodes.push(Node {
+            id: parse_id(&node.id)?,
+            kind,
+            name: node.name.clone(),
+            lang,
+            file: node.file.clone(),
+            span: Span::new(
+                node.span.start_byte,
+                node.span.end_byte,
+                node.span.start_line,
+                node.span.end_line,
+                node.span.start_column,
+                node.span.end_column,
+            ),
+            signature: node.attributes.get(SIGNATURE_ATTRIBUTE).cloned(),
+            metadata,
+        });
+    }
+
+    Ok(nodes)
+}
+
+/// Serde name of a unit enum variant, e.g. `type_alias` or `CALLS`
+fn enum_name<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn parse_enum<T: serde::de::DeserializeOwned>(name: &str, what: &str) -> Result<T> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| Error::tool_execution(format!("Unknown {what} in subgraph: {name}")))
+}
+
+fn parse_id(hex: &str) -> Result<NodeId> {
+    NodeId::from_hex(hex)
+        .map_err(|e| Error::tool_execution(format!("Invalid node id in subgraph {hex}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeprism_core::GraphQuery;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn node(name: &str, kind: NodeKind, start: usize) -> Node {
+        let mut node = Node::new(
+            "repo",
+            kind,
+            name.to_string(),
+            Language::TypeScript,
+            PathBuf::from("src/app.ts"),
+            Span::new(start, start + 5, 1, 1, 1, 6),
+        );
+        node.metadata = serde_json::json!({ "exported": true });
+        node
+    }
+
+    #[test]
+    fn test_subgraph_round_trips_into_fresh_store() {
+        let original = Arc::new(GraphStore::new());
+        let shape = node("Shape", NodeKind::Interface, 0);
+        let square = node("Square", NodeKind::Class, 10);
+        let area = node("area", NodeKind::Method, 20);
+        let far = node("render", NodeKind::Function, 30);
+        for n in [&shape, &square, &area, &far] {
+            original.add_node(n.clone());
+        }
+        original.add_edge(Edge::new(square.id, shape.id, EdgeKind::Implements));
+        original.add_edge(Edge::new(square.id, area.id, EdgeKind::Calls));
+        original.add_edge(Edge::new(far.id, shape.id, EdgeKind::Reads));
+
+        let subgraph = GraphQuery::new(original)
+            .extract_subgraph(&square.id, 1)
+            .unwrap();
+        let serialized = to_serializable("repo", &subgraph);
+        assert_eq!(serialized.nodes.len(), 3);
+        assert_eq!(serialized.edges.len(), 2);
+
+        // Survives a JSON round trip and loads into an empty store
+        let json = serde_json::to_string(&serialized).unwrap();
+        let restored: SerializableGraph = serde_json::from_str(&json).unwrap();
+        let fresh = Arc::new(GraphStore::new());
+        assert_eq!(load_into(&restored, &fresh).unwrap(), 3);
+
+        let reloaded = fresh.get_node(&shape.id).unwrap();
+        assert_eq!(reloaded.kind, NodeKind::Interface);
+        assert_eq!(reloaded.lang, Language::TypeScript);
+        assert_eq!(reloaded.metadata["exported"], true);
+        assert!(fresh.get_node(&far.id).is_none());
+
+        let query = GraphQuery::new(fresh);
+        let implementers = query.get_subclasses(&shape.id).unwrap();
+        assert_eq!(implementers.len(), 1);
+        assert_eq!(implementers[0].class_name, "Square");
+        assert_eq!(query.find_callees(&square.id).unwrap()[0].node.id, area.id);
+    }
+}
@@ -0,0 +1,169 @@
+//! Concurrency caps on tool calls
+//!
+//! A client firing many expensive analyses at once would otherwise saturate
+//! the CPU and slow every other call down. Each call takes a permit from one
+//! of two semaphores, one for analysis and workflow tools and one for the
+//! rest, before it is dispatched; a call that cannot get one within the
+//! queue timeout is rejected as busy.
+
+use crate::config::ConcurrencyLimits;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Permits for running tool calls, shared by all clones of a server
+#[derive(Debug)]
+pub struct ToolThrottle {
+    cheap: Arc<Semaphore>,
+    expensive: Arc<Semaphore>,
+    limits: ConcurrencyLimits,
+}
+
+/// Slot held by a running tool call until dropped
+#[derive(Debug)]
+pub struct ToolPermit {
+    _permit: OwnedSemaphorePermit,
+    /// Time spent waiting for the slot
+    pub waited: Duration,
+}
+
+/// A tool call that found no free slot within the queue timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Busy {
+    /// Calls of the same kind allowed at once
+    pub limit: usize,
+    /// Whether the call counted against the expensive limit
+    pub expensive: bool,
+    /// How long the call waited before it was rejected
+    pub waited: Duration,
+}
+
+impl ToolThrottle {
+    /// Create a throttle with the given limits
+    pub fn new(limits: ConcurrencyLimits) -> Self {
+        Self {
+            cheap: Arc::new(Semaphore::new(limits.cheap)),
+            expensive: Arc::new(Semaphore::new(limits.expensive)),
+            limits,
+        }
+    }
+
+    /// Wait for a slot to run `tool_name`, up to the queue timeout
+    pub async fn acquire(&self, tool_name: &str) -> Result<ToolPermit, Busy> {
+        let expensive = ConcurrencyLimits::is_expensive(tool_name);
+        let (semaphore, limit) = if expensive {
+            (&self.expensive, self.limits.expensive)
+        } else {
+            (&self.cheap, self.limits.cheap)
+        };
+
+        let start = Instant::now();
+        let permit = if self.limits.queue_timeout.is_zero() {
+            Arc::clone(semaphore).try_acquire_owned().ok()
+        } else {
+            tokio::time::timeout(
+                self.limits.queue_timeout,
+                Arc::clone(semaphore).acquire_owned(),
+            )
+            .await
+            .ok()
+            .and_then(|permit| permit.ok())
+        };
+        let waited = start.elapsed();
+
+        match permit {
+            Some(permit) => Ok(ToolPermit {
+                _permit: permit,
+                waited,
+            }),
+            None => Err(Busy {
+                limit,
+                expensive,
+                waited,
+            }),
+        }
+    }
+
+    /// Calls of each kind currently running
+    pub fn running(&self) -> (usize, usize) {
+        (
+            self.limits.cheap - self.cheap.available_permits(),
+            self.limits.expensive - self.expensive.available_permits(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(queue_timeout: Duration) -> ConcurrencyLimits {
+        ConcurrencyLimits {
+            cheap: 2,
+            expensive: 1,
+            queue_timeout,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expensive_and_cheap_tools_have_separate_limits() {
+        let throttle = ToolThrottle::new(limits(Duration::ZERO));
+
+        let analysis = throttle.acquire("analyze_complexity").await.unwrap();
+        let busy = throttle.acquire("find_unused_code").await.unwrap_err();
+        assert_eq!(busy.limit, 1);
+        assert!(busy.expensive);
+
+        // Navigation still runs while the analysis slot is taken
+        let _first = throttle.acquire("search_symbols").await.unwrap();
+        let _second = throttle.acquire("find_references").await.unwrap();
+        assert_eq!(throttle.running(), (2, 1));
+        assert!(!throttle.acquire("trace_path").await.unwrap_err().expensive);
+
+        drop(analysis);
+        assert!(throttle.acquire("find_unused_code").await.is_ok());
+    }
+
+    #[test]
+    fn test_heavy_tools_count_against_the_expensive_limit() {
+        for tool in [
+            "analyze_security",
+            "analyze_logical_coupling",
+            "find_longest_call_chains",
+            "specialized_analysis",
+            "export_complexity_heatmap",
+            "tool_added_later",
+        ] {
+            assert!(ConcurrencyLimits::is_expensive(tool), "{tool}");
+        }
+        for tool in ["ping", "search_symbols", "find_files", "plan_index"] {
+            assert!(!ConcurrencyLimits::is_expensive(tool), "{tool}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calls_queue_until_a_slot_frees_up() {
+        let throttle = Arc::new(ToolThrottle::new(limits(Duration::from_secs(5))));
+        let running = throttle.acquire("analyze_complexity").await.unwrap();
+
+        let queued = {
+            let throttle = Arc::clone(&throttle);
+            tokio::spawn(async move { throttle.acquire("analyze_complexity").await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(running);
+
+        let permit = queued.await.unwrap().unwrap();
+        assert!(permit.waited >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_queued_call_is_rejected_after_the_timeout() {
+        let throttle = ToolThrottle::new(limits(Duration::from_millis(20)));
+        let _running = throttle.acquire("optimize_code").await.unwrap();
+
+        let busy = throttle.acquire("batch_process").await.unwrap_err();
+        assert!(busy.expensive);
+        assert!(busy.waited >= Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,323 @@
+//! Stdio transport with JSON-RPC batch support
+//!
+//! The rmcp service reads one JSON-RPC message per line and rejects arrays.
+//! This module sits between stdio and the service: batches are split into
+//! single messages before they reach the service, and the responses to the
+//! batch's requests are joined back into one array in request order.
+//! Notifications in a batch produce no response entry, and a batch made up
+//! only of notifications produces no output at all (JSON-RPC 2.0, section 6).
+
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::{debug, warn};
+
+/// JSON-RPC error code for a message that is not a valid request object
+pub const INVALID_REQUEST: i64 = -32600;
+
+fn invalid_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": { "code": INVALID_REQUEST, "message": "Invalid Request" }
+    })
+}
+
+/// Result of routing one incoming line
+#[derive(Debug, Default, PartialEq)]
+pub struct Inbound {
+    /// Single messages to pass to the service, one per line
+    pub forward: Vec<String>,
+    /// Response to write to the client right away
+    pub reply: Option<String>,
+}
+
+#[derive(Debug)]
+enum Slot {
+    Waiting(Value),
+    Done(Value),
+}
+
+/// Splits incoming batches and reassembles their responses
+#[derive(Debug, Default)]
+pub struct BatchRouter {
+    pending: Vec<Vec<Slot>>,
+}
+
+impl BatchRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route a line received from the client
+    pub fn inbound(&mut self, line: &str) -> Inbound {
+        let elements = match serde_json::from_str::<Value>(line) {
+            Ok(Value::Array(elements)) => elements,
+            // Single messages and unparseable input go to the service unchanged
+            _ => {
+                return Inbound {
+                    forward: vec![line.to_string()],
+                    reply: None,
+                }
+            }
+        };
+
+        if elements.is_empty() {
+            return Inbound {
+                forward: Vec::new(),
+                reply: Some(invalid_request().to_string()),
+            };
+        }
+
+        let mut inbound = Inbound::default();
+        let mut slots = Vec::new();
+        for element in elements {
+            let Some(message) = element.as_object() else {
+                slots.push(Slot::Done(invalid_request()));
+                continue;
+            };
+            if message.contains_key("method") {
+                if let Some(id) = message.get("id") {
+                    slots.push(Slot::Waiting(id.clone()));
+                }
+            }
+            inbound.forward.push(element.to_string());
+        }
+
+        if slots.iter().all(|slot| matches!(slot, Slot::Done(_))) {
+            if !slots.is_empty() {
+                inbound.reply = Some(Self::join(slots));
+            }
+        } else {
+            self.pending.push(slots);
+        }
+
+        inbound
+    }
+
+    /// Route a line written by the service
+    ///
+    /// Returns the line to send to the client, or `None` while the response
+    /// is held back for a batch that is still waiting on other requests.
+    pub fn outbound(&mut self, line: &str) -> Option<String> {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            return Some(line.to_string());
+        };
+        let is_response = message.get("method").is_none()
+            && (message.get("result").is_some() || message.get("error").is_some());
+        let Some(id) = message.get("id").filter(|_| is_response) else {
+            return Some(line.to_string());
+        };
+
+        let Some((batch_index, slot_index)) =
+            self.pending.iter().enumerate().find_map(|(b, slots)| {
+                slots
+                    .iter()
+                    .position(|slot| matches!(slot, Slot::Waiting(waiting) if waiting == id))
+                    .map(|s| (b, s))
+            })
+        else {
+            return Some(line.to_string());
+        };
+
+        let slots = &mut self.pending[batch_index];
+        slots[slot_index] = Slot::Done(message);
+        if slots.iter().any(|slot| matches!(slot, Slot::Waiting(_))) {
+            return None;
+        }
+
+        let slots = self.pending.remove(batch_index);
+        Some(Self::join(slots))
+    }
+
+    fn join(slots: Vec<Slot>) -> String {
+        let responses: Vec<Value> = slots
+            .into_iter()
+            .filter_map(|slot| match slot {
+                Slot::Done(response) => Some(response),
+                Slot::Waiting(_) => None,
+            })
+            .collect();
+        Value::Array(responses).to_string()
+    }
+}
+
+/// Put batch handling in front of a line-delimited JSON-RPC transport
+///
+/// Returns the reader and writer to hand to the rmcp service. Messages are
+/// copied between `input`/`output` and the service by background tasks.
+pub fn batching<R, W>(
+    input: R,
+    output: W,
+) -> (
+    tokio::io::ReadHalf<tokio::io::DuplexStream>,
+    tokio::io::WriteHalf<tokio::io::DuplexStream>,
+)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let (service_side, proxy_side) = tokio::io::duplex(64 * 1024);
+    let (from_service, mut to_service) = tokio::io::split(proxy_side);
+    let router = Arc::new(Mutex::new(BatchRouter::new()));
+    let output = Arc::new(tokio::sync::Mutex::new(output));
+
+    let inbound_router = Arc::clone(&router);
+    let inbound_output = Arc::clone(&output);
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(input).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let routed = inbound_router.lock().unwrap().inbound(&line);
+            if let Some(reply) = routed.reply {
+                debug!("Answering batch without forwarding it");
+                if write_line(&mut *inbound_output.lock().await, &reply)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            for message in routed.forward {
+                if write_line(&mut to_service, &message).await.is_err() {
+                    return;
+                }
+            }
+        }
+        // Closing the service input lets it shut down once stdin ends
+        let _ = to_service.shutdown().await;
+    });
+
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(from_service).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let routed = router.lock().unwrap().outbound(&line);
+            if let Some(line) = routed {
+                if let Err(e) = write_line(&mut *output.lock().await, &line).await {
+                    warn!("Failed to write response: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    tokio::io::split(service_side)
+}
+
+/// Stdio transport with JSON-RPC batch support
+pub fn batching_stdio() -> (
+    tokio::io::ReadHalf<tokio::io::DuplexStream>,
+    tokio::io::WriteHalf<tokio::io::DuplexStream>,
+) {
+    batching(tokio::io::stdin(), tokio::io::stdout())
+}
+
+async fn write_line<W: AsyncWrite + Unpin>(writer: &mut W, line: &str) -> std::io::Result<()> {
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_batch_responses_joined_in_request_order() {
+        let mut router = BatchRouter::new();
+        let inbound = router.inbound(
+            r#"[{"jsonrpc":"2.0","id":1,"method":"tools/list"},{"jsonrpc":"2.0","method":"notifications/initialized"},{"jsonrpc":"2.0","id":"b","method":"ping"}]"#,
+        );
+        assert_eq!(
+            inbound.forward.len(),
+            3,
+            "Every element reaches the service"
+        );
+        assert_eq!(inbound.reply, None);
+
+        // Responses arrive out of order; the first is held back
+        assert_eq!(
+            router.outbound(r#"{"jsonrpc":"2.0","id":"b","result":{}}"#),
+            None
+        );
+        let joined = router
+            .outbound(r#"{"jsonrpc":"2.0","id":1,"result":{"tools":[]}}"#)
+            .unwrap();
+        let responses: Vec<Value> = serde_json::from_str(&joined).unwrap();
+        assert_eq!(responses.len(), 2, "Notification gets no response");
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[1]["id"], "b");
+
+        // Unrelated messages pass through untouched
+        let single = r#"{"jsonrpc":"2.0","id":7,"result":{}}"#;
+        assert_eq!(router.outbound(single).as_deref(), Some(single));
+    }
+
+    #[test]
+    fn test_empty_and_invalid_batches() {
+        let mut router = BatchRouter::new();
+
+        let empty = router.inbound("[]");
+        assert!(empty.forward.is_empty());
+        let reply: Value = serde_json::from_str(&empty.reply.unwrap()).unwrap();
+        assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+        assert!(reply["id"].is_null());
+
+        let invalid = router.inbound("[1, 2]");
+        let replies: Vec<Value> = serde_json::from_str(&invalid.reply.unwrap()).unwrap();
+        assert_eq!(replies.len(), 2);
+        assert!(replies
+            .iter()
+            .all(|r| r["error"]["code"] == INVALID_REQUEST));
+
+        let notifications =
+            router.inbound(r#"[{"jsonrpc":"2.0","method":"notifications/initialized"}]"#);
+        assert_eq!(notifications.forward.len(), 1);
+        assert_eq!(
+            notifications.reply, None,
+            "Notification-only batch has no response"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batching_transport_round_trip() {
+        let (client, server_io) = tokio::io::duplex(4096);
+        let (server_in, server_out) = tokio::io::split(server_io);
+        let (service_in, mut service_out) = batching(server_in, server_out);
+
+        // Fake service answering every request with its method name
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(service_in).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let request: Value = serde_json::from_str(&line).unwrap();
+                if let Some(id) = request.get("id") {
+                    let response = json!({"jsonrpc": "2.0", "id": id, "result": request["method"]});
+                    write_line(&mut service_out, &response.to_string())
+                        .await
+                        .unwrap();
+                }
+            }
+        });
+
+        let (client_in, mut client_out) = tokio::io::split(client);
+        write_line(
+            &mut client_out,
+            r#"[{"jsonrpc":"2.0","id":1,"method":"a"},{"jsonrpc":"2.0","method":"n"},{"jsonrpc":"2.0","id":2,"method":"b"}]"#,
+        )
+        .await
+        .unwrap();
+
+        let mut lines = BufReader::new(client_in).lines();
+        let line = lines.next_line().await.unwrap().unwrap();
+        let responses: Vec<Value> = serde_json::from_str(&line).unwrap();
+        assert_eq!(
+            responses,
+            vec![
+                json!({"jsonrpc": "2.0", "id": 1, "result": "a"}),
+                json!({"jsonrpc": "2.0", "id": 2, "result": "b"}),
+            ]
+        );
+    }
+}
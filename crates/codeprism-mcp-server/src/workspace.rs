@@ -0,0 +1,210 @@
+//! Multi-repository workspace files
+//!
+//! A workspace file lists the repositories one server session works on. Each
+//! entry is indexed into the shared graph at startup and can be targeted by
+//! its id in tool calls. When the file is watched, editing it adds or drops
+//! repositories without restarting the server.
+//!
+//! ```yaml
+//! repositories:
+//!   - id: backend
+//!     path: ../backend
+//!     exclude_dirs: [migrations]
+//!   - id: web
+//!     path: ../web
+//!     include_extensions: [ts, tsx]
+//! ```
+
+use crate::{Error, Result};
+use codeprism_core::RepositoryScanner;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// One repository entry of a workspace file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceRepository {
+    /// Identifier used to scope tool calls to this repository
+    pub id: String,
+    /// Repository root; relative paths are resolved against the workspace file
+    pub path: PathBuf,
+    /// File extensions to index instead of the scanner defaults
+    #[serde(default)]
+    pub include_extensions: Option<Vec<String>>,
+    /// Directories to skip on top of the scanner defaults
+    #[serde(default)]
+    pub exclude_dirs: Vec<String>,
+}
+
+impl WorkspaceRepository {
+    /// Scanner applying this repository's include/exclude settings
+    pub fn scanner(&self) -> RepositoryScanner {
+        let mut scanner = RepositoryScanner::new();
+        if let Some(extensions) = &self.include_extensions {
+            scanner = scanner.with_extensions(
+                extensions
+                    .iter()
+                    .map(|ext| ext.trim_start_matches('.').to_string())
+                    .collect(),
+            );
+        }
+        scanner.add_exclude_dirs(self.exclude_dirs.clone());
+        scanner
+    }
+
+    /// Whether `file` lies inside this repository
+    pub fn contains(&self, file: &Path) -> bool {
+        file.starts_with(&self.path)
+    }
+}
+
+/// Repositories listed in a workspace file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    #[serde(default)]
+    pub repositories: Vec<WorkspaceRepository>,
+}
+
+impl WorkspaceConfig {
+    /// Load a workspace file in TOML, YAML or JSON format
+    pub async fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = tokio::fs::read_to_string(path).await?;
+        let extension = path.extension().and_then(|s| s.to_str());
+
+        let mut workspace = Self::parse(&content, extension)?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        workspace.resolve_paths(base);
+        workspace.validate()?;
+        Ok(workspace)
+    }
+
+    /// Parse workspace content, detecting the format when the extension is unknown
+    pub fn parse(content: &str, extension: Option<&str>) -> Result<Self> {
+        let workspace = match extension {
+            Some("toml") => toml::from_str(content)?,
+            Some("yaml") | Some("yml") => serde_yaml::from_str(content)?,
+            Some("json") => serde_json::from_str(content)?,
+            _ => toml::from_str(content)
+                .or_else(|_| serde_yaml::from_str(content))
+                .or_else(|_| serde_json::from_str(content))?,
+        };
+        Ok(workspace)
+    }
+
+    /// Make repository paths absolute, relative to `base`
+    pub fn resolve_paths(&mut self, base: &Path) {
+        for repository in &mut self.repositories {
+            let joined = base.join(&repository.path);
+            repository.path = joined.canonicalize().unwrap_or(joined);
+        }
+    }
+
+    /// Check that every repository has a unique, non-empty id
+    pub fn validate(&self) -> Result<()> {
+        let mut seen = HashSet::new();
+        for repository in &self.repositories {
+            if repository.id.trim().is_empty() {
+                return Err(Error::server_init(format!(
+                    "Workspace repository at {} has an empty id",
+                    repository.path.display()
+                )));
+            }
+            if !seen.insert(repository.id.as_str()) {
+                return Err(Error::server_init(format!(
+                    "Duplicate workspace repository id: {}",
+                    repository.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Look up a repository by id
+    pub fn repository(&self, id: &str) -> Option<&WorkspaceRepository> {
+        self.repositories.iter().find(|repo| repo.id == id)
+    }
+
+    /// Repositories to index and drop when moving from `previous` to this workspace
+    ///
+    /// An entry whose settings changed is dropped and indexed again.
+    pub fn changes_since(&self, previous: &WorkspaceConfig) -> WorkspaceChanges {
+        WorkspaceChanges {
+            added: self
+                .repositories
+                .iter()
+                .filter(|repo| !previous.repositories.contains(repo))
+                .cloned()
+                .collect(),
+            removed: previous
+                .repositories
+                .iter()
+                .filter(|repo| !self.repositories.contains(repo))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Difference between two versions of a workspace
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceChanges {
+    pub added: Vec<WorkspaceRepository>,
+    pub removed: Vec<WorkspaceRepository>,
+}
+
+impl WorkspaceChanges {
+    /// Whether the workspace is unchanged
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_resolves_paths_and_diffs_changes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("api")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("web")).unwrap();
+        let workspace_file = temp_dir.path().join("workspace.yaml");
+        std::fs::write(
+            &workspace_file,
+            "repositories:\n  - id: api\n    path: api\n    exclude_dirs: [generated]\n  - id: web\n    path: web\n    include_extensions: [ts]\n",
+        )
+        .unwrap();
+
+        let workspace = WorkspaceConfig::from_file(&workspace_file).await.unwrap();
+        assert_eq!(workspace.repositories.len(), 2);
+        let api = workspace.repository("api").unwrap();
+        assert!(api.path.is_absolute());
+        assert!(api.contains(&api.path.join("src/main.py")));
+        assert!(!api.contains(&workspace.repository("web").unwrap().path));
+
+        let mut edited = workspace.clone();
+        edited.repositories.remove(0);
+        edited.repositories[0]
+            .exclude_dirs
+            .push("vendor".to_string());
+        let changes = edited.changes_since(&workspace);
+        assert_eq!(
+            changes.removed.len(),
+            2,
+            "Changed settings count as removal"
+        );
+        assert_eq!(changes.added.len(), 1);
+        assert!(workspace.changes_since(&workspace).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_ids_rejected() {
+        let workspace = WorkspaceConfig::parse(
+            r#"{"repositories": [{"id": "a", "path": "x"}, {"id": "a", "path": "y"}]}"#,
+            Some("json"),
+        )
+        .unwrap();
+        assert!(workspace.validate().is_err());
+    }
+}
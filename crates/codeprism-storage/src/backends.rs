@@ -2,10 +2,12 @@
 
 use crate::{
     AnalysisResult, AnalysisStorage, EdgeReference, GraphMetadata, GraphStorage, SerializableEdge,
-    SerializableGraph, SerializableNode,
+    SerializableGraph, SerializableNode, WriteRetryConfig,
 };
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use codeprism_core::resilience::RetryExecutor;
+use codeprism_core::RetryConfig;
 use rusqlite::{params, Connection};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -135,9 +137,41 @@ impl GraphStorage for InMemoryGraphStorage {
     }
 }
 
+/// Writes files for [`FileGraphStorage`]
+#[async_trait]
+pub(crate) trait FileWriter: Send + Sync {
+    async fn write(&self, path: &Path, contents: &[u8]) -> std::io::Result<()>;
+}
+
+/// Writes files directly to disk
+struct DiskFileWriter;
+
+#[async_trait]
+impl FileWriter for DiskFileWriter {
+    async fn write(&self, path: &Path, contents: &[u8]) -> std::io::Result<()> {
+        fs::write(path, contents).await
+    }
+}
+
+/// Whether an I/O error may succeed when the operation is repeated
+fn is_transient_io_error(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        std::io::ErrorKind::Interrupted
+            | std::io::ErrorKind::WouldBlock
+            | std::io::ErrorKind::TimedOut
+            | std::io::ErrorKind::ResourceBusy
+    )
+}
+
 /// File-based graph storage implementation
+///
+/// Writes that fail with transient I/O errors are retried with exponential
+/// backoff, see [`crate::WriteRetryConfig`].
 pub struct FileGraphStorage {
     data_path: PathBuf,
+    retry: RetryExecutor,
+    writer: Arc<dyn FileWriter>,
 }
 
 impl FileGraphStorage {
@@ -145,6 +179,8 @@ impl FileGraphStorage {
     pub async fn new(data_path: &Path) -> Result<Self> {
         let storage = Self {
             data_path: data_path.to_path_buf(),
+            retry: RetryExecutor::new(WriteRetryConfig::default().to_retry_config()),
+            writer: Arc::new(DiskFileWriter),
         };
 
         // Ensure the data directory exists
@@ -155,6 +191,38 @@ impl FileGraphStorage {
         Ok(storage)
     }
 
+    /// Set how writes failing with transient I/O errors are retried
+    pub fn with_retry_config(mut self, config: RetryConfig) -> Self {
+        self.retry = RetryExecutor::new(config);
+        self
+    }
+
+    /// Replace the writer used for graph and metadata files
+    #[cfg(test)]
+    pub(crate) fn with_writer(mut self, writer: Arc<dyn FileWriter>) -> Self {
+        self.writer = writer;
+        self
+    }
+
+    /// Write a file, retrying transient I/O errors
+    async fn write_file(&self, path: &Path, contents: &str) -> Result<()> {
+        self.retry
+            .execute(|| async {
+                self.writer
+                    .write(path, contents.as_bytes())
+                    .await
+                    .map_err(|e| {
+                        if is_transient_io_error(&e) {
+                            codeprism_core::Error::Io(e)
+                        } else {
+                            codeprism_core::Error::other(e.to_string())
+                        }
+                    })
+            })
+            .await
+            .map_err(anyhow::Error::from)
+    }
+
     /// Get the file path for a repository's graph
     fn graph_file_path(&self, repo_id: &str) -> PathBuf {
         self.data_path.join(format!("{repo_id}.graph.json"))
@@ -175,14 +243,14 @@ impl GraphStorage for FileGraphStorage {
         // Serialize and write graph
         let graph_json =
             serde_json::to_string_pretty(graph).context("Failed to serialize graph")?;
-        fs::write(&graph_path, graph_json)
+        self.write_file(&graph_path, &graph_json)
             .await
             .with_context(|| format!("Failed to write graph to {graph_path:?}"))?;
 
         // Serialize and write metadata separately for efficiency
         let metadata_json = serde_json::to_string_pretty(&graph.metadata)
             .context("Failed to serialize metadata")?;
-        fs::write(&metadata_path, metadata_json)
+        self.write_file(&metadata_path, &metadata_json)
             .await
             .with_context(|| format!("Failed to write metadata to {metadata_path:?}"))?;
 
@@ -302,7 +370,7 @@ impl GraphStorage for FileGraphStorage {
 
         let metadata_json =
             serde_json::to_string_pretty(metadata).context("Failed to serialize metadata")?;
-        fs::write(&metadata_path, metadata_json)
+        self.write_file(&metadata_path, &metadata_json)
             .await
             .with_context(|| format!("Failed to write metadata to {metadata_path:?}"))?;
 
@@ -730,6 +798,313 @@ impl GraphStorage for SqliteGraphStorage {
     }
 }
 
+/// RocksDB-based graph storage implementation
+///
+/// Nodes, edges and metadata live in their own column families under keys
+/// prefixed with `<repo_id>\0`, so one repository's entries are contiguous
+/// and can be scanned or range-deleted without touching the others.
+#[cfg(feature = "rocksdb")]
+pub struct RocksDbGraphStorage {
+    db: rocksdb::DB,
+}
+
+#[cfg(feature = "rocksdb")]
+impl RocksDbGraphStorage {
+    const GRAPHS_CF: &'static str = "graphs";
+    const NODES_CF: &'static str = "nodes";
+    const EDGES_CF: &'static str = "edges";
+    const METADATA_CF: &'static str = "metadata";
+
+    /// Create a new RocksDB-based graph storage
+    pub async fn new(data_path: &Path) -> Result<Self> {
+        fs::create_dir_all(data_path)
+            .await
+            .context("Failed to create data directory")?;
+
+        let db_path = data_path.join("codeprism.rocksdb");
+        let mut options = rocksdb::Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = rocksdb::DB::open_cf(
+            &options,
+            &db_path,
+            [
+                Self::GRAPHS_CF,
+                Self::NODES_CF,
+                Self::EDGES_CF,
+                Self::METADATA_CF,
+            ],
+        )
+        .with_context(|| format!("Failed to open RocksDB database at {db_path:?}"))?;
+
+        Ok(Self { db })
+    }
+
+    fn cf(&self, name: &str) -> Result<&rocksdb::ColumnFamily> {
+        self.db
+            .cf_handle(name)
+            .with_context(|| format!("Missing RocksDB column family '{name}'"))
+    }
+
+    /// Key prefix shared by all entries of a repository
+    fn repo_prefix(repo_id: &str) -> Vec<u8> {
+        let mut prefix = repo_id.as_bytes().to_vec();
+        prefix.push(0);
+        prefix
+    }
+
+    /// Exclusive upper bound of the keys starting with `repo_prefix(repo_id)`
+    fn repo_prefix_end(repo_id: &str) -> Vec<u8> {
+        let mut end = repo_id.as_bytes().to_vec();
+        end.push(1);
+        end
+    }
+
+    fn node_key(repo_id: &str, node_id: &str) -> Vec<u8> {
+        let mut key = Self::repo_prefix(repo_id);
+        key.extend_from_slice(node_id.as_bytes());
+        key
+    }
+
+    fn edge_key(repo_id: &str, source: &str, target: &str, kind: &str) -> Vec<u8> {
+        let mut key = Self::repo_prefix(repo_id);
+        key.extend_from_slice(source.as_bytes());
+        key.push(0);
+        key.extend_from_slice(target.as_bytes());
+        key.push(0);
+        key.extend_from_slice(kind.as_bytes());
+        key
+    }
+
+    /// Values of all entries of a repository in a column family
+    fn scan_repo(&self, cf_name: &str, repo_id: &str) -> Result<Vec<Vec<u8>>> {
+        let prefix = Self::repo_prefix(repo_id);
+        let iter = self.db.iterator_cf(
+            self.cf(cf_name)?,
+            rocksdb::IteratorMode::From(prefix.as_slice(), rocksdb::Direction::Forward),
+        );
+
+        let mut values = Vec::new();
+        for entry in iter {
+            let (key, value) = entry.context("Failed to iterate RocksDB entries")?;
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            values.push(value.into_vec());
+        }
+        Ok(values)
+    }
+
+    /// Queue deletion of all nodes and edges of a repository
+    fn delete_repo_entries(&self, batch: &mut rocksdb::WriteBatch, repo_id: &str) -> Result<()> {
+        let start = Self::repo_prefix(repo_id);
+        let end = Self::repo_prefix_end(repo_id);
+        batch.delete_range_cf(self.cf(Self::NODES_CF)?, &start, &end);
+        batch.delete_range_cf(self.cf(Self::EDGES_CF)?, &start, &end);
+        Ok(())
+    }
+
+    fn put_node(
+        &self,
+        batch: &mut rocksdb::WriteBatch,
+        repo_id: &str,
+        node: &SerializableNode,
+    ) -> Result<()> {
+        let node_data = bincode::serialize(node).context("Failed to serialize node")?;
+        batch.put_cf(
+            self.cf(Self::NODES_CF)?,
+            Self::node_key(repo_id, &node.id),
+            node_data,
+        );
+        Ok(())
+    }
+
+    fn put_edge(
+        &self,
+        batch: &mut rocksdb::WriteBatch,
+        repo_id: &str,
+        edge: &SerializableEdge,
+    ) -> Result<()> {
+        let edge_data = bincode::serialize(edge).context("Failed to serialize edge")?;
+        batch.put_cf(
+            self.cf(Self::EDGES_CF)?,
+            Self::edge_key(repo_id, &edge.source, &edge.target, &edge.kind),
+            edge_data,
+        );
+        Ok(())
+    }
+
+    fn write(&self, batch: rocksdb::WriteBatch) -> Result<()> {
+        self.db
+            .write(batch)
+            .context("Failed to write RocksDB batch")
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+#[async_trait]
+impl GraphStorage for RocksDbGraphStorage {
+    async fn store_graph(&self, graph: &SerializableGraph) -> Result<()> {
+        let repo_id = &graph.repo_id;
+        let mut batch = rocksdb::WriteBatch::default();
+
+        // Replace whatever was stored for the repository before
+        self.delete_repo_entries(&mut batch, repo_id)?;
+
+        batch.put_cf(self.cf(Self::GRAPHS_CF)?, repo_id.as_bytes(), b"");
+        let metadata_data =
+            bincode::serialize(&graph.metadata).context("Failed to serialize metadata")?;
+        batch.put_cf(
+            self.cf(Self::METADATA_CF)?,
+            repo_id.as_bytes(),
+            metadata_data,
+        );
+
+        for node in &graph.nodes {
+            self.put_node(&mut batch, repo_id, node)?;
+        }
+        for edge in &graph.edges {
+            self.put_edge(&mut batch, repo_id, edge)?;
+        }
+
+        self.write(batch)
+    }
+
+    async fn load_graph(&self, repo_id: &str) -> Result<Option<SerializableGraph>> {
+        if !self.graph_exists(repo_id).await? {
+            return Ok(None);
+        }
+        let Some(metadata) = self.get_graph_metadata(repo_id).await? else {
+            return Ok(None);
+        };
+
+        let nodes = self
+            .scan_repo(Self::NODES_CF, repo_id)?
+            .iter()
+            .map(|data| bincode::deserialize(data).context("Failed to deserialize node"))
+            .collect::<Result<Vec<SerializableNode>>>()?;
+        let edges = self
+            .scan_repo(Self::EDGES_CF, repo_id)?
+            .iter()
+            .map(|data| bincode::deserialize(data).context("Failed to deserialize edge"))
+            .collect::<Result<Vec<SerializableEdge>>>()?;
+
+        Ok(Some(SerializableGraph {
+            repo_id: repo_id.to_string(),
+            nodes,
+            edges,
+            metadata,
+        }))
+    }
+
+    async fn update_nodes(&self, repo_id: &str, nodes: &[SerializableNode]) -> Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        for node in nodes {
+            self.put_node(&mut batch, repo_id, node)?;
+        }
+        self.write(batch)
+    }
+
+    async fn update_edges(&self, repo_id: &str, edges: &[SerializableEdge]) -> Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        for edge in edges {
+            self.put_edge(&mut batch, repo_id, edge)?;
+        }
+        self.write(batch)
+    }
+
+    async fn delete_nodes(&self, repo_id: &str, node_ids: &[String]) -> Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        let nodes_cf = self.cf(Self::NODES_CF)?;
+        for node_id in node_ids {
+            batch.delete_cf(nodes_cf, Self::node_key(repo_id, node_id));
+        }
+
+        // Delete edges that reference the removed nodes
+        let edges_cf = self.cf(Self::EDGES_CF)?;
+        for data in self.scan_repo(Self::EDGES_CF, repo_id)? {
+            let edge: SerializableEdge =
+                bincode::deserialize(&data).context("Failed to deserialize edge")?;
+            if node_ids.contains(&edge.source) || node_ids.contains(&edge.target) {
+                batch.delete_cf(
+                    edges_cf,
+                    Self::edge_key(repo_id, &edge.source, &edge.target, &edge.kind),
+                );
+            }
+        }
+
+        self.write(batch)
+    }
+
+    async fn delete_edges(&self, repo_id: &str, edge_refs: &[EdgeReference]) -> Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        let edges_cf = self.cf(Self::EDGES_CF)?;
+        for edge_ref in edge_refs {
+            batch.delete_cf(
+                edges_cf,
+                Self::edge_key(repo_id, &edge_ref.source, &edge_ref.target, &edge_ref.kind),
+            );
+        }
+        self.write(batch)
+    }
+
+    async fn get_graph_metadata(&self, repo_id: &str) -> Result<Option<GraphMetadata>> {
+        let metadata_data = self
+            .db
+            .get_cf(self.cf(Self::METADATA_CF)?, repo_id.as_bytes())
+            .context("Failed to read metadata from RocksDB")?;
+
+        match metadata_data {
+            Some(data) => {
+                let metadata: GraphMetadata =
+                    bincode::deserialize(&data).context("Failed to deserialize metadata")?;
+                Ok(Some(metadata))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn update_graph_metadata(&self, repo_id: &str, metadata: &GraphMetadata) -> Result<()> {
+        let metadata_data = bincode::serialize(metadata).context("Failed to serialize metadata")?;
+        self.db
+            .put_cf(
+                self.cf(Self::METADATA_CF)?,
+                repo_id.as_bytes(),
+                metadata_data,
+            )
+            .context("Failed to write metadata to RocksDB")
+    }
+
+    async fn list_repositories(&self) -> Result<Vec<String>> {
+        let mut repos = Vec::new();
+        for entry in self
+            .db
+            .iterator_cf(self.cf(Self::GRAPHS_CF)?, rocksdb::IteratorMode::Start)
+        {
+            let (key, _) = entry.context("Failed to iterate RocksDB entries")?;
+            repos.push(String::from_utf8_lossy(&key).into_owned());
+        }
+        Ok(repos)
+    }
+
+    async fn delete_graph(&self, repo_id: &str) -> Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        self.delete_repo_entries(&mut batch, repo_id)?;
+        batch.delete_cf(self.cf(Self::GRAPHS_CF)?, repo_id.as_bytes());
+        batch.delete_cf(self.cf(Self::METADATA_CF)?, repo_id.as_bytes());
+        self.write(batch)
+    }
+
+    async fn graph_exists(&self, repo_id: &str) -> Result<bool> {
+        let marker = self
+            .db
+            .get_pinned_cf(self.cf(Self::GRAPHS_CF)?, repo_id.as_bytes())
+            .context("Failed to read graph marker from RocksDB")?;
+        Ok(marker.is_some())
+    }
+}
+
 /// In-memory analysis storage
 pub struct InMemoryAnalysisStorage {
     results: Arc<Mutex<HashMap<String, AnalysisResult>>>,
@@ -806,40 +1181,353 @@ impl AnalysisStorage for InMemoryAnalysisStorage {
 }
 
 /// File analysis storage implementation
-pub struct FileAnalysisStorage;
+///
+/// Each result is stored as a JSON file under `<data_path>/analysis`.
+pub struct FileAnalysisStorage {
+    analysis_path: PathBuf,
+}
 
 impl FileAnalysisStorage {
     /// Create a new file-based analysis storage
-    pub async fn new(_data_path: &Path) -> Result<Self> {
-        Ok(Self)
+    pub async fn new(data_path: &Path) -> Result<Self> {
+        let storage = Self {
+            analysis_path: data_path.join("analysis"),
+        };
+
+        fs::create_dir_all(&storage.analysis_path)
+            .await
+            .context("Failed to create analysis directory")?;
+
+        Ok(storage)
+    }
+
+    /// Get the file path for an analysis result
+    fn result_file_path(&self, result_id: &str) -> PathBuf {
+        let file_name: String = result_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.analysis_path
+            .join(format!("{file_name}.analysis.json"))
+    }
+
+    /// Load every stored analysis result
+    async fn load_all(&self) -> Result<Vec<AnalysisResult>> {
+        let mut results = Vec::new();
+        let mut entries = fs::read_dir(&self.analysis_path)
+            .await
+            .context("Failed to read analysis directory")?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if !path.to_string_lossy().ends_with(".analysis.json") {
+                continue;
+            }
+            let json = fs::read_to_string(&path)
+                .await
+                .with_context(|| format!("Failed to read analysis result from {path:?}"))?;
+            match serde_json::from_str::<AnalysisResult>(&json) {
+                Ok(result) => results.push(result),
+                Err(e) => tracing::warn!("Skipping unreadable analysis result {:?}: {}", path, e),
+            }
+        }
+
+        Ok(results)
     }
 }
 
 #[async_trait]
 impl AnalysisStorage for FileAnalysisStorage {
-    async fn store_analysis(&self, _result: &AnalysisResult) -> Result<()> {
-        anyhow::bail!("Not implemented")
+    async fn store_analysis(&self, result: &AnalysisResult) -> Result<()> {
+        let path = self.result_file_path(&result.id);
+        let json =
+            serde_json::to_string_pretty(result).context("Failed to serialize analysis result")?;
+        fs::write(&path, json)
+            .await
+            .with_context(|| format!("Failed to write analysis result to {path:?}"))?;
+        Ok(())
     }
 
-    async fn load_analysis(&self, _result_id: &str) -> Result<Option<AnalysisResult>> {
-        anyhow::bail!("Not implemented")
+    async fn load_analysis(&self, result_id: &str) -> Result<Option<AnalysisResult>> {
+        let path = self.result_file_path(result_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let json = fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read analysis result from {path:?}"))?;
+        let result: AnalysisResult = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to deserialize analysis result from {path:?}"))?;
+        // Distinct ids can share a file name once sanitized
+        Ok((result.id == result_id).then_some(result))
     }
 
     async fn find_analysis(
         &self,
-        _repo_id: &str,
-        _analysis_type: Option<&str>,
-        _since: Option<SystemTime>,
+        repo_id: &str,
+        analysis_type: Option<&str>,
+        since: Option<SystemTime>,
     ) -> Result<Vec<AnalysisResult>> {
-        anyhow::bail!("Not implemented")
+        Ok(self
+            .load_all()
+            .await?
+            .into_iter()
+            .filter(|r| {
+                r.repo_id == repo_id
+                    && analysis_type.is_none_or(|t| r.analysis_type == t)
+                    && since.is_none_or(|s| r.timestamp >= s)
+            })
+            .collect())
     }
 
-    async fn delete_analysis(&self, _result_id: &str) -> Result<()> {
-        anyhow::bail!("Not implemented")
+    async fn delete_analysis(&self, result_id: &str) -> Result<()> {
+        let path = self.result_file_path(result_id);
+        if path.exists() {
+            fs::remove_file(&path)
+                .await
+                .with_context(|| format!("Failed to delete analysis result {path:?}"))?;
+        }
+        Ok(())
     }
 
-    async fn cleanup_old_results(&self, _older_than: SystemTime) -> Result<usize> {
-        anyhow::bail!("Not implemented")
+    async fn cleanup_old_results(&self, older_than: SystemTime) -> Result<usize> {
+        let mut count = 0;
+        for result in self.load_all().await? {
+            if result.timestamp < older_than {
+                self.delete_analysis(&result.id).await?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+/// PostgreSQL analysis storage implementation
+///
+/// Results live in one `analysis_results` table with the `data` payload in a
+/// `jsonb` column. Indexes on `(repo_id, analysis_type, timestamp)`,
+/// `(repo_id, timestamp)` and `timestamp` serve `find_analysis` with or
+/// without a type and `since` filter, and the ranged `DELETE` of
+/// `cleanup_old_results`. Queries are checked at compile time against the
+/// metadata in `.sqlx`; regenerate it with `cargo sqlx prepare` after
+/// changing them.
+#[cfg(feature = "postgres")]
+pub struct PostgresAnalysisStorage {
+    pool: sqlx::PgPool,
+}
+
+#[cfg(feature = "postgres")]
+impl PostgresAnalysisStorage {
+    const SCHEMA: &'static str = r#"
+        CREATE TABLE IF NOT EXISTS analysis_results (
+            id TEXT PRIMARY KEY,
+            repo_id TEXT NOT NULL,
+            analysis_type TEXT NOT NULL,
+            timestamp TIMESTAMPTZ NOT NULL,
+            data JSONB NOT NULL,
+            metadata JSONB NOT NULL DEFAULT '{}'
+        );
+        CREATE INDEX IF NOT EXISTS analysis_results_repo_type_time
+            ON analysis_results (repo_id, analysis_type, timestamp);
+        CREATE INDEX IF NOT EXISTS analysis_results_repo_time
+            ON analysis_results (repo_id, timestamp);
+        CREATE INDEX IF NOT EXISTS analysis_results_time
+            ON analysis_results (timestamp);
+    "#;
+
+    /// Connect to the database at `url` and create the schema if needed
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(8)
+            .connect(url)
+            .await
+            .context("Failed to connect to PostgreSQL")?;
+        Self::from_pool(pool).await
+    }
+
+    /// Use an existing connection pool, creating the schema if needed
+    pub async fn from_pool(pool: sqlx::PgPool) -> Result<Self> {
+        sqlx::Executor::execute(&pool, Self::SCHEMA)
+            .await
+            .context("Failed to create analysis_results schema")?;
+        Ok(Self { pool })
+    }
+
+    fn to_result(
+        id: String,
+        repo_id: String,
+        analysis_type: String,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        data: serde_json::Value,
+        metadata: serde_json::Value,
+    ) -> Result<AnalysisResult> {
+        Ok(AnalysisResult {
+            metadata: serde_json::from_value(metadata)
+                .with_context(|| format!("Invalid metadata for analysis result {id}"))?,
+            id,
+            repo_id,
+            analysis_type,
+            timestamp: timestamp.into(),
+            data,
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+#[async_trait]
+impl AnalysisStorage for PostgresAnalysisStorage {
+    async fn store_analysis(&self, result: &AnalysisResult) -> Result<()> {
+        let timestamp: chrono::DateTime<chrono::Utc> = result.timestamp.into();
+        let metadata = serde_json::to_value(&result.metadata)?;
+        sqlx::query!(
+            r#"
+            INSERT INTO analysis_results (id, repo_id, analysis_type, timestamp, data, metadata)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (id) DO UPDATE SET
+                repo_id = EXCLUDED.repo_id,
+                analysis_type = EXCLUDED.analysis_type,
+                timestamp = EXCLUDED.timestamp,
+                data = EXCLUDED.data,
+                metadata = EXCLUDED.metadata
+            "#,
+            result.id,
+            result.repo_id,
+            result.analysis_type,
+            timestamp,
+            result.data,
+            metadata
+        )
+        .execute(&self.pool)
+        .await
+        .with_context(|| format!("Failed to store analysis result {}", result.id))?;
+        Ok(())
+    }
+
+    async fn load_analysis(&self, result_id: &str) -> Result<Option<AnalysisResult>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, repo_id, analysis_type, timestamp, data, metadata
+            FROM analysis_results
+            WHERE id = $1
+            "#,
+            result_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .with_context(|| format!("Failed to load analysis result {result_id}"))?;
+
+        row.map(|row| {
+            Self::to_result(
+                row.id,
+                row.repo_id,
+                row.analysis_type,
+                row.timestamp,
+                row.data,
+                row.metadata,
+            )
+        })
+        .transpose()
+    }
+
+    async fn find_analysis(
+        &self,
+        repo_id: &str,
+        analysis_type: Option<&str>,
+        since: Option<SystemTime>,
+    ) -> Result<Vec<AnalysisResult>> {
+        let since = since.map(chrono::DateTime::<chrono::Utc>::from);
+        // Separate statements keep each filter a plain index range condition
+        let rows = match analysis_type {
+            Some(analysis_type) => sqlx::query!(
+                r#"
+                SELECT id, repo_id, analysis_type, timestamp, data, metadata
+                FROM analysis_results
+                WHERE repo_id = $1
+                    AND analysis_type = $2
+                    AND timestamp >= COALESCE($3, '-infinity'::timestamptz)
+                ORDER BY timestamp
+                "#,
+                repo_id,
+                analysis_type,
+                since
+            )
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Self::to_result(
+                    row.id,
+                    row.repo_id,
+                    row.analysis_type,
+                    row.timestamp,
+                    row.data,
+                    row.metadata,
+                )
+            })
+            .collect::<Result<Vec<_>>>(),
+            None => sqlx::query!(
+                r#"
+                SELECT id, repo_id, analysis_type, timestamp, data, metadata
+                FROM analysis_results
+                WHERE repo_id = $1
+                    AND timestamp >= COALESCE($2, '-infinity'::timestamptz)
+                ORDER BY timestamp
+                "#,
+                repo_id,
+                since
+            )
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Self::to_result(
+                    row.id,
+                    row.repo_id,
+                    row.analysis_type,
+                    row.timestamp,
+                    row.data,
+                    row.metadata,
+                )
+            })
+            .collect::<Result<Vec<_>>>(),
+        };
+        rows.with_context(|| format!("Failed to find analysis results for {repo_id}"))
+    }
+
+    async fn delete_analysis(&self, result_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM analysis_results
+            WHERE id = $1
+            "#,
+            result_id
+        )
+        .execute(&self.pool)
+        .await
+        .with_context(|| format!("Failed to delete analysis result {result_id}"))?;
+        Ok(())
+    }
+
+    async fn cleanup_old_results(&self, older_than: SystemTime) -> Result<usize> {
+        let older_than: chrono::DateTime<chrono::Utc> = older_than.into();
+        let deleted = sqlx::query!(
+            r#"
+            DELETE FROM analysis_results
+            WHERE timestamp < $1
+            "#,
+            older_than
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to clean up old analysis results")?;
+        Ok(deleted.rows_affected() as usize)
     }
 }
 
@@ -958,6 +1646,87 @@ mod tests {
         assert!(!storage.graph_exists("test_repo").await.unwrap());
     }
 
+    /// Fails the first `failures` writes with `kind`, then writes to disk
+    struct FlakyWriter {
+        failures: u32,
+        kind: std::io::ErrorKind,
+        attempts: std::sync::atomic::AtomicU32,
+    }
+
+    impl FlakyWriter {
+        fn new(failures: u32, kind: std::io::ErrorKind) -> Arc<Self> {
+            Arc::new(Self {
+                failures,
+                kind,
+                attempts: std::sync::atomic::AtomicU32::new(0),
+            })
+        }
+
+        fn attempts(&self) -> u32 {
+            self.attempts.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl FileWriter for FlakyWriter {
+        async fn write(&self, path: &Path, contents: &[u8]) -> std::io::Result<()> {
+            let attempt = self
+                .attempts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if attempt < self.failures {
+                return Err(std::io::Error::from(self.kind));
+            }
+            fs::write(path, contents).await
+        }
+    }
+
+    fn fast_retry() -> RetryConfig {
+        WriteRetryConfig {
+            max_attempts: 3,
+            base_delay: std::time::Duration::from_millis(1),
+            max_delay: std::time::Duration::from_millis(5),
+        }
+        .to_retry_config()
+    }
+
+    #[tokio::test]
+    async fn test_file_storage_retries_transient_write_errors() {
+        let temp_dir = tempdir().unwrap();
+        let writer = FlakyWriter::new(2, std::io::ErrorKind::ResourceBusy);
+        let storage = FileGraphStorage::new(temp_dir.path())
+            .await
+            .unwrap()
+            .with_retry_config(fast_retry())
+            .with_writer(writer.clone());
+
+        storage.store_graph(&create_test_graph()).await.unwrap();
+
+        // Two busy failures, then the graph and its metadata
+        assert_eq!(writer.attempts(), 4);
+        let loaded = storage.load_graph("test_repo").await.unwrap().unwrap();
+        assert_eq!(loaded.nodes.len(), 2);
+        assert!(storage
+            .get_graph_metadata("test_repo")
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_file_storage_does_not_retry_permanent_write_errors() {
+        let temp_dir = tempdir().unwrap();
+        let writer = FlakyWriter::new(1, std::io::ErrorKind::PermissionDenied);
+        let storage = FileGraphStorage::new(temp_dir.path())
+            .await
+            .unwrap()
+            .with_retry_config(fast_retry())
+            .with_writer(writer.clone());
+
+        assert!(storage.store_graph(&create_test_graph()).await.is_err());
+        assert_eq!(writer.attempts(), 1);
+        assert!(!storage.graph_exists("test_repo").await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_file_storage() {
         let temp_dir = tempdir().unwrap();
@@ -1155,6 +1924,81 @@ mod tests {
         assert!(!storage.graph_exists("test_repo").await.unwrap());
     }
 
+    #[cfg(feature = "rocksdb")]
+    #[tokio::test]
+    async fn test_rocksdb_storage_round_trips_large_graph() {
+        let temp_dir = tempdir().unwrap();
+        let storage = RocksDbGraphStorage::new(temp_dir.path()).await.unwrap();
+
+        let mut graph = SerializableGraph::new("big_repo".to_string());
+        for i in 0..1000 {
+            graph.add_node(SerializableNode::new(
+                format!("node{i}"),
+                format!("function_{i}"),
+                "function".to_string(),
+                PathBuf::from(format!("src/file_{}.rs", i % 10)),
+                SerializableSpan {
+                    start_byte: i * 10,
+                    end_byte: i * 10 + 9,
+                    start_line: i + 1,
+                    end_line: i + 1,
+                    start_column: 0,
+                    end_column: 9,
+                },
+            ));
+            if i > 0 {
+                graph.add_edge(SerializableEdge::new(
+                    format!("node{}", i - 1),
+                    format!("node{i}"),
+                    "calls".to_string(),
+                ));
+            }
+        }
+        graph.update_metadata();
+        storage.store_graph(&graph).await.unwrap();
+
+        // A repository sharing the prefix must not leak into the scan
+        storage.store_graph(&create_test_graph()).await.unwrap();
+        let mut other = create_test_graph();
+        other.repo_id = "big_repo_2".to_string();
+        storage.store_graph(&other).await.unwrap();
+
+        let loaded = storage.load_graph("big_repo").await.unwrap().unwrap();
+        assert_eq!(loaded.nodes.len(), 1000);
+        assert_eq!(loaded.edges.len(), 999);
+        assert_eq!(loaded.metadata.total_nodes, 1000);
+        let node = loaded.nodes.iter().find(|n| n.id == "node500").unwrap();
+        assert_eq!(node.name, "function_500");
+        assert_eq!(node.span.start_line, 501);
+
+        assert_eq!(
+            storage.list_repositories().await.unwrap(),
+            vec!["big_repo", "big_repo_2", "test_repo"]
+        );
+
+        // Deleting a node removes its edges
+        storage
+            .delete_nodes("big_repo", &["node500".to_string()])
+            .await
+            .unwrap();
+        let loaded = storage.load_graph("big_repo").await.unwrap().unwrap();
+        assert_eq!(loaded.nodes.len(), 999);
+        assert_eq!(loaded.edges.len(), 997);
+
+        storage.delete_graph("big_repo").await.unwrap();
+        assert!(storage.load_graph("big_repo").await.unwrap().is_none());
+        assert_eq!(
+            storage
+                .load_graph("big_repo_2")
+                .await
+                .unwrap()
+                .unwrap()
+                .nodes
+                .len(),
+            2
+        );
+    }
+
     #[tokio::test]
     async fn test_storage_error_handling() {
         // Test loading non-existent graph from FileGraphStorage
@@ -1190,6 +2034,26 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_file_analysis_storage_ignores_ids_sharing_a_file_name() {
+        let temp_dir = tempdir().unwrap();
+        let storage = FileAnalysisStorage::new(temp_dir.path()).await.unwrap();
+        storage
+            .store_analysis(&AnalysisResult {
+                id: "repo:a/b".to_string(),
+                repo_id: "repo".to_string(),
+                analysis_type: "complexity".to_string(),
+                timestamp: SystemTime::now(),
+                data: serde_json::json!({ "score": 1 }),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+
+        assert!(storage.load_analysis("repo:a/b").await.unwrap().is_some());
+        assert!(storage.load_analysis("repo:a:b").await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_concurrent_access() {
         use tokio::task;
@@ -1236,4 +2100,59 @@ mod tests {
             handle.await.unwrap();
         }
     }
+
+    #[cfg(feature = "postgres")]
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL server at CODEPRISM_TEST_POSTGRES_URL"]
+    async fn test_postgres_analysis_storage_roundtrip() {
+        use std::time::Duration;
+
+        let url = std::env::var("CODEPRISM_TEST_POSTGRES_URL").unwrap();
+        let storage = PostgresAnalysisStorage::connect(&url).await.unwrap();
+        let now = SystemTime::now();
+        let result = |id: &str, analysis_type: &str, age_secs: u64| AnalysisResult {
+            id: id.to_string(),
+            repo_id: "pg_test_repo".to_string(),
+            analysis_type: analysis_type.to_string(),
+            timestamp: now - Duration::from_secs(age_secs),
+            data: serde_json::json!({ "findings": [id] }),
+            metadata: HashMap::from([("commit".to_string(), "abc123".to_string())]),
+        };
+        storage.cleanup_old_results(now).await.unwrap();
+
+        storage
+            .store_analysis(&result("recent", "complexity", 10))
+            .await
+            .unwrap();
+        storage
+            .store_analysis(&result("old", "complexity", 3600))
+            .await
+            .unwrap();
+        storage
+            .store_analysis(&result("security", "security", 10))
+            .await
+            .unwrap();
+
+        let loaded = storage.load_analysis("recent").await.unwrap().unwrap();
+        assert_eq!(loaded.data["findings"][0], "recent");
+        assert_eq!(loaded.metadata["commit"], "abc123");
+
+        let since = now - Duration::from_secs(60);
+        let found = storage
+            .find_analysis("pg_test_repo", Some("complexity"), Some(since))
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "recent");
+        let all = storage
+            .find_analysis("pg_test_repo", None, None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+
+        assert_eq!(storage.cleanup_old_results(since).await.unwrap(), 1);
+        storage.delete_analysis("security").await.unwrap();
+        assert!(storage.load_analysis("security").await.unwrap().is_none());
+        storage.cleanup_old_results(now).await.unwrap();
+    }
 }
@@ -0,0 +1,285 @@
+//! Benchmark run history and regression tracking
+//!
+//! Each benchmark run's per-test latencies are stored as an analysis result so
+//! performance can be followed across commits. Trends are computed over a
+//! moving average, which catches latencies that creep up a little on every
+//! run and never trip a single run-to-run comparison.
+
+use crate::{AnalysisResult, AnalysisStorage};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Analysis type under which benchmark runs are stored
+pub const BENCHMARK_ANALYSIS_TYPE: &str = "benchmark_run";
+
+/// Per-test latencies of a single benchmark run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRun {
+    /// Commit the run was measured on, if known
+    pub commit: Option<String>,
+    pub timestamp: SystemTime,
+    /// Latency in milliseconds keyed by test name
+    pub latencies_ms: BTreeMap<String, f64>,
+}
+
+impl BenchmarkRun {
+    /// Create a run timestamped now
+    pub fn new(commit: Option<String>, latencies_ms: BTreeMap<String, f64>) -> Self {
+        Self {
+            commit,
+            timestamp: SystemTime::now(),
+            latencies_ms,
+        }
+    }
+
+    /// Override the run timestamp
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+}
+
+/// Settings for trend detection
+#[derive(Debug, Clone)]
+pub struct RegressionConfig {
+    /// Number of runs averaged per moving-average point
+    pub window: usize,
+    /// Increase of the moving average, in percent, that counts as a regression
+    pub threshold_percent: f64,
+}
+
+impl Default for RegressionConfig {
+    fn default() -> Self {
+        Self {
+            window: 3,
+            threshold_percent: 10.0,
+        }
+    }
+}
+
+/// Latency trend of one test across the stored runs
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyTrend {
+    pub test_name: String,
+    /// Latencies in run order
+    pub samples: Vec<f64>,
+    /// Trailing moving average over `RegressionConfig::window` runs
+    pub moving_average: Vec<f64>,
+    /// Change between the first and last moving-average point, in percent
+    pub change_percent: f64,
+    pub regressed: bool,
+    /// Regressed without any single run-to-run step exceeding the threshold
+    pub gradual: bool,
+}
+
+impl LatencyTrend {
+    /// Render the moving average as a unicode sparkline
+    pub fn sparkline(&self) -> String {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        let min = self.moving_average.iter().copied().fold(f64::MAX, f64::min);
+        let max = self.moving_average.iter().copied().fold(f64::MIN, f64::max);
+        let range = max - min;
+
+        self.moving_average
+            .iter()
+            .map(|value| {
+                if range <= f64::EPSILON {
+                    BARS[0]
+                } else {
+                    let level = ((value - min) / range * (BARS.len() - 1) as f64).round();
+                    BARS[level as usize]
+                }
+            })
+            .collect()
+    }
+}
+
+/// Compute per-test latency trends over runs ordered oldest first
+///
+/// Tests need at least `window + 1` samples, i.e. two moving-average points,
+/// before a trend is reported.
+pub fn analyze_trends(runs: &[BenchmarkRun], config: &RegressionConfig) -> Vec<LatencyTrend> {
+    let window = config.window.max(1);
+    let mut samples: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for run in runs {
+        for (test_name, latency) in &run.latencies_ms {
+            samples.entry(test_name).or_default().push(*latency);
+        }
+    }
+
+    samples
+        .into_iter()
+        .filter(|(_, samples)| samples.len() > window)
+        .map(|(test_name, samples)| {
+            let moving_average: Vec<f64> = samples
+                .windows(window)
+                .map(|w| w.iter().sum::<f64>() / window as f64)
+                .collect();
+
+            let first = moving_average[0];
+            let last = moving_average[moving_average.len() - 1];
+            let change_percent = percent_change(first, last);
+            let regressed = change_percent > config.threshold_percent;
+            let largest_step = samples
+                .windows(2)
+                .map(|pair| percent_change(pair[0], pair[1]))
+                .fold(f64::MIN, f64::max);
+
+            LatencyTrend {
+                test_name: test_name.to_string(),
+                samples,
+                moving_average,
+                change_percent,
+                regressed,
+                gradual: regressed && largest_step <= config.threshold_percent,
+            }
+        })
+        .collect()
+}
+
+fn percent_change(from: f64, to: f64) -> f64 {
+    if from <= 0.0 {
+        return 0.0;
+    }
+    (to - from) / from * 100.0
+}
+
+/// Benchmark history of one repository stored in an [`AnalysisStorage`]
+pub struct BenchmarkHistory<'a> {
+    storage: &'a dyn AnalysisStorage,
+    repo_id: String,
+}
+
+impl<'a> BenchmarkHistory<'a> {
+    pub fn new(storage: &'a dyn AnalysisStorage, repo_id: impl Into<String>) -> Self {
+        Self {
+            storage,
+            repo_id: repo_id.into(),
+        }
+    }
+
+    /// Persist a run, returning the id of the stored result
+    pub async fn record(&self, run: &BenchmarkRun) -> Result<String> {
+        let nanos = run
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let id = match &run.commit {
+            Some(commit) => format!("benchmark:{}:{}:{}", self.repo_id, nanos, commit),
+            None => format!("benchmark:{}:{}", self.repo_id, nanos),
+        };
+
+        let mut metadata = HashMap::new();
+        if let Some(commit) = &run.commit {
+            metadata.insert("commit".to_string(), commit.clone());
+        }
+
+        let result = AnalysisResult {
+            id: id.clone(),
+            repo_id: self.repo_id.clone(),
+            analysis_type: BENCHMARK_ANALYSIS_TYPE.to_string(),
+            timestamp: run.timestamp,
+            data: serde_json::to_value(run).context("Failed to serialize benchmark run")?,
+            metadata,
+        };
+        self.storage.store_analysis(&result).await?;
+
+        Ok(id)
+    }
+
+    /// All stored runs, oldest first
+    pub async fn runs(&self) -> Result<Vec<BenchmarkRun>> {
+        let mut runs = self
+            .storage
+            .find_analysis(&self.repo_id, Some(BENCHMARK_ANALYSIS_TYPE), None)
+            .await?
+            .into_iter()
+            .map(|result| {
+                serde_json::from_value::<BenchmarkRun>(result.data)
+                    .with_context(|| format!("Invalid benchmark run {}", result.id))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        runs.sort_by_key(|run| run.timestamp);
+        Ok(runs)
+    }
+
+    /// Latency trends across all stored runs
+    pub async fn trends(&self, config: &RegressionConfig) -> Result<Vec<LatencyTrend>> {
+        Ok(analyze_trends(&self.runs().await?, config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::FileAnalysisStorage;
+    use std::time::Duration;
+
+    fn run(index: u64, latencies: &[(&str, f64)]) -> BenchmarkRun {
+        BenchmarkRun::new(
+            Some(format!("commit{index}")),
+            latencies
+                .iter()
+                .map(|(name, latency)| (name.to_string(), *latency))
+                .collect(),
+        )
+        .with_timestamp(UNIX_EPOCH + Duration::from_secs(1_700_000_000 + index * 60))
+    }
+
+    #[tokio::test]
+    async fn test_gradual_regression_detected_across_stored_runs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        // Record out of order to make sure runs are sorted by time
+        {
+            let storage = FileAnalysisStorage::new(temp_dir.path()).await.unwrap();
+            let history = BenchmarkHistory::new(&storage, "repo");
+            for index in [3, 0, 5, 1, 4, 2] {
+                let creeping = 100.0 + 4.0 * index as f64;
+                let stable = if index % 2 == 0 { 50.0 } else { 51.0 };
+                history
+                    .record(&run(index, &[("search", creeping), ("parse", stable)]))
+                    .await
+                    .unwrap();
+            }
+        }
+
+        // A fresh storage instance sees the persisted runs
+        let storage = FileAnalysisStorage::new(temp_dir.path()).await.unwrap();
+        let history = BenchmarkHistory::new(&storage, "repo");
+        let runs = history.runs().await.unwrap();
+        assert_eq!(runs.len(), 6);
+        assert_eq!(runs[0].commit.as_deref(), Some("commit0"));
+
+        let trends = history.trends(&RegressionConfig::default()).await.unwrap();
+        let search = trends.iter().find(|t| t.test_name == "search").unwrap();
+        assert!(search.regressed, "4% per run should add up: {search:?}");
+        assert!(search.gradual, "No single step exceeds the threshold");
+        assert_eq!(search.moving_average.len(), 4);
+        assert_eq!(search.sparkline().chars().count(), 4);
+
+        let parse = trends.iter().find(|t| t.test_name == "parse").unwrap();
+        assert!(!parse.regressed, "Noise is not a regression: {parse:?}");
+    }
+
+    #[test]
+    fn test_sudden_jump_is_not_gradual() {
+        let runs: Vec<BenchmarkRun> = [100.0, 100.0, 100.0, 180.0]
+            .iter()
+            .enumerate()
+            .map(|(index, latency)| run(index as u64, &[("index", *latency)]))
+            .collect();
+
+        let trends = analyze_trends(&runs, &RegressionConfig::default());
+        assert_eq!(trends.len(), 1);
+        assert!(trends[0].regressed);
+        assert!(!trends[0].gradual);
+
+        let too_short = analyze_trends(&runs[..3], &RegressionConfig::default());
+        assert!(too_short.is_empty(), "Needs two moving-average points");
+    }
+}
@@ -1,6 +1,6 @@
 //! Cache storage implementations
 
-use crate::{CacheStats, CacheStorage};
+use crate::{CacheBackend, CacheStats, CacheStorage};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -239,3 +239,405 @@ impl CacheStorage for LruCacheStorage {
         Ok(())
     }
 }
+
+/// Number of connections opened by [`RedisCacheStorage::connect`]
+#[cfg(feature = "redis")]
+pub const DEFAULT_REDIS_POOL_SIZE: usize = 4;
+
+/// Redis-backed cache storage shared between server processes
+///
+/// Keys are namespaced with a prefix so `clear` and `invalidate_pattern`
+/// only touch entries written by CodePrism. Values are bincode-encoded like
+/// in [`LruCacheStorage`].
+#[cfg(feature = "redis")]
+pub struct RedisCacheStorage {
+    pool: Vec<redis::aio::ConnectionManager>,
+    next_connection: std::sync::atomic::AtomicUsize,
+    key_prefix: String,
+    db: i64,
+}
+
+#[cfg(feature = "redis")]
+impl RedisCacheStorage {
+    /// Connect to the Redis server at `url` with the default pool size
+    pub async fn connect(url: &str) -> Result<Self> {
+        Self::connect_with_pool_size(url, DEFAULT_REDIS_POOL_SIZE).await
+    }
+
+    /// Connect to the Redis server at `url`, opening `pool_size` connections
+    ///
+    /// Fails if the server cannot be reached. Established connections
+    /// reconnect on their own if the server goes away later.
+    pub async fn connect_with_pool_size(url: &str, pool_size: usize) -> Result<Self> {
+        let client = redis::Client::open(url)?;
+        let db = client.get_connection_info().redis.db;
+        let manager_config = redis::aio::ConnectionManagerConfig::new()
+            .set_number_of_retries(1)
+            .set_connection_timeout(Duration::from_secs(2));
+
+        let mut pool = Vec::with_capacity(pool_size.max(1));
+        for _ in 0..pool_size.max(1) {
+            pool.push(
+                redis::aio::ConnectionManager::new_with_config(
+                    client.clone(),
+                    manager_config.clone(),
+                )
+                .await?,
+            );
+        }
+
+        Ok(Self {
+            pool,
+            next_connection: std::sync::atomic::AtomicUsize::new(0),
+            key_prefix: "codeprism:cache:".to_string(),
+            db,
+        })
+    }
+
+    /// Use a different key namespace (default `codeprism:cache:`)
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    /// Pick the next pooled connection in round-robin order
+    fn connection(&self) -> redis::aio::ConnectionManager {
+        let index = self
+            .next_connection
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.pool[index % self.pool.len()].clone()
+    }
+
+    fn prefixed(&self, key: &str) -> String {
+        format!("{}{}", self.key_prefix, key)
+    }
+
+    /// Collect all keys matching a glob via `SCAN` + `MATCH`
+    async fn scan_keys(&self, pattern: &str) -> Result<Vec<String>> {
+        let mut conn = self.connection();
+        let mut keys = Vec::new();
+        let mut cursor: u64 = 0;
+        loop {
+            let (next_cursor, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(500)
+                .query_async(&mut conn)
+                .await?;
+            keys.extend(batch);
+            if next_cursor == 0 {
+                return Ok(keys);
+            }
+            cursor = next_cursor;
+        }
+    }
+
+    /// Delete keys in chunks to keep individual commands small
+    async fn delete_keys(&self, keys: &[String]) -> Result<()> {
+        let mut conn = self.connection();
+        for chunk in keys.chunks(500) {
+            let _: () = redis::cmd("DEL").arg(chunk).query_async(&mut conn).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Escape glob metacharacters so a substring matches literally in `MATCH`
+#[cfg(feature = "redis")]
+fn escape_glob(pattern: &str) -> String {
+    let mut escaped = String::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Build cache statistics from the output of the Redis `INFO` command
+///
+/// Hit, miss and eviction counts and memory usage are server-wide; the key
+/// count is taken from the keyspace line of database `db`.
+pub fn parse_redis_info(info: &str, db: i64) -> CacheStats {
+    let mut stats = CacheStats {
+        total_keys: 0,
+        memory_usage_bytes: 0,
+        hit_count: 0,
+        miss_count: 0,
+        eviction_count: 0,
+    };
+    let keyspace = format!("db{db}");
+
+    for line in info.lines() {
+        let Some((field, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        match field {
+            "used_memory" => stats.memory_usage_bytes = value.parse().unwrap_or(0),
+            "keyspace_hits" => stats.hit_count = value.parse().unwrap_or(0),
+            "keyspace_misses" => stats.miss_count = value.parse().unwrap_or(0),
+            "evicted_keys" => stats.eviction_count = value.parse().unwrap_or(0),
+            _ if field == keyspace => {
+                stats.total_keys = value
+                    .split(',')
+                    .find_map(|part| part.strip_prefix("keys="))
+                    .and_then(|keys| keys.parse().ok())
+                    .unwrap_or(0);
+            }
+            _ => {}
+        }
+    }
+
+    stats
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl CacheStorage for RedisCacheStorage {
+    async fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: for<'de> Deserialize<'de> + Send,
+    {
+        let mut conn = self.connection();
+        let data: Option<Vec<u8>> = redis::cmd("GET")
+            .arg(self.prefixed(key))
+            .query_async(&mut conn)
+            .await?;
+        match data {
+            Some(data) => Ok(Some(bincode::deserialize(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn set<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<()>
+    where
+        T: Serialize + Send + Sync,
+    {
+        let serialized = bincode::serialize(value)?;
+        let mut command = redis::cmd("SET");
+        command.arg(self.prefixed(key)).arg(serialized);
+        if let Some(ttl) = ttl {
+            // PX rejects zero, so round sub-millisecond TTLs up
+            command.arg("PX").arg(ttl.as_millis().max(1) as u64);
+        }
+
+        let mut conn = self.connection();
+        let _: () = command.query_async(&mut conn).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let mut conn = self.connection();
+        let _: () = redis::cmd("DEL")
+            .arg(self.prefixed(key))
+            .query_async(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) -> Result<()> {
+        // Same substring semantics as the LRU cache
+        let glob = format!(
+            "{}*{}*",
+            escape_glob(&self.key_prefix),
+            escape_glob(pattern)
+        );
+        let keys = self.scan_keys(&glob).await?;
+        self.delete_keys(&keys).await
+    }
+
+    async fn get_stats(&self) -> Result<CacheStats> {
+        let mut conn = self.connection();
+        let info: String = redis::cmd("INFO").query_async(&mut conn).await?;
+        Ok(parse_redis_info(&info, self.db))
+    }
+
+    async fn clear(&self) -> Result<()> {
+        let keys = self
+            .scan_keys(&format!("{}*", escape_glob(&self.key_prefix)))
+            .await?;
+        self.delete_keys(&keys).await
+    }
+}
+
+/// Cache layer selected by [`crate::StorageConfig::cache_backend`]
+pub enum CacheLayer {
+    Lru(LruCacheStorage),
+    #[cfg(feature = "redis")]
+    Redis(RedisCacheStorage),
+}
+
+impl CacheLayer {
+    /// Create the configured cache layer
+    ///
+    /// Falls back to an in-memory LRU cache when Redis is selected but no
+    /// connection string is set, the server cannot be reached, or the crate
+    /// was built without the `redis` feature.
+    pub async fn from_config(config: &crate::StorageConfig) -> Self {
+        let lru = || Self::Lru(LruCacheStorage::new(config.cache_size_mb * 1024 * 1024));
+
+        match config.cache_backend {
+            CacheBackend::Lru => lru(),
+            CacheBackend::Redis => {
+                let Some(url) = config.connection_string.as_deref() else {
+                    tracing::warn!(
+                        "Redis cache selected without a connection string, using LRU cache"
+                    );
+                    return lru();
+                };
+
+                #[cfg(feature = "redis")]
+                match RedisCacheStorage::connect(url).await {
+                    Ok(storage) => Self::Redis(storage),
+                    Err(e) => {
+                        tracing::warn!("Failed to connect to Redis cache, using LRU cache: {}", e);
+                        lru()
+                    }
+                }
+
+                #[cfg(not(feature = "redis"))]
+                {
+                    tracing::warn!(
+                        "Redis cache at {} requested but the redis feature is disabled, using LRU cache",
+                        url
+                    );
+                    lru()
+                }
+            }
+        }
+    }
+
+    /// Backend actually serving the cache
+    pub fn backend(&self) -> CacheBackend {
+        match self {
+            Self::Lru(_) => CacheBackend::Lru,
+            #[cfg(feature = "redis")]
+            Self::Redis(_) => CacheBackend::Redis,
+        }
+    }
+}
+
+#[async_trait]
+impl CacheStorage for CacheLayer {
+    async fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: for<'de> Deserialize<'de> + Send,
+    {
+        match self {
+            Self::Lru(cache) => cache.get(key).await,
+            #[cfg(feature = "redis")]
+            Self::Redis(cache) => cache.get(key).await,
+        }
+    }
+
+    async fn set<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<()>
+    where
+        T: Serialize + Send + Sync,
+    {
+        match self {
+            Self::Lru(cache) => cache.set(key, value, ttl).await,
+            #[cfg(feature = "redis")]
+            Self::Redis(cache) => cache.set(key, value, ttl).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self {
+            Self::Lru(cache) => cache.delete(key).await,
+            #[cfg(feature = "redis")]
+            Self::Redis(cache) => cache.delete(key).await,
+        }
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) -> Result<()> {
+        match self {
+            Self::Lru(cache) => cache.invalidate_pattern(pattern).await,
+            #[cfg(feature = "redis")]
+            Self::Redis(cache) => cache.invalidate_pattern(pattern).await,
+        }
+    }
+
+    async fn get_stats(&self) -> Result<CacheStats> {
+        match self {
+            Self::Lru(cache) => cache.get_stats().await,
+            #[cfg(feature = "redis")]
+            Self::Redis(cache) => cache.get_stats().await,
+        }
+    }
+
+    async fn clear(&self) -> Result<()> {
+        match self {
+            Self::Lru(cache) => cache.clear().await,
+            #[cfg(feature = "redis")]
+            Self::Redis(cache) => cache.clear().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StorageConfig;
+
+    #[test]
+    fn test_parse_redis_info() {
+        let info = "# Memory\r\nused_memory:1048576\r\n\r\n# Stats\r\nkeyspace_hits:42\r\nkeyspace_misses:7\r\nevicted_keys:3\r\n\r\n# Keyspace\r\ndb0:keys=12,expires=2,avg_ttl=0\r\ndb1:keys=5,expires=0,avg_ttl=0\r\n";
+
+        let stats = parse_redis_info(info, 0);
+        assert_eq!(stats.memory_usage_bytes, 1048576);
+        assert_eq!(stats.hit_count, 42);
+        assert_eq!(stats.miss_count, 7);
+        assert_eq!(stats.eviction_count, 3);
+        assert_eq!(stats.total_keys, 12);
+        assert_eq!(parse_redis_info(info, 1).total_keys, 5);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_redis_falls_back_to_lru() {
+        let config = StorageConfig::in_memory()
+            .with_cache_backend(CacheBackend::Redis)
+            .with_connection_string("redis://127.0.0.1:1/");
+
+        let cache = CacheLayer::from_config(&config).await;
+        assert_eq!(cache.backend(), CacheBackend::Lru);
+
+        cache.set("key", &42u32, None).await.unwrap();
+        assert_eq!(cache.get::<u32>("key").await.unwrap(), Some(42));
+    }
+
+    #[cfg(feature = "redis")]
+    #[tokio::test]
+    #[ignore = "requires a Redis server at CODEPRISM_TEST_REDIS_URL"]
+    async fn test_redis_cache_roundtrip() {
+        let url = std::env::var("CODEPRISM_TEST_REDIS_URL").unwrap();
+        let cache = RedisCacheStorage::connect(&url)
+            .await
+            .unwrap()
+            .with_key_prefix("codeprism:test:");
+        cache.clear().await.unwrap();
+
+        cache.set("graph:repo1", &"a", None).await.unwrap();
+        cache.set("graph:repo2", &"b", None).await.unwrap();
+        cache
+            .set("ttl", &"c", Some(Duration::from_millis(50)))
+            .await
+            .unwrap();
+        assert_eq!(
+            cache.get::<String>("graph:repo1").await.unwrap(),
+            Some("a".to_string())
+        );
+
+        cache.invalidate_pattern("repo1").await.unwrap();
+        assert_eq!(cache.get::<String>("graph:repo1").await.unwrap(), None);
+        assert!(cache.get::<String>("graph:repo2").await.unwrap().is_some());
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(cache.get::<String>("ttl").await.unwrap(), None);
+
+        cache.clear().await.unwrap();
+        assert_eq!(cache.get::<String>("graph:repo2").await.unwrap(), None);
+    }
+}
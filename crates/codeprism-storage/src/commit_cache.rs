@@ -0,0 +1,364 @@
+//! Analysis result caching keyed by git commit
+//!
+//! Results computed for a clean checkout are stored under the current `HEAD`
+//! commit hash, so repeating the same analysis on an unchanged commit (for
+//! example across CI jobs) returns the stored result instead of recomputing it.
+//! Working trees with uncommitted changes always bypass the cache.
+
+use crate::{AnalysisResult, AnalysisStorage};
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// State of a git working tree relevant for caching
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRevision {
+    /// Full hash of the `HEAD` commit
+    pub commit: String,
+    /// Whether the working tree has uncommitted or untracked changes
+    pub dirty: bool,
+}
+
+impl GitRevision {
+    /// Resolve the current revision of the repository at `repo_path`
+    ///
+    /// A single `git status` call reports both the `HEAD` commit and the
+    /// working tree changes.
+    pub async fn resolve(repo_path: &Path) -> Result<Self> {
+        let status = run_git(repo_path, &["status", "--porcelain=v2", "--branch"]).await?;
+
+        let commit = status
+            .lines()
+            .find_map(|line| line.strip_prefix("# branch.oid "))
+            .filter(|oid| *oid != "(initial)")
+            .ok_or_else(|| anyhow!("Repository at {} has no commits", repo_path.display()))?;
+
+        Ok(Self {
+            commit: commit.trim().to_string(),
+            dirty: status.lines().any(|line| !line.starts_with('#')),
+        })
+    }
+}
+
+async fn run_git(repo_path: &Path, args: &[&str]) -> Result<String> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Outcome of a cached analysis lookup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheOutcome {
+    /// Result was served from the cache
+    Hit,
+    /// Result was computed and stored for the commit
+    Miss,
+    /// Cache was skipped (disabled, dirty tree or not a git repository)
+    Bypassed,
+}
+
+/// Analysis result returned by [`CommitAnalysisCache::get_or_compute`]
+#[derive(Debug, Clone)]
+pub struct CachedAnalysis {
+    pub data: serde_json::Value,
+    pub outcome: CacheOutcome,
+    pub commit: Option<String>,
+}
+
+/// Cache layer that stores analysis results per git commit
+pub struct CommitAnalysisCache {
+    storage: Arc<dyn AnalysisStorage>,
+    enabled: bool,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CommitAnalysisCache {
+    /// Create a new commit-keyed cache on top of an analysis storage backend
+    pub fn new(storage: Arc<dyn AnalysisStorage>) -> Self {
+        Self {
+            storage,
+            enabled: true,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Enable or disable the cache
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Build the storage key for an analysis of a repository at a commit
+    ///
+    /// `params_key` distinguishes runs of the same analysis type with different
+    /// parameters (target, thresholds, ...). It is hashed so that keys stay
+    /// short and parameters differing only in punctuation do not collide once
+    /// a backend sanitizes the key.
+    pub fn cache_key(repo_id: &str, analysis_type: &str, params_key: &str, commit: &str) -> String {
+        let params_hash = blake3::hash(params_key.as_bytes()).to_hex();
+        format!("commit:{repo_id}:{commit}:{analysis_type}:{params_hash}")
+    }
+
+    /// Return the cached result for the current commit or compute and store it
+    pub async fn get_or_compute<F, Fut>(
+        &self,
+        repo_path: &Path,
+        repo_id: &str,
+        analysis_type: &str,
+        params_key: &str,
+        compute: F,
+    ) -> Result<CachedAnalysis>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<serde_json::Value>>,
+    {
+        let revision = if self.enabled {
+            match GitRevision::resolve(repo_path).await {
+                Ok(revision) => Some(revision),
+                Err(e) => {
+                    tracing::debug!("Commit cache bypassed for {}: {}", repo_path.display(), e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let revision = match revision {
+            Some(revision) if !revision.dirty => revision,
+            _ => {
+                return Ok(CachedAnalysis {
+                    data: compute().await?,
+                    outcome: CacheOutcome::Bypassed,
+                    commit: None,
+                });
+            }
+        };
+
+        let key = Self::cache_key(repo_id, analysis_type, params_key, &revision.commit);
+
+        match self.storage.load_analysis(&key).await {
+            Ok(Some(cached)) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(CachedAnalysis {
+                    data: cached.data,
+                    outcome: CacheOutcome::Hit,
+                    commit: Some(revision.commit),
+                });
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to read cached {} result: {}", analysis_type, e),
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let data = compute().await?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("commit".to_string(), revision.commit.clone());
+        metadata.insert("params_key".to_string(), params_key.to_string());
+
+        // A failed write only costs the next run a recomputation
+        if let Err(e) = self
+            .storage
+            .store_analysis(&AnalysisResult {
+                id: key,
+                repo_id: repo_id.to_string(),
+                analysis_type: analysis_type.to_string(),
+                timestamp: SystemTime::now(),
+                data: data.clone(),
+                metadata,
+            })
+            .await
+        {
+            tracing::warn!("Failed to cache {} result: {}", analysis_type, e);
+        }
+
+        Ok(CachedAnalysis {
+            data,
+            outcome: CacheOutcome::Miss,
+            commit: Some(revision.commit),
+        })
+    }
+
+    /// Number of lookups served from the cache
+    pub fn hit_count(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that had to compute a fresh result
+    pub fn miss_count(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileAnalysisStorage, InMemoryAnalysisStorage};
+    use std::sync::atomic::AtomicUsize;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn commit_file(dir: &Path, name: &str, content: &str) {
+        std::fs::write(dir.join(name), content).unwrap();
+        git(dir, &["add", "-A"]);
+        git(dir, &["commit", "-q", "-m", name]);
+    }
+
+    async fn run_analysis(
+        cache: &CommitAnalysisCache,
+        dir: &Path,
+        runs: &AtomicUsize,
+    ) -> CachedAnalysis {
+        cache
+            .get_or_compute(dir, "repo", "complexity", "all", || async {
+                runs.fetch_add(1, Ordering::SeqCst);
+                Ok(serde_json::json!({ "score": 7 }))
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_same_commit_hits_cache_and_new_commit_misses() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q"]);
+        commit_file(dir, "main.rs", "fn main() {}\n");
+
+        let cache = CommitAnalysisCache::new(Arc::new(InMemoryAnalysisStorage::new()));
+        let runs = AtomicUsize::new(0);
+
+        let first = run_analysis(&cache, dir, &runs).await;
+        assert_eq!(first.outcome, CacheOutcome::Miss);
+
+        let second = run_analysis(&cache, dir, &runs).await;
+        assert_eq!(second.outcome, CacheOutcome::Hit);
+        assert_eq!(second.data, first.data);
+        assert_eq!(
+            runs.load(Ordering::SeqCst),
+            1,
+            "Second run should not recompute"
+        );
+
+        commit_file(dir, "lib.rs", "pub fn lib() {}\n");
+        let third = run_analysis(&cache, dir, &runs).await;
+        assert_eq!(third.outcome, CacheOutcome::Miss);
+        assert_ne!(third.commit, first.commit);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.hit_count(), 1);
+        assert_eq!(cache.miss_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dirty_tree_bypasses_cache() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q"]);
+        commit_file(dir, "main.rs", "fn main() {}\n");
+        std::fs::write(dir.join("main.rs"), "fn main() { println!(); }\n").unwrap();
+
+        let cache = CommitAnalysisCache::new(Arc::new(InMemoryAnalysisStorage::new()));
+        let runs = AtomicUsize::new(0);
+
+        assert_eq!(
+            run_analysis(&cache, dir, &runs).await.outcome,
+            CacheOutcome::Bypassed
+        );
+        assert_eq!(
+            run_analysis(&cache, dir, &runs).await.outcome,
+            CacheOutcome::Bypassed
+        );
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_cache_and_non_git_directory_bypass() {
+        let temp_dir = tempdir().unwrap();
+        let runs = AtomicUsize::new(0);
+
+        let cache = CommitAnalysisCache::new(Arc::new(InMemoryAnalysisStorage::new()));
+        let result = run_analysis(&cache, temp_dir.path(), &runs).await;
+        assert_eq!(result.outcome, CacheOutcome::Bypassed);
+
+        let disabled =
+            CommitAnalysisCache::new(Arc::new(InMemoryAnalysisStorage::new())).with_enabled(false);
+        let result = run_analysis(&disabled, temp_dir.path(), &runs).await;
+        assert_eq!(result.outcome, CacheOutcome::Bypassed);
+    }
+
+    #[tokio::test]
+    async fn test_params_differing_in_punctuation_keep_separate_results() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q"]);
+        commit_file(dir, "main.rs", "fn main() {}\n");
+
+        let cache_dir = tempdir().unwrap();
+        let storage = FileAnalysisStorage::new(cache_dir.path()).await.unwrap();
+        let cache = CommitAnalysisCache::new(Arc::new(storage));
+
+        for (params_key, score) in [(r#"{"target":"a/b"}"#, 1), (r#"{"target":"a:b"}"#, 2)] {
+            let result = cache
+                .get_or_compute(dir, "repo", "complexity", params_key, || async move {
+                    Ok(serde_json::json!({ "score": score }))
+                })
+                .await
+                .unwrap();
+            assert_eq!(result.outcome, CacheOutcome::Miss);
+            assert_eq!(result.data["score"], score);
+        }
+        assert!(
+            CommitAnalysisCache::cache_key("repo", "complexity", &"x".repeat(4096), "c").len()
+                < 255
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_failure_returns_computed_result() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q"]);
+        commit_file(dir, "main.rs", "fn main() {}\n");
+
+        let cache_dir = tempdir().unwrap();
+        let storage = FileAnalysisStorage::new(cache_dir.path()).await.unwrap();
+        std::fs::remove_dir(cache_dir.path().join("analysis")).unwrap();
+        let cache = CommitAnalysisCache::new(Arc::new(storage));
+        let runs = AtomicUsize::new(0);
+
+        let result = run_analysis(&cache, dir, &runs).await;
+        assert_eq!(result.outcome, CacheOutcome::Miss);
+        assert_eq!(result.data["score"], 7);
+    }
+}
@@ -1,10 +1,11 @@
 //! Storage configuration
 
+use codeprism_core::RetryConfig;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::Duration;
 
-use crate::StorageBackend;
+use crate::{AnalysisBackend, CacheBackend, StorageBackend};
 
 /// Storage configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,8 +22,20 @@ pub struct StorageConfig {
     pub compression_enabled: bool,
     /// Retention period for analysis results
     pub retention_period: Duration,
-    /// Connection string for database backends
+    /// Connection string for database backends and the Redis cache
     pub connection_string: Option<String>,
+    /// Connection string for the PostgreSQL analysis storage
+    #[serde(default)]
+    pub analysis_connection_string: Option<String>,
+    /// Cache layer backend
+    #[serde(default)]
+    pub cache_backend: CacheBackend,
+    /// Analysis result storage backend
+    #[serde(default)]
+    pub analysis_backend: AnalysisBackend,
+    /// Retries of file writes that fail with transient I/O errors
+    #[serde(default)]
+    pub write_retry: WriteRetryConfig,
 }
 
 impl Default for StorageConfig {
@@ -35,6 +48,10 @@ impl Default for StorageConfig {
             compression_enabled: true,
             retention_period: Duration::from_secs(86400 * 7), // 1 week
             connection_string: None,
+            analysis_connection_string: None,
+            cache_backend: CacheBackend::Lru,
+            analysis_backend: AnalysisBackend::Local,
+            write_retry: WriteRetryConfig::default(),
         }
     }
 }
@@ -75,6 +92,15 @@ impl StorageConfig {
         }
     }
 
+    /// Create configuration for RocksDB storage
+    pub fn rocksdb(data_path: PathBuf) -> Self {
+        Self {
+            backend: StorageBackend::RocksDb,
+            data_path,
+            ..Default::default()
+        }
+    }
+
     /// Set cache size in megabytes
     pub fn with_cache_size(mut self, size_mb: usize) -> Self {
         self.cache_size_mb = size_mb;
@@ -99,6 +125,39 @@ impl StorageConfig {
         self
     }
 
+    /// Select the cache layer backend
+    pub fn with_cache_backend(mut self, backend: CacheBackend) -> Self {
+        self.cache_backend = backend;
+        self
+    }
+
+    /// Select where analysis results are stored
+    pub fn with_analysis_backend(mut self, backend: AnalysisBackend) -> Self {
+        self.analysis_backend = backend;
+        self
+    }
+
+    /// Set how transient file write failures are retried
+    pub fn with_write_retry(mut self, write_retry: WriteRetryConfig) -> Self {
+        self.write_retry = write_retry;
+        self
+    }
+
+    /// Set the connection string (e.g. `redis://localhost:6379/0`)
+    pub fn with_connection_string(mut self, connection_string: impl Into<String>) -> Self {
+        self.connection_string = Some(connection_string.into());
+        self
+    }
+
+    /// Set the analysis storage connection string (e.g. `postgres://localhost/codeprism`)
+    pub fn with_analysis_connection_string(
+        mut self,
+        analysis_connection_string: impl Into<String>,
+    ) -> Self {
+        self.analysis_connection_string = Some(analysis_connection_string.into());
+        self
+    }
+
     #[cfg(test)]
     pub fn default_for_testing(data_path: &std::path::Path) -> Self {
         Self {
@@ -109,6 +168,42 @@ impl StorageConfig {
             compression_enabled: false,
             retention_period: Duration::from_secs(86400),
             connection_string: None,
+            analysis_connection_string: None,
+            cache_backend: CacheBackend::Lru,
+            analysis_backend: AnalysisBackend::Local,
+            write_retry: WriteRetryConfig::default(),
+        }
+    }
+}
+
+/// Retry policy for file writes
+///
+/// Network filesystems can fail a write with EBUSY or EINTR that succeeds
+/// when repeated. Only such transient I/O errors are retried, with
+/// exponential backoff between attempts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteRetryConfig {
+    /// Attempts per write, including the first (1 disables retries)
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each later one
+    pub base_delay: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for WriteRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
         }
     }
 }
+
+impl WriteRetryConfig {
+    /// The equivalent resilience retry configuration
+    pub fn to_retry_config(&self) -> RetryConfig {
+        RetryConfig::new(self.max_attempts.max(1), self.base_delay).with_max_delay(self.max_delay)
+    }
+}
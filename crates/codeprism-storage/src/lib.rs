@@ -10,13 +10,19 @@ use std::collections::HashMap;
 use std::time::{Duration, SystemTime};
 
 pub mod backends;
+pub mod benchmark;
 pub mod cache;
+#[cfg(feature = "git")]
+pub mod commit_cache;
 pub mod config;
 pub mod graph;
 pub mod serialization;
 
 pub use backends::*;
+pub use benchmark::*;
 pub use cache::*;
+#[cfg(feature = "git")]
+pub use commit_cache::*;
 pub use config::*;
 pub use graph::*;
 
@@ -142,12 +148,34 @@ pub enum StorageBackend {
     InMemory,
     File,
     Sqlite,
+    /// RocksDB database under `data_path`, requires the `rocksdb` feature
+    RocksDb,
+}
+
+/// Cache layer backend type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CacheBackend {
+    /// In-process LRU cache, lost on restart
+    #[default]
+    Lru,
+    /// Redis server at `StorageConfig::connection_string`, shared between processes
+    Redis,
+}
+
+/// Analysis result storage backend type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnalysisBackend {
+    /// Follows `backend`: in memory for `InMemory`, JSON files under `data_path` otherwise
+    #[default]
+    Local,
+    /// PostgreSQL database at `StorageConfig::analysis_connection_string`, requires the `postgres` feature
+    Postgres,
 }
 
 /// Main storage manager that coordinates different storage backends
 pub struct StorageManager {
     graph_storage: Box<dyn GraphStorage>,
-    cache_storage: cache::LruCacheStorage,
+    cache_storage: cache::CacheLayer,
     analysis_storage: Box<dyn AnalysisStorage>,
     config: StorageConfig,
 }
@@ -156,7 +184,7 @@ impl StorageManager {
     /// Create a new storage manager with the specified configuration
     pub async fn new(config: StorageConfig) -> Result<Self> {
         let graph_storage = create_graph_storage(&config).await?;
-        let cache_storage = cache::LruCacheStorage::new(config.cache_size_mb * 1024 * 1024);
+        let cache_storage = cache::CacheLayer::from_config(&config).await;
         let analysis_storage = create_analysis_storage(&config).await?;
 
         Ok(Self {
@@ -173,7 +201,7 @@ impl StorageManager {
     }
 
     /// Get a reference to the cache storage
-    pub fn cache(&self) -> &cache::LruCacheStorage {
+    pub fn cache(&self) -> &cache::CacheLayer {
         &self.cache_storage
     }
 
@@ -204,16 +232,45 @@ async fn create_graph_storage(config: &StorageConfig) -> Result<Box<dyn GraphSto
     match config.backend {
         StorageBackend::InMemory => Ok(Box::new(backends::InMemoryGraphStorage::new())),
         StorageBackend::File => Ok(Box::new(
-            backends::FileGraphStorage::new(&config.data_path).await?,
+            backends::FileGraphStorage::new(&config.data_path)
+                .await?
+                .with_retry_config(config.write_retry.to_retry_config()),
         )),
         StorageBackend::Sqlite => Ok(Box::new(
             backends::SqliteGraphStorage::new(&config.data_path).await?,
         )),
+        #[cfg(feature = "rocksdb")]
+        StorageBackend::RocksDb => Ok(Box::new(
+            backends::RocksDbGraphStorage::new(&config.data_path).await?,
+        )),
+        #[cfg(not(feature = "rocksdb"))]
+        StorageBackend::RocksDb => Err(anyhow::anyhow!(
+            "RocksDB storage requested but codeprism-storage was built without the `rocksdb` feature"
+        )),
     }
 }
 
 /// Create appropriate analysis storage backend
 async fn create_analysis_storage(config: &StorageConfig) -> Result<Box<dyn AnalysisStorage>> {
+    if config.analysis_backend == AnalysisBackend::Postgres {
+        let url = config
+            .analysis_connection_string
+            .as_deref()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "PostgreSQL analysis storage requires an analysis_connection_string"
+                )
+            })?;
+        #[cfg(feature = "postgres")]
+        return Ok(Box::new(
+            backends::PostgresAnalysisStorage::connect(url).await?,
+        ));
+        #[cfg(not(feature = "postgres"))]
+        return Err(anyhow::anyhow!(
+            "PostgreSQL analysis storage at {url} requested but codeprism-storage was built without the `postgres` feature"
+        ));
+    }
+
     match config.backend {
         StorageBackend::InMemory => Ok(Box::new(backends::InMemoryAnalysisStorage::new())),
         _ => Ok(Box::new(
@@ -238,12 +295,49 @@ mod tests {
             compression_enabled: false,
             retention_period: Duration::from_secs(86400),
             connection_string: None,
+            analysis_connection_string: None,
+            cache_backend: CacheBackend::Lru,
+            analysis_backend: AnalysisBackend::Local,
+            write_retry: WriteRetryConfig::default(),
         };
 
         let storage = StorageManager::new(config).await.unwrap();
         assert_eq!(storage.config().backend, StorageBackend::InMemory);
     }
 
+    #[cfg(not(feature = "postgres"))]
+    #[tokio::test]
+    async fn test_postgres_analysis_backend_requires_feature() {
+        let config = StorageConfig::in_memory()
+            .with_analysis_backend(AnalysisBackend::Postgres)
+            .with_analysis_connection_string("postgres://localhost/codeprism");
+
+        let error = StorageManager::new(config).await.err().unwrap();
+        assert!(error.to_string().contains("`postgres` feature"));
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    #[tokio::test]
+    async fn test_redis_cache_and_postgres_analysis_use_separate_urls() {
+        let config = StorageConfig::in_memory()
+            .with_cache_backend(CacheBackend::Redis)
+            .with_connection_string("redis://127.0.0.1:1/")
+            .with_analysis_backend(AnalysisBackend::Postgres);
+
+        // The cache URL is not picked up by the analysis storage
+        let error = create_analysis_storage(&config).await.err().unwrap();
+        assert!(error.to_string().contains("analysis_connection_string"));
+
+        let config = config.with_analysis_connection_string("postgres://localhost/codeprism");
+        let error = create_analysis_storage(&config).await.err().unwrap();
+        let message = error.to_string();
+        assert!(message.contains("postgres://localhost/codeprism"));
+        assert!(!message.contains("redis://"));
+
+        let cache = CacheLayer::from_config(&config).await;
+        assert_eq!(cache.backend(), CacheBackend::Lru);
+    }
+
     #[tokio::test]
     async fn test_cache_operations() {
         let temp_dir = tempdir().unwrap();
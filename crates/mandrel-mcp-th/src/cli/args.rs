@@ -50,6 +50,9 @@ pub enum Commands {
 
     /// Watch files and auto-generate reports
     Watch(WatchArgs),
+
+    /// Record benchmark runs and chart latency trends across them
+    BenchmarkHistory(BenchmarkHistoryArgs),
 }
 
 #[derive(Args, Debug)]
@@ -116,6 +119,22 @@ pub struct RunArgs {
     /// Stop execution on first test failure
     #[arg(long)]
     pub fail_fast: bool,
+
+    /// Number of times to run the suite when collecting latency samples
+    #[arg(long, default_value = "1")]
+    pub iterations: usize,
+
+    /// Write per-test median/p95 latencies of this run to a baseline file
+    #[arg(long)]
+    pub save_baseline: Option<PathBuf>,
+
+    /// Compare per-test p95 latencies against a saved baseline file
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Allowed p95 latency increase over the baseline, in percent
+    #[arg(long, default_value = "20.0")]
+    pub regression_threshold: f64,
 }
 
 #[derive(Args, Debug)]
@@ -287,6 +306,37 @@ pub struct ProfileShowArgs {
     pub detailed: bool,
 }
 
+#[derive(Args, Debug)]
+pub struct BenchmarkHistoryArgs {
+    /// Test results file (JSON format) to record as a new run before charting
+    #[arg(short = 'i', long)]
+    pub input: Option<PathBuf>,
+
+    /// Commit the recorded run was measured on
+    #[arg(long)]
+    pub commit: Option<String>,
+
+    /// Suite whose history to chart (defaults to the suite in --input)
+    #[arg(long)]
+    pub suite: Option<String>,
+
+    /// Directory holding the benchmark history
+    #[arg(long, default_value = "./.moth/benchmarks")]
+    pub storage_dir: PathBuf,
+
+    /// Number of runs averaged per moving-average point
+    #[arg(long, default_value = "3")]
+    pub window: usize,
+
+    /// Moving-average latency increase in percent reported as a regression
+    #[arg(long, default_value = "10")]
+    pub threshold: f64,
+
+    /// Fail with non-zero exit code if a regression is detected
+    #[arg(long)]
+    pub fail_on_regression: bool,
+}
+
 #[derive(Args, Debug)]
 pub struct WatchArgs {
     #[command(subcommand)]
@@ -353,6 +403,7 @@ pub enum TemplateName {
     Executive,
     Technical,
     Minimal,
+    Markdown,
 }
 
 #[derive(ValueEnum, Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
@@ -428,6 +479,7 @@ impl TemplateName {
             TemplateName::Executive => "executive",
             TemplateName::Technical => "technical",
             TemplateName::Minimal => "minimal",
+            TemplateName::Markdown => "markdown",
         }
     }
 }
@@ -6,7 +6,9 @@ use crate::executor::{ExecutorConfig, TestCaseExecutor};
 use crate::reporting::{
     BrandingInfo, BuiltInTemplate, ReportConfig, ReportGenerator, TemplateSource,
 };
-use crate::runner::{RunnerConfig, TestSuiteResult, TestSuiteRunner};
+use crate::runner::{
+    BenchmarkBaseline, RegressionReport, RunnerConfig, TestSuiteResult, TestSuiteRunner,
+};
 use crate::spec::SpecificationLoader;
 use clap::Parser;
 use codeprism_utils::{ChangeEvent, FileWatcher};
@@ -45,6 +47,9 @@ impl CliApp {
             Commands::Validate(validate_args) => self.handle_validate_command(validate_args).await,
             Commands::Profile(profile_args) => self.handle_profile_command(profile_args).await,
             Commands::Watch(watch_args) => self.handle_watch_command(watch_args).await,
+            Commands::BenchmarkHistory(history_args) => {
+                self.handle_benchmark_history_command(history_args).await
+            }
         }
     }
 
@@ -81,6 +86,7 @@ impl CliApp {
                 TemplateName::Executive => BuiltInTemplate::ExecutiveSummary,
                 TemplateName::Technical => BuiltInTemplate::TechnicalDetailed,
                 TemplateName::Minimal => BuiltInTemplate::Minimal,
+                TemplateName::Markdown => BuiltInTemplate::Markdown,
             }))
         } else {
             Some(TemplateSource::BuiltIn(BuiltInTemplate::Professional))
@@ -102,7 +108,7 @@ impl CliApp {
         let report_generator = ReportGenerator::new(report_config)?;
 
         // Convert TestSuiteResult to SuiteResult for ReportGenerator compatibility
-        let suite_result_converted = self.convert_to_suite_result(&suite_result);
+        let suite_result_converted = Self::convert_to_suite_result(&suite_result);
 
         println!(
             "📝 Generating comprehensive reports in {} formats...",
@@ -197,8 +203,15 @@ impl CliApp {
             .with_fail_fast(args.fail_fast);
         let mut runner = TestSuiteRunner::new(executor, runner_config);
 
-        // 5. Execute the test suite
-        let suite_result = runner.run_test_suite(&args.config).await?;
+        // 5. Execute the test suite, repeating it when collecting latency samples
+        let mut iteration_results = Vec::with_capacity(args.iterations.max(1));
+        for _ in 0..args.iterations.max(1) {
+            iteration_results.push(runner.run_test_suite(&args.config).await?);
+        }
+        let suite_result = iteration_results
+            .last()
+            .cloned()
+            .expect("at least one iteration runs");
 
         // 6. Generate comprehensive reports using the advanced reporting system
         if let Some(output_dir) = &args.output {
@@ -222,7 +235,7 @@ impl CliApp {
             let report_generator = ReportGenerator::new(report_config)?;
 
             // Convert TestSuiteResult to SuiteResult for ReportGenerator compatibility
-            let suite_result_converted = self.convert_to_suite_result(&suite_result);
+            let suite_result_converted = Self::convert_to_suite_result(&suite_result);
 
             // Generate default reports: JSON, HTML, and JUnit
             let default_formats = vec![ReportFormat::Json, ReportFormat::Html, ReportFormat::Junit];
@@ -255,9 +268,33 @@ impl CliApp {
             }
         }
 
-        // 7. Display summary and return exit code
+        // 7. Save or compare benchmark baselines
+        let current_baseline = BenchmarkBaseline::from_results(&iteration_results);
+        if let Some(path) = &args.save_baseline {
+            current_baseline.save(path)?;
+            println!("💾 Saved benchmark baseline: {}", path.display());
+        }
+        let regression_report = match &args.baseline {
+            Some(path) => Some(
+                BenchmarkBaseline::load(path)?
+                    .compare(&current_baseline, args.regression_threshold),
+            ),
+            None => None,
+        };
+
+        // 8. Display summary and return exit code
         self.display_summary(&suite_result);
-        Ok(if suite_result.failed == 0 { 0 } else { 1 })
+        if let Some(report) = &regression_report {
+            self.display_regression_report(report);
+        }
+        let regressed = regression_report
+            .as_ref()
+            .is_some_and(|report| report.has_regressions());
+        Ok(if suite_result.failed == 0 && !regressed {
+            0
+        } else {
+            1
+        })
     }
 
     fn display_summary(&self, result: &TestSuiteResult) {
@@ -270,6 +307,38 @@ impl CliApp {
         println!("Duration: {:.2}s", result.total_duration.as_secs_f64());
     }
 
+    fn display_regression_report(&self, report: &RegressionReport) {
+        println!(
+            "\n📈 Regression Report (p95 threshold: {:.1}%)",
+            report.threshold_percent
+        );
+        for comparison in &report.comparisons {
+            let marker = if comparison.regressed { "🔴" } else { "✅" };
+            println!(
+                "  {} {}: p95 {:.2}ms -> {:.2}ms ({:+.1}%), median {:.2}ms",
+                marker,
+                comparison.test_name,
+                comparison.baseline_p95_ms,
+                comparison.current_p95_ms,
+                comparison.change_percent,
+                comparison.current_median_ms
+            );
+        }
+        for name in &report.missing_from_baseline {
+            println!("  ➕ {name}: not in baseline");
+        }
+        for name in &report.missing_from_run {
+            println!("  ➖ {name}: in baseline but not measured in this run");
+        }
+
+        let regressions = report.regressions().count();
+        if regressions > 0 {
+            println!("❌ {regressions} test(s) regressed beyond the threshold");
+        } else {
+            println!("✅ No latency regressions detected");
+        }
+    }
+
     async fn handle_validate_command(&self, args: &ValidateArgs) -> Result<i32> {
         // 1. Load and parse configuration file
         let spec_loader = SpecificationLoader::new()?;
@@ -881,7 +950,7 @@ impl CliApp {
     }
 
     /// Convert TestSuiteResult to SuiteResult for ReportGenerator compatibility
-    fn convert_to_suite_result(&self, test_suite_result: &TestSuiteResult) -> SuiteResult {
+    fn convert_to_suite_result(test_suite_result: &TestSuiteResult) -> SuiteResult {
         use crate::executor::{PerformanceMetrics, TestResult as ExecutorTestResult, TestStatus};
         use chrono::DateTime;
 
@@ -1099,6 +1168,108 @@ impl CliApp {
 
     // Helper functions for profile and watch commands
 
+    async fn handle_benchmark_history_command(&self, args: &BenchmarkHistoryArgs) -> Result<i32> {
+        use codeprism_storage::{
+            BenchmarkHistory, BenchmarkRun, FileAnalysisStorage, RegressionConfig,
+        };
+
+        let storage_error =
+            |e: anyhow::Error| crate::error::Error::config(format!("Benchmark history error: {e}"));
+
+        let storage = FileAnalysisStorage::new(&args.storage_dir)
+            .await
+            .map_err(storage_error)?;
+
+        // 1. Record the new run, if one was given
+        let mut suite_name = args.suite.clone();
+        if let Some(input) = &args.input {
+            let content = tokio::fs::read_to_string(input).await.map_err(|e| {
+                crate::error::Error::config(format!("Failed to read test results file: {e}"))
+            })?;
+            let suite_result: TestSuiteResult = serde_json::from_str(&content).map_err(|e| {
+                crate::error::Error::config(format!("Failed to parse test results JSON: {e}"))
+            })?;
+
+            // Failed tests often return early, so their latency is not comparable
+            let latencies_ms = suite_result
+                .test_results
+                .iter()
+                .filter(|test| test.success)
+                .map(|test| (test.test_name.clone(), test.duration.as_secs_f64() * 1000.0))
+                .collect();
+            let run = BenchmarkRun::new(args.commit.clone(), latencies_ms);
+
+            let suite = suite_name.get_or_insert(suite_result.suite_name);
+            BenchmarkHistory::new(&storage, suite.as_str())
+                .record(&run)
+                .await
+                .map_err(storage_error)?;
+            println!(
+                "💾 Recorded {} test latencies for suite '{}'",
+                run.latencies_ms.len(),
+                suite
+            );
+        }
+
+        let Some(suite_name) = suite_name else {
+            return Err(crate::error::Error::config(
+                "Either --input or --suite is required",
+            ));
+        };
+
+        // 2. Chart the moving-average trend of every test
+        let history = BenchmarkHistory::new(&storage, suite_name.as_str());
+        let runs = history.runs().await.map_err(storage_error)?;
+        let config = RegressionConfig {
+            window: args.window,
+            threshold_percent: args.threshold,
+        };
+        let trends = codeprism_storage::analyze_trends(&runs, &config);
+
+        println!(
+            "\n📈 Benchmark history for '{}' ({} runs, moving average over {})",
+            suite_name,
+            runs.len(),
+            config.window.max(1)
+        );
+        if trends.is_empty() {
+            println!(
+                "  Not enough runs yet, need at least {}",
+                config.window.max(1) + 1
+            );
+        }
+        for trend in &trends {
+            let marker = if trend.gradual {
+                "🐢 gradual regression"
+            } else if trend.regressed {
+                "⚠️  regression"
+            } else {
+                "✅"
+            };
+            println!(
+                "  {:<40} {} {:>+7.1}%  {}",
+                trend.test_name,
+                trend.sparkline(),
+                trend.change_percent,
+                marker
+            );
+        }
+
+        let regressions = trends.iter().filter(|trend| trend.regressed).count();
+        if regressions > 0 {
+            println!(
+                "\n❌ {regressions} test(s) regressed by more than {:.1}%",
+                config.threshold_percent
+            );
+        }
+
+        Ok(if args.fail_on_regression && regressions > 0 {
+            1
+        } else {
+            0
+        })
+    }
+
     fn expand_config_path(&self, path: &std::path::Path) -> std::path::PathBuf {
         if path.starts_with("~") {
             if let Ok(home) = std::env::var("HOME") {
@@ -1127,6 +1298,7 @@ impl CliApp {
                         TemplateName::Executive => BuiltInTemplate::ExecutiveSummary,
                         TemplateName::Technical => BuiltInTemplate::TechnicalDetailed,
                         TemplateName::Minimal => BuiltInTemplate::Minimal,
+                        TemplateName::Markdown => BuiltInTemplate::Markdown,
                     })
                 }),
                 branding: BrandingInfo::default(),
@@ -1299,6 +1471,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_argument_parsing_benchmark_history_command() {
+        let cli = Cli::parse_from([
+            "mandrel-mcp-th",
+            "benchmark-history",
+            "--input",
+            "test-results.json",
+            "--commit",
+            "abc123",
+            "--window",
+            "5",
+            "--fail-on-regression",
+        ]);
+
+        match cli.command {
+            Commands::BenchmarkHistory(args) => {
+                assert_eq!(args.input.unwrap().to_string_lossy(), "test-results.json");
+                assert_eq!(args.commit.as_deref(), Some("abc123"));
+                assert_eq!(args.window, 5);
+                assert_eq!(args.threshold, 10.0);
+                assert!(args.fail_on_regression);
+            }
+            _ => panic!("Expected BenchmarkHistory command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_argument_parsing_run_with_baseline() {
+        let cli = Cli::parse_from([
+            "mandrel-mcp-th",
+            "run",
+            "spec.yaml",
+            "--iterations",
+            "5",
+            "--baseline",
+            "baseline.json",
+            "--regression-threshold",
+            "15",
+        ]);
+
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.iterations, 5);
+                assert_eq!(args.baseline.unwrap().to_string_lossy(), "baseline.json");
+                assert!(args.save_baseline.is_none());
+                assert_eq!(args.regression_threshold, 15.0);
+            }
+            _ => panic!("Expected Run command"),
+        }
+    }
+
     #[test]
     fn test_cli_argument_parsing_with_template() {
         let cli = Cli::parse_from([
@@ -1766,6 +1989,69 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_watch_mode_writes_html_and_json_reports() {
+        use crate::runner::result::TestResult;
+        use std::time::SystemTime;
+
+        let temp_dir = TempDir::new().expect("Should create temp dir");
+        let input_file = temp_dir.path().join("test-results.json");
+        let output_dir = temp_dir.path().join("reports");
+
+        let test_results = vec![
+            TestResult::success("list_tools".to_string(), Duration::from_millis(40)),
+            TestResult::failure(
+                "call_tool".to_string(),
+                Duration::from_millis(75),
+                "unexpected response field".to_string(),
+            ),
+        ];
+        let suite = TestSuiteResult {
+            suite_name: "trivial".to_string(),
+            specification_file: PathBuf::from("trivial.yaml"),
+            execution_start: SystemTime::now(),
+            execution_end: SystemTime::now(),
+            total_duration: Duration::from_millis(115),
+            total_tests: 2,
+            passed: 1,
+            failed: 1,
+            skipped: 0,
+            error_rate: 0.5,
+            test_results,
+            suite_metrics: Default::default(),
+            execution_mode: Default::default(),
+            dependency_resolution: Default::default(),
+        };
+        std::fs::write(&input_file, serde_json::to_string(&suite).unwrap())
+            .expect("Should write test results");
+
+        let watch_manager = WatchManager::new(WatchConfig {
+            input_patterns: vec![input_file.to_string_lossy().to_string()],
+            output_directory: output_dir.clone(),
+            debounce_ms: 100,
+            formats: vec![ReportFormat::Html, ReportFormat::Json],
+            auto_open: false,
+        })
+        .expect("Should create watch manager");
+        watch_manager
+            .handle_change_event(ChangeEvent::new(
+                temp_dir.path().to_path_buf(),
+                input_file.clone(),
+                codeprism_utils::ChangeKind::Modified,
+            ))
+            .await
+            .expect("Should generate reports");
+
+        for name in ["report.html", "report.json"] {
+            let content = std::fs::read_to_string(output_dir.join(name))
+                .unwrap_or_else(|e| panic!("{name} should exist: {e}"));
+            assert!(!content.is_empty(), "{name} should not be empty");
+        }
+        let html = std::fs::read_to_string(output_dir.join("report.html")).unwrap();
+        assert!(html.contains("call_tool"));
+        assert!(html.contains("unexpected response field"));
+    }
+
     #[tokio::test]
     async fn test_watch_mode_debouncing() {
         let temp_dir = TempDir::new().expect("Should create temp dir");
@@ -2460,9 +2746,11 @@ impl WatchManager {
 
         tracing::info!("File change detected: {:?} -> {:?}", event.kind, event.path);
 
+        let suite_result = Self::load_suite_result(&event.path).await?;
+
         // Generate reports for all configured formats
         for format in &self.config.formats {
-            if let Err(e) = self.generate_report(&event.path, format).await {
+            if let Err(e) = self.generate_report(&suite_result, format).await {
                 tracing::error!(
                     "Failed to generate {} report for {}: {}",
                     format_to_string(format),
@@ -2495,34 +2783,60 @@ impl WatchManager {
         false
     }
 
-    async fn generate_report(&self, _input_path: &Path, format: &ReportFormat) -> Result<()> {
-        // Placeholder for actual report generation
-        // In real implementation, this would:
-        // 1. Read the input file
-        // 2. Parse test results
-        // 3. Generate report in specified format
-        // 4. Write to output directory
+    /// Load test results written by a previous run
+    async fn load_suite_result(input_path: &Path) -> Result<SuiteResult> {
+        let content = tokio::fs::read_to_string(input_path).await.map_err(|e| {
+            crate::error::Error::config(format!("Failed to read test results file: {e}"))
+        })?;
+        let suite_result: TestSuiteResult = serde_json::from_str(&content).map_err(|e| {
+            crate::error::Error::config(format!("Failed to parse test results JSON: {e}"))
+        })?;
+        Ok(CliApp::convert_to_suite_result(&suite_result))
+    }
 
-        tracing::info!(
-            "Generating {} report for input file",
-            format_to_string(format)
-        );
+    /// Write `report.<ext>` for one format into the output directory
+    async fn generate_report(
+        &self,
+        suite_result: &SuiteResult,
+        format: &ReportFormat,
+    ) -> Result<()> {
+        tracing::info!("Generating {} report", format_to_string(format));
 
-        // Simulate report generation
-        let output_file = self.config.output_directory.join(format!(
-            "report.{}",
-            match format {
-                ReportFormat::Json => "json",
-                ReportFormat::Html => "html",
-                ReportFormat::Junit => "xml",
-                ReportFormat::Markdown => "md",
-            }
-        ));
+        let report_generator = ReportGenerator::new(ReportConfig {
+            output_directory: Some(self.config.output_directory.clone()),
+            ..ReportConfig::default()
+        })?;
+        let report_content = match format {
+            ReportFormat::Json => report_generator.generate_json(suite_result)?,
+            ReportFormat::Html => report_generator.generate_html(suite_result)?,
+            ReportFormat::Junit => report_generator.generate_junit_xml(suite_result)?,
+            ReportFormat::Markdown => report_generator.generate_markdown(suite_result)?,
+        };
 
-        tokio::fs::write(&output_file, "Generated report content")
+        tokio::fs::create_dir_all(&self.config.output_directory)
+            .await
+            .map_err(|e| {
+                crate::error::Error::execution(format!("Failed to create output directory: {e}"))
+            })?;
+        let output_file = self
+            .config
+            .output_directory
+            .join(format!("report.{}", format.file_extension()));
+        tokio::fs::write(&output_file, report_content)
             .await
             .map_err(|e| crate::error::Error::execution(format!("Failed to write report: {e}")))?;
 
+        let written = tokio::fs::metadata(&output_file)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        if written == 0 {
+            return Err(crate::error::Error::execution(format!(
+                "Report was not written: {}",
+                output_file.display()
+            )));
+        }
+
         tracing::info!("Report generated: {}", output_file.display());
         Ok(())
     }
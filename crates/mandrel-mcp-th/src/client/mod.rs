@@ -538,15 +538,17 @@ impl ServerProcess {
         );
 
         let mut cmd = Command::new(&config.command);
+        let expand =
+            |value: &str| crate::spec::expand_env_vars(value, |name| std::env::var(name).ok());
 
         // Add arguments
         for arg in &config.args {
-            cmd.arg(arg);
+            cmd.arg(expand(arg)?);
         }
 
         // Set environment variables
         for (key, value) in &config.env {
-            cmd.env(key, value);
+            cmd.env(key, expand(value)?);
         }
 
         // Set working directory
@@ -822,6 +824,47 @@ mod tests {
         assert!(result.is_ok(), "Operation should succeed");
     }
 
+    #[tokio::test]
+    async fn test_server_process_expands_env_references() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ServerConfig {
+            command: "sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                "printf %s \"$GREETING\" > ${MANDREL_TEST_UNSET_VAR:-greeting.txt}".to_string(),
+            ],
+            env: [(
+                "GREETING".to_string(),
+                "${MANDREL_TEST_UNSET_VAR:-hello}$$".to_string(),
+            )]
+            .into(),
+            working_dir: Some(temp_dir.path().to_path_buf()),
+            ..create_test_config()
+        };
+        let mut process = ServerProcess::start(&config).await.unwrap();
+
+        let output = temp_dir.path().join("greeting.txt");
+        for _ in 0..100 {
+            if std::fs::read_to_string(&output).is_ok_and(|text| !text.is_empty()) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "hello$");
+        let _ = process.stop().await;
+
+        let config = ServerConfig {
+            env: [(
+                "API_KEY".to_string(),
+                "${MANDREL_TEST_UNSET_VAR}".to_string(),
+            )]
+            .into(),
+            ..create_test_config()
+        };
+        let error = ServerProcess::start(&config).await.err().unwrap();
+        assert!(error.to_string().contains("MANDREL_TEST_UNSET_VAR"));
+    }
+
     #[test]
     fn test_server_config_default() {
         let config = ServerConfig::default();
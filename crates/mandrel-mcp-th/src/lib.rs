@@ -58,6 +58,10 @@
 //!         // File watching and auto-generation
 //!         println!("Starting file watch mode");
 //!     }
+//!     Commands::BenchmarkHistory(_history_args) => {
+//!         // Benchmark trend tracking
+//!         println!("Charting benchmark history");
+//!     }
 //! }
 //! # Ok(())
 //! # }
@@ -142,6 +142,8 @@ pub enum BuiltInTemplate {
     ExecutiveSummary,
     TechnicalDetailed,
     Minimal,
+    /// GitHub-flavoured Markdown suited to pull request comments
+    Markdown,
 }
 
 /// Template source specification
@@ -214,7 +216,7 @@ impl ReportGenerator {
 
         // Set test suite metadata
         test_suite.set_timestamp(test_report.metadata.generated_at);
-        test_suite.set_time(std::time::Duration::from_secs(results.duration.as_secs()));
+        test_suite.set_time(results.duration);
 
         // Add properties for metadata and environment info
         let properties = vec![
@@ -292,21 +294,25 @@ impl ReportGenerator {
         let mut test_cases = Vec::new();
         for test_result in &results.test_results {
             // Determine test case status
-            let status = match test_result.status {
+            let mut status = match test_result.status {
                 TestStatus::Passed => TestCaseStatus::success(),
                 TestStatus::Failed => TestCaseStatus::non_success(NonSuccessKind::Failure),
                 TestStatus::Error => TestCaseStatus::non_success(NonSuccessKind::Error),
                 TestStatus::Timeout => TestCaseStatus::non_success(NonSuccessKind::Error),
                 TestStatus::Skipped => TestCaseStatus::skipped(),
             };
+            // CI tools show the message attribute as the failure summary and
+            // the element text as its details
+            if let Some(error_msg) = &test_result.error_message {
+                status.set_message(error_msg.lines().next().unwrap_or_default());
+                status.set_description(error_msg);
+            }
 
             let mut test_case = TestCase::new(&test_result.test_name, status);
 
             // Set test case metadata
             test_case.set_classname(&test_result.suite_name);
-            test_case.set_time(std::time::Duration::from_millis(
-                test_result.duration.as_millis() as u64,
-            ));
+            test_case.set_time(test_result.duration);
 
             // Add extra attributes for CI/CD compatibility
             test_case
@@ -357,6 +363,13 @@ impl ReportGenerator {
             .as_ref()
             .cloned()
             .unwrap_or(TemplateSource::BuiltIn(BuiltInTemplate::Professional));
+        // The Markdown template has no HTML counterpart
+        let template_source = match template_source {
+            TemplateSource::BuiltIn(BuiltInTemplate::Markdown) => {
+                TemplateSource::BuiltIn(BuiltInTemplate::Professional)
+            }
+            other => other,
+        };
 
         // Create template renderer and render
         let mut renderer = TemplateRenderer::new()?;
@@ -365,6 +378,12 @@ impl ReportGenerator {
 
     /// Generate Markdown report
     pub fn generate_markdown(&self, results: &SuiteResult) -> Result<String> {
+        if let Some(TemplateSource::BuiltIn(BuiltInTemplate::Markdown)) =
+            &self.config.template_source
+        {
+            return self.generate_markdown_summary(std::slice::from_ref(results));
+        }
+
         // Create test report for metadata
         let test_report = self.create_test_report(results)?;
 
@@ -711,6 +730,116 @@ impl ReportGenerator {
         Ok(markdown)
     }
 
+    /// Generate a Markdown summary of one or more suites for pull request comments
+    ///
+    /// Suites are sorted by name and no timestamps are emitted, so the output
+    /// of two runs only differs where the results do. Failures are listed in
+    /// collapsible `<details>` blocks with the error and the JSON-RPC payload.
+    pub fn generate_markdown_summary(&self, suites: &[SuiteResult]) -> Result<String> {
+        let mut suites: Vec<&SuiteResult> = suites.iter().collect();
+        suites.sort_by(|a, b| a.suite_name.cmp(&b.suite_name));
+
+        let mut markdown = String::new();
+        match &self.config.branding.company_name {
+            Some(company_name) => {
+                markdown.push_str(&format!("## {company_name} MCP Test Report\n\n"))
+            }
+            None => markdown.push_str("## MCP Test Report\n\n"),
+        }
+
+        markdown.push_str("| Suite | Total | Passed | Failed | Skipped | Duration |\n");
+        markdown.push_str("|-------|------:|-------:|-------:|--------:|---------:|\n");
+        for suite in &suites {
+            let count = |wanted: &[TestStatus]| {
+                suite
+                    .test_results
+                    .iter()
+                    .filter(|r| wanted.contains(&r.status))
+                    .count()
+            };
+            let passed = count(&[TestStatus::Passed]);
+            let skipped = count(&[TestStatus::Skipped]);
+            let failed = suite.test_results.len() - passed - skipped;
+            markdown.push_str(&format!(
+                "| {} {} | {} | {} | {} | {} | {}ms |\n",
+                if failed == 0 { "✅" } else { "❌" },
+                self.escape_markdown_if_needed(&suite.suite_name),
+                suite.test_results.len(),
+                passed,
+                failed,
+                skipped,
+                suite.duration.as_millis()
+            ));
+        }
+        markdown.push('\n');
+
+        for suite in &suites {
+            markdown.push_str(&format!(
+                "### {}\n\n",
+                self.escape_markdown_if_needed(&suite.suite_name)
+            ));
+            if suite.test_results.is_empty() {
+                markdown.push_str("_No tests were executed._\n\n");
+                continue;
+            }
+
+            markdown.push_str("| Test | Status | Duration |\n");
+            markdown.push_str("|------|--------|---------:|\n");
+            for test_result in &suite.test_results {
+                markdown.push_str(&format!(
+                    "| {} | {} | {}ms |\n",
+                    self.escape_markdown_if_needed(&test_result.test_name),
+                    Self::markdown_status(&test_result.status),
+                    test_result.duration.as_millis()
+                ));
+            }
+            markdown.push('\n');
+
+            for test_result in suite.test_results.iter().filter(|r| {
+                matches!(
+                    r.status,
+                    TestStatus::Failed | TestStatus::Error | TestStatus::Timeout
+                )
+            }) {
+                markdown.push_str("<details>\n");
+                markdown.push_str(&format!(
+                    "<summary>{} <code>{}</code></summary>\n\n",
+                    Self::markdown_status(&test_result.status),
+                    Self::escape_html(&test_result.test_name)
+                ));
+                if let Some(error) = &test_result.error_message {
+                    markdown.push_str("```\n");
+                    markdown.push_str(error);
+                    markdown.push_str("\n```\n\n");
+                }
+                if let Some(payload) = &test_result.response_data {
+                    markdown.push_str("**JSON-RPC payload:**\n\n```json\n");
+                    markdown.push_str(&serde_json::to_string_pretty(payload)?);
+                    markdown.push_str("\n```\n\n");
+                }
+                markdown.push_str("</details>\n\n");
+            }
+        }
+
+        Ok(markdown)
+    }
+
+    fn markdown_status(status: &TestStatus) -> &'static str {
+        match status {
+            TestStatus::Passed => "✅ passed",
+            TestStatus::Failed => "❌ failed",
+            TestStatus::Error => "❌ error",
+            TestStatus::Timeout => "⏰ timeout",
+            TestStatus::Skipped => "⏩ skipped",
+        }
+    }
+
+    fn escape_html(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
     /// Escape special Markdown characters only if they exist
     fn escape_markdown_if_needed(&self, text: &str) -> String {
         // Check if text contains any problematic Markdown characters
@@ -819,6 +948,7 @@ impl BuiltInTemplate {
             BuiltInTemplate::ExecutiveSummary => "executive-summary",
             BuiltInTemplate::TechnicalDetailed => "technical-detailed",
             BuiltInTemplate::Minimal => "minimal",
+            BuiltInTemplate::Markdown => "markdown",
         }
     }
 
@@ -835,6 +965,9 @@ impl BuiltInTemplate {
                 "Technical detailed report for developers and engineers"
             }
             BuiltInTemplate::Minimal => "Minimal clean report with essential information only",
+            BuiltInTemplate::Markdown => {
+                "Markdown summary for pull request comments with collapsible failures"
+            }
         }
     }
 }
@@ -2036,3 +2169,156 @@ mod tests {
 
 }
 */
+
+#[cfg(test)]
+mod markdown_summary_tests {
+    use super::*;
+    use chrono::Utc;
+    use std::time::Duration;
+
+    fn test_result(name: &str, suite: &str, status: TestStatus) -> TestResult {
+        let failed = status != TestStatus::Passed;
+        TestResult {
+            test_name: name.to_string(),
+            suite_name: suite.to_string(),
+            status,
+            error_message: failed.then(|| "expected 3 tools, got 2".to_string()),
+            start_time: Utc::now(),
+            duration: Duration::from_millis(40),
+            response_data: failed
+                .then(|| serde_json::json!({"jsonrpc": "2.0", "id": 7, "result": {"tools": []}})),
+            performance: Default::default(),
+        }
+    }
+
+    fn suite(name: &str, results: Vec<TestResult>) -> SuiteResult {
+        SuiteResult {
+            suite_name: name.to_string(),
+            start_time: Utc::now(),
+            duration: Duration::from_millis(120),
+            passed: results
+                .iter()
+                .filter(|r| r.status == TestStatus::Passed)
+                .count(),
+            failed: results
+                .iter()
+                .filter(|r| r.status == TestStatus::Failed)
+                .count(),
+            errors: 0,
+            skipped: 0,
+            total_tests: results.len(),
+            test_results: results,
+        }
+    }
+
+    #[test]
+    fn test_markdown_summary_is_sorted_and_collapses_failures() {
+        let generator = ReportGenerator::new(ReportConfig {
+            template_source: Some(TemplateSource::BuiltIn(BuiltInTemplate::Markdown)),
+            branding: BrandingInfo {
+                company_name: Some("Acme".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        let tools = suite(
+            "tools",
+            vec![
+                test_result("list_tools", "tools", TestStatus::Passed),
+                test_result("call_tool", "tools", TestStatus::Failed),
+            ],
+        );
+        let auth = suite(
+            "auth",
+            vec![test_result("login", "auth", TestStatus::Passed)],
+        );
+
+        let markdown = generator
+            .generate_markdown_summary(&[tools.clone(), auth.clone()])
+            .unwrap();
+        assert!(markdown.starts_with("## Acme MCP Test Report\n"));
+        assert!(markdown.contains("| ✅ auth | 1 | 1 | 0 | 0 | 120ms |"));
+        assert!(markdown.contains("| ❌ tools | 2 | 1 | 1 | 0 | 120ms |"));
+        assert!(markdown.find("### auth").unwrap() < markdown.find("### tools").unwrap());
+        assert_eq!(markdown.matches("<details>").count(), 1);
+        assert!(markdown.contains("<summary>❌ failed <code>call_tool</code></summary>"));
+        assert!(markdown.contains("expected 3 tools, got 2"));
+        assert!(markdown.contains("\"jsonrpc\": \"2.0\""));
+
+        // Input order does not change the output
+        assert_eq!(
+            markdown,
+            generator
+                .generate_markdown_summary(&[auth, tools.clone()])
+                .unwrap()
+        );
+
+        // The Markdown template routes single-suite reports through the summary
+        let single = generator.generate_markdown(&tools).unwrap();
+        assert!(single.contains("### tools"));
+        assert!(!single.starts_with("---"));
+    }
+}
+
+#[cfg(test)]
+mod junit_xml_tests {
+    use super::*;
+    use chrono::Utc;
+    use std::time::Duration;
+
+    fn test_result(name: &str, status: TestStatus, error_message: Option<&str>) -> TestResult {
+        TestResult {
+            test_name: name.to_string(),
+            suite_name: "Tools & <Resources>".to_string(),
+            status,
+            error_message: error_message.map(str::to_string),
+            start_time: Utc::now(),
+            duration: Duration::from_millis(1250),
+            response_data: None,
+            performance: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_junit_xml_failures_skips_durations_and_escaping() {
+        let generator = ReportGenerator::new(ReportConfig::default()).unwrap();
+        let results = SuiteResult {
+            suite_name: "Tools & <Resources>".to_string(),
+            start_time: Utc::now(),
+            duration: Duration::from_millis(2500),
+            passed: 1,
+            failed: 1,
+            errors: 0,
+            skipped: 1,
+            total_tests: 3,
+            test_results: vec![
+                test_result("list_tools", TestStatus::Passed, None),
+                test_result(
+                    "search \"a<b\"",
+                    TestStatus::Failed,
+                    Some("Assertion failed: $.count < 3\nactual: 5"),
+                ),
+                test_result(
+                    "resources",
+                    TestStatus::Skipped,
+                    Some("server has no resources"),
+                ),
+            ],
+        };
+
+        let xml = generator.generate_junit_xml(&results).unwrap();
+
+        // The first line of the error is the failure message, all of it the text
+        assert!(xml.contains("<failure message=\"Assertion failed: $.count &lt; 3\">"));
+        assert!(xml.contains("actual: 5</failure>"));
+        assert!(xml.contains("<skipped message=\"server has no resources\">"));
+        // Durations are in seconds, keeping fractions
+        assert!(xml.contains("time=\"2.500\""));
+        assert!(xml.contains("time=\"1.250\""));
+        // Names are escaped
+        assert!(xml.contains("name=\"Tools &amp; &lt;Resources&gt;\""));
+        assert!(xml.contains("name=\"search &quot;a&lt;b&quot;\""));
+        assert!(xml.contains("disabled=\"1\"") && xml.contains("failures=\"1\""));
+    }
+}
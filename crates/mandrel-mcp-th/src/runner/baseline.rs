@@ -0,0 +1,235 @@
+//! Benchmark baselines for detecting latency regressions between runs
+//!
+//! A baseline records the median and p95 latency of every test in a suite.
+//! Saving one from a known-good run and comparing later runs against it flags
+//! tests whose p95 latency grew beyond a configurable threshold.
+
+use crate::error::{Error, Result};
+use crate::runner::result::TestSuiteResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Default allowed p95 increase before a test counts as regressed
+pub const DEFAULT_REGRESSION_THRESHOLD_PERCENT: f64 = 20.0;
+
+/// Latency statistics for a single test
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestLatency {
+    pub median_ms: f64,
+    pub p95_ms: f64,
+    pub samples: usize,
+}
+
+impl TestLatency {
+    /// Compute statistics from raw latency samples in milliseconds
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        Some(Self {
+            median_ms: percentile(&sorted, 50.0),
+            p95_ms: percentile(&sorted, 95.0),
+            samples: sorted.len(),
+        })
+    }
+}
+
+/// Per-test latency baseline for a test suite
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkBaseline {
+    pub suite_name: String,
+    pub tests: BTreeMap<String, TestLatency>,
+}
+
+impl BenchmarkBaseline {
+    /// Build a baseline from one or more runs of the same suite
+    ///
+    /// Only successful test executions contribute samples, so a failing test
+    /// cannot drag the baseline down with an early exit.
+    pub fn from_results(results: &[TestSuiteResult]) -> Self {
+        let mut samples: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+        for suite in results {
+            for test in suite.test_results.iter().filter(|t| t.success) {
+                samples
+                    .entry(test.test_name.clone())
+                    .or_default()
+                    .push(test.duration.as_secs_f64() * 1000.0);
+            }
+        }
+
+        Self {
+            suite_name: results
+                .first()
+                .map(|suite| suite.suite_name.clone())
+                .unwrap_or_default(),
+            tests: samples
+                .into_iter()
+                .filter_map(|(name, samples)| {
+                    TestLatency::from_samples(&samples).map(|latency| (name, latency))
+                })
+                .collect(),
+        }
+    }
+
+    /// Load a baseline from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            Error::config(format!("Failed to read baseline {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| Error::config(format!("Invalid baseline {}: {}", path.display(), e)))
+    }
+
+    /// Write the baseline as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Compare `current` against this baseline
+    ///
+    /// Tests missing from either side are listed but never count as regressions.
+    pub fn compare(&self, current: &BenchmarkBaseline, threshold_percent: f64) -> RegressionReport {
+        let mut comparisons = Vec::new();
+        let mut missing_from_baseline = Vec::new();
+
+        for (name, latency) in &current.tests {
+            let Some(baseline) = self.tests.get(name) else {
+                missing_from_baseline.push(name.clone());
+                continue;
+            };
+            let change_percent = if baseline.p95_ms > 0.0 {
+                (latency.p95_ms - baseline.p95_ms) / baseline.p95_ms * 100.0
+            } else {
+                0.0
+            };
+            comparisons.push(LatencyComparison {
+                test_name: name.clone(),
+                baseline_p95_ms: baseline.p95_ms,
+                current_p95_ms: latency.p95_ms,
+                current_median_ms: latency.median_ms,
+                change_percent,
+                regressed: change_percent > threshold_percent,
+            });
+        }
+
+        RegressionReport {
+            threshold_percent,
+            comparisons,
+            missing_from_baseline,
+            missing_from_run: self
+                .tests
+                .keys()
+                .filter(|name| !current.tests.contains_key(*name))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// p95 comparison of one test against its baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyComparison {
+    pub test_name: String,
+    pub baseline_p95_ms: f64,
+    pub current_p95_ms: f64,
+    pub current_median_ms: f64,
+    pub change_percent: f64,
+    pub regressed: bool,
+}
+
+/// Result of comparing a run against a saved baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressionReport {
+    pub threshold_percent: f64,
+    pub comparisons: Vec<LatencyComparison>,
+    pub missing_from_baseline: Vec<String>,
+    pub missing_from_run: Vec<String>,
+}
+
+impl RegressionReport {
+    /// Tests whose p95 latency exceeded the threshold
+    pub fn regressions(&self) -> impl Iterator<Item = &LatencyComparison> {
+        self.comparisons.iter().filter(|c| c.regressed)
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice
+fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::result::TestResult;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    fn suite(durations: &[(&str, u64)]) -> TestSuiteResult {
+        let test_results: Vec<TestResult> = durations
+            .iter()
+            .map(|(name, ms)| TestResult::success(name.to_string(), Duration::from_millis(*ms)))
+            .collect();
+        TestSuiteResult {
+            suite_name: "bench".to_string(),
+            specification_file: PathBuf::from("bench.yaml"),
+            execution_start: SystemTime::now(),
+            execution_end: SystemTime::now(),
+            total_duration: Duration::from_secs(1),
+            total_tests: test_results.len(),
+            passed: test_results.len(),
+            failed: 0,
+            skipped: 0,
+            error_rate: 0.0,
+            test_results,
+            suite_metrics: Default::default(),
+            execution_mode: Default::default(),
+            dependency_resolution: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let samples: Vec<f64> = (1..=20).map(f64::from).collect();
+        let latency = TestLatency::from_samples(&samples).unwrap();
+        assert_eq!(latency.median_ms, 10.0);
+        assert_eq!(latency.p95_ms, 19.0);
+        assert_eq!(latency.samples, 20);
+        assert!(TestLatency::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_compare_flags_p95_regressions_over_threshold() {
+        let baseline = BenchmarkBaseline::from_results(&[
+            suite(&[("list_tools", 100), ("search", 50), ("removed", 10)]),
+            suite(&[("list_tools", 100), ("search", 50), ("removed", 10)]),
+        ]);
+        let current = BenchmarkBaseline::from_results(&[suite(&[
+            ("list_tools", 115),
+            ("search", 80),
+            ("added", 5),
+        ])]);
+
+        let report = baseline.compare(&current, DEFAULT_REGRESSION_THRESHOLD_PERCENT);
+        let regressed: Vec<_> = report.regressions().map(|c| c.test_name.as_str()).collect();
+        assert_eq!(regressed, vec!["search"]);
+        assert_eq!(report.missing_from_baseline, vec!["added"]);
+        assert_eq!(report.missing_from_run, vec!["removed"]);
+
+        let json = serde_json::to_string(&baseline).unwrap();
+        let restored: BenchmarkBaseline = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, baseline);
+    }
+}
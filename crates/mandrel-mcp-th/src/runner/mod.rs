@@ -5,6 +5,7 @@
 //! execution order, dependencies, parallel/sequential execution, and result
 //! aggregation.
 
+pub mod baseline;
 pub mod config;
 pub mod dependency;
 pub mod execution;
@@ -12,6 +13,7 @@ pub mod metrics;
 pub mod result;
 
 // Re-export main types
+pub use baseline::{BenchmarkBaseline, RegressionReport};
 pub use config::{ExecutionMode, RunnerConfig};
 pub use dependency::DependencyResolver;
 pub use execution::ExecutionStrategy;
@@ -39,6 +39,10 @@ pub struct TestSpecification {
     /// Server capabilities
     pub capabilities: ServerCapabilities,
     /// Server configuration
+    ///
+    /// `${VAR}` and `${VAR:-default}` in `args` and `env` values are expanded
+    /// from the host environment when the server is launched; see
+    /// [`expand_env_vars`].
     pub server: ServerConfig,
     /// Tool specifications
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -76,6 +80,10 @@ pub struct ServerCapabilities {
 }
 
 /// Server startup and connection configuration
+///
+/// `${VAR}` and `${VAR:-default}` in `args` and `env` values are expanded
+/// from the host environment when the server is launched; see
+/// [`expand_env_vars`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ServerConfig {
     pub command: String,
@@ -92,6 +100,60 @@ pub struct ServerConfig {
     pub shutdown_timeout_seconds: u32,
 }
 
+/// Expand environment variable references in a server `args` or `env` value
+///
+/// `${VAR}` is replaced by the value `lookup` returns for `VAR`, and
+/// `${VAR:-default}` falls back to `default` when the variable is unset or
+/// empty. `$$` is a literal `$`; any other `$` is kept as written. A
+/// variable that is unset and has no default is an error.
+pub fn expand_env_vars(
+    value: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> crate::error::Result<String> {
+    use crate::error::Error;
+
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        rest = &rest[dollar..];
+
+        if let Some(after) = rest.strip_prefix("$$") {
+            expanded.push('$');
+            rest = after;
+        } else if let Some(reference) = rest.strip_prefix("${") {
+            let end = reference.find('}').ok_or_else(|| {
+                Error::config(format!("Unterminated variable reference in '{value}'"))
+            })?;
+            let (name, default) = match reference[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&reference[..end], None),
+            };
+            if name.is_empty() {
+                return Err(Error::config(format!("Empty variable name in '{value}'")));
+            }
+
+            // Like the shell, an empty variable also falls back to the default
+            let resolved = lookup(name).filter(|v| !v.is_empty() || default.is_none());
+            match (resolved, default) {
+                (Some(resolved), _) => expanded.push_str(&resolved),
+                (None, Some(default)) => expanded.push_str(default),
+                (None, None) => {
+                    return Err(Error::config(format!(
+                        "Environment variable '{name}' is not set and has no default (use ${{{name}:-default}})"
+                    )))
+                }
+            }
+            rest = &reference[end + 1..];
+        } else {
+            expanded.push('$');
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
 /// Tool specification
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ToolSpec {
@@ -950,6 +1012,71 @@ server:
         assert!(result.is_err());
     }
 
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "API_KEY" => Some("secret".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_env_vars_with_set_variables() {
+        assert_eq!(
+            expand_env_vars("Bearer ${API_KEY}", lookup).unwrap(),
+            "Bearer secret"
+        );
+        assert_eq!(
+            expand_env_vars("${API_KEY:-fallback}", lookup).unwrap(),
+            "secret"
+        );
+        assert_eq!(expand_env_vars("${EMPTY}", lookup).unwrap(), "");
+        assert_eq!(
+            expand_env_vars("--port=8080", lookup).unwrap(),
+            "--port=8080"
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars_unset_with_default() {
+        assert_eq!(
+            expand_env_vars("${MISSING:-http://localhost:8080}", lookup).unwrap(),
+            "http://localhost:8080"
+        );
+        assert_eq!(expand_env_vars("${MISSING:-}", lookup).unwrap(), "");
+        // Empty variables fall back like unset ones
+        assert_eq!(
+            expand_env_vars("${EMPTY:-default}", lookup).unwrap(),
+            "default"
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars_unset_without_default_is_an_error() {
+        let error = expand_env_vars("key=${MISSING}", lookup).unwrap_err();
+        assert!(
+            error.to_string().contains("'MISSING' is not set"),
+            "{error}"
+        );
+
+        assert!(expand_env_vars("${API_KEY", lookup).is_err());
+        assert!(expand_env_vars("${}", lookup).is_err());
+    }
+
+    #[test]
+    fn test_expand_env_vars_escapes_dollar_signs() {
+        assert_eq!(
+            expand_env_vars("$${API_KEY}", lookup).unwrap(),
+            "${API_KEY}"
+        );
+        assert_eq!(expand_env_vars("cost: $$5", lookup).unwrap(), "cost: $5");
+        assert_eq!(
+            expand_env_vars("$HOME and $", lookup).unwrap(),
+            "$HOME and $"
+        );
+        assert_eq!(expand_env_vars("$$${API_KEY}", lookup).unwrap(), "$secret");
+    }
+
     // ========================================================================
     // PHASE 3: Validation Tests (Should FAIL until GREEN phase)
     // ========================================================================